@@ -182,6 +182,10 @@ pub enum ExprValue {
     Vector {
         elems: Vec<Expr>,
     },
+    WriteOk {
+        ptr: Expr,
+        size: Expr,
+    },
     Forall {
         variable: Expr, // symbol
         domain: Expr,   // where
@@ -776,6 +780,14 @@ pub fn read_ok(ptr: Expr, size: Expr) -> Self {
         expr!(ReadOk { ptr, size }, Type::bool())
     }
 
+    /// `write_ok(ptr, size)`
+    pub fn write_ok(ptr: Expr, size: Expr) -> Self {
+        assert_eq!(*ptr.typ(), Type::void_pointer());
+        assert_eq!(*size.typ(), Type::size_t());
+
+        expr!(WriteOk { ptr, size }, Type::bool())
+    }
+
     /// `e.g. NULL`
     pub fn pointer_constant(c: u64, typ: Type) -> Self {
         assert!(typ.is_pointer());
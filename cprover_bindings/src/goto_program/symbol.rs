@@ -94,16 +94,23 @@ pub fn as_contract_for(
 }
 
 /// The CBMC representation of a function contract. Represents
-/// https://diffblue.github.io/cbmc/contracts-user.html but currently only assigns clauses are
-/// supported.
+/// https://diffblue.github.io/cbmc/contracts-user.html with `requires`, `ensures` and `assigns`
+/// clauses.
 #[derive(Clone, Debug)]
 pub struct FunctionContract {
+    pub(crate) requires: Vec<Lambda>,
+    pub(crate) ensures: Vec<Lambda>,
     pub(crate) assigns: Vec<Lambda>,
 }
 
 impl FunctionContract {
     pub fn new(assigns: Vec<Lambda>) -> Self {
-        Self { assigns }
+        Self { requires: vec![], ensures: vec![], assigns }
+    }
+
+    /// A contract with only pre- and postconditions. Each lambda must have a boolean body.
+    pub fn with_conditions(requires: Vec<Lambda>, ensures: Vec<Lambda>) -> Self {
+        Self { requires, ensures, assigns: vec![] }
     }
 }
 
@@ -182,6 +189,8 @@ pub fn attach_contract(&mut self, contract: FunctionContract) {
         assert!(self.typ.is_code());
         match self.contract {
             Some(ref mut prior) => {
+                prior.requires.extend(contract.requires);
+                prior.ensures.extend(contract.ensures);
                 prior.assigns.extend(contract.assigns);
             }
             None => self.contract = Some(Box::new(contract)),
@@ -124,6 +124,18 @@ pub fn iter_mut(&mut self) -> std::collections::btree_map::IterMut<'_, InternedS
         self.symbol_table.iter_mut()
     }
 
+    /// Return the symbols ordered by name.
+    pub fn symbols(&self) -> std::collections::btree_map::Values<'_, InternedString, Symbol> {
+        self.symbol_table.values()
+    }
+
+    /// Consume the symbol table, and return its machine model and its symbols ordered by name.
+    pub fn into_symbols(
+        self,
+    ) -> (MachineModel, std::collections::btree_map::IntoValues<InternedString, Symbol>) {
+        (self.machine_model, self.symbol_table.into_values())
+    }
+
     pub fn lookup<T: Into<InternedString>>(&self, name: T) -> Option<&Symbol> {
         let name = name.into();
         self.symbol_table.get(&name)
@@ -4,7 +4,7 @@
 //!
 //! # Design overview
 //!
-//! When saving a [SymbolTable] to a binary file, the [Irep] describing each
+//! When saving a [goto_program::SymbolTable] to a binary file, the [Irep] describing each
 //! symbol's type, value and source location are structurally hashed and
 //! uniquely numbered so that structurally identical [Irep] only get written
 //! in full to the file the first time they are encountered and that ulterior
@@ -20,10 +20,10 @@
 //!   characters of their string representation, in the same number space
 //!   as [InternedString].
 //! - [Irep] objects get mapped to [NumberedIrep] based on:
-//!     + the unique numbers assigned to their [Irep::id] attribute,
-//!     + the unique numbers of [Irep] found in their [Irep::sub] attribute,
+//!     + the unique numbers assigned to their [`id`](crate::irep::IrepNode::id) attribute,
+//!     + the unique numbers of [Irep] found in their [`sub`](crate::irep::IrepNode::sub) attribute,
 //!     + the pairs of unique numbers assigned to the ([IrepId],[Irep]) pairs
-//!       found in their [Ipre::named_sub] attribute.
+//!       found in their [`named_sub`](crate::irep::IrepNode::named_sub) attribute.
 //!
 //! In order to assign the same number to structurally identical [Irep] objects,
 //! [IrepNumbering] essentially implements a cache where each [NumberedIrep] is
@@ -78,7 +78,8 @@
 //! Dually to the serializer, it will only attempt to decode the contents of an
 //! object from the byte stream on the first occurrence.
 
-use crate::irep::{Irep, IrepId, Symbol, SymbolTable};
+use crate::goto_program;
+use crate::irep::{Irep, IrepId, Symbol};
 use crate::{InternString, InternedString};
 #[cfg(not(test))]
 use fxhash::FxHashMap;
@@ -97,12 +98,16 @@
 /// - src/util/irep_serialization.h
 /// - src/util/irep_hash_container.h
 /// - src/util/irep_hash.h
-pub fn write_goto_binary_file(filename: &Path, source: &crate::goto_program::SymbolTable) {
+///
+/// The symbol table is consumed as it is written: each symbol is converted to ireps right before
+/// they are written, and the symbol is dropped once it is converted. The subtrees of the ireps are
+/// kept in the table of shared ireps until the whole file is written, so later symbols can share
+/// them.
+pub fn write_goto_binary_file(filename: &Path, source: goto_program::SymbolTable) {
     let out_file = File::create(filename).unwrap();
     let mut writer = BufWriter::new(out_file);
     let mut serializer = GotoBinarySerializer::new(&mut writer);
-    let irep_symbol_table = &source.to_irep();
-    serializer.write_file(irep_symbol_table);
+    serializer.write_file(source);
 }
 
 /// Reads a symbol table from a file expected to be in goto binary format in version 6.
@@ -553,13 +558,17 @@ fn write_symbol(&mut self, symbol: &Symbol) {
     }
 
     /// Writes a symbol table to the byte stream.
-    fn write_symbol_table(&mut self, symbol_table: &SymbolTable) {
+    fn write_symbol_table(&mut self, symbol_table: goto_program::SymbolTable) {
+        let symbols = symbol_table.into_ireps();
+
         // Write symbol table size
-        self.write_usize_varenc(symbol_table.symbol_table.len());
+        self.write_usize_varenc(symbols.len());
 
-        // Write symbols
-        for symbol in symbol_table.symbol_table.values() {
-            self.write_symbol(symbol);
+        // Write symbols. We convert each symbol to ireps right before writing it, so the goto
+        // program and its ireps are never both in memory. Only the distinct subtrees of the ireps
+        // accumulate, since they are shared until the iterator is dropped.
+        for symbol in symbols {
+            self.write_symbol(&symbol);
         }
     }
 
@@ -581,7 +590,7 @@ fn write_header(&mut self) {
     }
 
     /// Writes the symbol table using the GOTO binary file format to the byte stream.
-    fn write_file(&mut self, symbol_table: &SymbolTable) {
+    fn write_file(&mut self, symbol_table: goto_program::SymbolTable) {
         self.write_header();
         self.write_symbol_table(symbol_table);
         self.write_function_map();
@@ -1216,27 +1225,27 @@ mod tests {
     use super::IrepNumbering;
     use crate::InternedString;
     use crate::cbmc_string::InternString;
+    use crate::goto_program::SymbolTable;
     use crate::irep::Irep;
     use crate::irep::IrepId;
     use crate::irep::goto_binary_serde::GotoBinaryDeserializer;
     use crate::linear_map;
+    use crate::machine_model::test_util::machine_model_test_stub;
     use linear_map::LinearMap;
     use std::io::BufWriter;
     /// Utility function : creates a Irep representing a single symbol.
     fn make_symbol_expr(identifier: &str) -> Irep {
-        Irep {
-            id: IrepId::Symbol,
-            sub: vec![],
-            named_sub: linear_map![(IrepId::Identifier, Irep::just_string_id(identifier),)],
-        }
+        Irep::new(
+            IrepId::Symbol,
+            vec![],
+            linear_map![(IrepId::Identifier, Irep::just_string_id(identifier),)],
+        )
     }
 
     /// Utility function: creates an expression by folding the symbol expressions with the given operator.
     fn fold_with_op(identifiers: &Vec<&str>, id: IrepId) -> Irep {
-        identifiers.iter().fold(make_symbol_expr("dummy"), |acc, identifier| Irep {
-            id: id.clone(),
-            sub: vec![acc, make_symbol_expr(identifier)],
-            named_sub: LinearMap::new(),
+        identifiers.iter().fold(make_symbol_expr("dummy"), |acc, identifier| {
+            Irep::new(id.clone(), vec![acc, make_symbol_expr(identifier)], LinearMap::new())
         })
     }
 
@@ -1252,6 +1261,36 @@ fn test_irep_numbering_eq() {
         assert_eq!(num1, num2);
     }
 
+    #[test]
+    /// Create two structurally identical ireps and check that their subtrees are shared.
+    fn test_irep_sharing() {
+        let identifiers = vec!["foo", "bar", "baz"];
+        let lhs = Irep::just_sub(vec![fold_with_op(&identifiers, IrepId::And)]);
+        let rhs = Irep::just_sub(vec![fold_with_op(&identifiers, IrepId::And)]);
+        assert!(!lhs.ptr_eq(&rhs));
+        assert!(lhs.sub[0].ptr_eq(&rhs.sub[0]));
+        let other = Irep::just_sub(vec![fold_with_op(&identifiers, IrepId::Or)]);
+        assert!(!lhs.sub[0].ptr_eq(&other.sub[0]));
+        assert!(lhs.sub[0].sub[1].ptr_eq(&other.sub[0].sub[1]));
+    }
+
+    #[test]
+    /// Check that the ireps built after a symbol table is serialized aren't shared with the ones
+    /// built before, whether it is written as JSON or as a goto binary.
+    fn test_irep_sharing_cleared_after_symbol_table() {
+        let identifiers = vec!["foo", "bar", "baz"];
+        let symbol_table = SymbolTable::new(machine_model_test_stub());
+        let before = Irep::just_sub(vec![fold_with_op(&identifiers, IrepId::And)]);
+        serde_json::to_string(&symbol_table).unwrap();
+        let after_json = Irep::just_sub(vec![fold_with_op(&identifiers, IrepId::And)]);
+        assert!(!before.sub[0].ptr_eq(&after_json.sub[0]));
+
+        let mut writer = BufWriter::new(Vec::new());
+        GotoBinarySerializer::new(&mut writer).write_file(symbol_table);
+        let after_binary = Irep::just_sub(vec![fold_with_op(&identifiers, IrepId::And)]);
+        assert!(!after_json.sub[0].ptr_eq(&after_binary.sub[0]));
+    }
+
     #[test]
     /// Create two ireps with different named subs and check that they get different numbers.
     fn test_irep_numbering_ne_named_sub() {
@@ -9,18 +9,130 @@
 use crate::linear_map;
 use linear_map::LinearMap;
 use num::BigInt;
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::rc::Rc;
 
 /// The CBMC serialization format for goto-programs.
 /// CBMC implementation code is at:
 /// <https://github.com/diffblue/cbmc/blob/develop/src/util/irep.h>
+///
+/// Like in CBMC, an `Irep` is a reference counted pointer to its [IrepNode], so cloning it is
+/// cheap. Structurally equal subtrees are shared by the ireps built with [Irep::new], since the
+/// same types and expressions occur many times in a symbol table.
+#[derive(Clone)]
+pub struct Irep(Rc<IrepNode>);
+
 #[derive(Clone, Debug, PartialEq)]
-pub struct Irep {
+pub struct IrepNode {
     pub id: IrepId,
     pub sub: Vec<Irep>,
     pub named_sub: LinearMap<IrepId, Irep>,
 }
 
+impl Deref for Irep {
+    type Target = IrepNode;
+
+    fn deref(&self) -> &IrepNode {
+        &self.0
+    }
+}
+
+impl Debug for Irep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Irep")
+            .field("id", &self.id)
+            .field("sub", &self.sub)
+            .field("named_sub", &self.named_sub)
+            .finish()
+    }
+}
+
+impl PartialEq for Irep {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
+}
+
+/// A key of the table of shared ireps. The subtrees of a shared irep are shared themselves, so
+/// comparing and hashing them by address is enough to find structurally equal ireps.
+struct SharedIrep(Irep);
+
+impl PartialEq for SharedIrep {
+    fn eq(&self, other: &Self) -> bool {
+        let (lhs, rhs) = (&self.0, &other.0);
+        lhs.id == rhs.id
+            && lhs.sub.len() == rhs.sub.len()
+            && lhs.sub.iter().zip(&rhs.sub).all(|(l, r)| Rc::ptr_eq(&l.0, &r.0))
+            && lhs.named_sub.len() == rhs.named_sub.len()
+            && lhs
+                .named_sub
+                .iter()
+                .zip(&rhs.named_sub)
+                .all(|((lk, lv), (rk, rv))| lk == rk && Rc::ptr_eq(&lv.0, &rv.0))
+    }
+}
+
+impl Eq for SharedIrep {}
+
+impl Hash for SharedIrep {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.id.hash(state);
+        for sub in &self.0.sub {
+            Rc::as_ptr(&sub.0).hash(state);
+        }
+        for (key, value) in self.0.named_sub.iter() {
+            key.hash(state);
+            Rc::as_ptr(&value.0).hash(state);
+        }
+    }
+}
+
+thread_local! {
+    /// The ireps that are shared by the ireps built on this thread.
+    static SHARED_IREPS: RefCell<HashSet<SharedIrep>> = RefCell::new(HashSet::new());
+}
+
+/// Sharing
+impl Irep {
+    /// Build an irep whose subtrees are shared with the structurally equal subtrees of the ireps
+    /// built before on the same thread.
+    pub fn new(id: IrepId, sub: Vec<Irep>, named_sub: LinearMap<IrepId, Irep>) -> Irep {
+        let sub = sub.into_iter().map(Irep::share).collect();
+        let named_sub = named_sub.into_iter().map(|(key, value)| (key, value.share())).collect();
+        Irep(Rc::new(IrepNode { id, sub, named_sub }))
+    }
+
+    /// Return the shared irep that is structurally equal to `self`, which becomes the shared one if
+    /// there was none.
+    fn share(self) -> Irep {
+        SHARED_IREPS.with_borrow_mut(|shared| {
+            if let Some(existing) = shared.get(&SharedIrep(self.clone())) {
+                existing.0.clone()
+            } else {
+                shared.insert(SharedIrep(self.clone()));
+                self
+            }
+        })
+    }
+
+    /// Whether `self` and `other` are the same node, which is the case for shared ireps that are
+    /// structurally equal.
+    pub fn ptr_eq(&self, other: &Irep) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Drop the table of shared ireps of this thread. Ireps that are built afterwards aren't shared
+    /// with the existing ones, so this is only called once the ireps of a symbol table are built,
+    /// i.e., when [super::SymbolIreps] is dropped.
+    pub(super) fn clear_shared() {
+        SHARED_IREPS.with_borrow_mut(|shared| *shared = HashSet::new());
+    }
+}
+
 /// Getters
 impl Irep {
     pub fn lookup(&self, key: IrepId) -> Option<&Irep> {
@@ -54,7 +166,7 @@ pub fn with_comment<T: Into<InternedString>>(self, c: T) -> Self {
 
     pub fn with_named_sub(mut self, key: IrepId, value: Irep) -> Self {
         if !value.is_nil() {
-            self.named_sub.insert(key, value);
+            Rc::make_mut(&mut self.0).named_sub.insert(key, value.share());
         }
         self
     }
@@ -110,7 +222,7 @@ pub fn just_bitpattern_id<T>(i: T, width: u64, signed: bool) -> Irep
     }
 
     pub fn just_id(id: IrepId) -> Irep {
-        Irep { id, sub: Vec::new(), named_sub: LinearMap::new() }
+        Irep::new(id, Vec::new(), LinearMap::new())
     }
 
     pub fn just_int_id<T>(i: T) -> Irep
@@ -120,7 +232,7 @@ pub fn just_int_id<T>(i: T) -> Irep
         Irep::just_id(IrepId::from_int(i))
     }
     pub fn just_named_sub(named_sub: LinearMap<IrepId, Irep>) -> Irep {
-        Irep { id: IrepId::EmptyString, sub: vec![], named_sub }
+        Irep::new(IrepId::EmptyString, vec![], named_sub)
     }
 
     pub fn just_string_id<T: Into<InternedString>>(s: T) -> Irep {
@@ -128,7 +240,7 @@ pub fn just_string_id<T: Into<InternedString>>(s: T) -> Irep {
     }
 
     pub fn just_sub(sub: Vec<Irep>) -> Irep {
-        Irep { id: IrepId::EmptyString, sub, named_sub: LinearMap::new() }
+        Irep::new(IrepId::EmptyString, sub, LinearMap::new())
     }
 
     pub fn nil() -> Irep {
@@ -144,10 +256,6 @@ pub fn zero() -> Irep {
     }
 
     pub fn tuple(sub: Vec<Irep>) -> Self {
-        Irep {
-            id: IrepId::Tuple,
-            sub,
-            named_sub: linear_map![(IrepId::Type, Irep::just_id(IrepId::Tuple))],
-        }
+        Irep::new(IrepId::Tuple, sub, linear_map![(IrepId::Type, Irep::just_id(IrepId::Tuple))])
     }
 }
@@ -7,12 +7,11 @@
 //! You almost certainly want to create typesafe `goto_program` structures, and use the `to_irep` trait from this module to create canonical ireps from them.
 //! This module also supports getting typesafe `goto_program` structures from an irep, and hence can serve as the intermediate phase in a `goto` to `goto` translator.
 //!
-//! Internally, this module uses the naïve representation of an irep as a node with concrete named and unnamed subtrees.
-//! This representation does not take advantage of the sharing features available for ireps in CBMC to reduce memory usage.
+//! Internally, an irep is a reference counted node with named and unnamed subtrees.
+//! Like in CBMC, structurally equal subtrees are shared to reduce memory usage, and the goto binary writer converts one symbol to ireps at a time.
 //!
 //! TODO: Complete the from-irep trait for remaining data types
 //! TODO: Parser for json symbol tables into the internal irep format
-//! TODO: Investigate memory usage, and consider using sharing to reduce memory usage
 
 pub mod goto_binary_serde;
 #[allow(clippy::module_inception)]
@@ -23,8 +22,8 @@
 mod symbol_table;
 mod to_irep;
 
-pub use irep::Irep;
+pub use irep::{Irep, IrepNode};
 pub use irep_id::IrepId;
 pub use symbol::Symbol;
 pub use symbol_table::SymbolTable;
-pub use to_irep::ToIrep;
+pub use to_irep::{SymbolIreps, ToIrep};
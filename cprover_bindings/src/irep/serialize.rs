@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! This crate implements irep serialization using serde Serializer.
 use crate::InternedString;
-use crate::irep::{Irep, IrepId, Symbol, SymbolTable};
+use crate::goto_program;
+use crate::irep::{Irep, IrepId, Symbol, SymbolIreps, SymbolTable};
 use serde::Serialize;
-use serde::ser::{SerializeMap, Serializer};
+use serde::ser::{Error, SerializeMap, Serializer};
+use std::cell::RefCell;
+use std::collections::btree_map;
 
 impl Serialize for Irep {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -45,7 +48,9 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 
 // A direct serialization for the goto SymbolTable (contrasting to the irep SymbolTable just above).
 // This permits a "streaming optimization" where we reduce memory usage considerably by
-// only holding the irep conversion of one symbol in memory at a time.
+// converting each symbol to ireps right before it is written, instead of the whole table upfront.
+// Only the distinct subtrees of the ireps stay in memory until the table is written, since they
+// are shared between symbols.
 impl Serialize for crate::goto_program::SymbolTable {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -62,16 +67,62 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mm = self.0.machine_model();
+        serialize_symbols(self.0.ireps(), serializer)
+    }
+}
+
+impl crate::goto_program::SymbolTable {
+    /// Consume the symbol table into a value that serializes like it, but drops each symbol once
+    /// it is converted to ireps, like the goto binary writer does. The value can only be
+    /// serialized once.
+    pub fn into_streaming(self) -> impl Serialize {
+        ConsumedSymbolTable(RefCell::new(Some(self.into_ireps())))
+    }
+}
+
+type ConsumedSymbols = SymbolIreps<btree_map::IntoValues<InternedString, goto_program::Symbol>>;
+
+struct ConsumedSymbolTable(RefCell<Option<ConsumedSymbols>>);
+
+impl Serialize for ConsumedSymbolTable {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
         let mut obj = serializer.serialize_map(None)?;
-        for (k, v) in self.0.iter() {
-            // We're only storing the to_irep in RAM for one symbol at a time
-            obj.serialize_entry(k, &v.to_irep(mm))?;
-        }
+        obj.serialize_entry("symbolTable", &ConsumedSymbolsEntry(&self.0))?;
         obj.end()
     }
 }
 
+struct ConsumedSymbolsEntry<'a>(&'a RefCell<Option<ConsumedSymbols>>);
+
+impl Serialize for ConsumedSymbolsEntry<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Some(symbols) = self.0.borrow_mut().take() else {
+            return Err(S::Error::custom("the symbol table was already serialized"));
+        };
+        serialize_symbols(symbols, serializer)
+    }
+}
+
+fn serialize_symbols<S>(
+    symbols: impl Iterator<Item = Symbol>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut obj = serializer.serialize_map(None)?;
+    for symbol in symbols {
+        obj.serialize_entry(&symbol.name, &symbol)?;
+    }
+    obj.end()
+}
+
 impl Serialize for InternedString {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -145,6 +196,7 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::machine_model::test_util::machine_model_test_stub;
     use serde_test::{Token, assert_ser_tokens};
     #[test]
     fn serialize_irep() {
@@ -306,4 +358,13 @@ fn serialize_irep_sub() {
             ],
         );
     }
+
+    #[test]
+    fn serialize_consumed_goto_sym_table() {
+        let symbol_table = goto_program::SymbolTable::new(machine_model_test_stub());
+        let expected = serde_json::to_string(&symbol_table).unwrap();
+        let streaming = symbol_table.into_streaming();
+        assert_eq!(serde_json::to_string(&streaming).unwrap(), expected);
+        assert!(serde_json::to_string(&streaming).is_err());
+    }
 }
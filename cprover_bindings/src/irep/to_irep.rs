@@ -11,6 +11,8 @@
     BinaryOperator, CIntType, DatatypeComponent, Expr, ExprValue, Lambda, Location, Parameter,
     SelfOperator, Stmt, StmtBody, SwitchCase, SymbolValues, Type, UnaryOperator,
 };
+use std::borrow::Borrow;
+use std::collections::btree_map;
 
 pub trait ToIrep {
     fn to_irep(&self, mm: &MachineModel) -> Irep;
@@ -18,25 +20,13 @@ pub trait ToIrep {
 
 /// Utility functions
 fn arguments_irep<'a>(arguments: impl Iterator<Item = &'a Expr>, mm: &MachineModel) -> Irep {
-    Irep {
-        id: IrepId::Arguments,
-        sub: arguments.map(|x| x.to_irep(mm)).collect(),
-        named_sub: linear_map![],
-    }
+    Irep::new(IrepId::Arguments, arguments.map(|x| x.to_irep(mm)).collect(), linear_map![])
 }
 fn code_irep(kind: IrepId, ops: Vec<Irep>) -> Irep {
-    Irep {
-        id: IrepId::Code,
-        sub: ops,
-        named_sub: linear_map![(IrepId::Statement, Irep::just_id(kind))],
-    }
+    Irep::new(IrepId::Code, ops, linear_map![(IrepId::Statement, Irep::just_id(kind))])
 }
 fn side_effect_irep(kind: IrepId, ops: Vec<Irep>) -> Irep {
-    Irep {
-        id: IrepId::SideEffect,
-        sub: ops,
-        named_sub: linear_map![(IrepId::Statement, Irep::just_id(kind))],
-    }
+    Irep::new(IrepId::SideEffect, ops, linear_map![(IrepId::Statement, Irep::just_id(kind))])
 }
 fn switch_default_irep(body: &Stmt, mm: &MachineModel) -> Irep {
     code_irep(IrepId::SwitchCase, vec![Irep::nil(), body.to_irep(mm)])
@@ -161,13 +151,9 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
             } else {
                 Irep::just_int_id(i.clone())
             };
-            Irep {
-                id: IrepId::Constant,
-                sub: vec![],
-                named_sub: linear_map![(IrepId::Value, irep_value,)],
-            }
-            .with_location(self.location(), mm)
-            .with_type(self.typ(), mm)
+            Irep::new(IrepId::Constant, vec![], linear_map![(IrepId::Value, irep_value,)])
+                .with_location(self.location(), mm)
+                .with_type(self.typ(), mm)
         } else {
             self.value().to_irep(mm).with_location(self.location(), mm).with_type(self.typ(), mm)
         }
@@ -180,11 +166,11 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
 
 impl Irep {
     pub fn symbol(identifier: InternedString) -> Self {
-        Irep {
-            id: IrepId::Symbol,
-            sub: vec![],
-            named_sub: linear_map![(IrepId::Identifier, Irep::just_string_id(identifier))],
-        }
+        Irep::new(
+            IrepId::Symbol,
+            vec![],
+            linear_map![(IrepId::Identifier, Irep::just_string_id(identifier))],
+        )
     }
 }
 
@@ -192,235 +178,219 @@ impl ToIrep for ExprValue {
     fn to_irep(&self, mm: &MachineModel) -> Irep {
         match self {
             ExprValue::AddressOf(e) => {
-                Irep { id: IrepId::AddressOf, sub: vec![e.to_irep(mm)], named_sub: linear_map![] }
+                Irep::new(IrepId::AddressOf, vec![e.to_irep(mm)], linear_map![])
             }
-            ExprValue::Array { elems } => Irep {
-                id: IrepId::Array,
-                sub: elems.iter().map(|x| x.to_irep(mm)).collect(),
-                named_sub: linear_map![],
-            },
+            ExprValue::Array { elems } => Irep::new(
+                IrepId::Array,
+                elems.iter().map(|x| x.to_irep(mm)).collect(),
+                linear_map![],
+            ),
             ExprValue::ArrayOf { elem } => {
-                Irep { id: IrepId::ArrayOf, sub: vec![elem.to_irep(mm)], named_sub: linear_map![] }
+                Irep::new(IrepId::ArrayOf, vec![elem.to_irep(mm)], linear_map![])
             }
             ExprValue::Assign { left, right } => {
                 side_effect_irep(IrepId::Assign, vec![left.to_irep(mm), right.to_irep(mm)])
             }
-            ExprValue::BinOp { op, lhs, rhs } => Irep {
-                id: op.to_irep_id(),
-                sub: vec![lhs.to_irep(mm), rhs.to_irep(mm)],
-                named_sub: linear_map![],
-            },
-            ExprValue::BoolConstant(c) => Irep {
-                id: IrepId::Constant,
-                sub: vec![],
-                named_sub: linear_map![(
+            ExprValue::BinOp { op, lhs, rhs } => {
+                Irep::new(op.to_irep_id(), vec![lhs.to_irep(mm), rhs.to_irep(mm)], linear_map![])
+            }
+            ExprValue::BoolConstant(c) => Irep::new(
+                IrepId::Constant,
+                vec![],
+                linear_map![(
                     IrepId::Value,
                     if *c { Irep::just_id(IrepId::True) } else { Irep::just_id(IrepId::False) },
                 )],
-            },
-            ExprValue::ByteExtract { e, offset } => Irep {
-                id: if mm.is_big_endian {
+            ),
+            ExprValue::ByteExtract { e, offset } => Irep::new(
+                if mm.is_big_endian {
                     IrepId::ByteExtractBigEndian
                 } else {
                     IrepId::ByteExtractLittleEndian
                 },
-                sub: vec![e.to_irep(mm), Expr::int_constant(*offset, Type::ssize_t()).to_irep(mm)],
-                named_sub: linear_map![(IrepId::BitsPerByte, Irep::just_int_id(8u8))],
-            },
-            ExprValue::CBoolConstant(i) => Irep {
-                id: IrepId::Constant,
-                sub: vec![],
-                named_sub: linear_map![(
+                vec![e.to_irep(mm), Expr::int_constant(*offset, Type::ssize_t()).to_irep(mm)],
+                linear_map![(IrepId::BitsPerByte, Irep::just_int_id(8u8))],
+            ),
+            ExprValue::CBoolConstant(i) => Irep::new(
+                IrepId::Constant,
+                vec![],
+                linear_map![(
                     IrepId::Value,
                     Irep::just_bitpattern_id(if *i { 1u8 } else { 0 }, mm.bool_width, false)
                 )],
-            },
+            ),
             ExprValue::Dereference(e) => {
-                Irep { id: IrepId::Dereference, sub: vec![e.to_irep(mm)], named_sub: linear_map![] }
+                Irep::new(IrepId::Dereference, vec![e.to_irep(mm)], linear_map![])
             }
             //TODO, determine if there is an endineness problem here
             ExprValue::DoubleConstant(i) => {
                 let c: u64 = i.to_bits();
-                Irep {
-                    id: IrepId::Constant,
-                    sub: vec![],
-                    named_sub: linear_map![(
+                Irep::new(
+                    IrepId::Constant,
+                    vec![],
+                    linear_map![(
                         IrepId::Value,
                         Irep::just_bitpattern_id(c, mm.double_width, false)
                     )],
-                }
+                )
             }
             ExprValue::EmptyUnion => Irep::just_id(IrepId::EmptyUnion),
             ExprValue::FloatConstant(i) => {
                 let c: u32 = i.to_bits();
-                Irep {
-                    id: IrepId::Constant,
-                    sub: vec![],
-                    named_sub: linear_map![(
+                Irep::new(
+                    IrepId::Constant,
+                    vec![],
+                    linear_map![(
                         IrepId::Value,
                         Irep::just_bitpattern_id(c, mm.float_width, false)
                     )],
-                }
+                )
             }
             ExprValue::Float16Constant(i) => {
                 let c: u16 = i.to_bits();
-                Irep {
-                    id: IrepId::Constant,
-                    sub: vec![],
-                    named_sub: linear_map![(IrepId::Value, Irep::just_bitpattern_id(c, 16, false))],
-                }
+                Irep::new(
+                    IrepId::Constant,
+                    vec![],
+                    linear_map![(IrepId::Value, Irep::just_bitpattern_id(c, 16, false))],
+                )
             }
             ExprValue::Float128Constant(i) => {
                 let c: u128 = i.to_bits();
-                Irep {
-                    id: IrepId::Constant,
-                    sub: vec![],
-                    named_sub: linear_map![(
-                        IrepId::Value,
-                        Irep::just_bitpattern_id(c, 128, false)
-                    )],
-                }
+                Irep::new(
+                    IrepId::Constant,
+                    vec![],
+                    linear_map![(IrepId::Value, Irep::just_bitpattern_id(c, 128, false))],
+                )
             }
             ExprValue::FunctionCall { function, arguments } => side_effect_irep(
                 IrepId::FunctionCall,
                 vec![function.to_irep(mm), arguments_irep(arguments.iter(), mm)],
             ),
-            ExprValue::If { c, t, e } => Irep {
-                id: IrepId::If,
-                sub: vec![c.to_irep(mm), t.to_irep(mm), e.to_irep(mm)],
-                named_sub: linear_map![],
-            },
-            ExprValue::Index { array, index } => Irep {
-                id: IrepId::Index,
-                sub: vec![array.to_irep(mm), index.to_irep(mm)],
-                named_sub: linear_map![],
-            },
+            ExprValue::If { c, t, e } => Irep::new(
+                IrepId::If,
+                vec![c.to_irep(mm), t.to_irep(mm), e.to_irep(mm)],
+                linear_map![],
+            ),
+            ExprValue::Index { array, index } => {
+                Irep::new(IrepId::Index, vec![array.to_irep(mm), index.to_irep(mm)], linear_map![])
+            }
             ExprValue::IntConstant(_) => {
                 unreachable!("Should have been processed in previous step")
             }
-            ExprValue::Member { lhs, field } => Irep {
-                id: IrepId::Member,
-                sub: vec![lhs.to_irep(mm)],
-                named_sub: linear_map![
+            ExprValue::Member { lhs, field } => Irep::new(
+                IrepId::Member,
+                vec![lhs.to_irep(mm)],
+                linear_map![
                     (IrepId::CLvalue, Irep::one()),
                     (IrepId::ComponentName, Irep::just_string_id(field.to_string())),
                 ],
-            },
+            ),
             ExprValue::Nondet => side_effect_irep(IrepId::Nondet, vec![]),
-            ExprValue::PointerConstant(0) => Irep {
-                id: IrepId::Constant,
-                sub: vec![],
-                named_sub: linear_map![(IrepId::Value, Irep::just_id(IrepId::NULL))],
-            },
-            ExprValue::PointerConstant(i) => Irep {
-                id: IrepId::Constant,
-                sub: vec![],
-                named_sub: linear_map![(
-                    IrepId::Value,
-                    Irep::just_bitpattern_id(*i, mm.pointer_width, false)
-                )],
-            },
-            ExprValue::ReadOk { ptr, size } => Irep {
-                id: IrepId::ROk,
-                sub: vec![ptr.to_irep(mm), size.to_irep(mm)],
-                named_sub: linear_map![],
-            },
+            ExprValue::PointerConstant(0) => Irep::new(
+                IrepId::Constant,
+                vec![],
+                linear_map![(IrepId::Value, Irep::just_id(IrepId::NULL))],
+            ),
+            ExprValue::PointerConstant(i) => Irep::new(
+                IrepId::Constant,
+                vec![],
+                linear_map![(IrepId::Value, Irep::just_bitpattern_id(*i, mm.pointer_width, false))],
+            ),
+            ExprValue::ReadOk { ptr, size } => {
+                Irep::new(IrepId::ROk, vec![ptr.to_irep(mm), size.to_irep(mm)], linear_map![])
+            }
+            ExprValue::WriteOk { ptr, size } => {
+                Irep::new(IrepId::WOk, vec![ptr.to_irep(mm), size.to_irep(mm)], linear_map![])
+            }
             ExprValue::SelfOp { op, e } => side_effect_irep(op.to_irep_id(), vec![e.to_irep(mm)]),
             ExprValue::StatementExpression { statements: ops, location: loc } => side_effect_irep(
                 IrepId::StatementExpression,
                 vec![Stmt::block(ops.to_vec(), *loc).to_irep(mm)],
             ),
-            ExprValue::StringConstant { s } => Irep {
-                id: IrepId::StringConstant,
-                sub: vec![],
-                named_sub: linear_map![(IrepId::Value, Irep::just_string_id(s.to_string()),)],
-            },
-            ExprValue::Struct { values } => Irep {
-                id: IrepId::Struct,
-                sub: values.iter().map(|x| x.to_irep(mm)).collect(),
-                named_sub: linear_map![],
-            },
+            ExprValue::StringConstant { s } => Irep::new(
+                IrepId::StringConstant,
+                vec![],
+                linear_map![(IrepId::Value, Irep::just_string_id(s.to_string()),)],
+            ),
+            ExprValue::Struct { values } => Irep::new(
+                IrepId::Struct,
+                values.iter().map(|x| x.to_irep(mm)).collect(),
+                linear_map![],
+            ),
             ExprValue::Symbol { identifier } => Irep::symbol(*identifier),
             ExprValue::Typecast(e) => {
-                Irep { id: IrepId::Typecast, sub: vec![e.to_irep(mm)], named_sub: linear_map![] }
-            }
-            ExprValue::Union { value, field } => Irep {
-                id: IrepId::Union,
-                sub: vec![value.to_irep(mm)],
-                named_sub: linear_map![(
-                    IrepId::ComponentName,
-                    Irep::just_string_id(field.to_string()),
-                )],
-            },
-            ExprValue::UnOp { op: UnaryOperator::Bswap, e } => Irep {
-                id: IrepId::Bswap,
-                sub: vec![e.to_irep(mm)],
-                named_sub: linear_map![(IrepId::BitsPerByte, Irep::just_int_id(8u8))],
-            },
+                Irep::new(IrepId::Typecast, vec![e.to_irep(mm)], linear_map![])
+            }
+            ExprValue::Union { value, field } => Irep::new(
+                IrepId::Union,
+                vec![value.to_irep(mm)],
+                linear_map![(IrepId::ComponentName, Irep::just_string_id(field.to_string()),)],
+            ),
+            ExprValue::UnOp { op: UnaryOperator::Bswap, e } => Irep::new(
+                IrepId::Bswap,
+                vec![e.to_irep(mm)],
+                linear_map![(IrepId::BitsPerByte, Irep::just_int_id(8u8))],
+            ),
             ExprValue::UnOp { op: UnaryOperator::BitReverse, e } => {
-                Irep { id: IrepId::BitReverse, sub: vec![e.to_irep(mm)], named_sub: linear_map![] }
-            }
-            ExprValue::UnOp { op: UnaryOperator::CountLeadingZeros { allow_zero }, e } => Irep {
-                id: IrepId::CountLeadingZeros,
-                sub: vec![e.to_irep(mm)],
-                named_sub: linear_map![(
-                    IrepId::CBoundsCheck,
-                    if *allow_zero { Irep::zero() } else { Irep::one() }
-                )],
-            },
-            ExprValue::UnOp { op: UnaryOperator::CountTrailingZeros { allow_zero }, e } => Irep {
-                id: IrepId::CountTrailingZeros,
-                sub: vec![e.to_irep(mm)],
-                named_sub: linear_map![(
-                    IrepId::CBoundsCheck,
-                    if *allow_zero { Irep::zero() } else { Irep::one() }
-                )],
-            },
+                Irep::new(IrepId::BitReverse, vec![e.to_irep(mm)], linear_map![])
+            }
+            ExprValue::UnOp { op: UnaryOperator::CountLeadingZeros { allow_zero }, e } => {
+                Irep::new(
+                    IrepId::CountLeadingZeros,
+                    vec![e.to_irep(mm)],
+                    linear_map![(
+                        IrepId::CBoundsCheck,
+                        if *allow_zero { Irep::zero() } else { Irep::one() }
+                    )],
+                )
+            }
+            ExprValue::UnOp { op: UnaryOperator::CountTrailingZeros { allow_zero }, e } => {
+                Irep::new(
+                    IrepId::CountTrailingZeros,
+                    vec![e.to_irep(mm)],
+                    linear_map![(
+                        IrepId::CBoundsCheck,
+                        if *allow_zero { Irep::zero() } else { Irep::one() }
+                    )],
+                )
+            }
             ExprValue::UnOp { op, e } => {
-                Irep { id: op.to_irep_id(), sub: vec![e.to_irep(mm)], named_sub: linear_map![] }
+                Irep::new(op.to_irep_id(), vec![e.to_irep(mm)], linear_map![])
             }
-            ExprValue::Vector { elems } => Irep {
-                id: IrepId::Vector,
-                sub: elems.iter().map(|x| x.to_irep(mm)).collect(),
-                named_sub: linear_map![],
-            },
-            ExprValue::Forall { variable, domain } => Irep {
-                id: IrepId::Forall,
-                sub: vec![
-                    Irep {
-                        id: IrepId::Tuple,
-                        sub: vec![variable.to_irep(mm)],
-                        named_sub: linear_map![],
-                    },
+            ExprValue::Vector { elems } => Irep::new(
+                IrepId::Vector,
+                elems.iter().map(|x| x.to_irep(mm)).collect(),
+                linear_map![],
+            ),
+            ExprValue::Forall { variable, domain } => Irep::new(
+                IrepId::Forall,
+                vec![
+                    Irep::new(IrepId::Tuple, vec![variable.to_irep(mm)], linear_map![]),
                     domain.to_irep(mm),
                 ],
-                named_sub: linear_map![],
-            },
-            ExprValue::Exists { variable, domain } => Irep {
-                id: IrepId::Exists,
-                sub: vec![
-                    Irep {
-                        id: IrepId::Tuple,
-                        sub: vec![variable.to_irep(mm)],
-                        named_sub: linear_map![],
-                    },
+                linear_map![],
+            ),
+            ExprValue::Exists { variable, domain } => Irep::new(
+                IrepId::Exists,
+                vec![
+                    Irep::new(IrepId::Tuple, vec![variable.to_irep(mm)], linear_map![]),
                     domain.to_irep(mm),
                 ],
-                named_sub: linear_map![],
-            },
-            ExprValue::ShuffleVector { vector1, vector2, indexes } => Irep {
-                id: IrepId::ShuffleVector,
-                sub: vec![
+                linear_map![],
+            ),
+            ExprValue::ShuffleVector { vector1, vector2, indexes } => Irep::new(
+                IrepId::ShuffleVector,
+                vec![
                     vector1.to_irep(mm),
                     vector2.to_irep(mm),
-                    Irep {
-                        id: IrepId::EmptyString,
-                        sub: indexes.iter().map(|x| x.to_irep(mm)).collect(),
-                        named_sub: linear_map![],
-                    },
+                    Irep::new(
+                        IrepId::EmptyString,
+                        indexes.iter().map(|x| x.to_irep(mm)).collect(),
+                        linear_map![],
+                    ),
                 ],
-                named_sub: linear_map![],
-            },
+                linear_map![],
+            ),
         }
     }
 }
@@ -495,13 +465,9 @@ fn to_irep(&self, _mm: &MachineModel) -> Irep {
 
 impl ToIrep for Parameter {
     fn to_irep(&self, mm: &MachineModel) -> Irep {
-        Irep {
-            id: IrepId::Parameter,
-            sub: vec![],
-            named_sub: linear_map![(IrepId::Type, self.typ().to_irep(mm))],
-        }
-        .with_named_sub_option(IrepId::CIdentifier, self.identifier().map(Irep::just_string_id))
-        .with_named_sub_option(IrepId::CBaseName, self.base_name().map(Irep::just_string_id))
+        Irep::new(IrepId::Parameter, vec![], linear_map![(IrepId::Type, self.typ().to_irep(mm))])
+            .with_named_sub_option(IrepId::CIdentifier, self.identifier().map(Irep::just_string_id))
+            .with_named_sub_option(IrepId::CBaseName, self.base_name().map(Irep::just_string_id))
     }
 }
 
@@ -614,9 +580,8 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
 }
 
 impl ToIrep for Lambda {
-    /// At the moment this function assumes that this lambda is used for a
-    /// `modifies` contract. It should work for any other lambda body, but
-    /// the parameter names use "modifies" in their generated names.
+    /// Parameters without an identifier get a name derived from "modifies", since
+    /// those are only generated for `modifies` contracts.
     fn to_irep(&self, mm: &MachineModel) -> Irep {
         let (ops_ireps, types) = self
             .arguments
@@ -633,16 +598,16 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
                 )
             })
             .unzip();
-        let typ = Irep {
-            id: IrepId::MathematicalFunction,
-            sub: vec![Irep::just_sub(types), self.body.typ().to_irep(mm)],
-            named_sub: Default::default(),
-        };
-        Irep {
-            id: IrepId::Lambda,
-            sub: vec![Irep::tuple(ops_ireps), self.body.to_irep(mm)],
-            named_sub: linear_map!((IrepId::Type, typ)),
-        }
+        let typ = Irep::new(
+            IrepId::MathematicalFunction,
+            vec![Irep::just_sub(types), self.body.typ().to_irep(mm)],
+            Default::default(),
+        );
+        Irep::new(
+            IrepId::Lambda,
+            vec![Irep::tuple(ops_ireps), self.body.to_irep(mm)],
+            linear_map!((IrepId::Type, typ)),
+        )
     }
 }
 
@@ -650,10 +615,16 @@ impl goto_program::Symbol {
     pub fn to_irep(&self, mm: &MachineModel) -> super::Symbol {
         let mut typ = self.typ.to_irep(mm);
         if let Some(contract) = &self.contract {
-            typ = typ.with_named_sub(
-                IrepId::CSpecAssigns,
-                Irep::just_sub(contract.assigns.iter().map(|req| req.to_irep(mm)).collect()),
-            );
+            let clauses = |lambdas: &[Lambda]| {
+                Irep::just_sub(lambdas.iter().map(|lambda| lambda.to_irep(mm)).collect())
+            };
+            if !contract.requires.is_empty() {
+                typ = typ.with_named_sub(IrepId::CSpecRequires, clauses(&contract.requires));
+            }
+            if !contract.ensures.is_empty() {
+                typ = typ.with_named_sub(IrepId::CSpecEnsures, clauses(&contract.ensures));
+            }
+            typ = typ.with_named_sub(IrepId::CSpecAssigns, clauses(&contract.assigns));
         }
         if self.is_static_const {
             // Add a `const` to the type.
@@ -704,13 +675,67 @@ pub fn to_irep(&self, mm: &MachineModel) -> super::Symbol {
 
 impl goto_program::SymbolTable {
     pub fn to_irep(&self) -> super::SymbolTable {
-        let mm = self.machine_model();
         let mut st = super::SymbolTable::new();
-        for (_key, value) in self.iter() {
-            st.insert(value.to_irep(mm))
+        for symbol in self.ireps() {
+            st.insert(symbol)
         }
         st
     }
+
+    /// Return the ireps of the symbols ordered by name, which are built as they are iterated.
+    pub fn ireps(
+        &self,
+    ) -> SymbolIreps<btree_map::Values<'_, InternedString, goto_program::Symbol>> {
+        SymbolIreps { mm: self.machine_model().clone(), symbols: self.symbols() }
+    }
+
+    /// Consume the symbol table, and return the ireps of its symbols ordered by name. Each symbol
+    /// is dropped once its ireps are built.
+    pub fn into_ireps(
+        self,
+    ) -> SymbolIreps<btree_map::IntoValues<InternedString, goto_program::Symbol>> {
+        let (mm, symbols) = self.into_symbols();
+        SymbolIreps { mm, symbols }
+    }
+}
+
+/// The ireps of the symbols of a symbol table, which are built on demand.
+///
+/// The ireps of all the symbols share their structurally equal subtrees. Every conversion of a
+/// symbol table goes through this iterator, which drops the table of shared ireps of the thread
+/// when it is dropped, so the shared ireps don't outlive the conversion.
+pub struct SymbolIreps<I> {
+    mm: MachineModel,
+    symbols: I,
+}
+
+impl<I, S> Iterator for SymbolIreps<I>
+where
+    I: Iterator<Item = S>,
+    S: Borrow<goto_program::Symbol>,
+{
+    type Item = super::Symbol;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.symbols.next().map(|symbol| symbol.borrow().to_irep(&self.mm))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.symbols.size_hint()
+    }
+}
+
+impl<I, S> ExactSizeIterator for SymbolIreps<I>
+where
+    I: ExactSizeIterator<Item = S>,
+    S: Borrow<goto_program::Symbol>,
+{
+}
+
+impl<I> Drop for SymbolIreps<I> {
+    fn drop(&mut self) {
+        Irep::clear_shared();
+    }
 }
 
 impl ToIrep for Type {
@@ -719,200 +744,194 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
             Type::Array { typ, size } => {
                 //CBMC expects the size to be a signed int constant.
                 let size = Expr::int_constant(*size, Type::ssize_t());
-                Irep {
-                    id: IrepId::Array,
-                    sub: vec![typ.to_irep(mm)],
-                    named_sub: linear_map![(IrepId::Size, size.to_irep(mm))],
-                }
+                Irep::new(
+                    IrepId::Array,
+                    vec![typ.to_irep(mm)],
+                    linear_map![(IrepId::Size, size.to_irep(mm))],
+                )
             }
             //TODO make from_irep that matches this.
-            Type::CBitField { typ, width } => Irep {
-                id: IrepId::CBitField,
-                sub: vec![typ.to_irep(mm)],
-                named_sub: linear_map![(IrepId::Width, Irep::just_int_id(*width))],
-            },
+            Type::CBitField { typ, width } => Irep::new(
+                IrepId::CBitField,
+                vec![typ.to_irep(mm)],
+                linear_map![(IrepId::Width, Irep::just_int_id(*width))],
+            ),
             Type::Bool => Irep::just_id(IrepId::Bool),
-            Type::CInteger(CIntType::Bool) => Irep {
-                id: IrepId::CBool,
-                sub: vec![],
-                named_sub: linear_map![(IrepId::Width, Irep::just_int_id(mm.bool_width))],
-            },
-            Type::CInteger(CIntType::Char) => Irep {
-                id: if mm.char_is_unsigned { IrepId::Unsignedbv } else { IrepId::Signedbv },
-                sub: vec![],
-                named_sub: linear_map![(IrepId::Width, Irep::just_int_id(mm.char_width),)],
-            },
-            Type::CInteger(CIntType::Int) => Irep {
-                id: IrepId::Signedbv,
-                sub: vec![],
-                named_sub: linear_map![(IrepId::Width, Irep::just_int_id(mm.int_width),)],
-            },
-            Type::CInteger(CIntType::LongInt) => Irep {
-                id: IrepId::Signedbv,
-                sub: vec![],
-                named_sub: linear_map![(IrepId::Width, Irep::just_int_id(mm.long_int_width),)],
-            },
-            Type::CInteger(CIntType::SizeT) => Irep {
-                id: IrepId::Unsignedbv,
-                sub: vec![],
-                named_sub: linear_map![(IrepId::Width, Irep::just_int_id(mm.pointer_width),)],
-            },
-            Type::CInteger(CIntType::SSizeT) => Irep {
-                id: IrepId::Signedbv,
-                sub: vec![],
-                named_sub: linear_map![(IrepId::Width, Irep::just_int_id(mm.pointer_width),)],
-            },
-            Type::Code { parameters, return_type } => Irep {
-                id: IrepId::Code,
-                sub: vec![],
-                named_sub: linear_map![
+            Type::CInteger(CIntType::Bool) => Irep::new(
+                IrepId::CBool,
+                vec![],
+                linear_map![(IrepId::Width, Irep::just_int_id(mm.bool_width))],
+            ),
+            Type::CInteger(CIntType::Char) => Irep::new(
+                if mm.char_is_unsigned { IrepId::Unsignedbv } else { IrepId::Signedbv },
+                vec![],
+                linear_map![(IrepId::Width, Irep::just_int_id(mm.char_width),)],
+            ),
+            Type::CInteger(CIntType::Int) => Irep::new(
+                IrepId::Signedbv,
+                vec![],
+                linear_map![(IrepId::Width, Irep::just_int_id(mm.int_width),)],
+            ),
+            Type::CInteger(CIntType::LongInt) => Irep::new(
+                IrepId::Signedbv,
+                vec![],
+                linear_map![(IrepId::Width, Irep::just_int_id(mm.long_int_width),)],
+            ),
+            Type::CInteger(CIntType::SizeT) => Irep::new(
+                IrepId::Unsignedbv,
+                vec![],
+                linear_map![(IrepId::Width, Irep::just_int_id(mm.pointer_width),)],
+            ),
+            Type::CInteger(CIntType::SSizeT) => Irep::new(
+                IrepId::Signedbv,
+                vec![],
+                linear_map![(IrepId::Width, Irep::just_int_id(mm.pointer_width),)],
+            ),
+            Type::Code { parameters, return_type } => Irep::new(
+                IrepId::Code,
+                vec![],
+                linear_map![
                     (
                         IrepId::Parameters,
                         Irep::just_sub(parameters.iter().map(|x| x.to_irep(mm)).collect()),
                     ),
                     (IrepId::ReturnType, return_type.to_irep(mm)),
                 ],
-            },
+            ),
             Type::Constructor => Irep::just_id(IrepId::Constructor),
-            Type::Double => Irep {
-                id: IrepId::Floatbv,
-                sub: vec![],
-                named_sub: linear_map![
+            Type::Double => Irep::new(
+                IrepId::Floatbv,
+                vec![],
+                linear_map![
                     (IrepId::F, Irep::just_int_id(52)),
                     (IrepId::Width, Irep::just_int_id(64)),
                     (IrepId::CCType, Irep::just_id(IrepId::Double)),
                 ],
-            },
+            ),
             Type::Empty => Irep::just_id(IrepId::Empty),
             // CMBC currently represents these as 0 length arrays.
             Type::FlexibleArray { typ } => {
                 //CBMC expects the size to be a signed int constant.
                 let size = Type::ssize_t().zero();
-                Irep {
-                    id: IrepId::Array,
-                    sub: vec![typ.to_irep(mm)],
-                    named_sub: linear_map![(IrepId::Size, size.to_irep(mm))],
-                }
+                Irep::new(
+                    IrepId::Array,
+                    vec![typ.to_irep(mm)],
+                    linear_map![(IrepId::Size, size.to_irep(mm))],
+                )
             }
-            Type::Float => Irep {
-                id: IrepId::Floatbv,
-                sub: vec![],
-                named_sub: linear_map![
+            Type::Float => Irep::new(
+                IrepId::Floatbv,
+                vec![],
+                linear_map![
                     (IrepId::F, Irep::just_int_id(23)),
                     (IrepId::Width, Irep::just_int_id(32)),
                     (IrepId::CCType, Irep::just_id(IrepId::Float)),
                 ],
-            },
-            Type::Float16 => Irep {
-                id: IrepId::Floatbv,
-                sub: vec![],
+            ),
+            Type::Float16 => Irep::new(
+                IrepId::Floatbv,
+                vec![],
                 // Fraction bits: 10
                 // Exponent width bits: 5
                 // Sign bit: 1
-                named_sub: linear_map![
+                linear_map![
                     (IrepId::F, Irep::just_int_id(10)),
                     (IrepId::Width, Irep::just_int_id(16)),
                     (IrepId::CCType, Irep::just_id(IrepId::Float16)),
                 ],
-            },
-            Type::Float128 => Irep {
-                id: IrepId::Floatbv,
-                sub: vec![],
+            ),
+            Type::Float128 => Irep::new(
+                IrepId::Floatbv,
+                vec![],
                 // Fraction bits: 112
                 // Exponent width bits: 15
                 // Sign bit: 1
-                named_sub: linear_map![
+                linear_map![
                     (IrepId::F, Irep::just_int_id(112)),
                     (IrepId::Width, Irep::just_int_id(128)),
                     (IrepId::CCType, Irep::just_id(IrepId::Float128)),
                 ],
-            },
-            Type::IncompleteStruct { tag } => Irep {
-                id: IrepId::Struct,
-                sub: vec![],
-                named_sub: linear_map![
+            ),
+            Type::IncompleteStruct { tag } => Irep::new(
+                IrepId::Struct,
+                vec![],
+                linear_map![
                     (IrepId::Tag, Irep::just_string_id(tag.to_string())),
                     (IrepId::Incomplete, Irep::one()),
                 ],
-            },
-            Type::IncompleteUnion { tag } => Irep {
-                id: IrepId::Union,
-                sub: vec![],
-                named_sub: linear_map![
+            ),
+            Type::IncompleteUnion { tag } => Irep::new(
+                IrepId::Union,
+                vec![],
+                linear_map![
                     (IrepId::Tag, Irep::just_string_id(tag.to_string())),
                     (IrepId::Incomplete, Irep::one()),
                 ],
-            },
+            ),
             Type::InfiniteArray { typ } => {
                 let infinity = Irep::just_id(IrepId::Infinity).with_type(&Type::ssize_t(), mm);
-                Irep {
-                    id: IrepId::Array,
-                    sub: vec![typ.to_irep(mm)],
-                    named_sub: linear_map![(IrepId::Size, infinity)],
-                }
+                Irep::new(
+                    IrepId::Array,
+                    vec![typ.to_irep(mm)],
+                    linear_map![(IrepId::Size, infinity)],
+                )
             }
             Type::Integer => Irep::just_id(IrepId::Integer),
-            Type::Pointer { typ } => Irep {
-                id: IrepId::Pointer,
-                sub: vec![typ.to_irep(mm)],
-                named_sub: linear_map![(IrepId::Width, Irep::just_int_id(mm.pointer_width),)],
-            },
-            Type::Signedbv { width } => Irep {
-                id: IrepId::Signedbv,
-                sub: vec![],
-                named_sub: linear_map![(IrepId::Width, Irep::just_int_id(*width))],
-            },
-            Type::Struct { tag, components } => Irep {
-                id: IrepId::Struct,
-                sub: vec![],
-                named_sub: linear_map![
+            Type::Pointer { typ } => Irep::new(
+                IrepId::Pointer,
+                vec![typ.to_irep(mm)],
+                linear_map![(IrepId::Width, Irep::just_int_id(mm.pointer_width),)],
+            ),
+            Type::Signedbv { width } => Irep::new(
+                IrepId::Signedbv,
+                vec![],
+                linear_map![(IrepId::Width, Irep::just_int_id(*width))],
+            ),
+            Type::Struct { tag, components } => Irep::new(
+                IrepId::Struct,
+                vec![],
+                linear_map![
                     (IrepId::Tag, Irep::just_string_id(tag.to_string())),
                     (
                         IrepId::Components,
                         Irep::just_sub(components.iter().map(|x| x.to_irep(mm)).collect()),
                     ),
                 ],
-            },
-            Type::StructTag(name) => Irep {
-                id: IrepId::StructTag,
-                sub: vec![],
-                named_sub: linear_map![(
-                    IrepId::Identifier,
-                    Irep::just_string_id(name.to_string()),
-                )],
-            },
+            ),
+            Type::StructTag(name) => Irep::new(
+                IrepId::StructTag,
+                vec![],
+                linear_map![(IrepId::Identifier, Irep::just_string_id(name.to_string()),)],
+            ),
             Type::TypeDef { name, typ } => typ
                 .to_irep(mm)
                 .with_named_sub(IrepId::CTypedef, Irep::just_string_id(name.to_string())),
 
-            Type::Union { tag, components } => Irep {
-                id: IrepId::Union,
-                sub: vec![],
-                named_sub: linear_map![
+            Type::Union { tag, components } => Irep::new(
+                IrepId::Union,
+                vec![],
+                linear_map![
                     (IrepId::Tag, Irep::just_string_id(tag.to_string())),
                     (
                         IrepId::Components,
                         Irep::just_sub(components.iter().map(|x| x.to_irep(mm)).collect()),
                     ),
                 ],
-            },
-            Type::UnionTag(name) => Irep {
-                id: IrepId::UnionTag,
-                sub: vec![],
-                named_sub: linear_map![(
-                    IrepId::Identifier,
-                    Irep::just_string_id(name.to_string()),
-                )],
-            },
-            Type::Unsignedbv { width } => Irep {
-                id: IrepId::Unsignedbv,
-                sub: Vec::new(),
-                named_sub: linear_map![(IrepId::Width, Irep::just_int_id(*width))],
-            },
-            Type::VariadicCode { parameters, return_type } => Irep {
-                id: IrepId::Code,
-                sub: vec![],
-                named_sub: linear_map![
+            ),
+            Type::UnionTag(name) => Irep::new(
+                IrepId::UnionTag,
+                vec![],
+                linear_map![(IrepId::Identifier, Irep::just_string_id(name.to_string()),)],
+            ),
+            Type::Unsignedbv { width } => Irep::new(
+                IrepId::Unsignedbv,
+                Vec::new(),
+                linear_map![(IrepId::Width, Irep::just_int_id(*width))],
+            ),
+            Type::VariadicCode { parameters, return_type } => Irep::new(
+                IrepId::Code,
+                vec![],
+                linear_map![
                     (
                         IrepId::Parameters,
                         Irep::just_sub(parameters.iter().map(|x| x.to_irep(mm)).collect())
@@ -920,14 +939,14 @@ fn to_irep(&self, mm: &MachineModel) -> Irep {
                     ),
                     (IrepId::ReturnType, return_type.to_irep(mm)),
                 ],
-            },
+            ),
             Type::Vector { typ, size } => {
                 let size = Expr::int_constant(*size, Type::ssize_t());
-                Irep {
-                    id: IrepId::Vector,
-                    sub: vec![typ.to_irep(mm)],
-                    named_sub: linear_map![(IrepId::Size, size.to_irep(mm))],
-                }
+                Irep::new(
+                    IrepId::Vector,
+                    vec![typ.to_irep(mm)],
+                    linear_map![(IrepId::Size, size.to_irep(mm))],
+                )
             }
         }
     }
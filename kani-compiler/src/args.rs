@@ -1,6 +1,8 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use kani_metadata::{CheckCategory, DEFAULT_EXTERN_C_BUFFER_LEN};
+use std::path::PathBuf;
 use strum_macros::{AsRefStr, Display, EnumString, VariantNames};
 use tracing_subscriber::filter::Directive;
 
@@ -40,6 +42,72 @@ pub struct Arguments {
     /// Option used to disable asserting function contracts.
     #[clap(long)]
     pub no_assert_contracts: bool,
+    /// Option used to check and replace function contracts using CBMC's contract
+    /// instrumentation instead of Kani's.
+    #[clap(long)]
+    pub cbmc_contracts: bool,
+    /// Contract manifests of dependencies, which list the functions whose contracts were verified.
+    /// Calls to these functions are replaced by their contracts.
+    #[clap(long = "verified-contracts", value_name = "MANIFEST")]
+    pub verified_contracts: Vec<PathBuf>,
+    /// Option used to skip the codegen of harnesses that must be verified in a separate process.
+    /// The driver compiles and verifies each of them in its own process.
+    #[clap(long)]
+    pub defer_isolated_harnesses: bool,
+    /// Option used to check that public methods taking `&mut self` preserve the safety invariant
+    /// of their receiver.
+    #[clap(long)]
+    pub check_invariant_preservation: bool,
+    /// Option used to check that the end of every harness is reachable, i.e., that the
+    /// assumptions of the harness are not contradictory.
+    #[clap(long)]
+    pub check_vacuity: bool,
+    /// Option used to replace SipHash, the hashing algorithm of `DefaultHasher`, by a cheaper model.
+    #[clap(long)]
+    pub model_hashing: bool,
+    /// Option used to replace `HashMap`, `HashSet` and their iterators by the models in
+    /// `kani::models`.
+    #[clap(long)]
+    pub model_std_collections: bool,
+    /// Option used to replace the clocks of `std::time` by the nondeterministic models in
+    /// `kani::models::time`.
+    #[clap(long)]
+    pub model_time: bool,
+    /// Option used to replace the functions of `std::env` and `std::fs` that access environment
+    /// variables and files by the models in `kani::models::env` and `kani::models::fs`.
+    #[clap(long)]
+    pub model_std_io: bool,
+    /// Option used to run the threads spawned by the harnesses with a scheduler that makes at most
+    /// this many context switches.
+    #[clap(long)]
+    pub concurrency_bound: Option<usize>,
+    /// Option used to select the semantics of the atomic operations of the threads spawned by the
+    /// harnesses. They are sequentially consistent by default.
+    #[clap(long)]
+    pub memory_model: Option<MemoryModel>,
+    /// Option used to check that the reference counts of `Rc` and `Arc` never drop below zero and
+    /// that they are zero when a harness returns.
+    #[clap(long)]
+    pub check_refcounts: bool,
+    /// Option used to initialize a static with the contents of a file instead of its initializer,
+    /// given as `<STATIC>=<FILE>`.
+    #[clap(long)]
+    pub snapshot: Vec<String>,
+    /// Option used to turn off categories of checks for all harnesses, unless a harness turns
+    /// them back on with `#[kani::proof(checks(...))]`.
+    #[clap(long, value_delimiter = ',')]
+    pub disabled_checks: Vec<CheckCategory>,
+    /// Option used to list the unsafe operations that are reachable from each harness.
+    #[clap(long)]
+    pub list_unsafe: bool,
+    /// Option used to record the functions that are reachable from each harness, and the lines
+    /// that define them, so that the driver can select the harnesses affected by a change.
+    #[clap(long)]
+    pub reachable_functions: bool,
+    /// Option used to write the MIR of each harness, after Kani's transformations, next to its
+    /// goto model.
+    #[clap(long)]
+    pub emit_mir: bool,
     /// Option name used to enable assertion reachability checks.
     #[clap(long = "assertion-reach-checks")]
     pub check_assertion_reachability: bool,
@@ -49,6 +117,13 @@ pub struct Arguments {
     /// Option name used to dump function pointer restrictions.
     #[clap(long = "restrict-vtable-fn-ptrs")]
     pub emit_vtable_restrictions: bool,
+    /// Option used to replace the dynamic calls whose receiver can only have one concrete type by
+    /// direct calls.
+    #[clap(long)]
+    pub devirtualize: bool,
+    /// Option used to compile the harnesses of a codegen unit into one goto model.
+    #[clap(long)]
+    pub shared_model: bool,
     /// Option name used to use json pretty-print for output files.
     #[clap(long = "pretty-json-files")]
     pub output_pretty_json: bool,
@@ -62,6 +137,11 @@ pub struct Arguments {
     // (Passed here directly from [CargoKaniArgs] in `args_toml.rs`)
     #[arg(long = "harness", num_args(1), value_name = "HARNESS_FILTER")]
     pub harnesses: Vec<String>,
+    /// Add a stub to a harness, as `<HARNESS>=<ORIGINAL>=<REPLACEMENT>`, where the paths are
+    /// relative to the module of the harness like the ones of `#[kani::stub]`.
+    // (Passed here from the stub sets of the `Kani.toml` by `cargo kani`)
+    #[arg(long = "harness-stub", num_args(1), value_name = "HARNESS=ORIGINAL=REPLACEMENT")]
+    pub harness_stubs: Vec<String>,
     /// Option used for suppressing global ASM error.
     #[clap(long)]
     pub ignore_global_asm: bool,
@@ -103,6 +183,10 @@ pub struct Arguments {
     /// Print the final LLBC file to stdout.
     #[clap(long)]
     pub print_llbc: bool,
+    /// Emit a note at the definition of every struct, enum and union of the crate, which
+    /// `cargo kani fix` uses to find where to add the derives that are missing.
+    #[clap(long)]
+    pub print_type_definitions: bool,
     /// If we are running the autoharness subcommand, the paths to include.
     /// See kani_driver::autoharness_args for documentation.
     #[arg(long = "autoharness-include-pattern", num_args(1))]
@@ -111,6 +195,36 @@ pub struct Arguments {
     /// See kani_driver::autoharness_args for documentation.
     #[arg(long = "autoharness-exclude-pattern", num_args(1))]
     pub autoharness_excluded_patterns: Vec<String>,
+    /// If we are running the autoharness subcommand, the regular expressions matching the source
+    /// files to exclude. The driver translates the user's glob patterns into these expressions.
+    #[arg(long = "autoharness-exclude-file-pattern", num_args(1))]
+    pub autoharness_excluded_file_patterns: Vec<String>,
+    /// If we are running the autoharness subcommand, exclude functions generated by macro
+    /// expansions.
+    #[clap(long)]
+    pub autoharness_exclude_expanded: bool,
+    /// If we are running the autoharness subcommand, also create harnesses for `#[no_mangle] extern "C"`
+    /// functions with raw pointer arguments.
+    #[clap(long)]
+    pub autoharness_extern_c: bool,
+    /// The maximum number of elements that a raw pointer argument of an `extern "C"` function points to.
+    #[clap(long, default_value_t = DEFAULT_EXTERN_C_BUFFER_LEN)]
+    pub autoharness_extern_c_buffer_len: usize,
+    /// If we are running the autoharness subcommand, also create harnesses that check the laws of
+    /// the `Eq`, `Ord`, `Hash` and `From` implementations of the crate.
+    #[clap(long)]
+    pub autoharness_traits: bool,
+    /// If we are running the autoharness subcommand, only create harnesses for the functions that
+    /// are reachable from outside the crate. Set by `--prove-no-panics`.
+    #[clap(long)]
+    pub autoharness_public_only: bool,
+}
+
+#[derive(Debug, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum MemoryModel {
+    /// The release/acquire semantics of C11, where relaxed loads may read stale values.
+    ReleaseAcquire,
 }
 
 #[derive(Debug, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
@@ -121,4 +235,12 @@ pub enum ExtraChecks {
     Validity,
     /// Check for using uninitialized memory.
     Uninit,
+    /// Check that pointers cast from integers have the provenance of an exposed allocation, and
+    /// are within its bounds.
+    Provenance,
+    /// Check that every heap allocation of a harness is deallocated exactly once.
+    Leaks,
+    /// Check that integer `as` casts that may not preserve their value, and float-to-integer
+    /// casts, are applied to values that the target type can represent.
+    Truncation,
 }
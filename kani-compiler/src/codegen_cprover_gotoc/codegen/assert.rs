@@ -20,7 +20,6 @@
 
 use super::source_region::SourceRegion;
 use crate::codegen_cprover_gotoc::GotocCtx;
-use cbmc::InternedString;
 use cbmc::goto_program::{Expr, Location, Stmt, Type};
 use rustc_public::mir::{Place, ProjectionElem};
 use rustc_public::ty::{Span as SpanStable, Ty};
@@ -61,6 +60,12 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: "Errors" for this type of assertion just mean "reachable" not failure.
     CodeCoverage,
+    /// Reachability markers added by users with the `kani::probe` macro. Generally just an
+    /// `assert(false)` that's not an error.
+    ///
+    /// SPECIAL BEHAVIOR: "Errors" for this type of assertion just mean "reachable" not failure.
+    /// The driver aggregates the results of probes with the same name.
+    Probe,
     /// Ordinary (Rust) assertions and panics.
     ///
     /// SPECIAL BEHAVIOR: These assertion failures should be observable during normal execution of Rust code.
@@ -75,9 +80,25 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: Same as SafetyCheck. TODO: Replace this with `SafetyCheck`.
     FiniteCheck,
+    /// Calls to `std::process::abort`, and calls to `std::process::exit` with a non-zero exit code.
+    /// Kani treats both as terminal states, so nothing after the call executes.
+    ///
+    /// SPECIAL BEHAVIOR: These are not panics, so they are not caught by `#[should_panic]`.
+    ProcessExit,
     /// Checks added by Kani compiler to determine whether a property (e.g.
     /// `PropertyClass::Assertion` or `PropertyClass:Cover`) is reachable
     ReachabilityCheck,
+    /// Checks added by Kani compiler to bound the depth of the recursive calls of a harness with
+    /// `#[kani::proof(recursion_limit = N)]`.
+    ///
+    /// SPECIAL BEHAVIOR: Failures mean that some paths were not explored, rather than that the
+    /// harness failed. Also makes other properties UNDETERMINED, like unwinding assertions.
+    RecursionLimit,
+    /// An `assert(false)` that Kani adds at the end of each harness with `--check-vacuity`.
+    ///
+    /// SPECIAL BEHAVIOR: Failures mean that the end of the harness is reachable. The driver
+    /// reports the harness as vacuous if none of these checks fails, and does not show them.
+    VacuityCheck,
     /// Checks added by Kani compiler to detect safety conditions violation.
     /// E.g., things that trigger UB or unstable behavior.
     ///
@@ -135,6 +156,23 @@ pub fn codegen_assert_assume(
     ) -> Stmt {
         if property_class == PropertyClass::Assertion && self.queries.args().prove_safety_only {
             Stmt::assume(cond, loc)
+        } else if let (PropertyClass::Assertion, Some(expected)) =
+            (&property_class, &self.panic_expected)
+        {
+            // The contract of the function under verification may expect this panic, in which
+            // case it ends the execution like an assumption would.
+            Stmt::block(
+                vec![
+                    Stmt::assert(
+                        cond.clone().or(expected.clone()),
+                        property_class.as_str(),
+                        message,
+                        loc,
+                    ),
+                    Stmt::assume(cond, loc),
+                ],
+                loc,
+            )
         } else {
             let property_name = property_class.as_str();
             Stmt::block(
@@ -157,6 +195,22 @@ pub fn codegen_cover(&self, cond: Expr, msg: &str, span: SpanStable) -> Stmt {
         self.codegen_assert(cond.not(), PropertyClass::Cover, msg, loc)
     }
 
+    /// Generate a probe that fails if and only if the current location is reachable.
+    pub fn codegen_probe(&self, name: &str, span: SpanStable) -> Stmt {
+        let loc = self.codegen_caller_span_stable(span);
+        self.codegen_assert(Expr::bool_false(), PropertyClass::Probe, name, loc)
+    }
+
+    /// Generate a check that fails if and only if the end of the current harness is reachable.
+    pub fn codegen_vacuity_check(&self, loc: Location) -> Stmt {
+        self.codegen_assert(
+            Expr::bool_false(),
+            PropertyClass::VacuityCheck,
+            "end of the harness is reachable",
+            loc,
+        )
+    }
+
     /// Generate a cover statement for code coverage reports.
     pub fn codegen_coverage(
         &self,
@@ -283,10 +337,8 @@ pub fn codegen_unimplemented_stmt(
     ) -> Stmt {
         debug!("codegen_unimplemented: {} at {}", operation_name, loc.short_string());
 
-        // Save this occurrence so we can emit a warning in the compilation report.
-        let key: InternedString = operation_name.into();
-        let entry = self.unsupported_constructs.entry(key).or_default();
-        entry.push(loc);
+        // Save this occurrence so we can report it in the harness metadata.
+        self.store_unsupported_construct(operation_name, loc, url);
 
         self.codegen_assert_assume(
             Expr::bool_false(),
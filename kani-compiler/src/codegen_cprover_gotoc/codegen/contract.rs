@@ -1,16 +1,20 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
-use crate::codegen_cprover_gotoc::{GotocCtx, codegen::ty_stable::pointee_type_stable};
+use crate::codegen_cprover_gotoc::{GotocCtx, codegen::ty_stable::pointee_type_stable, utils};
 use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::is_async_fn;
+use crate::kani_middle::kani_functions::{KaniFunction, KaniHook};
+use cbmc::InternedString;
 use cbmc::goto_program::FunctionContract;
 use cbmc::goto_program::{Expr, Lambda, Location, Type};
-use kani_metadata::AssignsContract;
+use kani_metadata::{AssignsContract, CbmcContracts};
 use rustc_hir::def_id::DefId as InternalDefId;
 use rustc_public::CrateDef;
-use rustc_public::mir::mono::{Instance, MonoItem};
+use rustc_public::mir::mono::{Instance, InstanceKind, MonoItem};
 use rustc_public::mir::{Local, VarDebugInfoContents};
 use rustc_public::rustc_internal;
 use rustc_public::ty::{FnDef, RigidTy, TyKind};
+use rustc_span::Symbol;
 
 impl GotocCtx<'_> {
     /// Given the `proof_for_contract` target `function_under_contract` and the reachable `items`,
@@ -87,11 +91,23 @@ fn find_recursion_tracker(&mut self, items: &[MonoItem]) -> Option<String> {
 
     /// Find the modifies recursively since we may have a recursion wrapper.
     /// I.e.: [recursion_wrapper ->]? check -> modifies.
+    ///
+    /// The closures of an `async fn` return futures, so each closure is defined in the coroutine
+    /// returned by the function or closure that contains it.
     fn find_modifies(&mut self, instance: Instance) -> Option<Instance> {
         let contract_attrs =
             KaniAttributes::for_instance(self.tcx, instance).contract_attributes()?;
+        let is_async = is_async_fn(self.tcx, FnDef(instance.def.def_id()));
         let mut find_closure = |inside: Instance, name: &str| {
-            let body = self.transformer.body(self.tcx, inside);
+            let mut body = self.transformer.body(self.tcx, inside);
+            if is_async {
+                let TyKind::RigidTy(RigidTy::Coroutine(def, args)) = body.ret_local().ty.kind()
+                else {
+                    unreachable!("expected `{}` to return a future", inside.name())
+                };
+                let coroutine = Instance::resolve(FnDef(def.0), &args).unwrap();
+                body = self.transformer.body(self.tcx, coroutine);
+            }
             body.var_debug_info.iter().find_map(|var_info| {
                 if var_info.name.as_str() == name {
                     let ty = match &var_info.value {
@@ -165,55 +181,16 @@ fn codegen_modifies_contract(
 
         let assigns: Vec<_> = modifies_tys
             .into_iter()
-            // do not attempt to dereference (and assign) a ZST
-            .filter(|ty| !self.is_zst_stable(pointee_type_stable(*ty).unwrap()))
             .enumerate()
+            // do not attempt to dereference (and assign) a ZST
+            .filter(|(_, ty)| !self.is_zst_stable(pointee_type_stable(*ty).unwrap()))
             .map(|(idx, ty)| {
                 let ptr = modifies_args.clone().member(idx.to_string(), &self.symbol_table);
-                if self.is_fat_pointer_stable(ty) {
-                    let unref = match ty.kind() {
-                        TyKind::RigidTy(RigidTy::RawPtr(pointee_ty, _)) => pointee_ty,
-                        kind => unreachable!("Expected a raw pointer, but found {:?}", kind),
-                    };
-                    let size = match unref.kind() {
-                        TyKind::RigidTy(RigidTy::Slice(elt_type)) => {
-                            elt_type.layout().unwrap().shape().size.bytes()
-                        }
-                        TyKind::RigidTy(RigidTy::Str) => 1,
-                        // For adt, see https://rust-lang.zulipchat.com/#narrow/stream/182449-t-compiler.2Fhelp
-                        TyKind::RigidTy(RigidTy::Adt(..)) => {
-                            todo!("Adt fat pointers not implemented")
-                        }
-                        kind => unreachable!("Generating a slice fat pointer to {:?}", kind),
-                    };
-                    Lambda::as_contract_for(
-                        &goto_annotated_fn_typ,
-                        None,
-                        Expr::symbol_expression(
-                            "__CPROVER_object_upto",
-                            Type::code(
-                                vec![
-                                    Type::empty()
-                                        .to_pointer()
-                                        .as_parameter(None, Some("ptr".into())),
-                                    Type::size_t().as_parameter(None, Some("size".into())),
-                                ],
-                                Type::empty(),
-                            ),
-                        )
-                        .call(vec![
-                            ptr.clone()
-                                .member("data", &self.symbol_table)
-                                .cast_to(Type::empty().to_pointer()),
-                            ptr.member("len", &self.symbol_table).mul(Expr::size_constant(
-                                size.try_into().unwrap(),
-                                &self.symbol_table,
-                            )),
-                        ]),
-                    )
-                } else {
-                    Lambda::as_contract_for(&goto_annotated_fn_typ, None, ptr.dereference())
-                }
+                Lambda::as_contract_for(
+                    &goto_annotated_fn_typ,
+                    None,
+                    self.ty_to_assign_target(ty, &ptr),
+                )
             })
             .chain(shadow_memory_assign)
             .collect();
@@ -240,4 +217,151 @@ pub fn attach_modifies_contract(&mut self, instance: Instance) {
         self.symbol_table.attach_contract(&mangled_name, goto_contract);
         self.reset_current_fn();
     }
+
+    /// If the `kani::internal::expect_panic` hook is reachable, declare the global flag that it
+    /// sets, which is false unless the function under contract is expected to panic.
+    ///
+    /// This must be called before any function is codegen'd, since panics check the flag.
+    pub fn declare_panic_expectation(&mut self, items: &[MonoItem]) {
+        let expect_panic =
+            self.queries.kani_functions().get(&KaniFunction::Hook(KaniHook::ExpectPanic)).copied();
+        let is_reachable = expect_panic.is_some_and(|expect_panic| {
+            items.iter().any(|item| match item {
+                MonoItem::Fn(instance) => instance.def.def_id() == expect_panic.def_id(),
+                MonoItem::Static(_) | MonoItem::GlobalAsm(_) => false,
+            })
+        });
+        if is_reachable {
+            let flag = self
+                .ensure_global_var_init(
+                    "__kani_panic_expected",
+                    false,
+                    false,
+                    Type::bool(),
+                    Location::none(),
+                    |_, _| Expr::bool_false(),
+                )
+                .to_expr();
+            self.panic_expected = Some(flag);
+        }
+    }
+
+    /// Attach the contracts of the functions checked and replaced by the harnesses in
+    /// `starting_items` to their goto functions, so CBMC can instrument them, and store which
+    /// functions those are in `self.cbmc_contracts`.
+    ///
+    /// The function being checked is `check_contract`, while the replaced functions are the
+    /// targets of `#[kani::stub_verified]`. Their bodies were kept as is by the contract pass.
+    pub fn handle_cbmc_contracts(
+        &mut self,
+        starting_items: &[MonoItem],
+        check_contract: Option<InternalDefId>,
+        items: &[MonoItem],
+    ) {
+        let tcx = self.tcx;
+        let replaced: Vec<InternalDefId> = starting_items
+            .iter()
+            .filter_map(|item| if let MonoItem::Fn(harness) = item { Some(*harness) } else { None })
+            .flat_map(|harness| {
+                KaniAttributes::for_instance(tcx, harness).interpret_stub_verified_attribute()
+            })
+            .map(|def| rustc_internal::internal(tcx, def.def_id()))
+            .collect();
+        let mut contracts = CbmcContracts::default();
+        for item in items {
+            let MonoItem::Fn(instance) = *item else { continue };
+            let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+            if check_contract == Some(def_id) {
+                self.attach_cbmc_contract(instance, items);
+                contracts.enforce = Some(instance.mangled_name());
+                contracts.recursive = KaniAttributes::for_instance(tcx, instance).has_recursion();
+            } else if replaced.contains(&def_id) {
+                self.attach_cbmc_contract(instance, items);
+                contracts.replace.push(instance.mangled_name());
+            }
+        }
+        self.cbmc_contracts = Some(contracts);
+    }
+
+    /// Attach `requires` and `ensures` clauses to the goto function of `instance`. Each clause
+    /// calls the predicate closure generated by the contract macros with the lambda arguments,
+    /// i.e., the result (for `ensures`) and the function parameters.
+    fn attach_cbmc_contract(&mut self, instance: Instance, items: &[MonoItem]) {
+        let (requires, ensures) =
+            KaniAttributes::for_instance(self.tcx, instance).cbmc_contract().unwrap();
+        let mangled_name = instance.mangled_name();
+        let fn_typ = self.symbol_table.lookup(&mangled_name).unwrap().typ.clone();
+        let result_name: InternedString = format!("{mangled_name}::result").into();
+        let result = Expr::symbol_expression(result_name, fn_typ.return_type().unwrap().clone());
+        let params: Vec<_> = fn_typ
+            .parameters()
+            .unwrap()
+            .iter()
+            .map(|param| Expr::symbol_expression(param.identifier().unwrap(), param.typ().clone()))
+            .collect();
+
+        // The predicates are closures declared in the body of the function, so the body of this
+        // instance gives the type, and thus the instance, of its predicates.
+        let body = self.transformer.body(self.tcx, instance);
+        let predicate_call = |ctx: &Self, name: Symbol, args: Vec<Expr>| {
+            let predicate = body
+                .var_debug_info
+                .iter()
+                .filter(|var_info| var_info.name == name.as_str())
+                .find_map(|var_info| {
+                    let ty = match &var_info.value {
+                        VarDebugInfoContents::Place(place) => place.ty(body.locals()).ok()?,
+                        VarDebugInfoContents::Const(const_op) => const_op.ty(),
+                    };
+                    let TyKind::RigidTy(RigidTy::Closure(def, closure_args)) = ty.kind() else {
+                        return None;
+                    };
+                    items.iter().find_map(|item| match item {
+                        MonoItem::Fn(pred_instance)
+                            if pred_instance.kind == InstanceKind::Item
+                                && pred_instance.def.def_id() == def.def_id()
+                                && pred_instance.args() == closure_args =>
+                        {
+                            Some(*pred_instance)
+                        }
+                        _ => None,
+                    })
+                })
+                .unwrap_or_else(|| panic!("Expected `{name}` to be reachable"));
+            let symbol = ctx.symbol_table.lookup(predicate.mangled_name()).unwrap();
+            // The first parameter of the closure is a reference to its environment, which is
+            // never read since the closure doesn't capture anything.
+            let (env, pred_params) = symbol.typ.parameters().unwrap().split_first().unwrap();
+            // Zero-sized arguments are not part of the goto signature, which may include the
+            // result of the function. The predicate has the same parameters as the function
+            // otherwise, so we only pass the trailing arguments.
+            let num_params = pred_params.len();
+            let Some(first_arg) = args.len().checked_sub(num_params) else {
+                utils::span_err(
+                    ctx.tcx,
+                    instance.def.span(),
+                    format!(
+                        "cannot use the CBMC contract of `{}`: its predicate `{name}` takes \
+                        {num_params} arguments, but the function only provides {}",
+                        instance.name(),
+                        args.len()
+                    ),
+                );
+                return None;
+            };
+            let args = [env.typ().null()].into_iter().chain(args[first_arg..].iter().cloned());
+            Some(symbol.to_expr().call(args.collect()).cast_to(Type::bool()))
+        };
+        let (Some(requires_clause), Some(ensures_clause)) = (
+            predicate_call(self, requires, params.clone()),
+            predicate_call(self, ensures, [result].into_iter().chain(params).collect()),
+        ) else {
+            return;
+        };
+        let contract = FunctionContract::with_conditions(
+            vec![Lambda::as_contract_for(&fn_typ, Some(result_name), requires_clause)],
+            vec![Lambda::as_contract_for(&fn_typ, Some(result_name), ensures_clause)],
+        );
+        self.symbol_table.attach_contract(&mangled_name, contract);
+    }
 }
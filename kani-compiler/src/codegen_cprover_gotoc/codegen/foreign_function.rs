@@ -14,12 +14,13 @@
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
 use cbmc::goto_program::{Expr, Location, Stmt, Symbol, Type};
 use cbmc::{InternString, InternedString};
+use kani_metadata::{ForeignFunction, ForeignFunctionEncoding};
 use lazy_static::lazy_static;
 use rustc_public::CrateDef;
 use rustc_public::abi::{CallConvention, PassMode};
 use rustc_public::mir::Place;
 use rustc_public::mir::mono::Instance;
-use rustc_public::ty::{RigidTy, TyKind};
+use rustc_public::ty::{RigidTy, Ty, TyKind};
 use tracing::{debug, trace};
 
 lazy_static! {
@@ -71,6 +72,7 @@ pub fn codegen_foreign_fn(&mut self, instance: Instance) -> &Symbol {
             // When C-FFI feature is enabled, we just trust the rust declaration.
             // TODO: Add proper casting and clashing definitions check.
             // https://github.com/model-checking/kani/issues/2426
+            self.record_foreign_function(instance, ForeignFunctionEncoding::Declared);
             self.ensure(mangled_fn_name, |gcx, _| {
                 let typ = gcx.codegen_ffi_type(instance);
                 Symbol::function(mangled_fn_name, typ, None, instance.name(), loc)
@@ -79,6 +81,7 @@ pub fn codegen_foreign_fn(&mut self, instance: Instance) -> &Symbol {
         } else {
             let shim_name = format!("{mangled_fn_name}_ffi_shim");
             trace!(?shim_name, "codegen_foreign_function");
+            self.record_foreign_function(instance, ForeignFunctionEncoding::Unsupported);
             self.ensure(&shim_name, |gcx, _| {
                 // Generate a shim with an unsupported C-FFI error message.
                 let typ = gcx.codegen_ffi_type(instance);
@@ -127,6 +130,33 @@ pub fn codegen_foreign_call(
         }
     }
 
+    /// Count a call to `instance` by the current function if it is a foreign function that Kani
+    /// does not model.
+    pub fn record_foreign_call(&mut self, instance: Instance) {
+        let caller = self.current_fn_name();
+        if let Some(users) = self.foreign_function_users.get_mut(&instance.mangled_name().intern())
+        {
+            *users.entry(caller).or_default() += 1;
+        }
+    }
+
+    /// Record that `instance` is a foreign function that Kani does not model, so that we can
+    /// report it for the harness.
+    fn record_foreign_function(&mut self, instance: Instance, encoding: ForeignFunctionEncoding) {
+        let mangled_name = instance.mangled_name().intern();
+        self.foreign_functions.entry(mangled_name).or_insert_with(|| {
+            let fn_sig = instance.ty().kind().fn_sig().unwrap();
+            ForeignFunction {
+                name: instance.trimmed_name(),
+                signature: Ty::from_rigid_kind(RigidTy::FnPtr(fn_sig)).to_string(),
+                call_sites: 0,
+                encoding,
+            }
+        });
+        let user = self.current_fn_name();
+        self.foreign_function_users.entry(mangled_name).or_default().entry(user).or_default();
+    }
+
     /// Checks whether C-FFI has been enabled or not.
     /// When enabled, we blindly encode the function type as is.
     fn is_cffi_enabled(&self) -> bool {
@@ -179,10 +209,6 @@ fn codegen_ffi_unsupported(&mut self, instance: Instance, loc: Location) -> Stmt
         let fn_name = &instance.mangled_name();
         debug!(?fn_name, ?loc, "codegen_ffi_unsupported");
 
-        // Save this occurrence so we can emit a warning in the compilation report.
-        let entry = self.unsupported_constructs.entry("foreign function".into()).or_default();
-        entry.push(loc);
-
         let call_conv = instance.fn_abi().unwrap().conv;
         let msg = format!("call to foreign \"{call_conv:?}\" function `{fn_name}`");
         let url = if call_conv == CallConvention::C {
@@ -190,6 +216,9 @@ fn codegen_ffi_unsupported(&mut self, instance: Instance, loc: Location) -> Stmt
         } else {
             "https://github.com/model-checking/kani/issues/new/choose"
         };
+
+        // Save this occurrence so we can report it in the harness metadata.
+        self.store_unsupported_construct("foreign function", loc, url);
         self.codegen_assert_assume(
             Expr::bool_false(),
             PropertyClass::UnsupportedConstruct,
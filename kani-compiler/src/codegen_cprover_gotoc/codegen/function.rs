@@ -4,15 +4,16 @@
 //! This file contains functions related to codegenning MIR functions into gotoc
 
 use crate::codegen_cprover_gotoc::GotocCtx;
-use crate::codegen_cprover_gotoc::codegen::block::reverse_postorder;
+use crate::codegen_cprover_gotoc::codegen::block::{bb_label, reverse_postorder};
 use cbmc::InternString;
 use cbmc::InternedString;
-use cbmc::goto_program::{Expr, Stmt, Symbol};
+use cbmc::goto_program::{Expr, Stmt, StmtBody, Symbol};
 use rustc_public::CrateDef;
 use rustc_public::mir::mono::Instance;
-use rustc_public::mir::{Body, Local};
+use rustc_public::mir::{Body, Local, TerminatorKind};
+use rustc_public::rustc_internal;
 use rustc_public::ty::{RigidTy, TyKind};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use tracing::{debug, debug_span};
 
 /// Codegen MIR functions into gotoc
@@ -33,6 +34,7 @@ fn codegen_declare_variables(&mut self, body: &Body, function_name: InternedStri
             let base_name = self.codegen_var_base_name(&lc);
             let name = self.codegen_var_name(&lc);
             let var_type = self.codegen_ty_stable(ldata.ty);
+            self.record_largest_array(&var_type);
             let loc = self.codegen_span_stable(ldata.span);
             // Indices [1, N] represent the function parameters where N is the number of parameters.
             // Except that ZST fields are not included as parameters.
@@ -83,11 +85,48 @@ pub fn codegen_function(&mut self, instance: Instance) {
             let loc = self.codegen_span_stable(instance.def.span());
             let stmts = self.current_fn_mut().extract_block();
             let goto_body = Stmt::block(stmts, loc);
+            if !self.loop_labels.is_empty() {
+                self.record_labeled_loops(&name, &body, &goto_body);
+            }
             self.symbol_table.update_fn_declaration_with_definition(&name, goto_body);
             self.reset_current_fn();
         }
     }
 
+    /// Record the CBMC ids of the loops of this function that have one of the `loop_labels` of the
+    /// harness, so that the driver can bound each of them with `--unwindset`.
+    ///
+    /// CBMC numbers the backward gotos of a function in the order of its instructions, and the id
+    /// of a loop is `<function>.<number>`. rustc gives the back edge of a loop the span of the loop
+    /// expression, so the gotos of a labeled loop are the backward gotos to the target of that back
+    /// edge, which include the ones of its `continue` expressions.
+    fn record_labeled_loops(&mut self, name: &str, body: &Body, goto_body: &Stmt) {
+        let mut headers = BTreeMap::new();
+        for block in &body.blocks {
+            if let TerminatorKind::Goto { target } = block.terminator.kind {
+                let span = rustc_internal::internal(self.tcx, block.terminator.span);
+                if let Some((label, _)) =
+                    self.loop_labels.iter().find(|(_, loop_span)| *loop_span == span)
+                {
+                    headers.insert(InternedString::from(bb_label(target)), label.clone());
+                }
+            }
+        }
+        if headers.is_empty() {
+            return;
+        }
+        let mut backward_gotos = vec![];
+        collect_backward_gotos(goto_body, &mut HashSet::new(), &mut backward_gotos);
+        for (number, dest) in backward_gotos.into_iter().enumerate() {
+            if let Some(label) = dest.and_then(|dest| headers.get(&dest)) {
+                self.labeled_loops
+                    .entry(label.clone())
+                    .or_default()
+                    .push(format!("{name}.{number}"));
+            }
+        }
+    }
+
     /// Codegen changes required due to the function ABI.
     /// We currently untuple arguments for RustCall ABI where the `spread_arg` is set.
     fn codegen_function_prelude(&mut self, body: &Body) {
@@ -324,3 +363,52 @@ fn parse_coverage_opaque(coverage_opaque: &Opaque) -> BasicCoverageBlock {
         }
     }
 }
+
+/// Collect the backward gotos of `stmt` in the order in which CBMC numbers them, i.e., the order of
+/// the instructions that CBMC generates for it. The destination is `None` for the backward goto of
+/// a `while` or `for` statement.
+fn collect_backward_gotos(
+    stmt: &Stmt,
+    labels: &mut HashSet<InternedString>,
+    backward_gotos: &mut Vec<Option<InternedString>>,
+) {
+    match stmt.body() {
+        StmtBody::Label { label, body } => {
+            labels.insert(*label);
+            collect_backward_gotos(body, labels, backward_gotos);
+        }
+        StmtBody::Goto { dest, .. } => {
+            if labels.contains(dest) {
+                backward_gotos.push(Some(*dest));
+            }
+        }
+        StmtBody::Block(stmts) | StmtBody::AtomicBlock(stmts) => {
+            stmts.iter().for_each(|stmt| collect_backward_gotos(stmt, labels, backward_gotos));
+        }
+        StmtBody::Ifthenelse { t, e, .. } => {
+            collect_backward_gotos(t, labels, backward_gotos);
+            if let Some(e) = e {
+                collect_backward_gotos(e, labels, backward_gotos);
+            }
+        }
+        StmtBody::Switch { cases, default, .. } => {
+            for case in cases {
+                collect_backward_gotos(case.body(), labels, backward_gotos);
+            }
+            if let Some(default) = default {
+                collect_backward_gotos(default, labels, backward_gotos);
+            }
+        }
+        StmtBody::While { body, .. } => {
+            collect_backward_gotos(body, labels, backward_gotos);
+            backward_gotos.push(None);
+        }
+        StmtBody::For { init, update, body, .. } => {
+            collect_backward_gotos(init, labels, backward_gotos);
+            collect_backward_gotos(body, labels, backward_gotos);
+            collect_backward_gotos(update, labels, backward_gotos);
+            backward_gotos.push(None);
+        }
+        _ => {}
+    }
+}
@@ -13,7 +13,7 @@
 use rustc_public::mir::mono::Instance;
 use rustc_public::mir::{BasicBlockIdx, Operand, Place};
 use rustc_public::rustc_internal;
-use rustc_public::ty::{GenericArgs, RigidTy, Span, Ty, TyKind, UintTy};
+use rustc_public::ty::{GenericArgKind, GenericArgs, RigidTy, Span, Ty, TyKind, UintTy};
 use tracing::debug;
 
 pub struct SizeAlign {
@@ -153,6 +153,28 @@ macro_rules! codegen_simple_intrinsic {
             }};
         }
 
+        // Codegens an `f16` intrinsic by computing it in `f32` with the given C library function.
+        // Every `f16` value is exactly representable as an `f32`.
+        macro_rules! codegen_f16_intrinsic_in_f32 {
+            ($f:ident) => {{
+                let mm = self.symbol_table.machine_model();
+                let promoted_fargs = fargs
+                    .into_iter()
+                    .map(|arg| {
+                        if *arg.typ() == Type::float16() { arg.cast_to(Type::float()) } else { arg }
+                    })
+                    .collect();
+                let casted_fargs =
+                    Expr::cast_arguments_to_target_equivalent_function_parameter_types(
+                        &BuiltinFn::$f.as_expr(),
+                        promoted_fargs,
+                        mm,
+                    );
+                let expr = BuiltinFn::$f.call(casted_fargs, loc).cast_to(Type::float16());
+                self.codegen_expr_to_place_stable(place, expr, loc)
+            }};
+        }
+
         // Intrinsics which encode a division operation with overflow check
         macro_rules! codegen_op_with_div_overflow_check {
             ($f:ident) => {{
@@ -318,14 +340,21 @@ macro_rules! unstable_codegen {
             Intrinsic::Bswap => {
                 self.codegen_expr_to_place_stable(place, fargs.remove(0).bswap(), loc)
             }
+            Intrinsic::CeilF16 | Intrinsic::CeilF128 => {
+                self.codegen_round_to_integral(cbmc::RoundingMode::Upward, fargs, place, loc)
+            }
             Intrinsic::CeilF32 => codegen_simple_intrinsic!(Ceilf),
             Intrinsic::CeilF64 => codegen_simple_intrinsic!(Ceil),
             Intrinsic::CompareBytes => self.codegen_compare_bytes(fargs, place, loc),
             Intrinsic::Copy => {
                 self.codegen_copy(intrinsic_str, false, fargs, farg_types, Some(place), loc)
             }
+            Intrinsic::CopySignF16 | Intrinsic::CopySignF128 => {
+                self.codegen_copysign(fargs, place, loc)
+            }
             Intrinsic::CopySignF32 => codegen_simple_intrinsic!(Copysignf),
             Intrinsic::CopySignF64 => codegen_simple_intrinsic!(Copysign),
+            Intrinsic::CosF16 => codegen_f16_intrinsic_in_f32!(Cosf),
             Intrinsic::CosF32 => codegen_simple_intrinsic!(Cosf),
             Intrinsic::CosF64 => codegen_simple_intrinsic!(Cos),
             Intrinsic::Ctlz => codegen_count_intrinsic!(ctlz, true),
@@ -340,10 +369,13 @@ macro_rules! unstable_codegen {
                 self.codegen_expr_to_place_stable(place, e, loc)
             }
             Intrinsic::ExactDiv => self.codegen_exact_div(fargs, place, loc),
+            Intrinsic::Exp2F16 => codegen_f16_intrinsic_in_f32!(Exp2f),
             Intrinsic::Exp2F32 => codegen_simple_intrinsic!(Exp2f),
             Intrinsic::Exp2F64 => codegen_simple_intrinsic!(Exp2),
+            Intrinsic::ExpF16 => codegen_f16_intrinsic_in_f32!(Expf),
             Intrinsic::ExpF32 => codegen_simple_intrinsic!(Expf),
             Intrinsic::ExpF64 => codegen_simple_intrinsic!(Exp),
+            Intrinsic::FabsF16 | Intrinsic::FabsF128 => self.codegen_fabs(fargs, place, loc),
             Intrinsic::FabsF32 => codegen_simple_intrinsic!(Fabsf),
             Intrinsic::FabsF64 => codegen_simple_intrinsic!(Fabs),
             Intrinsic::FaddFast => {
@@ -364,8 +396,12 @@ macro_rules! unstable_codegen {
                 ret_ty,
                 loc,
             ),
+            Intrinsic::FloorF16 | Intrinsic::FloorF128 => {
+                self.codegen_round_to_integral(cbmc::RoundingMode::Downward, fargs, place, loc)
+            }
             Intrinsic::FloorF32 => codegen_simple_intrinsic!(Floorf),
             Intrinsic::FloorF64 => codegen_simple_intrinsic!(Floor),
+            Intrinsic::FmafF16 => self.codegen_fma_f16(fargs, place, loc),
             Intrinsic::FmafF32 => codegen_simple_intrinsic!(Fmaf),
             Intrinsic::FmafF64 => codegen_simple_intrinsic!(Fma),
             Intrinsic::FmulFast => {
@@ -385,21 +421,32 @@ macro_rules! unstable_codegen {
                 self.codegen_expr_to_place_stable(place, Expr::c_false(), loc)
             }
             Intrinsic::Likely => self.codegen_expr_to_place_stable(place, fargs.remove(0), loc),
+            Intrinsic::Log10F16 => codegen_f16_intrinsic_in_f32!(Log10f),
             Intrinsic::Log10F32 => codegen_simple_intrinsic!(Log10f),
             Intrinsic::Log10F64 => codegen_simple_intrinsic!(Log10),
+            Intrinsic::Log2F16 => codegen_f16_intrinsic_in_f32!(Log2f),
             Intrinsic::Log2F32 => codegen_simple_intrinsic!(Log2f),
             Intrinsic::Log2F64 => codegen_simple_intrinsic!(Log2),
+            Intrinsic::LogF16 => codegen_f16_intrinsic_in_f32!(Logf),
             Intrinsic::LogF32 => codegen_simple_intrinsic!(Logf),
             Intrinsic::LogF64 => codegen_simple_intrinsic!(Log),
+            Intrinsic::MaxNumF16 | Intrinsic::MaxNumF128 => {
+                self.codegen_min_max_num(true, fargs, place, loc)
+            }
             Intrinsic::MaxNumF32 => codegen_simple_intrinsic!(Fmaxf),
             Intrinsic::MaxNumF64 => codegen_simple_intrinsic!(Fmax),
+            Intrinsic::MinNumF16 | Intrinsic::MinNumF128 => {
+                self.codegen_min_max_num(false, fargs, place, loc)
+            }
             Intrinsic::MinNumF32 => codegen_simple_intrinsic!(Fminf),
             Intrinsic::MinNumF64 => codegen_simple_intrinsic!(Fmin),
             Intrinsic::MulWithOverflow => {
                 self.codegen_op_with_overflow(BinaryOperator::OverflowResultMult, fargs, place, loc)
             }
+            Intrinsic::PowF16 => codegen_f16_intrinsic_in_f32!(Powf),
             Intrinsic::PowF32 => codegen_simple_intrinsic!(Powf),
             Intrinsic::PowF64 => codegen_simple_intrinsic!(Pow),
+            Intrinsic::PowIF16 => codegen_f16_intrinsic_in_f32!(Powif),
             Intrinsic::PowIF32 => codegen_simple_intrinsic!(Powif),
             Intrinsic::PowIF64 => codegen_simple_intrinsic!(Powi),
             Intrinsic::PtrGuaranteedCmp => self.codegen_ptr_guaranteed_cmp(fargs, place, loc),
@@ -407,13 +454,20 @@ macro_rules! unstable_codegen {
             Intrinsic::RetagBoxToRaw => self.codegen_retag_box_to_raw(fargs, place, loc),
             Intrinsic::RotateLeft => codegen_intrinsic_binop!(rol),
             Intrinsic::RotateRight => codegen_intrinsic_binop!(ror),
+            Intrinsic::RoundF16 | Intrinsic::RoundF128 => {
+                self.codegen_round_to_integral(cbmc::RoundingMode::ToAway, fargs, place, loc)
+            }
             Intrinsic::RoundF32 => codegen_simple_intrinsic!(Roundf),
             Intrinsic::RoundF64 => codegen_simple_intrinsic!(Round),
-            Intrinsic::RoundTiesEvenF32 | Intrinsic::RoundTiesEvenF64 => {
+            Intrinsic::RoundTiesEvenF16
+            | Intrinsic::RoundTiesEvenF32
+            | Intrinsic::RoundTiesEvenF64
+            | Intrinsic::RoundTiesEvenF128 => {
                 self.codegen_round_to_integral(cbmc::RoundingMode::ToNearest, fargs, place, loc)
             }
             Intrinsic::SaturatingAdd => codegen_intrinsic_binop_with_mm!(saturating_add),
             Intrinsic::SaturatingSub => codegen_intrinsic_binop_with_mm!(saturating_sub),
+            Intrinsic::SinF16 => codegen_f16_intrinsic_in_f32!(Sinf),
             Intrinsic::SinF32 => codegen_simple_intrinsic!(Sinf),
             Intrinsic::SinF64 => codegen_simple_intrinsic!(Sin),
             Intrinsic::SimdAdd => self.codegen_simd_op_with_overflow(
@@ -425,6 +479,12 @@ macro_rules! unstable_codegen {
                 loc,
             ),
             Intrinsic::SimdAnd => codegen_intrinsic_binop!(bitand),
+            Intrinsic::SimdBitmask => {
+                self.codegen_intrinsic_simd_bitmask(fargs, place, farg_types, ret_ty, span)
+            }
+            Intrinsic::SimdCast => {
+                self.codegen_intrinsic_simd_cast(fargs, place, farg_types, ret_ty, span)
+            }
             // TODO: `simd_rem` doesn't check for overflow cases for floating point operands.
             // <https://github.com/model-checking/kani/pull/2645>
             Intrinsic::SimdDiv | Intrinsic::SimdRem => {
@@ -463,12 +523,28 @@ macro_rules! unstable_codegen {
                 self.codegen_simd_cmp(Expr::vector_neq, fargs, place, span, farg_types, ret_ty)
             }
             Intrinsic::SimdOr => codegen_intrinsic_binop!(bitor),
+            Intrinsic::SimdSelect => {
+                self.codegen_intrinsic_simd_select(fargs, place, farg_types, span)
+            }
             Intrinsic::SimdShl | Intrinsic::SimdShr => {
                 self.codegen_simd_shift_with_distance_check(fargs, intrinsic_str, place, loc)
             }
             Intrinsic::SimdShuffle(stripped) => {
                 let n: u64 = self.simd_shuffle_length(stripped.as_str(), farg_types, span);
-                self.codegen_intrinsic_simd_shuffle(fargs, place, farg_types, ret_ty, n, span)
+                // [u32; n] or a SIMD vector of `u32`, translated wrapped in a struct
+                let indexes = fargs.remove(2);
+                let indexes = (0..n)
+                    .map(|i| {
+                        let idx = Expr::int_constant(i, Type::ssize_t());
+                        // Must not use `indexes.index(i)` directly, because codegen wraps arrays in struct
+                        self.codegen_idx_array(indexes.clone(), idx)
+                    })
+                    .collect();
+                self.codegen_intrinsic_simd_shuffle(fargs, indexes, place, farg_types, ret_ty, span)
+            }
+            Intrinsic::SimdShuffleConstGeneric => {
+                let indexes = self.simd_shuffle_const_generic_indexes(instance, ret_ty, loc);
+                self.codegen_intrinsic_simd_shuffle(fargs, indexes, place, farg_types, ret_ty, span)
             }
             Intrinsic::SimdSub => self.codegen_simd_op_with_overflow(
                 Expr::sub,
@@ -479,6 +555,9 @@ macro_rules! unstable_codegen {
                 loc,
             ),
             Intrinsic::SimdXor => codegen_intrinsic_binop!(bitxor),
+            // Rounding the square root twice, first to `f32` and then to `f16`, yields the correctly
+            // rounded result, since `f32` has more than twice the precision of `f16`.
+            Intrinsic::SqrtF16 => codegen_f16_intrinsic_in_f32!(Sqrtf),
             Intrinsic::SqrtF32 => codegen_simple_intrinsic!(Sqrtf),
             Intrinsic::SqrtF64 => codegen_simple_intrinsic!(Sqrt),
             Intrinsic::SubWithOverflow => self.codegen_op_with_overflow(
@@ -488,6 +567,9 @@ macro_rules! unstable_codegen {
                 loc,
             ),
             Intrinsic::Transmute => self.codegen_intrinsic_transmute(fargs, ret_ty, place, loc),
+            Intrinsic::TruncF16 | Intrinsic::TruncF128 => {
+                self.codegen_round_to_integral(cbmc::RoundingMode::TowardsZero, fargs, place, loc)
+            }
             Intrinsic::TruncF32 => codegen_simple_intrinsic!(Truncf),
             Intrinsic::TruncF64 => codegen_simple_intrinsic!(Trunc),
             Intrinsic::TypedSwap => self.codegen_swap(fargs, farg_types, loc),
@@ -633,6 +715,68 @@ fn codegen_round_to_integral(
         self.codegen_expr_to_place_stable(place, expr, loc)
     }
 
+    /// Reinterpret the floating-point `value` as an unsigned integer of the same width, and return
+    /// it along with the mask that selects its sign bit.
+    fn float_to_bits(&self, value: Expr) -> (Expr, Expr) {
+        let width = value.typ().sizeof_in_bits(&self.symbol_table);
+        let bits_typ = Type::unsigned_int(width);
+        let sign_mask = Expr::int_constant(1u128 << (width - 1), bits_typ.clone());
+        (value.transmute_to(bits_typ, &self.symbol_table), sign_mask)
+    }
+
+    /// `fabs` for the floating-point types that the C library doesn't support, which clears the
+    /// sign bit of the argument.
+    fn codegen_fabs(&mut self, mut fargs: Vec<Expr>, place: &Place, loc: Location) -> Stmt {
+        let value = fargs.remove(0);
+        let typ = value.typ().clone();
+        let (bits, sign_mask) = self.float_to_bits(value);
+        let expr = bits.bitand(sign_mask.bitnot()).transmute_to(typ, &self.symbol_table);
+        self.codegen_expr_to_place_stable(place, expr, loc)
+    }
+
+    /// `copysign` for the floating-point types that the C library doesn't support, which
+    /// combines the sign bit of the second argument with the other bits of the first one.
+    fn codegen_copysign(&mut self, mut fargs: Vec<Expr>, place: &Place, loc: Location) -> Stmt {
+        let magnitude = fargs.remove(0);
+        let sign = fargs.remove(0);
+        let typ = magnitude.typ().clone();
+        let (magnitude_bits, sign_mask) = self.float_to_bits(magnitude);
+        let (sign_bits, _) = self.float_to_bits(sign);
+        let expr = magnitude_bits
+            .bitand(sign_mask.clone().bitnot())
+            .bitor(sign_bits.bitand(sign_mask))
+            .transmute_to(typ, &self.symbol_table);
+        self.codegen_expr_to_place_stable(place, expr, loc)
+    }
+
+    /// `minnum` and `maxnum` for the floating-point types that the C library doesn't support.
+    /// Like `fmin` and `fmax`, they only return NaN if both arguments are NaN.
+    fn codegen_min_max_num(
+        &mut self,
+        is_max: bool,
+        mut fargs: Vec<Expr>,
+        place: &Place,
+        loc: Location,
+    ) -> Stmt {
+        let x = fargs.remove(0);
+        let y = fargs.remove(0);
+        let is_nan = |value: &Expr| value.clone().fneq(value.clone());
+        let pick_x = if is_max { x.clone().ge(y.clone()) } else { x.clone().le(y.clone()) };
+        let expr =
+            is_nan(&x).ternary(y.clone(), is_nan(&y).ternary(x.clone(), pick_x.ternary(x, y)));
+        self.codegen_expr_to_place_stable(place, expr, loc)
+    }
+
+    /// `fma` for `f16`, which the C library doesn't support. Computing it with `fmaf` would round
+    /// the result twice, first to `f32` and then to `f16`. Instead, we compute it in `f128`, where
+    /// the product and the sum of `f16` values are exact, so the result is only rounded once.
+    fn codegen_fma_f16(&mut self, fargs: Vec<Expr>, place: &Place, loc: Location) -> Stmt {
+        let mut wide = fargs.into_iter().map(|arg| arg.cast_to(Type::float128()));
+        let (x, y, z) = (wide.next().unwrap(), wide.next().unwrap(), wide.next().unwrap());
+        let expr = x.mul(y).plus(z).cast_to(Type::float16());
+        self.codegen_expr_to_place_stable(place, expr, loc)
+    }
+
     /// Intrinsics of the form *_with_overflow
     fn codegen_op_with_overflow(
         &mut self,
@@ -1119,27 +1263,31 @@ fn vtable_info(
     ///  1. `simd_shuffleN`, where `N` is a number which is part of the name
     ///     (e.g., `simd_shuffle4`).
     ///  2. `simd_shuffle`, where `N` isn't specified and must be computed from
-    ///     the length of the indexes SIMD vector (the third argument).
+    ///     the length of the indexes (the third argument), which is either a
+    ///     SIMD vector or an array of `u32`. Since the instance is
+    ///     monomorphized, the length may come from a generic `const N: usize`.
     fn simd_shuffle_length(&mut self, stripped: &str, farg_types: &[Ty], span: Span) -> u64 {
         let n = if stripped.is_empty() {
-            // Make sure that this is an SIMD vector, since only the
+            // Make sure that this is an SIMD vector or an array of `u32`, since only the
             // length-suffixed version of `simd_shuffle` (e.g.,
             // `simd_shuffle4`) is type-checked
-            if farg_types[2].kind().is_simd()
-                && matches!(
-                    self.simd_size_and_type(farg_types[2]).1.kind(),
-                    TyKind::RigidTy(RigidTy::Uint(UintTy::U32))
-                )
-            {
-                self.simd_size_and_type(farg_types[2]).0
-            } else {
-                let err_msg = format!(
-                    "simd_shuffle index must be a SIMD vector of `u32`, got `{}`",
-                    farg_types[2]
-                );
-                utils::span_err(self.tcx, span, err_msg);
-                // Return a dummy value
-                u64::MIN
+            let is_u32 = |ty: Ty| matches!(ty.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+            match farg_types[2].kind() {
+                TyKind::RigidTy(RigidTy::Array(elem_ty, len)) if is_u32(elem_ty) => {
+                    len.eval_target_usize().unwrap()
+                }
+                kind if kind.is_simd() && is_u32(self.simd_size_and_type(farg_types[2]).1) => {
+                    self.simd_size_and_type(farg_types[2]).0
+                }
+                _ => {
+                    let err_msg = format!(
+                        "simd_shuffle index must be a SIMD vector or an array of `u32`, got `{}`",
+                        farg_types[2]
+                    );
+                    utils::span_err(self.tcx, span, err_msg);
+                    // Return a dummy value
+                    u64::MIN
+                }
             }
         } else {
             stripped.parse().unwrap_or_else(|_| {
@@ -1156,6 +1304,30 @@ fn simd_shuffle_length(&mut self, stripped: &str, farg_types: &[Ty], span: Span)
         n
     }
 
+    /// Gets the indexes of a `simd_shuffle_const_generic` instance, which are
+    /// given by its last generic argument, `const IDX: &'static [u32]`, and
+    /// whose length is the length of the return type.
+    fn simd_shuffle_const_generic_indexes(
+        &mut self,
+        instance: Instance,
+        rust_ret_type: Ty,
+        loc: Location,
+    ) -> Vec<Expr> {
+        let args = instance_args(&instance);
+        let Some(GenericArgKind::Const(idx)) = args.0.last() else {
+            unreachable!(
+                "Expected the last generic argument of `{}` to be the indexes, but found: `{args:?}`",
+                instance.trimmed_name()
+            )
+        };
+        // `&'static [u32]` is translated as a fat pointer
+        let data = self.codegen_const_ty(idx, loc).member("data", &self.symbol_table);
+        let (n, _) = self.simd_size_and_type(rust_ret_type);
+        (0..n)
+            .map(|i| data.clone().plus(Expr::int_constant(i, Type::size_t())).dereference())
+            .collect()
+    }
+
     /// This function computes the size and alignment of a dynamically-sized type.
     /// The implementations follows closely the SSA implementation found in
     /// `rustc_codegen_ssa::glue::size_and_align_of_dst`.
@@ -1333,6 +1505,243 @@ fn codegen_intrinsic_simd_insert(
         )
     }
 
+    /// `simd_select(mask, if_true, if_false)` takes each lane from `if_true` if the lane of
+    /// `mask` is all ones, and from `if_false` if it is all zeros. Any other mask value is
+    /// undefined behavior, which we check.
+    ///
+    /// The type checker ensures that `if_true`, `if_false` and the result have the same type, but
+    /// we check that the mask is a vector of integers with as many lanes as the other arguments.
+    fn codegen_intrinsic_simd_select(
+        &mut self,
+        mut fargs: Vec<Expr>,
+        p: &Place,
+        rust_arg_types: &[Ty],
+        span: Span,
+    ) -> Stmt {
+        assert!(fargs.len() == 3, "`simd_select` had unexpected arguments {fargs:?}");
+        let mask = fargs.remove(0);
+        let if_true = fargs.remove(0);
+        let if_false = fargs.remove(0);
+
+        let (mask_len, mask_base_type) = self.simd_size_and_type(rust_arg_types[0]);
+        let (len, _) = self.simd_size_and_type(rust_arg_types[1]);
+        if mask_len != len {
+            let err_msg = format!(
+                "expected mask with length {len} (same as input type `{}`), found `{}` with length {mask_len}",
+                rust_arg_types[1], rust_arg_types[0]
+            );
+            utils::span_err(self.tcx, span, err_msg);
+        }
+        if !mask_base_type.kind().is_integral() {
+            let err_msg = format!(
+                "expected mask with integer elements, found `{}` with non-integer `{mask_base_type}`",
+                rust_arg_types[0]
+            );
+            utils::span_err(self.tcx, span, err_msg);
+        }
+        self.tcx.dcx().abort_if_errors();
+
+        let loc = self.codegen_span_stable(span);
+        let mask_check = self.codegen_simd_mask_check(&mask, len, "simd_select", loc);
+        let lanes = (0..len)
+            .map(|i| {
+                let index = Expr::int_constant(i, Type::ssize_t());
+                let set = Self::simd_mask_lane_is_set(&mask, i);
+                set.ternary(
+                    if_true.clone().index_array(index.clone()),
+                    if_false.clone().index_array(index),
+                )
+            })
+            .collect();
+        let res = Expr::vector_expr(if_true.typ().clone(), lanes);
+        Stmt::block(vec![mask_check, self.codegen_expr_to_place_stable(p, res, loc)], loc)
+    }
+
+    /// `simd_bitmask(mask)` packs the lanes of `mask`, which must be all zeros or all ones, into
+    /// one bit each. Lane `i` is the `i`-th least significant bit of the result, which is either
+    /// an unsigned integer with `N` bits, where `N` is the number of lanes rounded up to a power of
+    /// two (and at least 8), or an array of `ceil(N / 8)` bytes.
+    ///
+    /// We usually replace calls to this intrinsic by the model in the Kani library (see
+    /// [crate::kani_middle::intrinsics]), so this only handles the calls where the model isn't
+    /// available, e.g., when verifying the standard library.
+    fn codegen_intrinsic_simd_bitmask(
+        &mut self,
+        mut fargs: Vec<Expr>,
+        p: &Place,
+        rust_arg_types: &[Ty],
+        rust_ret_type: Ty,
+        span: Span,
+    ) -> Stmt {
+        assert!(fargs.len() == 1, "`simd_bitmask` had unexpected arguments {fargs:?}");
+        let mask = fargs.remove(0);
+
+        let (len, mask_base_type) = self.simd_size_and_type(rust_arg_types[0]);
+        if !mask_base_type.kind().is_integral() {
+            let err_msg = format!(
+                "expected mask with integer elements, found `{}` with non-integer `{mask_base_type}`",
+                rust_arg_types[0]
+            );
+            utils::span_err(self.tcx, span, err_msg);
+        }
+        let expected_int_bits = len.max(8).next_power_of_two();
+        let expected_bytes = len.div_ceil(8);
+        let ret_bits = match rust_ret_type.kind() {
+            TyKind::RigidTy(RigidTy::Uint(UintTy::U8)) => Some(8),
+            TyKind::RigidTy(RigidTy::Uint(UintTy::U16)) => Some(16),
+            TyKind::RigidTy(RigidTy::Uint(UintTy::U32)) => Some(32),
+            TyKind::RigidTy(RigidTy::Uint(UintTy::U64)) => Some(64),
+            TyKind::RigidTy(RigidTy::Uint(UintTy::U128)) => Some(128),
+            _ => None,
+        };
+        let is_byte_array = matches!(
+            rust_ret_type.kind(),
+            TyKind::RigidTy(RigidTy::Array(elem, n))
+                if matches!(elem.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U8)))
+                    && n.eval_target_usize().is_ok_and(|n| n == expected_bytes)
+        );
+        if ret_bits != Some(expected_int_bits) && !is_byte_array {
+            let err_msg = format!(
+                "cannot return `{rust_ret_type}` for the {len} lanes of `{}`, expected `u{expected_int_bits}` or `[u8; {expected_bytes}]`",
+                rust_arg_types[0]
+            );
+            utils::span_err(self.tcx, span, err_msg);
+        }
+        self.tcx.dcx().abort_if_errors();
+
+        let loc = self.codegen_span_stable(span);
+        let mask_check = self.codegen_simd_mask_check(&mask, len, "simd_bitmask", loc);
+        let ret_typ = self.codegen_ty_stable(rust_ret_type);
+        // The bits of the given lanes of `mask`, from the least significant bit of a value of
+        // type `typ`.
+        let pack = |lanes: std::ops::Range<u64>, typ: &Type| {
+            let first_lane = lanes.start;
+            lanes.fold(Expr::int_constant(0, typ.clone()), |packed, i| {
+                let bit = Expr::int_constant(1u128 << (i - first_lane), typ.clone());
+                let unset = Expr::int_constant(0, typ.clone());
+                packed.bitor(Self::simd_mask_lane_is_set(&mask, i).ternary(bit, unset))
+            })
+        };
+        let res = if is_byte_array {
+            let byte_typ = ret_typ.base_type().unwrap().clone();
+            let bytes = (0..expected_bytes)
+                .map(|byte| pack(byte * 8..len.min(byte * 8 + 8), &byte_typ))
+                .collect();
+            Expr::array_expr(ret_typ, bytes)
+        } else {
+            pack(0..len, &ret_typ)
+        };
+        Stmt::block(vec![mask_check, self.codegen_expr_to_place_stable(p, res, loc)], loc)
+    }
+
+    /// Whether the lane `i` of the SIMD `mask` is set, i.e., isn't zero.
+    fn simd_mask_lane_is_set(mask: &Expr, i: u64) -> Expr {
+        let lane = mask.clone().index_array(Expr::int_constant(i, Type::ssize_t()));
+        let zero = Expr::int_constant(0, lane.typ().clone());
+        lane.neq(zero)
+    }
+
+    /// Check that each of the `len` lanes of the SIMD `mask` is either all zeros or all ones.
+    fn codegen_simd_mask_check(
+        &self,
+        mask: &Expr,
+        len: u64,
+        intrinsic: &str,
+        loc: Location,
+    ) -> Stmt {
+        let elem_typ = mask.typ().base_type().unwrap().clone();
+        let zero = Expr::int_constant(0, elem_typ.clone());
+        let ones = zero.clone().bitnot();
+        let valid = (0..len).fold(Expr::bool_true(), |valid, i| {
+            let lane = mask.clone().index_array(Expr::int_constant(i, Type::ssize_t()));
+            valid.and(lane.clone().eq(zero.clone()).or(lane.eq(ones.clone())))
+        });
+        self.codegen_assert_assume(
+            valid,
+            PropertyClass::SafetyCheck,
+            format!("{intrinsic}: mask lanes must be all zeros or all ones").as_str(),
+            loc,
+        )
+    }
+
+    /// `simd_cast(vector)` casts each lane of `vector` to the element type of the result, with
+    /// the semantics of an `as` cast, except that casting a float that is not finite or whose
+    /// truncated value is out of range of the target integer type is undefined behavior, which we
+    /// check.
+    ///
+    /// We check that both vectors have the same length and numeric elements.
+    fn codegen_intrinsic_simd_cast(
+        &mut self,
+        mut fargs: Vec<Expr>,
+        p: &Place,
+        rust_arg_types: &[Ty],
+        rust_ret_type: Ty,
+        span: Span,
+    ) -> Stmt {
+        assert!(fargs.len() == 1, "`simd_cast` had unexpected arguments {fargs:?}");
+        let vector = fargs.remove(0);
+
+        let (len, in_base_type) = self.simd_size_and_type(rust_arg_types[0]);
+        let (ret_len, out_base_type) = self.simd_size_and_type(rust_ret_type);
+        if len != ret_len {
+            let err_msg = format!(
+                "expected return type with length {len} (same as input type `{}`), found `{rust_ret_type}` with length {ret_len}",
+                rust_arg_types[0]
+            );
+            utils::span_err(self.tcx, span, err_msg);
+        }
+        for (simd_ty, base_ty) in
+            [(rust_arg_types[0], in_base_type), (rust_ret_type, out_base_type)]
+        {
+            if !base_ty.kind().is_integral() && !base_ty.kind().is_float() {
+                let err_msg = format!(
+                    "expected a vector of integers or floats, found `{simd_ty}` with elements of type `{base_ty}`"
+                );
+                utils::span_err(self.tcx, span, err_msg);
+            }
+        }
+        self.tcx.dcx().abort_if_errors();
+
+        let loc = self.codegen_span_stable(span);
+        let ret_typ = self.codegen_ty_stable(rust_ret_type);
+        let out_elem_typ = ret_typ.base_type().unwrap().clone();
+        let lanes: Vec<Expr> = (0..len)
+            .map(|i| vector.clone().index_array(Expr::int_constant(i, Type::ssize_t())))
+            .collect();
+
+        let mut stmts = vec![];
+        if let (TyKind::RigidTy(RigidTy::Float(float_ty)), TyKind::RigidTy(int_ty)) =
+            (in_base_type.kind(), out_base_type.kind())
+            && out_base_type.kind().is_integral()
+        {
+            let mm = self.symbol_table.machine_model();
+            let finite =
+                lanes.iter().fold(Expr::bool_true(), |all, lane| all.and(lane.clone().is_finite()));
+            let in_range = lanes.iter().fold(Expr::bool_true(), |all, lane| {
+                all.and(utils::codegen_in_range_expr(lane, float_ty, int_ty.clone(), mm))
+            });
+            stmts.push(self.codegen_assert_assume(
+                finite,
+                PropertyClass::ArithmeticOverflow,
+                "simd_cast: attempt to convert a non-finite value to an integer",
+                loc,
+            ));
+            stmts.push(self.codegen_assert_assume(
+                in_range,
+                PropertyClass::ArithmeticOverflow,
+                "simd_cast: attempt to convert a value out of range of the target integer",
+                loc,
+            ));
+        }
+
+        let res = Expr::vector_expr(
+            ret_typ,
+            lanes.into_iter().map(|lane| lane.cast_to(out_elem_typ.clone())).collect(),
+        );
+        stmts.push(self.codegen_expr_to_place_stable(p, res, loc));
+        Stmt::block(stmts, loc)
+    }
+
     /// Generates code for a SIMD vector comparison intrinsic.
     ///
     /// We perform some typechecks here for two reasons:
@@ -1545,9 +1954,13 @@ fn codegen_simd_shift_with_distance_check(
     /// vectors, choosing values according to an input array of indexes. See
     /// https://doc.rust-lang.org/std/intrinsics/simd/fn.simd_shuffle.html
     ///
+    /// The indexes are given as one expression per element of the result, since
+    /// `simd_shuffle` takes them as an argument while `simd_shuffle_const_generic`
+    /// takes them as a generic argument. They don't need to be constant: CBMC
+    /// selects the element of either vector for each index.
+    ///
     /// We check that:
-    ///  1. The return type length is equal to the expected length (`n`) of the
-    ///     `simd_shuffle` operation.
+    ///  1. The return type length is equal to the number of indexes.
     ///  2. The return type's subtype is equal to the vector's subtype (i.e.,
     ///     the 1st argument). Both input vectors are guaranteed to be of the
     ///     same type when they get here due to the `simd_shuffle` definition.
@@ -1561,17 +1974,16 @@ fn codegen_simd_shift_with_distance_check(
     fn codegen_intrinsic_simd_shuffle(
         &mut self,
         mut fargs: Vec<Expr>,
+        indexes: Vec<Expr>,
         p: &Place,
         rust_arg_types: &[Ty],
         rust_ret_type: Ty,
-        n: u64,
         span: Span,
     ) -> Stmt {
         // vector, size n: translated as vector types which cbmc treats as arrays
         let vec1 = fargs.remove(0);
         let vec2 = fargs.remove(0);
-        // [u32; n]: translated wrapped in a struct
-        let indexes = fargs.remove(0);
+        let n = indexes.len() as u64;
 
         let (_, vec_subtype) = self.simd_size_and_type(rust_arg_types[0]);
         let (ret_type_len, ret_type_subtype) = self.simd_size_and_type(rust_ret_type);
@@ -1594,13 +2006,7 @@ fn codegen_intrinsic_simd_shuffle(
         // Issue: https://github.com/diffblue/cbmc/issues/6298
         let st_rep = Type::ssize_t();
 
-        let elems = (0..n)
-            .map(|i| {
-                let idx = Expr::int_constant(i, st_rep.clone());
-                // Must not use `indexes.index(i)` directly, because codegen wraps arrays in struct
-                self.codegen_idx_array(indexes.clone(), idx).cast_to(st_rep.clone())
-            })
-            .collect();
+        let elems = indexes.into_iter().map(|idx| idx.cast_to(st_rep.clone())).collect();
         self.tcx.dcx().abort_if_errors();
         let cbmc_ret_ty = self.codegen_ty_stable(rust_ret_type);
         let loc = self.codegen_span_stable(span);
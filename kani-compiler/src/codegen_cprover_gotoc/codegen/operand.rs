@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use crate::codegen_cprover_gotoc::GotocCtx;
 use crate::codegen_cprover_gotoc::utils::slice_fat_ptr;
-use crate::kani_middle::is_anon_static;
+use crate::kani_middle::{is_anon_static, is_interior_mut};
 use crate::unwrap_or_return_codegen_unimplemented;
 use cbmc::goto_program::{DatatypeComponent, Expr, ExprValue, Location, Symbol, Type};
 use rustc_middle::ty::Const as ConstInternal;
@@ -335,17 +335,26 @@ fn codegen_const_ptr(
                         &self.symbol_table,
                     )
                 }
-                // TODO: Improve this check after we upgrade nightly to 2023-12-18.
                 TyKind::RigidTy(RigidTy::Adt(def, _)) if def.name().ends_with("::CStr") => {
-                    // TODO: Handle CString
-                    // <https://github.com/model-checking/kani/issues/2549>
-                    let typ = self.codegen_ty_stable(ty);
-                    let operation_name = "C string literal";
-                    self.codegen_unimplemented_expr(
-                        operation_name,
-                        typ,
-                        loc,
-                        "https://github.com/model-checking/kani/issues/2549",
+                    // A C string literal, e.g., `c"foo"`. `CStr` wraps a `[c_char]` that includes
+                    // the nul terminator, so its metadata is the number of bytes.
+                    assert_eq!(
+                        alloc.provenance.ptrs.len(),
+                        1,
+                        "Expected `&CStr` to point to a single buffer"
+                    );
+                    let alloc_id = alloc.provenance.ptrs[0].1.0;
+                    let GlobalAlloc::Memory(data) = GlobalAlloc::from(alloc_id) else {
+                        unreachable!()
+                    };
+                    let mem_var = self.codegen_const_allocation(&data, None, loc, false);
+                    let data_expr = mem_var.cast_to(self.codegen_ty_stable(inner_ty).to_pointer());
+                    let len_expr = Expr::int_constant(data.bytes.len(), Type::size_t());
+                    slice_fat_ptr(
+                        self.codegen_ty_stable(ty),
+                        data_expr,
+                        len_expr,
+                        &self.symbol_table,
                     )
                 }
                 _ => unreachable!("{inner_ty:?}"),
@@ -384,7 +393,7 @@ fn codegen_alloc_pointer(
                 if is_anon_static(self.tcx, def.def_id()) {
                     let alloc = def.eval_initializer().unwrap();
                     let name = format!("{}::{alloc_id:?}", self.full_crate_name());
-                    self.codegen_nested_static_allocation(&alloc, Some(name), loc)
+                    self.codegen_nested_static_allocation(def, &alloc, name, loc)
                 } else {
                     self.codegen_static_pointer(def)
                 }
@@ -477,10 +486,13 @@ fn codegen_instance_pointer(&mut self, instance: Instance, is_thread_local: bool
     /// Generate an expression that represents the address for a constant allocation.
     ///
     /// This function will only allocate a new memory location if necessary. The standard does
-    /// not offer any guarantees over the location of a constant.
+    /// not offer any guarantees over the location of a constant, so immutable allocations with
+    /// the same content share the same memory location. Mutable allocations, such as `&mut []`
+    /// promoted constants, get their own memory location.
     ///
     /// These constants can be named constants which are declared by the user, or constant values
-    /// used scattered throughout the source
+    /// used scattered throughout the source, including inline `const` blocks and promoted
+    /// temporaries.
     fn codegen_const_allocation(
         &mut self,
         alloc: &Allocation,
@@ -489,17 +501,23 @@ fn codegen_const_allocation(
         is_definitely_const: bool,
     ) -> Expr {
         debug!(?name, ?alloc, "codegen_const_allocation");
-        let alloc_name = match self.alloc_map.get(alloc) {
+        let is_shareable = alloc.mutability == Mutability::Not;
+        let alloc_name = match self.alloc_map.get(alloc).filter(|_| is_shareable) {
             None => {
                 let alloc_name = if let Some(name) = name { name } else { self.next_global_name() };
-                let has_interior_mutabity = false; // Constants cannot be mutated.
-                self.codegen_alloc_in_memory(
-                    alloc.clone(),
-                    alloc_name.clone(),
-                    loc,
-                    has_interior_mutabity,
-                    is_definitely_const,
-                );
+                if self.symbol_table.lookup(&alloc_name).is_none() {
+                    let has_interior_mutabity = false; // Constants cannot be mutated.
+                    self.codegen_alloc_in_memory(
+                        alloc.clone(),
+                        alloc_name.clone(),
+                        loc,
+                        has_interior_mutabity,
+                        is_definitely_const,
+                    );
+                }
+                if is_shareable {
+                    self.alloc_map.insert(alloc.clone(), alloc_name.clone());
+                }
                 alloc_name
             }
             Some(name) => name.clone(),
@@ -510,16 +528,31 @@ fn codegen_const_allocation(
     }
 
     /// Generate an expression that represents the address of a nested static allocation.
+    ///
+    /// Just like statics, nested static allocations have their own memory location, which may be
+    /// mutated through interior mutability, so they are never shared with constant allocations
+    /// that happen to have the same content.
     fn codegen_nested_static_allocation(
         &mut self,
+        def: StaticDef,
         alloc: &Allocation,
-        name: Option<String>,
+        name: String,
         loc: Location,
     ) -> Expr {
-        // The memory behind this allocation isn't constant, but codegen_alloc_in_memory (which codegen_const_allocation calls)
-        // uses alloc's mutability field to set the const-ness of the allocation in CBMC's symbol table,
-        // so we can reuse the code and without worrying that the allocation is set as immutable.
-        self.codegen_const_allocation(alloc, name, loc, false)
+        if self.symbol_table.lookup(&name).is_none() {
+            // The memory behind this allocation isn't constant, codegen_alloc_in_memory uses the
+            // alloc's mutability field and the type to set the const-ness of the allocation in
+            // CBMC's symbol table.
+            let has_interior_mutability = is_interior_mut(self.tcx, def.ty());
+            self.codegen_alloc_in_memory(
+                alloc.clone(),
+                name.clone(),
+                loc,
+                has_interior_mutability,
+                false,
+            );
+        }
+        self.symbol_table.lookup(name).unwrap().to_expr().address_of()
     }
 
     /// Insert an allocation into the goto symbol table, and generate an init value.
@@ -591,8 +624,6 @@ pub fn codegen_alloc_in_memory(
             loc,
             init_fn,
         );
-
-        self.alloc_map.insert(alloc, name);
     }
 
     /// This is an internal helper function for `codegen_alloc_in_memory`.
@@ -689,7 +689,9 @@ fn codegen_rvalue_aggregate(
                         let meta = self.codegen_operand_stable(&operands[1]);
                         slice_fat_ptr(typ, data_cast, meta, &self.symbol_table)
                     }
-                    TyKind::RigidTy(RigidTy::Adt(..)) => {
+                    // Custom DSTs, e.g., `struct Header<T: ?Sized> { len: usize, data: T }`, as well
+                    // as tuples, whose last field may be unsized.
+                    TyKind::RigidTy(RigidTy::Adt(..) | RigidTy::Tuple(..)) => {
                         let layout = LayoutOf::new(pointee_ty);
                         let pointee_goto_typ = self.codegen_ty_stable(pointee_ty);
                         let data_cast =
@@ -807,7 +809,8 @@ pub fn codegen_rvalue_stable(&mut self, rv: &Rvalue, loc: Location) -> Expr {
                                 PropertyClass::SafetyCheck,
                                 &format!(
                                     "Cannot transmute between types of different sizes. \
-                                Transmuting from `{src_sz}` to `{dst_sz}` bytes"
+                                Transmuting from `{src_ty}` (`{src_sz}` bytes) to `{ty}` \
+                                (`{dst_sz}` bytes)"
                                 ),
                                 loc,
                             ),
@@ -867,6 +870,14 @@ pub fn codegen_rvalue_stable(&mut self, rv: &Rvalue, loc: Location) -> Expr {
                     }
                 }
                 UnOp::Neg => self.codegen_operand_stable(e).neg(),
+                // The metadata of a pointer to a sized type, or to an extern type, is `()`.
+                UnOp::PtrMetadata
+                    if self.use_thin_pointer_stable(
+                        pointee_type_stable(self.operand_ty_stable(e)).unwrap(),
+                    ) =>
+                {
+                    Expr::init_unit(self.codegen_ty_stable(res_ty), &self.symbol_table)
+                }
                 UnOp::PtrMetadata => {
                     let src_goto_expr = self.codegen_operand_stable(e);
                     let dst_goto_typ = self.codegen_ty_stable(res_ty);
@@ -1,13 +1,16 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
+use super::ty_stable::pointee_type_stable;
 use super::typ::FN_RETURN_VOID_VAR_NAME;
 use super::typ::TypeExt;
 use super::{PropertyClass, bb_label};
 use crate::codegen_cprover_gotoc::codegen::function::rustc_public_bridge::region_from_coverage_opaque;
 use crate::codegen_cprover_gotoc::{GotocCtx, VtableCtx};
+use crate::kani_middle::attributes::is_proof_harness;
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
 use cbmc::goto_program::ExprValue;
 use cbmc::goto_program::{Expr, Location, Stmt, Type};
+use kani_metadata::CheckCategory;
 use rustc_abi::Size;
 use rustc_abi::{FieldsShape, Primitive, TagEncoding, Variants};
 use rustc_middle::ty::layout::LayoutOf;
@@ -24,35 +27,46 @@
 use tracing::{debug, debug_span, trace};
 
 impl GotocCtx<'_> {
+    /// Generate the assigns target of a loop or function contract for the pointer `expr` of
+    /// type `ty`.
+    ///
+    /// A pointer to a slice or a `str` only makes the bytes of the slice assignable using
+    /// `__CPROVER_object_upto`, so a target like `&buf[idx..idx + len]` does not make the rest of
+    /// `buf` assignable. Any other fat pointer, i.e. a trait object or a pointer to a struct with
+    /// an unsized tail, makes the whole object it points to assignable using
+    /// `__CPROVER_object_whole`. Any other pointer makes the value it points to assignable, which
+    /// may be a (nested) field of a struct, e.g. `&s.header.len`.
     pub fn ty_to_assign_target(&self, ty: Ty, expr: &Expr) -> Expr {
-        match ty.kind() {
-            TyKind::RigidTy(RigidTy::Ref(_, unref_ty, _))
-            | TyKind::RigidTy(RigidTy::RawPtr(unref_ty, _)) => match unref_ty.kind() {
-                TyKind::RigidTy(RigidTy::Slice(slice_ty)) => {
-                    let size = slice_ty.layout().unwrap().shape().size.bytes();
-                    Expr::symbol_expression(
-                        "__CPROVER_object_upto",
-                        Type::code(
-                            vec![
-                                Type::empty().to_pointer().as_parameter(None, Some("ptr".into())),
-                                Type::size_t().as_parameter(None, Some("size".into())),
-                            ],
-                            Type::empty(),
-                        ),
-                    )
-                    .call(vec![
-                        expr.clone()
-                            .member("data", &self.symbol_table)
-                            .cast_to(Type::empty().to_pointer()),
-                        expr.clone()
-                            .member("len", &self.symbol_table)
-                            .mul(Expr::size_constant(size.try_into().unwrap(), &self.symbol_table)),
-                    ])
-                }
-                _ => expr.clone().dereference(),
-            },
-            _ => expr.clone().dereference(),
-        }
+        let data =
+            || expr.clone().member("data", &self.symbol_table).cast_to(Type::empty().to_pointer());
+        let ptr_param = || Type::empty().to_pointer().as_parameter(None, Some("ptr".into()));
+        let elem_size = match pointee_type_stable(ty).map(|pointee| pointee.kind()) {
+            Some(TyKind::RigidTy(RigidTy::Slice(elem_ty))) => {
+                elem_ty.layout().unwrap().shape().size.bytes()
+            }
+            Some(TyKind::RigidTy(RigidTy::Str)) => 1,
+            Some(_) if self.is_fat_pointer_stable(ty) => {
+                return Expr::symbol_expression(
+                    "__CPROVER_object_whole",
+                    Type::code(vec![ptr_param()], Type::empty()),
+                )
+                .call(vec![data()]);
+            }
+            _ => return expr.clone().dereference(),
+        };
+        Expr::symbol_expression(
+            "__CPROVER_object_upto",
+            Type::code(
+                vec![ptr_param(), Type::size_t().as_parameter(None, Some("size".into()))],
+                Type::empty(),
+            ),
+        )
+        .call(vec![
+            data(),
+            expr.clone()
+                .member("len", &self.symbol_table)
+                .mul(Expr::size_constant(elem_size.try_into().unwrap(), &self.symbol_table)),
+        ])
     }
 
     pub fn rvalue_to_assign_targets(&mut self, rvalue: &Rvalue, location: Location) -> Vec<Expr> {
@@ -242,8 +256,11 @@ pub fn codegen_statement(&mut self, stmt: &Statement) -> Stmt {
                     Stmt::skip(location)
                 }
             }
-            StatementKind::PlaceMention(_) => todo!(),
-            StatementKind::FakeRead(..)
+            // A place mention, e.g., the scrutinee of `let _ = expr;`, only exists in runtime MIR
+            // with `-Z mir-keep-place-mention`. It doesn't read the place, so, as in rustc's own
+            // codegen, there is nothing to do.
+            StatementKind::PlaceMention(_)
+            | StatementKind::FakeRead(..)
             | StatementKind::Retag(_, _)
             | StatementKind::AscribeUserType { .. }
             | StatementKind::Nop
@@ -282,8 +299,9 @@ pub fn codegen_terminator(&mut self, term: &Terminator) -> Stmt {
                 "https://github.com/model-checking/kani/issues/692",
             ),
             TerminatorKind::Return => {
-                let rty = self.current_fn().instance_stable().fn_abi().unwrap().ret.ty;
-                if rty.kind().is_unit() {
+                let instance = self.current_fn().instance_stable();
+                let rty = instance.fn_abi().unwrap().ret.ty;
+                let ret = if rty.kind().is_unit() {
                     self.codegen_ret_unit(loc)
                 } else {
                     let place = Place::from(RETURN_LOCAL);
@@ -298,6 +316,11 @@ pub fn codegen_terminator(&mut self, term: &Terminator) -> Stmt {
                     } else {
                         place_expr.ret(loc)
                     }
+                };
+                if self.queries.args().check_vacuity && is_proof_harness(self.tcx, instance) {
+                    Stmt::block(vec![self.codegen_vacuity_check(loc), ret], loc)
+                } else {
+                    ret
                 }
             }
             TerminatorKind::Unreachable => self.codegen_assert_assume_false(
@@ -317,6 +340,20 @@ pub fn codegen_terminator(&mut self, term: &Terminator) -> Stmt {
                     if *expected { r } else { Expr::not(r) }
                 };
 
+                // Rust's overflow and bounds checks can be turned off, e.g., with
+                // `#[kani::proof(checks(overflow = false))]`, in which case the execution
+                // continues with the wrapped result or the out-of-bounds index.
+                let category = match msg {
+                    AssertMessage::Overflow { .. } | AssertMessage::OverflowNeg { .. } => {
+                        Some(CheckCategory::Overflow)
+                    }
+                    AssertMessage::BoundsCheck { .. } => Some(CheckCategory::Bounds),
+                    _ => None,
+                };
+                if category.is_some_and(|category| self.disabled_checks.contains(&category)) {
+                    return Stmt::goto(bb_label(*target), loc);
+                }
+
                 // Generate the message to print to the user and property class.
                 // For `msg`s with runtime values, replace them with static messages,
                 // since that's all that CBMC accepts.
@@ -764,6 +801,7 @@ fn codegen_funcall(
                         // (cf. the function documentation)
                         let func_exp = self.codegen_func_expr(instance, loc);
                         if instance.is_foreign_item() {
+                            self.record_foreign_call(instance);
                             vec![self.codegen_foreign_call(func_exp, fargs, destination, loc)]
                         } else {
                             vec![self.codegen_expr_to_place_stable(
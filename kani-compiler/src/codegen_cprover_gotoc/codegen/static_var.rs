@@ -4,9 +4,14 @@
 //! This file contains functions related to codegenning MIR static variables into gotoc
 
 use crate::codegen_cprover_gotoc::GotocCtx;
-use crate::kani_middle::is_interior_mut;
+use crate::kani_middle::transform::check_values::ty_validity_per_offset;
+use crate::kani_middle::{is_assigned_by_global_asm, is_interior_mut};
+use cbmc::goto_program::Expr;
 use rustc_public::CrateDef;
 use rustc_public::mir::mono::{Instance, StaticDef};
+use rustc_public::rustc_internal;
+use rustc_public::target::MachineInfo;
+use rustc_public::ty::{Allocation, ProvenanceMap, RigidTy, Ty, TyKind};
 use tracing::debug;
 
 impl GotocCtx<'_> {
@@ -17,7 +22,18 @@ impl GotocCtx<'_> {
     /// Source: <https://rust-lang.github.io/rfcs/0246-const-vs-static.html>
     pub fn codegen_static(&mut self, def: StaticDef) {
         debug!("codegen_static");
-        let alloc = def.eval_initializer().unwrap();
+        if is_assigned_by_global_asm(self.tcx, rustc_internal::internal(self.tcx, def.def_id())) {
+            // The global assembly may have assigned any value to the static.
+            let symbol_name = Instance::from(def).mangled_name();
+            let typ = self.codegen_ty_stable(def.ty());
+            let location = self.codegen_span_stable(def.span());
+            self.ensure_global_var_init(symbol_name, false, false, typ, location, |_, var| {
+                Expr::nondet(var.typ)
+            });
+            return;
+        }
+        let initializer = def.eval_initializer().unwrap();
+        let alloc = self.static_snapshot(def, &initializer).unwrap_or(initializer);
         let symbol_name = Instance::from(def).mangled_name();
         self.codegen_alloc_in_memory(
             alloc,
@@ -28,6 +44,62 @@ pub fn codegen_static(&mut self, def: StaticDef) {
         );
     }
 
+    /// The initial value of the static from the file given by `--snapshot <STATIC>=<FILE>`, if
+    /// any. The path of the static must start with the name of its crate.
+    ///
+    /// The file must have the size of the static and hold a valid value of its type, which cannot
+    /// contain pointers, since the snapshot doesn't tell which allocation they point to.
+    fn static_snapshot(&self, def: StaticDef, initializer: &Allocation) -> Option<Allocation> {
+        let name = def.name();
+        let path = self.queries.args().snapshot.iter().find_map(|snapshot| {
+            let (target, path) = snapshot.split_once('=')?;
+            (target == name).then_some(path)
+        })?;
+        let span = rustc_internal::internal(self.tcx, def.span());
+        let bytes = std::fs::read(path).unwrap_or_else(|err| {
+            self.tcx.dcx().span_fatal(
+                span,
+                format!("failed to read the snapshot of static `{name}` from `{path}`: {err}"),
+            )
+        });
+        let size = def.ty().layout().unwrap().shape().size.bytes();
+        if bytes.len() != size {
+            self.tcx.dcx().span_fatal(
+                span,
+                format!(
+                    "the snapshot `{path}` has {} bytes, but static `{name}` has {size} bytes",
+                    bytes.len()
+                ),
+            )
+        }
+        if may_contain_pointer(def.ty()) {
+            self.tcx.dcx().span_fatal(
+                span,
+                format!("static `{name}` cannot be initialized by a snapshot: its type contains pointers"),
+            )
+        }
+        let requirements = ty_validity_per_offset(&MachineInfo::target(), def.ty(), 0)
+            .unwrap_or_else(|msg| {
+                self.tcx.dcx().span_fatal(
+                    span,
+                    format!("static `{name}` cannot be initialized by a snapshot: {msg}"),
+                )
+            });
+        if !requirements.iter().all(|req| req.is_satisfied_by(&bytes)) {
+            self.tcx.dcx().span_fatal(
+                span,
+                format!("the snapshot `{path}` doesn't hold a valid value of static `{name}`"),
+            )
+        }
+        debug!(?name, ?path, "static_snapshot");
+        Some(Allocation {
+            bytes: bytes.into_iter().map(Some).collect(),
+            provenance: ProvenanceMap { ptrs: vec![] },
+            align: initializer.align,
+            mutability: initializer.mutability,
+        })
+    }
+
     /// Mutates the Goto-C symbol table to add a forward-declaration of the static variable.
     pub fn declare_static(&mut self, def: StaticDef) {
         let instance = Instance::from(def);
@@ -48,3 +120,19 @@ pub fn declare_static(&mut self, def: StaticDef) {
             .set_pretty_name(pretty_name);
     }
 }
+
+/// Whether a value of type `ty` may contain a pointer, i.e., anything but scalars and aggregates
+/// of scalars.
+fn may_contain_pointer(ty: Ty) -> bool {
+    match ty.kind() {
+        TyKind::RigidTy(
+            RigidTy::Bool | RigidTy::Char | RigidTy::Int(_) | RigidTy::Uint(_) | RigidTy::Float(_),
+        ) => false,
+        TyKind::RigidTy(RigidTy::Array(elem, _)) => may_contain_pointer(elem),
+        TyKind::RigidTy(RigidTy::Tuple(tys)) => tys.into_iter().any(may_contain_pointer),
+        TyKind::RigidTy(RigidTy::Adt(def, args)) => def.variants_iter().any(|variant| {
+            variant.fields().iter().any(|field| may_contain_pointer(field.ty_with_args(&args)))
+        }),
+        _ => true,
+    }
+}
@@ -528,8 +528,42 @@ pub fn enum_case_struct_name(&self, ty: Ty<'tcx>, case: &VariantDef) -> String {
         format!("{}::{}", self.ty_mangled_name(ty), case.name)
     }
 
+    /// Record the largest array that a local of type `typ` of the current function holds, whether
+    /// in its fields or, if it is a pointer, in its pointee, e.g., for `&[u8; 4096]`.
+    pub fn record_largest_array(&mut self, typ: &Type) {
+        fn largest_array(typ: &Type, st: &SymbolTable) -> u64 {
+            match typ.unwrap_typedef() {
+                Type::Array { typ, size } => (*size).max(largest_array(typ, st)),
+                typ => {
+                    typ.components().or_else(|| typ.lookup_components(st)).map_or(0, |components| {
+                        components
+                            .iter()
+                            .map(|component| largest_array(&component.typ(), st))
+                            .max()
+                            .unwrap_or(0)
+                    })
+                }
+            }
+        }
+        let typ: &Type = match typ.unwrap_typedef() {
+            Type::Pointer { typ } => typ,
+            typ => typ,
+        };
+        let len = largest_array(typ, &self.symbol_table);
+        if len > 0 {
+            let name = self.current_fn().readable_name().into();
+            let largest = self.largest_arrays.entry(name).or_default();
+            *largest = (*largest).max(len);
+        }
+    }
+
     fn codegen_ty_raw_array(&mut self, elem_ty: Ty<'tcx>, len: Const<'tcx>) -> Type {
-        let size = self.codegen_const_internal(len, None).int_constant_value().unwrap();
+        let size: u64 = self
+            .codegen_const_internal(len, None)
+            .int_constant_value()
+            .unwrap()
+            .try_into()
+            .unwrap();
         let elemt = self.codegen_ty(elem_ty);
         elemt.array_of(size)
     }
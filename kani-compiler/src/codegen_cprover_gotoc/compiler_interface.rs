@@ -7,19 +7,24 @@
 use crate::codegen_cprover_gotoc::context::MinimalGotocCtx;
 use crate::codegen_cprover_gotoc::utils::file_writing_pool::{FileDataToWrite, ThreadPool};
 use crate::codegen_cprover_gotoc::{GotocCtx, context};
+use crate::kani_middle::SourceLocation;
 use crate::kani_middle::analysis;
-use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::attributes::{KaniAttributes, is_proof_harness};
 use crate::kani_middle::check_reachable_items;
 use crate::kani_middle::codegen_units::{CodegenUnit, CodegenUnits};
+use crate::kani_middle::metadata::shared_model_file;
 use crate::kani_middle::provide;
 use crate::kani_middle::reachability::{collect_reachable_items, filter_crate_items};
-use crate::kani_middle::transform::{BodyTransformation, GlobalPasses};
+use crate::kani_middle::transform::{BodyTransformation, GlobalPasses, write_mir};
 use crate::kani_queries::QueryDb;
 use cbmc::goto_program::Location;
 use cbmc::{InternedString, MachineModel};
 use cbmc::{RoundingMode, WithInterner};
 use kani_metadata::artifact::convert_type;
-use kani_metadata::{ArtifactType, HarnessMetadata, KaniMetadata, UnsupportedFeature};
+use kani_metadata::{
+    ArtifactType, ForeignFunction, HarnessMetadata, KaniMetadata, ReachableFunction,
+    UnsafeOperation, UnsupportedFeature,
+};
 use kani_metadata::{AssignsContract, CompilerArtifactStub};
 use rustc_abi::{Align, Endian};
 use rustc_codegen_ssa::back::archive::{
@@ -28,7 +33,7 @@
 use rustc_codegen_ssa::back::link::link_binary;
 use rustc_codegen_ssa::traits::CodegenBackend;
 use rustc_codegen_ssa::{CodegenResults, CrateInfo, TargetConfig};
-use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_errors::DEFAULT_LOCALE_RESOURCE;
 use rustc_hir::def_id::{DefId as InternalDefId, LOCAL_CRATE};
 use rustc_metadata::EncodedMetadata;
@@ -48,7 +53,7 @@
 use std::cmp::min;
 use std::collections::BTreeMap;
 use std::fmt::Write;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::BufWriter;
 use std::num::NonZero;
 use std::path::Path;
@@ -81,7 +86,8 @@ pub fn new(queries: Arc<Mutex<QueryDb>>) -> Self {
 
     /// Generate code that is reachable from the given starting points.
     ///
-    /// Invariant: iff `check_contract.is_some()` then `return.2.is_some()`
+    /// Invariant: iff `check_contract.is_some()` then `return.2.is_some()`, unless contracts are
+    /// handed over to CBMC, in which case they are stored in the returned context instead.
     #[allow(clippy::too_many_arguments)]
     fn codegen_items<'tcx>(
         &self,
@@ -137,11 +143,40 @@ fn codegen_items<'tcx>(
             );
         }
 
+        // Persist the MIR that is verified, so users can inspect it with `--emit mir`.
+        if self.queries.lock().unwrap().args().emit_mir {
+            let instances: Vec<_> = items
+                .iter()
+                .filter_map(|item| match item {
+                    MonoItem::Fn(instance) if instance.has_body() => Some(*instance),
+                    _ => None,
+                })
+                .collect();
+            let mir_path = convert_type(symtab_goto, ArtifactType::SymTabGoto, ArtifactType::Mir);
+            write_mir(tcx, &mir_path, &instances, &mut transformer);
+        }
+
         // Follow rustc naming convention (cx is abbrev for context).
         // https://rustc-dev-guide.rust-lang.org/conventions.html#naming-conventions
         let mut gcx =
             GotocCtx::new(tcx, (*self.queries.lock().unwrap()).clone(), machine_model, transformer);
         check_reachable_items(gcx.tcx, &gcx.queries, &items);
+        gcx.unsafe_operations = global_passes.take_unsafe_operations();
+        gcx.devirtualized_calls = global_passes.take_devirtualized_calls();
+        // The harness may turn categories of checks on or off for itself.
+        if let [MonoItem::Fn(harness)] = starting_items
+            && is_proof_harness(tcx, *harness)
+        {
+            let attributes = KaniAttributes::for_instance(tcx, *harness);
+            let harness_attributes = attributes.harness_attributes();
+            let checks = harness_attributes.checks;
+            gcx.disabled_checks.retain(|category| !checks.enable.contains(category));
+            gcx.disabled_checks.extend(checks.disable);
+            if !harness_attributes.loop_unwind.is_empty() {
+                gcx.loop_labels = attributes.loop_labels();
+            }
+        }
+        gcx.declare_panic_expectation(&items);
 
         let contract_info = with_timer(
             || {
@@ -193,7 +228,12 @@ fn codegen_items<'tcx>(
                     }
                 }
 
-                check_contract.map(|check_id| gcx.handle_check_contract(check_id, &items))
+                if gcx.queries.args().cbmc_contracts {
+                    gcx.handle_cbmc_contracts(starting_items, check_contract, &items);
+                    None
+                } else {
+                    check_contract.map(|check_id| gcx.handle_check_contract(check_id, &items))
+                }
             },
             "codegen",
         );
@@ -388,6 +428,16 @@ fn codegen_crate(&self, tcx: TyCtxt) -> Box<dyn Any> {
                     let mut units = CodegenUnits::new(&queries, tcx);
                     let mut modifies_instances = vec![];
                     let mut loop_contracts_instances = vec![];
+                    let mut cbmc_contracts_instances = vec![];
+                    let mut foreign_functions = vec![];
+                    let mut unsafe_operations = vec![];
+                    let mut spec_fns = vec![];
+                    let mut unsupported_features = vec![];
+                    let mut reachable_fns = vec![];
+                    let mut largest_arrays = vec![];
+                    let mut devirtualized_calls = vec![];
+                    let mut labeled_loops = vec![];
+                    let mut shared_models = vec![];
 
                     // We know the # of harnesses here, so provide them to the thread_pool size calculation.
                     let num_harnesses: usize = units.iter().map(|unit| unit.harnesses.len()).sum();
@@ -396,22 +446,50 @@ fn codegen_crate(&self, tcx: TyCtxt) -> Box<dyn Any> {
                     let template_passes = GlobalPasses::new(&queries, tcx);
 
                     // Cross-crate collecting of all items that are reachable from the crate harnesses.
-                    for unit in units.iter() {
+                    for (index, unit) in units.iter().enumerate() {
                         // We reset the body cache for now because each codegen unit has different
                         // configurations that affect how we transform the instance body.
 
-                        // Generate an empty 'template' transformer once per codegen unit and then clone for each harness within.
+                        // Generate an empty 'template' transformer once per codegen unit and then clone for each model within.
                         // (They all share the same options.)
                         let template_transformer = BodyTransformation::new(&queries, tcx, unit);
-                        for harness in &unit.harnesses {
-                            let model_path = units.harness_model_path(*harness).unwrap();
-                            let is_automatic_harness = units.is_automatic_harness(harness);
-                            let contract_metadata =
-                                self.target_if_contract_harness(tcx, harness, is_automatic_harness);
-                            let (min_gcx, items, contract_info) = self.codegen_items(
+                        let (shared, separate): (Vec<_>, Vec<_>) =
+                            unit.harnesses.iter().copied().partition(|harness| {
+                                queries.args().shared_model && units.can_share_model(harness)
+                            });
+                        // The harnesses that share a model are all its starting items, so the code
+                        // that they reach is generated once. We derive the metadata of each harness
+                        // from the functions that it reaches.
+                        let mut models = vec![];
+                        let separate = if shared.len() > 1 {
+                            let model_path = shared_model_file(base_filename, index);
+                            fs::create_dir_all(model_path.parent().unwrap()).unwrap();
+                            shared_models.extend(
+                                shared.iter().map(|harness| (*harness, model_path.clone())),
+                            );
+                            models.push((shared, model_path));
+                            separate
+                        } else {
+                            unit.harnesses.clone()
+                        };
+                        models.extend(separate.into_iter().map(|harness| {
+                            (vec![harness], units.harness_model_path(harness).unwrap().clone())
+                        }));
+                        for (harnesses, model_path) in models {
+                            let contract_metadata = match harnesses.as_slice() {
+                                [harness] => self.target_if_contract_harness(
+                                    tcx,
+                                    harness,
+                                    units.is_automatic_harness(harness),
+                                ),
+                                _ => None,
+                            };
+                            let starting_items: Vec<_> =
+                                harnesses.iter().map(|harness| MonoItem::Fn(*harness)).collect();
+                            let (mut min_gcx, items, contract_info) = self.codegen_items(
                                 tcx,
-                                &[MonoItem::Fn(*harness)],
-                                model_path,
+                                &starting_items,
+                                &model_path,
                                 &results.machine_model,
                                 contract_metadata
                                     .map(|def| rustc_internal::internal(tcx, def.def_id())),
@@ -419,17 +497,73 @@ fn codegen_crate(&self, tcx: TyCtxt) -> Box<dyn Any> {
                                 template_transformer.clone_empty(),
                                 &export_thread_pool,
                             );
-                            if min_gcx.has_loop_contracts {
-                                loop_contracts_instances.push(*harness);
+                            for harness in &harnesses {
+                                let shared_items;
+                                let harness_items = if harnesses.len() == 1 {
+                                    &items
+                                } else {
+                                    let start = [MonoItem::Fn(*harness)];
+                                    (shared_items, _) = collect_reachable_items(
+                                        tcx,
+                                        &mut min_gcx.transformer,
+                                        &start,
+                                    );
+                                    &shared_items
+                                };
+                                let reached = ReachedMetadata::new(&min_gcx, harness_items);
+                                if min_gcx.has_loop_contracts {
+                                    loop_contracts_instances.push(*harness);
+                                }
+                                if let Some(cbmc_contracts) = &min_gcx.cbmc_contracts {
+                                    cbmc_contracts_instances
+                                        .push((*harness, cbmc_contracts.clone()));
+                                }
+                                if !reached.foreign_functions.is_empty() {
+                                    foreign_functions.push((*harness, reached.foreign_functions));
+                                }
+                                if !reached.unsafe_operations.is_empty() {
+                                    unsafe_operations.push((*harness, reached.unsafe_operations));
+                                }
+                                let reachable_spec_fns = reachable_spec_fns(tcx, harness_items);
+                                if !reachable_spec_fns.is_empty() {
+                                    spec_fns.push((*harness, reachable_spec_fns));
+                                }
+                                if queries.args().reachable_functions {
+                                    reachable_fns
+                                        .push((*harness, reachable_functions(harness_items)));
+                                }
+                                if !reached.unsupported_features.is_empty() {
+                                    unsupported_features
+                                        .push((*harness, reached.unsupported_features));
+                                }
+                                if let Some(len) = reached.largest_array {
+                                    largest_arrays.push((*harness, len));
+                                }
+                                if let Some(calls) = min_gcx.devirtualized_calls {
+                                    devirtualized_calls.push((*harness, calls));
+                                }
+                                if !min_gcx.labeled_loops.is_empty() {
+                                    labeled_loops.push((*harness, min_gcx.labeled_loops.clone()));
+                                }
                             }
                             results.extend(min_gcx, items, None);
                             if let Some(assigns_contract) = contract_info {
-                                modifies_instances.push((*harness, assigns_contract));
+                                modifies_instances.push((harnesses[0], assigns_contract));
                             }
                         }
                     }
                     units.store_modifies(&modifies_instances);
                     units.store_loop_contracts(&loop_contracts_instances);
+                    units.store_cbmc_contracts(&cbmc_contracts_instances);
+                    units.store_foreign_functions(&foreign_functions);
+                    units.store_unsafe_operations(&unsafe_operations);
+                    units.store_spec_fns(&spec_fns);
+                    units.store_unsupported_features(&unsupported_features);
+                    units.store_reachable_functions(&reachable_fns);
+                    units.store_largest_arrays(&largest_arrays);
+                    units.store_devirtualized_calls(&devirtualized_calls);
+                    units.store_labeled_loops(&labeled_loops);
+                    units.store_shared_models(&shared_models);
                     units.write_metadata(&queries, tcx);
                 }
                 ReachabilityType::None => unreachable!(),
@@ -633,6 +767,51 @@ fn codegen_results(tcx: TyCtxt, machine: &MachineModel) -> Box<dyn Any> {
     ))
 }
 
+/// The symbols of the functions annotated with `#[kani::spec_fn]` among the reachable `items`.
+fn reachable_spec_fns(tcx: TyCtxt, items: &[MonoItem]) -> Vec<String> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            MonoItem::Fn(instance) if KaniAttributes::for_instance(tcx, *instance).is_spec_fn() => {
+                Some(instance.mangled_name())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The crates whose functions aren't recorded by [reachable_functions], since they can't be
+/// changed by the user.
+const EXTERNAL_CRATES: [&str; 5] = ["core", "alloc", "std", "kani", "kani_core"];
+
+/// The functions of `items` that are defined outside of the standard library and Kani, with the
+/// lines that define them.
+fn reachable_functions(items: &[MonoItem]) -> Vec<ReachableFunction> {
+    let mut functions: Vec<_> = items
+        .iter()
+        .filter_map(|item| {
+            let MonoItem::Fn(instance) = item else { return None };
+            if EXTERNAL_CRATES.contains(&instance.def.krate().name.as_str()) {
+                return None;
+            }
+            let loc = SourceLocation::new(instance.body()?.span);
+            // Relative paths are relative to the working directory of the compiler, which the
+            // driver doesn't know.
+            let file = std::fs::canonicalize(&loc.filename).ok()?;
+            Some(ReachableFunction {
+                file: file.to_string_lossy().into_owned(),
+                start_line: loc.start_line,
+                end_line: loc.end_line,
+                name: instance.def.name(),
+            })
+        })
+        .collect();
+    // Generic functions have an instance for each of their arguments.
+    functions.sort();
+    functions.dedup();
+    functions
+}
+
 pub fn write_file<T>(base_path: &Path, file_type: ArtifactType, source: &T, pretty: bool)
 where
     T: serde::Serialize,
@@ -648,10 +827,122 @@ pub fn write_file<T>(base_path: &Path, file_type: ArtifactType, source: &T, pret
     }
 }
 
+/// The part of the metadata of a model that concerns a harness, i.e., what we recorded while
+/// generating the functions that the harness reaches.
+///
+/// This is all of the metadata of the model, unless the harness shares it with other harnesses.
+struct ReachedMetadata {
+    unsupported_features: Vec<UnsupportedFeature>,
+    foreign_functions: Vec<ForeignFunction>,
+    unsafe_operations: Vec<UnsafeOperation>,
+    largest_array: Option<u64>,
+}
+
+impl ReachedMetadata {
+    fn new(gcx: &MinimalGotocCtx, items: &[MonoItem]) -> Self {
+        let instances: Vec<_> = items
+            .iter()
+            .filter_map(|item| match item {
+                MonoItem::Fn(instance) => Some(instance),
+                _ => None,
+            })
+            .collect();
+        let names: FxHashSet<InternedString> =
+            instances.iter().map(|instance| instance.name().into()).collect();
+        let def_names: FxHashSet<String> =
+            instances.iter().map(|instance| instance.def.name()).collect();
+        // What was generated outside of a function body concerns every harness.
+        let is_reached = |function: &Option<InternedString>| {
+            function.as_ref().is_none_or(|function| names.contains(function))
+        };
+
+        let constructs: UnsupportedConstructs = gcx
+            .unsupported_constructs
+            .iter()
+            .filter_map(|(construct, locations)| {
+                let locations: Vec<_> = locations
+                    .iter()
+                    .filter(|location| match location {
+                        Location::Loc { function, .. } => is_reached(function),
+                        _ => true,
+                    })
+                    .copied()
+                    .collect();
+                (!locations.is_empty()).then_some((*construct, locations))
+            })
+            .collect();
+        let unsupported_features = unsupported_features_metadata(&constructs, &gcx.tracking_issues);
+
+        let mut foreign_functions: Vec<_> = gcx
+            .foreign_functions
+            .iter()
+            .filter_map(|(mangled_name, function)| {
+                let users = &gcx.foreign_function_users[mangled_name];
+                let mut calls = users.iter().filter(|&(user, _)| is_reached(user)).peekable();
+                calls.peek()?;
+                let call_sites = calls.map(|(_, count)| count).sum();
+                Some(ForeignFunction { call_sites, ..function.clone() })
+            })
+            .collect();
+        foreign_functions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let unsafe_operations = gcx
+            .unsafe_operations
+            .iter()
+            .filter(|operation| def_names.contains(&operation.function))
+            .cloned()
+            .collect();
+
+        let largest_array = gcx
+            .largest_arrays
+            .iter()
+            .filter(|&(function, _)| names.contains(function))
+            .map(|(_, len)| *len)
+            .max();
+
+        ReachedMetadata {
+            unsupported_features,
+            foreign_functions,
+            unsafe_operations,
+            largest_array,
+        }
+    }
+}
+
+/// Maps the goto-context "unsupported features" data into the KaniMetadata "unsupported features"
+/// format, sorted by feature.
+fn unsupported_features_metadata(
+    constructs: &UnsupportedConstructs,
+    tracking_issues: &FxHashMap<InternedString, InternedString>,
+) -> Vec<UnsupportedFeature> {
+    let mut features: Vec<_> = constructs
+        .iter()
+        .map(|(construct, locations)| UnsupportedFeature {
+            feature: construct.to_string(),
+            locations: locations
+                .iter()
+                .map(|l| {
+                    // We likely (and should) have no instances of
+                    // calling `codegen_unimplemented` without file/line.
+                    // So while we map out of `Option` here, we expect them to always be `Some`
+                    kani_metadata::Location {
+                        filename: l.filename().unwrap_or_default(),
+                        start_line: l.start_line().unwrap_or_default(),
+                    }
+                })
+                .collect(),
+            tracking_issue: tracking_issues.get(construct).map(|issue| issue.to_string()),
+        })
+        .collect();
+    features.sort_by(|a, b| a.feature.cmp(&b.feature));
+    features
+}
+
 struct GotoCodegenResults {
     reachability: ReachabilityType,
     harnesses: Vec<HarnessMetadata>,
     unsupported_constructs: UnsupportedConstructs,
+    tracking_issues: FxHashMap<InternedString, InternedString>,
     concurrent_constructs: UnsupportedConstructs,
     items: Vec<MonoItem>,
     crate_name: InternedString,
@@ -664,6 +955,7 @@ pub fn new(tcx: TyCtxt, reachability: ReachabilityType) -> Self {
             reachability,
             harnesses: vec![],
             unsupported_constructs: UnsupportedConstructs::default(),
+            tracking_issues: FxHashMap::default(),
             concurrent_constructs: UnsupportedConstructs::default(),
             items: vec![],
             crate_name: tcx.crate_name(LOCAL_CRATE).as_str().into(),
@@ -672,27 +964,8 @@ pub fn new(tcx: TyCtxt, reachability: ReachabilityType) -> Self {
     }
     /// Method that generates `KaniMetadata` from the given compilation results.
     pub fn generate_metadata(&self) -> KaniMetadata {
-        // Maps the goto-context "unsupported features" data into the KaniMetadata "unsupported features" format.
-        // TODO: Do we really need different formats??
-        let unsupported_features = self
-            .unsupported_constructs
-            .iter()
-            .map(|(construct, location)| UnsupportedFeature {
-                feature: construct.to_string(),
-                locations: location
-                    .iter()
-                    .map(|l| {
-                        // We likely (and should) have no instances of
-                        // calling `codegen_unimplemented` without file/line.
-                        // So while we map out of `Option` here, we expect them to always be `Some`
-                        kani_metadata::Location {
-                            filename: l.filename().unwrap_or_default(),
-                            start_line: l.start_line().unwrap_or_default(),
-                        }
-                    })
-                    .collect(),
-            })
-            .collect();
+        let unsupported_features =
+            unsupported_features_metadata(&self.unsupported_constructs, &self.tracking_issues);
         let (proofs, tests) = if self.reachability == ReachabilityType::Harnesses {
             (self.harnesses.clone(), vec![])
         } else {
@@ -721,14 +994,18 @@ fn extend(
         self.harnesses.extend(metadata);
         self.concurrent_constructs.extend(min_gcx.concurrent_constructs);
         self.unsupported_constructs.extend(min_gcx.unsupported_constructs);
+        self.tracking_issues.extend(min_gcx.tracking_issues);
         self.items.append(&mut items);
         min_gcx.transformer
     }
 
     /// Prints a report at the end of the compilation.
     fn print_report(&self, tcx: TyCtxt) {
-        // Print all unsupported constructs.
-        if !self.unsupported_constructs.is_empty() {
+        // Print all unsupported constructs. When Kani compiles harnesses, they are recorded in the
+        // metadata of each harness instead, and the driver reports them with its results.
+        if !self.unsupported_constructs.is_empty()
+            && !matches!(self.reachability, ReachabilityType::Harnesses | ReachabilityType::AllFns)
+        {
             // Sort alphabetically.
             let unsupported: BTreeMap<String, &Vec<Location>> = self
                 .unsupported_constructs
@@ -27,6 +27,7 @@
 };
 use cbmc::utils::aggr_tag;
 use cbmc::{InternedString, MachineModel};
+use kani_metadata::{CbmcContracts, CheckCategory, ForeignFunction, UnsafeOperation};
 use rustc_abi::{HasDataLayout, TargetDataLayout};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_middle::span_bug;
@@ -52,6 +53,8 @@
 pub struct MinimalGotocCtx {
     /// A map of unsupported constructs that were found while codegen
     pub unsupported_constructs: UnsupportedConstructs,
+    /// The issue that tracks the support of each unsupported construct, if there is one.
+    pub tracking_issues: FxHashMap<InternedString, InternedString>,
     /// A map of concurrency constructs that are treated sequentially.
     /// We collect them and print one warning at the end if not empty instead of printing one
     /// warning at each occurrence.
@@ -60,6 +63,20 @@ pub struct MinimalGotocCtx {
     pub transformer: BodyTransformation,
     /// If there exist some usage of loop contracts int context.
     pub has_loop_contracts: bool,
+    /// The contracts that were attached to goto functions for CBMC to check and replace.
+    pub cbmc_contracts: Option<CbmcContracts>,
+    /// The foreign functions that Kani does not model, indexed by their mangled name.
+    pub foreign_functions: BTreeMap<InternedString, ForeignFunction>,
+    /// The functions that use each foreign function, with the number of calls that they make.
+    pub foreign_function_users: FxHashMap<InternedString, FxHashMap<Option<InternedString>, usize>>,
+    /// The reachable unsafe operations, if they were requested.
+    pub unsafe_operations: Vec<UnsafeOperation>,
+    /// The number of elements of the largest array that the locals of each function hold.
+    pub largest_arrays: FxHashMap<InternedString, u64>,
+    /// The number of dynamic calls that were replaced by direct calls, with `-Z devirtualize`.
+    pub devirtualized_calls: Option<u64>,
+    /// The CBMC ids of the labeled loops of the harness, indexed by label.
+    pub labeled_loops: BTreeMap<String, Vec<String>>,
 }
 
 pub struct GotocCtx<'tcx> {
@@ -75,7 +92,7 @@ pub struct GotocCtx<'tcx> {
     pub full_crate_name: String,
     /// a global counter for generating unique names for global variables
     pub global_var_count: u64,
-    /// map a global allocation to a name in the symbol table
+    /// map an immutable global allocation to the name of the symbol that stores it
     pub alloc_map: FxHashMap<Allocation, String>,
     /// map (trait, method) pairs to possible implementations
     pub vtable_ctx: VtableCtx,
@@ -88,6 +105,8 @@ pub struct GotocCtx<'tcx> {
     pub global_checks_count: u64,
     /// A map of unsupported constructs that were found while codegen
     pub unsupported_constructs: UnsupportedConstructs,
+    /// The issue that tracks the support of each unsupported construct, if there is one.
+    pub tracking_issues: FxHashMap<InternedString, InternedString>,
     /// A map of concurrency constructs that are treated sequentially.
     /// We collect them and print one warning at the end if not empty instead of printing one
     /// warning at each occurrence.
@@ -96,8 +115,37 @@ pub struct GotocCtx<'tcx> {
     pub transformer: BodyTransformation,
     /// If there exist some usage of loop contracts int context.
     pub has_loop_contracts: bool,
+    /// The contracts that were attached to goto functions for CBMC to check and replace.
+    pub cbmc_contracts: Option<CbmcContracts>,
+    /// The foreign functions that Kani does not model, indexed by their mangled name.
+    pub foreign_functions: BTreeMap<InternedString, ForeignFunction>,
+    /// For each foreign function of `foreign_functions`, the functions that use it, indexed by
+    /// their readable name, with the number of calls that they make to it. Uses outside of a
+    /// function body are indexed by `None`.
+    pub foreign_function_users: FxHashMap<InternedString, FxHashMap<Option<InternedString>, usize>>,
     /// Track loop assign clause
     pub current_loop_modifies: Vec<Expr>,
+    /// The global flag that is set while the function under contract is expected to panic, if
+    /// its contract specifies when it panics. See [GotocCtx::declare_panic_expectation].
+    pub panic_expected: Option<Expr>,
+    /// The unsafe operations that are reachable from the starting items, if they were requested.
+    pub unsafe_operations: Vec<UnsafeOperation>,
+    /// The categories of checks that are not generated, from `--disabled-checks` and the
+    /// `checks` attribute of the harness.
+    pub disabled_checks: Vec<CheckCategory>,
+    /// The number of elements of the largest array that the locals of each function hold, indexed
+    /// by its readable name. With `--array-encoding auto`, the largest one that a harness reaches
+    /// selects how CBMC encodes its arrays.
+    pub largest_arrays: FxHashMap<InternedString, u64>,
+    /// The number of dynamic calls that were replaced by direct calls, if devirtualization is
+    /// enabled.
+    pub devirtualized_calls: Option<u64>,
+    /// The labels of the loops of the harness whose unwinding bound is set by its `unwind`
+    /// attribute, with the span of each loop.
+    pub loop_labels: Vec<(String, Span)>,
+    /// The CBMC ids of the loops with one of the `loop_labels`, e.g. `_RNvCs1_4main5check.0`,
+    /// indexed by label.
+    pub labeled_loops: BTreeMap<String, Vec<String>>,
 }
 
 /// Constructor
@@ -111,6 +159,7 @@ pub fn new(
         let fhks = fn_hooks();
         let symbol_table = SymbolTable::new(machine_model.clone());
         let emit_vtable_restrictions = queries.args().emit_vtable_restrictions;
+        let disabled_checks = queries.args().disabled_checks.clone();
         GotocCtx {
             tcx,
             queries,
@@ -125,10 +174,21 @@ pub fn new(
             str_literals: FxHashMap::default(),
             global_checks_count: 0,
             unsupported_constructs: FxHashMap::default(),
+            tracking_issues: FxHashMap::default(),
             concurrent_constructs: FxHashMap::default(),
             transformer,
             has_loop_contracts: false,
+            cbmc_contracts: None,
+            foreign_functions: BTreeMap::default(),
+            foreign_function_users: FxHashMap::default(),
             current_loop_modifies: Vec::new(),
+            panic_expected: None,
+            unsafe_operations: vec![],
+            disabled_checks,
+            largest_arrays: FxHashMap::default(),
+            devirtualized_calls: None,
+            loop_labels: vec![],
+            labeled_loops: BTreeMap::default(),
         }
     }
 
@@ -138,9 +198,17 @@ pub fn split(self) -> (MinimalGotocCtx, SymbolTable) {
         (
             MinimalGotocCtx {
                 unsupported_constructs: self.unsupported_constructs,
+                tracking_issues: self.tracking_issues,
                 concurrent_constructs: self.concurrent_constructs,
                 transformer: self.transformer,
                 has_loop_contracts: self.has_loop_contracts,
+                cbmc_contracts: self.cbmc_contracts,
+                foreign_functions: self.foreign_functions,
+                foreign_function_users: self.foreign_function_users,
+                unsafe_operations: self.unsafe_operations,
+                largest_arrays: self.largest_arrays,
+                devirtualized_calls: self.devirtualized_calls,
+                labeled_loops: self.labeled_loops,
             },
             self.symbol_table,
         )
@@ -156,6 +224,11 @@ pub fn current_fn(&self) -> &CurrentFnCtx<'tcx> {
     pub fn current_fn_mut(&mut self) -> &mut CurrentFnCtx<'tcx> {
         self.current_fn.as_mut().unwrap()
     }
+
+    /// The readable name of the function that is being generated, if any.
+    pub fn current_fn_name(&self) -> Option<InternedString> {
+        self.current_fn.as_ref().map(|current_fn| current_fn.readable_name().into())
+    }
 }
 
 /// Generate variables
@@ -16,12 +16,15 @@
 use cbmc::goto_program::CIntType;
 use cbmc::goto_program::Symbol as GotoSymbol;
 use cbmc::goto_program::{BuiltinFn, Expr, Location, Stmt, Type};
+use kani_metadata::SPLIT_CASE_VAR;
+use rustc_abi::Integer;
 use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::layout::IntegerExt;
 use rustc_public::mir::mono::Instance;
 use rustc_public::mir::{BasicBlockIdx, Place};
 use rustc_public::rustc_internal;
 use rustc_public::ty::ClosureKind;
-use rustc_public::ty::RigidTy;
+use rustc_public::ty::{IntTy, RigidTy, Ty};
 use rustc_public::{CrateDef, ty::Span};
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -88,6 +91,36 @@ fn handle(
     }
 }
 
+/// A probe is a reachability marker with a name, which Kani reports as covered if some execution
+/// of the harness reaches it, and uncovered otherwise.
+struct Probe;
+
+impl GotocHook for Probe {
+    fn hook_applies(&self, _tcx: TyCtxt, _instance: Instance) -> bool {
+        unreachable!("{UNEXPECTED_CALL}")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        fargs: Vec<Expr>,
+        _assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let name = gcx.extract_const_message(&fargs[0]).unwrap();
+        let target = target.unwrap();
+        let caller_loc = gcx.codegen_caller_span_stable(span);
+
+        Stmt::block(
+            vec![gcx.codegen_probe(&name, span), Stmt::goto(bb_label(target), caller_loc)],
+            caller_loc,
+        )
+    }
+}
+
 struct Assume;
 impl GotocHook for Assume {
     fn hook_applies(&self, _tcx: TyCtxt, _instance: Instance) -> bool {
@@ -215,6 +248,37 @@ fn handle(
     }
 }
 
+struct RecursionLimitCheck;
+impl GotocHook for RecursionLimitCheck {
+    fn hook_applies(&self, _tcx: TyCtxt, _instance: Instance) -> bool {
+        unreachable!("{UNEXPECTED_CALL}")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        mut fargs: Vec<Expr>,
+        _assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let msg = fargs.pop().unwrap();
+        let cond = fargs.pop().unwrap().cast_to(Type::bool());
+        let msg = gcx.extract_const_message(&msg).unwrap();
+        let target = target.unwrap();
+        let caller_loc = gcx.codegen_caller_span_stable(span);
+        Stmt::block(
+            vec![
+                gcx.codegen_assert_assume(cond, PropertyClass::RecursionLimit, &msg, caller_loc),
+                Stmt::goto(bb_label(target), caller_loc),
+            ],
+            caller_loc,
+        )
+    }
+}
+
 struct SafetyCheckNoAssume;
 impl GotocHook for SafetyCheckNoAssume {
     fn hook_applies(&self, _tcx: TyCtxt, _instance: Instance) -> bool {
@@ -361,30 +425,61 @@ fn handle(
         &self,
         gcx: &mut GotocCtx,
         _instance: Instance,
-        mut fargs: Vec<Expr>,
+        fargs: Vec<Expr>,
         assign_to: &Place,
         target: Option<BasicBlockIdx>,
         span: Span,
     ) -> Stmt {
-        assert_eq!(fargs.len(), 2);
-        let size = fargs.pop().unwrap();
-        let ptr = fargs.pop().unwrap().cast_to(Type::void_pointer());
-        let target = target.unwrap();
-        let loc = gcx.codegen_caller_span_stable(span);
-        let ret_place = unwrap_or_return_codegen_unimplemented_stmt!(
-            gcx,
-            gcx.codegen_place_stable(assign_to, loc)
-        );
-        let ret_type = ret_place.goto_expr.typ().clone();
+        codegen_ptr_access_check(gcx, fargs, assign_to, target, span, Expr::read_ok)
+    }
+}
 
-        Stmt::block(
-            vec![
-                ret_place.goto_expr.assign(Expr::read_ok(ptr, size).cast_to(ret_type), loc),
-                Stmt::goto(bb_label(target), loc),
-            ],
-            loc,
-        )
+/// Encodes __CPROVER_w_ok(ptr, size)
+struct IsWritable;
+impl GotocHook for IsWritable {
+    fn hook_applies(&self, _tcx: TyCtxt, _instance: Instance) -> bool {
+        unreachable!("{UNEXPECTED_CALL}")
     }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        fargs: Vec<Expr>,
+        assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        codegen_ptr_access_check(gcx, fargs, assign_to, target, span, Expr::write_ok)
+    }
+}
+
+/// Assign `check(ptr, size)` to `assign_to`, where `check` builds one of CBMC's pointer
+/// primitives, such as `__CPROVER_r_ok`.
+fn codegen_ptr_access_check(
+    gcx: &mut GotocCtx,
+    mut fargs: Vec<Expr>,
+    assign_to: &Place,
+    target: Option<BasicBlockIdx>,
+    span: Span,
+    check: fn(Expr, Expr) -> Expr,
+) -> Stmt {
+    assert_eq!(fargs.len(), 2);
+    let size = fargs.pop().unwrap();
+    let ptr = fargs.pop().unwrap().cast_to(Type::void_pointer());
+    let target = target.unwrap();
+    let loc = gcx.codegen_caller_span_stable(span);
+    let ret_place =
+        unwrap_or_return_codegen_unimplemented_stmt!(gcx, gcx.codegen_place_stable(assign_to, loc));
+    let ret_type = ret_place.goto_expr.typ().clone();
+
+    Stmt::block(
+        vec![
+            ret_place.goto_expr.assign(check(ptr, size).cast_to(ret_type), loc),
+            Stmt::goto(bb_label(target), loc),
+        ],
+        loc,
+    )
 }
 
 /// This is the hook for the `kani::float::float_to_int_in_range` intrinsic
@@ -609,6 +704,102 @@ fn handle(
     }
 }
 
+/// This hook models `std::process::abort` and `std::process::exit` as terminal states of the
+/// program, with checks of their own property class:
+/// ```c
+/// // std::process::abort()
+/// assert(false, "std::process::abort was called");
+/// assume(false);
+/// // std::process::exit(code)
+/// exit_code = code;
+/// assert(exit_code == 0, "std::process::exit was called with a non-zero exit code");
+/// assume(false);
+/// ```
+/// Exiting with code `0` is a successful termination, so only other exit codes fail the check.
+/// The temporary shows the exit code in the trace of a failure.
+struct ProcessExit;
+
+impl GotocHook for ProcessExit {
+    fn hook_applies(&self, _tcx: TyCtxt, instance: Instance) -> bool {
+        matches!(instance.name().as_str(), "std::process::abort" | "std::process::exit")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        instance: Instance,
+        mut fargs: Vec<Expr>,
+        _assign_to: &Place,
+        _target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        let loc = gcx.codegen_span_stable(span);
+        if instance.name() == "std::process::abort" {
+            return gcx.codegen_assert_assume_false(
+                PropertyClass::ProcessExit,
+                "std::process::abort was called",
+                loc,
+            );
+        }
+        assert_eq!(fargs.len(), 1);
+        let code = fargs.remove(0);
+        let (code_var, code_decl) = gcx.decl_temp_variable(code.typ().clone(), Some(code), loc);
+        let check = gcx.codegen_assert(
+            code_var.is_zero(),
+            PropertyClass::ProcessExit,
+            "std::process::exit was called with a non-zero exit code",
+            loc,
+        );
+        Stmt::block(vec![code_decl, check, gcx.codegen_assume(Expr::bool_false(), loc)], loc)
+    }
+}
+
+/// This hook intercepts calls to `core::mem::transmute_copy::<Src, Dst>` where `Dst` is larger
+/// than `Src`, which always panic.
+///
+/// The standard library panics with a message that doesn't say which types were involved, so
+/// we replace the call with an assertion failure that names both types and their sizes.
+/// Calls where `Dst` fits in `Src` are not hooked, and they copy the first `size_of::<Dst>()`
+/// bytes of `Src`, which are checked by the usual pointer and memory initialization checks.
+struct TransmuteCopy;
+
+impl TransmuteCopy {
+    /// Return the `Src` and `Dst` types and their sizes.
+    fn sizes(instance: Instance) -> Option<[(Ty, usize); 2]> {
+        let generic_args = instance.args().0;
+        let size = |idx: usize| {
+            let ty = generic_args[idx].expect_ty();
+            Some((*ty, ty.layout().ok()?.shape().size.bytes()))
+        };
+        Some([size(0)?, size(1)?])
+    }
+}
+
+impl GotocHook for TransmuteCopy {
+    fn hook_applies(&self, _tcx: TyCtxt, instance: Instance) -> bool {
+        instance.def.name() == "core::mem::transmute_copy"
+            && TransmuteCopy::sizes(instance)
+                .is_some_and(|[(_, src_size), (_, dst_size)]| dst_size > src_size)
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        instance: Instance,
+        _fargs: Vec<Expr>,
+        _assign_to: &Place,
+        _target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        let [(src_ty, src_size), (dst_ty, dst_size)] = TransmuteCopy::sizes(instance).unwrap();
+        let msg = format!(
+            "cannot transmute_copy from `{src_ty}` (`{src_size}` bytes) to `{dst_ty}` \
+            (`{dst_size}` bytes), since Dst is larger than Src"
+        );
+        gcx.codegen_fatal_error(PropertyClass::Assertion, &msg, span)
+    }
+}
+
 /// A builtin that is essentially a C-style dereference operation, creating an
 /// unsafe shallow copy. Importantly either this copy or the original needs to
 /// be `mem::forget`en or a double-free will occur.
@@ -682,11 +873,18 @@ fn handle(
     ) -> Stmt {
         assert_eq!(fargs.len(), 0,);
         let loc = gcx.codegen_span_stable(span);
+        // Contract checking havocs static variables, so reset whether a panic is expected.
+        let reset_panic_expected = gcx
+            .panic_expected
+            .clone()
+            .map(|flag| Stmt::assign(flag, Expr::bool_false(), loc))
+            .unwrap_or_else(|| Stmt::skip(loc));
         Stmt::block(
             vec![
                 BuiltinFn::Free
                     .call(vec![Expr::pointer_constant(0, Type::void_pointer())], loc)
                     .as_stmt(loc),
+                reset_panic_expected,
                 Stmt::goto(bb_label(target.unwrap()), loc),
             ],
             loc,
@@ -694,6 +892,102 @@ fn handle(
     }
 }
 
+struct ExpectPanic;
+
+/// Set the flag that tells whether the function under contract is expected to panic, which is
+/// declared by [GotocCtx::declare_panic_expectation]:
+/// ```c
+/// __kani_panic_expected = expected;
+/// goto target;
+/// ```
+impl GotocHook for ExpectPanic {
+    fn hook_applies(&self, _tcx: TyCtxt, _instance: Instance) -> bool {
+        unreachable!("{UNEXPECTED_CALL}")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        mut fargs: Vec<Expr>,
+        _assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let expected = fargs.remove(0).cast_to(Type::bool());
+        let flag = gcx.panic_expected.clone().expect("panic expectation should be declared");
+        let loc = gcx.codegen_span_stable(span);
+        Stmt::block(
+            vec![Stmt::assign(flag, expected, loc), Stmt::goto(bb_label(target.unwrap()), loc)],
+            loc,
+        )
+    }
+}
+
+struct SplitCase;
+
+/// Constrain the argument that a harness is split on to the variant whose index is the value of
+/// the global variable [SPLIT_CASE_VAR], which the driver defines for each run of the harness:
+/// ```c
+/// __CPROVER_assume((__kani_split_case == 0 && discriminant(*value) == <discriminant of 0>)
+///                  || (__kani_split_case == 1 && discriminant(*value) == <discriminant of 1>)
+///                  || ...);
+/// goto target;
+/// ```
+impl GotocHook for SplitCase {
+    fn hook_applies(&self, _tcx: TyCtxt, _instance: Instance) -> bool {
+        unreachable!("{UNEXPECTED_CALL}")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        instance: Instance,
+        mut fargs: Vec<Expr>,
+        _assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let loc = gcx.codegen_span_stable(span);
+        let ty = *instance.args().0[0].expect_ty();
+        let Some(RigidTy::Adt(def, _)) = ty.kind().rigid().cloned() else {
+            unreachable!("the metadata of a split harness rejects inputs that aren't enums")
+        };
+        let value = fargs.remove(0).dereference();
+        let discriminant = gcx.codegen_get_discriminant(value, ty, Ty::signed_ty(IntTy::I128));
+        let case = gcx.ensure_global_var(SPLIT_CASE_VAR, false, Type::unsigned_int(32), loc);
+        // The driver links the definition of the variable with the model.
+        case.is_extern = true;
+        let case = case.to_expr();
+
+        let ty_internal = rustc_internal::internal(gcx.tcx, ty);
+        let cond = def
+            .variants_iter()
+            .map(|variant| {
+                let idx = rustc_internal::internal(gcx.tcx, variant.idx);
+                let discr = ty_internal.discriminant_for_variant(gcx.tcx, idx).unwrap();
+                // The value of the discriminant is stored without its sign.
+                let discr_val = match discr.ty.kind() {
+                    rustc_middle::ty::Int(int_ty) => {
+                        Integer::from_int_ty(&gcx.tcx, *int_ty).size().sign_extend(discr.val)
+                    }
+                    _ => discr.val as i128,
+                };
+                case.clone().eq(Expr::int_constant(idx.as_u32(), Type::unsigned_int(32))).and(
+                    discriminant.clone().eq(Expr::int_constant(discr_val, Type::signed_int(128))),
+                )
+            })
+            .reduce(Expr::or)
+            .unwrap();
+        Stmt::block(
+            vec![gcx.codegen_assume(cond, loc), Stmt::goto(bb_label(target.unwrap()), loc)],
+            loc,
+        )
+    }
+}
+
 /// A loop contract register function call is assumed to be
 /// 1. of form `kani_register_loop_contract(inv)` where `inv`
 ///    is the closure wrapping loop invariants
@@ -917,16 +1211,21 @@ pub fn fn_hooks() -> GotocHooks {
         (KaniHook::Panic, Rc::new(Panic)),
         (KaniHook::Check, Rc::new(Check)),
         (KaniHook::Cover, Rc::new(Cover)),
+        (KaniHook::Probe, Rc::new(Probe)),
         (KaniHook::AnyRaw, Rc::new(Nondet)),
         (KaniHook::SafetyCheck, Rc::new(SafetyCheck)),
         (KaniHook::SafetyCheckNoAssume, Rc::new(SafetyCheckNoAssume)),
         (KaniHook::IsAllocated, Rc::new(IsAllocated)),
+        (KaniHook::IsWritable, Rc::new(IsWritable)),
         (KaniHook::PointerObject, Rc::new(PointerObject)),
         (KaniHook::PointerOffset, Rc::new(PointerOffset)),
         (KaniHook::UnsupportedCheck, Rc::new(UnsupportedCheck)),
+        (KaniHook::RecursionLimitCheck, Rc::new(RecursionLimitCheck)),
         (KaniHook::UntrackedDeref, Rc::new(UntrackedDeref)),
         (KaniHook::InitContracts, Rc::new(InitContracts)),
+        (KaniHook::ExpectPanic, Rc::new(ExpectPanic)),
         (KaniHook::FloatToIntInRange, Rc::new(FloatToIntInRange)),
+        (KaniHook::SplitCase, Rc::new(SplitCase)),
     ];
     GotocHooks {
         kani_lib_hooks: HashMap::from(kani_lib_hooks),
@@ -934,6 +1233,8 @@ pub fn fn_hooks() -> GotocHooks {
             Rc::new(Panic),
             Rc::new(RustAlloc),
             Rc::new(MemCmp),
+            Rc::new(ProcessExit),
+            Rc::new(TransmuteCopy),
             Rc::new(LoopInvariantRegister),
         ],
     }
@@ -101,7 +101,7 @@ fn handle_file(
     }: FileDataToWrite,
 ) {
     write_file(&symtab_goto, ArtifactType::PrettyNameMap, &pretty_name_map, pretty);
-    write_goto_binary_file(&symtab_goto, &symbol_table);
+    write_goto_binary_file(&symtab_goto, symbol_table);
     write_file(&symtab_goto, ArtifactType::TypeMap, &type_map, pretty);
     // If they exist, write out vtable virtual call function pointer restrictions
     if let Some(restrictions) = vtable_restrictions {
@@ -53,6 +53,15 @@ pub fn extract_const_message(&self, arg: &Expr) -> Option<String> {
         }
     }
 
+    /// Record an unsupported construct, and the issue that tracks its support, so Kani can
+    /// report it for every harness that reaches it.
+    pub fn store_unsupported_construct(&mut self, operation_name: &str, loc: Location, url: &str) {
+        debug!(op=?operation_name, location=?loc.short_string(), "store_unsupported_construct");
+        let key: InternedString = operation_name.into();
+        self.unsupported_constructs.entry(key).or_default().push(loc);
+        self.tracking_issues.insert(key, url.into());
+    }
+
     /// Store an occurrence of a concurrent construct that was treated as a sequential operation.
     ///
     /// Kani does not currently support concurrency and the compiler assumes that when generating
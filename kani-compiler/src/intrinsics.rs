@@ -41,12 +41,17 @@ pub enum Intrinsic {
     BlackBox,
     Breakpoint,
     Bswap,
+    CeilF16,
     CeilF32,
     CeilF64,
+    CeilF128,
     CompareBytes,
     Copy,
+    CopySignF16,
     CopySignF32,
     CopySignF64,
+    CopySignF128,
+    CosF16,
     CosF32,
     CosF64,
     Ctlz,
@@ -56,17 +61,24 @@ pub enum Intrinsic {
     CttzNonZero,
     DiscriminantValue,
     ExactDiv,
+    Exp2F16,
     Exp2F32,
     Exp2F64,
+    ExpF16,
     ExpF32,
     ExpF64,
+    FabsF16,
     FabsF32,
     FabsF64,
+    FabsF128,
     FaddFast,
     FdivFast,
     FloatToIntUnchecked,
+    FloorF16,
     FloorF32,
     FloorF64,
+    FloorF128,
+    FmafF16,
     FmafF32,
     FmafF64,
     FmulFast,
@@ -74,19 +86,28 @@ pub enum Intrinsic {
     FsubFast,
     IsValStaticallyKnown,
     Likely,
+    Log10F16,
     Log10F32,
     Log10F64,
+    Log2F16,
     Log2F32,
     Log2F64,
+    LogF16,
     LogF32,
     LogF64,
+    MaxNumF16,
     MaxNumF32,
     MaxNumF64,
+    MaxNumF128,
+    MinNumF16,
     MinNumF32,
     MinNumF64,
+    MinNumF128,
     MulWithOverflow,
+    PowF16,
     PowF32,
     PowF64,
+    PowIF16,
     PowIF32,
     PowIF64,
     PtrGuaranteedCmp,
@@ -96,16 +117,23 @@ pub enum Intrinsic {
     RetagBoxToRaw,
     RotateLeft,
     RotateRight,
+    RoundF16,
     RoundF32,
     RoundF64,
+    RoundF128,
+    RoundTiesEvenF16,
     RoundTiesEvenF32,
     RoundTiesEvenF64,
+    RoundTiesEvenF128,
     SaturatingAdd,
     SaturatingSub,
+    SinF16,
     SinF32,
     SinF64,
     SimdAdd,
     SimdAnd,
+    SimdBitmask,
+    SimdCast,
     SimdDiv,
     SimdRem,
     SimdEq,
@@ -118,18 +146,23 @@ pub enum Intrinsic {
     SimdMul,
     SimdNe,
     SimdOr,
+    SimdSelect,
     SimdShl,
     SimdShr,
     SimdShuffle(String),
+    SimdShuffleConstGeneric,
     SimdSub,
     SimdXor,
     SizeOfVal,
+    SqrtF16,
     SqrtF32,
     SqrtF64,
     SubWithOverflow,
     Transmute,
+    TruncF16,
     TruncF32,
     TruncF64,
+    TruncF128,
     TypedSwap,
     UnalignedVolatileLoad,
     UncheckedDiv,
@@ -177,6 +210,10 @@ pub fn from_instance(intrinsic_instance: &Instance) -> Self {
                 assert_sig_matches!(sig, _, _ => RigidTy::Tuple(_));
                 Self::AddWithOverflow
             }
+            // The `LowerIntrinsics` MIR pass lowers `aggregate_raw_ptr` to `AggregateKind::RawPtr`
+            "aggregate_raw_ptr" => unreachable!(
+                "Expected `core::intrinsics::aggregate_raw_ptr` to be handled by `AggregateKind::RawPtr`"
+            ),
             "align_of" => unreachable!(
                 "Expected `core::intrinsics::align_of` to be handled by NullOp::SizeOf"
             ),
@@ -324,6 +361,10 @@ pub fn from_instance(intrinsic_instance: &Instance) -> Self {
             "offset" => unreachable!(
                 "Expected `core::intrinsics::unreachable` to be handled by `BinOp::OffSet`"
             ),
+            // The `LowerIntrinsics` MIR pass lowers `ptr_metadata` to `UnOp::PtrMetadata`
+            "ptr_metadata" => unreachable!(
+                "Expected `core::intrinsics::ptr_metadata` to be handled by `UnOp::PtrMetadata`"
+            ),
             "ptr_guaranteed_cmp" => {
                 assert_sig_matches!(sig, RigidTy::RawPtr(_, Mutability::Not), RigidTy::RawPtr(_, Mutability::Not) => RigidTy::Uint(UintTy::U8));
                 Self::PtrGuaranteedCmp
@@ -449,8 +490,10 @@ pub fn from_instance(intrinsic_instance: &Instance) -> Self {
             }
             _ => try_match_atomic(intrinsic_instance)
                 .or_else(|| try_match_simd(intrinsic_instance))
+                .or_else(|| try_match_f16(intrinsic_instance))
                 .or_else(|| try_match_f32(intrinsic_instance))
                 .or_else(|| try_match_f64(intrinsic_instance))
+                .or_else(|| try_match_f128(intrinsic_instance))
                 .unwrap_or(Self::Unimplemented {
                     name: intrinsic_str,
                     issue_link: "https://github.com/model-checking/kani/issues/new/choose".into(),
@@ -551,6 +594,14 @@ fn try_match_simd(intrinsic_instance: &Instance) -> Option<Intrinsic> {
             assert_sig_matches!(sig, _, _ => _);
             Some(Intrinsic::SimdAnd)
         }
+        "simd_bitmask" => {
+            assert_sig_matches!(sig, _ => _);
+            Some(Intrinsic::SimdBitmask)
+        }
+        "simd_cast" => {
+            assert_sig_matches!(sig, _ => _);
+            Some(Intrinsic::SimdCast)
+        }
         "simd_div" => {
             assert_sig_matches!(sig, _, _ => _);
             Some(Intrinsic::SimdDiv)
@@ -599,6 +650,10 @@ fn try_match_simd(intrinsic_instance: &Instance) -> Option<Intrinsic> {
             assert_sig_matches!(sig, _, _ => _);
             Some(Intrinsic::SimdOr)
         }
+        "simd_select" => {
+            assert_sig_matches!(sig, _, _, _ => _);
+            Some(Intrinsic::SimdSelect)
+        }
         "simd_shl" => {
             assert_sig_matches!(sig, _, _ => _);
             Some(Intrinsic::SimdShl)
@@ -607,6 +662,10 @@ fn try_match_simd(intrinsic_instance: &Instance) -> Option<Intrinsic> {
             assert_sig_matches!(sig, _, _ => _);
             Some(Intrinsic::SimdShr)
         }
+        "simd_shuffle_const_generic" | "simd_shuffle_generic" => {
+            assert_sig_matches!(sig, _, _ => _);
+            Some(Intrinsic::SimdShuffleConstGeneric)
+        }
         "simd_sub" => {
             assert_sig_matches!(sig, _, _ => _);
             Some(Intrinsic::SimdSub)
@@ -626,6 +685,96 @@ fn try_match_simd(intrinsic_instance: &Instance) -> Option<Intrinsic> {
     }
 }
 
+/// Match f16 arithmetic intrinsics by instance, returning an instance of the intrinsics enum if the match
+/// is successful.
+fn try_match_f16(intrinsic_instance: &Instance) -> Option<Intrinsic> {
+    let intrinsic_str = intrinsic_instance.intrinsic_name().unwrap();
+    let sig = intrinsic_instance.ty().kind().fn_sig().unwrap().skip_binder();
+    match intrinsic_str.as_str() {
+        "ceilf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::CeilF16)
+        }
+        "copysignf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16), RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::CopySignF16)
+        }
+        "cosf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::CosF16)
+        }
+        "exp2f16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::Exp2F16)
+        }
+        "expf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::ExpF16)
+        }
+        "fabsf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::FabsF16)
+        }
+        "floorf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::FloorF16)
+        }
+        "fmaf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16), RigidTy::Float(FloatTy::F16), RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::FmafF16)
+        }
+        "log10f16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::Log10F16)
+        }
+        "log2f16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::Log2F16)
+        }
+        "logf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::LogF16)
+        }
+        "maxnumf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16), RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::MaxNumF16)
+        }
+        "minnumf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16), RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::MinNumF16)
+        }
+        "powf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16), RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::PowF16)
+        }
+        "powif16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16), RigidTy::Int(IntTy::I32) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::PowIF16)
+        }
+        "roundf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::RoundF16)
+        }
+        "round_ties_even_f16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::RoundTiesEvenF16)
+        }
+        "sinf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::SinF16)
+        }
+        "sqrtf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::SqrtF16)
+        }
+        "truncf16" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F16) => RigidTy::Float(FloatTy::F16));
+            Some(Intrinsic::TruncF16)
+        }
+        _ => None,
+    }
+}
+
 /// Match f32 arithmetic intrinsics by instance, returning an instance of the intrinsics enum if the match
 /// is successful.
 fn try_match_f32(intrinsic_instance: &Instance) -> Option<Intrinsic> {
@@ -805,3 +954,56 @@ fn try_match_f64(intrinsic_instance: &Instance) -> Option<Intrinsic> {
         _ => None,
     }
 }
+
+/// Match f128 arithmetic intrinsics by instance, returning an instance of the intrinsics enum if the match
+/// is successful.
+fn try_match_f128(intrinsic_instance: &Instance) -> Option<Intrinsic> {
+    let intrinsic_str = intrinsic_instance.intrinsic_name().unwrap();
+    let sig = intrinsic_instance.ty().kind().fn_sig().unwrap().skip_binder();
+    match intrinsic_str.as_str() {
+        "ceilf128" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F128) => RigidTy::Float(FloatTy::F128));
+            Some(Intrinsic::CeilF128)
+        }
+        "copysignf128" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F128), RigidTy::Float(FloatTy::F128) => RigidTy::Float(FloatTy::F128));
+            Some(Intrinsic::CopySignF128)
+        }
+        "fabsf128" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F128) => RigidTy::Float(FloatTy::F128));
+            Some(Intrinsic::FabsF128)
+        }
+        "floorf128" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F128) => RigidTy::Float(FloatTy::F128));
+            Some(Intrinsic::FloorF128)
+        }
+        "maxnumf128" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F128), RigidTy::Float(FloatTy::F128) => RigidTy::Float(FloatTy::F128));
+            Some(Intrinsic::MaxNumF128)
+        }
+        "minnumf128" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F128), RigidTy::Float(FloatTy::F128) => RigidTy::Float(FloatTy::F128));
+            Some(Intrinsic::MinNumF128)
+        }
+        "roundf128" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F128) => RigidTy::Float(FloatTy::F128));
+            Some(Intrinsic::RoundF128)
+        }
+        "round_ties_even_f128" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F128) => RigidTy::Float(FloatTy::F128));
+            Some(Intrinsic::RoundTiesEvenF128)
+        }
+        "truncf128" => {
+            assert_sig_matches!(sig, RigidTy::Float(FloatTy::F128) => RigidTy::Float(FloatTy::F128));
+            Some(Intrinsic::TruncF128)
+        }
+        // CBMC can't compute these operations on `f128`, and unlike `f16`, there is no wider type
+        // to compute them in.
+        "cosf128" | "expf128" | "exp2f128" | "fmaf128" | "logf128" | "log10f128" | "log2f128"
+        | "powf128" | "powif128" | "sinf128" | "sqrtf128" => Some(Intrinsic::Unimplemented {
+            name: intrinsic_str,
+            issue_link: "https://github.com/model-checking/kani/issues/new/choose".into(),
+        }),
+        _ => None,
+    }
+}
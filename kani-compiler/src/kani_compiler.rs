@@ -20,7 +20,7 @@
 use crate::codegen_aeneas_llbc::LlbcCodegenBackend;
 #[cfg(feature = "cprover")]
 use crate::codegen_cprover_gotoc::GotocCodegenBackend;
-use crate::kani_middle::check_crate_items;
+use crate::kani_middle::{check_crate_items, print_type_definitions};
 use crate::kani_queries::QueryDb;
 use crate::session::init_session;
 use clap::Parser;
@@ -122,6 +122,19 @@ fn config(&mut self, config: &mut Config) {
         debug!(?queries, "config end");
     }
 
+    /// After expansion, we report the type definitions for `cargo kani fix`, since the type
+    /// checking errors that it fixes abort the compilation before the analysis ends.
+    fn after_expansion(
+        &mut self,
+        _compiler: &rustc_interface::interface::Compiler,
+        tcx: TyCtxt<'_>,
+    ) -> Compilation {
+        if self.queries.lock().unwrap().args().print_type_definitions {
+            print_type_definitions(tcx);
+        }
+        Compilation::Continue
+    }
+
     /// After analysis, we check the crate items for Kani API misuse or configuration issues.
     fn after_analysis(
         &mut self,
@@ -4,15 +4,20 @@
 
 use std::collections::{BTreeMap, HashSet};
 
-use kani_metadata::{CbmcSolver, HarnessAttributes, HarnessKind, Stub};
+use kani_metadata::{
+    ArrayEncoding, CbmcCheck, CbmcChecks, CbmcSolver, CheckCategory, HarnessAttributes,
+    HarnessChecks, HarnessKind, Isolation, Stub, Timeout,
+};
 use quote::ToTokens;
-use rustc_ast::{LitKind, MetaItem, MetaItemKind};
+use rustc_ast::{LitKind, MetaItem, MetaItemKind, MetaItemLit};
 use rustc_errors::ErrorGuaranteed;
 use rustc_hir::{
     AttrArgs, Attribute,
     def::DefKind,
     def_id::{DefId, LocalDefId},
+    intravisit::{self, Visitor},
 };
+use rustc_middle::hir::nested_filter;
 use rustc_middle::ty::{Instance, TyCtxt, TyKind};
 use rustc_public::crate_def::Attribute as AttributeStable;
 use rustc_public::mir::mono::Instance as InstanceStable;
@@ -22,21 +27,55 @@
 use rustc_session::Session;
 use rustc_span::{Span, Symbol};
 use std::str::FromStr;
+use std::time::Duration;
+use strum::VariantNames;
 use strum_macros::{AsRefStr, EnumString};
 use syn::parse::Parser;
 use syn::punctuated::Punctuated;
-use syn::{Expr, ExprLit, Lit, PathSegment, TypePath};
+use syn::{Expr, ExprLit, Lit, MetaNameValue, PathSegment, TypePath};
 
-use super::resolve::{FnResolution, ResolveError, resolve_fn_path};
+use super::resolve::{
+    FnResolution, ResolveError, resolve_fn_in_body, resolve_fn_path, resolve_static_path,
+    resolve_type_def_path,
+};
 use tracing::{debug, trace};
 
 #[derive(Debug, Clone, Copy, AsRefStr, EnumString, PartialEq, Eq, PartialOrd, Ord)]
 #[strum(serialize_all = "snake_case")]
 enum KaniAttributeKind {
+    /// Choose how CBMC encodes the arrays of a harness, e.g. `#[kanitool::array_encoding = "uf"]`.
+    ArrayEncoding,
+    /// Configure the automatic harness generated for a function by the `autoharness` subcommand.
+    Autoharness,
+    /// Enable or disable CBMC checks for a harness, e.g.
+    /// `#[kanitool::cbmc_checks(disable = "pointer-overflow")]`.
+    CbmcChecks,
+    /// Turn categories of Kani's checks on or off for a harness, e.g.
+    /// `#[kanitool::checks(overflow = false)]`.
+    Checks,
+    /// Ignore the global assembly of a module, whose optional arguments are the statics that the
+    /// assembly may assign, e.g. `#[kanitool::ignore_global_asm(COUNTER)]`.
+    IgnoreGlobalAsm,
+    /// Verify the harness in a separate process, e.g. `#[kanitool::isolate = "process"]`.
+    Isolate,
     Proof,
+    /// The maximum depth of the recursive calls of a harness, e.g.
+    /// `#[kanitool::recursion_limit(5)]`.
+    RecursionLimit,
     ShouldPanic,
     Solver,
+    /// A pure function that contracts may call, e.g. in `#[kani::requires(is_sorted(v))]`. It
+    /// always executes its body, even if it has a contract that a harness replaces.
+    SpecFn,
+    /// The bound on the recursion of the spec functions that a harness reaches, e.g.
+    /// `#[kanitool::spec_unwind(3)]`.
+    SpecUnwind,
+    /// Verify each variant of an enum argument of a harness in a separate run, e.g.
+    /// `#[kanitool::split_on = "input"]`.
+    SplitOn,
     Stub,
+    /// Replace a type and its inherent methods by a model.
+    StubType,
     /// Attribute used to mark unstable APIs.
     Unstable,
     Unwind,
@@ -60,6 +99,16 @@ enum KaniAttributeKind {
     /// Attribute on a function with a contract that identifies the code
     /// implementing the recursive check for the harness.
     RecursionCheck,
+    /// Attribute on a foreign function that identifies the function generated
+    /// from its contract, which replaces the calls to the foreign function, e.g.
+    /// `#[kanitool::c_contract = "kani_c_contract_read"]`.
+    CContract,
+    /// Internal attribute of the contracts implementation. Identifies the
+    /// function declared in the body of a function with contract that
+    /// evaluates its preconditions, used to generate CBMC contracts.
+    CbmcRequiresWith,
+    /// Same as [`Self::CbmcRequiresWith`] but for the postconditions.
+    CbmcEnsuresWith,
     /// Attribute on a function that was auto-generated from expanding a
     /// function contract.
     IsContractGenerated,
@@ -88,21 +137,35 @@ impl KaniAttributeKind {
     pub fn is_harness_only(self) -> bool {
         match self {
             KaniAttributeKind::Proof
+            | KaniAttributeKind::ArrayEncoding
+            | KaniAttributeKind::CbmcChecks
+            | KaniAttributeKind::Checks
+            | KaniAttributeKind::Isolate
+            | KaniAttributeKind::RecursionLimit
             | KaniAttributeKind::ShouldPanic
             | KaniAttributeKind::Solver
             | KaniAttributeKind::Stub
+            | KaniAttributeKind::StubType
             | KaniAttributeKind::ProofForContract
             | KaniAttributeKind::StubVerified
+            | KaniAttributeKind::SpecUnwind
+            | KaniAttributeKind::SplitOn
             | KaniAttributeKind::Unwind => true,
-            KaniAttributeKind::Unstable
+            KaniAttributeKind::Autoharness
+            | KaniAttributeKind::IgnoreGlobalAsm
+            | KaniAttributeKind::SpecFn
+            | KaniAttributeKind::Unstable
             | KaniAttributeKind::FnMarker
             | KaniAttributeKind::Recursion
             | KaniAttributeKind::RecursionTracker
             | KaniAttributeKind::ReplacedWith
             | KaniAttributeKind::RecursionCheck
+            | KaniAttributeKind::CContract
             | KaniAttributeKind::CheckedWith
             | KaniAttributeKind::ModifiesWrapper
             | KaniAttributeKind::AssertedWith
+            | KaniAttributeKind::CbmcRequiresWith
+            | KaniAttributeKind::CbmcEnsuresWith
             | KaniAttributeKind::IsContractGenerated
             | KaniAttributeKind::DisableChecks => false,
         }
@@ -116,7 +179,7 @@ pub fn is_harness_only(self) -> bool {
     /// If we find an "inactive" contract attribute we chose not to error,
     /// because it wouldn't have any effect anyway.
     pub fn demands_function_contract_use(self) -> bool {
-        matches!(self, KaniAttributeKind::ProofForContract)
+        matches!(self, KaniAttributeKind::ProofForContract | KaniAttributeKind::CContract)
     }
 }
 
@@ -224,6 +287,91 @@ pub(crate) fn has_recursion(&self) -> bool {
         self.map.contains_key(&KaniAttributeKind::Recursion)
     }
 
+    /// The maximum depth of the recursive calls of this harness, if it has one.
+    pub(crate) fn recursion_limit(&self) -> Option<u32> {
+        self.expect_maybe_one(KaniAttributeKind::RecursionLimit)
+            .and_then(|attr| parse_unwind(self.tcx, KaniAttributeKind::RecursionLimit, attr))
+    }
+
+    /// The labels of the loops of this function and of its closures, without the leading `'`, with
+    /// the span of each loop.
+    pub(crate) fn loop_labels(&self) -> Vec<(String, Span)> {
+        let Some(local_def_id) = self.item.as_local() else { return vec![] };
+        let mut visitor = LoopLabels { tcx: self.tcx, labels: vec![] };
+        visitor.visit_body(self.tcx.hir_body_owned_by(local_def_id));
+        visitor.labels
+    }
+
+    /// The spans of the statements and expressions of this function, including its closures, that
+    /// are annotated with `#[kanitool::allow_truncation]`.
+    pub(crate) fn allowed_truncations(&self) -> Vec<Span> {
+        let Some(body) = self.item.as_local().and_then(|def| self.tcx.hir_maybe_body_owned_by(def))
+        else {
+            return vec![];
+        };
+        let mut visitor = AllowedTruncations { tcx: self.tcx, spans: vec![] };
+        visitor.visit_body(body);
+        visitor.spans
+    }
+
+    /// Is this module annotated with `#[kani::ignore_global_asm]`?
+    pub(crate) fn ignores_global_asm(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::IgnoreGlobalAsm)
+    }
+
+    /// The statics that the global assembly of this module may assign, which are the arguments of
+    /// `#[kani::ignore_global_asm(...)]`. Their paths are relative to the module, and they must be
+    /// declared in it.
+    ///
+    /// Any error is emitted and the static is filtered out.
+    pub(crate) fn global_asm_assigns(&self) -> Vec<DefId> {
+        let Some(attr) = self.map.get(&KaniAttributeKind::IgnoreGlobalAsm).map(|attrs| attrs[0])
+        else {
+            return vec![];
+        };
+        if attr.is_word() {
+            return vec![];
+        }
+        let paths = parse_paths(self.tcx, attr).unwrap_or_else(|_| {
+            self.tcx.dcx().span_err(
+                attr.span(),
+                "attribute `kani::ignore_global_asm` takes path arguments; found argument that \
+                is not a path",
+            );
+            vec![]
+        });
+        paths
+            .iter()
+            .filter_map(|path| {
+                let def_id = resolve_static_path(self.tcx, self.item, path)
+                    .map_err(|err| {
+                        self.tcx.dcx().span_err(
+                            attr.span(),
+                            format!("failed to resolve `{}`: {err}", pretty_type_path(path)),
+                        )
+                    })
+                    .ok()?;
+                if !self.tcx.is_descendant_of(def_id, self.item) {
+                    self.tcx.dcx().span_err(
+                        attr.span(),
+                        format!(
+                            "static `{}` is not declared in module `{}`",
+                            self.tcx.def_path_str(def_id),
+                            self.tcx.def_path_str(self.item)
+                        ),
+                    );
+                    return None;
+                }
+                Some(def_id)
+            })
+            .collect()
+    }
+
+    /// Is this function annotated with `#[kani::spec_fn]`?
+    pub(crate) fn is_spec_fn(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::SpecFn)
+    }
+
     /// Parse and extract the `proof_for_contract(TARGET)` attribute. The
     /// returned symbol and DefId are respectively the name and id of `TARGET`,
     /// the span in the span for the attribute (contents).
@@ -278,6 +426,28 @@ pub fn contract_attributes(&self) -> Option<ContractAttributes> {
         })
     }
 
+    /// The names of the closures that evaluate the pre- and postconditions of this function's
+    /// contract, which are used to encode it as a CBMC contract.
+    ///
+    /// `None` indicates this function has no contract, or that its contract cannot be encoded
+    /// this way, e.g., because it has a `modifies` clause.
+    pub fn cbmc_contract(&self) -> Option<(Symbol, Symbol)> {
+        Some((
+            self.attribute_value(KaniAttributeKind::CbmcRequiresWith)?,
+            self.attribute_value(KaniAttributeKind::CbmcEnsuresWith)?,
+        ))
+    }
+
+    /// Resolve the function generated from the contract of this foreign function, if it has one.
+    ///
+    /// Any error is emitted and `None` is returned.
+    pub fn c_contract(&self) -> Option<FnDefStable> {
+        self.expect_maybe_one(KaniAttributeKind::CContract).and_then(|attr| {
+            let target = self.parse_single_path_attr(attr).ok()?;
+            Some(target.def().to_owned())
+        })
+    }
+
     // Is this a function inserted by Kani instrumentation?
     pub fn is_kani_instrumentation(&self) -> bool {
         self.fn_marker().is_some() || self.is_contract_generated()
@@ -299,6 +469,20 @@ pub fn has_contract(&self) -> bool {
         self.map.contains_key(&KaniAttributeKind::CheckedWith)
     }
 
+    /// The documentation comments of the item, with the space that follows `///` removed.
+    pub fn docs(&self) -> String {
+        self.tcx
+            .get_all_attrs(self.item)
+            .iter()
+            .filter_map(|attr| attr.doc_str())
+            .map(|doc| {
+                let doc = doc.as_str();
+                doc.strip_prefix(' ').unwrap_or(doc).trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Check that all attributes assigned to an item is valid.
     /// Errors will be added to the session. Invoke self.tcx.sess.abort_if_errors() to terminate
     /// the session and emit all errors found.
@@ -315,11 +499,52 @@ pub(super) fn check_attributes(&self) {
                 ));
             }
             match kind {
+                KaniAttributeKind::Autoharness => {
+                    if is_harness {
+                        local_error(
+                            "the `autoharness` attribute cannot be used on a proof harness"
+                                .to_string(),
+                        );
+                    }
+                    let attr = expect_single(self.tcx, kind, attrs);
+                    parse_autoharness(self.tcx, attr);
+                }
+                KaniAttributeKind::IgnoreGlobalAsm => {
+                    if self.tcx.def_kind(self.item) != DefKind::Mod {
+                        local_error(
+                            "the `ignore_global_asm` attribute can only be used on modules"
+                                .to_string(),
+                        );
+                    } else {
+                        expect_single(self.tcx, kind, attrs);
+                        self.global_asm_assigns();
+                    }
+                }
+                KaniAttributeKind::Isolate => {
+                    let attr = expect_single(self.tcx, kind, attrs);
+                    parse_isolation(self.tcx, attr);
+                }
+                KaniAttributeKind::ArrayEncoding => {
+                    let attr = expect_single(self.tcx, kind, attrs);
+                    parse_array_encoding(self.tcx, attr);
+                }
+                KaniAttributeKind::SplitOn => {
+                    let attr = expect_single(self.tcx, kind, attrs);
+                    let _ = expect_key_string_value(self.tcx.sess, attr);
+                }
+                KaniAttributeKind::CbmcChecks => {
+                    let attr = expect_single(self.tcx, kind, attrs);
+                    parse_cbmc_checks(self.tcx, attr);
+                }
+                KaniAttributeKind::Checks => {
+                    let attr = expect_single(self.tcx, kind, attrs);
+                    parse_checks(self.tcx, attr);
+                }
                 KaniAttributeKind::ShouldPanic => {
-                    expect_single(self.tcx, kind, attrs);
-                    attrs.iter().for_each(|attr| {
-                        expect_no_args(self.tcx, kind, attr);
-                    })
+                    let attr = expect_single(self.tcx, kind, attrs);
+                    if !attr.is_word() {
+                        let _ = expect_key_string_value(self.tcx.sess, attr);
+                    }
                 }
                 KaniAttributeKind::Recursion => {
                     expect_single(self.tcx, kind, attrs);
@@ -336,10 +561,43 @@ pub(super) fn check_attributes(&self) {
                 KaniAttributeKind::Stub => {
                     self.parse_stubs(attrs);
                 }
+                KaniAttributeKind::StubType => {
+                    self.parse_type_stubs(attrs);
+                }
                 KaniAttributeKind::Unwind => {
+                    let attr = expect_single(self.tcx, kind, attrs);
+                    let (_, loop_bounds) = parse_unwind_bounds(self.tcx, attr);
+                    if !loop_bounds.is_empty() {
+                        let loop_labels = self.loop_labels();
+                        for label in loop_bounds.keys() {
+                            if !loop_labels.iter().any(|(loop_label, _)| loop_label == label) {
+                                self.tcx
+                                    .dcx()
+                                    .struct_span_err(
+                                        attr.span(),
+                                        format!("no loop labeled `'{label}` in this harness"),
+                                    )
+                                    .with_help("label the loop, e.g. `'outer: loop { ... }`")
+                                    .emit();
+                            }
+                        }
+                    }
+                }
+                KaniAttributeKind::SpecUnwind | KaniAttributeKind::RecursionLimit => {
                     expect_single(self.tcx, kind, attrs);
                     attrs.iter().for_each(|attr| {
-                        parse_unwind(self.tcx, attr);
+                        parse_unwind(self.tcx, kind, attr);
+                    })
+                }
+                KaniAttributeKind::SpecFn => {
+                    if is_harness {
+                        local_error(
+                            "the `spec_fn` attribute cannot be used on a proof harness".to_string(),
+                        );
+                    }
+                    expect_single(self.tcx, kind, attrs);
+                    attrs.iter().for_each(|attr| {
+                        expect_no_args(self.tcx, kind, attr);
                     })
                 }
                 KaniAttributeKind::Proof => {
@@ -371,11 +629,23 @@ pub(super) fn check_attributes(&self) {
                         self.check_stub_verified(attr);
                     });
                 }
+                KaniAttributeKind::CContract => {
+                    if !self.tcx.is_foreign_item(self.item) {
+                        local_error(
+                            "the `c_contract` attribute can only be used on functions declared in \
+                            an `extern` block"
+                                .to_string(),
+                        );
+                    }
+                    self.c_contract();
+                }
                 KaniAttributeKind::FnMarker
                 | KaniAttributeKind::CheckedWith
                 | KaniAttributeKind::ModifiesWrapper
                 | KaniAttributeKind::RecursionCheck
                 | KaniAttributeKind::AssertedWith
+                | KaniAttributeKind::CbmcRequiresWith
+                | KaniAttributeKind::CbmcEnsuresWith
                 | KaniAttributeKind::ReplacedWith => {
                     self.attribute_value(kind);
                 }
@@ -513,7 +783,39 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
         };
         self.map.iter().fold(harness_attrs, |mut harness, (kind, attributes)| {
             match kind {
-                KaniAttributeKind::ShouldPanic => harness.should_panic = true,
+                KaniAttributeKind::Autoharness => {
+                    // Only used for automatic harnesses, and rejected on proof harnesses.
+                }
+                KaniAttributeKind::CContract => {
+                    // Only used on foreign functions, which is validated in `check_attributes`.
+                }
+                KaniAttributeKind::Isolate => {
+                    harness.isolation = parse_isolation(self.tcx, attributes[0]);
+                }
+                KaniAttributeKind::ArrayEncoding => {
+                    harness.array_encoding = parse_array_encoding(self.tcx, attributes[0]);
+                }
+                KaniAttributeKind::SplitOn => {
+                    harness.split_on = expect_key_string_value(self.tcx.sess, attributes[0])
+                        .ok()
+                        .map(|input| input.to_string());
+                }
+                KaniAttributeKind::CbmcChecks => {
+                    harness.cbmc_checks = parse_cbmc_checks(self.tcx, attributes[0]);
+                }
+                KaniAttributeKind::Checks => {
+                    harness.checks = parse_checks(self.tcx, attributes[0]);
+                }
+                KaniAttributeKind::ShouldPanic => {
+                    harness.should_panic = true;
+                    harness.expected_panic = if attributes[0].is_word() {
+                        None
+                    } else {
+                        expect_key_string_value(self.tcx.sess, attributes[0])
+                            .ok()
+                            .map(|message| message.to_string())
+                    };
+                }
                 KaniAttributeKind::Recursion => {
                     self.tcx.dcx().span_err(self.tcx.def_span(self.item), "The attribute `kani::recursion` should only be used in combination with function contracts.");
                 }
@@ -523,8 +825,18 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
                 KaniAttributeKind::Stub => {
                     harness.stubs.extend_from_slice(&self.parse_stubs(attributes));
                 }
+                KaniAttributeKind::StubType => {
+                    harness.type_stubs.extend_from_slice(&self.parse_type_stubs(attributes));
+                }
                 KaniAttributeKind::Unwind => {
-                    harness.unwind_value = parse_unwind(self.tcx, attributes[0])
+                    (harness.unwind_value, harness.loop_unwind) =
+                        parse_unwind_bounds(self.tcx, attributes[0])
+                }
+                KaniAttributeKind::SpecUnwind => {
+                    harness.spec_unwind = parse_unwind(self.tcx, *kind, attributes[0])
+                }
+                KaniAttributeKind::RecursionLimit => {
+                    harness.recursion_limit = parse_unwind(self.tcx, *kind, attributes[0])
                 }
                 KaniAttributeKind::Proof => { /* no-op */ }
                 KaniAttributeKind::ProofForContract => self.handle_proof_for_contract(attributes[0]),
@@ -539,6 +851,8 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
                 | KaniAttributeKind::RecursionCheck
                 | KaniAttributeKind::RecursionTracker
                 | KaniAttributeKind::AssertedWith
+                | KaniAttributeKind::CbmcRequiresWith
+                | KaniAttributeKind::CbmcEnsuresWith
                 | KaniAttributeKind::ReplacedWith => {
                     self.tcx.dcx().span_err(self.tcx.def_span(self.item), format!("Contracts are not supported on harnesses. (Found the kani-internal contract attribute `{}`)", kind.as_ref()));
                 }
@@ -546,7 +860,11 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
                     // Internal attribute which shouldn't exist here.
                     unreachable!()
                 }
-                KaniAttributeKind::FnMarker => {
+                KaniAttributeKind::IgnoreGlobalAsm => {
+                    // Only used on modules, which cannot be harnesses.
+                    unreachable!()
+                }
+                KaniAttributeKind::FnMarker | KaniAttributeKind::SpecFn => {
                     /* no-op */
                 }
             };
@@ -554,6 +872,19 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
         })
     }
 
+    /// Extract the attributes of the automatic harness generated for this function, which can be
+    /// configured with `#[kani::autoharness(...)]`.
+    pub fn autoharness_attributes(&self, kind: HarnessKind) -> HarnessAttributes {
+        let mut harness = HarnessAttributes::new(kind);
+        if let Some(attr) = self.expect_maybe_one(KaniAttributeKind::Autoharness) {
+            let options = parse_autoharness(self.tcx, attr);
+            harness.unwind_value = options.unwind;
+            harness.solver = options.solver;
+            harness.timeout = options.timeout;
+        }
+        harness
+    }
+
     fn handle_proof_for_contract(&self, attr: &Attribute) {
         let target_def = match self.interpret_for_contract_attribute() {
             None => return, // This error was already emitted
@@ -614,6 +945,20 @@ fn check_stub_verified(&self, attr: &Attribute) {
                         ),
                     )
                     .emit();
+            } else if KaniAttributes::for_def_id(self.tcx, stub_target.def_id()).is_spec_fn() {
+                dcx.struct_span_err(
+                    attr.span(),
+                    format!(
+                        "Target function in stub_verified, `{}`, is a spec function.",
+                        stub_target.trimmed_name()
+                    ),
+                )
+                .with_span_note(
+                    rustc_internal::internal(self.tcx, stub_target.span()),
+                    "Calls to spec functions always execute their body, so their contract cannot \
+                    replace them.",
+                )
+                .emit();
             }
         }
     }
@@ -722,7 +1067,14 @@ fn parse_single_path_attr(
         });
 
         match path {
-            Ok(path) => self.resolve_path(current_module, &path, attr.span()),
+            Ok(path) => {
+                // Functions declared in the same body as this item take precedence, as they
+                // would for a regular Rust path.
+                if let Some(def) = resolve_fn_in_body(self.tcx, self.tcx.parent(self.item), &path) {
+                    return Ok(FnResolution::Fn(def));
+                }
+                self.resolve_path(current_module, &path, attr.span())
+            }
             Err(err) => {
                 self.tcx.dcx().span_err(attr.span(), err.to_string());
                 Err(err)
@@ -808,6 +1160,60 @@ fn parse_stubs(&self, attributes: &[&'tcx Attribute]) -> Vec<Stub> {
         })
         .collect()
     }
+
+    /// Parse the `kani::stub_type` attributes. Both paths must resolve to a struct, enum or union.
+    fn parse_type_stubs(&self, attributes: &[&'tcx Attribute]) -> Vec<Stub> {
+        let current_module =
+            self.tcx.parent_module_from_def_id(self.item.expect_local()).to_local_def_id();
+        let resolve = |path: &TypePath, span: Span| {
+            resolve_type_def_path(self.tcx, current_module, path).map_err(|err| {
+                self.tcx.dcx().span_err(
+                    span,
+                    format!("failed to resolve `{}`: {err}", pretty_type_path(path)),
+                );
+            })
+        };
+
+        attributes
+            .iter()
+            .filter_map(|attr| {
+                let paths = parse_paths(self.tcx, attr).unwrap_or_else(|_| {
+                    self.tcx.dcx().span_err(
+                        attr.span(),
+                        format!(
+                            "attribute `kani::{}` takes two path arguments; found argument that is not a path",
+                            KaniAttributeKind::StubType.as_ref()
+                        ),
+                    );
+                    vec![]
+                });
+                match paths.as_slice() {
+                    [orig, replace] => {
+                        let orig_res = resolve(orig, attr.span());
+                        let replace_res = resolve(replace, attr.span());
+                        (orig_res.is_ok() && replace_res.is_ok()).then(|| Stub {
+                            original: orig.to_token_stream().to_string(),
+                            replacement: replace.to_token_stream().to_string(),
+                        })
+                    }
+                    [] => {
+                        /* Error was already emitted */
+                        None
+                    }
+                    _ => {
+                        self.tcx.dcx().span_err(
+                            attr.span(),
+                            format!(
+                                "attribute `kani::stub_type` takes two path arguments; found {}",
+                                paths.len()
+                            ),
+                        );
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
 }
 
 /// An efficient check for the existence for a particular [`KaniAttributeKind`].
@@ -940,15 +1346,15 @@ fn expect_no_args(tcx: TyCtxt, kind: KaniAttributeKind, attr: &Attribute) {
     }
 }
 
-/// Return the unwind value from the given attribute.
-fn parse_unwind(tcx: TyCtxt, attr: &Attribute) -> Option<u32> {
+/// Return the unwind value from the given `unwind` or `spec_unwind` attribute.
+fn parse_unwind(tcx: TyCtxt, kind: KaniAttributeKind, attr: &Attribute) -> Option<u32> {
     // Get Attribute value and if it's not none, assign it to the metadata
     match parse_integer(attr) {
         None => {
             // There are no integers or too many arguments given to the attribute
             tcx.dcx().span_err(
                 attr.span(),
-                "invalid argument for `unwind` attribute, expected an integer",
+                format!("invalid argument for `{}` attribute, expected an integer", kind.as_ref()),
             );
             None
         }
@@ -963,6 +1369,116 @@ fn parse_unwind(tcx: TyCtxt, attr: &Attribute) -> Option<u32> {
     }
 }
 
+/// Parse `#[kanitool::unwind(...)]`, whose arguments are an optional unwind value for all the
+/// loops of the harness, followed by the unwind values of labeled loops, e.g.
+/// `#[kanitool::unwind(8, outer = 3, inner = 64)]`.
+fn parse_unwind_bounds(tcx: TyCtxt, attr: &Attribute) -> (Option<u32>, BTreeMap<String, u32>) {
+    let invalid_arg_err = || {
+        tcx.dcx().span_err(
+            attr.span(),
+            "invalid argument for `unwind` attribute, expected an integer or `<loop label> = <integer>`",
+        );
+    };
+    let to_u32 = |value: u128| {
+        u32::try_from(value)
+            .inspect_err(|_| {
+                tcx.dcx().span_err(attr.span(), "value above maximum permitted value - u32::MAX");
+            })
+            .ok()
+    };
+    let mut unwind = None;
+    let mut loop_bounds = BTreeMap::new();
+    let Some(args) = attr.meta_item_list().filter(|args| !args.is_empty()) else {
+        invalid_arg_err();
+        return (unwind, loop_bounds);
+    };
+    for (idx, arg) in args.iter().enumerate() {
+        match (arg.lit(), arg.meta_item()) {
+            (Some(MetaItemLit { kind: LitKind::Int(value, ..), .. }), _) if idx == 0 => {
+                unwind = to_u32(value.get());
+            }
+            (
+                _,
+                Some(MetaItem {
+                    path,
+                    kind: MetaItemKind::NameValue(MetaItemLit { kind: LitKind::Int(value, ..), .. }),
+                    ..
+                }),
+            ) if path.segments.len() == 1 => {
+                let label = path.segments[0].ident.to_string();
+                if let Some(bound) = to_u32(value.get())
+                    && loop_bounds.insert(label.clone(), bound).is_some()
+                {
+                    tcx.dcx().span_err(
+                        attr.span(),
+                        format!("duplicated unwind value for loop `'{label}`"),
+                    );
+                }
+            }
+            _ => invalid_arg_err(),
+        }
+    }
+    (unwind, loop_bounds)
+}
+
+/// Collect the labels of the loops of a body, including the loops of its closures.
+struct LoopLabels<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    labels: Vec<(String, Span)>,
+}
+
+impl<'tcx> Visitor<'tcx> for LoopLabels<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+        self.tcx
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx rustc_hir::Expr<'tcx>) {
+        if let rustc_hir::ExprKind::Loop(_, Some(label), _, _) = expr.kind {
+            let name = label.ident.name.to_string();
+            self.labels.push((name.trim_start_matches('\'').to_string(), expr.span));
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Collect the spans of the statements and expressions of a body that are annotated with
+/// `#[kanitool::allow_truncation]`, including the ones of its closures.
+struct AllowedTruncations<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    spans: Vec<Span>,
+}
+
+impl AllowedTruncations<'_> {
+    fn is_allowed(&self, hir_id: rustc_hir::HirId) -> bool {
+        let path = [Symbol::intern("kanitool"), Symbol::intern("allow_truncation")];
+        self.tcx.hir_attrs(hir_id).iter().any(|attr| attr.path_matches(&path))
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for AllowedTruncations<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+        self.tcx
+    }
+
+    fn visit_local(&mut self, local: &'tcx rustc_hir::LetStmt<'tcx>) {
+        if self.is_allowed(local.hir_id) {
+            self.spans.push(local.span);
+        }
+        intravisit::walk_local(self, local);
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx rustc_hir::Expr<'tcx>) {
+        if self.is_allowed(expr.hir_id) {
+            self.spans.push(expr.span);
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
 fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcSolver> {
     // TODO: Argument validation should be done as part of the `kani_macros` crate
     // <https://github.com/model-checking/kani/issues/2192>
@@ -1008,6 +1524,9 @@ fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcSolver> {
         MetaItemKind::NameValue(lit) if ident_str == "bin" && lit.kind.is_str() => {
             Some(CbmcSolver::Binary(lit.symbol.to_string()))
         }
+        MetaItemKind::NameValue(lit) if ident_str == "smt" && lit.kind.is_str() => {
+            Some(CbmcSolver::Smt(lit.symbol.to_string()))
+        }
         _ => {
             invalid_arg_err(attr);
             None
@@ -1015,6 +1534,237 @@ fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcSolver> {
     }
 }
 
+/// Return how the harness must be isolated from `#[kanitool::isolate = "<isolation>"]`.
+fn parse_isolation(tcx: TyCtxt, attr: &Attribute) -> Option<Isolation> {
+    let value = expect_key_string_value(tcx.sess, attr).ok()?;
+    match Isolation::from_str(value.as_str()) {
+        Ok(isolation) => Some(isolation),
+        Err(_) => {
+            tcx.dcx().span_err(
+                attr.span(),
+                format!("invalid isolation `{value}`, expected `isolate = \"process\"`"),
+            );
+            None
+        }
+    }
+}
+
+/// Return how CBMC must encode the arrays of the harness from
+/// `#[kanitool::array_encoding = "<encoding>"]`.
+fn parse_array_encoding(tcx: TyCtxt, attr: &Attribute) -> Option<ArrayEncoding> {
+    let value = expect_key_string_value(tcx.sess, attr).ok()?;
+    match ArrayEncoding::from_str(value.as_str()) {
+        Ok(encoding) => Some(encoding),
+        Err(_) => {
+            tcx.dcx().span_err(
+                attr.span(),
+                format!(
+                    "invalid array encoding `{value}`, expected `array_encoding = \"uf\"` or `array_encoding = \"field_sensitive\"`"
+                ),
+            );
+            None
+        }
+    }
+}
+
+/// Parse `#[kanitool::cbmc_checks(enable = "<checks>", disable = "<checks>")]`, where each
+/// option is optional and takes a comma-separated list of CBMC checks.
+fn parse_cbmc_checks(tcx: TyCtxt, attr: &Attribute) -> CbmcChecks {
+    const ATTRIBUTE: &str = "#[kani::proof(cbmc_checks(...))]";
+    let mut checks = CbmcChecks::default();
+    let parser = Punctuated::<MetaNameValue, syn::Token![,]>::parse_terminated;
+    let args = match syn_attr(tcx, attr).parse_args_with(parser) {
+        Ok(args) => args,
+        Err(err) => {
+            tcx.dcx().span_err(attr.span(), format!("invalid argument for `{ATTRIBUTE}`: {err}"));
+            return checks;
+        }
+    };
+    for arg in args {
+        let key = arg.path.to_token_stream().to_string();
+        let list = match key.as_str() {
+            "enable" => &mut checks.enable,
+            "disable" => &mut checks.disable,
+            _ => {
+                tcx.dcx()
+                    .struct_span_err(
+                        attr.span(),
+                        format!("unknown option `{key}` for `{ATTRIBUTE}`"),
+                    )
+                    .with_help("the supported options are `enable` and `disable`")
+                    .emit();
+                continue;
+            }
+        };
+        let Expr::Lit(ExprLit { lit: Lit::Str(value), .. }) = &arg.value else {
+            tcx.dcx().span_err(
+                attr.span(),
+                format!(
+                    "invalid value `{}` for option `{key}` of `{ATTRIBUTE}`, expected a string",
+                    arg.value.to_token_stream()
+                ),
+            );
+            continue;
+        };
+        for name in value.value().split(',').map(str::trim) {
+            match CbmcCheck::from_str(name) {
+                Ok(check) => list.push(check),
+                Err(_) => {
+                    tcx.dcx()
+                        .struct_span_err(attr.span(), format!("unknown CBMC check `{name}`"))
+                        .with_help(format!(
+                            "the supported checks are `{}`",
+                            CbmcCheck::VARIANTS.join("`, `")
+                        ))
+                        .emit();
+                }
+            }
+        }
+    }
+    if let Some(check) = checks.enable.iter().find(|check| checks.disable.contains(check)) {
+        tcx.dcx().span_err(
+            attr.span(),
+            format!("the CBMC check `{check}` cannot be both enabled and disabled"),
+        );
+    }
+    checks
+}
+
+/// Parse `#[kanitool::checks(<category> = <bool>, ...)]`, which turns the given categories of
+/// checks on or off.
+fn parse_checks(tcx: TyCtxt, attr: &Attribute) -> HarnessChecks {
+    const ATTRIBUTE: &str = "#[kani::proof(checks(...))]";
+    let mut checks = HarnessChecks::default();
+    let parser = Punctuated::<MetaNameValue, syn::Token![,]>::parse_terminated;
+    let args = match syn_attr(tcx, attr).parse_args_with(parser) {
+        Ok(args) => args,
+        Err(err) => {
+            tcx.dcx().span_err(attr.span(), format!("invalid argument for `{ATTRIBUTE}`: {err}"));
+            return checks;
+        }
+    };
+    for arg in args {
+        let key = arg.path.to_token_stream().to_string();
+        let Ok(category) = CheckCategory::from_str(&key) else {
+            tcx.dcx()
+                .struct_span_err(attr.span(), format!("unknown category of checks `{key}`"))
+                .with_help(format!(
+                    "the supported categories are `{}`",
+                    CheckCategory::VARIANTS.join("`, `")
+                ))
+                .emit();
+            continue;
+        };
+        let Expr::Lit(ExprLit { lit: Lit::Bool(value), .. }) = &arg.value else {
+            tcx.dcx().span_err(
+                attr.span(),
+                format!(
+                    "invalid value `{}` for option `{key}` of `{ATTRIBUTE}`, expected `true` or \
+                    `false`",
+                    arg.value.to_token_stream()
+                ),
+            );
+            continue;
+        };
+        if checks.enable.contains(&category) || checks.disable.contains(&category) {
+            tcx.dcx().span_err(
+                attr.span(),
+                format!("the category `{key}` is set more than once in `{ATTRIBUTE}`"),
+            );
+        } else if value.value {
+            checks.enable.push(category);
+        } else {
+            checks.disable.push(category);
+        }
+    }
+    checks
+}
+
+/// The options of a `#[kani::autoharness(...)]` attribute.
+#[derive(Debug, Default)]
+struct AutoharnessOptions {
+    unwind: Option<u32>,
+    solver: Option<CbmcSolver>,
+    timeout: Option<Duration>,
+}
+
+/// Parse `#[kanitool::autoharness(unwind = <u32>, solver = <solver>, timeout = "<duration>")]`,
+/// where every option is optional.
+fn parse_autoharness(tcx: TyCtxt, attr: &Attribute) -> AutoharnessOptions {
+    const ATTRIBUTE: &str = "#[kani::autoharness]";
+    let mut options = AutoharnessOptions::default();
+    if attr.is_word() {
+        return options;
+    }
+    let parser = Punctuated::<MetaNameValue, syn::Token![,]>::parse_terminated;
+    let args = match syn_attr(tcx, attr).parse_args_with(parser) {
+        Ok(args) => args,
+        Err(err) => {
+            tcx.dcx().span_err(attr.span(), format!("invalid argument for `{ATTRIBUTE}`: {err}"));
+            return options;
+        }
+    };
+    for arg in args {
+        let key = arg.path.to_token_stream().to_string();
+        match (key.as_str(), &arg.value) {
+            ("unwind", Expr::Lit(ExprLit { lit: Lit::Int(value), .. })) => {
+                match value.base10_parse() {
+                    Ok(value) => options.unwind = Some(value),
+                    Err(_) => {
+                        tcx.dcx().span_err(
+                            attr.span(),
+                            "value above maximum permitted value - u32::MAX",
+                        );
+                    }
+                }
+            }
+            ("solver", Expr::Path(path)) => {
+                let name = path.to_token_stream().to_string();
+                match CbmcSolver::from_str(&name) {
+                    Ok(solver) => options.solver = Some(solver),
+                    Err(_) => {
+                        tcx.dcx().span_err(attr.span(), format!("unknown solver `{name}`"));
+                    }
+                }
+            }
+            ("timeout", Expr::Lit(ExprLit { lit: Lit::Str(value), .. })) => {
+                match Timeout::from_str(&value.value()) {
+                    Ok(timeout) => options.timeout = Some(timeout.into()),
+                    Err(err) => {
+                        tcx.dcx()
+                            .struct_span_err(
+                                attr.span(),
+                                format!("invalid timeout `{}` in `{ATTRIBUTE}`", value.value()),
+                            )
+                            .with_note(err)
+                            .with_help("use a number followed by `s`, `m` or `h`, e.g. \"120s\"")
+                            .emit();
+                    }
+                }
+            }
+            ("unwind" | "solver" | "timeout", value) => {
+                tcx.dcx().span_err(
+                    attr.span(),
+                    format!(
+                        "invalid value `{}` for option `{key}` of `{ATTRIBUTE}`",
+                        value.to_token_stream()
+                    ),
+                );
+            }
+            _ => {
+                tcx.dcx()
+                    .struct_span_err(
+                        attr.span(),
+                        format!("unknown option `{key}` for `{ATTRIBUTE}`"),
+                    )
+                    .with_help("the supported options are `unwind`, `solver` and `timeout`")
+                    .emit();
+            }
+        }
+    }
+    options
+}
+
 /// Extracts the integer value argument from the attribute provided
 /// For example, `unwind(8)` return `Some(8)`
 fn parse_integer(attr: &Attribute) -> Option<u128> {
@@ -1119,7 +1869,7 @@ fn segments_str<'a, I>(segments: I) -> String
     where
         I: IntoIterator<Item = &'a PathSegment>,
     {
-        // We don't bother with path arguments for now since users shouldn't provide them.
+        // We don't bother pretty printing path arguments for now.
         segments
             .into_iter()
             .map(|segment| segment.to_token_stream().to_string())
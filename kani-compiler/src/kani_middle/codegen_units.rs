@@ -9,30 +9,37 @@
 
 use crate::args::{Arguments, ReachabilityType};
 use crate::kani_middle::attributes::{KaniAttributes, is_proof_harness};
-use crate::kani_middle::kani_functions::{KaniIntrinsic, KaniModel};
+use crate::kani_middle::kani_functions::{KaniFunction, KaniIntrinsic, KaniModel};
 use crate::kani_middle::metadata::{
-    gen_automatic_proof_metadata, gen_contracts_metadata, gen_proof_metadata,
+    AUTOHARNESS_SUFFIX, SHARED_MODEL_PREFIX, gen_automatic_proof_metadata, gen_contracts_metadata,
+    gen_proof_metadata, harnesses_dir,
 };
 use crate::kani_middle::reachability::filter_crate_items;
 use crate::kani_middle::stubbing::{check_compatibility, harness_stub_map};
-use crate::kani_middle::{can_derive_arbitrary, implements_arbitrary};
+use crate::kani_middle::{
+    can_derive_arbitrary, extern_c_ptr_elem_ty, implements_arbitrary, is_extern_c_entry_point,
+    stable_fn_def,
+};
 use crate::kani_queries::QueryDb;
 use fxhash::{FxHashMap, FxHashSet};
 use kani_metadata::{
-    ArtifactType, AssignsContract, AutoHarnessMetadata, AutoHarnessSkipReason, HarnessMetadata,
-    KaniMetadata, find_proof_harnesses,
+    ArtifactType, AssignsContract, AutoHarnessMetadata, AutoHarnessSkipReason, CbmcContracts,
+    ForeignFunction, HarnessKind, HarnessMetadata, KaniMetadata, ReachableFunction, Stub,
+    UnsafeOperation, UnsupportedFeature, find_proof_harnesses,
 };
 use regex::RegexSet;
 use rustc_hir::def_id::DefId;
 use rustc_middle::ty::TyCtxt;
+use rustc_public::mir::Body;
 use rustc_public::mir::mono::Instance;
 use rustc_public::rustc_internal;
-use rustc_public::ty::{FnDef, GenericArgKind, GenericArgs, RigidTy, Ty, TyKind};
+use rustc_public::ty::{AdtDef, FnDef, GenericArgKind, GenericArgs, RigidTy, Ty, TyKind};
 use rustc_public::{CrateDef, CrateItem};
 use rustc_public_bridge::IndexedVal;
 use rustc_session::config::OutputType;
+use rustc_span::Symbol;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::BufWriter;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
@@ -44,6 +51,9 @@
 /// A set of stubs.
 pub type Stubs = HashMap<FnDef, FnDef>;
 
+/// A set of types that are replaced by a model.
+pub type TypeStubs = HashMap<AdtDef, AdtDef>;
+
 static AUTOHARNESS_MD: OnceLock<AutoHarnessMetadata> = OnceLock::new();
 
 /// Store some relevant information about the crate compilation.
@@ -64,6 +74,8 @@ pub struct CodegenUnits {
 pub struct CodegenUnit {
     pub harnesses: Vec<Harness>,
     pub stubs: Stubs,
+    /// The stubbed types. Their methods are already included in `stubs`.
+    pub type_stubs: TypeStubs,
 }
 
 impl CodegenUnits {
@@ -74,23 +86,45 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt) -> Self {
         let args = queries.args();
         match args.reachability_analysis {
             ReachabilityType::Harnesses => {
-                let all_harnesses = determine_targets(
-                    get_all_manual_harnesses(tcx, base_filename),
-                    &args.harnesses,
-                    args.exact,
+                let manual_harnesses =
+                    get_all_manual_harnesses(tcx, base_filename, &args.harness_stubs);
+                let existing_dirs = harness_dirs(manual_harnesses.values());
+                let mut all_harnesses =
+                    determine_targets(manual_harnesses, &args.harnesses, args.exact);
+                if args.defer_isolated_harnesses {
+                    // These harnesses are compiled by a separate compiler invocation, so they
+                    // don't have a goto file in the metadata of this one.
+                    all_harnesses
+                        .values_mut()
+                        .filter(|metadata| metadata.attributes.isolation.is_some())
+                        .for_each(|metadata| metadata.goto_file = None);
+                }
+                let codegen_harnesses = all_harnesses
+                    .iter()
+                    .filter(|(_, metadata)| metadata.goto_file.is_some())
+                    .map(|(harness, metadata)| (*harness, metadata.clone()))
+                    .collect();
+                // We don't know which functions the automatic harnesses verify in this mode, so we
+                // keep their artifacts.
+                prepare_harness_dirs(
+                    base_filename,
+                    &existing_dirs,
+                    &codegen_harnesses,
+                    true,
+                    !args.harnesses.is_empty(),
                 );
                 // Even if no_stubs is empty we still need to store rustc metadata.
-                let units = group_by_stubs(tcx, &all_harnesses);
+                let units = group_by_stubs(tcx, &codegen_harnesses);
                 validate_units(tcx, &units);
                 debug!(?units, "CodegenUnits::new");
                 CodegenUnits { units, harness_info: all_harnesses, crate_info }
             }
             ReachabilityType::AllFns => {
-                let mut all_harnesses = determine_targets(
-                    get_all_manual_harnesses(tcx, base_filename),
-                    &args.harnesses,
-                    args.exact,
-                );
+                let manual_harnesses =
+                    get_all_manual_harnesses(tcx, base_filename, &args.harness_stubs);
+                let mut existing_dirs = harness_dirs(manual_harnesses.values());
+                let mut all_harnesses =
+                    determine_targets(manual_harnesses, &args.harnesses, args.exact);
                 let mut units = group_by_stubs(tcx, &all_harnesses);
                 validate_units(tcx, &units);
 
@@ -98,11 +132,17 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt) -> Self {
                 let kani_harness_intrinsic =
                     kani_fns.get(&KaniIntrinsic::AutomaticHarness.into()).unwrap();
 
+                let trait_laws = if args.autoharness_traits {
+                    trait_law_instances(tcx, kani_fns)
+                } else {
+                    vec![]
+                };
                 let (chosen, skipped) = automatic_harness_partition(
                     tcx,
                     args,
                     &crate_info.name,
                     *kani_fns.get(&KaniModel::Any.into()).unwrap(),
+                    trait_laws,
                 );
                 AUTOHARNESS_MD
                     .set(AutoHarnessMetadata {
@@ -125,10 +165,19 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt) -> Self {
                         .map(|harness| CodegenUnit {
                             harnesses: vec![*harness],
                             stubs: HashMap::default(),
+                            type_stubs: HashMap::default(),
                         })
                         .collect::<Vec<_>>(),
                 );
                 all_harnesses.extend(automatic_harnesses.clone());
+                existing_dirs.extend(harness_dirs(automatic_harnesses.values()));
+                prepare_harness_dirs(
+                    base_filename,
+                    &existing_dirs,
+                    &all_harnesses,
+                    false,
+                    !args.harnesses.is_empty(),
+                );
 
                 // No need to validate the units again because validation only checks stubs, and we haven't added any stubs.
                 debug!(?units, "CodegenUnits::new");
@@ -156,6 +205,99 @@ pub fn store_modifies(&mut self, harness_modifies: &[(Harness, AssignsContract)]
         }
     }
 
+    /// We store which contracts are checked and replaced by CBMC for each harness.
+    pub fn store_cbmc_contracts(&mut self, harness_contracts: &[(Harness, CbmcContracts)]) {
+        for (harness, contracts) in harness_contracts {
+            self.harness_info.get_mut(harness).unwrap().cbmc_contracts = Some(contracts.clone());
+        }
+    }
+
+    /// We store the foreign functions that Kani does not model for each harness that reaches them.
+    pub fn store_foreign_functions(&mut self, harness_fns: &[(Harness, Vec<ForeignFunction>)]) {
+        for (harness, foreign_functions) in harness_fns {
+            self.harness_info.get_mut(harness).unwrap().foreign_functions =
+                foreign_functions.clone();
+        }
+    }
+
+    /// We store the unsafe operations that are reachable from each harness.
+    pub fn store_unsafe_operations(&mut self, harness_ops: &[(Harness, Vec<UnsafeOperation>)]) {
+        for (harness, unsafe_operations) in harness_ops {
+            self.harness_info.get_mut(harness).unwrap().unsafe_operations =
+                unsafe_operations.clone();
+        }
+    }
+
+    /// We store the symbols of the spec functions that are reachable from each harness.
+    pub fn store_spec_fns(&mut self, harness_fns: &[(Harness, Vec<String>)]) {
+        for (harness, spec_fns) in harness_fns {
+            self.harness_info.get_mut(harness).unwrap().spec_fns = spec_fns.clone();
+        }
+    }
+
+    /// We store the unsupported features that are reachable from each harness.
+    pub fn store_unsupported_features(
+        &mut self,
+        harness_features: &[(Harness, Vec<UnsupportedFeature>)],
+    ) {
+        for (harness, features) in harness_features {
+            self.harness_info.get_mut(harness).unwrap().unsupported_features = features.clone();
+        }
+    }
+
+    /// We store the functions that are reachable from each harness, if they were requested.
+    pub fn store_reachable_functions(&mut self, harness_fns: &[(Harness, Vec<ReachableFunction>)]) {
+        for (harness, functions) in harness_fns {
+            self.harness_info.get_mut(harness).unwrap().reachable_functions = functions.clone();
+        }
+    }
+
+    /// We store the number of elements of the largest array that each harness reaches.
+    pub fn store_largest_arrays(&mut self, largest_arrays: &[(Harness, u64)]) {
+        for (harness, len) in largest_arrays {
+            self.harness_info.get_mut(harness).unwrap().largest_array = Some(*len);
+        }
+    }
+
+    /// We store the number of dynamic calls of each harness that were replaced by direct calls.
+    pub fn store_devirtualized_calls(&mut self, devirtualized_calls: &[(Harness, u64)]) {
+        for (harness, calls) in devirtualized_calls {
+            self.harness_info.get_mut(harness).unwrap().devirtualized_calls = Some(*calls);
+        }
+    }
+
+    /// We store the CBMC ids of the labeled loops of each harness, indexed by label.
+    pub fn store_labeled_loops(
+        &mut self,
+        harness_loops: &[(Harness, BTreeMap<String, Vec<String>>)],
+    ) {
+        for (harness, loops) in harness_loops {
+            self.harness_info.get_mut(harness).unwrap().labeled_loops = loops.clone();
+        }
+    }
+
+    /// We store the model that each harness shares with the other harnesses of its unit.
+    pub fn store_shared_models(&mut self, harness_models: &[(Harness, PathBuf)]) {
+        for (harness, model) in harness_models {
+            self.harness_info.get_mut(harness).unwrap().shared_model = Some(model.clone());
+        }
+    }
+
+    /// Whether the harness can be compiled into the model that its unit shares, i.e., none of its
+    /// attributes affects how the code that it reaches is generated.
+    pub fn can_share_model(&self, harness: &Harness) -> bool {
+        let metadata = &self.harness_info[harness];
+        let attributes = &metadata.attributes;
+        !metadata.is_automatically_generated
+            && metadata.split_cases.is_empty()
+            && matches!(attributes.kind, HarnessKind::Proof | HarnessKind::Test)
+            && attributes.verified_stubs.is_empty()
+            && attributes.loop_unwind.is_empty()
+            && attributes.recursion_limit.is_none()
+            && attributes.checks.enable.is_empty()
+            && attributes.checks.disable.is_empty()
+    }
+
     /// We flag that the harness contains usage of loop contracts.
     pub fn store_loop_contracts(&mut self, harnesses: &[Harness]) {
         for harness in harnesses {
@@ -200,6 +342,16 @@ fn stub_def(tcx: TyCtxt, def_id: DefId) -> FnDef {
     }
 }
 
+fn stub_type_def(tcx: TyCtxt, def_id: DefId) -> AdtDef {
+    let ty_internal = tcx.type_of(def_id).instantiate_identity();
+    let ty = rustc_internal::stable(ty_internal);
+    if let TyKind::RigidTy(RigidTy::Adt(def, _)) = ty.kind() {
+        def
+    } else {
+        unreachable!("Expected stubbed type for `{:?}`, but found: {ty}", tcx.def_path(def_id))
+    }
+}
+
 /// Group the harnesses by their stubs and contract usage.
 fn group_by_stubs(
     tcx: TyCtxt,
@@ -207,13 +359,14 @@ fn group_by_stubs(
 ) -> Vec<CodegenUnit> {
     let mut per_stubs: HashMap<_, CodegenUnit> = HashMap::default();
     for (harness, metadata) in all_harnesses {
-        let stub_ids = harness_stub_map(tcx, *harness, metadata);
+        let (stub_ids, type_stub_ids) = harness_stub_map(tcx, *harness, metadata);
         let contracts = extract_contracts(tcx, *harness);
-        let stub_map = stub_ids
-            .iter()
-            .map(|(k, v)| (tcx.def_path_hash(*k), tcx.def_path_hash(*v)))
-            .collect::<BTreeMap<_, _>>();
-        let key = (contracts, stub_map);
+        let def_path_hashes = |ids: &HashMap<DefId, DefId>| {
+            ids.iter()
+                .map(|(k, v)| (tcx.def_path_hash(*k), tcx.def_path_hash(*v)))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let key = (contracts, def_path_hashes(&stub_ids), def_path_hashes(&type_stub_ids));
         if let Some(unit) = per_stubs.get_mut(&key) {
             unit.harnesses.push(*harness);
         } else {
@@ -222,7 +375,11 @@ fn group_by_stubs(
                 .map(|(from, to)| (stub_def(tcx, *from), stub_def(tcx, *to)))
                 .collect::<HashMap<_, _>>();
             let stubs = apply_transitivity(tcx, *harness, stubs);
-            per_stubs.insert(key, CodegenUnit { stubs, harnesses: vec![*harness] });
+            let type_stubs = type_stub_ids
+                .iter()
+                .map(|(from, to)| (stub_type_def(tcx, *from), stub_type_def(tcx, *to)))
+                .collect::<HashMap<_, _>>();
+            per_stubs.insert(key, CodegenUnit { stubs, type_stubs, harnesses: vec![*harness] });
         }
     }
     per_stubs.into_values().collect()
@@ -276,7 +433,7 @@ fn validate_units(tcx: TyCtxt, units: &[CodegenUnit]) {
     for unit in units {
         for (from, to) in &unit.stubs {
             // We use harness span since we don't keep the attribute span.
-            let Err(msg) = check_compatibility(tcx, *from, *to) else { continue };
+            let Err(msg) = check_compatibility(tcx, *from, *to, &unit.type_stubs) else { continue };
             let span = unit.harnesses.first().unwrap().def.span();
             tcx.dcx().span_err(rustc_internal::internal(tcx, span), msg);
         }
@@ -314,20 +471,89 @@ fn apply_transitivity(tcx: TyCtxt, harness: Harness, stubs: Stubs) -> Stubs {
 }
 
 /// Fetch all manual harnesses (i.e., functions provided by the user) and generate their metadata
+///
+/// The stubs of `harness_stubs` (see [Arguments::harness_stubs]) are added to the ones of the
+/// attributes of their harnesses.
 fn get_all_manual_harnesses(
     tcx: TyCtxt,
     base_filename: &Path,
+    harness_stubs: &[String],
 ) -> HashMap<Harness, HarnessMetadata> {
+    let mut stubs: HashMap<&str, Vec<Stub>> = HashMap::default();
+    for stub in harness_stubs {
+        let mut parts = stub.splitn(3, '=');
+        let (Some(harness), Some(original), Some(replacement)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            tcx.dcx().err(format!(
+                "invalid harness stub `{stub}`, expected `<HARNESS>=<ORIGINAL>=<REPLACEMENT>`"
+            ));
+            continue;
+        };
+        stubs
+            .entry(harness)
+            .or_default()
+            .push(Stub { original: original.into(), replacement: replacement.into() });
+    }
     let harnesses = filter_crate_items(tcx, |_, instance| is_proof_harness(tcx, instance));
     harnesses
         .into_iter()
         .map(|harness| {
-            let metadata = gen_proof_metadata(tcx, harness, base_filename);
+            let mut metadata = gen_proof_metadata(tcx, harness, base_filename);
+            if let Some(stubs) = stubs.get(metadata.pretty_name.as_str()) {
+                metadata.attributes.stubs.extend_from_slice(stubs);
+            }
             (harness, metadata)
         })
         .collect::<HashMap<_, _>>()
 }
 
+/// The artifact directories of the given harnesses.
+fn harness_dirs<'a>(harnesses: impl Iterator<Item = &'a HarnessMetadata>) -> HashSet<PathBuf> {
+    harnesses
+        .filter_map(|metadata| Some(metadata.goto_file.as_ref()?.parent()?.to_path_buf()))
+        .collect()
+}
+
+/// Create the artifact directory of every harness that we generate code for, and remove the
+/// directories that don't belong to any harness in `existing_dirs`, i.e., the artifacts of
+/// harnesses that were deleted or renamed since the last compilation.
+///
+/// If `keep_automatic` is set, the directories of automatic harnesses are never removed.
+///
+/// The directories of the models that harnesses share are removed too, since we generate the ones
+/// that are still shared again, unless only some harnesses are compiled. In that case, the other
+/// harnesses, e.g., the ones compiled by the process that defers a harness to this one, may still
+/// use them.
+fn prepare_harness_dirs(
+    base_filename: &Path,
+    existing_dirs: &HashSet<PathBuf>,
+    codegen_harnesses: &HashMap<Harness, HarnessMetadata>,
+    keep_automatic: bool,
+    keep_shared: bool,
+) {
+    if let Ok(entries) = fs::read_dir(harnesses_dir(base_filename)) {
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let is_automatic = path.to_str().is_some_and(|dir| dir.ends_with(AUTOHARNESS_SUFFIX));
+            let is_shared = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(SHARED_MODEL_PREFIX));
+            let keep = existing_dirs.contains(&path)
+                || (keep_automatic && is_automatic)
+                || (keep_shared && is_shared);
+            if !keep {
+                debug!(?path, "remove stale harness artifacts");
+                // The artifacts are only stale, so it's fine if we fail to remove them.
+                let _ = fs::remove_dir_all(&path);
+            }
+        }
+    }
+    for dir in harness_dirs(codegen_harnesses.values()) {
+        fs::create_dir_all(&dir).unwrap();
+    }
+}
+
 /// Filter which harnesses to codegen based on user filters. Shares use of `find_proof_harnesses` with the `determine_targets` function
 /// in `kani-driver/src/metadata.rs` to ensure the filter is consistent and thus codegen is always done for the subset of harnesses we want
 /// to analyze.
@@ -407,13 +633,149 @@ fn autoharness_filtered_out(
     !included || excluded
 }
 
+/// Find the `Eq`, `Ord`, `Hash` and `From` implementations of the crate for concrete types, and
+/// return the instances of the `kani::laws` checks for these types.
+/// `Hash` is only checked for types that also implement `Eq`, since its law relates the two traits.
+fn trait_law_instances(
+    tcx: TyCtxt,
+    kani_fns: &HashMap<KaniFunction, FnDef>,
+) -> Vec<(Instance, DefId)> {
+    let trait_id = |name: &str| tcx.get_diagnostic_item(Symbol::intern(name));
+    let local_impls = tcx.all_local_trait_impls(());
+    let impls_of = |name: &str| {
+        trait_id(name)
+            .and_then(|id| local_impls.get(&id))
+            .into_iter()
+            .flatten()
+            .map(|impl_id| impl_id.to_def_id())
+            .filter(|impl_id| !tcx.generics_of(*impl_id).requires_monomorphization(tcx))
+            .filter_map(|impl_id| {
+                let self_ty = tcx.impl_trait_ref(impl_id)?.instantiate_identity().self_ty();
+                Some((rustc_internal::stable(self_ty), impl_id))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let eq_impls = impls_of("Eq");
+    let eq_tys = eq_impls.iter().map(|(ty, _)| *ty).collect::<FxHashSet<_>>();
+    let laws = [
+        (KaniModel::CheckEqLaws, eq_impls),
+        (KaniModel::CheckOrdLaws, impls_of("Ord")),
+        (
+            KaniModel::CheckHashLaws,
+            impls_of("Hash").into_iter().filter(|(ty, _)| eq_tys.contains(ty)).collect(),
+        ),
+    ];
+    laws.into_iter()
+        .filter_map(|(law, impls)| Some((*kani_fns.get(&law.into())?, impls)))
+        .flat_map(|(law_def, impls)| {
+            impls.into_iter().filter_map(move |(ty, impl_id)| {
+                let args = GenericArgs(vec![GenericArgKind::Type(ty)]);
+                Some((Instance::resolve(law_def, &args).ok()?, impl_id))
+            })
+        })
+        .chain(conversion_law_instances(tcx, kani_fns))
+        .collect()
+}
+
+/// Find the `From<T> for U` implementations of the crate for concrete types where `T` also
+/// implements `TryFrom<U>`, and return the instances of the `kani::laws` check that the two
+/// conversions are inverses of each other.
+/// Both types must implement `Clone` and `PartialEq`, which the check uses to compare the values.
+fn conversion_law_instances(
+    tcx: TyCtxt,
+    kani_fns: &HashMap<KaniFunction, FnDef>,
+) -> Vec<(Instance, DefId)> {
+    let trait_fn = |trait_name: &str, fn_name: &str| {
+        let trait_id = tcx.get_diagnostic_item(Symbol::intern(trait_name))?;
+        let fn_id = tcx
+            .associated_item_def_ids(trait_id)
+            .iter()
+            .find(|item| tcx.item_name(**item).as_str() == fn_name)?;
+        stable_fn_def(tcx, *fn_id)
+    };
+    let (Some(law_def), Some(from_id), Some(try_from), Some(clone), Some(eq)) = (
+        kani_fns.get(&KaniModel::CheckConversionLaws.into()),
+        tcx.get_diagnostic_item(Symbol::intern("From")),
+        trait_fn("TryFrom", "try_from"),
+        trait_fn("Clone", "clone"),
+        trait_fn("PartialEq", "eq"),
+    ) else {
+        return vec![];
+    };
+    let implements = |def: FnDef, tys: &[Ty]| {
+        let args = GenericArgs(tys.iter().map(|ty| GenericArgKind::Type(*ty)).collect());
+        Instance::resolve(def, &args).is_ok()
+    };
+    let comparable = |ty: Ty| implements(clone, &[ty]) && implements(eq, &[ty, ty]);
+
+    tcx.all_local_trait_impls(())
+        .get(&from_id)
+        .into_iter()
+        .flatten()
+        .map(|impl_id| impl_id.to_def_id())
+        .filter(|impl_id| !tcx.generics_of(*impl_id).requires_monomorphization(tcx))
+        .filter_map(|impl_id| {
+            let trait_ref = tcx.impl_trait_ref(impl_id)?.instantiate_identity();
+            let into_ty = rustc_internal::stable(trait_ref.self_ty());
+            let from_ty = rustc_internal::stable(trait_ref.args.type_at(1));
+            if !implements(try_from, &[from_ty, into_ty])
+                || !comparable(from_ty)
+                || !comparable(into_ty)
+            {
+                return None;
+            }
+            let args =
+                GenericArgs(vec![GenericArgKind::Type(from_ty), GenericArgKind::Type(into_ty)]);
+            Some((Instance::resolve(*law_def, &args).ok()?, impl_id))
+        })
+        .collect()
+}
+
+/// Return the (name, type) of each argument of `body` that does not implement Arbitrary and cannot derive it.
+fn missing_arbitrary_args(
+    tcx: TyCtxt,
+    body: &Body,
+    models_c_ptrs: bool,
+    kani_any_def: FnDef,
+    ty_arbitrary_cache: &mut FxHashMap<Ty, bool>,
+) -> Vec<(String, String)> {
+    let mut problematic_args = vec![];
+    for (idx, arg) in body.arg_locals().iter().enumerate() {
+        let arg_ty = extern_c_ptr_elem_ty(tcx, arg.ty).filter(|_| models_c_ptrs).unwrap_or(arg.ty);
+        if !ty_arbitrary_cache.contains_key(&arg_ty) {
+            let impls_arbitrary = implements_arbitrary(arg_ty, kani_any_def, ty_arbitrary_cache)
+                || can_derive_arbitrary(arg_ty, kani_any_def, ty_arbitrary_cache);
+            ty_arbitrary_cache.insert(arg_ty, impls_arbitrary);
+        }
+        let impls_arbitrary = ty_arbitrary_cache.get(&arg_ty).unwrap();
+
+        if !impls_arbitrary {
+            // Find the name of the argument by referencing var_debug_info.
+            // Note that enumerate() starts at 0, while rustc_public argument_index starts at 1, hence the idx+1.
+            let arg_name = body
+                .var_debug_info
+                .iter()
+                .find(|var| {
+                    var.argument_index.is_some_and(|arg_idx| idx + 1 == usize::from(arg_idx))
+                })
+                .map_or("_".to_string(), |debug_info| debug_info.name.to_string());
+            let arg_type = format!("{}", arg.ty);
+            problematic_args.push((arg_name, arg_type))
+        }
+    }
+    problematic_args
+}
+
 /// Partition every function in the crate into (chosen, skipped), where `chosen` is a vector of the Instances for which we'll generate automatic harnesses,
 /// and `skipped` is a map of function names to the reason why we skipped them.
+/// The checks in `trait_laws`, along with the trait implementation they check, are partitioned the same way.
 fn automatic_harness_partition(
     tcx: TyCtxt,
     args: &Arguments,
     crate_name: &str,
     kani_any_def: FnDef,
+    trait_laws: Vec<(Instance, DefId)>,
 ) -> (Vec<Instance>, BTreeMap<String, AutoHarnessSkipReason>) {
     let crate_fn_defs = rustc_public::local_crate().fn_defs().into_iter().collect::<FxHashSet<_>>();
     // Filter out CrateItems that are functions, but not functions defined in the crate itself, i.e., rustc-inserted functions
@@ -428,6 +790,7 @@ fn automatic_harness_partition(
 
     let included_set = make_regex_set(args.autoharness_included_patterns.clone());
     let excluded_set = make_regex_set(args.autoharness_excluded_patterns.clone());
+    let excluded_files_set = make_regex_set(args.autoharness_excluded_file_patterns.clone());
 
     // Cache whether a type implements or can derive Arbitrary
     let mut ty_arbitrary_cache: FxHashMap<Ty, bool> = FxHashMap::default();
@@ -466,32 +829,37 @@ fn automatic_harness_partition(
             return Some(AutoHarnessSkipReason::UserFilter);
         }
 
-        // Each argument of `instance` must implement Arbitrary.
-        // Note that we've already filtered out generic functions, so we know that each of these arguments has a concrete type.
-        let mut problematic_args = vec![];
-        for (idx, arg) in body.arg_locals().iter().enumerate() {
-            if !ty_arbitrary_cache.contains_key(&arg.ty) {
-                let impls_arbitrary =
-                    implements_arbitrary(arg.ty, kani_any_def, &mut ty_arbitrary_cache)
-                        || can_derive_arbitrary(arg.ty, kani_any_def, &mut ty_arbitrary_cache);
-                ty_arbitrary_cache.insert(arg.ty, impls_arbitrary);
-            }
-            let impls_arbitrary = ty_arbitrary_cache.get(&arg.ty).unwrap();
+        if args.autoharness_public_only
+            && !tcx
+                .effective_visibilities(())
+                .is_exported(rustc_internal::internal(tcx, fn_item.def_id()).expect_local())
+        {
+            return Some(AutoHarnessSkipReason::NotPublic);
+        }
 
-            if !impls_arbitrary {
-                // Find the name of the argument by referencing var_debug_info.
-                // Note that enumerate() starts at 0, while rustc_public argument_index starts at 1, hence the idx+1.
-                let arg_name = body
-                    .var_debug_info
-                    .iter()
-                    .find(|var| {
-                        var.argument_index.is_some_and(|arg_idx| idx + 1 == usize::from(arg_idx))
-                    })
-                    .map_or("_".to_string(), |debug_info| debug_info.name.to_string());
-                let arg_type = format!("{}", arg.ty);
-                problematic_args.push((arg_name, arg_type))
-            }
+        // Exclude functions whose source is generated, either by a macro or in a file that the
+        // user excluded (e.g., modules generated by a build script).
+        let filename = fn_item.span().get_filename();
+        if excluded_files_set.as_ref().is_some_and(|set| set.is_match(&filename))
+            || (args.autoharness_exclude_expanded
+                && tcx.def_span(rustc_internal::internal(tcx, fn_item.def_id())).from_expansion())
+        {
+            return Some(AutoHarnessSkipReason::UserFilter);
         }
+
+        // Raw pointer arguments of exported `extern "C"` functions point to nondeterministic elements,
+        // so it suffices for the element type to implement Arbitrary.
+        let models_c_ptrs = args.autoharness_extern_c && is_extern_c_entry_point(tcx, instance);
+
+        // Each argument of `instance` must implement Arbitrary.
+        // Note that we've already filtered out generic functions, so we know that each of these arguments has a concrete type.
+        let problematic_args = missing_arbitrary_args(
+            tcx,
+            &body,
+            models_c_ptrs,
+            kani_any_def,
+            &mut ty_arbitrary_cache,
+        );
         if !problematic_args.is_empty() {
             return Some(AutoHarnessSkipReason::MissingArbitraryImpl(problematic_args));
         }
@@ -509,6 +877,32 @@ fn automatic_harness_partition(
         }
     }
 
+    // The checks of trait laws are subject to the same filters as the functions of the crate,
+    // where the source of a check is the trait implementation that it checks.
+    for (law, impl_id) in trait_laws {
+        let name = format!("{crate_name}::{}", law.name());
+        let expanded = tcx.def_span(impl_id).from_expansion();
+        let filename = rustc_internal::stable(tcx.def_span(impl_id)).get_filename();
+        let problematic_args = missing_arbitrary_args(
+            tcx,
+            &law.body().unwrap(),
+            false,
+            kani_any_def,
+            &mut ty_arbitrary_cache,
+        );
+        if autoharness_filtered_out(&name, &included_set, &excluded_set)
+            || excluded_files_set.as_ref().is_some_and(|set| set.is_match(&filename))
+            || (args.autoharness_exclude_expanded && expanded)
+        {
+            skipped.insert(law.name(), AutoHarnessSkipReason::UserFilter);
+        } else if !problematic_args.is_empty() {
+            skipped
+                .insert(law.name(), AutoHarnessSkipReason::MissingArbitraryImpl(problematic_args));
+        } else {
+            chosen.push(law);
+        }
+    }
+
     (chosen, skipped)
 }
 
@@ -44,6 +44,8 @@ pub enum KaniFunction {
 pub enum KaniIntrinsic {
     #[strum(serialize = "AnyModifiesIntrinsic")]
     AnyModifies,
+    #[strum(serialize = "AssumeInitRangeIntrinsic")]
+    AssumeInitRange,
     #[strum(serialize = "CheckedAlignOfIntrinsic")]
     CheckedAlignOf,
     #[strum(serialize = "CheckedSizeOfIntrinsic")]
@@ -67,14 +69,64 @@ pub enum KaniModel {
     AlignOfVal,
     #[strum(serialize = "AnyModel")]
     Any,
+    #[strum(serialize = "AnyCPtrModel")]
+    AnyCPtr,
+    #[strum(serialize = "CheckConversionLawsModel")]
+    CheckConversionLaws,
+    #[strum(serialize = "CheckEqLawsModel")]
+    CheckEqLaws,
+    #[strum(serialize = "CheckHashLawsModel")]
+    CheckHashLaws,
+    #[strum(serialize = "CheckOrdLawsModel")]
+    CheckOrdLaws,
+    #[strum(serialize = "CStrFromBytesWithNulModel")]
+    CStrFromBytesWithNul,
+    #[strum(serialize = "CStrToStrModel")]
+    CStrToStr,
     #[strum(serialize = "CopyInitStateModel")]
     CopyInitState,
+    #[strum(serialize = "CopyInitStateReallocModel")]
+    CopyInitStateRealloc,
     #[strum(serialize = "CopyInitStateSingleModel")]
     CopyInitStateSingle,
+    #[strum(serialize = "EnvRemoveVarModel")]
+    EnvRemoveVar,
+    #[strum(serialize = "EnvSetVarModel")]
+    EnvSetVar,
+    #[strum(serialize = "EnvVarModel")]
+    EnvVar,
+    #[strum(serialize = "EnvVarOsModel")]
+    EnvVarOs,
+    #[strum(serialize = "ExposeProvenanceModel")]
+    ExposeProvenance,
+    #[strum(serialize = "FsReadModel")]
+    FsRead,
+    #[strum(serialize = "FsReadToStringModel")]
+    FsReadToString,
+    #[strum(serialize = "FsRemoveFileModel")]
+    FsRemoveFile,
+    #[strum(serialize = "FsWriteModel")]
+    FsWrite,
+    #[strum(serialize = "HasExposedProvenanceModel")]
+    HasExposedProvenance,
+    #[strum(serialize = "HasValidValuesModel")]
+    HasValidValues,
+    #[strum(serialize = "HasherFinishModel")]
+    HasherFinish,
+    #[strum(serialize = "HasherWriteModel")]
+    HasherWrite,
+    #[strum(serialize = "InstantNowModel")]
+    InstantNow,
     #[strum(serialize = "LoadArgumentModel")]
     LoadArgument,
+    #[strum(serialize = "InitializeLeakStateModel")]
+    InitializeLeakState,
     #[strum(serialize = "InitializeMemoryInitializationStateModel")]
     InitializeMemoryInitializationState,
+    #[strum(serialize = "InitializeProvenanceStateModel")]
+    InitializeProvenanceState,
+    #[strum(serialize = "IsLeakFreeModel")]
+    IsLeakFree,
     #[strum(serialize = "IsPtrInitializedModel")]
     IsPtrInitialized,
     #[strum(serialize = "IsStrPtrInitializedModel")]
@@ -83,22 +135,40 @@ pub enum KaniModel {
     IsSliceChunkPtrInitialized,
     #[strum(serialize = "IsSlicePtrInitializedModel")]
     IsSlicePtrInitialized,
+    #[strum(serialize = "IsWithinAllocationModel")]
+    IsWithinAllocation,
     #[strum(serialize = "OffsetModel")]
     Offset,
     #[strum(serialize = "PtrOffsetFromModel")]
     PtrOffsetFrom,
     #[strum(serialize = "PtrOffsetFromUnsignedModel")]
     PtrOffsetFromUnsigned,
+    #[strum(serialize = "RecordAllocationModel")]
+    RecordAllocation,
+    #[strum(serialize = "RecordDeallocationModel")]
+    RecordDeallocation,
+    #[strum(serialize = "RecursionEnterModel")]
+    RecursionEnter,
+    #[strum(serialize = "RecursionExitModel")]
+    RecursionExit,
     #[strum(serialize = "RunContractModel")]
     RunContract,
     #[strum(serialize = "RunLoopContractModel")]
     RunLoopContract,
     #[strum(serialize = "SetPtrInitializedModel")]
     SetPtrInitialized,
+    #[strum(serialize = "SetPtrInitializedPreservingModel")]
+    SetPtrInitializedPreserving,
     #[strum(serialize = "SetSliceChunkPtrInitializedModel")]
     SetSliceChunkPtrInitialized,
+    #[strum(serialize = "SetSliceChunkPtrInitializedPreservingModel")]
+    SetSliceChunkPtrInitializedPreserving,
     #[strum(serialize = "SetSlicePtrInitializedModel")]
     SetSlicePtrInitialized,
+    #[strum(serialize = "SetSlicePtrInitializedPreservingModel")]
+    SetSlicePtrInitializedPreserving,
+    #[strum(serialize = "StaysInAllocationModel")]
+    StaysInAllocation,
     #[strum(serialize = "PanicStub")]
     PanicStub,
     #[strum(serialize = "SetStrPtrInitializedModel")]
@@ -109,6 +179,8 @@ pub enum KaniModel {
     SizeOfSliceObject,
     #[strum(serialize = "SizeOfValRawModel")]
     SizeOfVal,
+    #[strum(serialize = "SystemTimeNowModel")]
+    SystemTimeNow,
     #[strum(serialize = "StoreArgumentModel")]
     StoreArgument,
     #[strum(serialize = "WriteAnySliceModel")]
@@ -137,6 +209,8 @@ pub enum KaniHook {
     Cover,
     #[strum(serialize = "ExistsHook")]
     Exists,
+    #[strum(serialize = "ExpectPanicHook")]
+    ExpectPanic,
     #[strum(serialize = "ForallHook")]
     Forall,
     // TODO: this is temporarily implemented as a hook, but should be implemented as an intrinsic
@@ -146,16 +220,24 @@ pub enum KaniHook {
     InitContracts,
     #[strum(serialize = "IsAllocatedHook")]
     IsAllocated,
+    #[strum(serialize = "IsWritableHook")]
+    IsWritable,
     #[strum(serialize = "PanicHook")]
     Panic,
     #[strum(serialize = "PointerObjectHook")]
     PointerObject,
     #[strum(serialize = "PointerOffsetHook")]
     PointerOffset,
+    #[strum(serialize = "ProbeHook")]
+    Probe,
+    #[strum(serialize = "RecursionLimitCheckHook")]
+    RecursionLimitCheck,
     #[strum(serialize = "SafetyCheckHook")]
     SafetyCheck,
     #[strum(serialize = "SafetyCheckNoAssumeHook")]
     SafetyCheckNoAssume,
+    #[strum(serialize = "SplitCaseHook")]
+    SplitCase,
     #[strum(serialize = "UnsupportedCheckHook")]
     UnsupportedCheck,
     #[strum(serialize = "UntrackedDerefHook")]
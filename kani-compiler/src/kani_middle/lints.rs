@@ -0,0 +1,322 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Lints that point users to common mistakes when writing harnesses.
+//!
+//! These lints are best effort. They only emit warnings, and they may miss cases that would be
+//! caught by a more precise analysis.
+
+use rustc_middle::ty::TyCtxt;
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::visit::{Location, PlaceContext};
+use rustc_public::mir::{
+    AssertMessage, BasicBlockIdx, BinOp, Body, Local, MirVisitor, Operand, Place, Rvalue,
+    Statement, StatementKind, Terminator, TerminatorKind, UnOp,
+};
+use rustc_public::rustc_internal;
+use rustc_public::ty::{ConstantKind, FnDef, RigidTy, Span, Ty, TyKind, UintTy};
+use std::collections::{HashMap, HashSet};
+
+/// Warn about symbolic indices that are not constrained before indexing a slice or an array.
+///
+/// An index created with `kani::any::<usize>()` can take any value, so indexing with it
+/// will almost certainly fail the bounds check. This is a common source of unexpected failures,
+/// which can be avoided by using `kani::any_usize_index()` instead.
+///
+/// We only warn if the symbolic value is not used anywhere other than the indexing operation,
+/// e.g., if it isn't compared against the length in a `kani::assume`.
+pub fn check_unconstrained_index(tcx: TyCtxt, kani_any: FnDef, instance: Instance) {
+    let Some(body) = instance.body() else { return };
+    let mut checker = UnconstrainedIndex::new(kani_any, &body);
+    if checker.nondet.is_empty() {
+        return;
+    }
+    checker.visit_body(&body);
+    for local in checker.indexed.difference(&checker.used) {
+        let span = checker.nondet[local];
+        tcx.dcx()
+            .struct_span_warn(
+                rustc_internal::internal(tcx, span),
+                "symbolic index is used to index a slice or array without being constrained",
+            )
+            .with_help(
+                "use `kani::any_usize_index(&slice)` to create an index that is always in \
+                bounds, or constrain the value with `kani::assume`",
+            )
+            .emit();
+    }
+}
+
+struct UnconstrainedIndex {
+    /// The locals that store a `kani::any::<usize>()` result, and the span of that call.
+    nondet: HashMap<Local, Span>,
+    /// Locals that are plain copies of a symbolic value, mapped to the original local.
+    copies: HashMap<Local, Local>,
+    /// Symbolic values that are used as an index.
+    indexed: HashSet<Local>,
+    /// The conditions of bounds checks on symbolic values.
+    bounds_conds: HashSet<Local>,
+    /// Symbolic values that are used by anything other than a bounds check.
+    used: HashSet<Local>,
+}
+
+impl UnconstrainedIndex {
+    fn new(kani_any: FnDef, body: &Body) -> Self {
+        let mut checker = UnconstrainedIndex {
+            nondet: HashMap::new(),
+            copies: HashMap::new(),
+            indexed: HashSet::new(),
+            bounds_conds: HashSet::new(),
+            used: HashSet::new(),
+        };
+        for bb in &body.blocks {
+            if let TerminatorKind::Call { func, destination, .. } = &bb.terminator.kind
+                && let Ok(TyKind::RigidTy(RigidTy::FnDef(def, _))) =
+                    func.ty(body.locals()).map(|ty| ty.kind())
+                && def == kani_any
+                && destination.projection.is_empty()
+                && body.locals()[destination.local].ty == Ty::unsigned_ty(UintTy::Usize)
+            {
+                checker.nondet.insert(destination.local, bb.terminator.span);
+            }
+        }
+        for stmt in body.blocks.iter().flat_map(|bb| &bb.statements) {
+            if let Some((copy, orig)) = checker.copy_of_nondet(stmt) {
+                checker.copies.insert(copy, orig);
+            }
+        }
+        for bb in &body.blocks {
+            if let TerminatorKind::Assert {
+                cond,
+                msg: AssertMessage::BoundsCheck { index, .. },
+                ..
+            } = &bb.terminator.kind
+                && let Some(orig) = checker.nondet_operand(index)
+            {
+                checker.indexed.insert(orig);
+                if let Operand::Copy(place) | Operand::Move(place) = cond {
+                    checker.bounds_conds.insert(place.local);
+                }
+            }
+        }
+        checker
+    }
+
+    /// Return the symbolic value that is stored in the given local, if any.
+    fn nondet_local(&self, local: Local) -> Option<Local> {
+        let orig = self.copies.get(&local).copied().unwrap_or(local);
+        self.nondet.contains_key(&orig).then_some(orig)
+    }
+
+    fn nondet_operand(&self, operand: &Operand) -> Option<Local> {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) if place.projection.is_empty() => {
+                self.nondet_local(place.local)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the statement is `copy = orig`, where `orig` stores a symbolic value.
+    fn copy_of_nondet(&self, stmt: &Statement) -> Option<(Local, Local)> {
+        if let StatementKind::Assign(place, Rvalue::Use(operand)) = &stmt.kind
+            && place.projection.is_empty()
+        {
+            self.nondet_operand(operand).map(|orig| (place.local, orig))
+        } else {
+            None
+        }
+    }
+
+    /// Whether this statement computes the condition of a bounds check on a symbolic value.
+    fn is_bounds_check_cond(&self, stmt: &Statement) -> bool {
+        matches!(
+            &stmt.kind,
+            StatementKind::Assign(place, Rvalue::BinaryOp(BinOp::Lt, index, _))
+                if self.bounds_conds.contains(&place.local)
+                    && self.nondet_operand(index).is_some()
+        )
+    }
+}
+
+impl MirVisitor for UnconstrainedIndex {
+    fn visit_statement(&mut self, stmt: &Statement, location: Location) {
+        if self.copy_of_nondet(stmt).is_none() && !self.is_bounds_check_cond(stmt) {
+            self.super_statement(stmt, location)
+        }
+    }
+
+    fn visit_terminator(&mut self, term: &Terminator, location: Location) {
+        if !matches!(
+            term.kind,
+            TerminatorKind::Assert { msg: AssertMessage::BoundsCheck { .. }, .. }
+        ) {
+            self.super_terminator(term, location)
+        }
+    }
+
+    /// Record any read of a symbolic value. Indexing projections are not visited, since they
+    /// are always preceded by a bounds check.
+    fn visit_place(&mut self, place: &Place, ptx: PlaceContext, _location: Location) {
+        if !ptx.is_mutating()
+            && let Some(orig) = self.nondet_local(place.local)
+        {
+            self.used.insert(orig);
+        }
+    }
+}
+
+/// Warn about assertions whose condition is the condition of an earlier `kani::assume`.
+///
+/// Such an assertion holds no matter what the code under verification does, so the harness most
+/// likely doesn't check what it was meant to check. We compare the expressions that compute both
+/// conditions syntactically, and only if the values that they read are assigned once, so that
+/// they can't change between the assumption and the assertion.
+pub fn check_trivial_assertions(
+    tcx: TyCtxt,
+    kani_assume: FnDef,
+    kani_assert: FnDef,
+    instance: Instance,
+) {
+    let Some(body) = instance.body() else { return };
+    let assumes = hook_calls(&body, kani_assume);
+    if assumes.is_empty() {
+        return;
+    }
+    let exprs = CondExprs::new(&body);
+    let assumes: Vec<_> =
+        assumes.into_iter().filter_map(|(bb, cond)| Some((bb, exprs.resolve(&cond)?))).collect();
+    for (assert_bb, cond) in hook_calls(&body, kani_assert) {
+        let Some(expr) = exprs.resolve(&cond) else { continue };
+        let Some((assume_bb, _)) = assumes.iter().find(|(assume_bb, assumed)| {
+            *assumed == expr && dominates(&body, *assume_bb, assert_bb)
+        }) else {
+            continue;
+        };
+        let span = |bb: BasicBlockIdx| {
+            rustc_internal::internal(tcx, body.blocks[bb].terminator.span).source_callsite()
+        };
+        tcx.dcx()
+            .struct_span_warn(
+                span(assert_bb),
+                "suspicious proof: this assertion always holds because of an earlier assumption",
+            )
+            .with_span_note(span(*assume_bb), "the assumption has the same condition")
+            .with_help(
+                "an assertion that only checks an assumption doesn't verify the code under test; \
+                assert a property of the result of the code instead",
+            )
+            .emit();
+    }
+}
+
+/// Return the block and the first argument of every call to the given function.
+fn hook_calls(body: &Body, hook: FnDef) -> Vec<(BasicBlockIdx, Operand)> {
+    body.blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(bb, block)| {
+            if let TerminatorKind::Call { func, args, .. } = &block.terminator.kind
+                && let Ok(TyKind::RigidTy(RigidTy::FnDef(def, _))) =
+                    func.ty(body.locals()).map(|ty| ty.kind())
+                && def == hook
+            {
+                Some((bb, args.first()?.clone()))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Whether every path from the start of the body to `target` goes through `block`.
+fn dominates(body: &Body, block: BasicBlockIdx, target: BasicBlockIdx) -> bool {
+    let mut visited = HashSet::from([block]);
+    let mut to_visit = vec![0];
+    while let Some(bb) = to_visit.pop() {
+        if bb == target {
+            return false;
+        }
+        if visited.insert(bb) {
+            to_visit.extend(body.blocks[bb].terminator.successors());
+        }
+    }
+    true
+}
+
+/// The expression that computes a condition, where the temporaries are replaced by their value.
+#[derive(Clone, Debug, PartialEq)]
+enum CondExpr {
+    /// A place that is assigned at most once.
+    Place(Place),
+    Constant(Ty, ConstantKind),
+    BinaryOp(BinOp, Box<CondExpr>, Box<CondExpr>),
+    Not(Box<CondExpr>),
+}
+
+struct CondExprs<'a> {
+    /// The value of the locals that are assigned exactly once.
+    values: HashMap<Local, &'a Rvalue>,
+    /// The number of times that each local is assigned or borrowed mutably.
+    mutations: HashMap<Local, usize>,
+}
+
+impl<'a> CondExprs<'a> {
+    fn new(body: &'a Body) -> Self {
+        let mut exprs = CondExprs { values: HashMap::new(), mutations: HashMap::new() };
+        exprs.visit_body(body);
+        for stmt in body.blocks.iter().flat_map(|bb| &bb.statements) {
+            if let StatementKind::Assign(place, rvalue) = &stmt.kind
+                && place.projection.is_empty()
+                && exprs.mutations.get(&place.local) == Some(&1)
+            {
+                exprs.values.insert(place.local, rvalue);
+            }
+        }
+        exprs
+    }
+
+    fn resolve(&self, operand: &Operand) -> Option<CondExpr> {
+        match operand {
+            Operand::Constant(constant) => {
+                Some(CondExpr::Constant(constant.const_.ty(), constant.const_.kind().clone()))
+            }
+            Operand::Copy(place) | Operand::Move(place) => {
+                if self.mutations.get(&place.local).copied().unwrap_or_default() > 1 {
+                    return None;
+                }
+                if place.projection.is_empty()
+                    && let Some(rvalue) = self.values.get(&place.local)
+                    && let Some(expr) = self.resolve_rvalue(rvalue)
+                {
+                    return Some(expr);
+                }
+                Some(CondExpr::Place(place.clone()))
+            }
+        }
+    }
+
+    fn resolve_rvalue(&self, rvalue: &Rvalue) -> Option<CondExpr> {
+        match rvalue {
+            Rvalue::Use(operand) => self.resolve(operand),
+            Rvalue::BinaryOp(op, lhs, rhs) => Some(CondExpr::BinaryOp(
+                *op,
+                Box::new(self.resolve(lhs)?),
+                Box::new(self.resolve(rhs)?),
+            )),
+            // `assert!` negates its condition twice.
+            Rvalue::UnaryOp(UnOp::Not, operand) => match self.resolve(operand)? {
+                CondExpr::Not(expr) => Some(*expr),
+                expr => Some(CondExpr::Not(Box::new(expr))),
+            },
+            _ => None,
+        }
+    }
+}
+
+impl MirVisitor for CondExprs<'_> {
+    fn visit_place(&mut self, place: &Place, ptx: PlaceContext, _location: Location) {
+        if ptx.is_mutating() {
+            *self.mutations.entry(place.local).or_default() += 1;
+        }
+    }
+}
@@ -3,17 +3,45 @@
 //! This module handles Kani metadata generation. For example, generating HarnessMetadata for a
 //! given function.
 
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 use crate::kani_middle::codegen_units::Harness;
+use crate::kani_middle::kani_functions::{KaniFunction, KaniHook};
 use crate::kani_middle::{KaniAttributes, SourceLocation};
 use kani_metadata::ContractedFunction;
-use kani_metadata::{ArtifactType, HarnessAttributes, HarnessKind, HarnessMetadata};
+use kani_metadata::{ArtifactType, HarnessKind, HarnessMetadata};
 use rustc_middle::ty::TyCtxt;
+use rustc_public::mir::TerminatorKind;
 use rustc_public::mir::mono::Instance;
+use rustc_public::rustc_internal;
+use rustc_public::ty::{AdtKind, RigidTy, TyKind};
 use rustc_public::{CrateDef, CrateItems, DefId};
 
+/// The suffix of the artifact directories of automatic harnesses.
+pub const AUTOHARNESS_SUFFIX: &str = "_autoharness";
+
+/// The prefix of the artifact directories of the models shared by the harnesses of a codegen unit.
+pub const SHARED_MODEL_PREFIX: &str = "_shared";
+
+/// The directory that holds the artifacts of the harnesses of the crate whose compiler output is
+/// `base_name`, e.g., `deps/my_crate-1234.kani` for `deps/my_crate-1234.o`. Each harness has its
+/// own subdirectory, which is named after the mangled name of the harness.
+pub fn harnesses_dir(base_name: &Path) -> PathBuf {
+    base_name.with_extension("kani")
+}
+
+/// The goto file of the harness whose artifact directory is `dir_name`.
+fn harness_model_file(base_name: &Path, dir_name: &str) -> PathBuf {
+    let file_stem = format!("{}_{dir_name}", base_name.file_stem().unwrap().to_str().unwrap());
+    harnesses_dir(base_name).join(dir_name).join(file_stem).with_extension(ArtifactType::SymTabGoto)
+}
+
+/// The goto file of the model that is shared by the harnesses of the `index`-th codegen unit.
+pub fn shared_model_file(base_name: &Path, index: usize) -> PathBuf {
+    harness_model_file(base_name, &format!("{SHARED_MODEL_PREFIX}{index}"))
+}
+
 /// Create the harness metadata for a proof harness for a given function.
 pub fn gen_proof_metadata(tcx: TyCtxt, instance: Instance, base_name: &Path) -> HarnessMetadata {
     let def = instance.def;
@@ -24,8 +52,13 @@ pub fn gen_proof_metadata(tcx: TyCtxt, instance: Instance, base_name: &Path) ->
     // We get the body span to include the entire function definition.
     // This is required for concrete playback to properly position the generated test.
     let loc = SourceLocation::new(instance.body().unwrap().span);
-    let file_stem = format!("{}_{mangled_name}", base_name.file_stem().unwrap().to_str().unwrap());
-    let model_file = base_name.with_file_name(file_stem).with_extension(ArtifactType::SymTabGoto);
+    let model_file = harness_model_file(base_name, &mangled_name);
+    let attributes = kani_attributes.harness_attributes();
+    let split_cases = attributes
+        .split_on
+        .as_ref()
+        .map(|input| split_cases(tcx, instance, input))
+        .unwrap_or_default();
 
     HarnessMetadata {
         pretty_name,
@@ -34,12 +67,72 @@ pub fn gen_proof_metadata(tcx: TyCtxt, instance: Instance, base_name: &Path) ->
         original_file: loc.filename,
         original_start_line: loc.start_line,
         original_end_line: loc.end_line,
-        attributes: kani_attributes.harness_attributes(),
+        docs: kani_attributes.docs(),
+        attributes,
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
+        shared_model: None,
         contract: Default::default(),
+        cbmc_contracts: None,
         has_loop_contracts: false,
         is_automatically_generated: false,
+        foreign_functions: vec![],
+        unsafe_operations: vec![],
+        spec_fns: vec![],
+        reachable_functions: vec![],
+        unsupported_features: vec![],
+        target_mir_size: None,
+        largest_array: None,
+        devirtualized_calls: None,
+        labeled_loops: BTreeMap::new(),
+        split_cases,
+    }
+}
+
+/// The names of the variants of the enum input that a harness annotated with
+/// `#[kanitool::split_on = "<input>"]` is split on, in the order of their indices.
+///
+/// The `#[kani::proof]` macro passes the input to `kani::internal::split_case`, so we find its
+/// type in the call to that function in the body of the harness.
+fn split_cases(tcx: TyCtxt, instance: Instance, input: &str) -> Vec<String> {
+    let body = instance.body().unwrap();
+    let split_ty = body.blocks.iter().find_map(|block| {
+        let TerminatorKind::Call { func, .. } = &block.terminator.kind else {
+            return None;
+        };
+        let TyKind::RigidTy(RigidTy::FnDef(def, args)) = func.ty(body.locals()).ok()?.kind() else {
+            return None;
+        };
+        (KaniFunction::try_from(def) == Ok(KaniHook::SplitCase.into()))
+            .then(|| *args.0[0].expect_ty())
+    });
+    let span = rustc_internal::internal(tcx, body.span);
+    match split_ty.as_ref().map(|ty| ty.kind()) {
+        Some(TyKind::RigidTy(RigidTy::Adt(def, _)))
+            if def.kind() == AdtKind::Enum && def.num_variants() > 0 =>
+        {
+            let enum_name = def.trimmed_name();
+            def.variants_iter().map(|variant| format!("{enum_name}::{}", variant.name())).collect()
+        }
+        Some(_) => {
+            tcx.dcx().span_err(
+                span,
+                format!(
+                    "cannot split harness on `{input}` of type `{}`, which is not an enum with at least one variant",
+                    split_ty.unwrap()
+                ),
+            );
+            vec![]
+        }
+        None => {
+            tcx.dcx().span_err(
+                span,
+                format!(
+                    "cannot split harness on `{input}`, which is not an argument of the harness"
+                ),
+            );
+            vec![]
+        }
     }
 }
 
@@ -62,8 +155,11 @@ pub fn gen_contracts_metadata(
         let attributes = KaniAttributes::for_def_id(tcx, item.def_id());
 
         if attributes.has_contract() {
-            fn_to_data
-                .insert(item.def_id(), ContractedFunction { function, file, harnesses: vec![] });
+            let docs = attributes.docs();
+            fn_to_data.insert(
+                item.def_id(),
+                ContractedFunction { function, file, harnesses: vec![], docs },
+            );
         // This logic finds manual contract harnesses only (automatic harnesses are a Kani intrinsic, not crate items annotated with the proof_for_contract attribute).
         } else if let Some(def) = attributes.interpret_for_contract_attribute() {
             let target_def_id = def.def_id();
@@ -78,6 +174,7 @@ pub fn gen_contracts_metadata(
                         function: item.name(),
                         file,
                         harnesses: vec![function],
+                        docs: KaniAttributes::for_def_id(tcx, target_def_id).docs(),
                     },
                 );
             }
@@ -116,15 +213,15 @@ pub fn gen_automatic_proof_metadata(
     fn_to_verify: &Instance,
     harness_mangled_name: String,
 ) -> HarnessMetadata {
-    let def = fn_to_verify.def;
     let pretty_name = fn_to_verify.name();
     let mangled_name = fn_to_verify.mangled_name();
 
     // Leave the concrete playback instrumentation for now, but this feature does not actually support concrete playback.
-    let loc = SourceLocation::new(fn_to_verify.body().unwrap().span);
-    let file_stem =
-        format!("{}_{mangled_name}_autoharness", base_name.file_stem().unwrap().to_str().unwrap());
-    let model_file = base_name.with_file_name(file_stem).with_extension(ArtifactType::SymTabGoto);
+    let body = fn_to_verify.body().unwrap();
+    let loc = SourceLocation::new(body.span);
+    // Each block has a terminator besides its statements.
+    let mir_size: usize = body.blocks.iter().map(|block| block.statements.len() + 1).sum();
+    let model_file = harness_model_file(base_name, &format!("{mangled_name}{AUTOHARNESS_SUFFIX}"));
 
     let kani_attributes = KaniAttributes::for_instance(tcx, *fn_to_verify);
     let harness_kind = if kani_attributes.has_contract() {
@@ -138,15 +235,30 @@ pub fn gen_automatic_proof_metadata(
         pretty_name,
         // We pass --function mangled_name to CBMC to select the entry point, which should be the mangled name of the automatic harness intrinsic
         mangled_name: harness_mangled_name,
-        crate_name: def.krate().name,
+        // Automatic harnesses belong to the crate under verification, including the checks of
+        // trait laws, whose functions are defined in the Kani library.
+        crate_name: rustc_public::local_crate().name,
         original_file: loc.filename,
         original_start_line: loc.start_line,
         original_end_line: loc.end_line,
-        attributes: HarnessAttributes::new(harness_kind),
+        docs: kani_attributes.docs(),
+        attributes: kani_attributes.autoharness_attributes(harness_kind),
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
+        shared_model: None,
         contract: Default::default(),
+        cbmc_contracts: None,
         has_loop_contracts: false,
         is_automatically_generated: true,
+        foreign_functions: vec![],
+        unsafe_operations: vec![],
+        spec_fns: vec![],
+        reachable_functions: vec![],
+        unsupported_features: vec![],
+        target_mir_size: Some(mir_size),
+        largest_array: None,
+        devirtualized_calls: None,
+        labeled_loops: BTreeMap::new(),
+        split_cases: vec![],
     }
 }
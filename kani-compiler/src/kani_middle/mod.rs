@@ -7,19 +7,23 @@
 
 use crate::kani_queries::QueryDb;
 use fxhash::FxHashMap;
-use rustc_hir::{def::DefKind, def_id::DefId as InternalDefId, def_id::LOCAL_CRATE};
+use rustc_hir::{LangItem, def::DefKind, def_id::DefId as InternalDefId, def_id::LOCAL_CRATE};
+use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_public::mir::TerminatorKind;
 use rustc_public::mir::mono::{Instance, MonoItem};
 use rustc_public::rustc_internal;
 use rustc_public::ty::{
-    AdtDef, AdtKind, FnDef, GenericArgKind, GenericArgs, RigidTy, Span as SpanStable, Ty, TyKind,
+    Abi, AdtDef, AdtKind, FnDef, GenericArgKind, GenericArgs, RigidTy, Span as SpanStable, Ty,
+    TyKind, UintTy,
 };
 use rustc_public::visitor::{Visitable, Visitor as TyVisitor};
 use rustc_public::{CrateDef, DefId};
 use std::ops::ControlFlow;
 
 use self::attributes::KaniAttributes;
+use self::kani_functions::{KaniHook, KaniModel};
 
 pub mod abi;
 pub mod analysis;
@@ -28,6 +32,7 @@
 pub mod coercion;
 mod intrinsics;
 pub mod kani_functions;
+mod lints;
 pub mod metadata;
 pub mod points_to;
 pub mod provide;
@@ -44,7 +49,7 @@ pub fn check_crate_items(tcx: TyCtxt, ignore_asm: bool) {
     for item in tcx.hir_free_items() {
         let def_id = item.owner_id.def_id.to_def_id();
         KaniAttributes::for_item(tcx, def_id).check_attributes();
-        if tcx.def_kind(def_id) == DefKind::GlobalAsm {
+        if tcx.def_kind(def_id) == DefKind::GlobalAsm && !is_global_asm_ignored(tcx, def_id) {
             if !ignore_asm {
                 let error_msg = format!(
                     "Crate {krate} contains global ASM, which is not supported by Kani. Rerun with \
@@ -59,9 +64,46 @@ pub fn check_crate_items(tcx: TyCtxt, ignore_asm: bool) {
             }
         }
     }
+    for item in tcx.hir_crate_items(()).foreign_items() {
+        KaniAttributes::for_item(tcx, item.owner_id.def_id.to_def_id()).check_attributes();
+    }
     tcx.dcx().abort_if_errors();
 }
 
+/// Emit a note at the definition of every struct, enum and union of the crate, which says its kind
+/// and its path, e.g., "type definition of struct `shapes::Shape`".
+///
+/// `cargo kani fix` matches these notes with the types of the errors about a missing
+/// `kani::Arbitrary` or `kani::Invariant` implementation to find where to add the derive, since
+/// rustc does not point at the definition of the type in these errors.
+pub fn print_type_definitions(tcx: TyCtxt) {
+    for item in tcx.hir_free_items() {
+        let def_id = item.owner_id.def_id.to_def_id();
+        if matches!(tcx.def_kind(def_id), DefKind::Struct | DefKind::Enum | DefKind::Union) {
+            let path = with_no_trimmed_paths!(tcx.def_path_str(def_id));
+            tcx.dcx().span_note(
+                tcx.def_span(def_id),
+                format!("type definition of {} `{path}`", tcx.def_descr(def_id)),
+            );
+        }
+    }
+}
+
+/// Whether the global assembly `def_id` is in a module annotated with
+/// `#[kani::ignore_global_asm]`.
+fn is_global_asm_ignored(tcx: TyCtxt, def_id: InternalDefId) -> bool {
+    std::iter::successors(tcx.opt_parent(def_id), |&module| tcx.opt_parent(module))
+        .any(|module| KaniAttributes::for_item(tcx, module).ignores_global_asm())
+}
+
+/// Whether the static `def_id` is assigned by the global assembly of one of its enclosing modules,
+/// according to the contract of its `#[kani::ignore_global_asm]` attribute. The value of such a
+/// static is unknown when the harness starts.
+pub fn is_assigned_by_global_asm(tcx: TyCtxt, def_id: InternalDefId) -> bool {
+    std::iter::successors(tcx.opt_parent(def_id), |&module| tcx.opt_parent(module))
+        .any(|module| KaniAttributes::for_item(tcx, module).global_asm_assigns().contains(&def_id))
+}
+
 /// Traverse the type definition to see if the type contains interior mutability.
 ///
 /// See <https://doc.rust-lang.org/reference/interior-mutability.html> for more details.
@@ -105,6 +147,9 @@ pub fn check_reachable_items(tcx: TyCtxt, queries: &QueryDb, items: &[MonoItem])
             _ => None,
         })
         .collect();
+    let kani_any = queries.kani_functions().get(&KaniModel::Any.into()).copied();
+    let kani_assume = queries.kani_functions().get(&KaniHook::Assume.into()).copied();
+    let kani_assert = queries.kani_functions().get(&KaniHook::Assert.into()).copied();
     for item in items.iter().filter(|i| matches!(i, MonoItem::Fn(..) | MonoItem::Static(..))) {
         let def_id = match item {
             MonoItem::Fn(instance) => instance.def.def_id(),
@@ -119,6 +164,20 @@ pub fn check_reachable_items(tcx: TyCtxt, queries: &QueryDb, items: &[MonoItem])
             attributes.check_unstable_features(&queries.args().unstable_features);
             // Check whether all `proof_for_contract` targets are reachable
             attributes.check_proof_for_contract_reachability(&reachable_functions);
+            // Suggest constraining symbolic indices in user code.
+            if let (MonoItem::Fn(instance), Some(kani_any)) = (item, kani_any)
+                && instance.def.krate().is_local
+            {
+                lints::check_unconstrained_index(tcx, kani_any, *instance);
+            }
+            // Warn about assertions that only check an assumption.
+            if let (MonoItem::Fn(instance), Some(kani_assume), Some(kani_assert)) =
+                (item, kani_assume, kani_assert)
+                && instance.def.krate().is_local
+                && attributes::is_proof_harness(tcx, *instance)
+            {
+                lints::check_trivial_assertions(tcx, kani_assume, kani_assert, *instance);
+            }
             def_ids.insert(def_id);
         }
     }
@@ -170,6 +229,11 @@ pub fn stable_fn_def(tcx: TyCtxt, def_id: InternalDefId) -> Option<FnDef> {
     }
 }
 
+/// Whether the function is an `async fn`, whose body runs in the coroutine that it returns.
+pub fn is_async_fn(tcx: TyCtxt, fn_def: FnDef) -> bool {
+    tcx.asyncness(rustc_internal::internal(tcx, fn_def.def_id())).is_async()
+}
+
 /// Inspect a `kani::any<T>()` call to determine if `T: Arbitrary`
 /// `kani_any_def` refers to a function that looks like:
 /// ```rust
@@ -272,3 +336,37 @@ fn can_derive_arbitrary(
         false
     }
 }
+
+/// Return whether `instance` is an `extern "C"` function exported with `#[no_mangle]`,
+/// i.e., an entry point that C code may call directly.
+fn is_extern_c_entry_point(tcx: TyCtxt, instance: Instance) -> bool {
+    let is_c_abi = instance
+        .ty()
+        .kind()
+        .fn_sig()
+        .is_some_and(|sig| matches!(sig.skip_binder().abi, Abi::C { .. }));
+    is_c_abi
+        && tcx
+            .codegen_fn_attrs(rustc_internal::internal(tcx, instance.def.def_id()))
+            .flags
+            .contains(CodegenFnAttrFlags::NO_MANGLE)
+}
+
+/// If `ty` is a raw pointer that an automatic harness for an `extern "C"` function can model,
+/// return the type of the elements that it points to. We model `c_void` pointees as bytes.
+fn extern_c_ptr_elem_ty(tcx: TyCtxt, ty: Ty) -> Option<Ty> {
+    let TyKind::RigidTy(RigidTy::RawPtr(pointee, _)) = ty.kind() else {
+        return None;
+    };
+    match pointee.kind() {
+        TyKind::RigidTy(RigidTy::Adt(def, _))
+            if tcx.is_lang_item(rustc_internal::internal(tcx, def.def_id()), LangItem::CVoid) =>
+        {
+            Some(Ty::unsigned_ty(UintTy::U8))
+        }
+        TyKind::RigidTy(
+            RigidTy::Slice(_) | RigidTy::Str | RigidTy::Dynamic(..) | RigidTy::Foreign(_),
+        ) => None,
+        _ => Some(pointee),
+    }
+}
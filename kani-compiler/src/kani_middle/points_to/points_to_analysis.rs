@@ -10,9 +10,14 @@
 //! handling statements and terminators, which we implement.
 //!
 //! The analysis proceeds by looking at each instruction in the dataflow order and collecting all
-//! possible aliasing relations that the instruction introduces. If a terminator is a function call,
-//! the analysis recurs into the function and then joins the information retrieved from it into the
-//! original graph.
+//! possible aliasing relations that the instruction introduces. The analysis is interprocedural and
+//! context-insensitive: each function reachable from the entry points is analyzed on its own, and
+//! a function call simply connects the arguments of the caller to the parameters of the callee and
+//! the return value of the callee to the destination of the call. The results of each function are
+//! joined into a global graph, which provides the initial state of the next analysis of each
+//! function. This is repeated until no new aliasing information is discovered, so the effects of a
+//! callee (e.g., writes through pointers it received as arguments) are visible in its callers and
+//! the other way around.
 //!
 //! For each instruction, the analysis first resolves dereference projections for each place to
 //! determine which places it could point to. This is done by finding a set of successors in the
@@ -28,7 +33,6 @@
     intrinsics::Intrinsic,
     kani_middle::{
         points_to::{MemLoc, PointsToGraph},
-        reachability::CallGraph,
         transform::RustcInternalMir,
     },
 };
@@ -41,7 +45,7 @@
     ty::{Instance, InstanceKind, List, TyCtxt, TyKind, TypingEnv},
 };
 use rustc_mir_dataflow::{Analysis, Forward, JoinSemiLattice};
-use rustc_public::mir::{Body as StableBody, mono::Instance as StableInstance};
+use rustc_public::mir::mono::Instance as StableInstance;
 use rustc_public::rustc_internal;
 use rustc_span::{DUMMY_SP, source_map::Spanned};
 use std::collections::HashSet;
@@ -51,64 +55,82 @@ struct PointsToAnalysis<'a, 'tcx> {
     instance: Instance<'tcx>,
     body: &'a Body<'tcx>,
     tcx: TyCtxt<'tcx>,
-    /// This will be used in the future to resolve function pointer and vtable calls. Currently, we
-    /// can resolve call graph edges just by looking at the terminators and erroring if we can't
-    /// resolve the callee.
-    call_graph: &'a CallGraph,
-    /// This graph should contain a subset of the points-to graph reachable from function arguments.
-    /// For the entry function it will be empty (as it supposedly does not have any parameters).
+    /// The subset of the global points-to graph discovered so far that is reachable from the
+    /// places of this function, as well as all statics.
     initial_graph: PointsToGraph<'tcx>,
 }
 
-/// Public points-to analysis entry point. Performs the analysis on a body, outputting the graph
-/// containing aliasing information of the body itself and any body reachable from it.
+/// Public points-to analysis entry point. Performs the analysis on the entry points and every
+/// function reachable from them, outputting the graph containing aliasing information of all of
+/// those functions.
 pub fn run_points_to_analysis<'tcx>(
-    body: &StableBody,
+    entry_points: &[StableInstance],
     tcx: TyCtxt<'tcx>,
-    instance: StableInstance,
-    call_graph: &CallGraph,
 ) -> PointsToGraph<'tcx> {
     // Dataflow analysis does not yet work with StableMIR, so need to perform backward
     // conversion.
-    let internal_instance = rustc_internal::internal(tcx, instance);
-    let internal_body = body.internal_mir(tcx);
-    PointsToAnalysis::run(
-        &internal_body,
-        tcx,
-        internal_instance,
-        call_graph,
-        PointsToGraph::empty(),
-    )
+    let mut functions: Vec<(Instance<'tcx>, Body<'tcx>)> = entry_points
+        .iter()
+        .map(|instance| {
+            let body = instance.body().unwrap().internal_mir(tcx);
+            (rustc_internal::internal(tcx, *instance), body)
+        })
+        .collect();
+    let mut visited: HashSet<Instance<'tcx>> =
+        functions.iter().map(|(instance, _)| *instance).collect();
+    let mut global_graph = PointsToGraph::empty();
+    loop {
+        let mut updated = false;
+        let mut idx = 0;
+        // New callees are pushed to the end of the list while we iterate over it.
+        while idx < functions.len() {
+            let (instance, body) = &functions[idx];
+            let instance = *instance;
+            let initial_graph =
+                global_graph.transitive_closure(global_graph.stack_allocations(instance));
+            let results = PointsToAnalysis::run(body, tcx, instance, initial_graph);
+            updated |= global_graph.join(&results);
+            let new_callees: Vec<_> = analyzed_callees(body, tcx)
+                .into_iter()
+                .filter(|callee| visited.insert(*callee))
+                .collect();
+            for callee in new_callees {
+                let body = rustc_internal::stable(callee).body().unwrap().internal_mir(tcx);
+                functions.push((callee, body));
+                updated = true;
+            }
+            idx += 1;
+        }
+        if !updated {
+            break;
+        }
+    }
+    global_graph
 }
 
 impl<'a, 'tcx> PointsToAnalysis<'a, 'tcx> {
     /// Perform the analysis on a body, outputting the graph containing aliasing information of the
-    /// body itself and any body reachable from it.
+    /// body itself given the information in the initial graph.
     pub fn run(
         body: &'a Body<'tcx>,
         tcx: TyCtxt<'tcx>,
         instance: Instance<'tcx>,
-        call_graph: &'a CallGraph,
         initial_graph: PointsToGraph<'tcx>,
     ) -> PointsToGraph<'tcx> {
-        let analysis = Self { body, tcx, instance, call_graph, initial_graph };
+        let analysis = Self { body, tcx, instance, initial_graph };
         // This creates a fixpoint solver using the initial graph, the body, and extra information
         // and solves the dataflow problem, producing the cursor, which contains dataflow state for
         // each instruction in the body.
         let mut cursor =
             analysis.iterate_to_fixpoint(tcx, body, Some(Self::NAME)).into_results_cursor(body);
-        // We collect dataflow state at each `Return` terminator to determine the full aliasing
-        // graph for the function. This is sound since those are the only places where the function
-        // finishes, so the dataflow state at those places will be a union of dataflow states
-        // preceding to it, which means every possible execution is taken into account.
+        // We collect dataflow state at the end of every basic block to determine the full aliasing
+        // graph for the function. Since the analysis only ever adds edges, this is equivalent to
+        // collecting the state at each block without successors, which covers every possible
+        // execution, including the ones that diverge instead of returning.
         let mut results = PointsToGraph::empty();
-        for (idx, bb) in body.basic_blocks.iter().enumerate() {
-            if let TerminatorKind::Return = bb.terminator().kind {
-                // Switch the cursor to the end of the block ending with `Return`.
-                cursor.seek_to_block_end(idx.into());
-                // Retrieve the dataflow state and join into the results graph.
-                results.join(&cursor.get().clone());
-            }
+        for idx in body.basic_blocks.indices() {
+            cursor.seek_to_block_end(idx);
+            results.join(&cursor.get().clone());
         }
         results
     }
@@ -371,6 +393,22 @@ fn try_resolve_instance<'tcx>(
     }
 }
 
+/// Collect the functions called by the body that are handled by
+/// `PointsToAnalysis::apply_regular_call_effect`, i.e., that need to be analyzed as well.
+fn analyzed_callees<'tcx>(body: &Body<'tcx>, tcx: TyCtxt<'tcx>) -> Vec<Instance<'tcx>> {
+    body.basic_blocks
+        .iter()
+        .filter_map(|bb| match &bb.terminator().kind {
+            TerminatorKind::Call { func, .. } => try_resolve_instance(body, func, tcx).ok(),
+            _ => None,
+        })
+        .filter(|instance| {
+            !matches!(instance.def, InstanceKind::Intrinsic(_))
+                && !tcx.is_foreign_item(instance.def_id())
+        })
+        .collect()
+}
+
 impl<'tcx> PointsToAnalysis<'_, 'tcx> {
     /// Update the analysis state according to the operation, which is semantically equivalent to `*to = *from`.
     fn apply_copy_effect(
@@ -429,6 +467,11 @@ fn successors_for_deref(
     }
 
     /// Update the analysis state according to the regular function call.
+    ///
+    /// The callee is analyzed separately, so we only need to connect the arguments of the call to
+    /// the parameters of the callee and the return value of the callee to the destination.
+    /// Anything else the callee does is part of the initial graph in the next round of the
+    /// analysis.
     fn apply_regular_call_effect(
         &mut self,
         state: &mut PointsToGraph<'tcx>,
@@ -436,26 +479,6 @@ fn apply_regular_call_effect(
         args: &[Spanned<Operand<'tcx>>],
         destination: &Place<'tcx>,
     ) {
-        // Here we simply call another function, so need to retrieve internal body for it.
-        let new_body = {
-            let stable_instance = rustc_internal::stable(instance);
-            let stable_body = stable_instance.body().unwrap();
-            stable_body.internal_mir(self.tcx)
-        };
-
-        // In order to be efficient, create a new graph for the function call analysis, which only
-        // contains arguments and statics and anything transitively reachable from them.
-        let mut initial_graph = PointsToGraph::empty();
-        for arg in args.iter() {
-            match arg.node {
-                Operand::Copy(place) | Operand::Move(place) => {
-                    initial_graph
-                        .join(&state.transitive_closure(state.resolve_place(place, self.instance)));
-                }
-                Operand::Constant(_) => {}
-            }
-        }
-
         // A missing link is the connections between the arguments in the caller and parameters in
         // the callee, add it to the graph.
         if self.tcx.is_closure_like(instance.def.def_id()) {
@@ -468,10 +491,10 @@ fn apply_regular_call_effect(
                 Place { local: 1usize.into(), projection: List::empty() },
             )]);
             let rvalue_set = self.successors_for_operand(state, args[0].node.clone());
-            initial_graph.extend(&lvalue_set, &rvalue_set);
+            state.extend(&lvalue_set, &rvalue_set);
             // Then, connect the argument tuple to each of the spread arguments.
             let spread_arg_operand = args[1].node.clone();
-            for i in 0..new_body.arg_count {
+            for i in 0..self.tcx.instance_mir(instance.def).arg_count {
                 let lvalue_set = HashSet::from([MemLoc::new_stack_allocation(
                     instance,
                     Place {
@@ -481,7 +504,7 @@ fn apply_regular_call_effect(
                 )]);
                 // This conservatively assumes all arguments alias to all parameters.
                 let rvalue_set = self.successors_for_operand(state, spread_arg_operand.clone());
-                initial_graph.extend(&lvalue_set, &rvalue_set);
+                state.extend(&lvalue_set, &rvalue_set);
             }
         } else {
             // Otherwise, simply connect all arguments to parameters.
@@ -494,16 +517,10 @@ fn apply_regular_call_effect(
                     },
                 )]);
                 let rvalue_set = self.successors_for_operand(state, arg.node.clone());
-                initial_graph.extend(&lvalue_set, &rvalue_set);
+                state.extend(&lvalue_set, &rvalue_set);
             }
         }
 
-        // Run the analysis.
-        let new_result =
-            PointsToAnalysis::run(&new_body, self.tcx, instance, self.call_graph, initial_graph);
-        // Merge the results into the current state.
-        state.join(&new_result);
-
         // Connect the return value to the return destination.
         let lvalue_set = state.resolve_place(*destination, self.instance);
         let rvalue_set = HashSet::from([MemLoc::new_stack_allocation(
@@ -621,11 +638,16 @@ fn is_identity_aliasing_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::BlackBox
         | Intrinsic::Breakpoint
         | Intrinsic::Bswap
+        | Intrinsic::CeilF16
         | Intrinsic::CeilF32
         | Intrinsic::CeilF64
+        | Intrinsic::CeilF128
         | Intrinsic::CompareBytes
+        | Intrinsic::CopySignF16
         | Intrinsic::CopySignF32
         | Intrinsic::CopySignF64
+        | Intrinsic::CopySignF128
+        | Intrinsic::CosF16
         | Intrinsic::CosF32
         | Intrinsic::CosF64
         | Intrinsic::Ctlz
@@ -635,16 +657,23 @@ fn is_identity_aliasing_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::CttzNonZero
         | Intrinsic::DiscriminantValue
         | Intrinsic::ExactDiv
+        | Intrinsic::Exp2F16
         | Intrinsic::Exp2F32
         | Intrinsic::Exp2F64
+        | Intrinsic::ExpF16
         | Intrinsic::ExpF32
         | Intrinsic::ExpF64
+        | Intrinsic::FabsF16
         | Intrinsic::FabsF32
         | Intrinsic::FabsF64
+        | Intrinsic::FabsF128
         | Intrinsic::FaddFast
         | Intrinsic::FdivFast
+        | Intrinsic::FloorF16
         | Intrinsic::FloorF32
         | Intrinsic::FloorF64
+        | Intrinsic::FloorF128
+        | Intrinsic::FmafF16
         | Intrinsic::FmafF32
         | Intrinsic::FmafF64
         | Intrinsic::FmulFast
@@ -652,19 +681,28 @@ fn is_identity_aliasing_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::FsubFast
         | Intrinsic::IsValStaticallyKnown
         | Intrinsic::Likely
+        | Intrinsic::Log10F16
         | Intrinsic::Log10F32
         | Intrinsic::Log10F64
+        | Intrinsic::Log2F16
         | Intrinsic::Log2F32
         | Intrinsic::Log2F64
+        | Intrinsic::LogF16
         | Intrinsic::LogF32
         | Intrinsic::LogF64
+        | Intrinsic::MaxNumF16
         | Intrinsic::MaxNumF32
         | Intrinsic::MaxNumF64
+        | Intrinsic::MaxNumF128
+        | Intrinsic::MinNumF16
         | Intrinsic::MinNumF32
         | Intrinsic::MinNumF64
+        | Intrinsic::MinNumF128
         | Intrinsic::MulWithOverflow
+        | Intrinsic::PowF16
         | Intrinsic::PowF32
         | Intrinsic::PowF64
+        | Intrinsic::PowIF16
         | Intrinsic::PowIF32
         | Intrinsic::PowIF64
         | Intrinsic::PtrGuaranteedCmp
@@ -674,21 +712,29 @@ fn is_identity_aliasing_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::RetagBoxToRaw
         | Intrinsic::RotateLeft
         | Intrinsic::RotateRight
+        | Intrinsic::RoundF16
         | Intrinsic::RoundF32
         | Intrinsic::RoundF64
+        | Intrinsic::RoundF128
+        | Intrinsic::RoundTiesEvenF16
         | Intrinsic::RoundTiesEvenF32
         | Intrinsic::RoundTiesEvenF64
+        | Intrinsic::RoundTiesEvenF128
         | Intrinsic::SaturatingAdd
         | Intrinsic::SaturatingSub
+        | Intrinsic::SinF16
         | Intrinsic::SinF32
         | Intrinsic::SinF64
         | Intrinsic::SizeOfVal
+        | Intrinsic::SqrtF16
         | Intrinsic::SqrtF32
         | Intrinsic::SqrtF64
         | Intrinsic::SubWithOverflow
         | Intrinsic::Transmute
+        | Intrinsic::TruncF16
         | Intrinsic::TruncF32
         | Intrinsic::TruncF64
+        | Intrinsic::TruncF128
         | Intrinsic::UncheckedDiv
         | Intrinsic::UncheckedRem
         | Intrinsic::Unlikely
@@ -703,6 +749,8 @@ fn is_identity_aliasing_intrinsic(intrinsic: Intrinsic) -> bool {
         }
         Intrinsic::SimdAdd
         | Intrinsic::SimdAnd
+        | Intrinsic::SimdBitmask
+        | Intrinsic::SimdCast
         | Intrinsic::SimdDiv
         | Intrinsic::SimdRem
         | Intrinsic::SimdEq
@@ -715,9 +763,11 @@ fn is_identity_aliasing_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::SimdMul
         | Intrinsic::SimdNe
         | Intrinsic::SimdOr
+        | Intrinsic::SimdSelect
         | Intrinsic::SimdShl
         | Intrinsic::SimdShr
         | Intrinsic::SimdShuffle(_)
+        | Intrinsic::SimdShuffleConstGeneric
         | Intrinsic::SimdSub
         | Intrinsic::SimdXor => {
             /* SIMD operations */
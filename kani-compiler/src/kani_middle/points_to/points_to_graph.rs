@@ -208,6 +208,15 @@ pub fn dump(&self, file_path: &str) {
         std::fs::write(file_path, format!("digraph {{\n{nodes_str}\n{edges_str}\n}}")).unwrap();
     }
 
+    /// Collect all stack allocations of the given function instance that are in the graph.
+    pub fn stack_allocations(&self, instance: Instance<'tcx>) -> HashSet<MemLoc<'tcx>> {
+        self.nodes
+            .keys()
+            .filter(|node| matches!(node, MemLoc::Stack(node_instance, _) if *node_instance == instance))
+            .copied()
+            .collect()
+    }
+
     /// Find a transitive closure of the graph starting from a set of given locations; this also
     /// includes statics.
     pub fn transitive_closure(&self, targets: HashSet<MemLoc<'tcx>>) -> PointsToGraph<'tcx> {
@@ -578,6 +578,78 @@ fn add_edges(&mut self, from: MonoItem, to: &[CollectedItem]) {
         }
     }
 
+    /// Find the groups of mutually recursive functions, i.e., the strongly connected components of
+    /// the graph of direct calls that contain a cycle.
+    ///
+    /// Functions that only recurse through function pointers or trait objects are not included.
+    /// The groups are sorted by the mangled name of their functions, so that their order doesn't
+    /// depend on the order in which the items were collected.
+    pub fn recursive_functions(&self) -> Vec<Vec<Instance>> {
+        let direct_calls = |edges: &HashMap<Node, Vec<CollectedNode>>, node: &Node| {
+            edges[node]
+                .iter()
+                .filter(|succ| succ.0.reason == CollectionReason::DirectCall)
+                .map(|succ| Node::from(succ.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        // Order the nodes by the time at which their depth-first traversal finishes.
+        let mut visited = HashSet::new();
+        let mut finished = vec![];
+        for root in &self.nodes {
+            if !visited.insert(root.clone()) {
+                continue;
+            }
+            let mut stack = vec![(root.clone(), direct_calls(&self.edges, root))];
+            while let Some((node, succs)) = stack.last_mut() {
+                if let Some(succ) = succs.pop() {
+                    if visited.insert(succ.clone()) {
+                        let next = direct_calls(&self.edges, &succ);
+                        stack.push((succ, next));
+                    }
+                } else {
+                    finished.push(node.clone());
+                    stack.pop();
+                }
+            }
+        }
+
+        // Each traversal of the reversed graph in the reverse finishing order visits exactly one
+        // strongly connected component.
+        let mut assigned = HashSet::new();
+        let mut groups = vec![];
+        for root in finished.into_iter().rev() {
+            if !assigned.insert(root.clone()) {
+                continue;
+            }
+            let mut component = vec![];
+            let mut queue = vec![root];
+            while let Some(node) = queue.pop() {
+                for pred in direct_calls(&self.back_edges, &node) {
+                    if assigned.insert(pred.clone()) {
+                        queue.push(pred);
+                    }
+                }
+                component.push(node);
+            }
+            let is_recursive = component.len() > 1
+                || direct_calls(&self.edges, &component[0]).contains(&component[0]);
+            let mut functions = component
+                .into_iter()
+                .filter_map(|node| match node.0 {
+                    MonoItem::Fn(instance) => Some(instance),
+                    MonoItem::Static(_) | MonoItem::GlobalAsm(_) => None,
+                })
+                .collect::<Vec<_>>();
+            if is_recursive && !functions.is_empty() {
+                functions.sort_by_cached_key(|instance| instance.mangled_name());
+                groups.push(functions);
+            }
+        }
+        groups.sort_by_cached_key(|functions| functions[0].mangled_name());
+        groups
+    }
+
     /// Print the graph in DOT format to a file.
     /// See <https://graphviz.org/doc/info/lang.html> for more information.
     fn dump_dot(&self, tcx: TyCtxt, initial: Option<MonoItem>) -> std::io::Result<()> {
@@ -10,10 +10,10 @@
 use crate::kani_middle::stable_fn_def;
 use quote::ToTokens;
 use rustc_hir::def::{DefKind, Res};
-use rustc_hir::def_id::{CRATE_DEF_INDEX, DefId, LOCAL_CRATE, LocalDefId, LocalModDefId};
+use rustc_hir::def_id::{CRATE_DEF_INDEX, DefId, LocalDefId, LocalModDefId};
 use rustc_hir::{ItemKind, UseKind};
-use rustc_middle::ty::TyCtxt;
 use rustc_middle::ty::fast_reject::{self, TreatParams};
+use rustc_middle::ty::{GenericParamDefKind, TyCtxt};
 use rustc_public::mir::mono::Instance;
 use rustc_public::rustc_internal;
 use rustc_public::ty::{
@@ -57,14 +57,16 @@ pub fn def(&self) -> FnDef {
 
 /// Resolve a path to a function / method.
 ///
-/// The path can either be a simple path or a qualified path.
+/// The path can either be a simple path or a qualified path. The function may be followed by
+/// generic arguments, e.g. `core::ptr::read::<T>`, which only need to match the number of generic
+/// parameters of the function, since the function is resolved to its generic definition.
 pub fn resolve_fn_path<'tcx>(
     tcx: TyCtxt<'tcx>,
     current_module: LocalDefId,
     path: &TypePath,
 ) -> Result<FnResolution, ResolveError<'tcx>> {
     let _span = debug_span!("resolve_fn_path", ?path).entered();
-    match &path.qself {
+    let resolution = match &path.qself {
         // Qualified path for a trait method implementation, like `<Foo as Bar>::bar`.
         Some(QSelf { ty: syn_ty, position, .. }) if *position > 0 => {
             // Resolve the self type and DefId of the trait definition method.
@@ -98,6 +100,45 @@ pub fn resolve_fn_path<'tcx>(
             validate_kind!(tcx, def_id, "function / method", DefKind::Fn | DefKind::AssocFn)?;
             Ok(FnResolution::Fn(stable_fn_def(tcx, def_id).unwrap()))
         }
+    }?;
+    if let Some(segment) = path.path.segments.last() {
+        check_fn_generic_args(tcx, resolution.def(), &segment.arguments)?;
+    }
+    Ok(resolution)
+}
+
+/// Check that the generic arguments that follow the name of a function in a path, if any, match
+/// the generic parameters of the function. Lifetime arguments may be omitted.
+fn check_fn_generic_args<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def: FnDef,
+    args: &PathArguments,
+) -> Result<(), ResolveError<'tcx>> {
+    let PathArguments::AngleBracketed(args) = args else { return Ok(()) };
+    let def_id = rustc_internal::internal(tcx, def.def_id());
+    let expected = tcx
+        .generics_of(def_id)
+        .own_params
+        .iter()
+        .filter(|param| {
+            matches!(
+                param.kind,
+                GenericParamDefKind::Type { synthetic: false, .. }
+                    | GenericParamDefKind::Const { .. }
+            )
+        })
+        .count();
+    let found =
+        args.args.iter().filter(|arg| !matches!(arg, syn::GenericArgument::Lifetime(_))).count();
+    if expected == found {
+        Ok(())
+    } else {
+        Err(ResolveError::InvalidPath {
+            msg: format!(
+                "function `{}` takes {expected} generic argument(s), but {found} were supplied",
+                tcx.def_path_str(def_id)
+            ),
+        })
     }
 }
 
@@ -115,6 +156,82 @@ pub fn resolve_fn<'tcx>(
     Ok(rustc_internal::internal(tcx, result.def().def_id()))
 }
 
+/// Resolve a path to the definition of a struct, enum or union.
+///
+/// Generic arguments in the path are ignored, i.e., `Vec<u8>` and `Vec` resolve to the same item.
+pub fn resolve_type_def_path<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    current_module: LocalDefId,
+    path: &TypePath,
+) -> Result<DefId, ResolveError<'tcx>> {
+    let _span = debug_span!("resolve_type_def_path", ?path).entered();
+    if path.qself.is_some() {
+        return Err(ResolveError::UnsupportedPath { kind: "qualified paths" });
+    }
+    let def_id = resolve_path(tcx, current_module, &path.path)?;
+    validate_kind!(
+        tcx,
+        def_id,
+        "struct, enum or union",
+        DefKind::Struct | DefKind::Enum | DefKind::Union
+    )
+}
+
+/// Resolve a path relative to `module` to the definition of a static.
+///
+/// Unlike the other paths, the module may belong to another crate, so the path can only name the
+/// items that are declared or imported in it and in its submodules.
+pub fn resolve_static_path<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    module: DefId,
+    path: &TypePath,
+) -> Result<DefId, ResolveError<'tcx>> {
+    let _span = debug_span!("resolve_static_path", ?path, ?module).entered();
+    if path.qself.is_some() || path.path.leading_colon.is_some() {
+        return Err(ResolveError::UnsupportedPath { kind: "qualified or absolute paths" });
+    }
+    let def_id = path.path.segments.iter().try_fold(module, |base, segment| {
+        let name = segment.ident.to_string();
+        match tcx.def_kind(base) {
+            DefKind::Mod => resolve_in_module(tcx, base, &name),
+            _ => Err(ResolveError::UnexpectedType { tcx, item: base, expected: "module" }),
+        }
+    })?;
+    validate_kind!(tcx, def_id, "static", DefKind::Static { .. })
+}
+
+/// Attempts to resolve a path (in the form of a string) to a struct, enum or union `DefId`.
+pub fn resolve_type_def<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    current_module: LocalDefId,
+    path_str: &str,
+) -> Result<DefId, ResolveError<'tcx>> {
+    let path = syn::parse_str(path_str).map_err(|err| ResolveError::InvalidPath {
+        msg: format!("Expected a path, but found `{path_str}`. {err}"),
+    })?;
+    resolve_type_def_path(tcx, current_module, &path)
+}
+
+/// Resolve the name of a function that is declared inside the body of `parent`.
+///
+/// Items declared in a function body are not visible from the enclosing module, so they are not
+/// found by [resolve_fn_path]. This is used to resolve the target of harnesses that are declared
+/// in the same body, e.g. the ones generated for closures with contracts.
+pub fn resolve_fn_in_body(tcx: TyCtxt, parent: DefId, path: &TypePath) -> Option<FnDef> {
+    if !matches!(tcx.def_kind(parent), DefKind::Fn | DefKind::AssocFn | DefKind::Closure) {
+        return None;
+    }
+    let name = path.qself.is_none().then(|| path.path.get_ident()).flatten()?.to_string();
+    tcx.hir_free_items().find_map(|item| {
+        let def_id = item.owner_id.def_id.to_def_id();
+        (tcx.def_kind(def_id) == DefKind::Fn
+            && tcx.opt_parent(def_id) == Some(parent)
+            && tcx.item_name(def_id).as_str() == name)
+            .then(|| stable_fn_def(tcx, def_id))
+            .flatten()
+    })
+}
+
 /// Attempts to resolve a simple path (in the form of a string) to a `DefId`.
 /// The current module is provided as an argument in order to resolve relative
 /// paths.
@@ -431,7 +548,7 @@ fn description(tcx: TyCtxt, def_id: DefId) -> String {
     let def_kind = tcx.def_kind(def_id);
     let kind_name = def_kind.descr(def_id);
     if def_id.is_crate_root() {
-        format!("{kind_name} `{}`", tcx.crate_name(LOCAL_CRATE))
+        format!("{kind_name} `{}`", tcx.crate_name(def_id.krate))
     } else {
         format!("{kind_name} `{}`", tcx.def_path_str(def_id))
     }
@@ -4,11 +4,14 @@
 
 use std::collections::HashMap;
 
+use itertools::Itertools;
 use kani_metadata::Stub;
+use rustc_hir::def::DefKind;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_middle::ty::TyCtxt;
+use rustc_span::Symbol;
 
-use crate::kani_middle::resolve::resolve_fn;
+use crate::kani_middle::resolve::{resolve_fn, resolve_type_def};
 
 /// Given a `kani::stub` attribute, tries to extract a pair of paths (the
 /// original function/method, and its stub). Returns `None` and errors if the
@@ -60,3 +63,158 @@ pub fn update_stub_mapping(
         }
     }
 }
+
+/// Given a `kani::stub_type` attribute, tries to extract the definitions of the
+/// original type and its model. Returns `None` and errors if the paths cannot be
+/// resolved.
+fn type_stub_def_ids(tcx: TyCtxt, harness: LocalDefId, stub: &Stub) -> Option<(DefId, DefId)> {
+    let current_module = tcx.parent_module_from_def_id(harness);
+    let resolve = |name: &str| -> Option<DefId> {
+        match resolve_type_def(tcx, current_module.to_local_def_id(), name) {
+            Ok(def_id) => {
+                tracing::debug!(?def_id, "Resolved {name} to {}", tcx.def_path_str(def_id));
+                Some(def_id)
+            }
+            Err(err) => {
+                tcx.dcx()
+                    .span_err(tcx.def_span(harness), format!("failed to resolve `{name}`: {err}"));
+                None
+            }
+        }
+    };
+    let orig = resolve(&stub.original);
+    let model = resolve(&stub.replacement);
+    Some((orig?, model?))
+}
+
+/// Check that the generic parameters of the model match the ones of the original type.
+///
+/// The methods of the model are instantiated with the generic arguments used for the original
+/// methods, so both types need to have the same number and kinds of parameters.
+fn check_type_stub_generics(tcx: TyCtxt, orig: DefId, model: DefId) -> Result<(), String> {
+    let orig_counts = tcx.generics_of(orig).own_counts();
+    let model_counts = tcx.generics_of(model).own_counts();
+    let mismatch = |kind: &str, orig_count: usize, model_count: usize| {
+        (orig_count != model_count).then(|| {
+            format!(
+                "mismatch in the number of {kind} parameters: type `{}` takes {orig_count}, model `{}` takes {model_count}",
+                tcx.def_path_str(orig),
+                tcx.def_path_str(model),
+            )
+        })
+    };
+    let errors = [
+        mismatch("lifetime", orig_counts.lifetimes, model_counts.lifetimes),
+        mismatch("type", orig_counts.types, model_counts.types),
+        mismatch("const", orig_counts.consts, model_counts.consts),
+    ];
+    let errors = errors.into_iter().flatten().collect::<Vec<_>>();
+    if errors.is_empty() { Ok(()) } else { Err(errors.join("\n")) }
+}
+
+/// Collect the methods declared in the inherent `impl` blocks of the given type.
+fn inherent_methods(tcx: TyCtxt, type_id: DefId) -> impl Iterator<Item = DefId> {
+    tcx.inherent_impls(type_id)
+        .iter()
+        .flat_map(move |impl_id| tcx.associated_item_def_ids(impl_id))
+        .copied()
+        .filter(move |item| tcx.def_kind(*item) == DefKind::AssocFn)
+}
+
+/// Map every inherent method of the original type that can be used by the harness to the method
+/// with the same name in the model.
+///
+/// Methods that are not accessible from the harness, as well as unstable methods, can only be
+/// reached through other methods and may be left without a model. Any other missing method is an
+/// error, since it would operate on the original representation of the type.
+fn type_stub_methods(
+    tcx: TyCtxt,
+    harness: LocalDefId,
+    orig: DefId,
+    model: DefId,
+) -> Result<Vec<(DefId, DefId)>, String> {
+    check_type_stub_generics(tcx, orig, model)?;
+    let harness_module = tcx.parent_module_from_def_id(harness).to_def_id();
+    let model_methods: HashMap<Symbol, Vec<DefId>> =
+        inherent_methods(tcx, model).map(|method| (tcx.item_name(method), method)).into_group_map();
+
+    let mut pairs = vec![];
+    let mut missing = vec![];
+    for method in inherent_methods(tcx, orig) {
+        let name = tcx.item_name(method);
+        match model_methods.get(&name).map(Vec::as_slice) {
+            Some([model_method]) => pairs.push((method, *model_method)),
+            Some(candidates) => {
+                return Err(format!(
+                    "model `{}` has {} methods named `{name}`",
+                    tcx.def_path_str(model),
+                    candidates.len()
+                ));
+            }
+            None => {
+                let is_accessible = tcx.visibility(method).is_accessible_from(harness_module, tcx);
+                let is_unstable =
+                    tcx.lookup_stability(method).is_some_and(|stab| stab.is_unstable());
+                if is_accessible && !is_unstable {
+                    missing.push(format!("`{name}`"));
+                }
+            }
+        }
+    }
+    if missing.is_empty() {
+        Ok(pairs)
+    } else {
+        Err(format!(
+            "model `{}` is missing the following methods of `{}`: {}",
+            tcx.def_path_str(model),
+            tcx.def_path_str(orig),
+            missing.join(", ")
+        ))
+    }
+}
+
+/// Updates the running map `stub_pairs` with the methods of a type replaced by
+/// its model, and records the type pair in `type_pairs`.
+///
+/// Stubs that were given explicitly with `kani::stub` take precedence over the
+/// methods of the model.
+pub fn update_type_stub_mapping(
+    tcx: TyCtxt,
+    harness: LocalDefId,
+    stub: &Stub,
+    stub_pairs: &mut HashMap<DefId, DefId>,
+    type_pairs: &mut HashMap<DefId, DefId>,
+) {
+    let Some((orig_id, model_id)) = type_stub_def_ids(tcx, harness, stub) else { return };
+    if let Some(other) = type_pairs.insert(orig_id, model_id)
+        && other != model_id
+    {
+        tcx.dcx().span_err(
+            tcx.def_span(harness),
+            format!(
+                "duplicate type stub mapping: {} mapped to {} and {}",
+                tcx.def_path_str(orig_id),
+                tcx.def_path_str(model_id),
+                tcx.def_path_str(other)
+            ),
+        );
+        return;
+    }
+    match type_stub_methods(tcx, harness, orig_id, model_id) {
+        Ok(methods) => {
+            for (orig_method, model_method) in methods {
+                stub_pairs.entry(orig_method).or_insert(model_method);
+            }
+        }
+        Err(msg) => {
+            tcx.dcx().span_err(
+                tcx.def_span(harness),
+                format!(
+                    "cannot stub type `{}` by `{}`: {msg}",
+                    tcx.def_path_str(orig_id),
+                    tcx.def_path_str(model_id)
+                ),
+            );
+        }
+    }
+}
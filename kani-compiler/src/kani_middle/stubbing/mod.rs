@@ -9,32 +9,48 @@
 use std::collections::HashMap;
 use tracing::{debug, trace};
 
+use crate::kani_middle::codegen_units::TypeStubs;
 use kani_metadata::HarnessMetadata;
 use rustc_hir::def_id::DefId;
 use rustc_middle::mir::Const;
-use rustc_middle::ty::{self, EarlyBinder, TyCtxt, TypeFoldable, TypingEnv};
+use rustc_middle::ty::{
+    self, EarlyBinder, TyCtxt, TypeFoldable, TypeFolder, TypeSuperFoldable, TypingEnv,
+};
 use rustc_public::mir::ConstOperand;
 use rustc_public::mir::mono::Instance;
 use rustc_public::mir::visit::{Location, MirVisitor};
 use rustc_public::rustc_internal;
-use rustc_public::ty::{FnDef, GenericArgs, RigidTy, TyKind};
+use rustc_public::ty::{FnDef, GenericArgs, RigidTy, Ty, TyKind};
 use rustc_public::{CrateDef, CrateItem};
 
-use self::annotations::update_stub_mapping;
+use self::annotations::{update_stub_mapping, update_type_stub_mapping};
 
 /// Collects the stubs from the harnesses in a crate.
+///
+/// Returns the function stubs, which include the methods of every stubbed type, and the type
+/// stubs.
 pub fn harness_stub_map(
     tcx: TyCtxt,
     harness: Instance,
     metadata: &HarnessMetadata,
-) -> HashMap<DefId, DefId> {
+) -> (HashMap<DefId, DefId>, HashMap<DefId, DefId>) {
     let def_id = rustc_internal::internal(tcx, harness.def.def_id());
     let attrs = &metadata.attributes;
     let mut stub_pairs = HashMap::default();
     for stubs in &attrs.stubs {
         update_stub_mapping(tcx, def_id.expect_local(), stubs, &mut stub_pairs);
     }
-    stub_pairs
+    let mut type_pairs = HashMap::default();
+    for stub in &attrs.type_stubs {
+        update_type_stub_mapping(
+            tcx,
+            def_id.expect_local(),
+            stub,
+            &mut stub_pairs,
+            &mut type_pairs,
+        );
+    }
+    (stub_pairs, type_pairs)
 }
 
 /// For the purpose of checking generic argument length, don't consider the `Self` generic argument.
@@ -68,11 +84,88 @@ fn generic_args_len_without_self(args: &GenericArgs) -> usize {
     if has_self { len - 1 } else { len }
 }
 
+/// Replace every stubbed type in `ty` by its model.
+struct TypeStubFolder<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    type_stubs: &'a HashMap<DefId, DefId>,
+}
+
+impl<'tcx> TypeFolder<TyCtxt<'tcx>> for TypeStubFolder<'_, 'tcx> {
+    fn cx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn fold_ty(&mut self, ty: ty::Ty<'tcx>) -> ty::Ty<'tcx> {
+        if let ty::Adt(adt_def, args) = ty.kind()
+            && let Some(model) = self.type_stubs.get(&adt_def.did())
+        {
+            let args = args.fold_with(self);
+            ty::Ty::new_adt(self.tcx, self.tcx.adt_def(*model), args)
+        } else {
+            ty.super_fold_with(self)
+        }
+    }
+}
+
+/// Return the type that a stub is expected to use in place of `ty`.
+fn expected_stub_ty(tcx: TyCtxt, ty: Ty, type_stubs: &TypeStubs) -> Ty {
+    if type_stubs.is_empty() {
+        return ty;
+    }
+    let type_stubs = type_stubs
+        .iter()
+        .map(|(orig, model)| {
+            (
+                rustc_internal::internal(tcx, orig.def_id()),
+                rustc_internal::internal(tcx, model.def_id()),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    let mut folder = TypeStubFolder { tcx, type_stubs: &type_stubs };
+    rustc_internal::stable(rustc_internal::internal(tcx, ty).fold_with(&mut folder))
+}
+
+/// Replace the generic parameters in `ty`, which is a type in the stub, by the parameters of the
+/// original function/method in the same position.
+///
+/// This is the substitution that is applied when the stub replaces an instance of the original
+/// function/method. If the parameters of both functions don't have the same kinds, e.g., when the
+/// original is a trait method whose `Self` parameter the stub doesn't take, the parameters are
+/// left unchanged.
+fn rename_generic_params(
+    tcx: TyCtxt,
+    old_args: &GenericArgs,
+    new_args: &GenericArgs,
+    ty: Ty,
+) -> Ty {
+    let same_kinds = old_args.0.len() == new_args.0.len()
+        && old_args
+            .0
+            .iter()
+            .zip(&new_args.0)
+            .all(|(old, new)| std::mem::discriminant(old) == std::mem::discriminant(new));
+    if !same_kinds {
+        return ty;
+    }
+    let old_args = rustc_internal::internal(tcx, old_args);
+    rustc_internal::stable(
+        EarlyBinder::bind(rustc_internal::internal(tcx, ty)).instantiate(tcx, old_args),
+    )
+}
+
 /// Checks whether the stub is compatible with the original function/method: do
 /// the arities and types (of the parameters and return values) match up? This
 /// does **NOT** check whether the type variables are constrained to implement
 /// the same traits; trait mismatches are checked during monomorphization.
-pub fn check_compatibility(tcx: TyCtxt, old_def: FnDef, new_def: FnDef) -> Result<(), String> {
+///
+/// Types that are stubbed by a model in `type_stubs` are expected to be
+/// replaced by their model in the stub signature.
+pub fn check_compatibility(
+    tcx: TyCtxt,
+    old_def: FnDef,
+    new_def: FnDef,
+    type_stubs: &TypeStubs,
+) -> Result<(), String> {
     // TODO: Validate stubs that do not have body.
     // We could potentially look at the function signature to see if they match.
     // However, they will include region information which can make types different.
@@ -117,13 +210,11 @@ pub fn check_compatibility(tcx: TyCtxt, old_def: FnDef, new_def: FnDef) -> Resul
     }
     // Check whether the types match. Index 0 refers to the returned value,
     // indices [1, `arg_count`] refer to the parameters.
-    // TODO: We currently force generic parameters in the stub to have exactly
-    // the same names as their counterparts in the original function/method;
-    // instead, we should be checking for the equivalence of types up to the
-    // renaming of generic parameters.
-    // <https://github.com/model-checking/kani/issues/1953>
-    let old_ret_ty = old_body.ret_local().ty;
-    let new_ret_ty = new_body.ret_local().ty;
+    // The stub is instantiated with the generic arguments of the original function/method, so
+    // its generic parameters are compared to the original ones by position, not by name.
+    let rename = |ty| rename_generic_params(tcx, &old_args, &new_args, ty);
+    let old_ret_ty = expected_stub_ty(tcx, old_body.ret_local().ty, type_stubs);
+    let new_ret_ty = rename(new_body.ret_local().ty);
     let mut diff = vec![];
     if old_ret_ty != new_ret_ty {
         diff.push(format!("Expected return type `{old_ret_ty}`, but found `{new_ret_ty}`"));
@@ -131,12 +222,14 @@ pub fn check_compatibility(tcx: TyCtxt, old_def: FnDef, new_def: FnDef) -> Resul
     for (i, (old_arg, new_arg)) in
         old_body.arg_locals().iter().zip(new_body.arg_locals().iter()).enumerate()
     {
-        if old_arg.ty != new_arg.ty {
+        let old_arg_ty = expected_stub_ty(tcx, old_arg.ty, type_stubs);
+        let new_arg_ty = rename(new_arg.ty);
+        if old_arg_ty != new_arg_ty {
             diff.push(format!(
                 "Expected type `{}` for parameter {}, but found `{}`",
-                old_arg.ty,
+                old_arg_ty,
                 i + 1,
-                new_arg.ty
+                new_arg_ty
             ));
         }
     }
@@ -152,6 +245,38 @@ pub fn check_compatibility(tcx: TyCtxt, old_def: FnDef, new_def: FnDef) -> Resul
     }
 }
 
+/// Check that a value of type `orig` can be reinterpreted as a value of type `model`.
+///
+/// This is required when a method of a stubbed type is replaced by a method of its model, since
+/// the arguments and return value are transmuted between both types. References and raw pointers
+/// are compared according to their pointee types.
+pub fn check_layout_compatibility(orig: Ty, model: Ty) -> Result<(), String> {
+    let (orig_ty, model_ty) =
+        match (orig.kind().builtin_deref(true), model.kind().builtin_deref(true)) {
+            (Some(orig_pointee), Some(model_pointee)) => (orig_pointee.ty, model_pointee.ty),
+            _ => (orig, model),
+        };
+    let layout = |ty: Ty| {
+        ty.layout()
+            .map(|layout| layout.shape())
+            .map_err(|err| format!("Cannot compute the layout of `{ty}`: {err}"))
+    };
+    let orig_layout = layout(orig_ty)?;
+    let model_layout = layout(model_ty)?;
+    if orig_layout.size != model_layout.size || orig_layout.abi_align != model_layout.abi_align {
+        Err(format!(
+            "Expected `{model_ty}` to have the same layout as `{orig_ty}`, but `{orig_ty}` has \
+            size {} and alignment {}, while `{model_ty}` has size {} and alignment {}",
+            orig_layout.size.bytes(),
+            orig_layout.abi_align,
+            model_layout.size.bytes(),
+            model_layout.abi_align,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 /// Validate that an instance body can be instantiated.
 ///
 /// Stubbing may cause an instance to not be correctly instantiated since we delay checking its
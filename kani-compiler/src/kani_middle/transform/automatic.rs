@@ -9,10 +9,10 @@
 use crate::args::ReachabilityType;
 use crate::kani_middle::attributes::KaniAttributes;
 use crate::kani_middle::codegen_units::CodegenUnit;
-use crate::kani_middle::implements_arbitrary;
 use crate::kani_middle::kani_functions::{KaniHook, KaniIntrinsic, KaniModel};
 use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
 use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_middle::{extern_c_ptr_elem_ty, implements_arbitrary, is_extern_c_entry_point};
 use crate::kani_queries::QueryDb;
 use fxhash::FxHashMap;
 use rustc_middle::ty::TyCtxt;
@@ -23,7 +23,8 @@
     Place, Rvalue, SwitchTargets, Terminator, TerminatorKind,
 };
 use rustc_public::ty::{
-    AdtDef, AdtKind, FnDef, GenericArgKind, GenericArgs, RigidTy, Ty, TyKind, UintTy, VariantDef,
+    AdtDef, AdtKind, FnDef, GenericArgKind, GenericArgs, Region, RegionKind, RigidTy, Ty, TyConst,
+    TyKind, UintTy, VariantDef,
 };
 use rustc_public_bridge::IndexedVal;
 use tracing::debug;
@@ -285,8 +286,12 @@ fn generate_struct_body(&self, def: AdtDef, args: GenericArgs, body: Body) -> Bo
 #[derive(Debug, Clone)]
 pub struct AutomaticHarnessPass {
     kani_any: FnDef,
+    kani_any_c_ptr: FnDef,
     init_contracts_hook: Instance,
     kani_autoharness_intrinsic: FnDef,
+    /// If we model the raw pointer arguments of `extern "C"` functions,
+    /// the number of elements in the allocations that they point to.
+    extern_c_buffer_len: Option<usize>,
 }
 
 impl AutomaticHarnessPass {
@@ -295,10 +300,20 @@ pub fn new(query_db: &QueryDb) -> Self {
         let kani_autoharness_intrinsic =
             *kani_fns.get(&KaniIntrinsic::AutomaticHarness.into()).unwrap();
         let kani_any = *kani_fns.get(&KaniModel::Any.into()).unwrap();
+        let kani_any_c_ptr = *kani_fns.get(&KaniModel::AnyCPtr.into()).unwrap();
         let init_contracts_hook = *kani_fns.get(&KaniHook::InitContracts.into()).unwrap();
         let init_contracts_hook =
             Instance::resolve(init_contracts_hook, &GenericArgs(vec![])).unwrap();
-        Self { kani_any, init_contracts_hook, kani_autoharness_intrinsic }
+        let args = query_db.args();
+        let extern_c_buffer_len =
+            args.autoharness_extern_c.then_some(args.autoharness_extern_c_buffer_len);
+        Self {
+            kani_any,
+            kani_any_c_ptr,
+            init_contracts_hook,
+            kani_autoharness_intrinsic,
+            extern_c_buffer_len,
+        }
     }
 }
 
@@ -352,19 +367,26 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
             );
         }
 
+        let buffer_len =
+            self.extern_c_buffer_len.filter(|_| is_extern_c_entry_point(tcx, fn_to_verify));
+
         // For each argument of `fn_to_verify`, create a nondeterministic value of its type
         // by generating a kani::any() call and saving the result in `arg_local`.
+        // Raw pointer arguments of `extern "C"` functions get a nondeterministic buffer instead.
         let arg_locals = fn_to_verify_body
             .arg_locals()
             .iter()
-            .map(|local_decl| {
-                call_kani_any_for_ty(
+            .map(|local_decl| match (buffer_len, extern_c_ptr_elem_ty(tcx, local_decl.ty)) {
+                (Some(len), Some(elem_ty)) => {
+                    self.call_any_c_ptr(&mut harness_body, local_decl.ty, elem_ty, len, &mut source)
+                }
+                _ => call_kani_any_for_ty(
                     self.kani_any,
                     &mut harness_body,
                     local_decl.ty,
                     local_decl.mutability,
                     &mut source,
-                )
+                ),
             })
             .collect::<Vec<_>>();
 
@@ -387,3 +409,88 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
         (true, harness_body.into())
     }
 }
+
+impl AutomaticHarnessPass {
+    /// Insert the statements that create a raw pointer argument of type `ptr_ty` for an `extern "C"` function;
+    /// return the local storing the pointer.
+    /// The generated code is equivalent to:
+    /// ```ignore
+    /// let mut buffer: [elem_ty; len] = [kani::any(), kani::any(), ...];
+    /// let ptr = kani::internal::any_c_ptr(&mut buffer) as ptr_ty;
+    /// ```
+    fn call_any_c_ptr(
+        &self,
+        body: &mut MutableBody,
+        ptr_ty: Ty,
+        elem_ty: Ty,
+        len: usize,
+        source: &mut SourceInstruction,
+    ) -> Local {
+        let elems = (0..len)
+            .map(|_| {
+                let elem =
+                    call_kani_any_for_ty(self.kani_any, body, elem_ty, Mutability::Not, source);
+                Operand::Move(Place::from(elem))
+            })
+            .collect();
+        let span = source.span(body.blocks());
+        let buffer_ty = Ty::try_new_array(elem_ty, len as u64).unwrap();
+        let buffer = body.new_local(buffer_ty, span, Mutability::Mut);
+        body.assign_to(
+            Place::from(buffer),
+            Rvalue::Aggregate(AggregateKind::Array(elem_ty), elems),
+            source,
+            InsertPosition::Before,
+        );
+
+        let region = Region { kind: RegionKind::ReErased };
+        let buffer_ref = body.new_local(
+            Ty::new_ref(region.clone(), buffer_ty, Mutability::Mut),
+            span,
+            Mutability::Not,
+        );
+        body.assign_to(
+            Place::from(buffer_ref),
+            Rvalue::Ref(
+                region,
+                BorrowKind::Mut { kind: MutBorrowKind::Default },
+                Place::from(buffer),
+            ),
+            source,
+            InsertPosition::Before,
+        );
+
+        let any_c_ptr = Instance::resolve(
+            self.kani_any_c_ptr,
+            &GenericArgs(vec![
+                GenericArgKind::Type(elem_ty),
+                GenericArgKind::Const(TyConst::try_from_target_usize(len as u64).unwrap()),
+            ]),
+        )
+        .unwrap();
+        let elem_ptr_ty = Ty::new_ptr(elem_ty, Mutability::Mut);
+        let elem_ptr = body.new_local(elem_ptr_ty, span, Mutability::Not);
+        body.insert_call(
+            &any_c_ptr,
+            source,
+            InsertPosition::Before,
+            vec![Operand::Move(Place::from(buffer_ref))],
+            Place::from(elem_ptr),
+        );
+
+        // Cast the pointer if the argument is a `*const` or a `c_void` pointer.
+        if ptr_ty == elem_ptr_ty {
+            return elem_ptr;
+        }
+        let TyKind::RigidTy(RigidTy::RawPtr(pointee, mutability)) = ptr_ty.kind() else {
+            unreachable!("expected a raw pointer argument, got {ptr_ty}")
+        };
+        body.insert_ptr_cast(
+            Operand::Move(Place::from(elem_ptr)),
+            pointee,
+            mutability,
+            source,
+            InsertPosition::Before,
+        )
+    }
+}
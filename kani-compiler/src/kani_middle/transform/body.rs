@@ -189,7 +189,9 @@ pub fn insert_check(
         let span = source.span(&self.blocks);
         let msg_op = self.new_str_operand(msg, span);
         let (assert_fn, args) = match check_type {
-            CheckType::SafetyCheck(assert_fn) | CheckType::SafetyCheckNoAssume(assert_fn) => {
+            CheckType::SafetyCheck(assert_fn)
+            | CheckType::SafetyCheckNoAssume(assert_fn)
+            | CheckType::RecursionLimitCheck(assert_fn) => {
                 assert_eq!(
                     self.locals[value.unwrap()].ty,
                     Ty::bool_ty(),
@@ -454,6 +456,7 @@ pub enum CheckType {
     SafetyCheck(Instance),
     SafetyCheckNoAssume(Instance),
     UnsupportedCheck(Instance),
+    RecursionLimitCheck(Instance),
 }
 
 impl CheckType {
@@ -479,6 +482,13 @@ pub fn new_unsupported_check_assert_assume_false(queries: &QueryDb) -> CheckType
         let fn_def = queries.kani_functions()[&KaniHook::UnsupportedCheck.into()];
         CheckType::UnsupportedCheck(Instance::resolve(fn_def, &GenericArgs(vec![])).unwrap())
     }
+
+    /// This will create the check that a recursive call does not exceed the recursion limit of
+    /// the harness, which generates an assertion followed by an assumption of the same assertion.
+    pub fn new_recursion_limit_check(queries: &QueryDb) -> CheckType {
+        let fn_def = queries.kani_functions()[&KaniHook::RecursionLimitCheck.into()];
+        CheckType::RecursionLimitCheck(Instance::resolve(fn_def, &GenericArgs(vec![])).unwrap())
+    }
 }
 
 /// We store the index of an instruction to avoid borrow checker issues and unnecessary copies.
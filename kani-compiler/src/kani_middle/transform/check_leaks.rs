@@ -0,0 +1,249 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! A transformation pass that instruments the code to check that every heap allocation of a
+//! harness is deallocated exactly once, which catches the leaks of `mem::forget`, `Box::leak` or
+//! reference cycles of `Rc`, and the double drops that unsafe code may introduce.
+//!
+//! All the heap allocations of `Box`, `Vec`, `Rc` and the other collections of the standard
+//! library go through the global allocator, so we instrument the calls to its functions:
+//! `__rust_alloc`, `__rust_alloc_zeroed`, `__rust_realloc` and `__rust_dealloc`, as well as
+//! `exchange_malloc`, which allocates the contents of a `Box` and which Kani replaces by a call to
+//! `malloc`.
+//!
+//! Like the checks for uninitialized memory, we track a single allocation, which we choose
+//! non-deterministically at the beginning of every harness. We record when the allocator returns
+//! it, we check that it's still live when it's returned to the allocator, and we check that it's
+//! no longer live when the harness returns. Since CBMC explores every choice of the tracked
+//! allocation, this amounts to checking the whole set of live allocations.
+
+use crate::args::ExtraChecks;
+use crate::kani_middle::attributes::is_proof_harness;
+use crate::kani_middle::kani_functions::{KaniFunction, KaniModel};
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::{Body, Local, Mutability, Operand, Place, TerminatorKind};
+use rustc_public::ty::{FnDef, GenericArgs, RigidTy, Ty, TyKind, UintTy};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use tracing::{debug, trace};
+
+/// Instrument the code with checks that heap allocations are deallocated exactly once.
+#[derive(Debug, Clone)]
+pub struct LeakPass {
+    pub safety_check_type: CheckType,
+    pub kani_functions: HashMap<KaniFunction, FnDef>,
+}
+
+impl TransformPass for LeakPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        let args = query_db.args();
+        args.ub_check.contains(&ExtraChecks::Leaks)
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        let mut new_body = MutableBody::from(body);
+        let orig_len = new_body.blocks().len();
+        let is_harness = is_proof_harness(tcx, instance);
+
+        for (event, bb) in find_events(&new_body, is_harness) {
+            debug!(?event, ?bb, "transform");
+            let source = SourceInstruction::Terminator { bb };
+            match event {
+                HeapEvent::Alloc(place) => self.build_alloc(&mut new_body, place, source),
+                HeapEvent::Dealloc(operand) => self.build_dealloc(&mut new_body, operand, source),
+                HeapEvent::Realloc(operand, place) => {
+                    // Instrumenting after the call leaves it in its basic block, so we can still
+                    // instrument before it.
+                    self.build_alloc(&mut new_body, place, source);
+                    self.build_dealloc(&mut new_body, operand, source);
+                }
+                HeapEvent::HarnessReturn => self.build_leak_check(&mut new_body, source),
+            }
+        }
+
+        // Choose the tracked allocation at the beginning of the harness.
+        if is_harness {
+            let mut source = if new_body.blocks()[0].statements.is_empty() {
+                SourceInstruction::Terminator { bb: 0 }
+            } else {
+                SourceInstruction::Statement { idx: 0, bb: 0 }
+            };
+            self.call_model(
+                &mut new_body,
+                KaniModel::InitializeLeakState,
+                vec![],
+                Ty::new_tuple(&[]),
+                &mut source,
+                InsertPosition::Before,
+            );
+        }
+        (orig_len != new_body.blocks().len(), new_body.into())
+    }
+}
+
+impl LeakPass {
+    /// Record the allocation that the call at `source` stores in `place`.
+    fn build_alloc(&self, body: &mut MutableBody, place: Place, mut source: SourceInstruction) {
+        let ptr = body.insert_ptr_cast(
+            Operand::Copy(place),
+            Ty::unsigned_ty(UintTy::U8),
+            Mutability::Not,
+            &mut source,
+            InsertPosition::After,
+        );
+        self.call_model(
+            body,
+            KaniModel::RecordAllocation,
+            vec![Operand::Move(Place::from(ptr))],
+            Ty::new_tuple(&[]),
+            &mut source,
+            InsertPosition::After,
+        );
+    }
+
+    /// Record the deallocation of the pointer in `operand` by the call at `source`, and check that
+    /// it wasn't deallocated before.
+    fn build_dealloc(
+        &self,
+        body: &mut MutableBody,
+        operand: Operand,
+        mut source: SourceInstruction,
+    ) {
+        let ptr = body.insert_ptr_cast(
+            operand,
+            Ty::unsigned_ty(UintTy::U8),
+            Mutability::Not,
+            &mut source,
+            InsertPosition::Before,
+        );
+        let result = self.call_model(
+            body,
+            KaniModel::RecordDeallocation,
+            vec![Operand::Move(Place::from(ptr))],
+            Ty::bool_ty(),
+            &mut source,
+            InsertPosition::Before,
+        );
+        body.insert_check(
+            &self.safety_check_type,
+            &mut source,
+            InsertPosition::Before,
+            Some(result),
+            "Memory leak check: the heap allocation is deallocated more than once",
+        );
+    }
+
+    /// Check that no allocation is live when the harness returns at `source`.
+    fn build_leak_check(&self, body: &mut MutableBody, mut source: SourceInstruction) {
+        let result = self.call_model(
+            body,
+            KaniModel::IsLeakFree,
+            vec![],
+            Ty::bool_ty(),
+            &mut source,
+            InsertPosition::Before,
+        );
+        body.insert_check(
+            &self.safety_check_type,
+            &mut source,
+            InsertPosition::Before,
+            Some(result),
+            "Memory leak check: the heap allocation is never deallocated",
+        );
+    }
+
+    /// Insert a call to the given model, and return the local that stores its result.
+    fn call_model(
+        &self,
+        body: &mut MutableBody,
+        model: KaniModel,
+        args: Vec<Operand>,
+        ret_ty: Ty,
+        source: &mut SourceInstruction,
+        position: InsertPosition,
+    ) -> Local {
+        let fn_def = self.kani_functions[&KaniFunction::Model(model)];
+        let instance = Instance::resolve(fn_def, &GenericArgs(vec![])).unwrap();
+        let result = body.new_local(ret_ty, source.span(body.blocks()), Mutability::Not);
+        body.insert_call(&instance, source, position, args, Place::from(result));
+        result
+    }
+}
+
+/// A terminator that the leak checks instrument.
+#[derive(Debug)]
+enum HeapEvent {
+    /// A call that stores a new allocation in the given place.
+    Alloc(Place),
+    /// A call that deallocates the given pointer.
+    Dealloc(Operand),
+    /// A call that deallocates the given pointer, and stores a new allocation in the given place.
+    Realloc(Operand, Place),
+    /// The return of a harness.
+    HarnessReturn,
+}
+
+/// Find the terminators that allocate or deallocate heap memory, and the returns of the harness
+/// if `is_harness` holds.
+fn find_events(body: &MutableBody, is_harness: bool) -> Vec<(HeapEvent, usize)> {
+    let mut events = vec![];
+    for (bb, block) in body.blocks().iter().enumerate() {
+        let (func, args, destination) = match &block.terminator.kind {
+            TerminatorKind::Call { func, args, destination, target: Some(_), .. } => {
+                (func, args, destination)
+            }
+            TerminatorKind::Return if is_harness => {
+                events.push((HeapEvent::HarnessReturn, bb));
+                continue;
+            }
+            _ => continue,
+        };
+        let TyKind::RigidTy(RigidTy::FnDef(def, generic_args)) =
+            func.ty(body.locals()).unwrap().kind()
+        else {
+            continue;
+        };
+        let Ok(instance) = Instance::resolve(def, &generic_args) else {
+            continue;
+        };
+        // The instrumentation uses the pointer before the call does.
+        let ptr_arg = || match &args[0] {
+            Operand::Copy(place) | Operand::Move(place) => Operand::Copy(place.clone()),
+            operand @ Operand::Constant(_) => operand.clone(),
+        };
+        let event = match instance.name().as_str() {
+            "alloc::alloc::exchange_malloc" => HeapEvent::Alloc(destination.clone()),
+            "alloc::alloc::__rust_alloc" | "alloc::alloc::__rust_alloc_zeroed"
+                if instance.is_foreign_item() =>
+            {
+                HeapEvent::Alloc(destination.clone())
+            }
+            "alloc::alloc::__rust_realloc" if instance.is_foreign_item() => {
+                HeapEvent::Realloc(ptr_arg(), destination.clone())
+            }
+            "alloc::alloc::__rust_dealloc" if instance.is_foreign_item() => {
+                HeapEvent::Dealloc(ptr_arg())
+            }
+            _ => continue,
+        };
+        events.push((event, bb));
+    }
+    events
+}
@@ -0,0 +1,286 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! A transformation pass that instruments the code to detect violations of the provenance rules of
+//! Rust by pointers that are cast from integers, and by wrapping pointer arithmetic. See
+//! <https://doc.rust-lang.org/std/ptr/index.html#provenance> for the rules.
+//!
+//! A pointer-to-integer cast (`ptr as usize` or `ptr.expose_provenance()`) exposes the provenance
+//! of the allocation of `ptr`. An integer-to-pointer cast (`addr as *const T` or
+//! `ptr::with_exposed_provenance(addr)`) may only pick up the provenance of an allocation that was
+//! exposed before, and the integer arithmetic that computed its address must not leave that
+//! allocation. Wrapping pointer arithmetic, e.g., `ptr.wrapping_add(n)` or `ptr.with_addr(addr)`,
+//! keeps the provenance of `ptr`, so the pointer that it computes must stay in the bounds of that
+//! allocation, or one past its end. The arithmetic that `ptr.add(n)` does is already checked.
+//!
+//! Whether an allocation was exposed is only recorded for a single allocation, which the harness
+//! chooses non-deterministically when it starts, so the check covers every allocation at once.
+//! Every pointer-to-integer cast records whether it exposes the tracked allocation, and every
+//! integer-to-pointer cast checks that its pointer either doesn't point to the tracked allocation,
+//! or that the tracked allocation was exposed. CBMC keeps track of the allocation that an address
+//! derived from a pointer belongs to. Code that respects strict provenance doesn't cast integers
+//! to pointers, so it only needs to pass the checks of wrapping arithmetic.
+
+use crate::args::ExtraChecks;
+use crate::kani_middle::attributes::is_proof_harness;
+use crate::kani_middle::kani_functions::{KaniFunction, KaniModel};
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::{
+    Body, CastKind, Local, Mutability, Operand, Place, Rvalue, StatementKind, TerminatorKind,
+};
+use rustc_public::ty::{FnDef, GenericArgs, RigidTy, Ty, TyKind, UintTy};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use tracing::{debug, trace};
+
+/// Instrument the code with checks for pointers cast from integers.
+#[derive(Debug, Clone)]
+pub struct ProvenancePass {
+    pub safety_check_type: CheckType,
+    pub kani_functions: HashMap<KaniFunction, FnDef>,
+}
+
+impl TransformPass for ProvenancePass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        let args = query_db.args();
+        args.ub_check.contains(&ExtraChecks::Provenance)
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        let mut new_body = MutableBody::from(body);
+        let orig_len = new_body.blocks().len();
+
+        // Instrument the casts in reverse order, so that the instrumentation of a cast doesn't
+        // move the casts that come before it.
+        for (cast, source) in find_casts(&new_body).into_iter().rev() {
+            debug!(?cast, ?source, "transform");
+            match cast {
+                ProvenanceCast::Expose(operand) => {
+                    self.build_expose(tcx, &mut new_body, operand, source)
+                }
+                ProvenanceCast::WithExposed(place) => {
+                    self.build_checks(tcx, &mut new_body, place, source, WITH_EXPOSED_CHECKS)
+                }
+                ProvenanceCast::WrappingOffset(place) => {
+                    self.build_checks(tcx, &mut new_body, place, source, WRAPPING_OFFSET_CHECKS)
+                }
+            }
+        }
+
+        // Choose the tracked allocation at the beginning of the harness.
+        if is_proof_harness(tcx, instance) {
+            let mut source = if new_body.blocks()[0].statements.is_empty() {
+                SourceInstruction::Terminator { bb: 0 }
+            } else {
+                SourceInstruction::Statement { idx: 0, bb: 0 }
+            };
+            self.call_model(
+                tcx,
+                &mut new_body,
+                KaniModel::InitializeProvenanceState,
+                vec![],
+                Ty::new_tuple(&[]),
+                &mut source,
+                InsertPosition::Before,
+            );
+        }
+        (orig_len != new_body.blocks().len(), new_body.into())
+    }
+}
+
+impl ProvenancePass {
+    /// Record that the allocation of the pointer in `operand` is exposed, before the cast at
+    /// `source` takes its address.
+    fn build_expose(
+        &self,
+        tcx: TyCtxt,
+        body: &mut MutableBody,
+        operand: Operand,
+        mut source: SourceInstruction,
+    ) {
+        let ptr = body.insert_ptr_cast(
+            operand,
+            Ty::unsigned_ty(UintTy::U8),
+            Mutability::Not,
+            &mut source,
+            InsertPosition::Before,
+        );
+        self.call_model(
+            tcx,
+            body,
+            KaniModel::ExposeProvenance,
+            vec![Operand::Move(Place::from(ptr))],
+            Ty::new_tuple(&[]),
+            &mut source,
+            InsertPosition::Before,
+        );
+    }
+
+    /// Check the pointer that the instruction at `source` assigns to `place` with each of the
+    /// given models.
+    fn build_checks(
+        &self,
+        tcx: TyCtxt,
+        body: &mut MutableBody,
+        place: Place,
+        mut source: SourceInstruction,
+        checks: &[(KaniModel, &str)],
+    ) {
+        let ptr = body.insert_ptr_cast(
+            Operand::Copy(place),
+            Ty::unsigned_ty(UintTy::U8),
+            Mutability::Not,
+            &mut source,
+            InsertPosition::After,
+        );
+        for (model, msg) in checks {
+            let result = self.call_model(
+                tcx,
+                body,
+                *model,
+                vec![Operand::Copy(Place::from(ptr))],
+                Ty::bool_ty(),
+                &mut source,
+                InsertPosition::After,
+            );
+            body.insert_check(
+                &self.safety_check_type,
+                &mut source,
+                InsertPosition::After,
+                Some(result),
+                msg,
+            );
+        }
+    }
+
+    /// Insert a call to the given model, and return the local that stores its result.
+    #[allow(clippy::too_many_arguments)]
+    fn call_model(
+        &self,
+        tcx: TyCtxt,
+        body: &mut MutableBody,
+        model: KaniModel,
+        args: Vec<Operand>,
+        ret_ty: Ty,
+        source: &mut SourceInstruction,
+        position: InsertPosition,
+    ) -> Local {
+        let Some(&fn_def) = self.kani_functions.get(&KaniFunction::Model(model)) else {
+            let name: &'static str = model.into();
+            tcx.dcx().fatal(format!(
+                "the provenance checks require the `{name}` function of the Kani library, which \
+                could not be found"
+            ))
+        };
+        let instance = Instance::resolve(fn_def, &GenericArgs(vec![])).unwrap();
+        let result = body.new_local(ret_ty, source.span(body.blocks()), Mutability::Not);
+        body.insert_call(&instance, source, position, args, Place::from(result));
+        result
+    }
+}
+
+/// The checks of the pointers cast from integers.
+const WITH_EXPOSED_CHECKS: &[(KaniModel, &str)] = &[
+    (
+        KaniModel::HasExposedProvenance,
+        "Provenance violation: the pointer cast from an integer points to an allocation whose \
+        provenance was never exposed",
+    ),
+    (
+        KaniModel::IsWithinAllocation,
+        "Provenance violation: the pointer cast from an integer is out of the bounds of its \
+        allocation",
+    ),
+];
+
+/// The checks of the pointers computed by wrapping pointer arithmetic.
+const WRAPPING_OFFSET_CHECKS: &[(KaniModel, &str)] = &[(
+    KaniModel::StaysInAllocation,
+    "Provenance violation: the wrapping pointer arithmetic leaves the bounds of the allocation",
+)];
+
+/// An instruction that the provenance checks instrument.
+#[derive(Debug)]
+enum ProvenanceCast {
+    /// A pointer-to-integer cast of the given raw pointer.
+    Expose(Operand),
+    /// An integer-to-pointer cast whose result is stored in the given place.
+    WithExposed(Place),
+    /// A call to the `arith_offset` intrinsic, which implements the wrapping pointer arithmetic,
+    /// whose result is stored in the given place.
+    WrappingOffset(Place),
+}
+
+/// Find the casts that expose the provenance of a raw pointer or that pick it up, and the
+/// wrapping pointer arithmetic, in order.
+fn find_casts(body: &MutableBody) -> Vec<(ProvenanceCast, SourceInstruction)> {
+    let mut casts = vec![];
+    for (bb, block) in body.blocks().iter().enumerate() {
+        if let TerminatorKind::Call { func, destination, target: Some(_), .. } =
+            &block.terminator.kind
+            && let TyKind::RigidTy(RigidTy::FnDef(def, args)) =
+                func.ty(body.locals()).unwrap().kind()
+            && Instance::resolve(def, &args).unwrap().intrinsic_name().as_deref()
+                == Some("arith_offset")
+        {
+            casts.push((
+                ProvenanceCast::WrappingOffset(destination.clone()),
+                SourceInstruction::Terminator { bb },
+            ));
+        }
+        for (idx, stmt) in block.statements.iter().enumerate() {
+            let StatementKind::Assign(place, Rvalue::Cast(kind, operand, _)) = &stmt.kind else {
+                continue;
+            };
+            let source = SourceInstruction::Statement { idx, bb };
+            match kind {
+                CastKind::PointerExposeAddress => {
+                    // Function pointers can also be cast to integers, but they don't point to an
+                    // allocation.
+                    if operand.ty(body.locals()).unwrap().kind().is_raw_ptr() {
+                        // The instrumentation uses the pointer before the cast does.
+                        let operand = match operand {
+                            Operand::Copy(place) | Operand::Move(place) => {
+                                Operand::Copy(place.clone())
+                            }
+                            Operand::Constant(_) => operand.clone(),
+                        };
+                        casts.push((ProvenanceCast::Expose(operand), source));
+                    }
+                }
+                CastKind::PointerWithExposedProvenance => {
+                    casts.push((ProvenanceCast::WithExposed(place.clone()), source));
+                }
+                // Transmuting a pointer to an integer doesn't expose its provenance, and
+                // transmuting an integer to a pointer creates a pointer without provenance, which
+                // the existing pointer checks reject when it's dereferenced.
+                CastKind::PointerCoercion(_)
+                | CastKind::IntToInt
+                | CastKind::FloatToInt
+                | CastKind::FloatToFloat
+                | CastKind::IntToFloat
+                | CastKind::PtrToPtr
+                | CastKind::FnPtrToPtr
+                | CastKind::Transmute => {}
+            }
+        }
+    }
+    casts
+}
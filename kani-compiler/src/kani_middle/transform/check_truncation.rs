@@ -0,0 +1,276 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! A transformation pass that instruments the code to detect `as` casts that lose information.
+//!
+//! An integer cast silently truncates or changes the sign of a value that the target type can't
+//! represent, e.g. `300u32 as u8` is `44` and `-1i8 as u64` is `u64::MAX`. A float-to-integer cast
+//! saturates the values out of the range of the target type, and turns NaN into `0`. We check
+//! that the value is representable in the target type before every cast that may lose information,
+//! i.e., the integer casts whose target type can't represent every value of the source type, and
+//! all the float-to-integer casts. Truncating the fractional part of a float is expected, so a
+//! float-to-integer cast only fails if the truncated value is out of range.
+//!
+//! The casts of a statement or an expression annotated with `#[kani::allow_truncation]` are
+//! intentionally lossy, and we don't check them. We also don't check the casts of the standard
+//! library and of the Kani library, which truncate values on purpose, e.g. to extract bytes.
+
+use crate::args::ExtraChecks;
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::kani_functions::{KaniFunction, KaniHook};
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_public::CrateDef;
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::{
+    BinOp, Body, CastKind, Local, Mutability, Operand, Place, Rvalue, StatementKind,
+};
+use rustc_public::rustc_internal;
+use rustc_public::ty::{FnDef, GenericArgKind, GenericArgs, IntTy, RigidTy, Ty, UintTy};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use tracing::{debug, trace};
+
+/// The crates whose casts are not checked.
+const STD_CRATES: [&str; 4] = ["core", "alloc", "std", "kani"];
+
+/// Instrument the code with checks for lossy `as` casts.
+#[derive(Debug, Clone)]
+pub struct TruncationPass {
+    pub safety_check_type: CheckType,
+    pub kani_functions: HashMap<KaniFunction, FnDef>,
+}
+
+impl TransformPass for TruncationPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        let args = query_db.args();
+        args.ub_check.contains(&ExtraChecks::Truncation)
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        if STD_CRATES.contains(&instance.def.krate().name.as_str()) {
+            return (false, body);
+        }
+        let mut new_body = MutableBody::from(body);
+        let casts = find_lossy_casts(&new_body);
+        if casts.is_empty() {
+            return (false, new_body.into());
+        }
+        let allowed = KaniAttributes::for_instance(tcx, instance).allowed_truncations();
+        let orig_len = new_body.blocks().len();
+
+        // Instrument the casts in reverse order, so that the instrumentation of a cast doesn't
+        // move the casts that come before it.
+        for cast in casts.into_iter().rev() {
+            let span = rustc_internal::internal(tcx, cast.source.span(new_body.blocks()));
+            if allowed.iter().any(|allowed| allowed.contains(span)) {
+                debug!(?cast, "allowed truncation");
+                continue;
+            }
+            debug!(?cast, "transform");
+            if matches!(cast.from.kind().rigid(), Some(RigidTy::Float(_))) {
+                self.build_float_check(&mut new_body, cast);
+            } else {
+                self.build_int_check(&mut new_body, cast);
+            }
+        }
+        (orig_len != new_body.blocks().len(), new_body.into())
+    }
+}
+
+impl TruncationPass {
+    /// Check that the integer that `cast` converts is in the range of the target type, by
+    /// comparing it to the bounds of the target type that are within the range of the source type.
+    fn build_int_check(&self, body: &mut MutableBody, cast: LossyCast) {
+        let LossyCast { operand, from, to, mut source } = cast;
+        let span = source.span(body.blocks());
+        let from_range = IntRange::of(from).unwrap();
+        let to_range = IntRange::of(to).unwrap();
+        // Compare `char` values as `u32` values, which have the same range.
+        let (value, value_ty) = if matches!(from.kind().rigid(), Some(RigidTy::Char)) {
+            let u32_ty = Ty::unsigned_ty(UintTy::U32);
+            let rvalue = Rvalue::Cast(CastKind::IntToInt, operand, u32_ty);
+            let value = body.insert_assignment(rvalue, &mut source, InsertPosition::Before);
+            (Operand::Copy(Place::from(value)), u32_ty)
+        } else {
+            (operand, from)
+        };
+
+        let mut bounds = vec![];
+        if to_range.min > from_range.min {
+            bounds.push((BinOp::Ge, to_range.min as u128));
+        }
+        if to_range.max < from_range.max {
+            bounds.push((BinOp::Le, to_range.max));
+        }
+        let mut result: Option<Local> = None;
+        for (op, bound) in bounds {
+            // Encode the bound with the bits of the source type, and reinterpret them as a value
+            // of the source type if it is signed.
+            let bits = bound & (u128::MAX >> (128 - from_range.bits));
+            let mut bound = body.new_uint_operand(bits, from_range.unsigned_ty, span);
+            if from_range.signed {
+                let rvalue = Rvalue::Cast(CastKind::IntToInt, bound, value_ty);
+                let signed = body.insert_assignment(rvalue, &mut source, InsertPosition::Before);
+                bound = Operand::Move(Place::from(signed));
+            }
+            let check = body.insert_binary_op(
+                op,
+                value.clone(),
+                bound,
+                &mut source,
+                InsertPosition::Before,
+            );
+            result = Some(match result {
+                None => check,
+                Some(previous) => body.insert_binary_op(
+                    BinOp::BitAnd,
+                    Operand::Move(Place::from(previous)),
+                    Operand::Move(Place::from(check)),
+                    &mut source,
+                    InsertPosition::Before,
+                ),
+            });
+        }
+        body.insert_check(
+            &self.safety_check_type,
+            &mut source,
+            InsertPosition::Before,
+            result,
+            &format!("Lossy cast: `{from} as {to}` truncates a value that doesn't fit in `{to}`"),
+        );
+    }
+
+    /// Check that the float that `cast` converts is in the range of the target type after its
+    /// fractional part is truncated.
+    fn build_float_check(&self, body: &mut MutableBody, cast: LossyCast) {
+        let LossyCast { operand, from, to, mut source } = cast;
+        let span = source.span(body.blocks());
+        let fn_def = self.kani_functions[&KaniHook::FloatToIntInRange.into()];
+        let args = GenericArgs(vec![GenericArgKind::Type(from), GenericArgKind::Type(to)]);
+        let instance = Instance::resolve(fn_def, &args).unwrap();
+        let result = body.new_local(Ty::bool_ty(), span, Mutability::Not);
+        body.insert_call(
+            &instance,
+            &mut source,
+            InsertPosition::Before,
+            vec![operand],
+            Place::from(result),
+        );
+        body.insert_check(
+            &self.safety_check_type,
+            &mut source,
+            InsertPosition::Before,
+            Some(result),
+            &format!("Lossy cast: `{from} as {to}` saturates a NaN or out of range value"),
+        );
+    }
+}
+
+/// A cast that may lose information.
+#[derive(Debug)]
+struct LossyCast {
+    /// The value being cast, which the check uses before the cast does.
+    operand: Operand,
+    from: Ty,
+    to: Ty,
+    source: SourceInstruction,
+}
+
+/// The range of values of an integer type, or of `char`, whose values casts treat as `u32`
+/// values.
+#[derive(Debug)]
+struct IntRange {
+    signed: bool,
+    bits: u32,
+    min: i128,
+    max: u128,
+    /// The unsigned type with the same size.
+    unsigned_ty: UintTy,
+}
+
+impl IntRange {
+    fn of(ty: Ty) -> Option<IntRange> {
+        let (signed, unsigned_ty) = match ty.kind().rigid()? {
+            RigidTy::Int(int_ty) => (true, unsigned_ty(*int_ty)),
+            RigidTy::Uint(uint_ty) => (false, *uint_ty),
+            RigidTy::Char => (false, UintTy::U32),
+            _ => return None,
+        };
+        let bits = (ty.layout().unwrap().shape().size.bytes() * 8) as u32;
+        let (min, max) = if signed {
+            (i128::MIN >> (128 - bits), u128::MAX >> (129 - bits))
+        } else {
+            (0, u128::MAX >> (128 - bits))
+        };
+        Some(IntRange { signed, bits, min, max, unsigned_ty })
+    }
+}
+
+/// The unsigned integer type with the same size as the given signed one.
+fn unsigned_ty(int_ty: IntTy) -> UintTy {
+    match int_ty {
+        IntTy::Isize => UintTy::Usize,
+        IntTy::I8 => UintTy::U8,
+        IntTy::I16 => UintTy::U16,
+        IntTy::I32 => UintTy::U32,
+        IntTy::I64 => UintTy::U64,
+        IntTy::I128 => UintTy::U128,
+    }
+}
+
+/// Find the casts that may lose information, in order.
+fn find_lossy_casts(body: &MutableBody) -> Vec<LossyCast> {
+    let mut casts = vec![];
+    for (bb, block) in body.blocks().iter().enumerate() {
+        for (idx, stmt) in block.statements.iter().enumerate() {
+            let StatementKind::Assign(_, Rvalue::Cast(kind, operand, to)) = &stmt.kind else {
+                continue;
+            };
+            let from = operand.ty(body.locals()).unwrap();
+            let is_lossy = match kind {
+                CastKind::IntToInt => match (IntRange::of(from), IntRange::of(*to)) {
+                    (Some(from), Some(to)) => to.min > from.min || to.max < from.max,
+                    // Casts from `bool` and to `char` preserve the value.
+                    _ => false,
+                },
+                CastKind::FloatToInt => true,
+                // Casts between floats and from integers to floats round the value to the nearest
+                // representable one, which is a loss of precision rather than a truncation.
+                CastKind::FloatToFloat
+                | CastKind::IntToFloat
+                | CastKind::PointerExposeAddress
+                | CastKind::PointerWithExposedProvenance
+                | CastKind::PointerCoercion(_)
+                | CastKind::PtrToPtr
+                | CastKind::FnPtrToPtr
+                | CastKind::Transmute => false,
+            };
+            if is_lossy {
+                // The check uses the value before the cast does.
+                let operand = match operand {
+                    Operand::Copy(place) | Operand::Move(place) => Operand::Copy(place.clone()),
+                    Operand::Constant(_) => operand.clone(),
+                };
+                let source = SourceInstruction::Statement { idx, bb };
+                casts.push(LossyCast { operand, from, to: *to, source });
+            }
+        }
+    }
+    casts
+}
@@ -9,7 +9,7 @@
 use crate::args::ExtraChecks;
 use crate::kani_middle::kani_functions::KaniFunction;
 use crate::kani_middle::{
-    points_to::{MemLoc, PointsToGraph, run_points_to_analysis},
+    points_to::{MemLoc, run_points_to_analysis},
     reachability::CallGraph,
     transform::{
         BodyTransformation, GlobalPass, TransformationResult, body::CheckType,
@@ -20,7 +20,6 @@
 use initial_target_visitor::{AnalysisTarget, InitialTargetVisitor};
 use instrumentation_visitor::InstrumentationVisitor;
 use rustc_middle::ty::TyCtxt;
-use rustc_mir_dataflow::JoinSemiLattice;
 use rustc_public::{
     mir::MirVisitor,
     mir::mono::{Instance, MonoItem},
@@ -61,7 +60,7 @@ fn is_enabled(&self, query_db: &QueryDb) -> bool {
     fn transform(
         &mut self,
         tcx: TyCtxt,
-        call_graph: &CallGraph,
+        _call_graph: &CallGraph,
         starting_items: &[MonoItem],
         instances: Vec<Instance>,
         transformer: &mut BodyTransformation,
@@ -91,24 +90,20 @@ fn transform(
         // Only perform this analysis if there is something to analyze.
         if !targets.is_empty() {
             let mut analysis_targets = HashSet::new();
-            let mut global_points_to_graph = PointsToGraph::empty();
-            // Analyze aliasing for every harness.
-            for entry_item in starting_items {
-                // Convert each entry function into instance, if possible.
-                let entry_fn = match entry_item {
+            // Convert each entry item into instance, if possible.
+            let entry_points: Vec<_> = starting_items
+                .iter()
+                .filter_map(|entry_item| match entry_item {
                     MonoItem::Fn(instance) => Some(*instance),
                     MonoItem::Static(static_def) => {
                         let instance: Instance = (*static_def).into();
                         instance.has_body().then_some(instance)
                     }
                     MonoItem::GlobalAsm(_) => None,
-                };
-                if let Some(instance) = entry_fn {
-                    let body = instance.body().unwrap();
-                    let results = run_points_to_analysis(&body, tcx, instance, call_graph);
-                    global_points_to_graph.join(&results);
-                }
-            }
+                })
+                .collect();
+            // Analyze aliasing for every harness and every function reachable from it.
+            let global_points_to_graph = run_points_to_analysis(&entry_points, tcx);
 
             // Since analysis targets are *pointers*, need to get its successors for instrumentation.
             analysis_targets.extend(global_points_to_graph.successors(&targets));
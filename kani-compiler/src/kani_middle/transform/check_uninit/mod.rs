@@ -20,6 +20,7 @@
 use crate::kani_middle::kani_functions::{KaniFunction, KaniModel};
 pub use delayed_ub::DelayedUbPass;
 pub use ptr_uninit::UninitPass;
+use ty_layout::is_maybe_uninit;
 pub use ty_layout::{PointeeInfo, PointeeLayout};
 
 mod delayed_ub;
@@ -46,7 +47,15 @@ pub trait TargetFinder {
     KaniFunction::Model(KaniModel::IsStrPtrInitialized);
 const KANI_SET_STR_PTR_INITIALIZED: KaniFunction =
     KaniFunction::Model(KaniModel::SetStrPtrInitialized);
+const KANI_SET_PTR_INITIALIZED_PRESERVING: KaniFunction =
+    KaniFunction::Model(KaniModel::SetPtrInitializedPreserving);
+const KANI_SET_SLICE_CHUNK_PTR_INITIALIZED_PRESERVING: KaniFunction =
+    KaniFunction::Model(KaniModel::SetSliceChunkPtrInitializedPreserving);
+const KANI_SET_SLICE_PTR_INITIALIZED_PRESERVING: KaniFunction =
+    KaniFunction::Model(KaniModel::SetSlicePtrInitializedPreserving);
 const KANI_COPY_INIT_STATE: KaniFunction = KaniFunction::Model(KaniModel::CopyInitState);
+const KANI_COPY_INIT_STATE_REALLOC: KaniFunction =
+    KaniFunction::Model(KaniModel::CopyInitStateRealloc);
 const KANI_COPY_INIT_STATE_SINGLE: KaniFunction =
     KaniFunction::Model(KaniModel::CopyInitStateSingle);
 const KANI_LOAD_ARGUMENT: KaniFunction = KaniFunction::Model(KaniModel::LoadArgument);
@@ -62,7 +71,11 @@ pub trait TargetFinder {
     KANI_SET_SLICE_PTR_INITIALIZED,
     KANI_IS_STR_PTR_INITIALIZED,
     KANI_SET_STR_PTR_INITIALIZED,
+    KANI_SET_PTR_INITIALIZED_PRESERVING,
+    KANI_SET_SLICE_CHUNK_PTR_INITIALIZED_PRESERVING,
+    KANI_SET_SLICE_PTR_INITIALIZED_PRESERVING,
     KANI_COPY_INIT_STATE,
+    KANI_COPY_INIT_STATE_REALLOC,
     KANI_COPY_INIT_STATE_SINGLE,
     KANI_LOAD_ARGUMENT,
     KANI_STORE_ARGUMENT,
@@ -182,7 +195,9 @@ fn build_check_for_operation(
             | MemoryInitOp::CreateUnion { .. } => {
                 self.build_set(body, source, operation, pointee_info)
             }
-            MemoryInitOp::Copy { .. } => self.build_copy(body, source, operation, pointee_info),
+            MemoryInitOp::Copy { .. } | MemoryInitOp::Realloc { .. } => {
+                self.build_copy(body, source, operation, pointee_info)
+            }
             MemoryInitOp::AssignUnion { .. } => {
                 self.build_assign_union(body, source, operation, pointee_info)
             }
@@ -289,6 +304,10 @@ fn build_get_and_check(
                 self.inject_unsupported_check(body, source, operation.position(), reason);
                 return;
             }
+            PointeeLayout::Union { .. } if is_maybe_uninit(*pointee_info.ty()) => {
+                // Reading a `MaybeUninit` does not require any of its bytes to be initialized.
+                return;
+            }
             PointeeLayout::Union { .. } => {
                 // Here we are reading from a pointer to a union.
                 // TODO: we perhaps need to check that the union at least contains an intersection
@@ -340,37 +359,49 @@ fn build_set(
         let mut statements = vec![];
         let ptr_operand = operation.mk_operand(body, &mut statements, source);
         let value = operation.expect_value();
+        // Marking memory as initialized keeps the initialization state of `MaybeUninit` fields,
+        // since they may or may not hold a value.
+        let preserved_mask = pointee_info.maybe_uninit_mask().filter(|_| value);
         let terminator = match pointee_info.layout() {
             PointeeLayout::Sized { layout } => {
                 let layout_operand = mk_layout_operand(body, &mut statements, source, layout);
+                let preserved_operand = preserved_mask
+                    .map(|mask| mk_layout_operand(body, &mut statements, source, mask));
                 // Depending on whether writing to the known number of elements in the slice, need to
                 // pass is as an argument.
                 let (diagnostic, args) = match &operation {
                     MemoryInitOp::Set { .. } | MemoryInitOp::SetRef { .. } => {
-                        let diagnostic = KANI_SET_PTR_INITIALIZED;
-                        let args = vec![
-                            ptr_operand,
-                            layout_operand,
-                            Operand::Constant(ConstOperand {
-                                span: source.span(body.blocks()),
-                                user_ty: None,
-                                const_: MirConst::from_bool(value),
-                            }),
-                        ];
+                        let (diagnostic, mut args) = match preserved_operand {
+                            Some(preserved_operand) => (
+                                KANI_SET_PTR_INITIALIZED_PRESERVING,
+                                vec![ptr_operand, layout_operand, preserved_operand],
+                            ),
+                            None => (KANI_SET_PTR_INITIALIZED, vec![ptr_operand, layout_operand]),
+                        };
+                        args.push(Operand::Constant(ConstOperand {
+                            span: source.span(body.blocks()),
+                            user_ty: None,
+                            const_: MirConst::from_bool(value),
+                        }));
                         (diagnostic, args)
                     }
                     MemoryInitOp::SetSliceChunk { .. } => {
-                        let diagnostic = KANI_SET_SLICE_CHUNK_PTR_INITIALIZED;
-                        let args = vec![
-                            ptr_operand,
-                            layout_operand,
-                            operation.expect_count(),
-                            Operand::Constant(ConstOperand {
-                                span: source.span(body.blocks()),
-                                user_ty: None,
-                                const_: MirConst::from_bool(value),
-                            }),
-                        ];
+                        let (diagnostic, mut args) = match preserved_operand {
+                            Some(preserved_operand) => (
+                                KANI_SET_SLICE_CHUNK_PTR_INITIALIZED_PRESERVING,
+                                vec![ptr_operand, layout_operand, preserved_operand],
+                            ),
+                            None => (
+                                KANI_SET_SLICE_CHUNK_PTR_INITIALIZED,
+                                vec![ptr_operand, layout_operand],
+                            ),
+                        };
+                        args.push(operation.expect_count());
+                        args.push(Operand::Constant(ConstOperand {
+                            span: source.span(body.blocks()),
+                            user_ty: None,
+                            const_: MirConst::from_bool(value),
+                        }));
                         (diagnostic, args)
                     }
                     _ => unreachable!(),
@@ -406,13 +437,29 @@ fn build_set(
                     }
                     _ => unreachable!(),
                 };
+                let layout_operand =
+                    mk_layout_operand(body, &mut statements, source, element_layout);
+                let (diagnostic, mut args) = match preserved_mask {
+                    Some(mask) => (
+                        KANI_SET_SLICE_PTR_INITIALIZED_PRESERVING,
+                        vec![
+                            ptr_operand,
+                            layout_operand,
+                            mk_layout_operand(body, &mut statements, source, mask),
+                        ],
+                    ),
+                    None => (diagnostic, vec![ptr_operand, layout_operand]),
+                };
+                args.push(Operand::Constant(ConstOperand {
+                    span: source.span(body.blocks()),
+                    user_ty: None,
+                    const_: MirConst::from_bool(value),
+                }));
                 let set_ptr_initialized_instance = resolve_mem_init_fn(
                     get_mem_init_fn_def(diagnostic, self.mem_init_fn_cache),
                     element_layout.len(),
                     slicee_ty,
                 );
-                let layout_operand =
-                    mk_layout_operand(body, &mut statements, source, element_layout);
                 Terminator {
                     kind: TerminatorKind::Call {
                         func: Operand::Copy(Place::from(body.new_local(
@@ -420,15 +467,7 @@ fn build_set(
                             source.span(body.blocks()),
                             Mutability::Not,
                         ))),
-                        args: vec![
-                            ptr_operand,
-                            layout_operand,
-                            Operand::Constant(ConstOperand {
-                                span: source.span(body.blocks()),
-                                user_ty: None,
-                                const_: MirConst::from_bool(value),
-                            }),
-                        ],
+                        args,
                         destination: ret_place.clone(),
                         target: Some(0), // The current value does not matter, since it will be overwritten in add_bb.
                         unwind: UnwindAction::Terminate,
@@ -449,8 +488,15 @@ fn build_set(
                 // since the union must be already tracked (on creation and update). In the latter
                 // case, we should have been using union assignment instead. Nevertheless, this is
                 // currently mitigated by injecting `assert!(false)`.
-                let union_field = match operation.union_field() {
-                    Some(field) => field,
+                let layout = match operation.union_field() {
+                    Some(field) => &field_layouts[field],
+                    // A `MaybeUninit` is only initialized by writing a value to it, which creates
+                    // or assigns the union, so a new pointer to it does not change its state.
+                    None if is_maybe_uninit(*pointee_info.ty()) && value => return,
+                    // Any layout of the size of the union deinitializes all its bytes.
+                    None if is_maybe_uninit(*pointee_info.ty()) => {
+                        field_layouts.iter().max_by_key(|layout| layout.len()).unwrap()
+                    }
                     None => {
                         let reason =
                             "Interaction between raw pointers and unions is not yet supported.";
@@ -458,7 +504,6 @@ fn build_set(
                         return;
                     }
                 };
-                let layout = &field_layouts[union_field];
                 let layout_operand = mk_layout_operand(body, &mut statements, source, layout);
                 let diagnostic = KANI_SET_PTR_INITIALIZED;
                 let args = vec![
@@ -508,21 +553,25 @@ fn build_copy(
             projection: vec![],
         };
         let layout_size = pointee_info.layout().maybe_size().unwrap();
+        // A `realloc` only preserves the bytes that fit into both allocations, so pass both sizes
+        // and let the model copy the smaller number of elements.
+        let (diagnostic, count_args) = match &operation {
+            MemoryInitOp::Copy { count, .. } => (KANI_COPY_INIT_STATE, vec![count.clone()]),
+            MemoryInitOp::Realloc { old_size, new_size, .. } => {
+                (KANI_COPY_INIT_STATE_REALLOC, vec![old_size.clone(), new_size.clone()])
+            }
+            _ => unreachable!(),
+        };
         let copy_init_state_instance = resolve_mem_init_fn(
-            get_mem_init_fn_def(KANI_COPY_INIT_STATE, self.mem_init_fn_cache),
+            get_mem_init_fn_def(diagnostic, self.mem_init_fn_cache),
             layout_size,
             *pointee_info.ty(),
         );
         let position = operation.position();
         let (from, to) = operation.expect_copy_operands();
-        let count = operation.expect_count();
-        body.insert_call(
-            &copy_init_state_instance,
-            source,
-            position,
-            vec![from, to, count],
-            ret_place.clone(),
-        );
+        let mut args = vec![from, to];
+        args.extend(count_args);
+        body.insert_call(&copy_init_state_instance, source, position, args, ret_place.clone());
     }
 
     /// Instrument the code to pass information about arguments containing unions. Whenever a
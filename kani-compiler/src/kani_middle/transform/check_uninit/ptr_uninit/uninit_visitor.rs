@@ -10,11 +10,12 @@
         check_uninit::{
             PointeeInfo, TargetFinder,
             relevant_instruction::{InitRelevantInstruction, MemoryInitOp},
-            ty_layout::{LayoutComputationError, tys_layout_compatible_to_size},
+            ty_layout::{LayoutComputationError, is_maybe_uninit, tys_layout_compatible_to_size},
         },
     },
 };
 use rustc_public::{
+    CrateDef,
     mir::{
         AggregateKind, CastKind, LocalDecl, MirVisitor, NonDivergingIntrinsic, Operand, Place,
         PointerCoercion, ProjectionElem, Rvalue, Statement, StatementKind, Terminator,
@@ -92,6 +93,54 @@ pub fn new() -> Self {
     fn push_target(&mut self, source_op: MemoryInitOp) {
         self.current_target.push_operation(source_op);
     }
+
+    /// Mark the `size` bytes of the heap allocation returned in `destination` as uninitialized.
+    ///
+    /// This covers the memory behind `Box::new_uninit` and `alloc::alloc`, so reading it before
+    /// it's written, e.g., after `Box::<MaybeUninit<T>>::assume_init`, fails the checks.
+    fn track_heap_allocation(&mut self, destination: &Place, size: &Operand) {
+        self.push_target(MemoryInitOp::SetSliceChunk {
+            operand: Operand::Copy(destination.clone()),
+            count: size.clone(),
+            value: false,
+            position: InsertPosition::After,
+        });
+    }
+
+    /// The initialization state of a union returned by a function is not propagated to the caller,
+    /// so set the state of a `MaybeUninit` returned by one of its constructors.
+    fn track_maybe_uninit_constructor(&mut self, instance: &Instance, destination: &Place) {
+        let destination_ty = destination.ty(&self.locals).unwrap();
+        if !is_maybe_uninit(destination_ty) || instance.def.krate().name != "core" {
+            return;
+        }
+        let TyKind::RigidTy(RigidTy::Adt(adt_def, _)) = destination_ty.kind() else {
+            unreachable!()
+        };
+        let name = instance.def.name();
+        match name.rsplit("::").next().unwrap() {
+            "new" | "zeroed" => {
+                let value_field = adt_def
+                    .variants_iter()
+                    .next()
+                    .unwrap()
+                    .fields()
+                    .iter()
+                    .position(|field| field.name == "value")
+                    .expect("`MaybeUninit` should have a `value` field");
+                self.push_target(MemoryInitOp::CreateUnion {
+                    operand: Operand::Copy(destination.clone()),
+                    field: value_field,
+                });
+            }
+            "uninit" => self.push_target(MemoryInitOp::SetRef {
+                operand: Operand::Copy(destination.clone()),
+                value: false,
+                position: InsertPosition::After,
+            }),
+            _ => {}
+        }
+    }
 }
 
 impl MirVisitor for CheckUninitVisitor {
@@ -165,9 +214,9 @@ fn visit_statement(&mut self, stmt: &Statement, location: Location) {
                     });
                 }
 
-                // TODO: add support for ADTs which could have unions as subfields. Currently,
-                // if a union as a subfield is detected, `assert!(false)` will be injected from
-                // the type layout code.
+                // TODO: add support for ADTs which could have unions other than `MaybeUninit` as
+                // subfields. Currently, if such a union is detected, `assert!(false)` will be
+                // injected from the type layout code.
                 let is_inside_union = {
                     let mut place_to_add_projections =
                         Place { local: place.local, projection: vec![] };
@@ -180,7 +229,7 @@ fn visit_statement(&mut self, stmt: &Statement, location: Location) {
                         }
                         place_to_add_projections.projection.push(projection_elem.clone());
                     }
-                    contains_union
+                    contains_union || place.ty(&self.locals).unwrap().kind().is_union()
                 };
 
                 // Need to copy some information about union initialization, since lvalue is
@@ -190,7 +239,14 @@ fn visit_statement(&mut self, stmt: &Statement, location: Location) {
                         Rvalue::Use(operand) => {
                             // This is a union-to-union assignment, so we need to copy the
                             // initialization state.
-                            if place.ty(&self.locals).unwrap().kind().is_union() {
+                            if place.ty(&self.locals).unwrap().kind().is_union()
+                                && let Operand::Constant(_) = operand
+                            {
+                                self.push_target(MemoryInitOp::Unsupported {
+                                    reason: "Kani does not yet support assigning a constant union"
+                                        .to_string(),
+                                });
+                            } else if place.ty(&self.locals).unwrap().kind().is_union() {
                                 self.push_target(MemoryInitOp::AssignUnion {
                                     lvalue: place.clone(),
                                     rvalue: operand.clone(),
@@ -362,8 +418,20 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
                     InstanceKind::Item => {
                         if instance.is_foreign_item() {
                             match instance.name().as_str() {
-                                "alloc::alloc::__rust_alloc" | "alloc::alloc::__rust_realloc" => {
-                                    /* Memory is uninitialized, nothing to do here. */
+                                "alloc::alloc::__rust_alloc" => {
+                                    /* Memory is uninitialized here, need to update shadow memory. */
+                                    self.track_heap_allocation(destination, &args[0]);
+                                }
+                                "alloc::alloc::__rust_realloc" => {
+                                    /* The new allocation is uninitialized, except for the bytes
+                                    copied over from the old one, which never exceed `new_size`. */
+                                    self.track_heap_allocation(destination, &args[3]);
+                                    self.push_target(MemoryInitOp::Realloc {
+                                        from: args[0].clone(),
+                                        to: Operand::Copy(destination.clone()),
+                                        old_size: args[1].clone(),
+                                        new_size: args[3].clone(),
+                                    });
                                 }
                                 "alloc::alloc::__rust_alloc_zeroed" => {
                                     /* Memory is initialized here, need to update shadow memory. */
@@ -385,7 +453,12 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
                                 }
                                 _ => {}
                             }
+                        } else if instance.name() == "alloc::alloc::exchange_malloc" {
+                            /* Kani replaces the body of this function, which allocates the memory
+                            of a `Box`, so we update shadow memory here. */
+                            self.track_heap_allocation(destination, &args[0]);
                         } else {
+                            self.track_maybe_uninit_constructor(&instance, destination);
                             let union_args: Vec<_> = args
                                 .iter()
                                 .enumerate()
@@ -582,10 +655,15 @@ fn can_skip_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::BlackBox
         | Intrinsic::Breakpoint
         | Intrinsic::Bswap
+        | Intrinsic::CeilF16
         | Intrinsic::CeilF32
         | Intrinsic::CeilF64
+        | Intrinsic::CeilF128
+        | Intrinsic::CopySignF16
         | Intrinsic::CopySignF32
         | Intrinsic::CopySignF64
+        | Intrinsic::CopySignF128
+        | Intrinsic::CosF16
         | Intrinsic::CosF32
         | Intrinsic::CosF64
         | Intrinsic::Ctlz
@@ -595,16 +673,23 @@ fn can_skip_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::CttzNonZero
         | Intrinsic::DiscriminantValue
         | Intrinsic::ExactDiv
+        | Intrinsic::Exp2F16
         | Intrinsic::Exp2F32
         | Intrinsic::Exp2F64
+        | Intrinsic::ExpF16
         | Intrinsic::ExpF32
         | Intrinsic::ExpF64
+        | Intrinsic::FabsF16
         | Intrinsic::FabsF32
         | Intrinsic::FabsF64
+        | Intrinsic::FabsF128
         | Intrinsic::FaddFast
         | Intrinsic::FdivFast
+        | Intrinsic::FloorF16
         | Intrinsic::FloorF32
         | Intrinsic::FloorF64
+        | Intrinsic::FloorF128
+        | Intrinsic::FmafF16
         | Intrinsic::FmafF32
         | Intrinsic::FmafF64
         | Intrinsic::FmulFast
@@ -612,35 +697,50 @@ fn can_skip_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::FsubFast
         | Intrinsic::IsValStaticallyKnown
         | Intrinsic::Likely
+        | Intrinsic::Log10F16
         | Intrinsic::Log10F32
         | Intrinsic::Log10F64
+        | Intrinsic::Log2F16
         | Intrinsic::Log2F32
         | Intrinsic::Log2F64
+        | Intrinsic::LogF16
         | Intrinsic::LogF32
         | Intrinsic::LogF64
+        | Intrinsic::MaxNumF16
         | Intrinsic::MaxNumF32
         | Intrinsic::MaxNumF64
+        | Intrinsic::MaxNumF128
+        | Intrinsic::MinNumF16
         | Intrinsic::MinNumF32
         | Intrinsic::MinNumF64
+        | Intrinsic::MinNumF128
         | Intrinsic::MulWithOverflow
+        | Intrinsic::PowF16
         | Intrinsic::PowF32
         | Intrinsic::PowF64
+        | Intrinsic::PowIF16
         | Intrinsic::PowIF32
         | Intrinsic::PowIF64
         | Intrinsic::RawEq
         | Intrinsic::RotateLeft
         | Intrinsic::RotateRight
+        | Intrinsic::RoundF16
         | Intrinsic::RoundF32
         | Intrinsic::RoundF64
+        | Intrinsic::RoundF128
         | Intrinsic::SaturatingAdd
         | Intrinsic::SaturatingSub
+        | Intrinsic::SinF16
         | Intrinsic::SinF32
         | Intrinsic::SinF64
+        | Intrinsic::SqrtF16
         | Intrinsic::SqrtF32
         | Intrinsic::SqrtF64
         | Intrinsic::SubWithOverflow
+        | Intrinsic::TruncF16
         | Intrinsic::TruncF32
         | Intrinsic::TruncF64
+        | Intrinsic::TruncF128
         | Intrinsic::UncheckedDiv
         | Intrinsic::UncheckedRem
         | Intrinsic::Unlikely
@@ -661,6 +761,8 @@ fn can_skip_intrinsic(intrinsic: Intrinsic) -> bool {
         }
         Intrinsic::SimdAdd
         | Intrinsic::SimdAnd
+        | Intrinsic::SimdBitmask
+        | Intrinsic::SimdCast
         | Intrinsic::SimdDiv
         | Intrinsic::SimdRem
         | Intrinsic::SimdEq
@@ -673,9 +775,11 @@ fn can_skip_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::SimdMul
         | Intrinsic::SimdNe
         | Intrinsic::SimdOr
+        | Intrinsic::SimdSelect
         | Intrinsic::SimdShl
         | Intrinsic::SimdShr
         | Intrinsic::SimdShuffle(_)
+        | Intrinsic::SimdShuffleConstGeneric
         | Intrinsic::SimdSub
         | Intrinsic::SimdXor => {
             /* SIMD operations */
@@ -38,6 +38,9 @@ pub enum MemoryInitOp {
     TriviallyUnsafe { reason: String },
     /// Copy memory initialization state over to another operand.
     Copy { from: Operand, to: Operand, count: Operand },
+    /// Copy memory initialization state of the bytes preserved by `realloc`, i.e., the first
+    /// `min(old_size, new_size)` bytes, from the old allocation over to the new one.
+    Realloc { from: Operand, to: Operand, old_size: Operand, new_size: Operand },
     /// Copy memory initialization state over from one union variable to another.
     AssignUnion { lvalue: Place, rvalue: Operand },
     /// Create a union from scratch with a given field index and store it in the provided operand.
@@ -73,6 +76,7 @@ pub fn mk_operand(
                 mk_ref(operand, body, statements, source)
             }
             MemoryInitOp::Copy { .. }
+            | MemoryInitOp::Realloc { .. }
             | MemoryInitOp::AssignUnion { .. }
             | MemoryInitOp::Unsupported { .. }
             | MemoryInitOp::TriviallyUnsafe { .. } => {
@@ -84,7 +88,9 @@ pub fn mk_operand(
     /// A helper to access operands of copy operation.
     pub fn expect_copy_operands(&self) -> (Operand, Operand) {
         match self {
-            MemoryInitOp::Copy { from, to, .. } => (from.clone(), to.clone()),
+            MemoryInitOp::Copy { from, to, .. } | MemoryInitOp::Realloc { from, to, .. } => {
+                (from.clone(), to.clone())
+            }
             _ => unreachable!(),
         }
     }
@@ -129,7 +135,7 @@ pub fn operand_ty(&self, body: &MutableBody) -> Ty {
             MemoryInitOp::Unsupported { .. } | MemoryInitOp::TriviallyUnsafe { .. } => {
                 unreachable!("operands do not exist for this operation")
             }
-            MemoryInitOp::Copy { from, to, .. } => {
+            MemoryInitOp::Copy { from, to, .. } | MemoryInitOp::Realloc { from, to, .. } => {
                 // It does not matter which operand to return for layout generation, since both of
                 // them have the same pointee type, so we assert that.
                 let from_kind = from.ty(body.locals()).unwrap().kind();
@@ -163,6 +169,7 @@ pub fn expect_count(&self) -> Operand {
             | MemoryInitOp::CheckRef { .. }
             | MemoryInitOp::SetRef { .. }
             | MemoryInitOp::CreateUnion { .. }
+            | MemoryInitOp::Realloc { .. }
             | MemoryInitOp::AssignUnion { .. }
             | MemoryInitOp::Unsupported { .. }
             | MemoryInitOp::TriviallyUnsafe { .. }
@@ -183,6 +190,7 @@ pub fn expect_value(&self) -> bool {
             | MemoryInitOp::Unsupported { .. }
             | MemoryInitOp::TriviallyUnsafe { .. }
             | MemoryInitOp::Copy { .. }
+            | MemoryInitOp::Realloc { .. }
             | MemoryInitOp::AssignUnion { .. }
             | MemoryInitOp::StoreArgument { .. }
             | MemoryInitOp::LoadArgument { .. } => unreachable!(),
@@ -201,6 +209,7 @@ pub fn union_field(&self) -> Option<FieldIdx> {
             | MemoryInitOp::Unsupported { .. }
             | MemoryInitOp::TriviallyUnsafe { .. }
             | MemoryInitOp::Copy { .. }
+            | MemoryInitOp::Realloc { .. }
             | MemoryInitOp::AssignUnion { .. }
             | MemoryInitOp::StoreArgument { .. }
             | MemoryInitOp::LoadArgument { .. } => None,
@@ -220,6 +229,7 @@ pub fn position(&self) -> InsertPosition {
             | MemoryInitOp::StoreArgument { .. }
             | MemoryInitOp::LoadArgument { .. } => InsertPosition::Before,
             MemoryInitOp::Copy { .. }
+            | MemoryInitOp::Realloc { .. }
             | MemoryInitOp::AssignUnion { .. }
             | MemoryInitOp::CreateUnion { .. } => InsertPosition::After,
         }
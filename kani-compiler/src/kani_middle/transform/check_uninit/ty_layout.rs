@@ -5,7 +5,10 @@
 
 use std::fmt::Display;
 
+use rustc_middle::ty::{TyKind as InternalTyKind, tls};
+use rustc_public::rustc_internal;
 use rustc_public::{
+    CrateDef,
     abi::{FieldsShape, Scalar, TagEncoding, ValueAbi, VariantsShape},
     target::{MachineInfo, MachineSize},
     ty::{AdtKind, RigidTy, Ty, TyKind, UintTy, VariantIdx},
@@ -68,6 +71,9 @@ pub fn maybe_size(&self) -> Option<usize> {
 pub struct PointeeInfo {
     pointee_ty: Ty,
     layout: PointeeLayout,
+    /// Byte mask of the bytes (of the element, for slices) that belong to a `MaybeUninit` field.
+    /// `None` if there are no such bytes.
+    maybe_uninit_mask: Option<Layout>,
 }
 
 /// Different layout computation errors that could arise from the currently unsupported constructs.
@@ -109,6 +115,11 @@ pub fn from_ty(ty: Ty) -> Result<Self, LayoutComputationError> {
             TyKind::RigidTy(rigid_ty) => match rigid_ty {
                 RigidTy::Adt(adt_def, args) if adt_def.kind() == AdtKind::Union => {
                     assert!(adt_def.variants().len() == 1);
+                    // `MaybeUninit::uninit()` creates the union from its zero-sized field, which
+                    // leaves the whole union uninitialized. Extend the layout of the fields to the
+                    // size of the union so that creating it overwrites the state of all its bytes.
+                    let union_size =
+                        is_maybe_uninit(ty).then(|| ty.layout().unwrap().shape().size.bytes());
                     let fields: Result<_, _> = adt_def
                         .variant(VariantIdx::to_val(0))
                         .unwrap()
@@ -116,7 +127,8 @@ pub fn from_ty(ty: Ty) -> Result<Self, LayoutComputationError> {
                         .into_iter()
                         .map(|field_def| {
                             let ty = field_def.ty_with_args(&args);
-                            let size_in_bytes = ty.layout().unwrap().shape().size.bytes();
+                            let size_in_bytes = union_size
+                                .unwrap_or_else(|| ty.layout().unwrap().shape().size.bytes());
                             data_bytes_for_ty(&MachineInfo::target(), ty, 0)
                                 .map(|data_chunks| generate_byte_mask(size_in_bytes, data_chunks))
                         })
@@ -124,6 +136,7 @@ pub fn from_ty(ty: Ty) -> Result<Self, LayoutComputationError> {
                     Ok(PointeeInfo {
                         pointee_ty: ty,
                         layout: PointeeLayout::Union { field_layouts: fields? },
+                        maybe_uninit_mask: None,
                     })
                 }
                 RigidTy::Str => {
@@ -133,7 +146,7 @@ pub fn from_ty(ty: Ty) -> Result<Self, LayoutComputationError> {
                     let layout = PointeeLayout::Slice {
                         element_layout: generate_byte_mask(size_in_bytes, data_chunks),
                     };
-                    Ok(PointeeInfo { pointee_ty: ty, layout })
+                    Ok(PointeeInfo { pointee_ty: ty, layout, maybe_uninit_mask: None })
                 }
                 RigidTy::Slice(slicee_ty) => {
                     let size_in_bytes = slicee_ty.layout().unwrap().shape().size.bytes();
@@ -141,11 +154,17 @@ pub fn from_ty(ty: Ty) -> Result<Self, LayoutComputationError> {
                     let layout = PointeeLayout::Slice {
                         element_layout: generate_byte_mask(size_in_bytes, data_chunks),
                     };
-                    Ok(PointeeInfo { pointee_ty: ty, layout })
-                }
-                RigidTy::Dynamic(..) => {
-                    Ok(PointeeInfo { pointee_ty: ty, layout: PointeeLayout::TraitObject })
+                    Ok(PointeeInfo {
+                        pointee_ty: ty,
+                        layout,
+                        maybe_uninit_mask: maybe_uninit_mask(slicee_ty),
+                    })
                 }
+                RigidTy::Dynamic(..) => Ok(PointeeInfo {
+                    pointee_ty: ty,
+                    layout: PointeeLayout::TraitObject,
+                    maybe_uninit_mask: None,
+                }),
                 _ => {
                     if ty.layout().unwrap().shape().is_sized() {
                         let size_in_bytes = ty.layout().unwrap().shape().size.bytes();
@@ -153,7 +172,11 @@ pub fn from_ty(ty: Ty) -> Result<Self, LayoutComputationError> {
                         let layout = PointeeLayout::Sized {
                             layout: generate_byte_mask(size_in_bytes, data_chunks),
                         };
-                        Ok(PointeeInfo { pointee_ty: ty, layout })
+                        Ok(PointeeInfo {
+                            pointee_ty: ty,
+                            layout,
+                            maybe_uninit_mask: maybe_uninit_mask(ty),
+                        })
                     } else {
                         Err(LayoutComputationError::UnknownUnsizedLayout(ty))
                     }
@@ -172,6 +195,75 @@ pub fn ty(&self) -> &Ty {
     pub fn layout(&self) -> &PointeeLayout {
         &self.layout
     }
+
+    /// Byte mask of the bytes that belong to a `MaybeUninit` field of the pointee. Marking the
+    /// pointee as initialized should preserve the initialization state of these bytes.
+    pub fn maybe_uninit_mask(&self) -> Option<&Layout> {
+        self.maybe_uninit_mask.as_ref()
+    }
+}
+
+/// Returns true if `ty` is `core::mem::MaybeUninit<T>`.
+pub fn is_maybe_uninit(ty: Ty) -> bool {
+    match ty.kind() {
+        TyKind::RigidTy(RigidTy::Adt(def, _)) => {
+            def.kind() == AdtKind::Union
+                && def.krate().name == "core"
+                && def.trimmed_name() == "MaybeUninit"
+        }
+        _ => false,
+    }
+}
+
+/// Create a byte mask of the bytes of `ty` that belong to a `MaybeUninit`, if there are any.
+fn maybe_uninit_mask(ty: Ty) -> Option<Layout> {
+    let chunks = maybe_uninit_bytes_for_ty(ty, 0);
+    (!chunks.is_empty())
+        .then(|| generate_byte_mask(ty.layout().unwrap().shape().size.bytes(), chunks))
+}
+
+/// Retrieve the chunks of bytes of a type that belong to a `MaybeUninit`.
+///
+/// Only arrays, tuples and structs are traversed. The bytes of a `MaybeUninit` inside of an enum are
+/// treated as padding.
+fn maybe_uninit_bytes_for_ty(ty: Ty, current_offset: usize) -> Vec<DataBytes> {
+    let layout = ty.layout().unwrap().shape();
+    if is_maybe_uninit(ty) {
+        return vec![DataBytes { offset: current_offset, size: layout.size }];
+    }
+    let fields_bytes = |field_tys: Vec<Ty>, offsets: &[MachineSize]| -> Vec<DataBytes> {
+        field_tys
+            .into_iter()
+            .zip(offsets)
+            .flat_map(|(field_ty, offset)| {
+                maybe_uninit_bytes_for_ty(field_ty, current_offset + offset.bytes())
+            })
+            .collect()
+    };
+    match (ty.kind(), &layout.fields) {
+        (TyKind::RigidTy(RigidTy::Array(elem_ty, _)), FieldsShape::Array { stride, count }) => {
+            let elem_bytes = maybe_uninit_bytes_for_ty(elem_ty, 0);
+            (0..*count as usize)
+                .flat_map(|idx| {
+                    let elem_offset = current_offset + idx * stride.bytes();
+                    elem_bytes.iter().map(move |chunk| DataBytes {
+                        offset: elem_offset + chunk.offset,
+                        size: chunk.size,
+                    })
+                })
+                .collect()
+        }
+        (TyKind::RigidTy(RigidTy::Tuple(tys)), FieldsShape::Arbitrary { offsets }) => {
+            fields_bytes(tys, offsets)
+        }
+        (TyKind::RigidTy(RigidTy::Adt(def, args)), FieldsShape::Arbitrary { offsets })
+            if def.kind() == AdtKind::Struct =>
+        {
+            let fields = def.variants_iter().next().unwrap().fields();
+            fields_bytes(fields.iter().map(|field| field.ty_with_args(&args)).collect(), offsets)
+        }
+        _ => vec![],
+    }
 }
 
 /// Retrieve a set of data bytes with offsets for a type.
@@ -391,21 +483,82 @@ fn data_bytes_for_ty(
                     }
                     _ => unreachable!("RigidTy::RawPtr | RigidTy::Ref with a non-scalar ABI."),
                 }),
+                RigidTy::Closure(..) | RigidTy::CoroutineClosure(..) => {
+                    // A closure is laid out like a struct whose fields are its captured variables.
+                    let upvars = upvar_tys(ty)
+                        .filter(|upvars| upvars.len() == offsets.len())
+                        .ok_or(LayoutComputationError::UnsupportedType(ty))?;
+                    let mut closure_data_bytes = vec![];
+                    for idx in layout.fields.fields_by_offset_order() {
+                        closure_data_bytes.append(&mut data_bytes_for_ty(
+                            machine_info,
+                            upvars[idx],
+                            offsets[idx].bytes() + current_offset,
+                        )?);
+                    }
+                    Ok(closure_data_bytes)
+                }
+                RigidTy::Coroutine(..) => {
+                    // The prefix of a coroutine holds its captured variables followed by the tag
+                    // of its state, while the locals that it saves across suspension points only
+                    // hold a value in some of its states. Like for the bytes of a `MaybeUninit`,
+                    // we only require the captured variables and the state to be initialized.
+                    let VariantsShape::Multiple {
+                        tag: Scalar::Initialized { value: tag_value, .. },
+                        tag_field,
+                        ..
+                    } = layout.variants
+                    else {
+                        return Err(LayoutComputationError::UnsupportedType(ty));
+                    };
+                    let upvars = upvar_tys(ty)
+                        .filter(|upvars| upvars.len() == tag_field)
+                        .ok_or(LayoutComputationError::UnsupportedType(ty))?;
+                    let mut coroutine_data_bytes = vec![];
+                    for (idx, upvar_ty) in upvars.into_iter().enumerate() {
+                        coroutine_data_bytes.append(&mut data_bytes_for_ty(
+                            machine_info,
+                            upvar_ty,
+                            offsets[idx].bytes() + current_offset,
+                        )?);
+                    }
+                    coroutine_data_bytes.push(DataBytes {
+                        offset: offsets[tag_field].bytes() + current_offset,
+                        size: tag_value.size(machine_info),
+                    });
+                    Ok(coroutine_data_bytes)
+                }
                 RigidTy::FnDef(_, _)
                 | RigidTy::FnPtr(_)
-                | RigidTy::Closure(_, _)
-                | RigidTy::Coroutine(_, _)
-                | RigidTy::CoroutineClosure(_, _)
                 | RigidTy::CoroutineWitness(_, _)
                 | RigidTy::Foreign(_)
                 | RigidTy::Dynamic(_, _, _) => Err(LayoutComputationError::UnsupportedType(ty)),
             }
         }
+        // The bytes of a `MaybeUninit` do not need to be initialized.
+        FieldsShape::Union(_) if is_maybe_uninit(ty) => Ok(vec![]),
         FieldsShape::Union(_) => Err(LayoutComputationError::UnionAsField(ty)),
         FieldsShape::Array { .. } => Ok(vec![]),
     }
 }
 
+/// The types of the variables captured by a closure, coroutine or coroutine closure, in the order
+/// of the fields of its layout.
+///
+/// The stable MIR API doesn't tell which generic argument of these types holds the captured
+/// variables, so we ask the compiler.
+fn upvar_tys(ty: Ty) -> Option<Vec<Ty>> {
+    tls::with(|tcx| {
+        let upvars = match rustc_internal::internal(tcx, ty).kind() {
+            InternalTyKind::Closure(_, args) => args.as_closure().upvar_tys(),
+            InternalTyKind::Coroutine(_, args) => args.as_coroutine().upvar_tys(),
+            InternalTyKind::CoroutineClosure(_, args) => args.as_coroutine_closure().upvar_tys(),
+            _ => return None,
+        };
+        Some(upvars.iter().map(rustc_internal::stable).collect())
+    })
+}
+
 /// Returns true if `to_ty` has a smaller or equal size and padding bytes in `from_ty` are padding
 /// bytes in `to_ty`.
 pub fn tys_layout_compatible_to_size(from_ty: &Ty, to_ty: &Ty) -> bool {
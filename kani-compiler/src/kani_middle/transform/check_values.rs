@@ -14,6 +14,7 @@
 //!   1. We could merge the invalid values by the offset.
 //!   2. We could avoid checking places that have been checked before.
 use crate::args::ExtraChecks;
+use crate::kani_middle::kani_functions::{KaniFunction, KaniModel};
 use crate::kani_middle::transform::body::{
     CheckType, InsertPosition, MutableBody, SourceInstruction,
 };
@@ -21,18 +22,23 @@
 use crate::kani_queries::QueryDb;
 use rustc_middle::ty::{Const, TyCtxt};
 use rustc_public::CrateDef;
-use rustc_public::abi::{FieldsShape, Scalar, TagEncoding, ValueAbi, VariantsShape, WrappingRange};
+use rustc_public::abi::{
+    FieldsShape, LayoutShape, Scalar, TagEncoding, ValueAbi, VariantsShape, WrappingRange,
+};
 use rustc_public::mir::mono::Instance;
 use rustc_public::mir::visit::{Location, PlaceContext, PlaceRef};
 use rustc_public::mir::{
     AggregateKind, BasicBlockIdx, BinOp, Body, CastKind, FieldIdx, Local, LocalDecl, MirVisitor,
     Mutability, NonDivergingIntrinsic, Operand, Place, ProjectionElem, RawPtrKind, Rvalue,
-    Statement, StatementKind, Terminator, TerminatorKind,
+    Statement, StatementKind, Terminator, TerminatorKind, UnOp,
 };
 use rustc_public::rustc_internal;
 use rustc_public::target::{MachineInfo, MachineSize};
-use rustc_public::ty::{AdtKind, RigidTy, Span, Ty, TyKind, UintTy};
+use rustc_public::ty::{
+    AdtKind, FnDef, GenericArgKind, GenericArgs, RigidTy, Span, Ty, TyKind, UintTy,
+};
 use rustc_public_bridge::IndexedVal;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use strum_macros::AsRefStr;
 use tracing::{debug, trace};
@@ -42,6 +48,19 @@
 pub struct ValidValuePass {
     pub safety_check_type: CheckType,
     pub unsupported_check_type: CheckType,
+    /// The model that checks every value copied by a memory copy.
+    has_valid_values: FnDef,
+}
+
+impl ValidValuePass {
+    pub fn new(
+        safety_check_type: CheckType,
+        unsupported_check_type: CheckType,
+        kani_functions: &HashMap<KaniFunction, FnDef>,
+    ) -> Self {
+        let has_valid_values = kani_functions[&KaniFunction::Model(KaniModel::HasValidValues)];
+        ValidValuePass { safety_check_type, unsupported_check_type, has_valid_values }
+    }
 }
 
 impl TransformPass for ValidValuePass {
@@ -115,6 +134,63 @@ fn build_check(&self, body: &mut MutableBody, instruction: UnsafeInstruction) {
                         );
                     }
                 }
+                SourceOp::WriteBytesValidity { target_ty, rvalue, count, ranges } => {
+                    let span = source.span(body.blocks());
+                    let value = body.insert_assignment(rvalue, &mut source, InsertPosition::Before);
+                    let rvalue_ptr = Rvalue::AddressOf(RawPtrKind::Const, Place::from(value));
+                    // Nothing is written if `count` is zero.
+                    let zero = body.new_uint_operand(0, UintTy::Usize, span);
+                    let no_write = body.insert_binary_op(
+                        BinOp::Eq,
+                        count,
+                        zero,
+                        &mut source,
+                        InsertPosition::Before,
+                    );
+                    for range in ranges {
+                        let valid = build_limits(body, &range, rvalue_ptr.clone(), &mut source);
+                        let result = body.insert_binary_op(
+                            BinOp::BitOr,
+                            Operand::Copy(Place::from(no_write)),
+                            move_local(valid),
+                            &mut source,
+                            InsertPosition::Before,
+                        );
+                        let msg =
+                            format!("Undefined Behavior: Invalid value of type `{target_ty}`",);
+                        body.insert_check(
+                            &self.safety_check_type,
+                            &mut source,
+                            InsertPosition::Before,
+                            Some(result),
+                            &msg,
+                        );
+                    }
+                }
+                SourceOp::CopyValidity { pointee_ty, ptr, count } => {
+                    let span = source.span(body.blocks());
+                    let has_valid_values = Instance::resolve(
+                        self.has_valid_values,
+                        &GenericArgs(vec![GenericArgKind::Type(pointee_ty)]),
+                    )
+                    .unwrap();
+                    let result = body.new_local(Ty::bool_ty(), span, Mutability::Not);
+                    body.insert_call(
+                        &has_valid_values,
+                        &mut source,
+                        InsertPosition::Before,
+                        vec![ptr, count],
+                        Place::from(result),
+                    );
+                    let msg = format!("Undefined Behavior: Invalid value of type `{pointee_ty}`",);
+                    body.insert_check(
+                        &self.safety_check_type,
+                        &mut source,
+                        InsertPosition::Before,
+                        Some(result),
+                        &msg,
+                    );
+                }
                 SourceOp::UnsupportedCheck { check, ty } => {
                     let reason = format!(
                         "Kani currently doesn't support checking validity of `{check}` for `{ty}`",
@@ -145,6 +221,14 @@ fn move_local(local: Local) -> Operand {
     Operand::Move(Place::from(local))
 }
 
+/// Copy the value of `operand`, so the instruction that uses it can still move it.
+fn copy_operand(operand: &Operand) -> Operand {
+    match operand {
+        Operand::Move(place) => Operand::Copy(place.clone()),
+        Operand::Copy(_) | Operand::Constant(_) => operand.clone(),
+    }
+}
+
 fn uint_ty(bytes: usize) -> UintTy {
     match bytes {
         1 => UintTy::U8,
@@ -165,6 +249,20 @@ pub struct ValidValueReq {
     size: MachineSize,
     /// The range restriction is represented by a Scalar.
     valid_range: ValidityRange,
+    /// The requirement only applies if all conditions hold. This is the case for the fields of the
+    /// untagged variant of an enum with a niche encoding, which are only valid values if the enum
+    /// holds that variant.
+    conditions: Vec<ValidityCondition>,
+}
+
+/// A condition that holds if the value stored in the given offset is in `range`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct ValidityCondition {
+    /// Offset in bytes.
+    offset: usize,
+    /// Size of the value.
+    size: MachineSize,
+    range: WrappingRange,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -199,6 +297,7 @@ pub fn try_from_ty(machine_info: &MachineInfo, ty: Ty) -> Option<ValidValueReq>
                     WrappingRange { start: 0, end: 0xD7FF },
                     WrappingRange { start: 0xE000, end: char::MAX.into() },
                 ]),
+                conditions: vec![],
             })
         } else {
             let shape = ty.layout().unwrap().shape();
@@ -209,6 +308,7 @@ pub fn try_from_ty(machine_info: &MachineInfo, ty: Ty) -> Option<ValidValueReq>
                         offset: 0,
                         size: value.size(machine_info),
                         valid_range: ValidityRange::Single(valid_range),
+                        conditions: vec![],
                     })
                 }
                 ValueAbi::Scalar(_)
@@ -228,6 +328,33 @@ pub fn is_full(&self) -> bool {
         }
     }
 
+    /// Check if the value that `bytes` stores at the offset of this requirement is valid.
+    ///
+    /// `bytes` is the little-endian representation of the whole value that the requirement was
+    /// computed for. The requirement is trivially satisfied if one of its conditions doesn't hold.
+    pub fn is_satisfied_by(&self, bytes: &[u8]) -> bool {
+        let read = |offset: usize, size: MachineSize| {
+            bytes[offset..offset + size.bytes()]
+                .iter()
+                .rev()
+                .fold(0u128, |value, byte| value << 8 | *byte as u128)
+        };
+        if !self
+            .conditions
+            .iter()
+            .all(|cond| range_includes(&cond.range, read(cond.offset, cond.size)))
+        {
+            return true;
+        }
+        let value = read(self.offset, self.size);
+        match &self.valid_range {
+            ValidityRange::Single(range) => range_includes(range, value),
+            ValidityRange::Multiple(ranges) => {
+                ranges.iter().any(|range| range_includes(range, value))
+            }
+        }
+    }
+
     /// Check if this range contains `other` range.
     ///
     /// I.e., `scalar_2` ⊆ `scalar_1`
@@ -261,6 +388,15 @@ pub fn contains(&self, other: &ValidValueReq) -> bool {
     }
 }
 
+/// Check if `value` is in `range`.
+fn range_includes(range: &WrappingRange, value: u128) -> bool {
+    if range.wraps_around() {
+        value >= range.start || value <= range.end
+    } else {
+        range.start <= value && value <= range.end
+    }
+}
+
 /// Check if range `r1` contains range `r2`.
 ///
 /// I.e., `r2` ⊆ `r1`
@@ -293,6 +429,17 @@ enum SourceOp {
     ///  - Raw pointer dereference
     DerefValidity { pointee_ty: Ty, rvalue: Rvalue, ranges: Vec<ValidValueReq> },
 
+    /// Similar to BytesValidity, but for the `count` values written by `write_bytes`, which all
+    /// have the value of `rvalue`.
+    WriteBytesValidity { target_ty: Ty, rvalue: Rvalue, count: Operand, ranges: Vec<ValidValueReq> },
+
+    /// Similar to DerefValidity, but for the `count` values that a memory copy reads from `ptr`.
+    ///
+    /// This can happen for:
+    ///  - `copy_nonoverlapping`
+    ///  - `copy`
+    CopyValidity { pointee_ty: Ty, ptr: Operand, count: Operand },
+
     /// Represents a range check Kani currently does not support.
     ///
     /// This will translate into an assertion failure with an unsupported message.
@@ -374,6 +521,27 @@ fn push_target(&mut self, op: SourceOp) {
             .get_or_insert_with(|| UnsafeInstruction { source: self.current, operations: vec![] });
         target.operations.push(op);
     }
+
+    /// A memory copy from `src` (a `*const T`) is typed, so the `count` values that it copies must
+    /// be valid values of `T`.
+    fn push_copy_target(&mut self, check: &str, src: &Operand, count: &Operand) {
+        let TyKind::RigidTy(RigidTy::RawPtr(pointee_ty, _)) = src.ty(self.locals).unwrap().kind()
+        else {
+            unreachable!()
+        };
+        match ty_validity_per_offset(&self.machine, pointee_ty, 0) {
+            Ok(ranges) if ranges.is_empty() => {}
+            Ok(_) => self.push_target(SourceOp::CopyValidity {
+                pointee_ty,
+                ptr: copy_operand(src),
+                count: copy_operand(count),
+            }),
+            Err(_msg) => self.push_target(SourceOp::UnsupportedCheck {
+                check: check.to_string(),
+                ty: pointee_ty,
+            }),
+        }
+    }
 }
 
 impl MirVisitor for CheckValueVisitor<'_, '_> {
@@ -384,12 +552,8 @@ fn visit_statement(&mut self, stmt: &Statement, location: Location) {
             // Leave it as an exhaustive match to be notified when a new kind is added.
             match &stmt.kind {
                 StatementKind::Intrinsic(NonDivergingIntrinsic::CopyNonOverlapping(copy)) => {
-                    // Source is a *const T and it must be safe for read.
-                    // TODO: Implement value check.
-                    self.push_target(SourceOp::UnsupportedCheck {
-                        check: "copy_nonoverlapping".to_string(),
-                        ty: copy.src.ty(self.locals).unwrap(),
-                    });
+                    self.super_statement(stmt, location);
+                    self.push_copy_target("copy_nonoverlapping", &copy.src, &copy.count);
                 }
                 StatementKind::Assign(place, rvalue) => {
                     // First check rvalue.
@@ -442,7 +606,8 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
                             // pub unsafe fn write_bytes<T>(dst: *mut T, val: u8, count: usize)
                             // <https://doc.rust-lang.org/stable/core/intrinsics/fn.write_bytes.html>
                             // This is an over-approximation since writing an invalid value is
-                            // not UB, only reading it will be.
+                            // not UB, only reading it will be. Nothing is written if `count` is
+                            // zero, so the check only applies to non-zero counts.
                             assert_eq!(
                                 args.len(),
                                 3,
@@ -461,9 +626,10 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
                                         self.tcx,
                                         target_ty.layout().unwrap().shape().size.bytes() as u64,
                                     ));
-                                    self.push_target(SourceOp::BytesValidity {
+                                    self.push_target(SourceOp::WriteBytesValidity {
                                         target_ty,
-                                        rvalue: Rvalue::Repeat(args[1].clone(), sz),
+                                        rvalue: Rvalue::Repeat(copy_operand(&args[1]), sz),
+                                        count: copy_operand(&args[2]),
                                         ranges,
                                     })
                                 }
@@ -473,6 +639,11 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
                                 }),
                             }
                         }
+                        Some("copy") => {
+                            // pub unsafe fn copy<T>(src: *const T, dst: *mut T, count: usize)
+                            assert_eq!(args.len(), 3, "Unexpected number of arguments for `copy`");
+                            self.push_copy_target("copy", &args[0], &args[2]);
+                        }
                         Some("transmute") | Some("transmute_copy") => {
                             unreachable!("Should've been lowered")
                         }
@@ -802,6 +973,7 @@ fn intrinsic_name(locals: &[LocalDecl], func: &Operand) -> Option<String> {
 ///     let value = unsafe { *typed_ptr };
 ///     req.valid_range.contains(value)
 /// ```
+/// If the requirement has conditions, the result also holds if any condition doesn't.
 pub fn build_limits(
     body: &mut MutableBody,
     req: &ValidValueReq,
@@ -811,7 +983,58 @@ pub fn build_limits(
     let span = source.span(body.blocks());
     debug!(?req, ?rvalue_ptr, ?span, "build_limits");
     let primitive_ty = uint_ty(req.size.bytes());
-    let orig_ptr = if req.offset != 0 {
+    let value = build_value(body, req.offset, primitive_ty, rvalue_ptr.clone(), source);
+    let valid = match &req.valid_range {
+        ValidityRange::Single(range) => {
+            build_single_limit(body, range, source, span, primitive_ty, value)
+        }
+        ValidityRange::Multiple([range1, range2]) => {
+            // Build `let valid = range1.contains(value) || range2.contains(value);
+            let cond1 = build_single_limit(body, range1, source, span, primitive_ty, value.clone());
+            let cond2 = build_single_limit(body, range2, source, span, primitive_ty, value);
+            body.insert_binary_op(
+                BinOp::BitOr,
+                move_local(cond1),
+                move_local(cond2),
+                source,
+                InsertPosition::Before,
+            )
+        }
+    };
+    // Build `let valid = !condition || valid;` for every condition.
+    req.conditions.iter().fold(valid, |valid, condition| {
+        let primitive_ty = uint_ty(condition.size.bytes());
+        let value = build_value(body, condition.offset, primitive_ty, rvalue_ptr.clone(), source);
+        let holds = build_single_limit(body, &condition.range, source, span, primitive_ty, value);
+        let not_holds = body.insert_assignment(
+            Rvalue::UnaryOp(UnOp::Not, move_local(holds)),
+            source,
+            InsertPosition::Before,
+        );
+        body.insert_binary_op(
+            BinOp::BitOr,
+            move_local(not_holds),
+            move_local(valid),
+            source,
+            InsertPosition::Before,
+        )
+    })
+}
+
+/// Instrument MIR to read the value of type `primitive_ty` stored at `offset` bytes from
+/// `rvalue_ptr`.
+///
+/// Note that the value is read even if it is only used by a requirement whose conditions don't
+/// hold, in which case it may not be initialized.
+fn build_value(
+    body: &mut MutableBody,
+    offset: usize,
+    primitive_ty: UintTy,
+    rvalue_ptr: Rvalue,
+    source: &mut SourceInstruction,
+) -> Operand {
+    let span = source.span(body.blocks());
+    let orig_ptr = if offset != 0 {
         let start_ptr =
             move_local(body.insert_assignment(rvalue_ptr, source, InsertPosition::Before));
         let byte_ptr = move_local(body.insert_ptr_cast(
@@ -821,7 +1044,7 @@ pub fn build_limits(
             source,
             InsertPosition::Before,
         ));
-        let offset_const = body.new_uint_operand(req.offset as _, UintTy::Usize, span);
+        let offset_const = body.new_uint_operand(offset as _, UintTy::Usize, span);
         let offset = move_local(body.insert_assignment(
             Rvalue::Use(offset_const),
             source,
@@ -844,24 +1067,7 @@ pub fn build_limits(
         source,
         InsertPosition::Before,
     );
-    let value = Operand::Copy(Place { local: value_ptr, projection: vec![ProjectionElem::Deref] });
-    match &req.valid_range {
-        ValidityRange::Single(range) => {
-            build_single_limit(body, range, source, span, primitive_ty, value)
-        }
-        ValidityRange::Multiple([range1, range2]) => {
-            // Build `let valid = range1.contains(value) || range2.contains(value);
-            let cond1 = build_single_limit(body, range1, source, span, primitive_ty, value.clone());
-            let cond2 = build_single_limit(body, range2, source, span, primitive_ty, value);
-            body.insert_binary_op(
-                BinOp::BitOr,
-                move_local(cond1),
-                move_local(cond2),
-                source,
-                InsertPosition::Before,
-            )
-        }
-    }
+    Operand::Copy(Place { local: value_ptr, projection: vec![ProjectionElem::Deref] })
 }
 
 fn build_single_limit(
@@ -938,6 +1144,9 @@ pub fn ty_validity_per_offset(
                         .cloned()
                         .map(|mut req| {
                             req.offset += elem_offset;
+                            for condition in &mut req.conditions {
+                                condition.offset += elem_offset;
+                            }
                             req
                         })
                         .collect::<Vec<_>>();
@@ -973,9 +1182,7 @@ pub fn ty_validity_per_offset(
                                 VariantsShape::Multiple {
                                     tag_encoding: TagEncoding::Niche { .. },
                                     ..
-                                } => {
-                                    Err(format!("Unsupported Enum `{}` check", def.trimmed_name()))?
-                                }
+                                } => niche_enum_validity(machine_info, ty, &layout, current_offset),
                                 VariantsShape::Multiple { variants, .. } => {
                                     let enum_validity = ty_req();
                                     let mut fields_validity = vec![];
@@ -1070,3 +1277,111 @@ pub fn ty_validity_per_offset(
         }
     }
 }
+
+/// Compute the validity requirements of an enum with a niche encoding.
+///
+/// The tag of such an enum is stored in a field of its untagged variant, whose valid range is
+/// extended with one niche value per niche variant. Thus, the tag must be in the extended range,
+/// and the requirements of the fields of a variant only apply if the tag encodes that variant.
+fn niche_enum_validity(
+    machine_info: &MachineInfo,
+    ty: Ty,
+    layout: &LayoutShape,
+    current_offset: usize,
+) -> Result<Vec<ValidValueReq>, String> {
+    let TyKind::RigidTy(RigidTy::Adt(def, args)) = ty.kind() else { unreachable!() };
+    let FieldsShape::Arbitrary { offsets } = &layout.fields else { unreachable!() };
+    let VariantsShape::Multiple {
+        tag,
+        tag_encoding: TagEncoding::Niche { untagged_variant, niche_variants, niche_start },
+        variants,
+        ..
+    } = &layout.variants
+    else {
+        unreachable!()
+    };
+    let Scalar::Initialized { value, valid_range } = tag else {
+        return Err(format!("Unsupported Enum `{}` check", def.trimmed_name()));
+    };
+    // For enums, tag is the only field.
+    assert_eq!(offsets.len(), 1);
+    let tag_offset = current_offset + offsets[0].bytes();
+    let tag_size = value.size(machine_info);
+    let tag_req = ValidValueReq {
+        offset: tag_offset,
+        size: tag_size,
+        valid_range: ValidityRange::Single(*valid_range),
+        conditions: vec![],
+    };
+
+    // The niche values `niche_start..=niche_end` may wrap around.
+    let max = u128::MAX >> (128 - tag_size.bits());
+    let first_niche = niche_variants.start().to_index();
+    let last_niche = niche_variants.end().to_index();
+    let niche_value =
+        |variant: usize| niche_start.wrapping_add((variant - first_niche) as u128) & max;
+    let niche_end = niche_value(last_niche);
+    // The untagged variant is encoded by any value that is not a niche value.
+    let untagged_range = WrappingRange {
+        start: niche_end.wrapping_add(1) & max,
+        end: niche_start.wrapping_sub(1) & max,
+    };
+
+    let mut validity = if tag_req.is_full() { vec![] } else { vec![tag_req] };
+    // The niche variants may include the untagged variant, e.g., `B` in `enum E { A, B(bool), C }`.
+    // Its niche value is reserved but it doesn't encode any variant, so it's invalid.
+    let untagged = untagged_variant.to_index();
+    if (first_niche..=last_niche).contains(&untagged) {
+        let value = niche_value(untagged);
+        validity.push(ValidValueReq {
+            offset: tag_offset,
+            size: tag_size,
+            valid_range: ValidityRange::Single(WrappingRange {
+                start: value.wrapping_add(1) & max,
+                end: value.wrapping_sub(1) & max,
+            }),
+            conditions: vec![],
+        });
+    }
+    let ty_variants = def.variants();
+    for (index, variant) in variants.iter().enumerate() {
+        let fields = ty_variants[index].fields();
+        let FieldsShape::Arbitrary { offsets: field_offsets } = &variant.fields else {
+            unreachable!()
+        };
+        let mut variant_validity = vec![];
+        for field_idx in variant.fields.fields_by_offset_order() {
+            let field_offset = field_offsets[field_idx].bytes();
+            let field_ty = fields[field_idx].ty_with_args(&args);
+            variant_validity.append(&mut ty_validity_per_offset(
+                machine_info,
+                field_ty,
+                field_offset + current_offset,
+            )?);
+        }
+        let range = if index == untagged {
+            // The requirement of the field that holds the tag is implied by the tag requirement,
+            // unless it has more than one range, e.g., for `char`.
+            variant_validity.retain(|req| {
+                !(req.offset == tag_offset
+                    && req.size == tag_size
+                    && req.conditions.is_empty()
+                    && matches!(req.valid_range, ValidityRange::Single(_)))
+            });
+            untagged_range
+        } else if (first_niche..=last_niche).contains(&index) {
+            let value = niche_value(index);
+            WrappingRange { start: value, end: value }
+        } else if variant_validity.is_empty() {
+            continue;
+        } else {
+            return Err(format!("Unsupported Enum `{}` check", def.trimmed_name()));
+        };
+        let condition = ValidityCondition { offset: tag_offset, size: tag_size, range };
+        for mut req in variant_validity {
+            req.conditions.push(condition.clone());
+            validity.push(req);
+        }
+    }
+    Ok(validity)
+}
@@ -0,0 +1,275 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module replaces `HashMap`, `HashSet`, `VecDeque`, `BTreeMap` and their iterators from
+//! `std::collections` by the models in `kani::models` when `--model-std-collections` is enabled.
+//!
+//! The models store their entries in a vector: an association list for the hash collections,
+//! which avoids the hashing and the probing of the underlying `hashbrown` table, a vector sorted
+//! by key for `BTreeMap`, and a contiguous buffer for `VecDeque`. Each model has the same layout as
+//! the type it replaces, so we can transmute between both representations at the boundary of
+//! every method.
+//!
+//! The body of every method of the standard library types, including their trait
+//! implementations, is replaced by a call to the method with the same name in the model. Their
+//! drop glue is replaced by the drop glue of the model. Methods without a model, such as
+//! `HashMap::entry`, are replaced by an unsupported check.
+
+use crate::kani_middle::resolve::resolve_type_def;
+use crate::kani_middle::stable_fn_def;
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::stubs::forward_to_model;
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{CRATE_DEF_ID, DefId};
+use rustc_middle::ty::{self as rustc_ty, GenericArg, TyCtxt};
+use rustc_public::CrateDef;
+use rustc_public::mir::mono::{Instance, InstanceKind};
+use rustc_public::mir::{Body, Mutability, Operand, Place, TerminatorKind};
+use rustc_public::rustc_internal;
+use rustc_public::ty::{GenericArgs, RigidTy, Ty, TyKind};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// The types that we replace, and their models.
+const MODELS: [(&str, &str); 18] = [
+    ("::std::collections::HashMap", "::kani::models::HashMap"),
+    ("::std::collections::HashSet", "::kani::models::HashSet"),
+    ("::std::collections::hash_map::Iter", "::kani::models::hash_map::Iter"),
+    ("::std::collections::hash_map::Keys", "::kani::models::hash_map::Keys"),
+    ("::std::collections::hash_map::Values", "::kani::models::hash_map::Values"),
+    ("::std::collections::hash_map::IntoIter", "::kani::models::hash_map::IntoIter"),
+    ("::std::collections::hash_set::Iter", "::kani::models::hash_set::Iter"),
+    ("::std::collections::hash_set::IntoIter", "::kani::models::hash_set::IntoIter"),
+    ("::std::collections::VecDeque", "::kani::models::collections::VecDeque"),
+    ("::std::collections::vec_deque::Iter", "::kani::models::collections::vec_deque::Iter"),
+    ("::std::collections::vec_deque::IterMut", "::kani::models::collections::vec_deque::IterMut"),
+    ("::std::collections::vec_deque::IntoIter", "::kani::models::collections::vec_deque::IntoIter"),
+    ("::std::collections::BTreeMap", "::kani::models::collections::BTreeMap"),
+    ("::std::collections::btree_map::Iter", "::kani::models::collections::btree_map::Iter"),
+    ("::std::collections::btree_map::Keys", "::kani::models::collections::btree_map::Keys"),
+    ("::std::collections::btree_map::Values", "::kani::models::collections::btree_map::Values"),
+    ("::std::collections::btree_map::Range", "::kani::models::collections::btree_map::Range"),
+    ("::std::collections::btree_map::IntoIter", "::kani::models::collections::btree_map::IntoIter"),
+];
+
+/// Replace the methods and the drop glue of the standard library collections by their models.
+#[derive(Debug, Clone)]
+pub struct CollectionModelPass {
+    /// Map each type that we replace to its model.
+    models: HashMap<DefId, DefId>,
+    unsupported_check_type: CheckType,
+}
+
+impl CollectionModelPass {
+    pub fn new(tcx: TyCtxt, queries: &QueryDb, unsupported_check_type: CheckType) -> Self {
+        let mut models = HashMap::new();
+        if queries.args().model_std_collections {
+            let resolve = |path: &str| {
+                resolve_type_def(tcx, CRATE_DEF_ID, path)
+                    .map_err(|err| {
+                        tcx.dcx().err(format!(
+                            "failed to resolve `{path}` required by `--model-std-collections`: \
+                            {err}"
+                        ))
+                    })
+                    .ok()
+            };
+            for (orig, model) in MODELS {
+                if let (Some(orig), Some(model)) = (resolve(orig), resolve(model)) {
+                    models.insert(orig, model);
+                }
+            }
+        }
+        CollectionModelPass { models, unsupported_check_type }
+    }
+
+    /// Return the model of `ty`, or of the type that `ty` references.
+    fn model_ty<'tcx>(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        ty: rustc_ty::Ty<'tcx>,
+    ) -> Option<rustc_ty::Ty<'tcx>> {
+        match ty.kind() {
+            rustc_ty::Adt(def, args) => {
+                let model = tcx.adt_def(*self.models.get(&def.did())?);
+                Some(rustc_ty::Ty::new_adt(tcx, model, args))
+            }
+            rustc_ty::Ref(region, pointee, mutability) => Some(rustc_ty::Ty::new_ref(
+                tcx,
+                *region,
+                self.model_ty(tcx, *pointee)?,
+                *mutability,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Return the method of the model that replaces `instance` if `instance` is a method of one
+    /// of the types that we replace. The inner option is `None` if the model has no such method.
+    fn model_method(&self, tcx: TyCtxt, instance: Instance) -> Option<Option<Instance>> {
+        let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+        if tcx.def_kind(def_id) != DefKind::AssocFn {
+            return None;
+        }
+        let impl_id = tcx.parent(def_id);
+        let DefKind::Impl { of_trait } = tcx.def_kind(impl_id) else { return None };
+        let name = tcx.item_name(def_id);
+        let args = rustc_internal::internal(tcx, instance.args());
+        let resolve = |model_fn: DefId, args| {
+            stable_fn_def(tcx, model_fn).and_then(|model_fn| Instance::resolve(model_fn, args).ok())
+        };
+        if of_trait {
+            // Call the method of the same trait implemented by the model, e.g.,
+            // `<kani::models::HashMap<K, V, S> as Clone>::clone`.
+            // Implementations for other types that take a model as a trait argument, such as
+            // `From<VecDeque<T>> for Vec<T>`, are replaced as well.
+            let trait_ref = tcx.impl_trait_ref(impl_id)?.instantiate(tcx, args);
+            let model_args = trait_ref
+                .args
+                .iter()
+                .map(|arg| arg.as_type().and_then(|ty| self.model_ty(tcx, ty)))
+                .collect::<Vec<_>>();
+            if model_args.iter().all(Option::is_none) {
+                return None;
+            }
+            let trait_fn = tcx
+                .associated_item_def_ids(trait_ref.def_id)
+                .iter()
+                .find(|item| tcx.item_name(**item) == name)?;
+            let trait_args = trait_ref
+                .args
+                .iter()
+                .zip(model_args)
+                .map(|(arg, model)| model.map_or(arg, GenericArg::from));
+            let fn_args = args.iter().skip(tcx.generics_of(impl_id).count());
+            let model_args =
+                trait_args.chain(fn_args).map(|arg| rustc_internal::stable(arg.kind())).collect();
+            Some(resolve(*trait_fn, &GenericArgs(model_args)))
+        } else {
+            // The model declares its inherent methods with the same generic parameters, so we can
+            // instantiate it with the same generic arguments.
+            let rustc_ty::Adt(def, _) = tcx.type_of(impl_id).instantiate_identity().kind() else {
+                return None;
+            };
+            let model = self.models.get(&def.did())?;
+            let model_fn = tcx
+                .inherent_impls(*model)
+                .iter()
+                .flat_map(|impl_id| tcx.associated_item_def_ids(impl_id))
+                .find(|item| {
+                    tcx.def_kind(**item) == DefKind::AssocFn
+                        && tcx.item_name(**item) == name
+                        && tcx.generics_of(**item).count() == args.len()
+                });
+            let instance_args = instance.args();
+            Some(model_fn.and_then(|model_fn| resolve(*model_fn, &instance_args)))
+        }
+    }
+
+    /// Replace the body of `drop_in_place::<T>` for a type that we replace by:
+    /// ```ignore
+    /// fn drop_in_place(ptr: *mut T) {
+    ///     drop_in_place::<Model>(ptr as *mut Model)
+    /// }
+    /// ```
+    fn replace_drop(&self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+        if tcx.lang_items().drop_in_place_fn() != Some(def_id) {
+            return (false, body);
+        }
+        let TyKind::RigidTy(RigidTy::RawPtr(pointee, _)) = body.arg_locals()[0].ty.kind() else {
+            return (false, body);
+        };
+        let Some(model_ty) = self.model_ty(tcx, rustc_internal::internal(tcx, pointee)) else {
+            return (false, body);
+        };
+        let model_ty: Ty = rustc_internal::stable(model_ty);
+        debug!(function=?instance.name(), ?model_ty, "CollectionModelPass::replace_drop");
+
+        let mut new_body = MutableBody::from(body);
+        new_body.clear_body(TerminatorKind::Return);
+        let model_drop = Instance::resolve_drop_in_place(model_ty);
+        if !model_drop.is_empty_shim() {
+            let mut source = SourceInstruction::Terminator { bb: 0 };
+            let ptr = new_body.insert_ptr_cast(
+                Operand::Move(Place::from(1)),
+                model_ty,
+                Mutability::Mut,
+                &mut source,
+                InsertPosition::Before,
+            );
+            let span = source.span(new_body.blocks());
+            let ret = new_body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+            new_body.insert_call(
+                &model_drop,
+                &mut source,
+                InsertPosition::Before,
+                vec![Operand::Move(Place::from(ptr))],
+                Place::from(ret),
+            );
+        }
+        (true, new_body.into())
+    }
+
+    /// Replace the body of a method of a type that we replace by a call to the method of the
+    /// model, or by an unsupported check if the model does not implement it.
+    fn replace_method(&self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        let Some(model_method) = self.model_method(tcx, instance) else {
+            return (false, body);
+        };
+        debug!(function=?instance.name(), ?model_method, "CollectionModelPass::replace_method");
+        let orig_fn = stable_fn_def(tcx, rustc_internal::internal(tcx, instance.def.def_id()));
+        if let Some(model_method) = model_method
+            && let Some(model_body) = model_method.body()
+            && let Some(orig_fn) = orig_fn
+            && let Some(model_fn) =
+                stable_fn_def(tcx, rustc_internal::internal(tcx, model_method.def.def_id()))
+        {
+            let stub = (orig_fn, model_fn);
+            return match forward_to_model(tcx, stub, &body, model_method, &model_body) {
+                Some(new_body) => (true, new_body),
+                None => (false, body),
+            };
+        }
+        let mut new_body = MutableBody::from(body);
+        new_body.clear_body(TerminatorKind::Unreachable);
+        let mut source = SourceInstruction::Terminator { bb: 0 };
+        let msg = format!("`{}` is not supported with `--model-std-collections`", instance.name());
+        new_body.insert_check(
+            &self.unsupported_check_type,
+            &mut source,
+            InsertPosition::Before,
+            None,
+            &msg,
+        );
+        (true, new_body.into())
+    }
+}
+
+impl TransformPass for CollectionModelPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        query_db.args().model_std_collections && !self.models.is_empty()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        match instance.kind {
+            InstanceKind::Shim => self.replace_drop(tcx, body, instance),
+            InstanceKind::Item => self.replace_method(tcx, body, instance),
+            InstanceKind::Intrinsic | InstanceKind::Virtual { .. } => (false, body),
+        }
+    }
+}
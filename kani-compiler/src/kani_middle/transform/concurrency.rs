@@ -0,0 +1,324 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module instruments the code with the scheduler of `kani::thread` when `--concurrency` is
+//! enabled, so that the threads spawned by a harness interleave with the atomic operations.
+//!
+//! The standard library that Kani uses already replaces `std::thread::spawn` by the model, which
+//! only starts the threads when the scheduler is enabled. This pass enables the scheduler at the
+//! beginning of every harness, and adds a preemption point before every atomic operation of the
+//! crates under verification, i.e., every call to a function of `core::sync::atomic`. We ignore the
+//! atomic operations of the standard library, such as the reference counting of `Arc`, since adding
+//! preemption points there would make every schedule much more expensive to explore.
+//!
+//! With `--memory-model release-acquire`, this pass also instruments the functions of
+//! `core::sync::atomic` that wrap the atomic intrinsics, e.g., `atomic_load`, with the hooks of
+//! `kani::thread` that model the release/acquire semantics. The loads return the value chosen by
+//! `kani::thread::weak_load` instead of the latest value, and the other operations record their
+//! stores so that later loads can read them.
+
+use crate::args::MemoryModel;
+use crate::kani_middle::attributes::is_proof_harness;
+use crate::kani_middle::resolve::resolve_fn;
+use crate::kani_middle::stable_fn_def;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::def_id::CRATE_DEF_ID;
+use rustc_middle::ty::TyCtxt;
+use rustc_public::CrateDef;
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::{
+    Body, Local, Mutability, Operand, Place, RETURN_LOCAL, Rvalue, TerminatorKind,
+};
+use rustc_public::rustc_internal;
+use rustc_public::ty::{FnDef, GenericArgs, RigidTy, Ty, TyKind, UintTy};
+use rustc_span::{Symbol, sym};
+use tracing::debug;
+
+/// The crates whose atomic operations don't get a preemption point.
+const STD_CRATES: [&str; 4] = ["core", "alloc", "std", "kani"];
+
+/// Add the preemption points of the scheduler of `--concurrency`.
+#[derive(Debug, Clone)]
+pub struct ConcurrencyPass {
+    /// The number of context switches of `--concurrency bounded:N`.
+    bound: Option<usize>,
+    init_scheduler: Option<Instance>,
+    preemption_point: Option<Instance>,
+    /// The hooks of `--memory-model release-acquire`.
+    weak_memory: Option<WeakMemoryHooks>,
+}
+
+/// The functions of `kani::thread` that implement the release/acquire memory model.
+#[derive(Debug, Clone)]
+struct WeakMemoryHooks {
+    init: Instance,
+    load: FnDef,
+    track: FnDef,
+    store: FnDef,
+    rmw: FnDef,
+    fence: Instance,
+}
+
+/// The atomic operations of `core::sync::atomic` that the memory model instruments.
+#[derive(Debug, Clone, Copy)]
+enum AtomicOperation {
+    Load,
+    Store,
+    /// A read-modify-write operation, whose ordering is the argument `order`.
+    ReadModifyWrite {
+        order: Local,
+    },
+    Fence,
+}
+
+impl ConcurrencyPass {
+    pub fn new(tcx: TyCtxt, queries: &QueryDb) -> Self {
+        let bound = queries.args().concurrency_bound;
+        let resolve_def = |path: &str| {
+            resolve_fn(tcx, CRATE_DEF_ID, path)
+                .map_err(|err| {
+                    tcx.dcx().err(format!(
+                        "failed to resolve `{path}` required by `--concurrency`: {err}"
+                    ))
+                })
+                .ok()
+                .and_then(|def_id| stable_fn_def(tcx, def_id))
+        };
+        let resolve = |path: &str| {
+            let fn_def = resolve_def(path)?;
+            Instance::resolve(fn_def, &GenericArgs(vec![])).ok()
+        };
+        let (init_scheduler, preemption_point) = if bound.is_some() {
+            (resolve("::kani::thread::init_scheduler"), resolve("::kani::thread::preemption_point"))
+        } else {
+            (None, None)
+        };
+        let release_acquire = queries.args().memory_model == Some(MemoryModel::ReleaseAcquire);
+        let weak_memory = (bound.is_some() && release_acquire)
+            .then(|| {
+                Some(WeakMemoryHooks {
+                    init: resolve("::kani::thread::init_weak_memory")?,
+                    load: resolve_def("::kani::thread::weak_load")?,
+                    track: resolve_def("::kani::thread::weak_track")?,
+                    store: resolve_def("::kani::thread::weak_store")?,
+                    rmw: resolve_def("::kani::thread::weak_rmw")?,
+                    fence: resolve("::kani::thread::weak_fence")?,
+                })
+            })
+            .flatten();
+        ConcurrencyPass { bound, init_scheduler, preemption_point, weak_memory }
+    }
+}
+
+impl TransformPass for ConcurrencyPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        query_db.args().concurrency_bound.is_some()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        let (Some(bound), Some(init_scheduler), Some(preemption_point)) =
+            (self.bound, self.init_scheduler, self.preemption_point)
+        else {
+            return (false, body);
+        };
+        if let Some(hooks) = &self.weak_memory
+            && let Some(operation) = atomic_operation(tcx, instance)
+        {
+            return instrument_atomic_operation(hooks, operation, body, instance);
+        }
+        let is_harness = is_proof_harness(tcx, instance);
+        if !is_harness && STD_CRATES.contains(&instance.def.krate().name.as_str()) {
+            return (false, body);
+        }
+        debug!(function=?instance.name(), "ConcurrencyPass::transform");
+
+        let mut new_body = MutableBody::from(body);
+        let orig_len = new_body.blocks().len();
+        let atomic_calls: Vec<_> = new_body
+            .blocks()
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| is_atomic_operation(tcx, &new_body, &block.terminator.kind))
+            .map(|(bb, _)| bb)
+            .collect();
+        for bb in atomic_calls.into_iter().rev() {
+            let mut source = SourceInstruction::Terminator { bb };
+            let span = source.span(new_body.blocks());
+            let ret = new_body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+            new_body.insert_call(
+                &preemption_point,
+                &mut source,
+                InsertPosition::Before,
+                vec![],
+                Place::from(ret),
+            );
+        }
+
+        // Enable the scheduler at the beginning of the harness.
+        if is_harness {
+            let mut source = if new_body.blocks()[0].statements.is_empty() {
+                SourceInstruction::Terminator { bb: 0 }
+            } else {
+                SourceInstruction::Statement { idx: 0, bb: 0 }
+            };
+            let span = source.span(new_body.blocks());
+            let bound = new_body.new_uint_operand(bound as u128, UintTy::Usize, span);
+            let ret = new_body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+            new_body.insert_call(
+                &init_scheduler,
+                &mut source,
+                InsertPosition::Before,
+                vec![bound],
+                Place::from(ret),
+            );
+            if let Some(hooks) = &self.weak_memory {
+                let ret = new_body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+                new_body.insert_call(
+                    &hooks.init,
+                    &mut source,
+                    InsertPosition::Before,
+                    vec![],
+                    Place::from(ret),
+                );
+            }
+        }
+        (orig_len != new_body.blocks().len(), new_body.into())
+    }
+}
+
+/// Whether `kind` calls a function of `core::sync::atomic`, e.g., `AtomicUsize::load`.
+fn is_atomic_operation(tcx: TyCtxt, body: &MutableBody, kind: &TerminatorKind) -> bool {
+    let TerminatorKind::Call { func, .. } = kind else { return false };
+    let Ok(func_ty) = func.ty(body.locals()) else { return false };
+    let TyKind::RigidTy(RigidTy::FnDef(def, _)) = func_ty.kind() else { return false };
+    is_atomic_fn(tcx, def)
+}
+
+fn is_atomic_fn(tcx: TyCtxt, def: FnDef) -> bool {
+    let def_id = rustc_internal::internal(tcx, def.def_id());
+    if tcx.crate_name(def_id.krate) != sym::core {
+        return false;
+    }
+    let path = tcx.def_path(def_id);
+    let module: Vec<_> = path.data.iter().take(2).map(|data| data.data.get_opt_name()).collect();
+    module == [Some(Symbol::intern("sync")), Some(Symbol::intern("atomic"))]
+}
+
+/// The atomic operation implemented by `instance`, if it's one of the functions of
+/// `core::sync::atomic` that wrap the atomic intrinsics.
+fn atomic_operation(tcx: TyCtxt, instance: Instance) -> Option<AtomicOperation> {
+    let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+    if tcx.crate_name(def_id.krate) != sym::core {
+        return None;
+    }
+    let path = tcx.def_path(def_id);
+    let names: Vec<_> = path.data.iter().map(|data| data.data.get_opt_name()).collect();
+    let [Some(module), Some(atomic), Some(name)] = names.as_slice() else { return None };
+    if *module != Symbol::intern("sync") || *atomic != Symbol::intern("atomic") {
+        return None;
+    }
+    let operation = match name.as_str() {
+        "atomic_load" => AtomicOperation::Load,
+        "atomic_store" => AtomicOperation::Store,
+        "atomic_swap" | "atomic_add" | "atomic_sub" | "atomic_and" | "atomic_nand"
+        | "atomic_or" | "atomic_xor" | "atomic_max" | "atomic_min" | "atomic_umax"
+        | "atomic_umin" => AtomicOperation::ReadModifyWrite { order: 3 },
+        // The success ordering, since a failed exchange doesn't write.
+        "atomic_compare_exchange" | "atomic_compare_exchange_weak" => {
+            AtomicOperation::ReadModifyWrite { order: 4 }
+        }
+        "fence" => AtomicOperation::Fence,
+        _ => return None,
+    };
+    Some(operation)
+}
+
+/// Add the calls to the hooks of the memory model to the body of an atomic operation.
+///
+/// The first argument of the operations that access memory is the pointer `dst`, and the hooks
+/// are generic over its pointee type, like the operations themselves.
+fn instrument_atomic_operation(
+    hooks: &WeakMemoryHooks,
+    operation: AtomicOperation,
+    body: Body,
+    instance: Instance,
+) -> (bool, Body) {
+    debug!(function=?instance.name(), ?operation, "ConcurrencyPass::instrument_atomic_operation");
+    let resolve = |fn_def| Instance::resolve(fn_def, &instance.args()).unwrap();
+    let dst = || Operand::Copy(Place::from(1));
+    let mut new_body = MutableBody::from(body);
+    let returns: Vec<_> = new_body
+        .blocks()
+        .iter()
+        .enumerate()
+        .filter(|(_, block)| matches!(block.terminator.kind, TerminatorKind::Return))
+        .map(|(bb, _)| bb)
+        .collect();
+    let (entry_hook, exit_hook) = match operation {
+        AtomicOperation::Load => {
+            // Replace the value read by the intrinsic, which is the latest value, by the one that
+            // the memory model chooses.
+            let weak_load = resolve(hooks.load);
+            for bb in returns {
+                let mut source = SourceInstruction::Terminator { bb };
+                let latest = new_body.insert_assignment(
+                    Rvalue::Use(Operand::Copy(Place::from(RETURN_LOCAL))),
+                    &mut source,
+                    InsertPosition::Before,
+                );
+                new_body.insert_call(
+                    &weak_load,
+                    &mut source,
+                    InsertPosition::Before,
+                    vec![dst(), Operand::Move(Place::from(latest)), Operand::Copy(Place::from(2))],
+                    Place::from(RETURN_LOCAL),
+                );
+            }
+            return (true, new_body.into());
+        }
+        AtomicOperation::Store => {
+            (Some((resolve(hooks.track), vec![dst()])), Some((resolve(hooks.store), 3)))
+        }
+        AtomicOperation::ReadModifyWrite { order } => {
+            (Some((resolve(hooks.track), vec![dst()])), Some((resolve(hooks.rmw), order)))
+        }
+        AtomicOperation::Fence => (Some((hooks.fence, vec![Operand::Copy(Place::from(1))])), None),
+    };
+    if let Some((hook, order)) = exit_hook {
+        for bb in returns {
+            let mut source = SourceInstruction::Terminator { bb };
+            let span = source.span(new_body.blocks());
+            let ret = new_body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+            new_body.insert_call(
+                &hook,
+                &mut source,
+                InsertPosition::Before,
+                vec![dst(), Operand::Copy(Place::from(order))],
+                Place::from(ret),
+            );
+        }
+    }
+    if let Some((hook, args)) = entry_hook {
+        let mut source = if new_body.blocks()[0].statements.is_empty() {
+            SourceInstruction::Terminator { bb: 0 }
+        } else {
+            SourceInstruction::Statement { idx: 0, bb: 0 }
+        };
+        let span = source.span(new_body.blocks());
+        let ret = new_body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+        new_body.insert_call(&hook, &mut source, InsertPosition::Before, args, Place::from(ret));
+    }
+    (true, new_body.into())
+}
@@ -3,12 +3,16 @@
 //! This module contains code related to the MIR-to-MIR pass to enable contracts.
 use crate::args::ReachabilityType;
 use crate::kani_middle::attributes::KaniAttributes;
-use crate::kani_middle::codegen_units::CodegenUnit;
+use crate::kani_middle::codegen_units::{CodegenUnit, Stubs};
 use crate::kani_middle::kani_functions::{KaniIntrinsic, KaniModel};
 use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::stubs::replace_fn_calls;
 use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_middle::{is_async_fn, stable_fn_def};
 use crate::kani_queries::QueryDb;
 use cbmc::{InternString, InternedString};
+use kani_metadata::ContractManifest;
+use rustc_hir::{CoroutineDesugaring, CoroutineKind, CoroutineSource};
 use rustc_middle::ty::TyCtxt;
 use rustc_public::CrateDef;
 use rustc_public::mir::mono::Instance;
@@ -16,10 +20,15 @@
     Body, ConstOperand, Operand, Rvalue, Terminator, TerminatorKind, VarDebugInfoContents,
 };
 use rustc_public::rustc_internal;
-use rustc_public::ty::{ClosureDef, FnDef, MirConst, RigidTy, TyKind, TypeAndMut, UintTy};
+use rustc_public::ty::{
+    ClosureDef, CoroutineDef, FnDef, MirConst, RigidTy, TyKind, TypeAndMut, UintTy,
+};
 use rustc_span::Symbol;
 use std::collections::HashSet;
 use std::fmt::Debug;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
 use tracing::{debug, trace};
 
 /// Check if we can replace calls to any_modifies or write_any.
@@ -66,8 +75,12 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
         if instance.def.def_id() == self.kani_any.unwrap().def_id() {
             // Ensure kani::any is valid.
             self.any_body(tcx, body)
-        } else if instance.ty().kind().is_closure() {
-            // Replace any modifies occurrences. They should only happen in the contract closures.
+        } else if matches!(
+            instance.ty().kind().rigid(),
+            Some(RigidTy::Closure(..) | RigidTy::Coroutine(..))
+        ) {
+            // Replace any modifies occurrences. They should only happen in the contract closures,
+            // or in the futures that they return for an `async fn`.
             self.replace_any_modifies(body)
         } else {
             (false, body)
@@ -214,6 +227,56 @@ fn any_body(&self, tcx: TyCtxt, mut body: Body) -> (bool, Body) {
     }
 }
 
+/// Replace the calls to foreign functions that have a contract, i.e., functions declared in an
+/// `extern` block with a `#[kani::c_contract]` attribute, by the function generated from their
+/// contract.
+///
+/// Foreign functions don't have a body that we could replace, so we replace the calls and the
+/// function pointers instead, like [`super::stubs::ExternFnStubPass`] does.
+#[derive(Debug, Clone)]
+pub struct CContractPass {
+    /// Map each foreign function to the function generated from its contract.
+    contracts: Stubs,
+}
+
+impl TransformPass for CContractPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        query_db.args().unstable_features.contains(&"function-contracts".to_string())
+            && !self.contracts.is_empty()
+    }
+
+    fn transform(&mut self, _tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "CContractPass::transform");
+        replace_fn_calls(body, &self.contracts)
+    }
+}
+
+impl CContractPass {
+    /// Build the pass with the contracts of the foreign functions declared in the local crate.
+    pub fn new(tcx: TyCtxt) -> CContractPass {
+        let contracts = tcx
+            .hir_crate_items(())
+            .foreign_items()
+            .filter_map(|item| {
+                let def_id = item.owner_id.def_id.to_def_id();
+                let replacement = KaniAttributes::for_item(tcx, def_id).c_contract()?;
+                Some((stable_fn_def(tcx, def_id)?, replacement))
+            })
+            .collect();
+        CContractPass { contracts }
+    }
+}
+
 /// This pass will transform functions annotated with contracts based on the harness configuration.
 ///
 /// Functions with contract will always follow the same structure:
@@ -231,6 +294,7 @@ fn any_body(&self, tcx: TyCtxt, mut body: Body) -> (bool, Body) {
 ///     }
 ///     let kani_contract_mode = kani::internal::mode();
 ///     match kani_contract_mode {
+///         kani::internal::ORIGINAL | kani::internal::CBMC_CONTRACT => { /* original body */ }
 ///         kani::internal::RECURSION_CHECK => {
 ///             #[kanitool::is_contract_generated(recursion_check)]
 ///             let mut __kani_recursion_check_name_fn = || { /* recursion check body */ };
@@ -251,11 +315,14 @@ fn any_body(&self, tcx: TyCtxt, mut body: Body) -> (bool, Body) {
 ///             let mut __kani_check_name_fn = || { /* assert body */ };
 ///             kani_register_contract(__kani_assert_name_fn)
 ///         }
-///         _ => { /* original body */ }
+///         _ => unreachable!(),
 ///     }
 /// }
 /// ```
 ///
+/// For an `async fn`, this is the body of the coroutine that the function returns, and the
+/// closures return futures that are awaited.
+///
 /// This pass will perform the following operations:
 /// 1. For functions with contract that are not being used for check or replacement:
 ///    - Set `kani_contract_mode` to the value ORIGINAL.
@@ -263,6 +330,8 @@ fn any_body(&self, tcx: TyCtxt, mut body: Body) -> (bool, Body) {
 /// 2. For functions with contract that are being used:
 ///    - Set `kani_contract_mode` to the value corresponding to the expected usage.
 ///    - Replace the non-used generated closures body with unreachable.
+///    - With `--cbmc-contracts`, the mode is CBMC_CONTRACT, which keeps the original body and
+///      all closures are unused. The contract is attached to the function during codegen.
 /// 3. Replace the body of `kani_register_contract` by `kani::internal::run_contract_fn` to
 ///    invoke the closure.
 #[derive(Debug, Default, Clone)]
@@ -273,6 +342,8 @@ pub struct FunctionWithContractPass {
     replace_fns: HashSet<FnDef>,
     /// Should we interpret contracts as assertions? (true iff the no-assert-contracts option is not passed)
     assert_contracts: bool,
+    /// Should the contracts being checked or replaced be handed over to CBMC?
+    cbmc_contracts: bool,
     /// Functions annotated with contract attributes will contain contract closures even if they
     /// are not to be used in this harness.
     /// In order to avoid bringing unnecessary logic, we clear their body.
@@ -301,7 +372,9 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
         trace!(function=?instance.name(), "FunctionWithContractPass::transform");
         match instance.ty().kind().rigid().unwrap() {
             RigidTy::FnDef(def, args) => {
-                if let Some(mode) = self.contract_mode(tcx, *def) {
+                if let Some(mode) = self.contract_mode(tcx, *def)
+                    && !is_async_fn(tcx, *def)
+                {
                     self.mark_unused(tcx, *def, &body, mode);
                     let new_body = self.set_mode(tcx, body, mode);
                     (true, new_body)
@@ -326,6 +399,18 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
                     (false, body)
                 }
             }
+            RigidTy::Coroutine(def, _args) => {
+                // The body of an `async fn` runs in its coroutine, which contains the contract.
+                if let Some(fn_def) = async_fn_of(tcx, *def)
+                    && let Some(mode) = self.contract_mode(tcx, fn_def)
+                {
+                    self.mark_unused(tcx, fn_def, &body, mode);
+                    let new_body = self.set_mode(tcx, body, mode);
+                    (true, new_body)
+                } else {
+                    (false, body)
+                }
+            }
             _ => {
                 /* static variables case */
                 (false, body)
@@ -354,8 +439,9 @@ pub fn new(tcx: TyCtxt, queries: &QueryDb, unit: &CodegenUnit) -> FunctionWithCo
                 } else {
                     let attrs = KaniAttributes::for_instance(tcx, *harness);
                     let check_fn = attrs.interpret_for_contract_attribute();
-                    let replace_fns: HashSet<_> =
+                    let mut replace_fns: HashSet<_> =
                         attrs.interpret_stub_verified_attribute().into_iter().collect();
+                    replace_fns.extend(queries.verified_dependency_contracts(tcx));
                     (check_fn, replace_fns)
                 }
             };
@@ -366,6 +452,7 @@ pub fn new(tcx: TyCtxt, queries: &QueryDb, unit: &CodegenUnit) -> FunctionWithCo
                 check_fn,
                 replace_fns,
                 assert_contracts: !queries.args().no_assert_contracts,
+                cbmc_contracts: queries.args().cbmc_contracts,
                 unused_closures: Default::default(),
                 run_contract_fn,
             }
@@ -381,6 +468,7 @@ pub fn new(tcx: TyCtxt, queries: &QueryDb, unit: &CodegenUnit) -> FunctionWithCo
     /// fn original([self], args*) {
     ///    let kani_contract_mode = kani::internal::mode(); // ** Replace this call
     ///    match kani_contract_mode {
+    ///        kani::internal::ORIGINAL | kani::internal::CBMC_CONTRACT => { /* original code */ }
     ///        kani::internal::RECURSION_CHECK => {
     ///            let closure = |/*args*/|{ /*body*/};
     ///            kani_register_contract(closure) // ** Replace this call
@@ -394,7 +482,7 @@ pub fn new(tcx: TyCtxt, queries: &QueryDb, unit: &CodegenUnit) -> FunctionWithCo
     ///        kani::internal::ASSERT => {
     ///            // same as above
     ///        }
-    ///        _ => { /* original code */}
+    ///        _ => unreachable!(),
     ///    }
     /// }
     /// ```
@@ -455,17 +543,41 @@ fn set_mode(&self, tcx: TyCtxt, body: Body, mode: ContractMode) -> Body {
 
     /// Return which contract mode to use for this function if any.
     /// Note that the Check and Replace modes take precedence over the Assert mode.
+    /// Spec functions keep their original body unless their own contract is being checked.
     /// This precedence ensures that a given `target` of a proof_for_contract(target) or stub_verified(target)
     /// use their Check or Replace closures, respectively, rather than the Assert closure.
+    /// When contracts are handed over to CBMC, those targets keep their original body instead.
     fn contract_mode(&self, tcx: TyCtxt, fn_def: FnDef) -> Option<ContractMode> {
         let kani_attributes = KaniAttributes::for_def_id(tcx, fn_def.def_id());
         kani_attributes.has_contract().then(|| {
-            if self.check_fn == Some(fn_def) {
+            let is_target = self.check_fn == Some(fn_def) || self.replace_fns.contains(&fn_def);
+            if self.cbmc_contracts && is_target {
+                if kani_attributes.cbmc_contract().is_none() {
+                    tcx.dcx()
+                        .struct_span_err(
+                            rustc_internal::internal(tcx, fn_def.span()),
+                            format!(
+                                "the contract of `{}` cannot be checked or replaced by CBMC",
+                                fn_def.name()
+                            ),
+                        )
+                        .with_note(
+                            "`--cbmc-contracts` only supports `requires` and `ensures` clauses \
+                            that do not use `old`, on functions without a receiver",
+                        )
+                        .emit();
+                    tcx.dcx().abort_if_errors();
+                }
+                ContractMode::Cbmc
+            } else if self.check_fn == Some(fn_def) {
                 if kani_attributes.has_recursion() {
                     ContractMode::RecursiveCheck
                 } else {
                     ContractMode::SimpleCheck
                 }
+            } else if kani_attributes.is_spec_fn() {
+                // Contracts may call spec functions, which must compute their actual result.
+                ContractMode::Original
             } else if self.replace_fns.contains(&fn_def) {
                 ContractMode::Replace
             } else if self.assert_contracts {
@@ -485,7 +597,7 @@ fn mark_unused(&mut self, tcx: TyCtxt, fn_def: FnDef, body: &Body, mode: Contrac
         let replace_closure = find_closure(tcx, fn_def, body, contract.replaced_with.as_str());
         let assert_closure = find_closure(tcx, fn_def, body, contract.asserted_with.as_str());
         match mode {
-            ContractMode::Original => {
+            ContractMode::Original | ContractMode::Cbmc => {
                 // No contract instrumentation needed. Add all closures to the list of unused.
                 self.unused_closures.insert(recursion_closure);
                 self.unused_closures.insert(check_closure);
@@ -526,6 +638,7 @@ enum ContractMode {
     SimpleCheck = 2,
     Replace = 3,
     Assert = 4,
+    Cbmc = 5,
 }
 
 fn find_closure(tcx: TyCtxt, fn_def: FnDef, body: &Body, name: &str) -> ClosureDef {
@@ -552,3 +665,51 @@ fn find_closure(tcx: TyCtxt, fn_def: FnDef, body: &Body, name: &str) -> ClosureD
             unreachable!()
         })
 }
+
+/// Return the `async fn` whose body is the given coroutine, if any.
+fn async_fn_of(tcx: TyCtxt, coroutine: CoroutineDef) -> Option<FnDef> {
+    let def_id = rustc_internal::internal(tcx, coroutine.0);
+    let is_fn_body = matches!(
+        tcx.coroutine_kind(def_id),
+        Some(CoroutineKind::Desugared(CoroutineDesugaring::Async, CoroutineSource::Fn))
+    );
+    if is_fn_body { stable_fn_def(tcx, tcx.parent(def_id)) } else { None }
+}
+
+/// Find the functions of dependencies whose contracts were verified according to the given
+/// contract manifests, so calls to them can be replaced by their contracts.
+///
+/// Functions that no longer exist or no longer have a contract are ignored, which only means
+/// that Kani verifies their body instead.
+pub(crate) fn verified_dependency_contracts(tcx: TyCtxt, manifests: &[PathBuf]) -> HashSet<FnDef> {
+    let mut verified = HashSet::new();
+    for path in manifests {
+        let manifest: ContractManifest = File::open(path)
+            .map_err(|err| err.to_string())
+            .and_then(|file| {
+                serde_json::from_reader(BufReader::new(file)).map_err(|err| err.to_string())
+            })
+            .unwrap_or_else(|err| {
+                tcx.dcx().fatal(format!(
+                    "failed to read the contract manifest `{}`: {err}",
+                    path.display()
+                ))
+            });
+        let functions: HashSet<_> = manifest
+            .functions
+            .iter()
+            .map(|function| format!("{}::{function}", manifest.crate_name))
+            .collect();
+        verified.extend(
+            rustc_public::find_crates(&manifest.crate_name)
+                .iter()
+                .flat_map(|krate| krate.fn_defs())
+                .filter(|fn_def| {
+                    functions.contains(&fn_def.name())
+                        && KaniAttributes::for_def_id(tcx, fn_def.def_id()).has_contract()
+                }),
+        );
+    }
+    debug!(?verified, "verified_dependency_contracts");
+    verified
+}
@@ -0,0 +1,104 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module replaces the body of `CStr::from_bytes_with_nul` and `CStr::to_str` with the models
+//! from the Kani library.
+//!
+//! The standard library looks for the nul byte and validates ASCII a word at a time, which is fast
+//! at runtime, but it makes the formula much larger than scanning the bytes one at a time.
+
+use crate::kani_middle::kani_functions::KaniModel;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_public::CrateDef;
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::{Body, Operand, Place, RETURN_LOCAL, TerminatorKind};
+use rustc_public::rustc_internal;
+use rustc_public::ty::{FnDef, GenericArgs};
+use rustc_span::Symbol;
+use tracing::debug;
+
+/// Replace the body of the `CStr` methods that have a model by a call to the model.
+#[derive(Debug, Clone)]
+pub struct CStrModelPass {
+    from_bytes_with_nul_model: FnDef,
+    to_str_model: FnDef,
+}
+
+impl CStrModelPass {
+    pub fn new(queries: &QueryDb) -> Self {
+        let kani_fns = queries.kani_functions();
+        CStrModelPass {
+            from_bytes_with_nul_model: *kani_fns
+                .get(&KaniModel::CStrFromBytesWithNul.into())
+                .unwrap(),
+            to_str_model: *kani_fns.get(&KaniModel::CStrToStr.into()).unwrap(),
+        }
+    }
+
+    /// Return the model for `instance` if it is one of the methods that we replace.
+    fn model(&self, tcx: TyCtxt, instance: Instance) -> Option<FnDef> {
+        let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+        if tcx.def_kind(def_id) != DefKind::AssocFn
+            || tcx.def_kind(tcx.parent(def_id)) != (DefKind::Impl { of_trait: false })
+        {
+            return None;
+        }
+        let adt = tcx.type_of(tcx.parent(def_id)).instantiate_identity().ty_adt_def()?;
+        if !tcx.is_diagnostic_item(Symbol::intern("cstr_type"), adt.did()) {
+            return None;
+        }
+        match tcx.item_name(def_id).as_str() {
+            "from_bytes_with_nul" => Some(self.from_bytes_with_nul_model),
+            "to_str" => Some(self.to_str_model),
+            _ => None,
+        }
+    }
+}
+
+impl TransformPass for CStrModelPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        true
+    }
+
+    /// Replace the body of the `CStr` methods by:
+    /// ```ignore
+    /// fn to_str(&self) -> Result<&str, Utf8Error> {
+    ///     cstr_models::to_str(self)
+    /// }
+    /// ```
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        let Some(model) = self.model(tcx, instance) else {
+            return (false, body);
+        };
+        debug!(function=?instance.name(), "CStrModelPass::transform");
+
+        let model = Instance::resolve(model, &GenericArgs(vec![])).unwrap();
+        let args =
+            (1..=body.arg_locals().len()).map(|local| Operand::Move(Place::from(local))).collect();
+        let mut new_body = MutableBody::from(body);
+        new_body.clear_body(TerminatorKind::Return);
+        let mut source = SourceInstruction::Terminator { bb: 0 };
+        new_body.insert_call(
+            &model,
+            &mut source,
+            InsertPosition::Before,
+            args,
+            Place::from(RETURN_LOCAL),
+        );
+        (true, new_body.into())
+    }
+}
@@ -0,0 +1,314 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Global pass that replaces dynamic calls by direct calls with `-Z devirtualize`.
+//!
+//! A call to a method of a trait object goes through its vtable, which CBMC encodes as a call to a
+//! function pointer that may target the implementation of the method of any type in the model.
+//! However, a trait object can only be created from a value of a concrete type, either by an
+//! unsizing coercion, or by a constant that contains a vtable. We collect the types of the trait
+//! objects created by the code that is reachable from the harness. If every trait object of the
+//! principal trait of a call was created from the same type, the call can only target the
+//! implementation of that type, so we call it directly and pass it the data pointer of the
+//! receiver.
+//!
+//! We leave the other dynamic calls as they are, including the calls of a trait that some trait
+//! object is upcast to, since the upcast object may have been created from any type that implements
+//! the subtrait, and the calls whose receiver isn't a reference, e.g. `self: Box<Self>`.
+
+use crate::kani_middle::coercion::{CoercionBaseStable, extract_unsize_casting_stable};
+use crate::kani_middle::reachability::CallGraph;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{BodyTransformation, GlobalPass, TransformationResult};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::{ExistentialTraitRef, TyCtxt};
+use rustc_public::mir::alloc::{AllocId, GlobalAlloc};
+use rustc_public::mir::mono::{Instance, InstanceKind, MonoItem, StaticDef};
+use rustc_public::mir::visit::Location;
+use rustc_public::mir::{
+    Body, BorrowKind, CastKind, ConstOperand, LocalDecl, MirVisitor, MutBorrowKind, Mutability,
+    Operand, Place, PointerCoercion, ProjectionElem, RawPtrKind, Rvalue, Terminator,
+    TerminatorKind,
+};
+use rustc_public::rustc_internal;
+use rustc_public::ty::{
+    Allocation, ConstantKind, GenericArgKind, MirConst, Region, RegionKind, RigidTy, Ty, TyKind,
+};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, info};
+
+/// Replace the dynamic calls whose receiver can only have one concrete type by direct calls.
+#[derive(Debug, Clone, Default)]
+pub struct DevirtualizePass {
+    /// The number of calls that the last run devirtualized.
+    devirtualized: Option<u64>,
+}
+
+impl DevirtualizePass {
+    /// Take the number of calls that the last run devirtualized.
+    pub fn take_devirtualized_calls(&mut self) -> Option<u64> {
+        self.devirtualized.take()
+    }
+}
+
+impl GlobalPass for DevirtualizePass {
+    fn is_enabled(&self, query_db: &QueryDb) -> bool {
+        query_db.args().devirtualize
+    }
+
+    fn transform(
+        &mut self,
+        tcx: TyCtxt,
+        _call_graph: &CallGraph,
+        _starting_items: &[MonoItem],
+        instances: Vec<Instance>,
+        transformer: &mut BodyTransformation,
+    ) -> bool {
+        let instances: Vec<_> =
+            instances.into_iter().filter(|instance| instance.has_body()).collect();
+        let mut sources = TraitObjectSources::new(tcx);
+        for instance in &instances {
+            if let Ok(def) = StaticDef::try_from(*instance)
+                && let Ok(alloc) = def.eval_initializer()
+            {
+                sources.visit_allocation(&alloc);
+            }
+            sources.collect(&transformer.body(tcx, *instance));
+        }
+
+        let mut devirtualized = 0;
+        let mut dynamic = 0;
+        for instance in instances {
+            let body = transformer.body(tcx, instance);
+            let mut new_body = MutableBody::from(body);
+            let calls = dynamic_calls(&new_body);
+            dynamic += calls.len();
+            let mut modified = false;
+            for call in calls {
+                if let Some(concrete_ty) = sources.single_source(call.self_ty) {
+                    debug!(?instance, ?call, ?concrete_ty, "devirtualize");
+                    devirtualize(&mut new_body, call, concrete_ty);
+                    modified = true;
+                    devirtualized += 1;
+                }
+            }
+            if modified {
+                transformer.cache.entry(instance).and_modify(|transformation_result| {
+                    *transformation_result = TransformationResult::Modified(new_body.into());
+                });
+            }
+        }
+        info!(devirtualized, dynamic, "DevirtualizePass::transform");
+        self.devirtualized = Some(devirtualized as u64);
+        devirtualized > 0
+    }
+}
+
+/// A dynamic call whose receiver is a reference to a trait object.
+#[derive(Debug)]
+struct DynamicCall {
+    /// The block that the call terminates.
+    bb: usize,
+    /// The type of the trait object.
+    self_ty: Ty,
+    /// Whether the receiver is a mutable reference.
+    mutability: Mutability,
+}
+
+/// Find the dynamic calls of the body that we know how to devirtualize.
+fn dynamic_calls(body: &MutableBody) -> Vec<DynamicCall> {
+    body.blocks()
+        .iter()
+        .enumerate()
+        .filter_map(|(bb, block)| {
+            let TerminatorKind::Call { func, args, .. } = &block.terminator.kind else {
+                return None;
+            };
+            let TyKind::RigidTy(RigidTy::FnDef(def, fn_args)) = func.ty(body.locals()).ok()?.kind()
+            else {
+                return None;
+            };
+            let instance = Instance::resolve(def, &fn_args).ok()?;
+            if !matches!(instance.kind, InstanceKind::Virtual { .. }) {
+                return None;
+            }
+            let GenericArgKind::Type(self_ty) = fn_args.0.first()? else {
+                return None;
+            };
+            let receiver_ty = args.first()?.ty(body.locals()).ok()?;
+            match receiver_ty.kind().rigid()? {
+                RigidTy::Ref(_, pointee, mutability) if pointee == self_ty => {
+                    Some(DynamicCall { bb, self_ty: *self_ty, mutability: *mutability })
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Replace the dynamic call by a call to the implementation of `concrete_ty`:
+/// ```ignore
+/// receiver = <receiver of the call>;
+/// fat = &raw const *receiver;
+/// thin = fat as *const ConcreteTy;
+/// new_receiver = &*thin;
+/// <ConcreteTy as Trait>::method(move new_receiver, <other arguments>)
+/// ```
+fn devirtualize(body: &mut MutableBody, call: DynamicCall, concrete_ty: Ty) {
+    let mut source = SourceInstruction::Terminator { bb: call.bb };
+    let Terminator {
+        kind: TerminatorKind::Call { func, mut args, destination, target, unwind },
+        span,
+    } = body.blocks()[call.bb].terminator.clone()
+    else {
+        unreachable!("expected a call terminator")
+    };
+    let TyKind::RigidTy(RigidTy::FnDef(def, mut fn_args)) = func.ty(body.locals()).unwrap().kind()
+    else {
+        unreachable!("expected a direct call")
+    };
+
+    let receiver =
+        body.insert_assignment(Rvalue::Use(args.remove(0)), &mut source, InsertPosition::Before);
+    let (raw_kind, borrow_kind) = match call.mutability {
+        Mutability::Not => (RawPtrKind::Const, BorrowKind::Shared),
+        Mutability::Mut => (RawPtrKind::Mut, BorrowKind::Mut { kind: MutBorrowKind::Default }),
+    };
+    let fat = body.insert_assignment(
+        Rvalue::AddressOf(raw_kind, deref(receiver)),
+        &mut source,
+        InsertPosition::Before,
+    );
+    let thin = body.insert_ptr_cast(
+        Operand::Move(Place::from(fat)),
+        concrete_ty,
+        call.mutability,
+        &mut source,
+        InsertPosition::Before,
+    );
+    let new_receiver = body.insert_assignment(
+        Rvalue::Ref(Region { kind: RegionKind::ReErased }, borrow_kind, deref(thin)),
+        &mut source,
+        InsertPosition::Before,
+    );
+    args.insert(0, Operand::Move(Place::from(new_receiver)));
+
+    fn_args.0[0] = GenericArgKind::Type(concrete_ty);
+    let fn_ty = Ty::from_rigid_kind(RigidTy::FnDef(def, fn_args));
+    let literal = MirConst::try_new_zero_sized(fn_ty).unwrap();
+    let func = Operand::Constant(ConstOperand { span, user_ty: None, const_: literal });
+    let kind = TerminatorKind::Call { func, args, destination, target, unwind };
+    body.replace_terminator(&source, Terminator { kind, span });
+}
+
+fn deref(local: usize) -> Place {
+    Place { local, projection: vec![ProjectionElem::Deref] }
+}
+
+/// The concrete types that the trait objects of each principal trait were created from.
+struct TraitObjectSources<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    types: HashMap<ExistentialTraitRef<'tcx>, HashSet<Ty>>,
+    /// The traits that a trait object is upcast to.
+    upcast: HashSet<ExistentialTraitRef<'tcx>>,
+    visited_allocs: HashSet<AllocId>,
+    /// The locals of the body being visited.
+    locals: Vec<LocalDecl>,
+}
+
+impl<'tcx> TraitObjectSources<'tcx> {
+    fn new(tcx: TyCtxt<'tcx>) -> Self {
+        TraitObjectSources {
+            tcx,
+            types: HashMap::default(),
+            upcast: HashSet::default(),
+            visited_allocs: HashSet::default(),
+            locals: vec![],
+        }
+    }
+
+    /// Collect the trait objects that the body creates.
+    fn collect(&mut self, body: &Body) {
+        self.locals = body.locals().to_vec();
+        self.visit_body(body);
+    }
+
+    /// The only type that the trait objects of type `dyn_ty` may have been created from, if
+    /// there is one.
+    fn single_source(&self, dyn_ty: Ty) -> Option<Ty> {
+        let principal = self.principal(dyn_ty)?;
+        if self.upcast.contains(&principal) {
+            return None;
+        }
+        let types = self.types.get(&principal)?;
+        if types.len() == 1 { types.iter().next().copied() } else { None }
+    }
+
+    /// The principal trait of a trait object type, with its bound regions erased.
+    fn principal(&self, dyn_ty: Ty) -> Option<ExistentialTraitRef<'tcx>> {
+        let principal = dyn_ty.kind().trait_principal()?;
+        let principal = rustc_internal::internal(self.tcx, principal);
+        Some(self.tcx.instantiate_bound_regions_with_erased(principal))
+    }
+
+    fn add_source(&mut self, concrete_ty: Ty, dyn_ty: Ty) {
+        if let Some(principal) = self.principal(dyn_ty) {
+            if concrete_ty.kind().is_trait() {
+                self.upcast.insert(principal);
+            } else {
+                self.types.entry(principal).or_default().insert(concrete_ty);
+            }
+        }
+    }
+
+    /// Collect the vtables of the allocation and of the allocations it points to.
+    fn visit_allocation(&mut self, alloc: &Allocation) {
+        for (_, prov) in &alloc.provenance.ptrs {
+            self.visit_alloc_id(prov.0);
+        }
+    }
+
+    fn visit_alloc_id(&mut self, alloc_id: AllocId) {
+        if !self.visited_allocs.insert(alloc_id) {
+            return;
+        }
+        match GlobalAlloc::from(alloc_id) {
+            GlobalAlloc::VTable(concrete_ty, Some(principal)) => {
+                let principal = rustc_internal::internal(self.tcx, principal);
+                let principal = self.tcx.instantiate_bound_regions_with_erased(principal);
+                self.types.entry(principal).or_default().insert(concrete_ty);
+            }
+            GlobalAlloc::Memory(alloc) => self.visit_allocation(&alloc),
+            GlobalAlloc::Static(def) => {
+                if let Ok(alloc) = def.eval_initializer() {
+                    self.visit_allocation(&alloc);
+                }
+            }
+            GlobalAlloc::VTable(_, None)
+            | GlobalAlloc::Function(_)
+            | GlobalAlloc::TypeId { .. } => {}
+        }
+    }
+}
+
+impl MirVisitor for TraitObjectSources<'_> {
+    fn visit_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
+        if let Rvalue::Cast(CastKind::PointerCoercion(PointerCoercion::Unsize), operand, target) =
+            rvalue
+        {
+            let source_ty = operand.ty(&self.locals).unwrap();
+            let CoercionBaseStable { src_ty, dst_ty } =
+                extract_unsize_casting_stable(self.tcx, source_ty, *target);
+            if dst_ty.kind().is_trait() {
+                self.add_source(src_ty, dst_ty);
+            }
+        }
+        self.super_rvalue(rvalue, location);
+    }
+
+    fn visit_const_operand(&mut self, constant: &ConstOperand, _location: Location) {
+        if let ConstantKind::Allocated(alloc) = constant.const_.kind() {
+            self.visit_allocation(alloc);
+        }
+    }
+}
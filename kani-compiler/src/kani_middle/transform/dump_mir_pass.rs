@@ -13,6 +13,7 @@
 use std::fs::File;
 use std::io::BufWriter;
 use std::io::Write;
+use std::path::Path;
 
 use super::BodyTransformation;
 
@@ -57,16 +58,26 @@ fn transform(
                 base_name.with_extension(ArtifactType::SymTabGoto)
             }
         };
-        let out_file = File::create(file_path.with_extension("kani.mir")).unwrap();
-        let mut writer = BufWriter::new(out_file);
-
-        // For each def_id, dump their MIR.
-        for instance in instances.iter() {
-            writeln!(writer, "// Item: {} ({})", instance.name(), instance.mangled_name()).unwrap();
-            let _ = transformer.body(tcx, *instance).dump(&mut writer, &instance.name());
-        }
+        write_mir(tcx, &file_path.with_extension("kani.mir"), &instances, transformer);
 
         // This pass just reads the MIR and thus never modifies it.
         false
     }
 }
+
+/// Write the MIR of `instances`, as transformed by `transformer`, to `path`.
+pub fn write_mir(
+    tcx: TyCtxt,
+    path: &Path,
+    instances: &[Instance],
+    transformer: &mut BodyTransformation,
+) {
+    let out_file = File::create(path).unwrap();
+    let mut writer = BufWriter::new(out_file);
+
+    // For each def_id, dump their MIR.
+    for instance in instances.iter() {
+        writeln!(writer, "// Item: {} ({})", instance.name(), instance.mangled_name()).unwrap();
+        let _ = transformer.body(tcx, *instance).dump(&mut writer, &instance.name());
+    }
+}
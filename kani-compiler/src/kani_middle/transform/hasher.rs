@@ -0,0 +1,153 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module replaces the SipHash implementation used by `std::hash::DefaultHasher` with the
+//! cheaper models from the Kani library when `--model-hashing` is enabled.
+//!
+//! SipHash mixes every byte through several rounds of additions, rotations and XORs, which
+//! dominates the size of the formula of harnesses that use a `HashMap` or a `HashSet`.
+
+use crate::kani_middle::kani_functions::KaniModel;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::smart_pointers::push_field;
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::TyCtxt;
+use rustc_public::CrateDef;
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::{
+    Body, BorrowKind, MutBorrowKind, Mutability, Operand, Place, ProjectionElem, RETURN_LOCAL,
+    Rvalue, TerminatorKind,
+};
+use rustc_public::rustc_internal;
+use rustc_public::ty::{FnDef, GenericArgs, Region, RegionKind, RigidTy, TyKind, UintTy};
+use rustc_span::sym;
+use tracing::debug;
+
+/// Replace the body of `<core::hash::sip::Hasher<S> as core::hash::Hasher>::{write, finish}`,
+/// which every other method of `DefaultHasher` relies on, by a call to the corresponding model.
+#[derive(Debug, Clone)]
+pub struct HasherModelPass {
+    write_model: FnDef,
+    finish_model: FnDef,
+}
+
+impl HasherModelPass {
+    pub fn new(queries: &QueryDb) -> Self {
+        let kani_fns = queries.kani_functions();
+        HasherModelPass {
+            write_model: *kani_fns.get(&KaniModel::HasherWrite.into()).unwrap(),
+            finish_model: *kani_fns.get(&KaniModel::HasherFinish.into()).unwrap(),
+        }
+    }
+
+    /// Return the model for `instance` and the projection from its `self` argument to the field
+    /// of the hasher that the model uses as its state, if `instance` is one of the methods that we
+    /// replace.
+    fn model(
+        &self,
+        tcx: TyCtxt,
+        instance: Instance,
+        body: &Body,
+    ) -> Option<(FnDef, Vec<ProjectionElem>)> {
+        let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+        if tcx.def_kind(def_id) != DefKind::AssocFn
+            || tcx.def_kind(tcx.parent(def_id)) != (DefKind::Impl { of_trait: true })
+        {
+            return None;
+        }
+        let model = match tcx.item_name(def_id).as_str() {
+            "write" => self.write_model,
+            "finish" => self.finish_model,
+            _ => return None,
+        };
+        let TyKind::RigidTy(RigidTy::Ref(_, hasher_ty, _)) = body.arg_locals().first()?.ty.kind()
+        else {
+            return None;
+        };
+        let TyKind::RigidTy(RigidTy::Adt(def, _)) = hasher_ty.kind() else { return None };
+        if !is_sip_hasher(tcx, rustc_internal::internal(tcx, def.def_id())) {
+            return None;
+        }
+        let mut projection = vec![ProjectionElem::Deref];
+        let state_ty = push_field(&mut projection, hasher_ty, "tail")?;
+        matches!(state_ty.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U64)))
+            .then_some((model, projection))
+    }
+}
+
+/// Whether `def_id` is the SipHash implementation of `core`, i.e., `core::hash::sip::Hasher`.
+///
+/// This type is private to `core`, so there is no diagnostic item or path that we can resolve.
+/// Compare its definition path instead.
+fn is_sip_hasher(tcx: TyCtxt, def_id: DefId) -> bool {
+    let path = tcx.def_path(def_id).data;
+    tcx.crate_name(def_id.krate) == sym::core
+        && path.len() == 3
+        && path.iter().zip(["hash", "sip", "Hasher"]).all(|(segment, name)| {
+            segment.data.get_opt_name().is_some_and(|segment| segment.as_str() == name)
+        })
+}
+
+impl TransformPass for HasherModelPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        query_db.args().model_hashing
+    }
+
+    /// Replace the body of the SipHash methods by:
+    /// ```ignore
+    /// fn write(&mut self, msg: &[u8]) {
+    ///     hasher_models::write(&mut (*self).tail, msg)
+    /// }
+    /// ```
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        let Some((model, projection)) = self.model(tcx, instance, &body) else {
+            return (false, body);
+        };
+        debug!(function=?instance.name(), "HasherModelPass::transform");
+
+        let model = Instance::resolve(model, &GenericArgs(vec![])).unwrap();
+        let borrow = match body.arg_locals()[0].ty.kind() {
+            TyKind::RigidTy(RigidTy::Ref(_, _, Mutability::Mut)) => {
+                BorrowKind::Mut { kind: MutBorrowKind::Default }
+            }
+            _ => BorrowKind::Shared,
+        };
+        let num_args = body.arg_locals().len();
+        let mut new_body = MutableBody::from(body);
+        new_body.clear_body(TerminatorKind::Return);
+        let mut source = SourceInstruction::Terminator { bb: 0 };
+        let state = new_body.insert_assignment(
+            Rvalue::Ref(
+                Region { kind: RegionKind::ReErased },
+                borrow,
+                Place { local: 1, projection },
+            ),
+            &mut source,
+            InsertPosition::Before,
+        );
+        let args = std::iter::once(Operand::Move(Place::from(state)))
+            .chain((2..=num_args).map(|local| Operand::Move(Place::from(local))))
+            .collect();
+        new_body.insert_call(
+            &model,
+            &mut source,
+            InsertPosition::Before,
+            args,
+            Place::from(RETURN_LOCAL),
+        );
+        (true, new_body.into())
+    }
+}
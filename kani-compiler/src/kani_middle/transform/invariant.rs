@@ -0,0 +1,243 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module contains two passes for types that implement `kani::Invariant`:
+//! 1. `AnyInvariantPass`, which makes `kani::any::<T>()` assume the invariant of the value it
+//!    creates if the `Invariant` implementation of `T` was derived.
+//! 2. `InvariantPreservationPass`, which checks that public methods that take `&mut self`
+//!    preserve the invariant of `self`.
+
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::kani_functions::{KaniHook, KaniModel};
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_public::CrateDef;
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::{
+    BasicBlockIdx, Body, BorrowKind, Local, Mutability, Operand, Place, ProjectionElem,
+    RETURN_LOCAL, Rvalue, TerminatorKind,
+};
+use rustc_public::rustc_internal;
+use rustc_public::ty::{
+    FnDef, GenericArgKind, GenericArgs, Region, RegionKind, RigidTy, Ty, TyKind,
+};
+use rustc_span::Symbol;
+use tracing::debug;
+
+/// Make `kani::any::<T>()` assume `T`'s invariant if `T` derives `kani::Invariant`.
+#[derive(Debug, Clone)]
+pub struct AnyInvariantPass {
+    /// The FnDef of KaniModel::Any
+    kani_any: FnDef,
+    kani_assume: Instance,
+    /// The FnDef of `kani::Invariant::is_safe`, which is `None` if the `kani` crate is not available.
+    is_safe: Option<FnDef>,
+}
+
+impl AnyInvariantPass {
+    pub fn new(tcx: TyCtxt, query_db: &QueryDb) -> Self {
+        let kani_fns = query_db.kani_functions();
+        let kani_any = *kani_fns.get(&KaniModel::Any.into()).unwrap();
+        let kani_assume = *kani_fns.get(&KaniHook::Assume.into()).unwrap();
+        let kani_assume = Instance::resolve(kani_assume, &GenericArgs(vec![])).unwrap();
+        Self { kani_any, kani_assume, is_safe: find_is_safe(tcx) }
+    }
+}
+
+impl TransformPass for AnyInvariantPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        self.is_safe.is_some()
+    }
+
+    /// Transform the body of `kani::any::<T>()` into:
+    /// ```ignore
+    /// pub fn any() -> T {
+    ///   let value = T::any();
+    ///   kani::assume(value.is_safe());
+    ///   value
+    /// }
+    /// ```
+    /// if the `Invariant` implementation of `T` was derived.
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        if instance.def.def_id() != self.kani_any.def_id() {
+            return (false, body);
+        }
+
+        let ty = *instance.args().0[0].expect_ty();
+        let Some(is_safe) = resolve_is_safe(self.is_safe.unwrap(), ty) else {
+            return (false, body);
+        };
+        if KaniAttributes::for_instance(tcx, is_safe).fn_marker()
+            != Some(Symbol::intern("kani_derived_invariant"))
+        {
+            return (false, body);
+        }
+        debug!(function=?instance.name(), "AnyInvariantPass::transform");
+
+        let mut new_body = MutableBody::from(body);
+        for bb in return_blocks(&new_body) {
+            let mut source = SourceInstruction::Terminator { bb };
+            let is_safe_local =
+                call_is_safe(&mut new_body, &is_safe, Place::from(RETURN_LOCAL), &mut source);
+            let ret_local = new_body.new_local(
+                Ty::new_tuple(&[]),
+                source.span(new_body.blocks()),
+                Mutability::Not,
+            );
+            new_body.insert_call(
+                &self.kani_assume,
+                &mut source,
+                InsertPosition::Before,
+                vec![Operand::Move(Place::from(is_safe_local))],
+                Place::from(ret_local),
+            );
+        }
+        (true, new_body.into())
+    }
+}
+
+/// Check that public methods that take `&mut self` preserve the invariant of `self`.
+#[derive(Debug, Clone)]
+pub struct InvariantPreservationPass {
+    check_type: CheckType,
+    /// The FnDef of `kani::Invariant::is_safe`, which is `None` if the `kani` crate is not available.
+    is_safe: Option<FnDef>,
+}
+
+impl InvariantPreservationPass {
+    pub fn new(tcx: TyCtxt, check_type: CheckType) -> Self {
+        Self { check_type, is_safe: find_is_safe(tcx) }
+    }
+}
+
+impl TransformPass for InvariantPreservationPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        query_db.args().check_invariant_preservation && self.is_safe.is_some()
+    }
+
+    /// Assert `self.is_safe()` before every return of a public method that takes `&mut self`,
+    /// where the type of `self` is an ADT that implements `kani::Invariant`.
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        let Some(self_ty) = mut_self_ty(tcx, instance, &body) else {
+            return (false, body);
+        };
+        let Some(is_safe) = resolve_is_safe(self.is_safe.unwrap(), self_ty) else {
+            return (false, body);
+        };
+        debug!(function=?instance.name(), "InvariantPreservationPass::transform");
+
+        let msg = format!(
+            "`{}` must preserve the safety invariant of `{self_ty}`",
+            instance.trimmed_name()
+        );
+        let self_place = Place { local: 1, projection: vec![ProjectionElem::Deref] };
+        let mut new_body = MutableBody::from(body);
+        for bb in return_blocks(&new_body) {
+            let mut source = SourceInstruction::Terminator { bb };
+            let is_safe_local =
+                call_is_safe(&mut new_body, &is_safe, self_place.clone(), &mut source);
+            new_body.insert_check(
+                &self.check_type,
+                &mut source,
+                InsertPosition::Before,
+                Some(is_safe_local),
+                &msg,
+            );
+        }
+        (true, new_body.into())
+    }
+}
+
+/// Find `kani::Invariant::is_safe`.
+fn find_is_safe(tcx: TyCtxt) -> Option<FnDef> {
+    let invariant = tcx.get_diagnostic_item(Symbol::intern("KaniInvariant"))?;
+    let is_safe = *tcx.associated_item_def_ids(invariant).first()?;
+    let is_safe_ty = rustc_internal::stable(tcx.type_of(is_safe)).value;
+    is_safe_ty.kind().fn_def().map(|(def, _)| def)
+}
+
+/// Resolve `<ty as kani::Invariant>::is_safe`, or return `None` if `ty` does not implement the trait.
+fn resolve_is_safe(is_safe: FnDef, ty: Ty) -> Option<Instance> {
+    Instance::resolve(is_safe, &GenericArgs(vec![GenericArgKind::Type(ty)]))
+        .ok()
+        .filter(|instance| instance.has_body())
+}
+
+/// If `instance` is a public method that takes `&mut self`, where `self` is an ADT,
+/// return the type of `self`.
+fn mut_self_ty(tcx: TyCtxt, instance: Instance, body: &Body) -> Option<Ty> {
+    let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+    if tcx.def_kind(def_id) != DefKind::AssocFn
+        || tcx.def_kind(tcx.parent(def_id)) != (DefKind::Impl { of_trait: false })
+        || !tcx.visibility(def_id).is_public()
+    {
+        return None;
+    }
+    let has_self = body
+        .var_debug_info
+        .iter()
+        .any(|info| info.argument_index == Some(1) && info.name == "self");
+    let TyKind::RigidTy(RigidTy::Ref(_, self_ty, Mutability::Mut)) =
+        body.arg_locals().first()?.ty.kind()
+    else {
+        return None;
+    };
+    (has_self && matches!(self_ty.kind(), TyKind::RigidTy(RigidTy::Adt(..)))).then_some(self_ty)
+}
+
+/// Insert a call to `is_safe(&place)` before `source`; return the local storing the result.
+fn call_is_safe(
+    body: &mut MutableBody,
+    is_safe: &Instance,
+    place: Place,
+    source: &mut SourceInstruction,
+) -> Local {
+    let value_ref = body.insert_assignment(
+        Rvalue::Ref(Region { kind: RegionKind::ReErased }, BorrowKind::Shared, place),
+        source,
+        InsertPosition::Before,
+    );
+    let result = body.new_local(Ty::bool_ty(), source.span(body.blocks()), Mutability::Not);
+    body.insert_call(
+        is_safe,
+        source,
+        InsertPosition::Before,
+        vec![Operand::Move(Place::from(value_ref))],
+        Place::from(result),
+    );
+    result
+}
+
+/// Return the basic blocks of `body` that return from the function.
+fn return_blocks(body: &MutableBody) -> Vec<BasicBlockIdx> {
+    body.blocks()
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, bb)| matches!(bb.terminator.kind, TerminatorKind::Return).then_some(idx))
+        .collect()
+}
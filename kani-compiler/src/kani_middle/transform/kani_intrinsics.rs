@@ -73,6 +73,7 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
                 KaniIntrinsic::CheckedAlignOf => (true, self.checked_align_of(body, instance)),
                 KaniIntrinsic::CheckedSizeOf => (true, self.checked_size_of(body, instance)),
                 KaniIntrinsic::IsInitialized => (true, self.is_initialized_body(body)),
+                KaniIntrinsic::AssumeInitRange => (true, self.assume_init_range_body(body)),
                 KaniIntrinsic::ValidValue => (true, self.valid_value_body(body)),
                 // The former two are handled in contracts pass for now, while the latter is handled in the the automatic harness pass.
                 KaniIntrinsic::WriteAny
@@ -348,6 +349,112 @@ fn is_initialized_body(&mut self, body: Body) -> Body {
         new_body.into()
     }
 
+    /// Generate the body for `assume_init_range`, which looks like the following
+    ///
+    /// ```
+    /// pub fn assume_init_range<T>(ptr: *const T, count: usize) {
+    ///     let layout = ... // Byte mask representing the layout of T.
+    ///     __kani_mem_init_sm_set_slice_chunk(ptr, layout, count, true)
+    /// }
+    /// ```
+    ///
+    /// If `T` has `MaybeUninit` fields, the state of their bytes is preserved.
+    fn assume_init_range_body(&mut self, body: Body) -> Body {
+        let mut new_body = MutableBody::from(body);
+        new_body.clear_body(TerminatorKind::Return);
+        // Nothing to do if uninitialized memory checks are not enabled.
+        if !self.enable_uninit {
+            return new_body.into();
+        }
+        let mut source = SourceInstruction::Terminator { bb: 0 };
+        let mut statements = vec![];
+
+        // The first argument type.
+        let arg_ty = new_body.locals()[1].ty;
+        let TyKind::RigidTy(RigidTy::RawPtr(pointee_ty, _)) = arg_ty.kind() else { unreachable!() };
+        let pointee_info = match PointeeInfo::from_ty(pointee_ty) {
+            Ok(pointee_info) => pointee_info,
+            Err(reason) => {
+                let reason = format!(
+                    "Kani currently doesn't support updating memory initialization for pointers to `{pointee_ty}. {reason}",
+                );
+                new_body.insert_check(
+                    &self.unsupported_check_type,
+                    &mut source,
+                    InsertPosition::Before,
+                    None,
+                    &reason,
+                );
+                return new_body.into();
+            }
+        };
+        let layout = match pointee_info.layout() {
+            PointeeLayout::Sized { layout } => layout.clone(),
+            // A union is initialized if the bytes of one of its fields are, so mark the data bytes
+            // of all its fields.
+            PointeeLayout::Union { field_layouts } => {
+                let mut layout = vec![false; pointee_info.layout().maybe_size().unwrap()];
+                for field_layout in field_layouts {
+                    for (byte, field_byte) in layout.iter_mut().zip(field_layout) {
+                        *byte |= *field_byte;
+                    }
+                }
+                layout
+            }
+            PointeeLayout::Slice { .. } | PointeeLayout::TraitObject => {
+                unreachable!("Expected a sized pointee, but found `{pointee_ty}`")
+            }
+        };
+        if layout.is_empty() {
+            // Encountered a ZST, so there is nothing to do.
+            return new_body.into();
+        }
+
+        let layout_operand =
+            mk_layout_operand(&mut new_body, &mut statements, &mut source, &layout);
+        let mut args = vec![Operand::Copy(Place::from(1)), layout_operand];
+        let model = match pointee_info.maybe_uninit_mask() {
+            Some(mask) => {
+                args.push(mk_layout_operand(&mut new_body, &mut statements, &mut source, mask));
+                KaniModel::SetSliceChunkPtrInitializedPreserving
+            }
+            None => KaniModel::SetSliceChunkPtrInitialized,
+        };
+        let span = source.span(new_body.blocks());
+        args.push(Operand::Copy(Place::from(2)));
+        args.push(Operand::Constant(ConstOperand {
+            span,
+            user_ty: None,
+            const_: MirConst::from_bool(true),
+        }));
+        let set_ptr_initialized_instance = resolve_mem_init_fn(
+            *self.kani_defs.get(&model.into()).unwrap(),
+            layout.len(),
+            pointee_ty,
+        );
+        let terminator = Terminator {
+            kind: TerminatorKind::Call {
+                func: Operand::Copy(Place::from(new_body.new_local(
+                    set_ptr_initialized_instance.ty(),
+                    span,
+                    Mutability::Not,
+                ))),
+                args,
+                destination: Place::from(RETURN_LOCAL),
+                target: Some(0), // The current value does not matter, since it will be overwritten in add_bb.
+                unwind: UnwindAction::Terminate,
+            },
+            span,
+        };
+        // Construct the basic block and insert it into the body.
+        new_body.insert_bb(
+            BasicBlock { statements, terminator },
+            &mut source,
+            InsertPosition::Before,
+        );
+        new_body.into()
+    }
+
     /// Generate the body for retrieving the size of a val starting from its raw pointer.
     ///
     /// The body generated will depend on the type of the pointer.
@@ -19,36 +19,68 @@
 use crate::kani_middle::codegen_units::CodegenUnit;
 use crate::kani_middle::reachability::CallGraph;
 use crate::kani_middle::transform::body::CheckType;
+use crate::kani_middle::transform::check_leaks::LeakPass;
+use crate::kani_middle::transform::check_provenance::ProvenancePass;
+use crate::kani_middle::transform::check_truncation::TruncationPass;
 use crate::kani_middle::transform::check_uninit::{DelayedUbPass, UninitPass};
 use crate::kani_middle::transform::check_values::ValidValuePass;
 use crate::kani_middle::transform::clone::{ClonableGlobalPass, ClonableTransformPass};
-use crate::kani_middle::transform::contracts::{AnyModifiesPass, FunctionWithContractPass};
+use crate::kani_middle::transform::collections::CollectionModelPass;
+use crate::kani_middle::transform::concurrency::ConcurrencyPass;
+use crate::kani_middle::transform::contracts::{
+    AnyModifiesPass, CContractPass, FunctionWithContractPass,
+};
+use crate::kani_middle::transform::cstr::CStrModelPass;
+use crate::kani_middle::transform::devirtualize::DevirtualizePass;
+use crate::kani_middle::transform::hasher::HasherModelPass;
+use crate::kani_middle::transform::invariant::{AnyInvariantPass, InvariantPreservationPass};
 use crate::kani_middle::transform::kani_intrinsics::IntrinsicGeneratorPass;
 use crate::kani_middle::transform::loop_contracts::LoopContractPass;
+use crate::kani_middle::transform::recursion_limit::RecursionLimitPass;
+use crate::kani_middle::transform::refcount::RefCountPass;
+use crate::kani_middle::transform::std_models::StdModelPass;
 use crate::kani_middle::transform::stubs::{ExternFnStubPass, FnStubPass};
 use crate::kani_queries::QueryDb;
 use automatic::{AutomaticArbitraryPass, AutomaticHarnessPass};
 use dump_mir_pass::DumpMirPass;
+use kani_metadata::UnsafeOperation;
 use rustc_middle::ty::TyCtxt;
 use rustc_public::mir::Body;
 use rustc_public::mir::mono::{Instance, MonoItem};
 use std::collections::HashMap;
 use std::fmt::Debug;
+use unsafe_operations::UnsafeOperationsPass;
 
 use crate::kani_middle::transform::rustc_intrinsics::RustcIntrinsicsPass;
+use crate::kani_middle::transform::smart_pointers::SmartPointerDerefPass;
+pub use dump_mir_pass::write_mir;
 pub use internal_mir::RustcInternalMir;
 
 mod automatic;
 pub(crate) mod body;
+mod check_leaks;
+mod check_provenance;
+mod check_truncation;
 mod check_uninit;
-mod check_values;
-mod contracts;
+pub(crate) mod check_values;
+mod collections;
+mod concurrency;
+pub(crate) mod contracts;
+mod cstr;
+mod devirtualize;
 mod dump_mir_pass;
+mod hasher;
 mod internal_mir;
+mod invariant;
 mod kani_intrinsics;
 mod loop_contracts;
+mod recursion_limit;
+mod refcount;
 mod rustc_intrinsics;
+mod smart_pointers;
+mod std_models;
 mod stubs;
+mod unsafe_operations;
 
 /// Object used to retrieve a transformed instance body.
 /// The transformations to be applied may be controlled by user options.
@@ -78,18 +110,31 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt, unit: &CodegenUnit) -> Self {
         // This has to come first, since creating harnesses affects later stubbing and contract passes.
         transformer.add_pass(queries, AutomaticHarnessPass::new(queries));
         transformer.add_pass(queries, AutomaticArbitraryPass::new(unit, queries));
+        transformer.add_pass(queries, AnyInvariantPass::new(tcx, queries));
         transformer.add_pass(queries, FnStubPass::new(&unit.stubs));
         transformer.add_pass(queries, ExternFnStubPass::new(&unit.stubs));
+        transformer.add_pass(queries, CContractPass::new(tcx));
+        transformer.add_pass(queries, HasherModelPass::new(queries));
+        transformer.add_pass(queries, CStrModelPass::new(queries));
+        transformer.add_pass(queries, SmartPointerDerefPass);
+        transformer.add_pass(
+            queries,
+            CollectionModelPass::new(tcx, queries, unsupported_check_type.clone()),
+        );
+        transformer.add_pass(queries, StdModelPass::new(tcx, queries));
         transformer.add_pass(queries, FunctionWithContractPass::new(tcx, queries, unit));
         // This has to come after the contract pass since we want this to only replace the closure
         // body that is relevant for this harness.
         transformer.add_pass(queries, AnyModifiesPass::new(tcx, queries, unit));
+        transformer
+            .add_pass(queries, InvariantPreservationPass::new(tcx, safety_check_type.clone()));
         transformer.add_pass(
             queries,
-            ValidValuePass {
-                safety_check_type,
-                unsupported_check_type: unsupported_check_type.clone(),
-            },
+            ValidValuePass::new(
+                safety_check_type.clone(),
+                unsupported_check_type.clone(),
+                queries.kani_functions(),
+            ),
         );
         // Putting `UninitPass` after `ValidValuePass` makes sure that the code generated by
         // `UninitPass` does not get unnecessarily instrumented by valid value checks. However, it
@@ -105,8 +150,30 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt, unit: &CodegenUnit) -> Self {
                 mem_init_fn_cache: queries.kani_functions().clone(),
             },
         );
+        transformer.add_pass(
+            queries,
+            ProvenancePass {
+                // Like `UninitPass`, this tracks a non-deterministic allocation.
+                safety_check_type: CheckType::new_safety_check_assert_no_assume(queries),
+                kani_functions: queries.kani_functions().clone(),
+            },
+        );
+        transformer.add_pass(
+            queries,
+            LeakPass {
+                // Like `UninitPass`, this tracks a non-deterministic allocation.
+                safety_check_type: CheckType::new_safety_check_assert_no_assume(queries),
+                kani_functions: queries.kani_functions().clone(),
+            },
+        );
+        transformer.add_pass(
+            queries,
+            TruncationPass { safety_check_type, kani_functions: queries.kani_functions().clone() },
+        );
         transformer.add_pass(queries, IntrinsicGeneratorPass::new(unsupported_check_type, queries));
         transformer.add_pass(queries, LoopContractPass::new(tcx, queries, unit));
+        transformer.add_pass(queries, ConcurrencyPass::new(tcx, queries));
+        transformer.add_pass(queries, RefCountPass::new(tcx, queries));
         transformer.add_pass(queries, RustcIntrinsicsPass::new(queries));
         transformer
     }
@@ -216,11 +283,25 @@ pub struct GlobalPasses {
     /// The passes that operate on the whole codegen unit, they run after all previous passes are
     /// done.
     global_passes: Vec<Box<dyn ClonableGlobalPass>>,
+    /// The read-only pass that lists the reachable unsafe operations, if it is enabled. It is kept
+    /// apart from the other global passes so that its results can be retrieved.
+    unsafe_operations: Option<UnsafeOperationsPass>,
+    /// The pass that replaces dynamic calls by direct calls, if it is enabled. It runs before the
+    /// other global passes, which then analyze the direct calls, and it is kept apart from them so
+    /// that the number of calls it replaced can be retrieved.
+    devirtualize: Option<DevirtualizePass>,
 }
 
 impl GlobalPasses {
     pub fn new(queries: &QueryDb, tcx: TyCtxt) -> Self {
-        let mut global_passes = GlobalPasses { global_passes: vec![] };
+        let unsafe_operations = UnsafeOperationsPass::default();
+        let devirtualize = DevirtualizePass::default();
+        let mut global_passes = GlobalPasses {
+            global_passes: vec![],
+            unsafe_operations: unsafe_operations.is_enabled(queries).then_some(unsafe_operations),
+            devirtualize: devirtualize.is_enabled(queries).then_some(devirtualize),
+        };
+        global_passes.add_global_pass(queries, RecursionLimitPass::new(queries));
         global_passes.add_global_pass(
             queries,
             DelayedUbPass::new(
@@ -250,6 +331,10 @@ pub fn run_global_passes(
         call_graph: CallGraph,
     ) -> bool {
         let mut modified = false;
+        if let Some(pass) = &mut self.devirtualize {
+            modified |=
+                pass.transform(tcx, &call_graph, starting_items, instances.clone(), transformer);
+        }
         for global_pass in &mut self.global_passes {
             modified |= global_pass.transform(
                 tcx,
@@ -259,8 +344,26 @@ pub fn run_global_passes(
                 transformer,
             );
         }
+        if let Some(pass) = &mut self.unsafe_operations {
+            pass.transform(tcx, &call_graph, starting_items, instances, transformer);
+        }
         modified
     }
+
+    /// Take the unsafe operations that are reachable from the starting items of the last run,
+    /// which are only listed with `--list-unsafe`.
+    pub fn take_unsafe_operations(&mut self) -> Vec<UnsafeOperation> {
+        self.unsafe_operations
+            .as_mut()
+            .map(UnsafeOperationsPass::take_operations)
+            .unwrap_or_default()
+    }
+
+    /// Take the number of dynamic calls that the last run replaced by direct calls, which is only
+    /// computed with `-Z devirtualize`.
+    pub fn take_devirtualized_calls(&mut self) -> Option<u64> {
+        self.devirtualize.as_mut().and_then(DevirtualizePass::take_devirtualized_calls)
+    }
 }
 
 mod clone {
@@ -0,0 +1,167 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module bounds the depth of the recursive calls of a harness that has a recursion limit,
+//! i.e., `#[kani::proof(recursion_limit = N)]`.
+//!
+//! Every group of mutually recursive functions gets a depth counter, which counts the calls between
+//! the functions of the group that are active. This pass wraps each of these calls with:
+//! ```ignore
+//! let entered = kani::recursion::recursion_enter(GROUP, LIMIT);
+//! kani::recursion::recursion_limit_check(entered, "recursion limit of LIMIT reached ...");
+//! callee(args);
+//! kani::recursion::recursion_exit(GROUP);
+//! ```
+//! The check assumes its condition, so the paths that exceed the limit are not explored. Its
+//! failures have their own property class, which the driver reports apart from the failures of the
+//! harness, the same way it reports the failures of unwinding assertions.
+
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::kani_functions::{KaniFunction, KaniModel};
+use crate::kani_middle::reachability::CallGraph;
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::{BodyTransformation, GlobalPass, TransformationResult};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_public::mir::mono::{Instance, MonoItem};
+use rustc_public::mir::{Mutability, Place, TerminatorKind};
+use rustc_public::ty::{FnDef, GenericArgs, RigidTy, Ty, TyKind, UintTy};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Bound the depth of the recursive calls of the harnesses with a recursion limit.
+#[derive(Debug, Clone)]
+pub struct RecursionLimitPass {
+    check_type: CheckType,
+    kani_functions: HashMap<KaniFunction, FnDef>,
+}
+
+impl RecursionLimitPass {
+    pub fn new(queries: &QueryDb) -> Self {
+        RecursionLimitPass {
+            check_type: CheckType::new_recursion_limit_check(queries),
+            kani_functions: queries.kani_functions().clone(),
+        }
+    }
+
+    fn model(&self, model: KaniModel) -> Instance {
+        Instance::resolve(self.kani_functions[&model.into()], &GenericArgs(vec![])).unwrap()
+    }
+
+    /// Instrument the calls of `body` to the functions of the group `group`. Return whether any
+    /// call was instrumented.
+    fn instrument(
+        &self,
+        body: &mut MutableBody,
+        group: usize,
+        limit: u32,
+        groups: &HashMap<Instance, usize>,
+    ) -> bool {
+        let calls = (0..body.blocks().len())
+            .filter_map(|bb| {
+                let TerminatorKind::Call { func, target, .. } = &body.blocks()[bb].terminator.kind
+                else {
+                    return None;
+                };
+                let TyKind::RigidTy(RigidTy::FnDef(def, args)) =
+                    func.ty(body.locals()).unwrap().kind()
+                else {
+                    return None;
+                };
+                let callee = Instance::resolve(def, &args).unwrap();
+                (groups.get(&callee) == Some(&group)).then_some((bb, callee, target.is_some()))
+            })
+            .collect::<Vec<_>>();
+
+        let enter = self.model(KaniModel::RecursionEnter);
+        let exit = self.model(KaniModel::RecursionExit);
+        for (bb, callee, returns) in &calls {
+            let mut source = SourceInstruction::Terminator { bb: *bb };
+            let span = source.span(body.blocks());
+            let entered = body.new_local(Ty::bool_ty(), span, Mutability::Not);
+            let group_op = body.new_uint_operand(group as u128, UintTy::Usize, span);
+            let limit_op = body.new_uint_operand(limit.into(), UintTy::U32, span);
+            body.insert_call(
+                &enter,
+                &mut source,
+                InsertPosition::Before,
+                vec![group_op, limit_op],
+                Place::from(entered),
+            );
+            body.insert_check(
+                &self.check_type,
+                &mut source,
+                InsertPosition::Before,
+                Some(entered),
+                &format!(
+                    "recursion limit of {limit} reached when calling `{}`",
+                    callee.trimmed_name()
+                ),
+            );
+            // Calls that diverge never return from the recursion.
+            if *returns {
+                let group_op = body.new_uint_operand(group as u128, UintTy::Usize, span);
+                let ret = body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+                body.insert_call(
+                    &exit,
+                    &mut source,
+                    InsertPosition::After,
+                    vec![group_op],
+                    Place::from(ret),
+                );
+            }
+        }
+        !calls.is_empty()
+    }
+}
+
+impl GlobalPass for RecursionLimitPass {
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool {
+        true
+    }
+
+    fn transform(
+        &mut self,
+        tcx: TyCtxt,
+        call_graph: &CallGraph,
+        starting_items: &[MonoItem],
+        instances: Vec<Instance>,
+        transformer: &mut BodyTransformation,
+    ) -> bool {
+        let Some(limit) = starting_items
+            .iter()
+            .filter_map(|item| match item {
+                MonoItem::Fn(instance) => {
+                    KaniAttributes::for_instance(tcx, *instance).recursion_limit()
+                }
+                MonoItem::Static(_) | MonoItem::GlobalAsm(_) => None,
+            })
+            .min()
+        else {
+            return false;
+        };
+
+        let groups = call_graph
+            .recursive_functions()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(group, functions)| functions.into_iter().map(move |f| (f, group)))
+            .collect::<HashMap<_, _>>();
+        debug!(?limit, groups = groups.len(), "RecursionLimitPass::transform");
+
+        let mut modified = false;
+        for instance in instances {
+            let Some(&group) = groups.get(&instance) else {
+                continue;
+            };
+            let mut body = MutableBody::from(transformer.body(tcx, instance));
+            if self.instrument(&mut body, group, limit, &groups) {
+                modified = true;
+                transformer.cache.insert(instance, TransformationResult::Modified(body.into()));
+            }
+        }
+        modified
+    }
+}
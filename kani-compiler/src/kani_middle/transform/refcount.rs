@@ -0,0 +1,317 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module instruments the updates of the strong counts of `Rc` and `Arc` with the hooks of
+//! `kani::refcount` when `--check-refcounts` is enabled, so that Kani checks that the counts never
+//! drop below zero and that they are zero when the harness returns.
+//!
+//! The hooks observe the value of a counter after each update, instead of modeling the operations
+//! that update it, so that we only need to find where the updates happen:
+//! - `Rc` updates its strong count in `RcInnerPtr::inc_strong` and `RcInnerPtr::dec_strong`, which
+//!   are implemented for both `RcInner` and `WeakInner`. We instrument their bodies.
+//! - `Arc` updates its strong count with atomic operations on the field `strong` of `ArcInner` or
+//!   `WeakInner`, e.g., `self.inner().strong.fetch_add(1, Relaxed)` in `Arc::clone`. We instrument
+//!   these calls in the functions of `alloc::sync`.
+
+use crate::kani_middle::attributes::is_proof_harness;
+use crate::kani_middle::resolve::resolve_fn;
+use crate::kani_middle::stable_fn_def;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::smart_pointers::push_field;
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::def_id::CRATE_DEF_ID;
+use rustc_middle::ty::TyCtxt;
+use rustc_public::CrateDef;
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::{
+    BasicBlockIdx, Body, BorrowKind, Local, Mutability, Operand, Place, ProjectionElem, Rvalue,
+    StatementKind, TerminatorKind,
+};
+use rustc_public::rustc_internal;
+use rustc_public::ty::{GenericArgs, Region, RegionKind, RigidTy, Ty, TyKind};
+use rustc_span::{Symbol, sym};
+use tracing::debug;
+
+/// Instrument the updates of the strong counts of `Rc` and `Arc`.
+#[derive(Debug, Clone)]
+pub struct RefCountPass {
+    hooks: Option<RefCountHooks>,
+}
+
+/// The functions of `kani::refcount` that the instrumentation calls.
+#[derive(Debug, Clone)]
+struct RefCountHooks {
+    rc_updated: Instance,
+    rc_decrement: Instance,
+    arc_updated: Instance,
+    arc_decrement: Instance,
+    check: Instance,
+}
+
+impl RefCountPass {
+    pub fn new(tcx: TyCtxt, queries: &QueryDb) -> Self {
+        let hooks = if queries.args().check_refcounts { RefCountHooks::resolve(tcx) } else { None };
+        RefCountPass { hooks }
+    }
+}
+
+impl RefCountHooks {
+    fn resolve(tcx: TyCtxt) -> Option<Self> {
+        let resolve = |path: &str| {
+            let def_id = resolve_fn(tcx, CRATE_DEF_ID, path)
+                .map_err(|err| {
+                    tcx.dcx().err(format!(
+                        "failed to resolve `{path}` required by `--check-refcounts`: {err}"
+                    ))
+                })
+                .ok()?;
+            Instance::resolve(stable_fn_def(tcx, def_id)?, &GenericArgs(vec![])).ok()
+        };
+        Some(RefCountHooks {
+            rc_updated: resolve("::kani::refcount::rc_strong_updated")?,
+            rc_decrement: resolve("::kani::refcount::rc_strong_decrement")?,
+            arc_updated: resolve("::kani::refcount::arc_strong_updated")?,
+            arc_decrement: resolve("::kani::refcount::arc_strong_decrement")?,
+            check: resolve("::kani::refcount::check_refcounts")?,
+        })
+    }
+}
+
+impl TransformPass for RefCountPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        query_db.args().check_refcounts
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        let Some(hooks) = &self.hooks else { return (false, body) };
+        if is_proof_harness(tcx, instance) {
+            return instrument_harness(hooks, body, instance);
+        }
+        let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+        if tcx.crate_name(def_id.krate) != sym::alloc {
+            return (false, body);
+        }
+        let path = tcx.def_path(def_id);
+        let names: Vec<_> = path.data.iter().map(|data| data.data.get_opt_name()).collect();
+        match names.as_slice() {
+            [Some(rc), Some(inner_ptr), Some(name)]
+                if *rc == Symbol::intern("rc") && *inner_ptr == Symbol::intern("RcInnerPtr") =>
+            {
+                let decrement = match name.as_str() {
+                    "inc_strong" => false,
+                    "dec_strong" => true,
+                    _ => return (false, body),
+                };
+                instrument_rc_update(hooks, decrement, body, instance)
+            }
+            [Some(sync), ..] if *sync == Symbol::intern("sync") => {
+                instrument_arc_updates(tcx, hooks, body, instance)
+            }
+            _ => (false, body),
+        }
+    }
+}
+
+/// Check the counts before every return of the harness.
+fn instrument_harness(hooks: &RefCountHooks, body: Body, instance: Instance) -> (bool, Body) {
+    debug!(function=?instance.name(), "RefCountPass::instrument_harness");
+    let mut new_body = MutableBody::from(body);
+    for bb in return_blocks(&new_body) {
+        let mut source = SourceInstruction::Terminator { bb };
+        insert_hook(&mut new_body, &hooks.check, &mut source, InsertPosition::Before, vec![]);
+    }
+    (true, new_body.into())
+}
+
+/// Instrument the body of `RcInnerPtr::inc_strong` or `RcInnerPtr::dec_strong`, whose receiver is
+/// a reference to either `RcInner`, which stores the counter in its field `strong`, or
+/// `WeakInner`, which stores a reference to it.
+fn instrument_rc_update(
+    hooks: &RefCountHooks,
+    decrement: bool,
+    body: Body,
+    instance: Instance,
+) -> (bool, Body) {
+    let TyKind::RigidTy(RigidTy::Ref(_, self_ty, _)) = body.arg_locals()[0].ty.kind() else {
+        return (false, body);
+    };
+    let mut projection = vec![ProjectionElem::Deref];
+    let Some(field_ty) = push_field(&mut projection, self_ty, "strong") else {
+        return (false, body);
+    };
+    debug!(function=?instance.name(), "RefCountPass::instrument_rc_update");
+    let field = Place { local: 1, projection };
+    let mut new_body = MutableBody::from(body);
+    let mut source = if new_body.blocks()[0].statements.is_empty() {
+        SourceInstruction::Terminator { bb: 0 }
+    } else {
+        SourceInstruction::Statement { idx: 0, bb: 0 }
+    };
+    let rvalue = if field_ty.kind().is_ref() {
+        Rvalue::Use(Operand::Copy(field))
+    } else {
+        Rvalue::Ref(Region { kind: RegionKind::ReErased }, BorrowKind::Shared, field)
+    };
+    let counter = new_body.insert_assignment(rvalue, &mut source, InsertPosition::Before);
+    if decrement {
+        let args = vec![Operand::Copy(Place::from(counter))];
+        insert_hook(&mut new_body, &hooks.rc_decrement, &mut source, InsertPosition::Before, args);
+    }
+    for bb in return_blocks(&new_body) {
+        let mut source = SourceInstruction::Terminator { bb };
+        let args = vec![Operand::Copy(Place::from(counter))];
+        insert_hook(&mut new_body, &hooks.rc_updated, &mut source, InsertPosition::Before, args);
+    }
+    (true, new_body.into())
+}
+
+/// Instrument the atomic operations on the strong count of `Arc` in a function of `alloc::sync`.
+fn instrument_arc_updates(
+    tcx: TyCtxt,
+    hooks: &RefCountHooks,
+    body: Body,
+    instance: Instance,
+) -> (bool, Body) {
+    let mut new_body = MutableBody::from(body);
+    let updates: Vec<_> = new_body
+        .blocks()
+        .iter()
+        .enumerate()
+        .filter_map(|(bb, block)| {
+            let TerminatorKind::Call { func, args, target: Some(_), .. } = &block.terminator.kind
+            else {
+                return None;
+            };
+            let name = atomic_method(tcx, &new_body, func)?;
+            let counter = strong_count_arg(&new_body, args.first()?)?;
+            Some((bb, counter, name == "fetch_sub"))
+        })
+        .collect();
+    if updates.is_empty() {
+        return (false, new_body.into());
+    }
+    debug!(function=?instance.name(), count=updates.len(), "RefCountPass::instrument_arc_updates");
+    for (bb, counter, decrement) in updates.into_iter().rev() {
+        let args = vec![Operand::Copy(Place::from(counter))];
+        let mut source = SourceInstruction::Terminator { bb };
+        insert_hook(&mut new_body, &hooks.arc_updated, &mut source, InsertPosition::After, args);
+        if decrement {
+            let mut source = SourceInstruction::Terminator { bb };
+            let args = vec![Operand::Copy(Place::from(counter))];
+            insert_hook(
+                &mut new_body,
+                &hooks.arc_decrement,
+                &mut source,
+                InsertPosition::Before,
+                args,
+            );
+        }
+    }
+    (true, new_body.into())
+}
+
+/// The name of the method of `core::sync::atomic` that `func` calls, if it updates the value of
+/// the atomic.
+fn atomic_method(tcx: TyCtxt, body: &MutableBody, func: &Operand) -> Option<String> {
+    let TyKind::RigidTy(RigidTy::FnDef(def, _)) = func.ty(body.locals()).ok()?.kind() else {
+        return None;
+    };
+    let def_id = rustc_internal::internal(tcx, def.def_id());
+    if tcx.crate_name(def_id.krate) != sym::core {
+        return None;
+    }
+    let path = tcx.def_path(def_id);
+    let module: Vec<_> = path.data.iter().take(2).map(|data| data.data.get_opt_name()).collect();
+    if module != [Some(Symbol::intern("sync")), Some(Symbol::intern("atomic"))] {
+        return None;
+    }
+    let name = def.name();
+    let method = name.rsplit("::").next()?;
+    matches!(
+        method,
+        "fetch_add"
+            | "fetch_sub"
+            | "fetch_update"
+            | "compare_exchange"
+            | "compare_exchange_weak"
+            | "store"
+            | "swap"
+    )
+    .then(|| method.to_string())
+}
+
+/// The local that holds the reference to the strong count of an `Arc` that `arg` passes to an
+/// atomic operation, if it does.
+///
+/// The reference is either borrowed from the field `strong` of `ArcInner`, or copied from the
+/// field `strong` of `WeakInner`, before the call.
+fn strong_count_arg(body: &MutableBody, arg: &Operand) -> Option<Local> {
+    let (Operand::Copy(place) | Operand::Move(place)) = arg else { return None };
+    if !place.projection.is_empty() {
+        return None;
+    }
+    let local = place.local;
+    body.blocks().iter().flat_map(|block| &block.statements).find_map(|stmt| {
+        let StatementKind::Assign(dest, rvalue) = &stmt.kind else { return None };
+        if dest.local != local || !dest.projection.is_empty() {
+            return None;
+        }
+        let source = match rvalue {
+            Rvalue::Ref(_, _, source) => source,
+            Rvalue::Use(Operand::Copy(source) | Operand::Move(source)) => source,
+            _ => return None,
+        };
+        is_strong_count(body, source).then_some(local)
+    })
+}
+
+/// Whether `place` is the field `strong` of `ArcInner` or `WeakInner`.
+fn is_strong_count(body: &MutableBody, place: &Place) -> bool {
+    let Some((ProjectionElem::Field(idx, _), parent)) = place.projection.split_last() else {
+        return false;
+    };
+    let parent = Place { local: place.local, projection: parent.to_vec() };
+    let Ok(parent_ty) = parent.ty(body.locals()) else { return false };
+    let TyKind::RigidTy(RigidTy::Adt(def, _)) = parent_ty.kind() else { return false };
+    if !matches!(def.name().as_str(), "alloc::sync::ArcInner" | "alloc::sync::WeakInner") {
+        return false;
+    }
+    def.variants_iter()
+        .next()
+        .and_then(|variant| variant.fields().into_iter().nth(*idx))
+        .is_some_and(|field| field.name == "strong")
+}
+
+/// The basic blocks that return from `body`.
+fn return_blocks(body: &MutableBody) -> Vec<BasicBlockIdx> {
+    body.blocks()
+        .iter()
+        .enumerate()
+        .filter(|(_, block)| matches!(block.terminator.kind, TerminatorKind::Return))
+        .map(|(bb, _)| bb)
+        .collect()
+}
+
+/// Insert a call to `hook`, which returns `()`.
+fn insert_hook(
+    body: &mut MutableBody,
+    hook: &Instance,
+    source: &mut SourceInstruction,
+    position: InsertPosition,
+    args: Vec<Operand>,
+) {
+    let span = source.span(body.blocks());
+    let ret = body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+    body.insert_call(hook, source, position, args, Place::from(ret));
+}
@@ -0,0 +1,123 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module replaces the body of `Deref::deref` for `Rc` and `Arc` by a direct projection to
+//! the value stored in their allocation.
+//!
+//! The standard library implements these methods with a chain of calls, e.g.,
+//! `Rc::deref -> Rc::inner -> NonNull::as_ref -> NonNull::as_ptr`, each of which becomes a
+//! function call in the goto program. `Box` does not need a model, since dereferencing a box is
+//! already a builtin operation.
+
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_public::CrateDef;
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::{
+    Body, BorrowKind, Place, ProjectionElem, RETURN_LOCAL, Rvalue, TerminatorKind,
+};
+use rustc_public::rustc_internal;
+use rustc_public::ty::{AdtKind, Region, RegionKind, RigidTy, Ty, TyKind};
+use tracing::debug;
+
+/// Replace the body of `<Rc<T, A> as Deref>::deref` and `<Arc<T, A> as Deref>::deref` by
+/// `&(*(*self).ptr.pointer).value`.
+#[derive(Debug, Clone)]
+pub struct SmartPointerDerefPass;
+
+impl SmartPointerDerefPass {
+    /// Return the projection from `self` to the value of the smart pointer if `instance` is one
+    /// of the methods that we replace.
+    fn projection(tcx: TyCtxt, instance: Instance, body: &Body) -> Option<Vec<ProjectionElem>> {
+        let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+        if tcx.def_kind(def_id) != DefKind::AssocFn
+            || tcx.def_kind(tcx.parent(def_id)) != (DefKind::Impl { of_trait: true })
+            || tcx.item_name(def_id).as_str() != "deref"
+        {
+            return None;
+        }
+        let trait_ref = tcx.impl_trait_ref(tcx.parent(def_id))?;
+        if Some(trait_ref.skip_binder().def_id) != tcx.lang_items().deref_trait() {
+            return None;
+        }
+        let TyKind::RigidTy(RigidTy::Ref(_, self_ty, _)) = body.arg_locals().first()?.ty.kind()
+        else {
+            return None;
+        };
+        let TyKind::RigidTy(RigidTy::Adt(def, _)) = self_ty.kind() else { return None };
+        let value_field = match def.name().as_str() {
+            "alloc::rc::Rc" => "value",
+            "alloc::sync::Arc" => "data",
+            _ => return None,
+        };
+        let mut projection = vec![ProjectionElem::Deref];
+        let non_null = push_field(&mut projection, self_ty, "ptr")?;
+        let inner_ptr = push_field(&mut projection, non_null, "pointer")?;
+        let TyKind::RigidTy(RigidTy::RawPtr(inner_ty, _)) = inner_ptr.kind() else { return None };
+        projection.push(ProjectionElem::Deref);
+        push_field(&mut projection, inner_ty, value_field)?;
+        Some(projection)
+    }
+}
+
+/// Push the projection to the field `name` of the struct `ty`, and return the type of the field.
+pub(super) fn push_field(projection: &mut Vec<ProjectionElem>, ty: Ty, name: &str) -> Option<Ty> {
+    let TyKind::RigidTy(RigidTy::Adt(def, args)) = ty.kind() else { return None };
+    if def.kind() != AdtKind::Struct {
+        return None;
+    }
+    let (idx, field) = def
+        .variants_iter()
+        .next()?
+        .fields()
+        .into_iter()
+        .enumerate()
+        .find(|(_, f)| f.name == name)?;
+    let field_ty = field.ty_with_args(&args);
+    projection.push(ProjectionElem::Field(idx, field_ty));
+    Some(field_ty)
+}
+
+impl TransformPass for SmartPointerDerefPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        true
+    }
+
+    /// Replace the body of the deref methods by:
+    /// ```ignore
+    /// fn deref(&self) -> &T {
+    ///     &(*(*self).ptr.pointer).value
+    /// }
+    /// ```
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        let Some(projection) = SmartPointerDerefPass::projection(tcx, instance, &body) else {
+            return (false, body);
+        };
+        debug!(function=?instance.name(), "SmartPointerDerefPass::transform");
+
+        let mut new_body = MutableBody::from(body);
+        new_body.clear_body(TerminatorKind::Return);
+        let mut source = SourceInstruction::Terminator { bb: 0 };
+        let value = Place { local: 1, projection };
+        new_body.assign_to(
+            Place::from(RETURN_LOCAL),
+            Rvalue::Ref(Region { kind: RegionKind::ReErased }, BorrowKind::Shared, value),
+            &mut source,
+            InsertPosition::Before,
+        );
+        (true, new_body.into())
+    }
+}
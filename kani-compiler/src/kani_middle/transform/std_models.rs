@@ -0,0 +1,159 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! This module replaces functions of the standard library that rely on system calls by the
+//! models in `kani::models` when the option of their model is enabled:
+//! - `--model-time` replaces the clocks of `std::time` by the nondeterministic models in
+//!   `kani::models::time`. Every other function of `std::time` that reads a clock, e.g.,
+//!   `Instant::elapsed`, calls `Instant::now()` or `SystemTime::now()`.
+//! - `--model-std-io` replaces the functions of `std::env` and `std::fs` that access environment
+//!   variables and files by the models in `kani::models::env` and `kani::models::fs`.
+//!
+//! Kani does not model these system calls, so a harness that reads a clock, or loads its
+//! configuration from the environment or from a file, would otherwise fail. Every model has the
+//! same generic parameters and arguments as the function that it replaces, so we call it with the
+//! generic and function arguments of that function.
+
+use crate::args::Arguments;
+use crate::kani_middle::kani_functions::KaniModel;
+use crate::kani_middle::resolve::resolve_fn;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::def_id::{CRATE_DEF_ID, DefId};
+use rustc_middle::ty::TyCtxt;
+use rustc_public::CrateDef;
+use rustc_public::mir::mono::Instance;
+use rustc_public::mir::{Body, Operand, Place, RETURN_LOCAL, TerminatorKind};
+use rustc_public::rustc_internal;
+use rustc_public::ty::FnDef;
+use std::collections::HashMap;
+use tracing::debug;
+
+/// The options that enable the models of the standard library.
+#[derive(Debug, Clone, Copy)]
+enum ModelOption {
+    Time,
+    StdIo,
+}
+
+impl ModelOption {
+    fn is_enabled(self, args: &Arguments) -> bool {
+        match self {
+            ModelOption::Time => args.model_time,
+            ModelOption::StdIo => args.model_std_io,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ModelOption::Time => "--model-time",
+            ModelOption::StdIo => "--model-std-io",
+        }
+    }
+}
+
+/// The functions that we replace, their models, and the option that enables the model.
+const MODELS: [(&str, KaniModel, ModelOption); 10] = [
+    ("::std::time::Instant::now", KaniModel::InstantNow, ModelOption::Time),
+    ("::std::time::SystemTime::now", KaniModel::SystemTimeNow, ModelOption::Time),
+    ("::std::env::var", KaniModel::EnvVar, ModelOption::StdIo),
+    ("::std::env::var_os", KaniModel::EnvVarOs, ModelOption::StdIo),
+    ("::std::env::set_var", KaniModel::EnvSetVar, ModelOption::StdIo),
+    ("::std::env::remove_var", KaniModel::EnvRemoveVar, ModelOption::StdIo),
+    ("::std::fs::read", KaniModel::FsRead, ModelOption::StdIo),
+    ("::std::fs::read_to_string", KaniModel::FsReadToString, ModelOption::StdIo),
+    ("::std::fs::write", KaniModel::FsWrite, ModelOption::StdIo),
+    ("::std::fs::remove_file", KaniModel::FsRemoveFile, ModelOption::StdIo),
+];
+
+/// Replace the body of the functions of the standard library that have a model by a call to
+/// their model.
+#[derive(Debug, Clone)]
+pub struct StdModelPass {
+    /// Map each function that we replace to its model and the option that enables it.
+    models: HashMap<DefId, (FnDef, ModelOption)>,
+}
+
+impl StdModelPass {
+    pub fn new(tcx: TyCtxt, queries: &QueryDb) -> Self {
+        let mut models = HashMap::new();
+        let kani_fns = queries.kani_functions();
+        for (orig, model, option) in MODELS {
+            if !option.is_enabled(queries.args()) {
+                continue;
+            }
+            let Some(model_fn) = kani_fns.get(&model.into()) else {
+                tcx.dcx().err(format!(
+                    "failed to find the model `{model:?}` of `{orig}` required by `{}`",
+                    option.name()
+                ));
+                continue;
+            };
+            match resolve_fn(tcx, CRATE_DEF_ID, orig) {
+                Ok(orig) => {
+                    models.insert(orig, (*model_fn, option));
+                }
+                Err(err) => {
+                    tcx.dcx().err(format!(
+                        "failed to resolve `{orig}` required by `{}`: {err}",
+                        option.name()
+                    ));
+                }
+            }
+        }
+        StdModelPass { models }
+    }
+}
+
+impl TransformPass for StdModelPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        !self.models.is_empty()
+    }
+
+    /// Replace the body of the functions by a call to their model, e.g.:
+    /// ```ignore
+    /// fn var<K: AsRef<OsStr>>(key: K) -> Result<String, VarError> {
+    ///     kani::models::env::var::<K>(key)
+    /// }
+    /// ```
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+        let Some((model, option)) = self.models.get(&def_id) else {
+            return (false, body);
+        };
+        debug!(function=?instance.name(), "StdModelPass::transform");
+
+        let Ok(model) = Instance::resolve(*model, &instance.args()) else {
+            tcx.dcx().err(format!(
+                "failed to resolve the model of `{}` required by `{}`",
+                instance.name(),
+                option.name()
+            ));
+            return (false, body);
+        };
+        let args =
+            (1..=body.arg_locals().len()).map(|arg| Operand::Move(Place::from(arg))).collect();
+        let mut new_body = MutableBody::from(body);
+        new_body.clear_body(TerminatorKind::Return);
+        let mut source = SourceInstruction::Terminator { bb: 0 };
+        new_body.insert_call(
+            &model,
+            &mut source,
+            InsertPosition::Before,
+            args,
+            Place::from(RETURN_LOCAL),
+        );
+        (true, new_body.into())
+    }
+}
@@ -3,15 +3,20 @@
 //! This module contains code related to the MIR-to-MIR pass that performs the
 //! stubbing of functions and methods.
 use crate::kani_middle::codegen_units::Stubs;
-use crate::kani_middle::stubbing::validate_stub_const;
-use crate::kani_middle::transform::body::{MutMirVisitor, MutableBody};
+use crate::kani_middle::stubbing::{check_layout_compatibility, validate_stub_const};
+use crate::kani_middle::transform::body::{
+    InsertPosition, MutMirVisitor, MutableBody, SourceInstruction,
+};
 use crate::kani_middle::transform::{TransformPass, TransformationType};
 use crate::kani_queries::QueryDb;
 use rustc_middle::ty::TyCtxt;
 use rustc_public::CrateDef;
 use rustc_public::mir::mono::Instance;
 use rustc_public::mir::visit::{Location, MirVisitor};
-use rustc_public::mir::{Body, ConstOperand, LocalDecl, Operand, Terminator, TerminatorKind};
+use rustc_public::mir::{
+    Body, CastKind, ConstOperand, LocalDecl, Mutability, Operand, Place, RETURN_LOCAL, Rvalue,
+    Terminator, TerminatorKind,
+};
 use rustc_public::rustc_internal;
 use rustc_public::ty::{FnDef, MirConst, RigidTy, TyKind};
 use std::collections::HashMap;
@@ -51,8 +56,16 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
         {
             let new_instance = Instance::resolve(*replace, &args).unwrap();
             debug!(from=?instance.name(), to=?new_instance.name(), "FnStubPass::transform");
-            if let Some(body) = FnStubValidator::validate(tcx, (fn_def, *replace), new_instance) {
-                return (true, body);
+            let stub = (fn_def, *replace);
+            if let Some(stub_body) = FnStubValidator::validate(tcx, stub, new_instance) {
+                if has_same_signature(&body, &stub_body) {
+                    return (true, stub_body);
+                }
+                // The stub is a method of a type model, which operates on the model type.
+                if let Some(new_body) = forward_to_model(tcx, stub, &body, new_instance, &stub_body)
+                {
+                    return (true, new_body);
+                }
             }
         }
         (false, body)
@@ -100,15 +113,21 @@ fn is_enabled(&self, query_db: &QueryDb) -> bool
     /// We should replace this with a visitor once rustc_public includes a mutable one.
     fn transform(&mut self, _tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
         trace!(function=?instance.name(), "transform");
-        let mut new_body = MutableBody::from(body);
-        let changed = false;
-        let locals = new_body.locals().to_vec();
-        let mut visitor = ExternFnStubVisitor { changed, locals, stubs: &self.stubs };
-        visitor.visit_body(&mut new_body);
-        (visitor.changed, new_body.into())
+        replace_fn_calls(body, &self.stubs)
     }
 }
 
+/// Replace the calls to the functions in `stubs`, and the function pointers to them, by their
+/// replacement.
+pub(crate) fn replace_fn_calls(body: Body, stubs: &Stubs) -> (bool, Body) {
+    let mut new_body = MutableBody::from(body);
+    let changed = false;
+    let locals = new_body.locals().to_vec();
+    let mut visitor = ExternFnStubVisitor { changed, locals, stubs };
+    visitor.visit_body(&mut new_body);
+    (visitor.changed, new_body.into())
+}
+
 impl ExternFnStubPass {
     /// Build the pass with the extern function stubs.
     ///
@@ -126,6 +145,91 @@ fn has_body(def: FnDef) -> bool {
     def.body().is_some()
 }
 
+fn has_same_signature(body: &Body, other: &Body) -> bool {
+    body.ret_local().ty == other.ret_local().ty
+        && body.arg_locals().len() == other.arg_locals().len()
+        && body.arg_locals().iter().zip(other.arg_locals()).all(|(arg, other)| arg.ty == other.ty)
+}
+
+/// Build a body for the original method of a stubbed type that calls the method of the model.
+///
+/// Arguments and the return value whose types differ between both methods are transmuted, which
+/// requires the original type and its model to have the same layout.
+pub(crate) fn forward_to_model(
+    tcx: TyCtxt,
+    stub: (FnDef, FnDef),
+    body: &Body,
+    new_instance: Instance,
+    stub_body: &Body,
+) -> Option<Body> {
+    let arg_tys = body
+        .arg_locals()
+        .iter()
+        .zip(stub_body.arg_locals())
+        .map(|(orig, model)| (orig.ty, model.ty))
+        .collect::<Vec<_>>();
+    let (orig_ret_ty, model_ret_ty) = (body.ret_local().ty, stub_body.ret_local().ty);
+    let errors = arg_tys
+        .iter()
+        .chain(std::iter::once(&(orig_ret_ty, model_ret_ty)))
+        .filter(|(orig, model)| orig != model)
+        .filter_map(|(orig, model)| check_layout_compatibility(*orig, *model).err())
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        tcx.dcx().span_err(
+            rustc_internal::internal(tcx, body.span),
+            format!(
+                "Cannot stub `{}` by `{}`.\n - {}",
+                stub.0.name(),
+                stub.1.name(),
+                errors.join("\n - ")
+            ),
+        );
+        return None;
+    }
+
+    let mut new_body = MutableBody::from(body.clone());
+    new_body.clear_body(TerminatorKind::Return);
+    let mut source = SourceInstruction::Terminator { bb: 0 };
+    let args = arg_tys
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (orig_ty, model_ty))| {
+            let arg = Operand::Move(Place::from(idx + 1));
+            if orig_ty == model_ty {
+                arg
+            } else {
+                let rvalue = Rvalue::Cast(CastKind::Transmute, arg, model_ty);
+                let local = new_body.insert_assignment(rvalue, &mut source, InsertPosition::Before);
+                Operand::Move(Place::from(local))
+            }
+        })
+        .collect();
+    if orig_ret_ty == model_ret_ty {
+        new_body.insert_call(
+            &new_instance,
+            &mut source,
+            InsertPosition::Before,
+            args,
+            Place::from(RETURN_LOCAL),
+        );
+    } else {
+        let span = source.span(new_body.blocks());
+        let ret = new_body.new_local(model_ret_ty, span, Mutability::Not);
+        new_body.insert_call(
+            &new_instance,
+            &mut source,
+            InsertPosition::Before,
+            args,
+            Place::from(ret),
+        );
+        let rvalue =
+            Rvalue::Cast(CastKind::Transmute, Operand::Move(Place::from(ret)), orig_ret_ty);
+        new_body.assign_to(Place::from(RETURN_LOCAL), rvalue, &mut source, InsertPosition::Before);
+    }
+    Some(new_body.into())
+}
+
 /// Validate that the body of the stub is valid for the given instantiation
 struct FnStubValidator<'a, 'tcx> {
     stub: (FnDef, FnDef),
@@ -0,0 +1,173 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Global pass, which does not modify bodies but lists the unsafe operations that are reachable
+//! from a harness when `--list-unsafe` is passed.
+//!
+//! This lets reviewers see which unsafe code a proof covers. We look at the bodies after they were
+//! stubbed and instrumented, since those are the bodies that are verified, but we ignore the
+//! functions of the Kani library and the calls to them, since they implement the verification.
+
+use crate::kani_middle::reachability::CallGraph;
+use crate::kani_middle::transform::GlobalPass;
+use crate::kani_queries::QueryDb;
+use kani_metadata::{UnsafeOperation, UnsafeOperationKind};
+use rustc_middle::ty::TyCtxt;
+use rustc_public::CrateDef;
+use rustc_public::mir::mono::{Instance, MonoItem};
+use rustc_public::mir::visit::{Location, PlaceContext, PlaceRef};
+use rustc_public::mir::{
+    CastKind, LocalDecl, MirVisitor, Operand, Place, ProjectionElem, Rvalue, Safety, Terminator,
+    TerminatorKind,
+};
+use rustc_public::ty::{RigidTy, Span, TyKind};
+use std::collections::BTreeSet;
+
+use super::BodyTransformation;
+
+/// The name of the crate that implements Kani's library.
+const KANI_CRATE: &str = "kani";
+
+/// List the unsafe operations of all reachable bodies.
+#[derive(Debug, Clone, Default)]
+pub struct UnsafeOperationsPass {
+    /// The operations found by the last run, sorted by function and location.
+    operations: Vec<UnsafeOperation>,
+}
+
+impl UnsafeOperationsPass {
+    /// Take the operations found by the last run.
+    pub fn take_operations(&mut self) -> Vec<UnsafeOperation> {
+        std::mem::take(&mut self.operations)
+    }
+}
+
+impl GlobalPass for UnsafeOperationsPass {
+    fn is_enabled(&self, query_db: &QueryDb) -> bool {
+        query_db.args().list_unsafe
+    }
+
+    fn transform(
+        &mut self,
+        tcx: TyCtxt,
+        _call_graph: &CallGraph,
+        _starting_items: &[MonoItem],
+        instances: Vec<Instance>,
+        transformer: &mut BodyTransformation,
+    ) -> bool {
+        // Different instances of a generic function have the same operations, so use a set.
+        let mut operations = BTreeSet::new();
+        for instance in instances {
+            if !instance.has_body() || instance.def.krate().name == KANI_CRATE {
+                continue;
+            }
+            let body = transformer.body(tcx, instance);
+            let mut visitor = UnsafeOperationsVisitor {
+                locals: body.locals(),
+                function: instance.def.name(),
+                operations: &mut operations,
+            };
+            visitor.visit_body(&body);
+        }
+        self.operations = operations.into_iter().collect();
+
+        // This pass just reads the MIR and thus never modifies it.
+        false
+    }
+}
+
+struct UnsafeOperationsVisitor<'a> {
+    locals: &'a [LocalDecl],
+    /// The name of the function whose body we visit.
+    function: String,
+    operations: &'a mut BTreeSet<UnsafeOperation>,
+}
+
+impl UnsafeOperationsVisitor<'_> {
+    fn record(&mut self, kind: UnsafeOperationKind, span: Span) {
+        let lines = span.get_lines();
+        let location = format!("{}:{}:{}", span.get_filename(), lines.start_line, lines.start_col);
+        self.operations.insert(UnsafeOperation { function: self.function.clone(), location, kind });
+    }
+}
+
+impl MirVisitor for UnsafeOperationsVisitor<'_> {
+    fn visit_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
+        if let Rvalue::Cast(CastKind::Transmute, ..) = rvalue {
+            self.record(UnsafeOperationKind::Transmute, location.span());
+        }
+        self.super_rvalue(rvalue, location)
+    }
+
+    fn visit_terminator(&mut self, term: &Terminator, location: Location) {
+        match &term.kind {
+            TerminatorKind::Call { func, .. } => {
+                let func_ty = func.ty(self.locals).unwrap();
+                let is_kani_fn = matches!(func_ty.kind(),
+                    TyKind::RigidTy(RigidTy::FnDef(def, _)) if def.krate().name == KANI_CRATE);
+                let is_unsafe = func_ty
+                    .kind()
+                    .fn_sig()
+                    .is_some_and(|sig| sig.skip_binder().safety == Safety::Unsafe);
+                if is_unsafe && !is_kani_fn {
+                    let kind = if is_transmute(self.locals, func) {
+                        UnsafeOperationKind::Transmute
+                    } else {
+                        UnsafeOperationKind::UnsafeCall
+                    };
+                    self.record(kind, location.span());
+                }
+            }
+            TerminatorKind::InlineAsm { .. } => {
+                self.record(UnsafeOperationKind::InlineAsm, location.span())
+            }
+            TerminatorKind::Goto { .. }
+            | TerminatorKind::SwitchInt { .. }
+            | TerminatorKind::Resume
+            | TerminatorKind::Abort
+            | TerminatorKind::Return
+            | TerminatorKind::Unreachable
+            | TerminatorKind::Drop { .. }
+            | TerminatorKind::Assert { .. } => {}
+        }
+        self.super_terminator(term, location)
+    }
+
+    fn visit_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+        for (idx, elem) in place.projection.iter().enumerate() {
+            let place_ref = PlaceRef { local: place.local, projection: &place.projection[..idx] };
+            match elem {
+                ProjectionElem::Deref => {
+                    if place_ref.ty(self.locals).unwrap().kind().is_raw_ptr() {
+                        self.record(UnsafeOperationKind::RawPointerDeref, location.span());
+                    }
+                }
+                ProjectionElem::Field(..) => {
+                    // Assigning to a field of a union is safe, but reading it is not.
+                    if place_ref.ty(self.locals).unwrap().kind().is_union()
+                        && (!ptx.is_mutating() || place.projection.len() > idx + 1)
+                    {
+                        self.record(UnsafeOperationKind::UnionAccess, location.span());
+                    }
+                }
+                ProjectionElem::Index(_)
+                | ProjectionElem::ConstantIndex { .. }
+                | ProjectionElem::Subslice { .. }
+                | ProjectionElem::Downcast(_)
+                | ProjectionElem::OpaqueCast(_)
+                | ProjectionElem::Subtype(_) => {}
+            }
+        }
+        self.super_place(place, ptx, location)
+    }
+}
+
+/// Whether `func` is one of the transmute intrinsics, which MIR usually lowers to casts.
+fn is_transmute(locals: &[LocalDecl], func: &Operand) -> bool {
+    let TyKind::RigidTy(RigidTy::FnDef(def, args)) = func.ty(locals).unwrap().kind() else {
+        return false;
+    };
+    Instance::resolve(def, &args).is_ok_and(|instance| {
+        matches!(instance.intrinsic_name().as_deref(), Some("transmute" | "transmute_unchecked"))
+    })
+}
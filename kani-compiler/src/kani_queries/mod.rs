@@ -6,9 +6,11 @@
 use crate::kani_middle::kani_functions::{
     KaniFunction, find_kani_functions, validate_kani_functions,
 };
+use crate::kani_middle::transform::contracts::verified_dependency_contracts;
+use rustc_middle::ty::TyCtxt;
 use rustc_public::ty::FnDef;
 use std::cell::OnceCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
 /// This structure should only be used behind a synchronized reference or a snapshot.
@@ -18,6 +20,7 @@
 pub struct QueryDb {
     args: Option<Arguments>,
     kani_functions: OnceCell<HashMap<KaniFunction, FnDef>>,
+    verified_contracts: OnceCell<HashSet<FnDef>>,
 }
 
 impl QueryDb {
@@ -62,4 +65,14 @@ pub fn kani_functions(&self) -> &HashMap<KaniFunction, FnDef> {
             })
         }
     }
+
+    /// Return the functions of dependencies whose contracts were verified according to the
+    /// contract manifests passed by the driver.
+    ///
+    /// The manifests are only read the first time this is called, and the same restrictions as
+    /// [QueryDb::kani_functions] apply to the cache.
+    pub fn verified_dependency_contracts(&self, tcx: TyCtxt) -> &HashSet<FnDef> {
+        self.verified_contracts
+            .get_or_init(|| verified_dependency_contracts(tcx, &self.args().verified_contracts))
+    }
 }
@@ -0,0 +1,173 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `--affected-by`, which only verifies the harnesses that can reach the code changed
+//! by a git range, e.g., in the CI job of a pull request.
+//!
+//! The compiler records the functions that each harness reaches, with the lines that define them.
+//! A harness is affected if `git diff` reports a change in one of these lines. Since the lines are
+//! the ones that Kani compiled, the range must end with the working tree or with the commit that
+//! is checked out.
+
+use anyhow::{Context, Result, bail};
+use kani_metadata::HarnessMetadata;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The lines that changed in each file, as inclusive ranges of lines of the new version.
+type ChangedLines = HashMap<PathBuf, Vec<(usize, usize)>>;
+
+/// Split `harnesses` into the ones that can reach the code changed by `range`, and the others.
+pub fn select_affected<'a>(
+    harnesses: Vec<&'a HarnessMetadata>,
+    range: &str,
+) -> Result<(Vec<&'a HarnessMetadata>, Vec<&'a HarnessMetadata>)> {
+    let root = git(&["rev-parse", "--show-toplevel"], None)?;
+    // The compiler records canonical paths.
+    let root = Path::new(root.trim()).canonicalize()?;
+    let diff = git(
+        &[
+            "diff",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+            range,
+            "--",
+        ],
+        Some(&root),
+    )?;
+    let changed = parse_diff(&diff, &root);
+    Ok(harnesses.into_iter().partition(|harness| is_affected(harness, &changed)))
+}
+
+/// Print the harnesses that aren't verified because they don't reach the code changed by `range`.
+pub fn print_unchanged(unchanged: &[&HarnessMetadata], range: &str) {
+    if unchanged.is_empty() {
+        return;
+    }
+    println!(
+        "Skipped {} harness(es) that don't reach the code changed in `{range}`:",
+        unchanged.len()
+    );
+    for harness in unchanged {
+        println!(" - {}", harness.pretty_name);
+    }
+}
+
+fn git(args: &[&str], dir: Option<&Path>) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.args(args);
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+    let output = cmd.output().context("Failed to invoke git")?;
+    if !output.status.success() {
+        bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Collect the lines that changed in each file of a diff without context lines.
+fn parse_diff(diff: &str, root: &Path) -> ChangedLines {
+    let mut changed = ChangedLines::new();
+    let mut file = None;
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            // A deleted file is `/dev/null` in the new version. It can't define a function that a
+            // harness reaches.
+            file =
+                path.strip_prefix("b/").map(|path| root.join(path.split('/').collect::<PathBuf>()));
+        } else if let Some(hunk) = line.strip_prefix("@@ ")
+            && let Some(file) = &file
+            && let Some(lines) = parse_hunk(hunk)
+        {
+            changed.entry(file.clone()).or_default().push(lines);
+        }
+    }
+    changed
+}
+
+/// The lines of the new version that the hunk with the given header, e.g.,
+/// `-10,2 +12,3 @@ fn foo() {`, changes. A hunk that only removes lines changes the lines around
+/// the ones it removes.
+fn parse_hunk(hunk: &str) -> Option<(usize, usize)> {
+    let new = hunk.split_whitespace().find_map(|part| part.strip_prefix('+'))?;
+    let (start, count) = match new.split_once(',') {
+        Some((start, count)) => (start.parse().ok()?, count.parse().ok()?),
+        None => (new.parse().ok()?, 1),
+    };
+    if count == 0 { Some((start, start + 1)) } else { Some((start, start + count - 1)) }
+}
+
+fn is_affected(harness: &HarnessMetadata, changed: &ChangedLines) -> bool {
+    harness.reachable_functions.iter().any(|function| {
+        changed.get(Path::new(&function.file)).is_some_and(|lines| {
+            lines
+                .iter()
+                .any(|(start, end)| *start <= function.end_line && function.start_line <= *end)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::tests::mock_proof_harness;
+    use kani_metadata::ReachableFunction;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -10,2 +10,3 @@ fn add(a: u8, b: u8) -> u8 {
+@@ -30 +31,0 @@ fn sub(a: u8, b: u8) -> u8 {
+diff --git a/src/old.rs b/src/old.rs
+deleted file mode 100644
+--- a/src/old.rs
++++ /dev/null
+@@ -1,5 +0,0 @@
+";
+
+    fn reaching(name: &str, file: &str, lines: (usize, usize)) -> HarnessMetadata {
+        let mut harness = mock_proof_harness(name, None, None, None);
+        harness.reachable_functions = vec![ReachableFunction {
+            file: file.to_string(),
+            start_line: lines.0,
+            end_line: lines.1,
+            name: "function".to_string(),
+        }];
+        harness
+    }
+
+    #[test]
+    fn check_parse_hunk() {
+        assert_eq!(parse_hunk("-10,2 +10,3 @@ fn add() {"), Some((10, 12)));
+        assert_eq!(parse_hunk("-4 +4 @@"), Some((4, 4)));
+        assert_eq!(parse_hunk("-30 +31,0 @@"), Some((31, 32)));
+        assert_eq!(parse_hunk("-30 @@"), None);
+    }
+
+    #[test]
+    fn check_parse_diff() {
+        let changed = parse_diff(DIFF, Path::new("/repo"));
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[&Path::new("/repo").join("src").join("lib.rs")], [(10, 12), (31, 32)]);
+    }
+
+    #[test]
+    fn check_is_affected() {
+        let changed = parse_diff(DIFF, Path::new("/repo"));
+        assert!(is_affected(&reaching("check_add", "/repo/src/lib.rs", (8, 10)), &changed));
+        assert!(is_affected(&reaching("check_sub", "/repo/src/lib.rs", (32, 40)), &changed));
+        assert!(!is_affected(&reaching("check_mul", "/repo/src/lib.rs", (14, 30)), &changed));
+        assert!(!is_affected(&reaching("check_other", "/repo/src/other.rs", (1, 50)), &changed));
+        assert!(!is_affected(&mock_proof_harness("check_none", None, None, None), &changed));
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Programmatic entry point for running Kani from other Rust tools.
+//!
+//! Orchestration tools and test frameworks can use [kani_verify] to run verification and inspect
+//! structured results instead of invoking `cargo kani` and scraping its output.
+//!
+//! ```no_run
+//! use kani_driver::api::{VerificationConfig, kani_verify};
+//!
+//! let config = VerificationConfig::cargo("my-crate/Cargo.toml")
+//!     .install_dir("/home/user/.kani/kani-0.65.0")
+//!     .harness("check_parser")
+//!     .arg("--default-unwind")
+//!     .arg("8");
+//! let report = kani_verify(&config).unwrap();
+//! for harness in &report.harnesses {
+//!     println!("{}: {:?}", harness.name, harness.status);
+//! }
+//! ```
+//!
+//! Configuration options that are not exposed directly can be given with
+//! [VerificationConfig::arg], which accepts any of the `kani` command line options. Subcommands
+//! are not supported.
+
+use crate::args::{CargoKaniArgs, StandaloneArgs, ValidateArgs};
+use crate::args_toml::join_args;
+use crate::call_cbmc::{ExitStatus, VerificationStatus};
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::harness_runner::{HarnessResult, HarnessRunner};
+use crate::project::{self, Project};
+use crate::session::{self, KaniSession};
+use anyhow::{Result, bail};
+use clap::Parser;
+use serde::Serialize;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The project to be verified.
+#[derive(Clone, Debug)]
+enum ProjectInput {
+    /// A cargo package or workspace identified by its manifest path.
+    Cargo(PathBuf),
+    /// A single Rust file verified with standalone `kani`.
+    Standalone(PathBuf),
+}
+
+/// Configuration for a verification run started with [kani_verify].
+#[derive(Clone, Debug)]
+pub struct VerificationConfig {
+    input: ProjectInput,
+    harnesses: Vec<String>,
+    extra_args: Vec<OsString>,
+    install_dir: Option<PathBuf>,
+    quiet: bool,
+}
+
+impl VerificationConfig {
+    /// Verify the cargo project with the given `Cargo.toml`.
+    /// Options from `[package.metadata.kani]` are taken into account as they are for `cargo kani`.
+    pub fn cargo(manifest_path: impl Into<PathBuf>) -> Self {
+        Self::new(ProjectInput::Cargo(manifest_path.into()))
+    }
+
+    /// Verify a single Rust file, like the standalone `kani` command does.
+    pub fn standalone(input: impl Into<PathBuf>) -> Self {
+        Self::new(ProjectInput::Standalone(input.into()))
+    }
+
+    fn new(input: ProjectInput) -> Self {
+        VerificationConfig {
+            input,
+            harnesses: vec![],
+            extra_args: vec![],
+            install_dir: None,
+            quiet: true,
+        }
+    }
+
+    /// Only verify harnesses that match this filter. Can be called multiple times.
+    pub fn harness(mut self, filter: impl Into<String>) -> Self {
+        self.harnesses.push(filter.into());
+        self
+    }
+
+    /// Pass an extra command line option, e.g. `--default-unwind`, to Kani.
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.extra_args.push(arg.into());
+        self
+    }
+
+    /// Pass extra command line options to Kani.
+    pub fn args<I, T>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        self.extra_args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// The directory of the Kani installation to use, e.g. `~/.kani/kani-<VERSION>`.
+    ///
+    /// This is required unless the current executable lives in the Kani installation `bin`
+    /// folder. Note that a process can only use one Kani installation.
+    pub fn install_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.install_dir = Some(dir.into());
+        self
+    }
+
+    /// Whether Kani should print its regular output to the terminal. Output is suppressed by
+    /// default.
+    pub fn print_output(mut self, print: bool) -> Self {
+        self.quiet = !print;
+        self
+    }
+
+    /// Build the equivalent command line for the given binary name.
+    fn command_line(&self, bin: &str) -> Vec<OsString> {
+        let mut args: Vec<OsString> = vec![bin.into()];
+        match &self.input {
+            ProjectInput::Cargo(manifest) => {
+                args.push("--manifest-path".into());
+                args.push(manifest.into());
+            }
+            ProjectInput::Standalone(input) => args.push(input.into()),
+        }
+        if self.quiet {
+            args.push("--quiet".into());
+        }
+        for harness in &self.harnesses {
+            args.push("--harness".into());
+            args.push(harness.into());
+        }
+        // Keep these last since `--cbmc-args` consumes everything after it.
+        args.extend(self.extra_args.iter().cloned());
+        args
+    }
+}
+
+/// The result of verifying a project.
+#[derive(Clone, Debug, Serialize)]
+pub struct VerificationReport {
+    /// One entry per harness that was verified.
+    pub harnesses: Vec<HarnessReport>,
+}
+
+impl VerificationReport {
+    /// Whether every harness was successfully verified.
+    pub fn is_success(&self) -> bool {
+        self.harnesses.iter().all(|harness| harness.status == HarnessStatus::Success)
+    }
+}
+
+/// The verification result of one harness.
+#[derive(Clone, Debug, Serialize)]
+pub struct HarnessReport {
+    /// The fully qualified name of the harness.
+    pub name: String,
+    /// The crate where the harness is defined.
+    pub crate_name: String,
+    /// Whether this harness was automatically generated.
+    pub is_automatically_generated: bool,
+    /// The overall verification status. This takes `#[kani::should_panic]` into account.
+    pub status: HarnessStatus,
+    /// The status of each property checked in this harness.
+    pub properties: Vec<PropertyReport>,
+    /// Why verification did not produce any results (e.g. a timeout), if that was the case.
+    pub error: Option<String>,
+    /// How long the verification took.
+    pub runtime: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum HarnessStatus {
+    Success,
+    Failure,
+    /// No execution reaches the end of the harness. Only reported with `--check-vacuity`.
+    Vacuous,
+}
+
+/// The result of a single property check.
+#[derive(Clone, Debug, Serialize)]
+pub struct PropertyReport {
+    /// The unique name of the property, e.g. `foo.assertion.1`.
+    pub name: String,
+    /// The property class, e.g. `assertion` or `cover`.
+    pub class: String,
+    pub description: String,
+    pub status: PropertyStatus,
+    /// The source location of the check, if known.
+    pub location: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum PropertyStatus {
+    Success,
+    Failure,
+    Unreachable,
+    Undetermined,
+    /// A `kani::cover` property that is satisfiable.
+    Satisfied,
+    /// A `kani::cover` property that is not satisfiable.
+    Unsatisfiable,
+    /// A coverage region that was reached.
+    Covered,
+    /// A coverage region that was not reached.
+    Uncovered,
+    Unknown,
+}
+
+impl From<CheckStatus> for PropertyStatus {
+    fn from(status: CheckStatus) -> Self {
+        match status {
+            CheckStatus::Success => PropertyStatus::Success,
+            CheckStatus::Failure => PropertyStatus::Failure,
+            CheckStatus::Unreachable => PropertyStatus::Unreachable,
+            CheckStatus::Undetermined => PropertyStatus::Undetermined,
+            CheckStatus::Satisfied => PropertyStatus::Satisfied,
+            CheckStatus::Unsatisfiable => PropertyStatus::Unsatisfiable,
+            CheckStatus::Covered => PropertyStatus::Covered,
+            CheckStatus::Uncovered => PropertyStatus::Uncovered,
+            CheckStatus::Unknown => PropertyStatus::Unknown,
+        }
+    }
+}
+
+impl From<&Property> for PropertyReport {
+    fn from(property: &Property) -> Self {
+        let location = &property.source_location;
+        PropertyReport {
+            name: property.property_name(),
+            class: property.property_class(),
+            description: property.description.clone(),
+            status: property.status.into(),
+            location: (!location.is_missing()).then(|| location.to_string()),
+        }
+    }
+}
+
+impl HarnessReport {
+    fn new(harness_result: &HarnessResult) -> Self {
+        let result = &harness_result.result;
+        let (properties, error) = match &result.results {
+            Ok(properties) => (properties.iter().map(PropertyReport::from).collect(), None),
+            Err(ExitStatus::Timeout) => (vec![], Some("CBMC timed out".to_string())),
+            Err(ExitStatus::OutOfMemory) => (vec![], Some("CBMC ran out of memory".to_string())),
+            Err(ExitStatus::Other(status)) => {
+                (vec![], Some(format!("CBMC failed with status {status}")))
+            }
+        };
+        HarnessReport {
+            name: harness_result.harness.pretty_name.clone(),
+            crate_name: harness_result.harness.crate_name.clone(),
+            is_automatically_generated: harness_result.harness.is_automatically_generated,
+            status: match result.status {
+                VerificationStatus::Success => HarnessStatus::Success,
+                VerificationStatus::Failure => HarnessStatus::Failure,
+                VerificationStatus::Vacuous => HarnessStatus::Vacuous,
+            },
+            properties,
+            error,
+            runtime: result.runtime,
+        }
+    }
+}
+
+/// Build and verify the project described by `config`.
+///
+/// Unlike the `kani` binary, this function does not print a summary or exit the process when a
+/// harness fails. Failures are reported in the returned [VerificationReport] instead, and an
+/// error is only returned if verification could not be performed at all (e.g. compilation
+/// errors or invalid options).
+pub fn kani_verify(config: &VerificationConfig) -> Result<VerificationReport> {
+    if let Some(install_dir) = &config.install_dir {
+        session::set_install_dir(install_dir)?;
+    }
+
+    let (session, project) = match &config.input {
+        ProjectInput::Cargo(_) => {
+            let input_args = join_args(config.command_line("cargo-kani"))?;
+            let args = CargoKaniArgs::try_parse_from(input_args)?;
+            args.validate()?;
+            if args.command.is_some() {
+                bail!("Kani subcommands are not supported by `kani_verify`");
+            }
+            let mut session = KaniSession::new(args.verify_opts)?;
+            let project = project::cargo_project(&mut session, false)?;
+            (session, project)
+        }
+        ProjectInput::Standalone(input) => {
+            let args = StandaloneArgs::try_parse_from(config.command_line("kani"))?;
+            args.validate()?;
+            if args.command.is_some() {
+                bail!("Kani subcommands are not supported by `kani_verify`");
+            }
+            let session = KaniSession::new(args.verify_opts)?;
+            let project = project::standalone_project(input, args.crate_name, &session)?;
+            (session, project)
+        }
+    };
+
+    if session.args.only_codegen {
+        return Ok(VerificationReport { harnesses: vec![] });
+    }
+    verify(&session, &project)
+}
+
+fn verify(session: &KaniSession, project: &Project) -> Result<VerificationReport> {
+    let harnesses = session.determine_targets(project.get_all_harnesses())?;
+    let runner = HarnessRunner { sess: session, project };
+    let results = runner.check_all_harnesses(&harnesses)?;
+    Ok(VerificationReport { harnesses: results.iter().map(HarnessReport::new).collect() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_standalone_command_line() {
+        let config = VerificationConfig::standalone("lib.rs").harness("check_a").args([
+            "-Z",
+            "unstable-options",
+            "--cbmc-args",
+            "--trace",
+        ]);
+        let args = StandaloneArgs::try_parse_from(config.command_line("kani")).unwrap();
+        assert_eq!(args.input, Some(PathBuf::from("lib.rs")));
+        assert!(args.verify_opts.common_args.quiet);
+        assert_eq!(args.verify_opts.harnesses, vec!["check_a"]);
+        assert_eq!(args.verify_opts.cbmc_args, vec!["--trace"]);
+    }
+
+    #[test]
+    fn check_cargo_command_line() {
+        let config = VerificationConfig::cargo("pkg/Cargo.toml").print_output(true);
+        let args = CargoKaniArgs::try_parse_from(config.command_line("cargo-kani")).unwrap();
+        assert_eq!(args.verify_opts.cargo.manifest_path, Some(PathBuf::from("pkg/Cargo.toml")));
+        assert!(!args.verify_opts.common_args.quiet);
+        assert!(args.command.is_none());
+    }
+}
@@ -5,10 +5,10 @@
 use std::path::PathBuf;
 
 use crate::args::list_args::Format;
-use crate::args::{ValidateArgs, VerificationArgs, validate_std_path};
+use crate::args::{Timeout, ValidateArgs, VerificationArgs, validate_std_path};
 use crate::util::warning;
 use clap::{Error, Parser, error::ErrorKind};
-use kani_metadata::UnstableFeature;
+use kani_metadata::{DEFAULT_EXTERN_C_BUFFER_LEN, UnstableFeature};
 use regex::Regex;
 
 #[derive(Debug, Parser)]
@@ -23,6 +23,42 @@ pub struct CommonAutoharnessArgs {
     #[arg(long = "exclude-pattern", num_args(1), value_name = "PATTERN")]
     pub exclude_pattern: Vec<String>,
 
+    /// Only create automatic harnesses for functions that are not defined in a file whose path matches the given glob pattern,
+    /// e.g., `**/generated/*.rs`. `*` and `?` do not match `/`, while `**/` matches any number of directories.
+    /// The pattern must match the entire path of the file, as given to the compiler.
+    #[arg(long = "exclude-file-pattern", num_args(1), value_name = "GLOB")]
+    pub exclude_file_pattern: Vec<String>,
+
+    /// Only create automatic harnesses for functions that are written by hand,
+    /// i.e., skip functions generated by macro expansions such as `#[derive]` implementations.
+    #[arg(long)]
+    pub exclude_expanded: bool,
+
+    /// Also create automatic harnesses for `#[no_mangle] extern "C"` functions that take raw pointer arguments.
+    /// Each pointer argument is either null or points to a valid allocation of a nondeterministic
+    /// number of elements, between 1 and `--extern-c-buffer-len`.
+    #[arg(long)]
+    pub extern_c: bool,
+
+    /// The maximum number of elements of the allocations that pointer arguments point to. Requires --extern-c.
+    #[arg(long, default_value_t = DEFAULT_EXTERN_C_BUFFER_LEN, requires = "extern_c", value_name = "N")]
+    pub extern_c_buffer_len: usize,
+
+    /// Also create automatic harnesses that check that the `Eq`, `Ord`, `Hash` and `From`
+    /// implementations of the crate's types satisfy the laws documented by the standard library,
+    /// e.g., that `Ord` is antisymmetric and transitive, and that equal values have equal hashes.
+    /// A `From` implementation is checked against the inverse `TryFrom` implementation, if any.
+    #[arg(long)]
+    pub traits: bool,
+
+    /// The wall-clock budget for verifying all automatic harnesses, with optional suffix ('s': seconds,
+    /// 'm': minutes, 'h': hours). Default is seconds. The harnesses of all packages are verified
+    /// together, from the smallest function to the largest. The verifications that are still
+    /// running when the budget runs out time out, and the functions whose verification didn't
+    /// start are reported as skipped.
+    #[arg(long, value_name = "TIMEOUT")]
+    pub total_timeout: Option<Timeout>,
+
     /// Run the `list` subcommand after generating the automatic harnesses. Note that this option implies --only-codegen.
     #[arg(long)]
     pub list: bool,
@@ -32,6 +68,13 @@ pub struct CommonAutoharnessArgs {
     pub format: Format,
 }
 
+impl Default for CommonAutoharnessArgs {
+    /// The options of the `autoharness` subcommand when the user doesn't give any.
+    fn default() -> Self {
+        CommonAutoharnessArgs::parse_from(["autoharness"])
+    }
+}
+
 /// Automatically verify functions in a crate.
 #[derive(Debug, Parser)]
 pub struct CargoAutoharnessArgs {
@@ -64,6 +107,30 @@ pub struct StandaloneAutoharnessArgs {
     pub verify_opts: VerificationArgs,
 }
 
+/// Translate a glob pattern over file paths into an equivalent regular expression.
+pub fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
 impl ValidateArgs for CommonAutoharnessArgs {
     fn validate(&self) -> Result<(), Error> {
         // Error gracefully if a pattern contains whitespace, since rustc_driver argument will panic later if we try to pass this back,
@@ -84,6 +151,25 @@ fn validate(&self) -> Result<(), Error> {
             }
         }
 
+        if let Some(pattern) =
+            self.exclude_file_pattern.iter().find(|pattern| pattern.contains(char::is_whitespace))
+        {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!(
+                    "The `--exclude-file-pattern` option does not support patterns with whitespace, but got `{pattern}`. \
+                        Use `?` to match any character instead."
+                ),
+            ));
+        }
+
+        if self.extern_c_buffer_len == 0 {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                "The `--extern-c-buffer-len` option must be greater than zero.",
+            ));
+        }
+
         for include_pattern in self.include_pattern.iter() {
             for exclude_pattern in self.exclude_pattern.iter() {
                 // Check if include pattern contains exclude pattern
@@ -193,3 +279,26 @@ fn validate(&self) -> Result<(), Error> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob_matches(glob: &str, path: &str) -> bool {
+        Regex::new(&glob_to_regex(glob)).unwrap().is_match(path)
+    }
+
+    #[test]
+    fn check_glob_to_regex() {
+        assert!(glob_matches("src/generated.rs", "src/generated.rs"));
+        assert!(!glob_matches("src/generated.rs", "src/generated_rs"));
+        assert!(glob_matches("src/*.rs", "src/lib.rs"));
+        assert!(!glob_matches("src/*.rs", "src/proto/lib.rs"));
+        assert!(glob_matches("src/**/*.rs", "src/lib.rs"));
+        assert!(glob_matches("src/**/*.rs", "src/proto/v1/lib.rs"));
+        assert!(glob_matches("**/out/*.rs", "/home/user/target/debug/build/foo/out/proto.rs"));
+        assert!(glob_matches("**/out/*.rs", "out/proto.rs"));
+        assert!(glob_matches("src/lib.r?", "src/lib.rs"));
+        assert!(!glob_matches("src/lib.r?", "src/lib.r/"));
+    }
+}
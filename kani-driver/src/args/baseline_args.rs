@@ -0,0 +1,96 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the baseline subcommand
+
+use std::path::PathBuf;
+
+use crate::args::{ValidateArgs, VerificationArgs};
+use clap::{Error, Parser, ValueEnum, error::ErrorKind};
+use kani_metadata::UnstableFeature;
+
+/// The default for `--baseline-file`.
+const DEFAULT_BASELINE_FILE: &str = "kani-baseline.json";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum BaselineAction {
+    /// Record the status of every property in the baseline file.
+    Record,
+    /// Report the properties whose status changed since the baseline was recorded, and fail if any
+    /// property fails that didn't fail in the baseline.
+    Compare,
+}
+
+#[derive(Debug, Parser)]
+pub struct CommonBaselineArgs {
+    /// Whether to record a new baseline, or to compare the results against the recorded one.
+    #[arg(value_enum)]
+    pub action: BaselineAction,
+
+    /// The file that stores the baseline.
+    #[arg(long, default_value = DEFAULT_BASELINE_FILE, value_name = "PATH")]
+    pub baseline_file: PathBuf,
+}
+
+/// Verify the harnesses of a package, and record or compare the status of their properties.
+#[derive(Debug, Parser)]
+pub struct CargoBaselineArgs {
+    #[command(flatten)]
+    pub common_baseline_args: CommonBaselineArgs,
+
+    #[command(flatten)]
+    pub verify_opts: VerificationArgs,
+}
+
+/// Verify the harnesses of a file, and record or compare the status of their properties.
+#[derive(Debug, Parser)]
+pub struct StandaloneBaselineArgs {
+    #[command(flatten)]
+    pub common_baseline_args: CommonBaselineArgs,
+
+    /// Rust crate's top file location.
+    #[arg(required = true)]
+    pub input: PathBuf,
+
+    #[arg(long, hide = true)]
+    pub crate_name: Option<String>,
+
+    #[command(flatten)]
+    pub verify_opts: VerificationArgs,
+}
+
+fn check_unstable(verify_opts: &VerificationArgs) -> Result<(), Error> {
+    if !verify_opts.common_args.unstable_features.contains(UnstableFeature::UnstableOptions) {
+        return Err(Error::raw(
+            ErrorKind::MissingRequiredArgument,
+            format!(
+                "The `baseline` subcommand is unstable and requires -Z {}",
+                UnstableFeature::UnstableOptions
+            ),
+        ));
+    }
+    Ok(())
+}
+
+impl ValidateArgs for CargoBaselineArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.verify_opts.validate()?;
+        check_unstable(&self.verify_opts)
+    }
+}
+
+impl ValidateArgs for StandaloneBaselineArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.verify_opts.validate()?;
+        check_unstable(&self.verify_opts)?;
+        if !self.input.is_file() {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!(
+                    "Invalid argument: Input invalid. `{}` is not a regular file.",
+                    self.input.display()
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -11,7 +11,7 @@
 /// These generally have to do with selection of packages or activation of features.
 /// These do not (currently) include cargo args that kani pays special attention to:
 /// for instance, we keep `--tests` and `--target-dir` elsewhere.
-#[derive(Debug, Default, clap::Args)]
+#[derive(Clone, Debug, Default, clap::Args)]
 #[clap(next_help_heading = "Cargo Common Options")]
 pub struct CargoCommonArgs {
     /// Activate all package features
@@ -99,7 +99,7 @@ fn validate(&self) -> Result<(), Error> {
 /// Arguments that cargo Kani supports to select build / verification / test target.
 /// See <https://doc.rust-lang.org/cargo/commands/cargo-test.html#target-selection> for more
 /// details.
-#[derive(Debug, Default, clap::Args)]
+#[derive(Clone, Debug, Default, clap::Args)]
 #[clap(next_help_heading = "Cargo Target Options")]
 pub struct CargoTargetArgs {
     /// Check only the specified binary target.
@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the clean subcommand
+
+use crate::args::{CommonArgs, ValidateArgs};
+use clap::{Error, Parser};
+
+/// Remove the artifacts that Kani generated for a package, i.e., the `kani` directory inside the
+/// target directory. Unlike `cargo clean`, this keeps the artifacts of regular builds.
+#[derive(Debug, Parser)]
+pub struct CargoCleanArgs {
+    #[command(flatten)]
+    pub common_args: CommonArgs,
+}
+
+impl ValidateArgs for CargoCleanArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.common_args.validate()
+    }
+}
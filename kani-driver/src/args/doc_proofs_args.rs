@@ -0,0 +1,78 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the doc-proofs subcommand
+
+use std::path::PathBuf;
+
+use crate::args::{ValidateArgs, VerificationArgs};
+use clap::{Error, Parser, ValueEnum, error::ErrorKind};
+use kani_metadata::UnstableFeature;
+
+/// Generate an index of the harnesses and contracts of a package, with their documentation, the
+/// functions that they cover, and their latest verification status.
+#[derive(Debug, Parser)]
+pub struct CargoDocProofsArgs {
+    /// The results of earlier runs, written by `--statistics` or `kani merge-results`, which
+    /// provide the status of the harnesses. If a harness appears in more than one file, its latest
+    /// result wins. Harnesses without results are reported as not verified.
+    #[arg(long, value_name = "PATH", num_args(1..))]
+    pub results: Vec<PathBuf>,
+
+    /// Format of the index.
+    #[arg(long, default_value = "html")]
+    pub format: DocFormat,
+
+    /// Write the index to the given file instead of `kani-proofs.html` or `kani-proofs.md`.
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub verify_opts: VerificationArgs,
+}
+
+/// Output formats available for the subcommand.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, strum_macros::Display)]
+#[strum(serialize_all = "kebab-case")]
+pub enum DocFormat {
+    /// A self-contained HTML page.
+    Html,
+    /// A Markdown document.
+    Markdown,
+}
+
+impl DocFormat {
+    /// The extension of the files in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DocFormat::Html => "html",
+            DocFormat::Markdown => "md",
+        }
+    }
+}
+
+impl ValidateArgs for CargoDocProofsArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.verify_opts.validate()?;
+        if !self
+            .verify_opts
+            .common_args
+            .unstable_features
+            .contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `doc-proofs` subcommand is unstable and requires -Z {}",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+        if let Some(path) = self.results.iter().find(|path| !path.is_file()) {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!("Invalid argument: `{}` is not a regular file.", path.display()),
+            ));
+        }
+        Ok(())
+    }
+}
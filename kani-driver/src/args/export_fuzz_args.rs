@@ -0,0 +1,86 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the export-fuzz subcommand
+
+use std::path::PathBuf;
+
+use crate::args::{ValidateArgs, VerificationArgs};
+use clap::{Error, Parser, error::ErrorKind};
+use kani_metadata::UnstableFeature;
+
+#[derive(Debug, Parser)]
+pub struct CommonExportFuzzArgs {
+    /// Write the test to this file instead of the standard output.
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+/// Translate a harness of a package into a `proptest` test.
+#[derive(Debug, Parser)]
+pub struct CargoExportFuzzArgs {
+    #[command(flatten)]
+    pub common_export_fuzz_args: CommonExportFuzzArgs,
+
+    #[command(flatten)]
+    pub verify_opts: VerificationArgs,
+}
+
+/// Translate a harness of a file into a `proptest` test.
+#[derive(Debug, Parser)]
+pub struct StandaloneExportFuzzArgs {
+    #[command(flatten)]
+    pub common_export_fuzz_args: CommonExportFuzzArgs,
+
+    /// Rust crate's top file location.
+    #[arg(required = true)]
+    pub input: PathBuf,
+
+    #[arg(long, hide = true)]
+    pub crate_name: Option<String>,
+
+    #[command(flatten)]
+    pub verify_opts: VerificationArgs,
+}
+
+fn check_args(verify_opts: &VerificationArgs) -> Result<(), Error> {
+    if !verify_opts.common_args.unstable_features.contains(UnstableFeature::UnstableOptions) {
+        return Err(Error::raw(
+            ErrorKind::MissingRequiredArgument,
+            format!(
+                "The `export-fuzz` subcommand is unstable and requires -Z {}",
+                UnstableFeature::UnstableOptions
+            ),
+        ));
+    }
+    if verify_opts.harnesses.len() != 1 {
+        return Err(Error::raw(
+            ErrorKind::MissingRequiredArgument,
+            "The `export-fuzz` subcommand requires exactly one `--harness`",
+        ));
+    }
+    Ok(())
+}
+
+impl ValidateArgs for CargoExportFuzzArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.verify_opts.validate()?;
+        check_args(&self.verify_opts)
+    }
+}
+
+impl ValidateArgs for StandaloneExportFuzzArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.verify_opts.validate()?;
+        check_args(&self.verify_opts)?;
+        if !self.input.is_file() {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!(
+                    "Invalid argument: Input invalid. `{}` is not a regular file.",
+                    self.input.display()
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the fix subcommand
+
+use crate::args::{CommonArgs, ValidateArgs};
+use clap::{Error, Parser};
+
+/// Add the `kani::Arbitrary` and `kani::Invariant` derives that the harnesses of a package
+/// require to compile.
+#[derive(Debug, Parser)]
+pub struct CargoFixArgs {
+    #[command(flatten)]
+    pub common_args: CommonArgs,
+
+    /// Print the derives that are missing without modifying the source files.
+    #[arg(long)]
+    pub check: bool,
+}
+
+impl ValidateArgs for CargoFixArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.common_args.validate()
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the inspect subcommand
+
+use std::path::PathBuf;
+
+use crate::args::{ValidateArgs, VerificationArgs};
+use clap::{Error, Parser, error::ErrorKind};
+use kani_metadata::UnstableFeature;
+
+#[derive(Debug, Parser)]
+pub struct CommonInspectArgs {
+    /// The harness to inspect. Like `--harness`, this can be a partial name, as long as it only
+    /// matches one harness.
+    #[arg(value_name = "HARNESS")]
+    pub harness: String,
+
+    /// Also print the symbol table of the goto program.
+    #[arg(long)]
+    pub symbol_table: bool,
+}
+
+/// Print the MIR and the goto program that Kani verifies for a harness of a package.
+#[derive(Debug, Parser)]
+pub struct CargoInspectArgs {
+    #[command(flatten)]
+    pub common_inspect_args: CommonInspectArgs,
+
+    #[command(flatten)]
+    pub verify_opts: VerificationArgs,
+}
+
+/// Print the MIR and the goto program that Kani verifies for a harness of a file.
+#[derive(Debug, Parser)]
+pub struct StandaloneInspectArgs {
+    #[command(flatten)]
+    pub common_inspect_args: CommonInspectArgs,
+
+    /// Rust crate's top file location.
+    #[arg(required = true)]
+    pub input: PathBuf,
+
+    #[arg(long, hide = true)]
+    pub crate_name: Option<String>,
+
+    #[command(flatten)]
+    pub verify_opts: VerificationArgs,
+}
+
+fn check_unstable(verify_opts: &VerificationArgs) -> Result<(), Error> {
+    if !verify_opts.common_args.unstable_features.contains(UnstableFeature::UnstableOptions) {
+        return Err(Error::raw(
+            ErrorKind::MissingRequiredArgument,
+            format!(
+                "The `inspect` subcommand is unstable and requires -Z {}",
+                UnstableFeature::UnstableOptions
+            ),
+        ));
+    }
+    Ok(())
+}
+
+impl ValidateArgs for CargoInspectArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.verify_opts.validate()?;
+        check_unstable(&self.verify_opts)
+    }
+}
+
+impl ValidateArgs for StandaloneInspectArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.verify_opts.validate()?;
+        check_unstable(&self.verify_opts)?;
+        if !self.input.is_file() {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!(
+                    "Invalid argument: Input invalid. `{}` is not a regular file.",
+                    self.input.display()
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
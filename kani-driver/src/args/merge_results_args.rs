@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the merge-results subcommand
+
+use std::path::PathBuf;
+
+use crate::args::{CommonArgs, ValidateArgs};
+use clap::{Error, Parser, error::ErrorKind};
+use kani_metadata::UnstableFeature;
+
+/// Merge the results that several runs wrote with `--statistics`, e.g., each shard of a `--shard`
+/// run, into a single summary. If a harness appears in more than one file, its latest result wins.
+#[derive(Debug, Parser)]
+pub struct MergeResultsArgs {
+    /// The results of each run.
+    #[arg(required = true, value_name = "PATH")]
+    pub files: Vec<PathBuf>,
+
+    /// Write the merged results and their aggregated statistics to the given JSON file. The file
+    /// can be passed to `--shard-timings` to balance the shards of later runs.
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Write a summary of the merged results to the given HTML file.
+    #[arg(long, value_name = "PATH")]
+    pub html: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub common_args: CommonArgs,
+}
+
+impl ValidateArgs for MergeResultsArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.common_args.validate()?;
+        if !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions) {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `merge-results` subcommand is unstable and requires -Z {}",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -3,10 +3,19 @@
 //! Module that define Kani's command line interface. This includes all subcommands.
 
 pub mod autoharness_args;
+pub mod baseline_args;
 pub mod cargo;
+pub mod clean_args;
 pub mod common;
+pub mod doc_proofs_args;
+pub mod export_fuzz_args;
+pub mod fix_args;
+pub mod inspect_args;
 pub mod list_args;
+pub mod merge_results_args;
 pub mod playback_args;
+pub mod report_args;
+pub mod server_args;
 pub mod std_args;
 
 use self::common::*;
@@ -16,10 +25,10 @@
 use clap::builder::{PossibleValue, TypedValueParser};
 use clap::{ValueEnum, error::ContextKind, error::ContextValue, error::Error, error::ErrorKind};
 use kani_metadata::CbmcSolver;
+pub use kani_metadata::Timeout;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::time::Duration;
 use strum::VariantNames;
 
 /// Trait used to perform extra validation after parsing.
@@ -91,49 +100,111 @@ pub fn print_stabilized_feature_warning(
 // By default we configure CBMC to use 16 bits to represent the object bits in pointers.
 const DEFAULT_OBJECT_BITS: u32 = 16;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, strum_macros::EnumString)]
-enum TimeUnit {
-    #[strum(serialize = "s")]
-    Seconds,
-    #[strum(serialize = "m")]
-    Minutes,
-    #[strum(serialize = "h")]
-    Hours,
+/// One of the shards that the harnesses are split into, given as `<INDEX>/<COUNT>`, where the
+/// index starts at 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Shard {
+    pub index: usize,
+    pub count: usize,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct Timeout {
-    value: u32,
-    unit: TimeUnit,
+impl FromStr for Shard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid shard `{s}`. Use `<INDEX>/<COUNT>`, e.g., `3/8`");
+        let (index, count) = s.split_once('/').ok_or_else(invalid)?;
+        let index = index.parse::<usize>().map_err(|_| invalid())?;
+        let count = count.parse::<usize>().map_err(|_| invalid())?;
+        if index == 0 || index > count {
+            return Err(format!("Invalid shard `{s}`. The index must be between 1 and {count}"));
+        }
+        Ok(Shard { index, count })
+    }
+}
+
+/// A static that `--snapshot` initializes with the contents of a file, given as
+/// `<STATIC>=<FILE>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The path of the static, starting with the name of its crate.
+    pub target: String,
+    pub file: PathBuf,
 }
 
-impl FromStr for Timeout {
+impl FromStr for Snapshot {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let last_char = s.chars().last().unwrap();
-        let (value_str, unit_str) = if last_char.is_ascii_digit() {
-            // no suffix
-            (s, "s")
-        } else {
-            s.split_at(s.len() - 1)
-        };
-        let value = value_str.parse::<u32>().map_err(|_| "Invalid timeout value")?;
+        match s.split_once('=') {
+            Some((target, file)) if !target.is_empty() && !file.is_empty() => {
+                Ok(Snapshot { target: target.to_string(), file: file.into() })
+            }
+            _ => Err(format!("Invalid snapshot `{s}`. Use `<STATIC>=<FILE>`")),
+        }
+    }
+}
 
-        let unit = TimeUnit::from_str(unit_str).map_err(
-            |_| "Invalid time unit. Use 's' for seconds, 'm' for minutes, or 'h' for hours",
-        )?;
+/// The backend that `--compare-backend` verifies every harness with, in addition to the default
+/// one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompareBackend {
+    /// Another CBMC binary, given as `cbmc=<PATH>`, e.g., a different version of CBMC.
+    Cbmc(PathBuf),
+    /// The default CBMC binary with another solver, given as for `--solver`.
+    Solver(CbmcSolver),
+}
 
-        Ok(Timeout { value, unit })
+impl FromStr for CompareBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some(("cbmc", path)) if !path.is_empty() => Ok(CompareBackend::Cbmc(path.into())),
+            Some(("bin", solver)) => Ok(CompareBackend::Solver(CbmcSolver::Binary(solver.into()))),
+            Some(("smt", solver)) => Ok(CompareBackend::Solver(CbmcSolver::Smt(solver.into()))),
+            None => CbmcSolver::from_str(s).map(CompareBackend::Solver).map_err(|_| {
+                format!(
+                    "Invalid backend `{s}`. Use `cbmc=<PATH>` or one of the values of `--solver`"
+                )
+            }),
+            Some(_) => Err(format!(
+                "Invalid backend `{s}`. Use `cbmc=<PATH>` or one of the values of `--solver`"
+            )),
+        }
     }
 }
 
-impl From<Timeout> for Duration {
-    fn from(timeout: Timeout) -> Self {
-        match timeout.unit {
-            TimeUnit::Seconds => Duration::from_secs(timeout.value as u64),
-            TimeUnit::Minutes => Duration::from_secs(timeout.value as u64 * 60),
-            TimeUnit::Hours => Duration::from_secs(timeout.value as u64 * 3600),
+impl std::fmt::Display for CompareBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompareBackend::Cbmc(path) => write!(f, "cbmc={}", path.display()),
+            CompareBackend::Solver(CbmcSolver::Binary(solver)) => write!(f, "bin={solver}"),
+            CompareBackend::Solver(CbmcSolver::Smt(solver)) => write!(f, "smt={solver}"),
+            CompareBackend::Solver(solver) => write!(f, "{}", solver.as_ref()),
+        }
+    }
+}
+
+/// The scheduling model of `--concurrency` for the threads that the harnesses spawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Concurrency {
+    /// Explore every schedule with at most this many context switches, given as `bounded:<N>`.
+    Bounded(usize),
+}
+
+impl FromStr for Concurrency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("bounded", bound)) => match bound.parse() {
+                Ok(bound) if bound > 0 => Ok(Concurrency::Bounded(bound)),
+                _ => Err(format!(
+                    "Invalid bound `{bound}`. The number of context switches must be positive"
+                )),
+            },
+            _ => Err(format!("Invalid concurrency model `{s}`. Use `bounded:<N>`")),
         }
     }
 }
@@ -169,10 +240,23 @@ pub struct StandaloneArgs {
 pub enum StandaloneSubcommand {
     /// Create and run harnesses automatically for eligible functions. Implies -Z function-contracts and -Z loop-contracts.
     Autoharness(Box<autoharness_args::StandaloneAutoharnessArgs>),
+    /// Record the status of every property in a baseline file, or report how the status of the
+    /// properties changed since the baseline was recorded.
+    Baseline(Box<baseline_args::StandaloneBaselineArgs>),
+    /// Translate a harness into a `proptest` test, which runs the same property without Kani.
+    ExportFuzz(Box<export_fuzz_args::StandaloneExportFuzzArgs>),
+    /// Print the MIR and the goto program that Kani verifies for a harness.
+    Inspect(Box<inspect_args::StandaloneInspectArgs>),
     /// List contracts and harnesses.
     List(Box<list_args::StandaloneListArgs>),
+    /// Merge the results of the shards of a `--shard` run.
+    MergeResults(Box<merge_results_args::MergeResultsArgs>),
     /// Execute concrete playback testcases of a local crate.
     Playback(Box<playback_args::KaniPlaybackArgs>),
+    /// Process the results of earlier runs, e.g., merge the results of independent runs.
+    Report(Box<report_args::ReportArgs>),
+    /// Serve the requests of an editor extension, e.g., to verify the harness under the cursor.
+    Server(Box<server_args::StandaloneServerArgs>),
     /// Verify the rust standard library.
     VerifyStd(Box<std_args::VerifyStdArgs>),
 }
@@ -199,19 +283,56 @@ pub enum CargoKaniSubcommand {
     /// See https://model-checking.github.io/kani/reference/experimental/autoharness.html for documentation.
     Autoharness(Box<autoharness_args::CargoAutoharnessArgs>),
 
+    /// Record the status of every property in a baseline file, or report how the status of the
+    /// properties changed since the baseline was recorded.
+    Baseline(Box<baseline_args::CargoBaselineArgs>),
+
+    /// Remove the artifacts that Kani generated for a package.
+    Clean(Box<clean_args::CargoCleanArgs>),
+
+    /// Generate an index of the harnesses and contracts of a package, with their documentation,
+    /// the functions that they cover, and their latest verification status.
+    DocProofs(Box<doc_proofs_args::CargoDocProofsArgs>),
+
+    /// Translate a harness into a `proptest` test, which runs the same property without Kani.
+    ExportFuzz(Box<export_fuzz_args::CargoExportFuzzArgs>),
+
+    /// Add the `kani::Arbitrary` and `kani::Invariant` derives that are missing for the harnesses
+    /// of a package to compile.
+    Fix(Box<fix_args::CargoFixArgs>),
+
+    /// Print the MIR and the goto program that Kani verifies for a harness.
+    Inspect(Box<inspect_args::CargoInspectArgs>),
+
     /// List contracts and harnesses.
     List(Box<list_args::CargoListArgs>),
 
+    /// Merge the results of the shards of a `--shard` run.
+    MergeResults(Box<merge_results_args::MergeResultsArgs>),
+
     /// Execute concrete playback testcases of a local package.
     Playback(Box<playback_args::CargoPlaybackArgs>),
+
+    /// Process the results of earlier runs, e.g., merge the results of independent runs.
+    Report(Box<report_args::ReportArgs>),
+
+    /// Serve the requests of an editor extension, e.g., to verify the harness under the cursor.
+    Server(Box<server_args::CargoServerArgs>),
 }
 
 // Common arguments for invoking Kani for verification purpose. This gets put into KaniContext,
 // whereas anything above is "local" to "main"'s control flow.
 // When adding an argument to this struct, make sure that it's in alphabetical order as displayed to the user when running --help.
-#[derive(Debug, clap::Args)]
+#[derive(Clone, Debug, clap::Args)]
 #[clap(next_help_heading = "Verification Options")]
 pub struct VerificationArgs {
+    /// How CBMC encodes the arrays of the harnesses without an `array_encoding` attribute. With
+    /// `auto`, the harnesses whose largest array has more than 256 elements encode their arrays
+    /// with uninterpreted functions. By default, CBMC picks the encoding.
+    /// This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, value_name = "ENCODING", value_enum, hide_short_help = true)]
+    pub array_encoding: Option<ArrayEncodingMode>,
+
     /// Link external C files referenced by Rust code.
     /// This is an experimental feature and requires `-Z c-ffi` to be used
     #[arg(long, hide = true, num_args(1..))]
@@ -234,6 +355,39 @@ pub struct VerificationArgs {
     #[arg(long, ignore_case = true, value_enum)]
     pub concrete_playback: Option<ConcretePlaybackMode>,
 
+    /// Run the threads that the harnesses spawn with a scheduler that explores every schedule with
+    /// at most N context switches at the atomic operations, given as `bounded:<N>`.
+    /// Requires -Z unstable-options.
+    #[arg(long, value_name = "MODEL", hide_short_help = true)]
+    pub concurrency: Option<Concurrency>,
+
+    /// The semantics of the atomic operations of the threads that the harnesses spawn. By default,
+    /// they are sequentially consistent, whatever their ordering. With `release-acquire`, relaxed
+    /// and acquire loads may read stale values, as allowed by the C11 memory model.
+    /// Requires `--concurrency` and -Z unstable-options.
+    #[arg(long, value_name = "MODEL", value_enum, requires("concurrency"), hide_short_help = true)]
+    pub memory_model: Option<MemoryModel>,
+
+    /// Check that the reference counts of `Rc` and `Arc` never drop below zero, and that every
+    /// allocation whose count the harness updates is released when the harness returns.
+    /// Requires -Z unstable-options.
+    #[arg(long, hide_short_help = true)]
+    pub check_refcounts: bool,
+
+    /// Initialize a static with the contents of a file instead of its initializer, e.g., with a
+    /// RAM snapshot of a running program. Given as `<STATIC>=<FILE>`, where `<STATIC>` is the path
+    /// of the static including its crate, and may be repeated. The file must hold a valid value of
+    /// the type of the static, which must not contain pointers.
+    /// Requires -Z unstable-options.
+    #[arg(long, value_name = "STATIC=FILE", hide_short_help = true)]
+    pub snapshot: Vec<Snapshot>,
+
+    /// Verify every harness a second time with the given backend, and report the harnesses whose
+    /// results differ between the two backends. The backend is either another CBMC binary, given
+    /// as `cbmc=<PATH>`, or a solver, given as for `--solver`. Requires -Z unstable-options.
+    #[arg(long, value_name = "BACKEND", hide_short_help = true)]
+    pub compare_backend: Option<CompareBackend>,
+
     /// Enable Kani coverage output alongside verification result
     #[arg(long, hide_short_help = true)]
     pub coverage: bool,
@@ -242,6 +396,13 @@ pub struct VerificationArgs {
     #[arg(long)]
     pub default_unwind: Option<u32>,
 
+    /// Write a contract manifest (`kani-contracts.json`) to the root of each verified package,
+    /// which lists the functions whose contracts were verified. When Kani verifies a crate that
+    /// depends on the package, it replaces the calls to these functions by their contracts.
+    /// Requires -Z function-contracts.
+    #[arg(long, hide_short_help = true)]
+    pub emit_contract_manifest: bool,
+
     /// When specified, the harness filter will only match the exact fully qualified name of a harness
     #[arg(long, requires("harnesses"))]
     pub exact: bool,
@@ -272,15 +433,32 @@ pub struct VerificationArgs {
     #[arg(long = "harness", num_args(1), value_name = "HARNESS_FILTER")]
     pub harnesses: Vec<String>,
 
+    /// If specified, only run the harnesses that the `Kani.toml` tags with one of these tags.
+    /// This option can be provided multiple times.
+    #[arg(long = "tag", num_args(1), value_name = "TAG")]
+    pub tags: Vec<String>,
+
     /// Timeout for each harness with optional suffix ('s': seconds, 'm': minutes, 'h': hours). Default is seconds. This option is experimental and requires `-Z unstable-options` to be used.
     #[arg(long)]
     pub harness_timeout: Option<Timeout>,
 
-    /// Do not error out for crates containing `global_asm!`.
+    /// Do not error out for crates containing `global_asm!`. Annotate a module with
+    /// `#[kani::ignore_global_asm]` to only ignore its assembly instead.
     /// This option may impact the soundness of the analysis and may cause false proofs and/or counterexamples
     #[arg(long, hide_short_help = true)]
     pub ignore_global_asm: bool,
 
+    /// Only verify the given harness, which is annotated with `#[kani::proof(isolate = "process")]`.
+    /// Kani passes this option to the process that verifies an isolated harness.
+    #[arg(long, hide = true, value_name = "HARNESS")]
+    pub isolated_harness: Option<String>,
+
+    /// Limit the virtual memory of each process that verifies a harness annotated with
+    /// `#[kani::proof(isolate = "process")]`, in MiB.
+    /// This feature is unstable and requires `-Z unstable-options` to be used
+    #[arg(long, hide_short_help = true, value_name = "MIB")]
+    pub isolation_memory_limit: Option<u64>,
+
     /// Number of threads to spawn to verify harnesses in parallel.
     /// Omit the flag entirely to run sequentially (i.e. one thread).
     /// Pass -j to run with the thread pool's default number of threads.
@@ -297,6 +475,93 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub no_assert_contracts: bool,
 
+    /// Check and replace function contracts with CBMC's contract instrumentation instead of
+    /// Kani's. Only contracts with `requires` and `ensures` clauses that do not use `old` are
+    /// supported. Requires -Z function-contracts.
+    #[arg(long, hide_short_help = true)]
+    pub cbmc_contracts: bool,
+
+    /// Check that public methods that take `&mut self` preserve the safety invariant of `self`,
+    /// if its type implements `kani::Invariant`. Requires -Z unstable-options.
+    #[arg(long, hide_short_help = true)]
+    pub check_invariant_preservation: bool,
+
+    /// Check that every heap allocation of a harness is deallocated exactly once, which catches
+    /// leaked and doubly dropped allocations. Requires -Z unstable-options.
+    #[arg(long, hide_short_help = true)]
+    pub check_leaks: bool,
+
+    /// Replace SipHash, the hashing algorithm of `std::hash::DefaultHasher`, with a cheaper model
+    /// that still produces equal hashes for equal inputs. Requires -Z unstable-options.
+    #[arg(long, hide_short_help = true)]
+    pub model_hashing: bool,
+
+    /// Replace `HashMap`, `HashSet`, `VecDeque`, `BTreeMap` and their iterators from
+    /// `std::collections` with the bounded models in `kani::models`. Requires -Z unstable-options.
+    #[arg(long, hide_short_help = true)]
+    pub model_std_collections: bool,
+
+    /// Replace `std::env::var`, `std::fs::read`, `std::fs::write` and the other functions that
+    /// access environment variables and whole files with an in-memory model whose initial
+    /// contents are nondeterministic. Requires -Z unstable-options.
+    #[arg(long, hide_short_help = true)]
+    pub model_std_io: bool,
+
+    /// Replace the clocks read by `std::time::Instant::now` and `std::time::SystemTime::now` with
+    /// nondeterministic models, where the monotonic clock never goes backwards. Requires
+    /// -Z unstable-options.
+    #[arg(long, hide_short_help = true)]
+    pub model_time: bool,
+
+    /// List the unsafe operations that are reachable from each harness, such as raw pointer
+    /// dereferences and calls to unsafe functions, instead of verifying the harnesses.
+    /// Requires -Z unstable-options.
+    #[arg(long, hide_short_help = true)]
+    pub list_unsafe: bool,
+
+    /// Persist the given artifacts of each harness to `<target-dir>/kani-emit`, named after the
+    /// harness: `goto` for the instrumented goto program and `mir` for the MIR after Kani's
+    /// transformations. Requires -Z unstable-options.
+    #[arg(long, value_delimiter = ',', hide_short_help = true)]
+    pub emit: Vec<EmitKind>,
+
+    /// Write statistics about the cost of verifying each harness, such as the size of the formula,
+    /// the solver time and the peak memory usage of CBMC, to the given JSON file, and print a
+    /// summary table. Requires -Z unstable-options.
+    #[arg(long, value_name = "PATH", hide_short_help = true)]
+    pub statistics: Option<PathBuf>,
+
+    /// Only verify the harnesses that can reach the code changed by the given git range, e.g.,
+    /// `--affected-by main..HEAD`, or `--affected-by HEAD` for the uncommitted changes. A harness
+    /// is affected if one of the lines that changed is in a function that it reaches. Changes to
+    /// constants, types, and manifests that don't change such a function are not detected.
+    /// Requires -Z unstable-options.
+    #[arg(long, value_name = "RANGE", hide_short_help = true)]
+    pub affected_by: Option<String>,
+
+    /// Only verify the harnesses of the given shard, e.g., `--shard 3/8`, so that a large suite can
+    /// be split across CI jobs. Every harness belongs to exactly one shard, and the assignment is
+    /// the same in every job. Use `kani merge-results` to merge the results of the shards.
+    /// Requires -Z unstable-options.
+    #[arg(long, value_name = "INDEX/COUNT", hide_short_help = true)]
+    pub shard: Option<Shard>,
+
+    /// Balance the shards using the runtime of each harness in the given results, which were
+    /// written by `--statistics` or `kani merge-results`. Requires -Z unstable-options.
+    #[arg(long, value_name = "PATH", requires("shard"), hide_short_help = true)]
+    pub shard_timings: Option<PathBuf>,
+
+    /// Write the status of every labeled cover property, i.e., `kani::cover!(cond, label = name)`,
+    /// aggregated across all harnesses, to the given JSON file. Requires -Z unstable-options.
+    #[arg(long, value_name = "PATH", hide_short_help = true)]
+    pub cover_summary: Option<PathBuf>,
+
+    /// Check that the end of every harness is reachable, and report the harnesses whose
+    /// assumptions are contradictory as VACUOUS instead of successful, since all of their checks
+    /// hold trivially. Requires -Z unstable-options.
+    #[arg(long, hide_short_help = true)]
+    pub check_vacuity: bool,
+
     /// Turn off assertion reachability checks
     #[arg(long)]
     pub no_assertion_reach_checks: bool,
@@ -306,6 +571,11 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub no_codegen: bool,
 
+    /// Do not replace the calls to functions of dependencies by their contracts, even if the
+    /// dependency lists them in its contract manifest. Requires -Z function-contracts.
+    #[arg(long, hide_short_help = true)]
+    pub no_dependency_contracts: bool,
+
     /// Disable restricting the targets of virtual table function pointer calls
     #[arg(long, hide_short_help = true)]
     pub no_restrict_vtable: bool,
@@ -314,6 +584,12 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub no_slice_formula: bool,
 
+    /// Remove the parts of the goto program that cannot affect any assertion before running
+    /// CBMC. With `--verbose`, report how many instructions were removed.
+    /// This feature is unstable and requires `-Z unstable-options` to be used
+    #[arg(long, hide_short_help = true)]
+    pub slice_assertions: bool,
+
     /// Kani will only compile the crate. No verification will be performed
     #[arg(long, hide_short_help = true)]
     pub only_codegen: bool,
@@ -326,6 +602,12 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub output_into_files: bool,
 
+    /// Show a table with the state of each harness that is updated while the harnesses are
+    /// verified. Kani prints its regular output if stdout isn't a terminal.
+    /// Requires -Z unstable-options.
+    #[arg(long, hide_short_help = true)]
+    pub ui: bool,
+
     /// Print final LLBC for Lean backend. This requires the `-Z lean` option.
     #[arg(long, hide = true)]
     pub print_llbc: bool,
@@ -335,6 +617,18 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true)]
     pub prove_safety_only: bool,
 
+    /// Check that no public function of the crate panics, instead of running the crate's harnesses.
+    /// Kani generates a harness for every public function, as the `autoharness` subcommand does,
+    /// and checks panics, arithmetic overflows and the other safety properties, but not the
+    /// postconditions of function contracts. Requires -Z unstable-options.
+    #[arg(
+        long,
+        hide_short_help = true,
+        conflicts_with("prove_safety_only"),
+        conflicts_with("concrete_playback")
+    )]
+    pub prove_no_panics: bool,
+
     /// Randomize the layout of structures. This option can help catching code that relies on
     /// a specific layout chosen by the compiler that is not guaranteed to be stable in the future.
     /// If a value is given, it will be used as the seed for randomization
@@ -490,6 +784,15 @@ pub fn is_stubbing_enabled(&self) -> bool {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ArrayEncodingMode {
+    /// Pick the encoding from the size of the largest array of the harness.
+    Auto,
+    Uf,
+    #[value(name = "field_sensitive")]
+    FieldSensitive,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum ConcretePlaybackMode {
     Print,
@@ -498,6 +801,17 @@ pub enum ConcretePlaybackMode {
     InPlace,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum EmitKind {
+    Goto,
+    Mir,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MemoryModel {
+    ReleaseAcquire,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     Regular,
@@ -505,7 +819,7 @@ pub enum OutputFormat {
     Old,
 }
 
-#[derive(Debug, clap::Args)]
+#[derive(Clone, Debug, clap::Args)]
 #[clap(next_help_heading = "Memory Checks")]
 pub struct CheckArgs {
     /// Turn off all default checks
@@ -520,6 +834,10 @@ pub struct CheckArgs {
     #[arg(long)]
     pub no_overflow_checks: bool,
 
+    /// Turn off the bounds checks of indexing operations
+    #[arg(long)]
+    pub no_bounds_checks: bool,
+
     /// Turn off undefined function checks
     #[arg(long)]
     pub no_undefined_function_checks: bool,
@@ -536,6 +854,9 @@ pub fn memory_safety_on(&self) -> bool {
     pub fn overflow_on(&self) -> bool {
         !self.no_default_checks && !self.no_overflow_checks
     }
+    pub fn bounds_on(&self) -> bool {
+        !self.no_default_checks && !self.no_bounds_checks
+    }
     pub fn undefined_function_on(&self) -> bool {
         !self.no_default_checks && !self.no_undefined_function_checks
     }
@@ -567,7 +888,13 @@ fn validate(&self) -> Result<(), Error> {
         match &self.command {
             Some(StandaloneSubcommand::VerifyStd(args)) => args.validate()?,
             Some(StandaloneSubcommand::List(args)) => args.validate()?,
+            Some(StandaloneSubcommand::MergeResults(args)) => args.validate()?,
             Some(StandaloneSubcommand::Autoharness(args)) => args.validate()?,
+            Some(StandaloneSubcommand::Baseline(args)) => args.validate()?,
+            Some(StandaloneSubcommand::ExportFuzz(args)) => args.validate()?,
+            Some(StandaloneSubcommand::Inspect(args)) => args.validate()?,
+            Some(StandaloneSubcommand::Report(args)) => args.validate()?,
+            Some(StandaloneSubcommand::Server(args)) => args.validate()?,
             // TODO: Invoke PlaybackArgs::validate()
             None | Some(StandaloneSubcommand::Playback(..)) => {}
         };
@@ -584,6 +911,7 @@ fn validate(&self) -> Result<(), Error> {
         check_no_cargo_opt(!self.verify_opts.cargo.exclude.is_empty(), "--exclude")?;
         check_no_cargo_opt(self.verify_opts.cargo.workspace, "--workspace")?;
         check_no_cargo_opt(self.verify_opts.cargo.manifest_path.is_some(), "--manifest-path")?;
+        check_no_cargo_opt(!self.verify_opts.tags.is_empty(), "--tag")?;
         if let Some(input) = &self.input
             && !input.is_file()
         {
@@ -612,8 +940,17 @@ impl ValidateArgs for CargoKaniSubcommand {
     fn validate(&self) -> Result<(), Error> {
         match self {
             CargoKaniSubcommand::Autoharness(autoharness) => autoharness.validate(),
+            CargoKaniSubcommand::Baseline(baseline) => baseline.validate(),
+            CargoKaniSubcommand::Clean(clean) => clean.validate(),
+            CargoKaniSubcommand::DocProofs(doc_proofs) => doc_proofs.validate(),
+            CargoKaniSubcommand::ExportFuzz(export_fuzz) => export_fuzz.validate(),
+            CargoKaniSubcommand::Fix(fix) => fix.validate(),
+            CargoKaniSubcommand::Inspect(inspect) => inspect.validate(),
             CargoKaniSubcommand::Playback(playback) => playback.validate(),
             CargoKaniSubcommand::List(list) => list.validate(),
+            CargoKaniSubcommand::MergeResults(merge) => merge.validate(),
+            CargoKaniSubcommand::Report(report) => report.validate(),
+            CargoKaniSubcommand::Server(server) => server.validate(),
         }
     }
 }
@@ -686,6 +1023,12 @@ fn validate(&self) -> Result<(), Error> {
                 UnstableFeature::UnstableOptions,
             )?;
 
+            self.common_args.check_unstable(
+                self.slice_assertions,
+                "slice-assertions",
+                UnstableFeature::UnstableOptions,
+            )?;
+
             self.common_args.check_unstable(
                 self.synthesize_loop_contracts,
                 "synthesize-loop-contracts",
@@ -708,6 +1051,7 @@ fn validate(&self) -> Result<(), Error> {
                 "output-into-files",
                 UnstableFeature::UnstableOptions,
             )?;
+            self.common_args.check_unstable(self.ui, "ui", UnstableFeature::UnstableOptions)?;
             self.common_args.check_unstable(
                 self.print_llbc,
                 "print-llbc",
@@ -723,12 +1067,133 @@ fn validate(&self) -> Result<(), Error> {
                 "no-assert",
                 UnstableFeature::FunctionContracts,
             )?;
+            self.common_args.check_unstable(
+                self.cbmc_contracts,
+                "cbmc-contracts",
+                UnstableFeature::FunctionContracts,
+            )?;
+            self.common_args.check_unstable(
+                self.emit_contract_manifest,
+                "emit-contract-manifest",
+                UnstableFeature::FunctionContracts,
+            )?;
+            self.common_args.check_unstable(
+                self.no_dependency_contracts,
+                "no-dependency-contracts",
+                UnstableFeature::FunctionContracts,
+            )?;
+            self.common_args.check_unstable(
+                self.check_invariant_preservation,
+                "check-invariant-preservation",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.check_leaks,
+                "check-leaks",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.model_hashing,
+                "model-hashing",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.model_std_collections,
+                "model-std-collections",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.model_time,
+                "model-time",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.model_std_io,
+                "model-std-io",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.list_unsafe,
+                "list-unsafe",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.concurrency.is_some(),
+                "concurrency",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.array_encoding.is_some(),
+                "array-encoding",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.memory_model.is_some(),
+                "memory-model",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.check_refcounts,
+                "check-refcounts",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                !self.snapshot.is_empty(),
+                "snapshot",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                !self.emit.is_empty(),
+                "emit",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.statistics.is_some(),
+                "statistics",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.affected_by.is_some(),
+                "affected-by",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.shard.is_some(),
+                "shard",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.cover_summary.is_some(),
+                "cover-summary",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.check_vacuity,
+                "check-vacuity",
+                UnstableFeature::UnstableOptions,
+            )?;
+            self.common_args.check_unstable(
+                self.compare_backend.is_some(),
+                "compare-backend",
+                UnstableFeature::UnstableOptions,
+            )?;
 
             self.common_args.check_unstable(
                 self.prove_safety_only,
                 "prove-safety-only",
                 UnstableFeature::UnstableOptions,
             )?;
+            self.common_args.check_unstable(
+                self.prove_no_panics,
+                "prove-no-panics",
+                UnstableFeature::UnstableOptions,
+            )?;
+
+            self.common_args.check_unstable(
+                self.isolation_memory_limit.is_some(),
+                "isolation-memory-limit",
+                UnstableFeature::UnstableOptions,
+            )?;
 
             Ok(())
         };
@@ -767,6 +1232,22 @@ fn validate(&self) -> Result<(), Error> {
                 --output-format=old.",
                 ));
             }
+            if self.compare_backend.is_some() && self.output_format == OutputFormat::Old {
+                return Err(Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "Conflicting options: --compare-backend isn't compatible with \
+                --output-format=old.",
+                ));
+            }
+            if self.concrete_playback.is_some() && self.slice_assertions {
+                // Slicing removes the nondeterministic values that do not affect any assertion,
+                // so the trace would not have a value for every call to `kani::any()`.
+                return Err(Error::raw(
+                    ErrorKind::ArgumentConflict,
+                    "Conflicting options: --concrete-playback isn't compatible with \
+                --slice-assertions.",
+                ));
+            }
             if self.concrete_playback.is_some() && self.jobs().will_multithread() {
                 // Concrete playback currently embeds a lot of assumptions about the order in which harnesses get called.
                 return Err(Error::raw(
@@ -839,6 +1320,16 @@ fn validate(&self) -> Result<(), Error> {
             );
         }
 
+        if let Some(snapshot) = self.snapshot.iter().find(|snapshot| !snapshot.file.is_file()) {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!(
+                    "Invalid argument: `--snapshot` file `{}` does not exist",
+                    snapshot.file.display()
+                ),
+            ));
+        }
+
         if let Some(out_dir) = &self.target_dir
             && out_dir.exists()
             && !out_dir.is_dir()
@@ -912,7 +1403,7 @@ fn parse_ref(
         value: &std::ffi::OsStr,
     ) -> Result<Self::Value, clap::error::Error> {
         let value = value.to_str().unwrap();
-        // `value` is one of the possible `CbmcSolver` values or `bin=<binary>`
+        // `value` is one of the possible `CbmcSolver` values, `bin=<binary>` or `smt=<binary>`
         let segments: Vec<&str> = value.split('=').collect();
 
         let mut err = clap::Error::new(ErrorKind::InvalidValue).with_cmd(cmd);
@@ -920,10 +1411,11 @@ fn parse_ref(
         err.insert(ContextKind::InvalidValue, ContextValue::String(value.to_string()));
 
         if segments.len() == 2 {
-            if segments[0] != "bin" {
-                return Err(err);
-            }
-            return Ok(CbmcSolver::Binary(segments[1].into()));
+            return match segments[0] {
+                "bin" => Ok(CbmcSolver::Binary(segments[1].into())),
+                "smt" => Ok(CbmcSolver::Smt(segments[1].into())),
+                _ => Err(err),
+            };
         } else if segments.len() == 1 {
             let solver = CbmcSolver::from_str(value);
             return solver.map_err(|_| err);
@@ -994,6 +1486,18 @@ fn check_multiple_harnesses_without_flag_fail() {
         assert_eq!(result.unwrap_err().kind(), ErrorKind::ArgumentConflict);
     }
 
+    #[test]
+    fn check_solver_binaries() {
+        let parse = |solver: &str| {
+            StandaloneArgs::try_parse_from(["kani", "input.rs", "--solver", solver])
+                .map(|args| args.verify_opts.solver)
+        };
+        assert_eq!(parse("z3").unwrap(), Some(CbmcSolver::Z3));
+        assert_eq!(parse("bin=kissat").unwrap(), Some(CbmcSolver::Binary("kissat".into())));
+        assert_eq!(parse("smt=bitwuzla").unwrap(), Some(CbmcSolver::Smt("bitwuzla".into())));
+        assert!(parse("sat=kissat").is_err());
+    }
+
     #[test]
     fn check_multiple_packages() {
         // accepts repeated:
@@ -1011,6 +1515,113 @@ fn check_multiple_packages() {
         assert!(b.is_ok());
     }
 
+    #[test]
+    fn check_clean_target_dir() {
+        let args =
+            CargoKaniArgs::try_parse_from(["cargo-kani", "--target-dir", "my_target", "clean"])
+                .unwrap();
+        assert_eq!(args.verify_opts.target_dir, Some(PathBuf::from("my_target")));
+        assert!(matches!(args.command, Some(CargoKaniSubcommand::Clean(_))));
+    }
+
+    #[test]
+    fn check_baseline_args() {
+        let args = CargoKaniArgs::try_parse_from([
+            "cargo-kani",
+            "baseline",
+            "compare",
+            "--baseline-file",
+            "known.json",
+            "-Z",
+            "unstable-options",
+        ])
+        .unwrap();
+        let Some(CargoKaniSubcommand::Baseline(baseline)) = &args.command else {
+            panic!("expected the baseline subcommand, but got {:?}", args.command)
+        };
+        assert_eq!(baseline.common_baseline_args.action, baseline_args::BaselineAction::Compare);
+        assert_eq!(baseline.common_baseline_args.baseline_file, PathBuf::from("known.json"));
+        assert!(args.validate().is_ok());
+
+        let args = CargoKaniArgs::try_parse_from(["cargo-kani", "baseline", "record"]).unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_inspect_args() {
+        let args = CargoKaniArgs::try_parse_from([
+            "cargo-kani",
+            "inspect",
+            "check_foo",
+            "--symbol-table",
+            "-Z",
+            "unstable-options",
+        ])
+        .unwrap();
+        let Some(CargoKaniSubcommand::Inspect(inspect)) = &args.command else {
+            panic!("expected the inspect subcommand, but got {:?}", args.command)
+        };
+        assert_eq!(inspect.common_inspect_args.harness, "check_foo");
+        assert!(inspect.common_inspect_args.symbol_table);
+        assert!(args.validate().is_ok());
+
+        let args = CargoKaniArgs::try_parse_from(["cargo-kani", "inspect", "check_foo"]).unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_export_fuzz_args() {
+        let args = CargoKaniArgs::try_parse_from([
+            "cargo-kani",
+            "export-fuzz",
+            "--harness",
+            "check_foo",
+            "-o",
+            "tests/fuzz.rs",
+            "-Z",
+            "unstable-options",
+        ])
+        .unwrap();
+        let Some(CargoKaniSubcommand::ExportFuzz(export)) = &args.command else {
+            panic!("expected the export-fuzz subcommand, but got {:?}", args.command)
+        };
+        assert_eq!(export.verify_opts.harnesses, ["check_foo"]);
+        assert_eq!(export.common_export_fuzz_args.output, Some(PathBuf::from("tests/fuzz.rs")));
+        assert!(args.validate().is_ok());
+
+        let args =
+            CargoKaniArgs::try_parse_from(["cargo-kani", "export-fuzz", "-Z", "unstable-options"])
+                .unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_server_args() {
+        let args = CargoKaniArgs::try_parse_from([
+            "cargo-kani",
+            "server",
+            "--port",
+            "9000",
+            "-Z",
+            "unstable-options",
+        ])
+        .unwrap();
+        let Some(CargoKaniSubcommand::Server(server)) = &args.command else {
+            panic!("expected the server subcommand, but got {:?}", args.command)
+        };
+        assert_eq!(server.common_server_args.port, 9000);
+        assert!(args.validate().is_ok());
+
+        let args = CargoKaniArgs::try_parse_from(["cargo-kani", "server"]).unwrap();
+        assert_eq!(args.validate().unwrap_err().kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_fix_check() {
+        let args = CargoKaniArgs::try_parse_from(["cargo-kani", "fix", "--check"]).unwrap();
+        assert!(matches!(args.command, Some(CargoKaniSubcommand::Fix(fix)) if fix.check));
+    }
+
     fn check(args: &str, feature: Option<UnstableFeature>, pred: fn(StandaloneArgs) -> bool) {
         let mut res = parse_unstable_disabled(args);
         if let Some(unstable) = feature {
@@ -1103,6 +1714,223 @@ fn check_disable_slicing_unstable() {
         check_unstable_flag!("--no-slice-formula", no_slice_formula);
     }
 
+    #[test]
+    fn check_leaks_unstable() {
+        check_unstable_flag!("--check-leaks", check_leaks);
+    }
+
+    #[test]
+    fn check_model_hashing_unstable() {
+        check_unstable_flag!("--model-hashing", model_hashing);
+    }
+
+    #[test]
+    fn check_model_std_collections_unstable() {
+        check_unstable_flag!("--model-std-collections", model_std_collections);
+    }
+
+    #[test]
+    fn check_model_time_unstable() {
+        check_unstable_flag!("--model-time", model_time);
+    }
+
+    #[test]
+    fn check_model_std_io_unstable() {
+        check_unstable_flag!("--model-std-io", model_std_io);
+    }
+
+    #[test]
+    fn check_statistics_unstable() {
+        check("--statistics stats.json", Some(UnstableFeature::UnstableOptions), |p| {
+            p.verify_opts.statistics == Some(PathBuf::from("stats.json"))
+        });
+    }
+
+    #[test]
+    fn check_affected_by_unstable() {
+        check("--affected-by main..HEAD", Some(UnstableFeature::UnstableOptions), |p| {
+            p.verify_opts.affected_by.as_deref() == Some("main..HEAD")
+        });
+    }
+
+    #[test]
+    fn check_shard_unstable() {
+        check("--shard 3/8", Some(UnstableFeature::UnstableOptions), |p| {
+            p.verify_opts.shard == Some(Shard { index: 3, count: 8 })
+        });
+    }
+
+    #[test]
+    fn check_shard_parsing() {
+        assert_eq!(Shard::from_str("1/1"), Ok(Shard { index: 1, count: 1 }));
+        assert!(Shard::from_str("0/8").is_err());
+        assert!(Shard::from_str("9/8").is_err());
+        assert!(Shard::from_str("3").is_err());
+        assert!(Shard::from_str("a/8").is_err());
+
+        // `--shard-timings` requires `--shard`.
+        let err =
+            parse_unstable_enabled("--shard-timings stats.json", UnstableFeature::UnstableOptions)
+                .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_cover_summary_unstable() {
+        check("--cover-summary covers.json", Some(UnstableFeature::UnstableOptions), |p| {
+            p.verify_opts.cover_summary == Some(PathBuf::from("covers.json"))
+        });
+    }
+
+    #[test]
+    fn check_vacuity_unstable() {
+        check_unstable_flag!("--check-vacuity", check_vacuity);
+    }
+
+    #[test]
+    fn check_compare_backend_unstable() {
+        check("--compare-backend cbmc=/opt/cbmc", Some(UnstableFeature::UnstableOptions), |p| {
+            p.verify_opts.compare_backend == Some(CompareBackend::Cbmc("/opt/cbmc".into()))
+        });
+    }
+
+    #[test]
+    fn check_concurrency_unstable() {
+        check("--concurrency bounded:2", Some(UnstableFeature::UnstableOptions), |p| {
+            p.verify_opts.concurrency == Some(Concurrency::Bounded(2))
+        });
+    }
+
+    #[test]
+    fn check_concurrency_parsing() {
+        assert_eq!(Concurrency::from_str("bounded:3"), Ok(Concurrency::Bounded(3)));
+        assert!(Concurrency::from_str("bounded:0").is_err());
+        assert!(Concurrency::from_str("bounded:x").is_err());
+        assert!(Concurrency::from_str("round-robin").is_err());
+    }
+
+    #[test]
+    fn check_array_encoding_args() {
+        check_opt!(
+            "--array-encoding auto",
+            Some(UnstableFeature::UnstableOptions),
+            array_encoding,
+            Some(ArrayEncodingMode::Auto)
+        );
+        check_opt!(
+            "--array-encoding field_sensitive",
+            Some(UnstableFeature::UnstableOptions),
+            array_encoding,
+            Some(ArrayEncodingMode::FieldSensitive)
+        );
+    }
+
+    #[test]
+    fn check_memory_model_args() {
+        check(
+            "--concurrency bounded:2 --memory-model release-acquire",
+            Some(UnstableFeature::UnstableOptions),
+            |p| p.verify_opts.memory_model == Some(MemoryModel::ReleaseAcquire),
+        );
+        // The memory model only applies to the threads of `--concurrency`.
+        let err = parse_unstable_enabled(
+            "--memory-model release-acquire",
+            UnstableFeature::UnstableOptions,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_bounds_checks_args() {
+        check("--no-bounds-checks", None, |p| {
+            !p.verify_opts.checks.bounds_on() && p.verify_opts.checks.overflow_on()
+        });
+        check("--no-default-checks", None, |p| !p.verify_opts.checks.bounds_on());
+        check("--no-overflow-checks", None, |p| p.verify_opts.checks.bounds_on());
+    }
+
+    #[test]
+    fn check_ui_unstable() {
+        check("--ui", Some(UnstableFeature::UnstableOptions), |p| p.verify_opts.ui);
+    }
+
+    #[test]
+    fn check_refcounts_unstable() {
+        check("--check-refcounts", Some(UnstableFeature::UnstableOptions), |p| {
+            p.verify_opts.check_refcounts
+        });
+    }
+
+    #[test]
+    fn check_snapshot() {
+        // Tests run from the package directory.
+        check("--snapshot STATE=Cargo.toml", Some(UnstableFeature::UnstableOptions), |p| {
+            p.verify_opts.snapshot
+                == [Snapshot { target: "STATE".to_string(), file: "Cargo.toml".into() }]
+        });
+        assert!(Snapshot::from_str("STATE").is_err());
+        assert!(Snapshot::from_str("=state.bin").is_err());
+
+        let err = parse_unstable_enabled(
+            "--snapshot STATE=missing.bin",
+            UnstableFeature::UnstableOptions,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn check_emit_unstable() {
+        check("--emit goto,mir", Some(UnstableFeature::UnstableOptions), |p| {
+            p.verify_opts.emit == [EmitKind::Goto, EmitKind::Mir]
+        });
+        check("--emit mir", Some(UnstableFeature::UnstableOptions), |p| {
+            p.verify_opts.emit == [EmitKind::Mir]
+        });
+    }
+
+    #[test]
+    fn check_compare_backend_parsing() {
+        let parse = |s: &str| CompareBackend::from_str(s);
+        assert_eq!(parse("minisat"), Ok(CompareBackend::Solver(CbmcSolver::Minisat)));
+        assert_eq!(parse("smt=z3"), Ok(CompareBackend::Solver(CbmcSolver::Smt("z3".into()))));
+        assert_eq!(
+            parse("bin=kissat"),
+            Ok(CompareBackend::Solver(CbmcSolver::Binary("kissat".into())))
+        );
+        assert!(parse("cbmc=").is_err());
+        assert!(parse("lean").is_err());
+        assert!(parse("foo=bar").is_err());
+        assert_eq!(parse("smt=z3").unwrap().to_string(), "smt=z3");
+
+        // The old output format doesn't report the properties to compare.
+        let err = parse_unstable_enabled(
+            "--compare-backend minisat --output-format old",
+            UnstableFeature::UnstableOptions,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_prove_no_panics_unstable() {
+        check_unstable_flag!("--prove-no-panics", prove_no_panics);
+
+        // Assuming that no panic occurs would make `--prove-no-panics` vacuous.
+        let err = parse_unstable_enabled(
+            "--prove-no-panics --prove-safety-only",
+            UnstableFeature::UnstableOptions,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_slice_assertions_unstable() {
+        check_unstable_flag!("--slice-assertions", slice_assertions);
+    }
+
     #[test]
     fn check_concrete_playback_unstable() {
         let check = |input: &str| {
@@ -1134,6 +1962,10 @@ fn check_concrete_playback_conflicts() {
             "kani --concrete-playback=inplace --output-format=old -Z concrete-playback test.rs",
             ErrorKind::ArgumentConflict,
         );
+        expect_validation_error(
+            "kani --concrete-playback=print --slice-assertions -Z concrete-playback -Z unstable-options test.rs",
+            ErrorKind::ArgumentConflict,
+        );
     }
 
     #[test]
@@ -1211,4 +2043,18 @@ fn check_no_assert_contracts() {
         let err = StandaloneArgs::try_parse_from(args).unwrap().validate().unwrap_err();
         assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
     }
+
+    #[test]
+    fn check_cbmc_contracts() {
+        let args = "kani input.rs --cbmc-contracts".split_whitespace();
+        let err = StandaloneArgs::try_parse_from(args).unwrap().validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_invariant_preservation() {
+        let args = "kani input.rs --check-invariant-preservation".split_whitespace();
+        let err = StandaloneArgs::try_parse_from(args).unwrap().validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
 }
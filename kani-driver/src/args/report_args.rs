@@ -0,0 +1,68 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the report subcommand
+
+use std::path::PathBuf;
+
+use crate::args::{CommonArgs, ValidateArgs};
+use clap::{Error, Parser, error::ErrorKind};
+use kani_metadata::UnstableFeature;
+
+/// Process the results of earlier runs of Kani.
+#[derive(Debug, Parser)]
+pub struct ReportArgs {
+    #[command(subcommand)]
+    pub command: ReportSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ReportSubcommand {
+    /// Merge the results of independent runs, e.g., the jobs of a sharded CI workflow, into a
+    /// single report.
+    Merge(ReportMergeArgs),
+}
+
+/// Merge the results that several runs wrote with `--statistics` into a single report. A harness
+/// that appears in more than one run is reported once. If the runs disagree on its status, the
+/// report lists the conflict and the command fails.
+#[derive(Debug, Parser)]
+pub struct ReportMergeArgs {
+    /// The results of each run. For a directory, every JSON file in it is read.
+    #[arg(required = true, value_name = "PATH")]
+    pub inputs: Vec<PathBuf>,
+
+    /// Write the merged report and its aggregated statistics to the given JSON file.
+    #[arg(long, value_name = "PATH")]
+    pub out: Option<PathBuf>,
+
+    /// Write a summary of the merged report to the given HTML file.
+    #[arg(long, value_name = "PATH")]
+    pub html: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub common_args: CommonArgs,
+}
+
+impl ValidateArgs for ReportArgs {
+    fn validate(&self) -> Result<(), Error> {
+        match &self.command {
+            ReportSubcommand::Merge(args) => args.validate(),
+        }
+    }
+}
+
+impl ValidateArgs for ReportMergeArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.common_args.validate()?;
+        if !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions) {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `report` subcommand is unstable and requires -Z {}",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
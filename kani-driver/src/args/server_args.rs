@@ -0,0 +1,81 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the subcommand handling of the server subcommand
+
+use std::path::PathBuf;
+
+use crate::args::{ValidateArgs, VerificationArgs};
+use clap::{Error, Parser, error::ErrorKind};
+use kani_metadata::UnstableFeature;
+
+#[derive(Debug, Parser)]
+pub struct CommonServerArgs {
+    /// Listen for clients on this port of the local host. By default, the server picks a free
+    /// port, which it prints when it starts.
+    #[arg(long, default_value_t = 0)]
+    pub port: u16,
+}
+
+/// Serve the requests of an editor extension for a package.
+#[derive(Debug, Parser)]
+pub struct CargoServerArgs {
+    #[command(flatten)]
+    pub common_server_args: CommonServerArgs,
+
+    #[command(flatten)]
+    pub verify_opts: VerificationArgs,
+}
+
+/// Serve the requests of an editor extension for a file.
+#[derive(Debug, Parser)]
+pub struct StandaloneServerArgs {
+    #[command(flatten)]
+    pub common_server_args: CommonServerArgs,
+
+    /// Rust crate's top file location.
+    #[arg(required = true)]
+    pub input: PathBuf,
+
+    #[arg(long, hide = true)]
+    pub crate_name: Option<String>,
+
+    #[command(flatten)]
+    pub verify_opts: VerificationArgs,
+}
+
+fn check_args(verify_opts: &VerificationArgs) -> Result<(), Error> {
+    if !verify_opts.common_args.unstable_features.contains(UnstableFeature::UnstableOptions) {
+        return Err(Error::raw(
+            ErrorKind::MissingRequiredArgument,
+            format!(
+                "The `server` subcommand is unstable and requires -Z {}",
+                UnstableFeature::UnstableOptions
+            ),
+        ));
+    }
+    Ok(())
+}
+
+impl ValidateArgs for CargoServerArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.verify_opts.validate()?;
+        check_args(&self.verify_opts)
+    }
+}
+
+impl ValidateArgs for StandaloneServerArgs {
+    fn validate(&self) -> Result<(), Error> {
+        self.verify_opts.validate()?;
+        check_args(&self.verify_opts)?;
+        if !self.input.is_file() {
+            return Err(Error::raw(
+                ErrorKind::InvalidValue,
+                format!(
+                    "Invalid argument: Input invalid. `{}` is not a regular file.",
+                    self.input.display()
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,29 +1,96 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use anyhow::{Result, bail};
+use crate::args::Timeout;
+use anyhow::{Context, Result, anyhow, bail};
+use cargo_metadata::{Metadata, Package};
 use clap::Parser;
-use std::collections::BTreeMap;
+use kani_metadata::{CbmcSolver, HarnessAttributes, HarnessMetadata, KaniMetadata, Stub};
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsString;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::str::FromStr;
+use std::time::Duration;
 use toml::Value;
 use toml::value::Table;
 
+/// The name of the Kani configuration file of a workspace or a package, which lives next to its
+/// `Cargo.toml`.
+pub const KANI_TOML: &str = "Kani.toml";
+
 /// Produce the list of arguments to pass to ourself (cargo-kani).
 ///
-/// The arguments passed via command line have precedence over the ones from the Cargo.toml.
+/// The arguments are taken from the following sources, from the lowest to the highest precedence:
+/// 1. The `kani` tables of the Cargo.toml.
+/// 2. The Kani.toml of the workspace.
+/// 3. The Kani.toml of the package, if it isn't the root of the workspace.
+/// 4. The command line.
 pub fn join_args(input_args: Vec<OsString>) -> Result<Vec<OsString>> {
-    let toml_path = cargo_locate_project(&input_args);
+    let toml_path = cargo_locate_project(Some(&input_args));
     if toml_path.is_err() {
         // We're not inside a Cargo project. Don't error... yet.
         return Ok(input_args);
     }
-    let file = std::fs::read_to_string(toml_path?)?;
-    let (kani_args, cbmc_args) = toml_to_args(&file)?;
+    let toml_path = toml_path?;
+    let file = std::fs::read_to_string(&toml_path)?;
+    let mut configs = vec![toml_to_args(&file)?];
+    for kani_toml in package_kani_toml_files(&toml_path) {
+        let file = std::fs::read_to_string(&kani_toml)?;
+        configs.push(
+            kani_toml_to_args(&file)
+                .with_context(|| format!("Invalid `{}`", kani_toml.display()))?,
+        );
+    }
+    let (kani_args, cbmc_args) = concat_args(configs);
     merge_args(input_args, kani_args, cbmc_args)
 }
 
+/// The Kani.toml files that apply to an invocation of Kani, which are the ones of the Cargo package
+/// of its `--manifest-path` for `cargo kani` arguments, and of the Cargo package that contains the
+/// current directory otherwise. There are none outside of a Cargo package.
+pub fn locate_kani_toml_files(cargo_kani_args: Option<&[OsString]>) -> Vec<PathBuf> {
+    cargo_locate_project(cargo_kani_args)
+        .map(|toml_path| package_kani_toml_files(&toml_path))
+        .unwrap_or_default()
+}
+
+/// The Kani.toml files of the package whose manifest is `toml_path` and of its workspace.
+fn package_kani_toml_files(toml_path: &Path) -> Vec<PathBuf> {
+    let workspace_path =
+        cargo_locate_workspace(toml_path).unwrap_or_else(|_| toml_path.to_path_buf());
+    kani_toml_files(&workspace_path, toml_path)
+}
+
+/// The Kani.toml files next to the manifests of a workspace and of one of its packages that exist,
+/// from the lowest to the highest precedence. This is how Kani finds all its Kani.toml files.
+fn kani_toml_files(workspace_path: &Path, package_path: &Path) -> Vec<PathBuf> {
+    let workspace_toml = workspace_path.with_file_name(KANI_TOML);
+    let package_toml = package_path.with_file_name(KANI_TOML);
+    let mut files = vec![workspace_toml];
+    if files[0] != package_toml {
+        files.push(package_toml);
+    }
+    files.retain(|file| file.is_file());
+    files
+}
+
+/// Concatenate the arguments of several configurations, such that the arguments of the later ones
+/// take precedence, with a single `--cbmc-args` flag.
+fn concat_args(configs: Vec<(Vec<OsString>, Vec<OsString>)>) -> (Vec<OsString>, Vec<OsString>) {
+    let mut kani_args = vec![];
+    let mut cbmc_args = vec![];
+    for (config_kani_args, config_cbmc_args) in configs {
+        kani_args.extend(config_kani_args);
+        // Skip the `--cbmc-args` flag of each configuration.
+        cbmc_args.extend(config_cbmc_args.into_iter().skip(1));
+    }
+    if !cbmc_args.is_empty() {
+        cbmc_args.insert(0, "--cbmc-args".into());
+    }
+    (kani_args, cbmc_args)
+}
+
 /// Join the arguments passed via command line with the ones found in the Cargo.toml.
 ///
 /// The arguments passed via command line have precedence over the ones from the Cargo.toml. Thus,
@@ -66,11 +133,13 @@ fn merge_args(
 }
 
 /// `locate-project` produces a response like: `/full/path/to/src/cargo-kani/Cargo.toml`
-fn cargo_locate_project(input_args: &[OsString]) -> Result<PathBuf> {
+fn cargo_locate_project(input_args: Option<&[OsString]>) -> Result<PathBuf> {
     // Try parsing our command line arguments as they presently look, to see if a "manifest-path" has been given.
-    let current_args = crate::args::CargoKaniArgs::parse_from(input_args);
+    let manifest_path = input_args.and_then(|input_args| {
+        crate::args::CargoKaniArgs::parse_from(input_args).verify_opts.cargo.manifest_path
+    });
 
-    if let Some(path) = current_args.verify_opts.cargo.manifest_path {
+    if let Some(path) = manifest_path {
         Ok(path)
     } else {
         let cmd =
@@ -85,6 +154,20 @@ fn cargo_locate_project(input_args: &[OsString]) -> Result<PathBuf> {
     }
 }
 
+/// The manifest of the workspace that the package of `toml_path` belongs to.
+fn cargo_locate_workspace(toml_path: &Path) -> Result<PathBuf> {
+    let cmd = Command::new("cargo")
+        .args(["locate-project", "--workspace", "--message-format", "plain", "--manifest-path"])
+        .arg(toml_path)
+        .output()?;
+    if !cmd.status.success() {
+        let err = std::str::from_utf8(&cmd.stderr)?;
+        bail!("{}", err);
+    }
+    let path = std::str::from_utf8(&cmd.stdout)?;
+    Ok(path.trim().into())
+}
+
 /// Parse a config toml string and extract the cargo-kani arguments we should try injecting.
 /// This returns two different vectors since all cbmc-args have to be at the end.
 /// We currently support the following entries:
@@ -97,30 +180,51 @@ fn cargo_locate_project(input_args: &[OsString]) -> Result<PathBuf> {
 /// - "kani"
 fn toml_to_args(tomldata: &str) -> Result<(Vec<OsString>, Vec<OsString>)> {
     let config = tomldata.parse::<Value>()?;
+    let tables = ["workspace.metadata.kani", "package.metadata.kani", "kani"];
+    tables_to_args(tables.iter().filter_map(|table| get_table(&config, table)))
+}
+
+/// Parse a Kani.toml string and extract the cargo-kani arguments we should try injecting.
+/// Its top-level `flags` and `unstable` tables are the same as the ones of the Cargo.toml, e.g.:
+/// ```toml
+/// [flags]
+/// default-unwind = "4"
+/// solver = "kissat"
+///
+/// [unstable]
+/// uninit-checks = true
+/// ```
+fn kani_toml_to_args(tomldata: &str) -> Result<(Vec<OsString>, Vec<OsString>)> {
+    let config = tomldata.parse::<Value>()?;
+    tables_to_args(config.as_table().into_iter())
+}
+
+/// Extract the arguments of the `flags` and `unstable` entries of the given tables. The flags of
+/// the later tables take precedence.
+fn tables_to_args<'a>(
+    tables: impl Iterator<Item = &'a Table>,
+) -> Result<(Vec<OsString>, Vec<OsString>)> {
     // To make testing easier, our function contract is to produce a stable ordering of flags for a given input.
     // Consequently, we use BTreeMap instead of HashMap here.
     let mut map: BTreeMap<String, Value> = BTreeMap::new();
-    let tables = ["workspace.metadata.kani", "package.metadata.kani", "kani"];
     let mut args = Vec::new();
 
     for table in tables {
-        if let Some(table) = get_table(&config, table) {
-            if let Some(entry) = table.get("flags")
-                && let Some(val) = entry.as_table()
-            {
-                map.extend(val.iter().map(|(x, y)| (x.to_owned(), y.to_owned())));
-            }
+        if let Some(entry) = table.get("flags")
+            && let Some(val) = entry.as_table()
+        {
+            map.extend(val.iter().map(|(x, y)| (x.to_owned(), y.to_owned())));
+        }
 
-            if let Some(entry) = table.get("unstable")
-                && let Some(val) = entry.as_table()
-            {
-                args.append(
-                    &mut val
-                        .iter()
-                        .filter_map(|(k, v)| unstable_entry(k, v).transpose())
-                        .collect::<Result<Vec<_>>>()?,
-                );
-            }
+        if let Some(entry) = table.get("unstable")
+            && let Some(val) = entry.as_table()
+        {
+            args.append(
+                &mut val
+                    .iter()
+                    .filter_map(|(k, v)| unstable_entry(k, v).transpose())
+                    .collect::<Result<Vec<_>>>()?,
+            );
         }
     }
 
@@ -218,6 +322,252 @@ fn get_table<'a>(start: &'a Value, table: &str) -> Option<&'a Table> {
     current.as_table()
 }
 
+/// The settings of a harness that a `[harness."<name>"]` table of a Kani.toml overrides, e.g.:
+/// ```toml
+/// [harness."module::check_parser"]
+/// unwind = 10
+/// solver = "kissat"
+/// timeout = "5m"
+/// stubs = ["no_io"]
+/// tags = ["parser", "slow"]
+/// ```
+///
+/// They take the place of the attributes of the harness, so the `--unwind` and `--solver` command
+/// line options still take precedence over them. The `stubs` are names of stub sets, which the
+/// compiler adds to the stubs of the harness, and `--tag` selects the harnesses by their `tags`.
+#[derive(Debug, Default, PartialEq)]
+struct HarnessOverrides {
+    unwind: Option<u32>,
+    solver: Option<CbmcSolver>,
+    timeout: Option<Duration>,
+    stubs: Option<Vec<String>>,
+    tags: Option<Vec<String>>,
+}
+
+impl HarnessOverrides {
+    fn apply(&self, attributes: &mut HarnessAttributes) {
+        if let Some(unwind) = self.unwind {
+            attributes.unwind_value = Some(unwind);
+        }
+        if let Some(solver) = &self.solver {
+            attributes.solver = Some(solver.clone());
+        }
+        if let Some(timeout) = self.timeout {
+            attributes.timeout = Some(timeout);
+        }
+    }
+}
+
+/// The harness settings of a Kani.toml: the overrides of its `[harness."<name>"]` tables, and the
+/// stub sets of its `[stubs."<set>"]` tables, which map the functions to stub to their
+/// replacements, e.g.:
+/// ```toml
+/// [stubs.no_io]
+/// "std::fs::read" = "mocks::read"
+/// ```
+///
+/// Like the paths of `#[kani::stub]`, the paths of a stub set are relative to the module of the
+/// harness that uses it.
+#[derive(Debug, Default, PartialEq)]
+struct HarnessConfig {
+    overrides: BTreeMap<String, HarnessOverrides>,
+    stub_sets: BTreeMap<String, Vec<Stub>>,
+}
+
+/// Apply the harness overrides of the Kani.toml of the workspace, and then of the Kani.toml of the
+/// package of each crate, to the harnesses of the crate.
+///
+/// If `tags` isn't empty, only keep the harnesses that have one of them.
+pub fn apply_harness_overrides(
+    metadata: &mut [KaniMetadata],
+    cargo_metadata: &Metadata,
+    tags: &[String],
+) -> Result<()> {
+    for crate_metadata in metadata {
+        let package = cargo_metadata.packages.iter().find(|package| {
+            package
+                .targets
+                .iter()
+                .any(|target| target.name.replace('-', "_") == crate_metadata.crate_name)
+        });
+        let configs = package_harness_configs(cargo_metadata, package)?;
+        let harnesses = crate_metadata
+            .proof_harnesses
+            .iter_mut()
+            .chain(crate_metadata.test_harnesses.iter_mut());
+        for harness in harnesses {
+            for config in &configs {
+                if let Some(overrides) = config.overrides.get(&harness.pretty_name) {
+                    overrides.apply(&mut harness.attributes);
+                }
+            }
+        }
+        if !tags.is_empty() {
+            let has_tag = |harness: &HarnessMetadata| {
+                harness_setting(&configs, &harness.pretty_name, |overrides| overrides.tags.as_ref())
+                    .is_some_and(|harness_tags| harness_tags.iter().any(|tag| tags.contains(tag)))
+            };
+            crate_metadata.proof_harnesses.retain(has_tag);
+            crate_metadata.test_harnesses.retain(has_tag);
+        }
+    }
+    Ok(())
+}
+
+/// The `--harness-stub` arguments of the compiler for the stub sets that the Kani.toml files of
+/// `package` assign to its harnesses.
+pub fn harness_stub_args(cargo_metadata: &Metadata, package: &Package) -> Result<Vec<String>> {
+    let configs = package_harness_configs(cargo_metadata, Some(package))?;
+    harness_stubs(&configs)
+}
+
+/// The `--harness-stub` arguments for the stub sets of the harnesses of the given configurations.
+fn harness_stubs(configs: &[HarnessConfig]) -> Result<Vec<String>> {
+    let harnesses: BTreeSet<&String> =
+        configs.iter().flat_map(|config| config.overrides.keys()).collect();
+    let mut args = vec![];
+    for harness in harnesses {
+        let Some(sets) = harness_setting(configs, harness, |overrides| overrides.stubs.as_ref())
+        else {
+            continue;
+        };
+        for set in sets {
+            let Some(stubs) = configs.iter().rev().find_map(|config| config.stub_sets.get(set))
+            else {
+                bail!("Unknown stub set `{set}` for harness `{harness}`");
+            };
+            args.extend(stubs.iter().map(|stub| {
+                format!("--harness-stub={harness}={}={}", stub.original, stub.replacement)
+            }));
+        }
+    }
+    Ok(args)
+}
+
+/// The setting of a harness in the configuration with the highest precedence that has one.
+fn harness_setting<'a, T>(
+    configs: &'a [HarnessConfig],
+    harness: &str,
+    setting: impl Fn(&'a HarnessOverrides) -> Option<&'a T>,
+) -> Option<&'a T> {
+    configs.iter().rev().find_map(|config| setting(config.overrides.get(harness)?))
+}
+
+/// Read the harness settings of the Kani.toml files of the workspace and of `package`, from the
+/// lowest to the highest precedence. Only the workspace ones apply if there's no package.
+fn package_harness_configs(
+    cargo_metadata: &Metadata,
+    package: Option<&Package>,
+) -> Result<Vec<HarnessConfig>> {
+    let workspace_manifest = cargo_metadata.workspace_root.as_std_path().join("Cargo.toml");
+    let package_manifest =
+        package.map(|package| package.manifest_path.as_std_path()).unwrap_or(&workspace_manifest);
+    kani_toml_files(&workspace_manifest, package_manifest)
+        .iter()
+        .map(|path| {
+            let file = std::fs::read_to_string(path)?;
+            toml_to_harness_config(&file).with_context(|| format!("Invalid `{}`", path.display()))
+        })
+        .collect()
+}
+
+/// Parse the `harness` and `stubs` tables of a Kani.toml string. The former maps the names of
+/// harnesses to the settings they override, and the latter the names of stub sets to their stubs.
+fn toml_to_harness_config(tomldata: &str) -> Result<HarnessConfig> {
+    let config = tomldata.parse::<Value>()?;
+    Ok(HarnessConfig {
+        overrides: toml_to_harness_overrides(&config)?,
+        stub_sets: toml_to_stub_sets(&config)?,
+    })
+}
+
+/// Parse the `harness` table of a Kani.toml.
+fn toml_to_harness_overrides(config: &Value) -> Result<BTreeMap<String, HarnessOverrides>> {
+    let Some(harnesses) = config.get("harness") else {
+        return Ok(BTreeMap::new());
+    };
+    let Some(harnesses) = harnesses.as_table() else {
+        bail!("Expected `harness` to be a table of harness names");
+    };
+    let mut overrides = BTreeMap::new();
+    for (name, table) in harnesses {
+        let Some(table) = table.as_table() else {
+            bail!("Expected the overrides of harness `{name}` to be a table");
+        };
+        let mut harness = HarnessOverrides::default();
+        for (key, value) in table {
+            match (key.as_str(), value) {
+                ("unwind", Value::Integer(unwind)) => {
+                    harness.unwind = Some(u32::try_from(*unwind).map_err(|_| {
+                        anyhow!("Invalid unwind value `{unwind}` for harness `{name}`")
+                    })?);
+                }
+                ("solver", Value::String(solver)) => {
+                    harness.solver =
+                        Some(CbmcSolver::from_str(solver).map_err(|_| {
+                            anyhow!("Unknown solver `{solver}` for harness `{name}`")
+                        })?);
+                }
+                ("timeout", Value::String(timeout)) => {
+                    let timeout = Timeout::from_str(timeout)
+                        .map_err(|err| anyhow!("{err} for harness `{name}`"))?;
+                    harness.timeout = Some(timeout.into());
+                }
+                ("stubs", Value::Array(sets)) => {
+                    harness.stubs = Some(string_array(sets).ok_or_else(|| {
+                        anyhow!("Expected the stubs of harness `{name}` to be names of stub sets")
+                    })?);
+                }
+                ("tags", Value::Array(tags)) => {
+                    harness.tags = Some(string_array(tags).ok_or_else(|| {
+                        anyhow!("Expected the tags of harness `{name}` to be strings")
+                    })?);
+                }
+                ("unwind" | "solver" | "timeout" | "stubs" | "tags", _) => {
+                    bail!("Invalid value `{value}` for `{key}` of harness `{name}`")
+                }
+                _ => bail!("Unknown setting `{key}` for harness `{name}`"),
+            }
+        }
+        overrides.insert(name.clone(), harness);
+    }
+    Ok(overrides)
+}
+
+/// Parse the `stubs` table of a Kani.toml.
+fn toml_to_stub_sets(config: &Value) -> Result<BTreeMap<String, Vec<Stub>>> {
+    let Some(sets) = config.get("stubs") else {
+        return Ok(BTreeMap::new());
+    };
+    let Some(sets) = sets.as_table() else {
+        bail!("Expected `stubs` to be a table of stub set names");
+    };
+    let mut stub_sets = BTreeMap::new();
+    for (name, table) in sets {
+        let Some(table) = table.as_table() else {
+            bail!("Expected the stub set `{name}` to be a table of functions to stub");
+        };
+        let stubs = table
+            .iter()
+            .map(|(original, replacement)| match replacement {
+                Value::String(replacement) => {
+                    Ok(Stub { original: original.clone(), replacement: replacement.clone() })
+                }
+                _ => bail!(
+                    "Invalid replacement `{replacement}` of `{original}` in stub set `{name}`"
+                ),
+            })
+            .collect::<Result<_>>()?;
+        stub_sets.insert(name.clone(), stubs);
+    }
+    Ok(stub_sets)
+}
+
+/// The strings of a toml array, if all its values are strings.
+fn string_array(values: &[Value]) -> Option<Vec<String>> {
+    values.iter().map(|value| value.as_str().map(String::from)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -235,6 +585,159 @@ fn check_toml_parsing() {
         assert_eq!(b.1, vec!["--cbmc-args", "--fake"]);
     }
 
+    #[test]
+    fn check_kani_toml_parsing() {
+        let data = "[flags]
+                    default-unwind = \"4\"
+                    cbmc-args = [\"--fake\"]
+                    [unstable]
+                    uninit-checks = true
+                    [tools]
+                    cbmc = \"6.7.1\"
+                    [harness.check_foo]
+                    unwind = 10";
+        let (kani_args, cbmc_args) = kani_toml_to_args(data).unwrap();
+        assert_eq!(kani_args, vec!["-Zuninit-checks", "--default-unwind", "4"]);
+        assert_eq!(cbmc_args, vec!["--cbmc-args", "--fake"]);
+    }
+
+    #[test]
+    fn check_concat_args_precedence() {
+        let workspace = (
+            vec!["--default-unwind".into(), "2".into()],
+            vec!["--cbmc-args".into(), "--trace".into()],
+        );
+        let package = (
+            vec!["--default-unwind".into(), "4".into()],
+            vec!["--cbmc-args".into(), "--fake".into()],
+        );
+        let (kani_args, cbmc_args) = concat_args(vec![workspace, (vec![], vec![]), package]);
+        // The later `--default-unwind` wins, since clap lets arguments override themselves.
+        assert_eq!(kani_args, vec!["--default-unwind", "2", "--default-unwind", "4"]);
+        assert_eq!(cbmc_args, vec!["--cbmc-args", "--trace", "--fake"]);
+        assert_eq!(concat_args(vec![]), (vec![], vec![]));
+    }
+
+    #[test]
+    fn check_harness_overrides_parsing() {
+        let data = "[harness.\"module::check_foo\"]
+                    unwind = 10
+                    solver = \"kissat\"
+                    timeout = \"5m\"
+                    tags = [\"parser\"]
+                    [harness.check_bar]
+                    unwind = 2
+                    stubs = [\"no_io\"]";
+        let overrides = toml_to_harness_config(data).unwrap().overrides;
+        assert_eq!(
+            overrides["module::check_foo"],
+            HarnessOverrides {
+                unwind: Some(10),
+                solver: Some(CbmcSolver::Kissat),
+                timeout: Some(Duration::from_secs(300)),
+                tags: Some(vec!["parser".into()]),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            overrides["check_bar"],
+            HarnessOverrides {
+                unwind: Some(2),
+                stubs: Some(vec!["no_io".into()]),
+                ..Default::default()
+            }
+        );
+        assert!(toml_to_harness_config("[flags]\nunwind = \"2\"").unwrap().overrides.is_empty());
+    }
+
+    #[test]
+    fn check_invalid_harness_overrides() {
+        assert!(toml_to_harness_config("[harness.check_foo]\nunwind = -1").is_err());
+        assert!(toml_to_harness_config("[harness.check_foo]\nunwind = \"2\"").is_err());
+        assert!(toml_to_harness_config("[harness.check_foo]\nsolver = \"none\"").is_err());
+        assert!(toml_to_harness_config("[harness.check_foo]\nstubs = \"no_io\"").is_err());
+        assert!(toml_to_harness_config("[harness.check_foo]\ntags = [1]").is_err());
+        assert!(toml_to_harness_config("[harness.check_foo]\nloops = 1").is_err());
+        assert!(toml_to_harness_config("harness = 1").is_err());
+    }
+
+    #[test]
+    fn check_stub_sets_parsing() {
+        let data = "[stubs.no_io]
+                    \"std::fs::read\" = \"mocks::read\"
+                    \"std::fs::write\" = \"mocks::write\"";
+        let stub_sets = toml_to_harness_config(data).unwrap().stub_sets;
+        assert_eq!(
+            stub_sets["no_io"],
+            vec![
+                Stub { original: "std::fs::read".into(), replacement: "mocks::read".into() },
+                Stub { original: "std::fs::write".into(), replacement: "mocks::write".into() },
+            ]
+        );
+        assert!(toml_to_harness_config("[stubs.no_io]\n\"std::fs::read\" = 1").is_err());
+        assert!(toml_to_harness_config("stubs = [\"no_io\"]").is_err());
+    }
+
+    #[test]
+    fn check_harness_stubs_precedence() {
+        let workspace = toml_to_harness_config(
+            "[stubs.no_io]
+             \"std::fs::read\" = \"mocks::read\"
+             [stubs.no_time]
+             \"std::time::Instant::now\" = \"mocks::now\"
+             [harness.check_foo]
+             stubs = [\"no_io\"]
+             [harness.check_bar]
+             stubs = [\"no_io\"]",
+        )
+        .unwrap();
+        // The package redefines the `no_io` set and the stubs of `check_bar`.
+        let package = toml_to_harness_config(
+            "[stubs.no_io]
+             \"std::fs::read\" = \"package_mocks::read\"
+             [harness.check_bar]
+             stubs = [\"no_time\"]",
+        )
+        .unwrap();
+        assert_eq!(
+            harness_stubs(&[workspace, package]).unwrap(),
+            vec![
+                "--harness-stub=check_bar=std::time::Instant::now=mocks::now",
+                "--harness-stub=check_foo=std::fs::read=package_mocks::read",
+            ]
+        );
+
+        let unknown = toml_to_harness_config("[harness.check_foo]\nstubs = [\"no_io\"]").unwrap();
+        assert!(harness_stubs(&[unknown]).is_err());
+    }
+
+    #[test]
+    fn check_kani_toml_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let package_dir = dir.path().join("package");
+        std::fs::create_dir(&package_dir).unwrap();
+        let workspace_manifest = dir.path().join("Cargo.toml");
+        let package_manifest = package_dir.join("Cargo.toml");
+        assert!(kani_toml_files(&workspace_manifest, &package_manifest).is_empty());
+
+        std::fs::write(package_dir.join(KANI_TOML), "").unwrap();
+        assert_eq!(
+            kani_toml_files(&workspace_manifest, &package_manifest),
+            vec![package_dir.join(KANI_TOML)]
+        );
+
+        std::fs::write(dir.path().join(KANI_TOML), "").unwrap();
+        assert_eq!(
+            kani_toml_files(&workspace_manifest, &package_manifest),
+            vec![dir.path().join(KANI_TOML), package_dir.join(KANI_TOML)]
+        );
+        // The root package of a workspace has a single Kani.toml.
+        assert_eq!(
+            kani_toml_files(&workspace_manifest, &workspace_manifest),
+            vec![dir.path().join(KANI_TOML)]
+        );
+    }
+
     #[test]
     fn check_merge_args_with_only_command_line_args() {
         let cmd_args: Vec<OsString> =
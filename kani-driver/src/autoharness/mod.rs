@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use std::str::FromStr;
+use std::time::Duration;
 
 use crate::args::Timeout;
 use crate::args::autoharness_args::{
-    CargoAutoharnessArgs, CommonAutoharnessArgs, StandaloneAutoharnessArgs,
+    CargoAutoharnessArgs, CommonAutoharnessArgs, StandaloneAutoharnessArgs, glob_to_regex,
 };
 use crate::args::common::UnstableFeature;
 use crate::call_cbmc::VerificationStatus;
@@ -54,10 +55,7 @@ pub fn autoharness_standalone(args: StandaloneAutoharnessArgs) -> Result<()> {
 fn setup_session(session: &mut KaniSession, common_autoharness_args: &CommonAutoharnessArgs) {
     session.enable_autoharness();
     session.add_default_bounds();
-    session.add_auto_harness_args(
-        &common_autoharness_args.include_pattern,
-        &common_autoharness_args.exclude_pattern,
-    );
+    session.add_auto_harness_args(common_autoharness_args);
 }
 
 /// After generating the automatic harnesses, postprocess metadata and run verification.
@@ -108,6 +106,7 @@ fn print_autoharness_metadata(metadata: Vec<KaniMetadata>) {
                 ]),
                 AutoHarnessSkipReason::GenericFn
                 | AutoHarnessSkipReason::NoBody
+                | AutoHarnessSkipReason::NotPublic
                 | AutoHarnessSkipReason::UserFilter => {
                     Some(vec![md.crate_name.clone(), func, reason.to_string()])
                 }
@@ -161,24 +160,42 @@ pub fn enable_autoharness(&mut self) {
     }
 
     /// Add the compiler arguments specific to the `autoharness` subcommand.
-    pub fn add_auto_harness_args(&mut self, included: &[String], excluded: &[String]) {
+    pub fn add_auto_harness_args(&mut self, common_autoharness_args: &CommonAutoharnessArgs) {
         let mut args = vec![];
-        for pattern in included {
+        for pattern in &common_autoharness_args.include_pattern {
             args.push(format!("--autoharness-include-pattern {pattern}"));
         }
-        for pattern in excluded {
+        for pattern in &common_autoharness_args.exclude_pattern {
             args.push(format!("--autoharness-exclude-pattern {pattern}"));
         }
+        for pattern in &common_autoharness_args.exclude_file_pattern {
+            args.push(format!("--autoharness-exclude-file-pattern {}", glob_to_regex(pattern)));
+        }
+        if common_autoharness_args.exclude_expanded {
+            args.push("--autoharness-exclude-expanded".to_string());
+        }
+        if common_autoharness_args.extern_c {
+            args.push("--autoharness-extern-c".to_string());
+            args.push(format!(
+                "--autoharness-extern-c-buffer-len {}",
+                common_autoharness_args.extern_c_buffer_len
+            ));
+        }
+        if common_autoharness_args.traits {
+            args.push("--autoharness-traits".to_string());
+        }
+        if self.args.prove_no_panics {
+            args.push("--autoharness-public-only".to_string());
+        }
         self.autoharness_compiler_flags = Some(args);
+        self.autoharness_budget = common_autoharness_args.total_timeout.map(Duration::from);
     }
 
     /// Add global harness timeout and loop unwinding bounds if not provided.
     /// These prevent automatic harnesses from hanging.
     pub fn add_default_bounds(&mut self) {
-        if self.args.harness_timeout.is_none() {
-            let timeout = Timeout::from_str(AUTOHARNESS_TIMEOUT).unwrap();
-            self.args.harness_timeout = Some(timeout);
-        }
+        let timeout = Timeout::from_str(AUTOHARNESS_TIMEOUT).unwrap();
+        self.default_harness_timeout = Some(timeout.into());
         if self.args.default_unwind.is_none() {
             self.args.default_unwind = Some(LOOP_UNWIND_DEFAULT);
         }
@@ -238,11 +255,24 @@ pub fn print_autoharness_summary(
             );
         }
 
+        let mut skipped = self.skipped_by_budget.lock().unwrap().clone();
+        if !skipped.is_empty() {
+            skipped.sort();
+            let mut skipped_fns = PrettyTable::new();
+            skipped_fns.set_header(vec!["Crate", "Skipped Function"]);
+            skipped_fns.add_rows(skipped.iter().map(|(krate, func)| vec![krate, func]));
+            println!(
+                "Kani did not verify {} function(s) because the --total-timeout ran out before their verification started:",
+                skipped.len()
+            );
+            println!("{skipped_fns}");
+        }
+
         if total > 0 {
             println!(
                 "Complete - {succeeding} successfully verified functions, {failing} failures, {total} total."
             );
-        } else {
+        } else if skipped.is_empty() {
             println!("No functions were eligible for automatic verification.");
         }
 
@@ -0,0 +1,327 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the `baseline` subcommand, which lets a codebase with known failures adopt Kani
+//! incrementally.
+//!
+//! `baseline record` verifies the harnesses and writes the status of every property to the baseline
+//! file. `baseline compare` verifies them again and reports the properties that fail but didn't
+//! fail in the baseline, the ones that failed in the baseline but no longer do, and the ones that
+//! are new. It only fails if a property fails that didn't fail in the baseline.
+//!
+//! Properties are identified by their harness, their class, their description and their source
+//! location, rather than by their name, e.g., `foo.assertion.1`, whose number changes whenever the
+//! properties that CBMC numbers before it change.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use crate::args::baseline_args::{
+    BaselineAction, CargoBaselineArgs, CommonBaselineArgs, StandaloneBaselineArgs,
+};
+use crate::cbmc_output_parser::CheckStatus;
+use crate::harness_runner::{HarnessResult, HarnessRunner};
+use crate::project::{self, Project};
+use crate::session::KaniSession;
+use crate::util::warning;
+use crate::{InvocationType, print_kani_version};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BaselineFile {
+    properties: Vec<PropertyEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct PropertyEntry {
+    harness: String,
+    class: String,
+    description: String,
+    location: String,
+    /// Tells apart the properties that have the same harness, class, description and location,
+    /// in the order in which CBMC reports them.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    occurrence: usize,
+    status: CheckStatus,
+}
+
+/// The fields that identify a property.
+type PropertyKey<'a> = (&'a str, &'a str, &'a str, &'a str, usize);
+
+impl PropertyEntry {
+    fn fails(&self) -> bool {
+        self.status == CheckStatus::Failure
+    }
+
+    fn key(&self) -> PropertyKey<'_> {
+        (&self.harness, &self.class, &self.description, &self.location, self.occurrence)
+    }
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// How the current results differ from the baseline.
+#[derive(Debug, Default, PartialEq)]
+struct BaselineDiff<'a> {
+    newly_failing: Vec<&'a PropertyEntry>,
+    newly_passing: Vec<&'a PropertyEntry>,
+    added: Vec<&'a PropertyEntry>,
+    /// The properties of the baseline that the verified harnesses no longer have.
+    removed: Vec<&'a PropertyEntry>,
+}
+
+impl BaselineDiff<'_> {
+    /// Whether a property fails that didn't fail in the baseline.
+    fn has_regressions(&self) -> bool {
+        !self.newly_failing.is_empty() || self.added.iter().any(|entry| entry.fails())
+    }
+}
+
+pub fn baseline_cargo(args: CargoBaselineArgs) -> Result<()> {
+    let mut session = KaniSession::new(args.verify_opts)?;
+    if !session.args.common_args.quiet {
+        print_kani_version(InvocationType::CargoKani(vec![]));
+    }
+    let project = project::cargo_project(&mut session, false)?;
+    run_baseline(project, session, args.common_baseline_args)
+}
+
+pub fn baseline_standalone(args: StandaloneBaselineArgs) -> Result<()> {
+    let session = KaniSession::new(args.verify_opts)?;
+    if !session.args.common_args.quiet {
+        print_kani_version(InvocationType::Standalone);
+    }
+    let project = project::standalone_project(&args.input, args.crate_name, &session)?;
+    run_baseline(project, session, args.common_baseline_args)
+}
+
+fn run_baseline(project: Project, session: KaniSession, args: CommonBaselineArgs) -> Result<()> {
+    if session.args.only_codegen {
+        return Ok(());
+    }
+    let harnesses = session.determine_targets(project.get_all_harnesses())?;
+    let runner = HarnessRunner { sess: &session, project: &project };
+    let results = runner.check_all_harnesses(&harnesses)?;
+    let (current, incomplete) = property_entries(&results);
+    let quiet = session.args.common_args.quiet;
+
+    match args.action {
+        BaselineAction::Record => {
+            for harness in &incomplete {
+                warning(&format!(
+                    "The baseline doesn't include the harness `{harness}`, since its verification \
+                    didn't complete."
+                ));
+            }
+            let baseline = BaselineFile { properties: current };
+            std::fs::write(&args.baseline_file, serde_json::to_string_pretty(&baseline)?)
+                .with_context(|| {
+                    format!("Failed to write baseline to `{}`", args.baseline_file.display())
+                })?;
+            if !quiet {
+                println!(
+                    "Recorded the status of {} properties of {} harnesses in `{}`.",
+                    baseline.properties.len(),
+                    results.len() - incomplete.len(),
+                    args.baseline_file.display()
+                );
+            }
+            Ok(())
+        }
+        BaselineAction::Compare => {
+            let baseline = read_baseline(&args.baseline_file)?;
+            let diff = compare(&baseline.properties, &current);
+            if !quiet {
+                print_diff(&diff, &incomplete, &args.baseline_file);
+            }
+            if diff.has_regressions() || !incomplete.is_empty() {
+                // Failure exit code without additional error message
+                drop(session);
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Return the entries of the properties of every harness, sorted by their key, and the names of the
+/// harnesses whose verification didn't report the status of their properties.
+fn property_entries<'a>(results: &'a [HarnessResult<'_>]) -> (Vec<PropertyEntry>, Vec<&'a str>) {
+    let mut entries = vec![];
+    let mut incomplete = vec![];
+    for result in results {
+        let harness = result.harness.pretty_name.as_str();
+        let Ok(properties) = &result.result.results else {
+            incomplete.push(harness);
+            continue;
+        };
+        let mut occurrences: HashMap<(String, String, String), usize> = HashMap::new();
+        for prop in properties
+            .iter()
+            .filter(|prop| !prop.is_code_coverage_property() && !prop.is_probe_property())
+        {
+            let class = prop.property_class();
+            let location = prop.source_location.to_string();
+            let occurrence = occurrences
+                .entry((class.clone(), prop.description.clone(), location.clone()))
+                .or_default();
+            entries.push(PropertyEntry {
+                harness: harness.to_string(),
+                class,
+                description: prop.description.clone(),
+                location,
+                occurrence: *occurrence,
+                status: prop.status,
+            });
+            *occurrence += 1;
+        }
+    }
+    entries.sort_by(|a, b| a.key().cmp(&b.key()));
+    (entries, incomplete)
+}
+
+fn read_baseline(path: &Path) -> Result<BaselineFile> {
+    let content = std::fs::read_to_string(path).with_context(|| {
+        format!(
+            "Failed to read baseline from `{}`. Run `baseline record` to create it",
+            path.display()
+        )
+    })?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse baseline from `{}`", path.display()))
+}
+
+/// Compare the current properties to the baseline. Only the harnesses that were verified are
+/// compared, so that verifying a subset of the harnesses doesn't report the others as removed.
+fn compare<'a>(baseline: &'a [PropertyEntry], current: &'a [PropertyEntry]) -> BaselineDiff<'a> {
+    let verified: BTreeSet<&str> = current.iter().map(|entry| entry.harness.as_str()).collect();
+    let recorded: HashMap<PropertyKey, &PropertyEntry> =
+        baseline.iter().map(|entry| (entry.key(), entry)).collect();
+    let mut diff = BaselineDiff::default();
+    for entry in current {
+        match recorded.get(&entry.key()) {
+            None => diff.added.push(entry),
+            Some(old) if entry.fails() && !old.fails() => diff.newly_failing.push(entry),
+            Some(old) if !entry.fails() && old.fails() => diff.newly_passing.push(entry),
+            Some(_) => {}
+        }
+    }
+    let present: BTreeSet<PropertyKey> = current.iter().map(PropertyEntry::key).collect();
+    diff.removed = baseline
+        .iter()
+        .filter(|entry| verified.contains(entry.harness.as_str()))
+        .filter(|entry| !present.contains(&entry.key()))
+        .collect();
+    diff
+}
+
+fn print_diff(diff: &BaselineDiff, incomplete: &[&str], path: &Path) {
+    println!("\nBaseline Comparison:");
+    let print_entries = |title: &str, entries: &[&PropertyEntry]| {
+        for entry in entries {
+            println!(
+                " - {title}: {} {} at {} ({}): {}",
+                entry.harness, entry.class, entry.location, entry.description, entry.status
+            );
+        }
+    };
+    print_entries("Newly failing", &diff.newly_failing);
+    print_entries("Newly passing", &diff.newly_passing);
+    print_entries("New", &diff.added);
+    for harness in incomplete {
+        println!(" - Verification didn't complete for harness {harness}");
+    }
+    println!(
+        " ** {} newly failing, {} newly passing, {} new and {} removed properties compared to `{}`",
+        diff.newly_failing.len(),
+        diff.newly_passing.len(),
+        diff.added.len(),
+        diff.removed.len(),
+        path.display()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CheckStatus::*;
+
+    /// An entry for the assertion at `line` of the harness.
+    fn entry(harness: &str, line: usize, status: CheckStatus) -> PropertyEntry {
+        PropertyEntry {
+            harness: harness.to_string(),
+            class: "assertion".to_string(),
+            description: "assertion failed: x".to_string(),
+            location: format!("src/lib.rs:{line}:5 in function {harness}"),
+            occurrence: 0,
+            status,
+        }
+    }
+
+    #[test]
+    fn check_compare() {
+        let baseline = [
+            entry("check_a", 1, Failure),
+            entry("check_a", 2, Success),
+            entry("check_a", 3, Success),
+            entry("check_b", 1, Failure),
+            entry("check_c", 1, Failure),
+        ];
+        // `check_c` wasn't verified, so it isn't reported as removed.
+        let current = [
+            entry("check_a", 1, Success),
+            entry("check_a", 2, Failure),
+            entry("check_b", 1, Failure),
+            entry("check_b", 2, Success),
+        ];
+        let diff = compare(&baseline, &current);
+        assert_eq!(
+            diff,
+            BaselineDiff {
+                newly_failing: vec![&current[1]],
+                newly_passing: vec![&current[0]],
+                added: vec![&current[3]],
+                removed: vec![&baseline[2]],
+            }
+        );
+        assert!(diff.has_regressions());
+    }
+
+    #[test]
+    fn check_known_failures_are_not_regressions() {
+        let baseline = [entry("check_a", 1, Failure)];
+        let current = [
+            entry("check_a", 1, Failure),
+            PropertyEntry { class: "cover".to_string(), ..entry("check_a", 1, Unsatisfiable) },
+        ];
+        let diff = compare(&baseline, &current);
+        assert!(!diff.has_regressions());
+        let current = [entry("check_a", 1, Failure), entry("check_b", 1, Failure)];
+        assert!(compare(&baseline, &current).has_regressions());
+    }
+
+    #[test]
+    fn check_properties_are_matched_by_location() {
+        let mut reordered = entry("check_a", 2, Failure);
+        reordered.description = "known failure".to_string();
+        let baseline = [entry("check_a", 1, Success), reordered.clone()];
+        // The same properties, which CBMC reports in another order, e.g., with other numbers.
+        let current = [reordered, entry("check_a", 1, Success)];
+        let diff = compare(&baseline, &current);
+        assert_eq!(diff, BaselineDiff::default());
+    }
+
+    #[test]
+    fn check_duplicate_properties_are_told_apart() {
+        let baseline = [entry("check_a", 1, Success)];
+        let current = [
+            entry("check_a", 1, Success),
+            PropertyEntry { occurrence: 1, ..entry("check_a", 1, Failure) },
+        ];
+        let diff = compare(&baseline, &current);
+        assert_eq!(diff.added, vec![&current[1]]);
+        assert!(diff.has_regressions());
+    }
+}
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use crate::args::VerificationArgs;
+use crate::args_toml::harness_stub_args;
 use crate::call_single_file::LibConfig;
+use crate::fix::is_type_definition;
 use crate::project::Artifact;
 use crate::session::{
     KaniSession, get_cargo_path, lib_folder, lib_no_core_folder, setup_cargo_command,
@@ -131,17 +133,20 @@ pub fn cargo_build_std(&self, std_path: &Path, krate_path: &Path) -> Result<Vec<
             .collect())
     }
 
+    /// The directory where Kani builds the package, which only contains Kani artifacts.
+    pub fn kani_target_dir(&self, metadata: &Metadata) -> PathBuf {
+        self.args
+            .target_dir
+            .clone()
+            .unwrap_or_else(|| metadata.target_directory.clone().into())
+            .join("kani")
+    }
+
     /// Calls `cargo_build` to generate `*.symtab.json` files in `target_dir`
     pub fn cargo_build(&mut self, keep_going: bool) -> Result<CargoOutputs> {
         let build_target = env!("TARGET"); // see build.rs
         let metadata = self.cargo_metadata(build_target)?;
-        let target_dir = self
-            .args
-            .target_dir
-            .as_ref()
-            .unwrap_or(&metadata.target_directory.clone().into())
-            .clone()
-            .join("kani");
+        let target_dir = self.kani_target_dir(&metadata);
         let outdir = target_dir.join(build_target).join("debug/deps");
 
         if self.args.force_build && target_dir.exists() {
@@ -207,9 +212,16 @@ pub fn cargo_build(&mut self, keep_going: bool) -> Result<CargoOutputs> {
 
         let mut found_target = false;
         let packages = self.packages_to_verify(&self.args, &metadata)?;
+        for manifest in self.dependency_contract_manifests(&metadata, &packages) {
+            kani_pkg_args.push(format!("--verified-contracts {}", manifest.display()).into());
+        }
         let mut artifacts = vec![];
         let mut failed_targets = vec![];
         for package in packages {
+            // The stubs that the Kani.toml files of the package assign to its harnesses.
+            let mut kani_pkg_args = kani_pkg_args.clone();
+            kani_pkg_args
+                .extend(harness_stub_args(&metadata, package)?.into_iter().map(KaniArg::from));
             for verification_target in package_targets(&self.args, package) {
                 let mut cmd =
                     setup_cargo_command_inner(Some(verification_target.target().name.clone()))?;
@@ -298,6 +310,13 @@ fn run_build(&self, cargo_cmd: Command) -> Result<Vec<RustcArtifact>> {
                     DiagnosticLevel::Error => {
                         error_count += 1;
                         print_msg(&msg.message, support_color)?;
+                        self.compiler_errors.lock().unwrap().push(msg);
+                    }
+                    DiagnosticLevel::Note
+                        if self.print_type_definitions
+                            && is_type_definition(&msg.message.message) =>
+                    {
+                        self.type_definitions.lock().unwrap().push(msg);
                     }
                     DiagnosticLevel::Ice => {
                         print_msg(&msg.message, support_color)?;
@@ -418,12 +437,14 @@ fn to_package_ids<'a>(
     /// The result is built following these rules (mimicking cargo, see
     /// https://github.com/rust-lang/cargo/blob/master/src/cargo/core/workspace.rs):
     /// - If `--package <pkg>` is given, return the list of packages selected.
-    /// - If `--exclude <pkg>` is given, return the list of packages not excluded.
     /// - If `--workspace` is given, return the list of workspace members.
     /// - Else obtain the set of packages from cargo's default_workspace_members (i.e., if
     ///   `default-members` is specified in Cargo.toml, use that list; else if a root package is
     ///   specified use that; else use all members).
     ///
+    /// The companions of these packages are added to the list, and then the packages given with
+    /// `--exclude <pkg>` are removed from it.
+    ///
     /// In addition, if either `--package <pkg>` or `--exclude <pkg>` is given,
     /// validate that `<pkg>` is a package name in the workspace, or return an error
     /// otherwise.
@@ -453,20 +474,19 @@ fn packages_to_verify<'b>(
                 );
             }
             filtered
-        } else if !args.cargo.exclude.is_empty() {
-            // should be ensured by argument validation
-            assert!(args.cargo.workspace);
-            let pkg_ids = self.to_package_ids(&args.cargo.exclude)?;
-            metadata
-                .workspace_packages()
-                .into_iter()
-                .filter(|pkg| !pkg_ids.contains_key(&pkg.id))
-                .collect()
         } else if args.cargo.workspace {
             metadata.workspace_packages()
         } else {
             metadata.workspace_default_packages()
         };
+        // Exclude packages after adding the companions, so an excluded companion isn't verified.
+        let mut packages = with_companions(metadata, packages)?;
+        if !args.cargo.exclude.is_empty() {
+            // should be ensured by argument validation
+            assert!(args.cargo.workspace);
+            let pkg_ids = self.to_package_ids(&args.cargo.exclude)?;
+            packages.retain(|pkg| !pkg_ids.contains_key(&pkg.id));
+        }
         trace!(?packages, "packages_to_verify result");
         Ok(packages)
     }
@@ -572,6 +592,65 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// Add the verification companions of the given packages right after them.
+///
+/// A companion is a package of the workspace with the stubs, contracts and harnesses for the items
+/// of another package, which keeps these verification models out of the production crate. The
+/// production package lists its companions in its `Cargo.toml`:
+///
+/// ```toml
+/// [package.metadata.kani]
+/// companions = ["my-crate-proofs"]
+/// ```
+fn with_companions<'b>(
+    metadata: &'b Metadata,
+    packages: Vec<&'b Package>,
+) -> Result<Vec<&'b Package>> {
+    let workspace_packages = metadata.workspace_packages();
+    let mut result: Vec<&Package> = Vec::with_capacity(packages.len());
+    for package in packages {
+        if !result.contains(&package) {
+            result.push(package);
+        }
+        let Some(names) = companion_names(&package.metadata) else {
+            bail!(
+                "Expected `package.metadata.kani.companions` of `{}` to be a list of package names",
+                package.name
+            );
+        };
+        for name in names {
+            let Some(companion) = workspace_packages.iter().find(|pkg| pkg.name.as_str() == name)
+            else {
+                bail!(
+                    "The companion `{name}` of `{}` is not a package of this workspace",
+                    package.name
+                );
+            };
+            if !companion.dependencies.iter().any(|dep| dep.name == package.name.as_str()) {
+                util::warning(&format!(
+                    "the companion `{name}` doesn't depend on `{}`, so its harnesses cannot use \
+                    the items of `{}`",
+                    package.name, package.name
+                ));
+            }
+            if !result.contains(companion) {
+                result.push(companion);
+            }
+        }
+    }
+    trace!(packages=?result.iter().map(|pkg| pkg.name.as_str()).collect::<Vec<_>>(), "with_companions");
+    Ok(result)
+}
+
+/// The names of the companions in the `[package.metadata.kani]` table of a package, or `None` if
+/// they aren't a list of strings.
+fn companion_names(package_metadata: &serde_json::Value) -> Option<Vec<&str>> {
+    match package_metadata.pointer("/kani/companions") {
+        None => Some(vec![]),
+        Some(companions) => companions.as_array()?.iter().map(|name| name.as_str()).collect(),
+    }
+}
+
 /// Extract the targets inside a package.
 ///
 /// If `--tests` is given, the list of targets will include any integration tests.
@@ -656,3 +735,23 @@ fn package_targets(args: &VerificationArgs, package: &Package) -> Vec<Verificati
     }
     verification_targets
 }
+
+#[cfg(test)]
+mod tests {
+    use super::companion_names;
+    use serde_json::json;
+
+    #[test]
+    fn check_companion_names() {
+        let metadata = json!({ "kani": { "companions": ["my-crate-proofs", "other-proofs"] } });
+        assert_eq!(companion_names(&metadata), Some(vec!["my-crate-proofs", "other-proofs"]));
+        assert_eq!(companion_names(&json!({ "kani": { "flags": {} } })), Some(vec![]));
+        assert_eq!(companion_names(&json!(null)), Some(vec![]));
+    }
+
+    #[test]
+    fn check_invalid_companion_names() {
+        assert_eq!(companion_names(&json!({ "kani": { "companions": "my-crate-proofs" } })), None);
+        assert_eq!(companion_names(&json!({ "kani": { "companions": [1] } })), None);
+    }
+}
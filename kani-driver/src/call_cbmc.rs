@@ -2,21 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::{Result, bail};
-use kani_metadata::{CbmcSolver, HarnessMetadata};
+use kani_metadata::{ArrayEncoding, CbmcCheck, CbmcSolver, CheckCategory, HarnessMetadata};
 use regex::Regex;
 use rustc_demangle::demangle;
-use std::collections::BTreeMap;
 use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsString;
 use std::fmt::Write;
 use std::path::Path;
 use std::sync::OnceLock;
 use std::time::{Duration, Instant};
+use strum::IntoEnumIterator;
 use strum_macros::Display;
 use tokio::process::Command as TokioCommand;
 
 use crate::args::common::Verbosity;
-use crate::args::{OutputFormat, VerificationArgs};
+use crate::args::{ArrayEncodingMode, OutputFormat, VerificationArgs};
 use crate::cbmc_output_parser::{
     CheckStatus, Property, VerificationOutput, extract_results, process_cbmc_output,
 };
@@ -24,16 +25,25 @@
 use crate::coverage::cov_results::{CoverageCheck, CoverageResults};
 use crate::coverage::cov_results::{CoverageRegion, CoverageTerm};
 use crate::session::KaniSession;
+use crate::statistics::{CbmcStatistics, PeakMemory};
 use crate::util::render_command;
 
 /// We will use Cadical by default since it performed better than MiniSAT in our analysis.
 /// Note: Kissat was marginally better, but it is an external solver which could be more unstable.
 static DEFAULT_SOLVER: CbmcSolver = CbmcSolver::Cadical;
 
+/// With `--array-encoding auto`, harnesses whose largest array has more elements than this encode
+/// their arrays with uninterpreted functions, unless they pick an encoding. Otherwise, CBMC
+/// flattens the arrays with up to 1000 elements into bit-vectors, which makes the formula explode.
+const AUTO_UF_ARRAY_LEN: u64 = 256;
+
 #[derive(Clone, Copy, Debug, Display, PartialEq, Eq)]
 pub enum VerificationStatus {
     Success,
     Failure,
+    /// All checks succeeded, but no execution reaches the end of the harness, so they hold
+    /// trivially. Only reported with `--check-vacuity`.
+    Vacuous,
 }
 
 /// Represents failed properties in three different categories.
@@ -44,6 +54,9 @@ pub enum FailedProperties {
     None,
     // One or more panic-related failures
     PanicsOnly,
+    // One or more panic-related failures, some of which don't have the message that a
+    // `#[kani::should_panic(expected = "...")]` harness expects
+    UnexpectedPanics,
     // One or more failures that aren't panic-related
     Other,
 }
@@ -75,19 +88,41 @@ pub struct VerificationResult {
     pub generated_concrete_test: bool,
     /// The coverage results
     pub coverage_results: Option<CoverageResults>,
+    /// The statistics reported by CBMC, used by `--statistics`.
+    pub statistics: CbmcStatistics,
 }
 
 impl KaniSession {
+    /// The timeout of the verification of `harness`, if it has one. The timeout never exceeds
+    /// what is left of the budget of `--total-timeout`, so a harness that is still running when
+    /// the budget runs out is stopped.
+    pub fn harness_timeout(&self, harness: &HarnessMetadata) -> Option<Duration> {
+        let timeout = resolve_harness_timeout(&self.args, harness, self.default_harness_timeout);
+        match (timeout, self.remaining_budget()) {
+            (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+            (timeout, remaining) => timeout.or(remaining),
+        }
+    }
+
+    /// The time that is left of the budget of `--total-timeout`, if there is a budget.
+    pub fn remaining_budget(&self) -> Option<Duration> {
+        let budget = self.autoharness_budget?;
+        let start = self.budget_start.get_or_init(Instant::now);
+        Some(budget.saturating_sub(start.elapsed()))
+    }
+
     /// Verify a goto binary that's been prepared with goto-instrument
     pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<VerificationResult> {
+        if let CbmcSolver::Smt(solver) = self.resolve_solver(&harness.attributes.solver) {
+            return self.run_smt_solver(file, harness, solver);
+        }
         let args: Vec<OsString> = self.cbmc_flags(file, harness)?;
 
-        // TODO get cbmc path from self
-        let mut cmd = TokioCommand::new("cbmc");
+        let mut cmd = TokioCommand::new(&self.cbmc);
         cmd.args(args);
 
         let verification_results = if self.args.output_format == crate::args::OutputFormat::Old {
-            if self.run_terminal_timeout(cmd).is_err() {
+            if self.run_terminal_timeout(cmd, self.harness_timeout(harness)).is_err() {
                 VerificationResult::mock_failure()
             } else {
                 VerificationResult::mock_success()
@@ -119,15 +154,20 @@ async fn run_cbmc_piped(
             .map_err(|_| anyhow::Error::msg("Failed to run cbmc"))?;
 
         let start_time = Instant::now();
+        let peak_memory = (self.args.statistics.is_some() && cfg!(target_os = "linux"))
+            .then(|| cbmc_process.id().map(PeakMemory::track))
+            .flatten();
 
-        let res = if let Some(timeout) = self.args.harness_timeout {
+        // The table of `--ui` replaces the messages that are printed while CBMC runs.
+        let quiet = self.args.common_args.quiet || self.progress().is_some();
+        let res = if let Some(timeout) = self.harness_timeout(harness) {
             tokio::time::timeout(
-                timeout.into(),
+                timeout,
                 process_cbmc_output(&mut cbmc_process, |i| {
                     kani_cbmc_output_filter(
                         i,
                         self.args.extra_pointer_checks,
-                        self.args.common_args.quiet,
+                        quiet,
                         &self.args.output_format,
                     )
                 }),
@@ -138,14 +178,14 @@ async fn run_cbmc_piped(
                 kani_cbmc_output_filter(
                     i,
                     self.args.extra_pointer_checks,
-                    self.args.common_args.quiet,
+                    quiet,
                     &self.args.output_format,
                 )
             })
             .await)
         };
 
-        let verification_results = if res.is_err() {
+        let mut verification_results = if res.is_err() {
             // An error occurs if the timeout was reached
 
             // Kill the process
@@ -158,12 +198,19 @@ async fn run_cbmc_piped(
                 runtime: start_time.elapsed(),
                 generated_concrete_test: false,
                 coverage_results: None,
+                statistics: CbmcStatistics::default(),
             }
         } else {
             // The timeout wasn't reached
             let output = res.unwrap()?;
-            VerificationResult::from(output, harness.attributes.should_panic, start_time)
+            VerificationResult::from(
+                output,
+                harness.attributes.should_panic,
+                harness.attributes.expected_panic.as_deref(),
+                start_time,
+            )
         };
+        verification_results.statistics.peak_memory = peak_memory.and_then(PeakMemory::stop);
 
         Ok(verification_results)
     }
@@ -174,20 +221,28 @@ pub fn cbmc_flags(
         file: &Path,
         harness_metadata: &HarnessMetadata,
     ) -> Result<Vec<OsString>> {
-        let mut args = self.cbmc_check_flags();
+        let mut args = self.cbmc_check_flags(harness_metadata);
 
         if let Some(object_bits) = self.args.cbmc_object_bits() {
             args.push("--object-bits".into());
             args.push(object_bits.to_string().into());
         }
 
-        if let Some(unwind_value) = resolve_unwind_value(&self.args, harness_metadata) {
-            args.push("--unwind".into());
-            args.push(unwind_value.to_string().into());
-        }
+        args.extend(self.unwind_flags(harness_metadata));
 
         self.handle_solver_args(&harness_metadata.attributes.solver, &mut args)?;
 
+        match resolve_array_encoding(&self.args, harness_metadata) {
+            Some(ArrayEncoding::Uf) => args.push("--arrays-uf-always".into()),
+            Some(ArrayEncoding::FieldSensitive) => {
+                if let Some(len) = harness_metadata.largest_array {
+                    args.push("--max-field-sensitivity-array-size".into());
+                    args.push(len.to_string().into());
+                }
+            }
+            None => {}
+        }
+
         if self.args.run_sanity_checks {
             args.push("--validate-goto-model".into());
             args.push("--validate-ssa-equation".into());
@@ -213,8 +268,29 @@ pub fn cbmc_flags(
         Ok(args)
     }
 
+    /// The `--unwind` and `--unwindset` flags that bound the loops of the harness, which CBMC and
+    /// goto-instrument both accept.
+    pub fn unwind_flags(&self, harness_metadata: &HarnessMetadata) -> Vec<OsString> {
+        let mut args: Vec<OsString> = Vec::new();
+        if let Some(unwind_value) = resolve_unwind_value(&self.args, harness_metadata) {
+            args.push("--unwind".into());
+            args.push(unwind_value.to_string().into());
+        }
+
+        let unwindset: Vec<_> =
+            [loop_unwindset(harness_metadata), spec_unwindset(harness_metadata)]
+                .into_iter()
+                .flatten()
+                .collect();
+        if !unwindset.is_empty() {
+            args.push("--unwindset".into());
+            args.push(unwindset.join(",").into());
+        }
+        args
+    }
+
     /// Just the flags to CBMC that enable property checking of any sort.
-    pub fn cbmc_check_flags(&self) -> Vec<OsString> {
+    pub fn cbmc_check_flags(&self, harness_metadata: &HarnessMetadata) -> Vec<OsString> {
         let mut args = Vec::new();
 
         // We assume that malloc cannot fail, see https://github.com/model-checking/kani/issues/891
@@ -229,56 +305,89 @@ pub fn cbmc_check_flags(&self) -> Vec<OsString> {
         // So these options are deliberately skipped to avoid erroneously re-checking operations.
         args.push("--no-signed-overflow-check".into());
 
-        if !self.args.checks.memory_safety_on() {
-            args.push("--no-bounds-check".into());
-            args.push("--no-pointer-check".into());
+        let checks = self.resolve_cbmc_checks(harness_metadata);
+        if self.args.common_args.verbose() {
+            let names: Vec<_> = checks.iter().map(|check| check.to_string()).collect();
+            println!(
+                "[Kani] CBMC checks for harness `{}`: {}",
+                harness_metadata.pretty_name,
+                names.join(", ")
+            );
         }
-        if self.args.checks.overflow_on() {
-            args.push("--nan-check".into());
-
-            // TODO: Implement conversion checks as an optional check.
-            // They are a well defined operation in rust, but they may yield unexpected results to
-            // many users. https://github.com/model-checking/kani/issues/840
-            // We might want to create a transformation pass instead of enabling CBMC since Kani
-            // compiler sometimes rely on the bitwise conversion of signed <-> unsigned.
-            // args.push("--conversion-check".into());
-        } else {
-            args.push("--no-div-by-zero-check".into());
+        for check in CbmcCheck::iter() {
+            match (check.is_cbmc_default(), checks.contains(&check)) {
+                (true, false) => args.push(format!("--no-{check}-check").into()),
+                (false, true) => args.push(format!("--{check}-check").into()),
+                (true, true) | (false, false) => {}
+            }
         }
 
-        if !self.args.checks.unwinding_on() {
+        if !self.unwinding_on(harness_metadata) {
             args.push("--no-unwinding-assertions".into());
         } else {
             args.push("--no-self-loops-to-assumptions".into());
         }
 
+        args
+    }
+
+    /// Whether the unwinding assertions of the harness are checked, which its `checks` attribute
+    /// may override.
+    pub fn unwinding_on(&self, harness_metadata: &HarnessMetadata) -> bool {
+        let checks = &harness_metadata.attributes.checks;
+        checks.is_enabled(CheckCategory::Unwinding, self.args.checks.unwinding_on())
+    }
+
+    /// The CBMC checks that the command-line options select, adjusted by the harness's
+    /// `checks` and `cbmc_checks` attributes.
+    fn resolve_cbmc_checks(&self, harness_metadata: &HarnessMetadata) -> BTreeSet<CbmcCheck> {
+        let categories = &harness_metadata.attributes.checks;
+        let mut checks = BTreeSet::new();
+        if self.args.checks.memory_safety_on() {
+            checks.insert(CbmcCheck::Pointer);
+            if categories.is_enabled(CheckCategory::Bounds, self.args.checks.bounds_on()) {
+                checks.insert(CbmcCheck::Bounds);
+            }
+        }
+        if categories.is_enabled(CheckCategory::Overflow, self.args.checks.overflow_on()) {
+            checks.extend([CbmcCheck::DivByZero, CbmcCheck::Nan]);
+            // TODO: Implement conversion checks as an optional check.
+            // They are a well defined operation in rust, but they may yield unexpected results to
+            // many users. https://github.com/model-checking/kani/issues/840
+            // We might want to create a transformation pass instead of enabling CBMC since Kani
+            // compiler sometimes rely on the bitwise conversion of signed <-> unsigned.
+            // Until then, harnesses can enable them with `cbmc_checks(enable = "conversion")`.
+        }
         if self.args.extra_pointer_checks {
             // This was adding a lot of false positives with std dangling pointer. We should
             // still catch any invalid dereference with --pointer-check. Thus, only enable them
             // if the user explicitly request them.
-            args.push("--pointer-overflow-check".into());
-        } else {
-            args.push("--no-pointer-primitive-check".into());
+            checks.extend([CbmcCheck::PointerOverflow, CbmcCheck::PointerPrimitive]);
         }
-
-        args
+        let harness_checks = &harness_metadata.attributes.cbmc_checks;
+        checks.extend(harness_checks.enable.iter().copied());
+        checks.retain(|check| !harness_checks.disable.contains(check));
+        checks
     }
 
-    pub fn handle_solver_args(
-        &self,
-        harness_solver: &Option<CbmcSolver>,
-        args: &mut Vec<OsString>,
-    ) -> Result<()> {
-        let solver = if let Some(solver) = &self.args.solver {
+    /// The solver that verifies a harness with the given `solver` attribute.
+    fn resolve_solver<'a>(&'a self, harness_solver: &'a Option<CbmcSolver>) -> &'a CbmcSolver {
+        if let Some(solver) = &self.args.solver {
             // `--solver` option takes precedence over attributes
             solver
         } else if let Some(solver) = harness_solver {
             solver
         } else {
             &DEFAULT_SOLVER
-        };
+        }
+    }
 
-        match solver {
+    pub fn handle_solver_args(
+        &self,
+        harness_solver: &Option<CbmcSolver>,
+        args: &mut Vec<OsString>,
+    ) -> Result<()> {
+        match self.resolve_solver(harness_solver) {
             CbmcSolver::Bitwuzla => {
                 args.push("--bitwuzla".into());
             }
@@ -308,6 +417,13 @@ pub fn handle_solver_args(
                 args.push("--external-sat-solver".into());
                 args.push(solver_binary.into());
             }
+            CbmcSolver::Smt(solver_binary) => {
+                if which::which(solver_binary).is_err() {
+                    bail!("the specified solver \"{solver_binary}\" was not found in path")
+                }
+                // Kani runs the solver itself on the formula that CBMC writes with `--outfile`.
+                args.push("--smt2".into());
+            }
         }
         Ok(())
     }
@@ -322,17 +438,25 @@ impl VerificationResult {
     ///      (CBMC will regularly report "failure" but that's just our cover checks.)
     ///   2. Positively checking for the presence of results.
     ///      (Do not mistake lack of results for success: report it as failure.)
-    fn from(
+    pub(crate) fn from(
         output: VerificationOutput,
         should_panic: bool,
+        expected_panic: Option<&str>,
         start_time: Instant,
     ) -> VerificationResult {
         let runtime = start_time.elapsed();
-        let (_, results) = extract_results(output.processed_items);
+        let (messages, results) = extract_results(output.processed_items);
+        let statistics = CbmcStatistics::from_messages(&messages);
 
-        if let Some(results) = results {
+        if let Some(mut results) = results {
+            let vacuous = extract_vacuity_checks(&mut results) && !should_panic;
             let (status, failed_properties) =
-                verification_outcome_from_properties(&results, should_panic);
+                verification_outcome_from_properties(&results, should_panic, expected_panic);
+            let status = if vacuous && status == VerificationStatus::Success {
+                VerificationStatus::Vacuous
+            } else {
+                status
+            };
             let coverage_results = coverage_results_from_properties(&results);
             VerificationResult {
                 status,
@@ -341,6 +465,7 @@ fn from(
                 runtime,
                 generated_concrete_test: false,
                 coverage_results,
+                statistics,
             }
         } else {
             // We never got results from CBMC - something went wrong (e.g. crash) so it's failure
@@ -356,6 +481,7 @@ fn from(
                 runtime,
                 generated_concrete_test: false,
                 coverage_results: None,
+                statistics,
             }
         }
     }
@@ -368,6 +494,7 @@ pub fn mock_success() -> VerificationResult {
             runtime: Duration::from_secs(0),
             generated_concrete_test: false,
             coverage_results: None,
+            statistics: CbmcStatistics::default(),
         }
     }
 
@@ -382,6 +509,7 @@ fn mock_failure() -> VerificationResult {
             runtime: Duration::from_secs(0),
             generated_concrete_test: false,
             coverage_results: None,
+            statistics: CbmcStatistics::default(),
         }
     }
 
@@ -436,27 +564,52 @@ pub fn render(&self, output_format: &OutputFormat, should_panic: bool) -> String
 }
 
 /// We decide if verification succeeded based on properties, not (typically) on exit code
-fn verification_outcome_from_properties(
+///
+/// A harness that should panic with an `expected_panic` message only succeeds if the description
+/// of every failed panic check contains that message.
+pub(crate) fn verification_outcome_from_properties(
     properties: &[Property],
     should_panic: bool,
+    expected_panic: Option<&str>,
 ) -> (VerificationStatus, FailedProperties) {
-    let failed_properties = determine_failed_properties(properties);
+    let failed_properties = determine_failed_properties(properties, expected_panic);
     let status = if should_panic {
         match failed_properties {
-            FailedProperties::None | FailedProperties::Other => VerificationStatus::Failure,
+            FailedProperties::None
+            | FailedProperties::UnexpectedPanics
+            | FailedProperties::Other => VerificationStatus::Failure,
             FailedProperties::PanicsOnly => VerificationStatus::Success,
         }
     } else {
         match failed_properties {
             FailedProperties::None => VerificationStatus::Success,
-            FailedProperties::PanicsOnly | FailedProperties::Other => VerificationStatus::Failure,
+            FailedProperties::PanicsOnly
+            | FailedProperties::UnexpectedPanics
+            | FailedProperties::Other => VerificationStatus::Failure,
         }
     };
     (status, failed_properties)
 }
 
+/// Remove the checks that Kani adds at the end of the harness with `--check-vacuity`, and return
+/// whether they show that the harness is vacuous, i.e., that none of them is reachable.
+///
+/// A harness that is expected to panic never reaches its end, so the caller ignores the result.
+fn extract_vacuity_checks(properties: &mut Vec<Property>) -> bool {
+    let (checks, others): (Vec<_>, Vec<_>) =
+        std::mem::take(properties).into_iter().partition(Property::is_vacuity_check_property);
+    *properties = others;
+    !checks.is_empty()
+        && checks
+            .iter()
+            .all(|check| matches!(check.status, CheckStatus::Success | CheckStatus::Unreachable))
+}
+
 /// Determines the `FailedProperties` variant that corresponds to an array of properties
-fn determine_failed_properties(properties: &[Property]) -> FailedProperties {
+fn determine_failed_properties(
+    properties: &[Property],
+    expected_panic: Option<&str>,
+) -> FailedProperties {
     let failed_properties: Vec<&Property> =
         properties.iter().filter(|prop| prop.status == CheckStatus::Failure).collect();
     // Return `FAILURE` if there isn't at least one failed property
@@ -467,15 +620,19 @@ fn determine_failed_properties(properties: &[Property]) -> FailedProperties {
         // Note: Panics caused by `panic!` and `assert!` fall into this class.
         let all_failed_checks_are_panics =
             failed_properties.iter().all(|prop| prop.property_class() == "assertion");
-        if all_failed_checks_are_panics {
-            FailedProperties::PanicsOnly
-        } else {
+        if !all_failed_checks_are_panics {
             FailedProperties::Other
+        } else if expected_panic
+            .is_some_and(|msg| failed_properties.iter().any(|prop| !prop.description.contains(msg)))
+        {
+            FailedProperties::UnexpectedPanics
+        } else {
+            FailedProperties::PanicsOnly
         }
     }
 }
 
-fn coverage_results_from_properties(properties: &[Property]) -> Option<CoverageResults> {
+pub(crate) fn coverage_results_from_properties(properties: &[Property]) -> Option<CoverageResults> {
     let cov_properties: Vec<&Property> =
         properties.iter().filter(|p| p.is_code_coverage_property()).collect();
 
@@ -547,9 +704,66 @@ pub fn resolve_unwind_value(
     args.unwind.or(harness_metadata.attributes.unwind_value).or(args.default_unwind)
 }
 
+/// The `--unwindset` entries that bound each labeled loop of the harness with the unwind value of
+/// its label in the `unwind` attribute of the harness, e.g. `#[kani::unwind(outer = 3)]`.
+fn loop_unwindset(harness_metadata: &HarnessMetadata) -> Option<String> {
+    let entries: Vec<_> = harness_metadata
+        .attributes
+        .loop_unwind
+        .iter()
+        .flat_map(|(label, bound)| {
+            let loop_ids = harness_metadata.labeled_loops.get(label).into_iter().flatten();
+            loop_ids.map(move |loop_id| format!("{loop_id}:{bound}"))
+        })
+        .collect();
+    (!entries.is_empty()).then(|| entries.join(","))
+}
+
+/// The `--unwindset` argument that bounds the recursion of the spec functions that the harness
+/// reaches with the bound of its `#[kani::spec_unwind]` attribute, if it has one.
+fn spec_unwindset(harness_metadata: &HarnessMetadata) -> Option<String> {
+    let bound = harness_metadata.attributes.spec_unwind?;
+    let spec_fns = &harness_metadata.spec_fns;
+    (!spec_fns.is_empty()).then(|| {
+        spec_fns.iter().map(|spec_fn| format!("{spec_fn}:{bound}")).collect::<Vec<_>>().join(",")
+    })
+}
+
+/// The array encoding of a harness: the one of its `array_encoding` attribute if it has one, or
+/// the one of `--array-encoding`, where `auto` picks uninterpreted functions if the harness uses
+/// a large array. `None` leaves the encoding to CBMC.
+pub fn resolve_array_encoding(
+    args: &VerificationArgs,
+    harness_metadata: &HarnessMetadata,
+) -> Option<ArrayEncoding> {
+    harness_metadata.attributes.array_encoding.or_else(|| match args.array_encoding? {
+        ArrayEncodingMode::Auto => harness_metadata
+            .largest_array
+            .is_some_and(|len| len > AUTO_UF_ARRAY_LEN)
+            .then_some(ArrayEncoding::Uf),
+        ArrayEncodingMode::Uf => Some(ArrayEncoding::Uf),
+        ArrayEncodingMode::FieldSensitive => Some(ArrayEncoding::FieldSensitive),
+    })
+}
+
+/// Solve the timeout of a harness from `--harness-timeout`, the timeout of an
+/// `#[kani::autoharness]` annotation and the `default_timeout` of the session, in this order of
+/// precedence, like the unwind value.
+pub fn resolve_harness_timeout(
+    args: &VerificationArgs,
+    harness_metadata: &HarnessMetadata,
+    default_timeout: Option<Duration>,
+) -> Option<Duration> {
+    args.harness_timeout
+        .map(Duration::from)
+        .or(harness_metadata.attributes.timeout)
+        .or(default_timeout)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::args;
+    use crate::cbmc_output_parser::{PropertyId, SourceLocation};
     use crate::metadata::tests::mock_proof_harness;
     use clap::Parser;
 
@@ -586,4 +800,142 @@ fn resolve(args: &[&str], harness: &HarnessMetadata) -> Option<u32> {
         assert_eq!(resolve(&args_only_harness, &harness_some), Some(1));
         assert_eq!(resolve(&args_both, &harness_some), Some(1));
     }
+
+    #[test]
+    fn check_spec_unwindset() {
+        let mut harness = mock_proof_harness("check_one", None, None, None);
+        harness.spec_fns = vec!["_RNvCs1_4main9is_sorted".into(), "_RNvCs1_4main7in_heap".into()];
+        assert_eq!(spec_unwindset(&harness), None);
+
+        harness.attributes.spec_unwind = Some(4);
+        assert_eq!(
+            spec_unwindset(&harness).as_deref(),
+            Some("_RNvCs1_4main9is_sorted:4,_RNvCs1_4main7in_heap:4")
+        );
+
+        harness.spec_fns.clear();
+        assert_eq!(spec_unwindset(&harness), None);
+    }
+
+    #[test]
+    fn check_loop_unwindset() {
+        let mut harness = mock_proof_harness("check_one", None, None, None);
+        harness.labeled_loops = BTreeMap::from([
+            ("outer".into(), vec!["_RNvCs1_4main5check.2".into()]),
+            ("inner".into(), vec!["_RNvCs1_4main5check.0".into(), "_RNvCs1_4main5check.1".into()]),
+        ]);
+        assert_eq!(loop_unwindset(&harness), None);
+
+        harness.attributes.loop_unwind =
+            BTreeMap::from([("inner".into(), 64), ("outer".into(), 3)]);
+        assert_eq!(
+            loop_unwindset(&harness).as_deref(),
+            Some("_RNvCs1_4main5check.0:64,_RNvCs1_4main5check.1:64,_RNvCs1_4main5check.2:3")
+        );
+
+        // Labeled loops without a back edge, e.g. `'done: loop { break }`, have no id.
+        harness.attributes.loop_unwind = BTreeMap::from([("done".into(), 1)]);
+        assert_eq!(loop_unwindset(&harness), None);
+    }
+
+    #[test]
+    fn check_resolve_array_encoding() {
+        let args_default = args::StandaloneArgs::try_parse_from(["kani", "x.rs"]).unwrap();
+        let args_auto = args::StandaloneArgs::try_parse_from([
+            "kani",
+            "x.rs",
+            "-Z",
+            "unstable-options",
+            "--array-encoding",
+            "auto",
+        ])
+        .unwrap();
+        let default = &args_default.verify_opts;
+        let auto = &args_auto.verify_opts;
+
+        let mut harness = mock_proof_harness("check_one", None, None, None);
+        assert_eq!(resolve_array_encoding(auto, &harness), None);
+
+        harness.largest_array = Some(AUTO_UF_ARRAY_LEN);
+        assert_eq!(resolve_array_encoding(auto, &harness), None);
+
+        // Large arrays only pick the encoding with `--array-encoding auto`.
+        harness.largest_array = Some(AUTO_UF_ARRAY_LEN + 1);
+        assert_eq!(resolve_array_encoding(default, &harness), None);
+        assert_eq!(resolve_array_encoding(auto, &harness), Some(ArrayEncoding::Uf));
+
+        harness.attributes.array_encoding = Some(ArrayEncoding::FieldSensitive);
+        assert_eq!(resolve_array_encoding(default, &harness), Some(ArrayEncoding::FieldSensitive));
+        assert_eq!(resolve_array_encoding(auto, &harness), Some(ArrayEncoding::FieldSensitive));
+    }
+
+    #[test]
+    fn check_resolve_harness_timeout() {
+        let args_empty = ["kani", "x.rs"];
+        let args_timeout = ["kani", "x.rs", "--harness-timeout", "2m"];
+
+        let harness_none = mock_proof_harness("check_one", None, None, None);
+        let mut harness_some = mock_proof_harness("check_one", None, None, None);
+        harness_some.attributes.timeout = Some(Duration::from_secs(30));
+
+        fn resolve(
+            args: &[&str],
+            harness: &HarnessMetadata,
+            default_timeout: Option<Duration>,
+        ) -> Option<Duration> {
+            resolve_harness_timeout(
+                &args::StandaloneArgs::try_parse_from(args).unwrap().verify_opts,
+                harness,
+                default_timeout,
+            )
+        }
+
+        let default = Some(Duration::from_secs(60));
+        assert_eq!(resolve(&args_empty, &harness_none, None), None);
+        assert_eq!(resolve(&args_empty, &harness_none, default), Some(Duration::from_secs(60)));
+        assert_eq!(resolve(&args_timeout, &harness_none, default), Some(Duration::from_secs(120)));
+        assert_eq!(resolve(&args_empty, &harness_some, default), Some(Duration::from_secs(30)));
+        assert_eq!(resolve(&args_timeout, &harness_some, default), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn check_expected_panic_outcome() {
+        fn failed_property(class: &str, description: &str) -> Property {
+            Property {
+                description: description.into(),
+                property_id: PropertyId { fn_name: None, class: class.into(), id: 1 },
+                source_location: SourceLocation {
+                    column: None,
+                    file: None,
+                    function: None,
+                    line: None,
+                },
+                status: CheckStatus::Failure,
+                reach: None,
+                trace: None,
+                cover_label: None,
+            }
+        }
+        fn status(properties: &[Property], expected_panic: Option<&str>) -> VerificationStatus {
+            verification_outcome_from_properties(properties, true, expected_panic).0
+        }
+
+        let index_panic = [failed_property("assertion", "index out of bounds: the len is 4")];
+        assert_eq!(status(&index_panic, None), VerificationStatus::Success);
+        assert_eq!(status(&index_panic, Some("out of bounds")), VerificationStatus::Success);
+        assert_eq!(status(&index_panic, Some("division by zero")), VerificationStatus::Failure);
+
+        let mixed_panics = [
+            failed_property("assertion", "index out of bounds: the len is 4"),
+            failed_property("assertion", "called `Option::unwrap()` on a `None` value"),
+        ];
+        assert_eq!(status(&mixed_panics, Some("out of bounds")), VerificationStatus::Failure);
+        assert!(matches!(
+            verification_outcome_from_properties(&mixed_panics, true, Some("out of bounds")).1,
+            FailedProperties::UnexpectedPanics
+        ));
+
+        let overflow = [failed_property("arithmetic_overflow", "attempt to add with overflow")];
+        assert_eq!(status(&overflow, Some("overflow")), VerificationStatus::Failure);
+    }
 }
@@ -32,6 +32,17 @@ pub fn link_goto_binary(&self, inputs: &[PathBuf], output: &Path) -> Result<()>
         Ok(())
     }
 
+    /// Link the goto binary `input` with the C file `definitions`, which defines symbols that
+    /// the binary only declares.
+    pub fn link_definitions(&self, input: &Path, definitions: &Path, output: &Path) -> Result<()> {
+        let mut cmd = Command::new("goto-cc");
+        cmd.arg(input).arg(definitions).arg("-o").arg(output);
+
+        self.run_suppress(cmd)?;
+
+        Ok(())
+    }
+
     /// Produce a goto binary with its entry point set to a particular proof harness.
     pub fn specialize_to_proof_harness(
         &self,
@@ -1,7 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use std::ffi::{OsStr, OsString};
 use std::fs::File;
 use std::io::BufReader;
@@ -54,6 +54,10 @@ pub fn instrument_model(
 
         self.rewrite_back_edges(output)?;
 
+        if self.args.slice_assertions {
+            self.slice_assertions(output, harness)?;
+        }
+
         if self.args.gen_c {
             let c_outfile = alter_extension(output, "c");
             // We don't put the C file into temporaries to be deleted.
@@ -148,6 +152,52 @@ fn rewrite_back_edges(&self, file: &Path) -> Result<()> {
         self.call_goto_instrument(args)
     }
 
+    /// Remove the instructions that cannot affect any property of the harness (--slice-assertions),
+    /// and report how many instructions were removed in verbose mode.
+    fn slice_assertions(&self, file: &Path, harness: &HarnessMetadata) -> Result<()> {
+        // Counting the instructions dumps the whole goto program, so we only do it when we
+        // report the count.
+        let before =
+            if self.args.common_args.verbose { Some(self.count_instructions(file)?) } else { None };
+        let args: Vec<OsString> = vec![
+            "--full-slice".into(),
+            file.to_owned().into_os_string(), // input
+            file.to_owned().into_os_string(), // output
+        ];
+        self.call_goto_instrument(args)?;
+
+        if let Some(before) = before {
+            let after = self.count_instructions(file)?;
+            println!(
+                "Slicing removed {} of {before} instructions from harness {}",
+                before.saturating_sub(after),
+                harness.pretty_name
+            );
+        }
+        Ok(())
+    }
+
+    /// Count the instructions in the bodies of all functions of a goto binary.
+    fn count_instructions(&self, file: &Path) -> Result<usize> {
+        let mut cmd = Command::new("goto-instrument");
+        cmd.arg("--show-goto-functions").arg("--json-ui").arg(file);
+        // For some reason clippy cannot see that we are invoking wait() below.
+        #[allow(clippy::zombie_processes)]
+        let mut process = self.run_piped(cmd)?;
+        let output: serde_json::Value =
+            serde_json::from_reader(BufReader::new(process.stdout.take().unwrap()))?;
+        if !process.wait()?.success() {
+            bail!("Failed to list the goto functions of `{}`", file.display());
+        }
+        let messages = output.as_array().into_iter().flatten();
+        Ok(messages
+            .filter_map(|message| message.get("functions")?.as_array())
+            .flatten()
+            .filter_map(|function| function.get("instructions")?.as_array())
+            .map(Vec::len)
+            .sum())
+    }
+
     fn goto_sanity_check(&self, file: &Path) -> Result<()> {
         let args: Vec<OsString> = vec![
             "--validate-goto-model".into(),
@@ -158,6 +208,26 @@ fn goto_sanity_check(&self, file: &Path) -> Result<()> {
         self.call_goto_instrument(args)
     }
 
+    /// Unroll the loops of the goto binary `file` with the unwind bounds of the harness, and write
+    /// the result to `output`.
+    ///
+    /// The unwinding assertions of the loops become regular properties at the location of their
+    /// loop, whereas the ones that CBMC adds during symbolic execution can't be listed or checked
+    /// on their own.
+    pub fn unwind_loops(
+        &self,
+        harness: &HarnessMetadata,
+        file: &Path,
+        output: &Path,
+    ) -> Result<()> {
+        let mut args = self.unwind_flags(harness);
+        args.push("--unwinding-assertions".into());
+        args.push(file.to_owned().into_os_string());
+        args.push(output.to_owned().into_os_string());
+
+        self.call_goto_instrument(args)
+    }
+
     /// Generate a .c file from a goto binary (i.e. --gen-c)
     pub fn gen_c(&self, file: &Path, output_file: &Path) -> Result<()> {
         let args: Vec<OsString> = vec![
@@ -177,7 +247,10 @@ pub fn instrument_contracts(
         file: &Path,
     ) -> Result<()> {
         // Do nothing if neither loop contracts nor function contracts is enabled.
-        if !is_loop_contracts_enabled && harness.contract.is_none() {
+        if !is_loop_contracts_enabled
+            && harness.contract.is_none()
+            && harness.cbmc_contracts.is_none()
+        {
             return Ok(());
         }
 
@@ -206,6 +279,22 @@ pub fn instrument_contracts(
             }
         }
 
+        if let Some(contracts) = harness.cbmc_contracts.as_ref() {
+            if let Some(function) = &contracts.enforce {
+                let flag = if contracts.recursive {
+                    "--enforce-contract-rec"
+                } else {
+                    "--enforce-contract"
+                };
+                args.push(flag.into());
+                args.push(function.into());
+            }
+            for function in &contracts.replace {
+                args.push("--replace-call-with-contract".into());
+                args.push(function.into());
+            }
+        }
+
         args.push(file.into());
         args.push(file.into());
 
@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::Result;
-use kani_metadata::UnstableFeature;
+use kani_metadata::{CheckCategory, UnstableFeature};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::args::{Concurrency, EmitKind, MemoryModel};
 use crate::session::{KaniSession, lib_folder};
 use crate::util::args::{CommandWrapper, KaniArg, PassTo, RustcArg, encode_as_rustc_arg};
 
@@ -151,6 +152,25 @@ pub fn kani_compiler_local_flags(&self) -> Vec<KaniArg> {
             flags.push("--ub-check=uninit".into());
         }
 
+        if self.args.common_args.unstable_features.contains(UnstableFeature::ProvenanceChecks) {
+            flags.push("--ub-check=provenance".into());
+        }
+
+        if self.args.common_args.unstable_features.contains(UnstableFeature::TruncationChecks) {
+            // The checks of float-to-integer casts use the range check of the floating-point API,
+            // so `-Z truncation-checks` implies `-Z float-lib`, as its documentation says.
+            flags.push("-Z float-lib".into());
+            flags.push("--ub-check=truncation".into());
+        }
+
+        if self.args.common_args.unstable_features.contains(UnstableFeature::Devirtualize) {
+            flags.push("--devirtualize".into());
+        }
+
+        if self.args.common_args.unstable_features.contains(UnstableFeature::SharedModel) {
+            flags.push("--shared-model".into());
+        }
+
         if self.args.common_args.unstable_features.contains(UnstableFeature::Lean) {
             flags.push("--backend=llbc".into());
         }
@@ -163,6 +183,87 @@ pub fn kani_compiler_local_flags(&self) -> Vec<KaniArg> {
             flags.push("--no-assert-contracts".into());
         }
 
+        if self.args.cbmc_contracts {
+            flags.push("--cbmc-contracts".into());
+        }
+
+        if self.isolation_command.is_some() {
+            flags.push("--defer-isolated-harnesses".into());
+        }
+
+        if self.args.check_invariant_preservation {
+            flags.push("--check-invariant-preservation".into());
+        }
+
+        if self.args.check_leaks {
+            flags.push("--ub-check=leaks".into());
+        }
+
+        if self.args.model_hashing {
+            flags.push("--model-hashing".into());
+        }
+
+        if self.args.model_std_collections {
+            flags.push("--model-std-collections".into());
+        }
+
+        if self.args.model_time {
+            flags.push("--model-time".into());
+        }
+
+        if self.args.model_std_io {
+            flags.push("--model-std-io".into());
+        }
+
+        if self.args.check_vacuity {
+            flags.push("--check-vacuity".into());
+        }
+
+        if self.args.list_unsafe {
+            flags.push("--list-unsafe".into());
+        }
+
+        if self.record_reachable_functions {
+            flags.push("--reachable-functions".into());
+        }
+
+        if self.print_type_definitions {
+            flags.push("--print-type-definitions".into());
+        }
+
+        if let Some(Concurrency::Bounded(bound)) = self.args.concurrency {
+            flags.push(format!("--concurrency-bound={bound}").into());
+        }
+
+        if let Some(MemoryModel::ReleaseAcquire) = self.args.memory_model {
+            flags.push("--memory-model=release-acquire".into());
+        }
+
+        if self.args.check_refcounts {
+            flags.push("--check-refcounts".into());
+        }
+
+        for snapshot in &self.args.snapshot {
+            let file = snapshot.file.canonicalize().unwrap_or_else(|_| snapshot.file.clone());
+            flags.push(format!("--snapshot={}={}", snapshot.target, file.display()).into());
+        }
+
+        let disabled_checks: Vec<_> = [
+            (CheckCategory::Overflow, self.args.checks.overflow_on()),
+            (CheckCategory::Bounds, self.args.checks.bounds_on()),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| !enabled)
+        .map(|(category, _)| category.to_string())
+        .collect();
+        if !disabled_checks.is_empty() {
+            flags.push(format!("--disabled-checks={}", disabled_checks.join(",")).into());
+        }
+
+        if self.args.emit.contains(&EmitKind::Mir) {
+            flags.push("--emit-mir".into());
+        }
+
         for harness in &self.args.harnesses {
             flags.push(format!("--harness {harness}").into());
         }
@@ -204,12 +305,24 @@ pub fn kani_rustc_flags(&self, lib_config: LibConfig) -> Vec<RustcArg> {
                 "-Z",
                 "mir-enable-passes=-RemoveStorageMarkers",
                 "--check-cfg=cfg(kani)",
+                "--check-cfg=cfg(kani_cbmc_contracts)",
+                "--check-cfg=cfg(kani_prove_no_panics)",
                 // Do not invoke the linker since the compiler will not generate real object files
                 "-Clinker=echo",
             ]
             .map(RustcArg::from),
         );
 
+        // The contract macros only generate the predicates used by CBMC contracts with this cfg.
+        if self.args.cbmc_contracts {
+            flags.push("--cfg=kani_cbmc_contracts".into());
+        }
+
+        // The contract macros skip the postconditions with this cfg.
+        if self.args.prove_no_panics {
+            flags.push("--cfg=kani_prove_no_panics".into());
+        }
+
         if self.args.no_codegen {
             flags.push("-Z".into());
             flags.push("no-codegen".into());
@@ -228,6 +341,12 @@ pub fn kani_rustc_flags(&self, lib_config: LibConfig) -> Vec<RustcArg> {
             flags.push("-Zmir-enable-passes=-SingleUseConsts".into());
         }
 
+        // Print the full path of the types in the errors, so `cargo kani fix` can tell apart the
+        // types with the same name.
+        if self.print_type_definitions {
+            flags.push("-Ztrim-diagnostic-paths=false".into());
+        }
+
         if self.args.prove_safety_only {
             flags.push("-C".into());
             flags.push("debug-assertions=off".into());
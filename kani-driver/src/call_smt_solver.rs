@@ -0,0 +1,290 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Verify a harness with an SMT solver selected by `#[kani::solver(smt = "<SOLVER>")]` or
+//! `--solver smt=<SOLVER>`.
+//!
+//! CBMC writes the verification conditions of a goto binary as an SMT-LIB formula with
+//! `--smt2 --outfile`, which Kani gives to the solver. The formula is satisfiable iff one of the
+//! properties it encodes can fail. Kani first solves the formula of all properties at once, which
+//! is enough to prove all of them. Otherwise, it solves the formula of each property to find the
+//! ones that fail.
+//!
+//! CBMC adds the unwinding assertions of loops during symbolic execution, so they aren't listed
+//! and can't be selected with `--property`. Kani unrolls the loops with goto-instrument first, which
+//! turns these assertions into regular properties at the location of their loop.
+
+use anyhow::{Context, Result, bail};
+use kani_metadata::HarnessMetadata;
+use serde::Deserialize;
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::{Output, Stdio};
+use std::time::Instant;
+use tokio::process::Command as TokioCommand;
+
+use crate::args::common::Verbosity;
+use crate::call_cbmc::{ExitStatus, FailedProperties, VerificationResult, VerificationStatus};
+use crate::cbmc_output_parser::{
+    CheckStatus, ParserItem, Property, PropertyId, SourceLocation, VerificationOutput,
+};
+use crate::cbmc_property_renderer::kani_cbmc_output_filter;
+use crate::session::KaniSession;
+use crate::statistics::CbmcStatistics;
+use crate::util::render_command;
+
+/// The answer of an SMT solver to `(check-sat)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SmtResult {
+    Sat,
+    Unsat,
+    Unknown,
+}
+
+/// A property listed by `cbmc --show-properties --json-ui`.
+#[derive(Debug, Deserialize)]
+struct ListedProperty {
+    name: String,
+    description: String,
+    #[serde(rename = "sourceLocation")]
+    source_location: SourceLocation,
+}
+
+impl KaniSession {
+    /// Verify a goto binary with the SMT solver `solver`.
+    pub(crate) fn run_smt_solver(
+        &self,
+        file: &Path,
+        harness: &HarnessMetadata,
+        solver: &str,
+    ) -> Result<VerificationResult> {
+        let unwound;
+        let file = if self.unwinding_on(harness) && !self.unwind_flags(harness).is_empty() {
+            unwound = file.with_extension("unwound.out");
+            self.record_temporary_file(&unwound);
+            self.unwind_loops(harness, file, &unwound)?;
+            unwound.as_path()
+        } else {
+            file
+        };
+        let args = self.cbmc_flags(file, harness)?;
+        if !self.args.common_args.quiet {
+            println!("Solving with SMT solver `{solver}`");
+        }
+
+        let start_time = Instant::now();
+        let check = self.check_properties_with_smt(&args, file, harness, solver);
+        let properties = if let Some(timeout) = self.harness_timeout(harness) {
+            // The processes are killed when the future is dropped.
+            match self.runtime.block_on(async { tokio::time::timeout(timeout, check).await }) {
+                Ok(properties) => properties?,
+                Err(_) => {
+                    return Ok(VerificationResult {
+                        status: VerificationStatus::Failure,
+                        failed_properties: FailedProperties::None,
+                        results: Err(ExitStatus::Timeout),
+                        runtime: start_time.elapsed(),
+                        generated_concrete_test: false,
+                        coverage_results: None,
+                        statistics: CbmcStatistics::default(),
+                    });
+                }
+            }
+        } else {
+            self.runtime.block_on(check)?
+        };
+
+        // Process the properties as if CBMC had reported them.
+        let processed_items = kani_cbmc_output_filter(
+            ParserItem::Result { result: properties },
+            self.args.extra_pointer_checks,
+            self.args.common_args.quiet,
+            &self.args.output_format,
+        )
+        .into_iter()
+        .collect();
+        let output = VerificationOutput { process_status: 0, processed_items };
+        Ok(VerificationResult::from(
+            output,
+            harness.attributes.should_panic,
+            harness.attributes.expected_panic.as_deref(),
+            start_time,
+        ))
+    }
+
+    /// Compute the status of every property of the goto binary, in the format of CBMC's results.
+    async fn check_properties_with_smt(
+        &self,
+        args: &[OsString],
+        file: &Path,
+        harness: &HarnessMetadata,
+        solver: &str,
+    ) -> Result<Vec<Property>> {
+        let listed = self.list_properties(args, file).await?;
+        let formula = file.with_extension("smt2");
+        self.record_temporary_file(&formula);
+
+        let mut properties: Vec<Property> = listed
+            .iter()
+            .map(|property| {
+                Ok(Property {
+                    description: property.description.clone(),
+                    property_id: serde_json::from_value(property.name.clone().into())?,
+                    source_location: property.source_location.clone(),
+                    status: CheckStatus::Success,
+                    reach: None,
+                    cover_label: None,
+                    trace: None,
+                })
+            })
+            .collect::<Result<_>>()?;
+        if self.solve_formula(args, &[], &formula, solver).await? == SmtResult::Unsat {
+            return Ok(properties);
+        }
+
+        // The loops are unrolled already, so the only unwinding assertions that CBMC still
+        // generates during symbolic execution bound recursion. They cannot be selected with
+        // `--property`, so leave them out of the formula of each property, and blame the
+        // recursion of the harness for the failure if no property fails.
+        let unwinding = self.unwinding_on(harness);
+        let mut has_failure = false;
+        for (property, listed) in properties.iter_mut().zip(&listed) {
+            let mut extra_args: Vec<OsString> = vec!["--property".into(), (&listed.name).into()];
+            if unwinding {
+                extra_args.push("--no-unwinding-assertions".into());
+            }
+            property.status = match self.solve_formula(args, &extra_args, &formula, solver).await? {
+                SmtResult::Sat => {
+                    has_failure = true;
+                    CheckStatus::Failure
+                }
+                SmtResult::Unsat => CheckStatus::Success,
+                SmtResult::Unknown => CheckStatus::Undetermined,
+            };
+        }
+        if !has_failure && unwinding {
+            properties.push(Property {
+                description: "recursion unwinding assertion".to_string(),
+                property_id: PropertyId {
+                    fn_name: Some(harness.pretty_name.clone()),
+                    class: "unwind".to_string(),
+                    id: 0,
+                },
+                source_location: SourceLocation {
+                    column: None,
+                    file: Some(harness.original_file.clone()),
+                    function: Some(harness.pretty_name.clone()),
+                    line: Some(harness.original_start_line.to_string()),
+                },
+                status: CheckStatus::Failure,
+                reach: None,
+                cover_label: None,
+                trace: None,
+            });
+        }
+        Ok(properties)
+    }
+
+    /// List the properties of the goto binary with `cbmc --show-properties`.
+    async fn list_properties(&self, args: &[OsString], file: &Path) -> Result<Vec<ListedProperty>> {
+        let mut cmd = TokioCommand::new(&self.cbmc);
+        cmd.args(args).args(["--show-properties", "--json-ui"]);
+        let output = self.run_smt_step(cmd).await.context("Failed to run cbmc")?;
+        if !output.status.success() {
+            bail!("cbmc failed to list the properties of `{}`", file.display());
+        }
+        let items: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)?;
+        let properties = items
+            .into_iter()
+            .find_map(|mut item| item.get_mut("properties").map(serde_json::Value::take))
+            .with_context(|| format!("cbmc did not list the properties of `{}`", file.display()))?;
+        Ok(serde_json::from_value(properties)?)
+    }
+
+    /// Write the SMT formula of the goto binary to `formula`, with the extra CBMC arguments
+    /// `extra_args`, and solve it with `solver`.
+    async fn solve_formula(
+        &self,
+        args: &[OsString],
+        extra_args: &[OsString],
+        formula: &Path,
+        solver: &str,
+    ) -> Result<SmtResult> {
+        let mut cmd = TokioCommand::new(&self.cbmc);
+        cmd.args(args).args(extra_args).arg("--outfile").arg(formula);
+        let output = self.run_smt_step(cmd).await.context("Failed to run cbmc")?;
+        if !output.status.success() {
+            bail!("cbmc failed to write the SMT formula to `{}`", formula.display());
+        }
+
+        let mut cmd = TokioCommand::new(solver);
+        cmd.arg(formula);
+        let output = self
+            .run_smt_step(cmd)
+            .await
+            .with_context(|| format!("Failed to run SMT solver `{solver}`"))?;
+        // Solvers may exit with an error after they answer, e.g., for a `(get-value)` command
+        // that follows an unsatisfiable `(check-sat)`, so only the answer matters.
+        parse_smt_result(&String::from_utf8_lossy(&output.stdout)).with_context(|| {
+            format!(
+                "unexpected output from SMT solver `{solver}`: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+        })
+    }
+
+    async fn run_smt_step(&self, mut cmd: TokioCommand) -> Result<Output> {
+        if self.args.common_args.verbose() {
+            println!("[Kani] Running: `{}`", render_command(cmd.as_std()).to_string_lossy());
+        }
+        Ok(cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .output()
+            .await?)
+    }
+}
+
+/// Parse the answer of the solver to the first `(check-sat)` command.
+fn parse_smt_result(output: &str) -> Option<SmtResult> {
+    match output.lines().map(str::trim).find(|line| !line.is_empty())? {
+        "sat" => Some(SmtResult::Sat),
+        "unsat" => Some(SmtResult::Unsat),
+        "unknown" => Some(SmtResult::Unknown),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse_smt_result() {
+        assert_eq!(parse_smt_result("unsat\n"), Some(SmtResult::Unsat));
+        assert_eq!(parse_smt_result("\nsat\n((|B0| true))\n"), Some(SmtResult::Sat));
+        assert_eq!(parse_smt_result("unknown\n"), Some(SmtResult::Unknown));
+        assert_eq!(
+            parse_smt_result("unsat\n(error \"line 12: model is not available\")\n"),
+            Some(SmtResult::Unsat)
+        );
+        assert_eq!(parse_smt_result("(error \"unknown logic\")\n"), None);
+        assert_eq!(parse_smt_result(""), None);
+    }
+
+    #[test]
+    fn check_listed_property_deserialization() {
+        let output = r#"{
+            "class": "assertion",
+            "coveredLines": "5",
+            "description": "assertion failed: x < 10",
+            "expression": "x < 10",
+            "name": "check.assertion.1",
+            "sourceLocation": { "file": "test.rs", "function": "check", "line": "5" }
+        }"#;
+        let property: ListedProperty = serde_json::from_str(output).unwrap();
+        assert_eq!(property.name, "check.assertion.1");
+        assert_eq!(property.source_location.line.as_deref(), Some("5"));
+    }
+}
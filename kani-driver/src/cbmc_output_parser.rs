@@ -30,7 +30,6 @@
 use serde::{Deserialize, Deserializer, Serialize};
 
 use std::env;
-use std::os::unix::process::ExitStatusExt;
 use std::path::PathBuf;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::{Child, ChildStdout};
@@ -72,8 +71,8 @@ struct ResultStruct {
 
 /// Struct that represents a single property in the set of CBMC results.
 ///
-/// Note: `reach` is not part of the parsed data, but it's useful to annotate
-/// its reachability status.
+/// Note: `reach` and `cover_label` are not part of the parsed data, but it's useful to annotate
+/// its reachability status and the label of cover properties.
 #[derive(Clone, Debug, Deserialize)]
 pub struct Property {
     pub description: String,
@@ -84,6 +83,9 @@ pub struct Property {
     pub status: CheckStatus,
     pub reach: Option<CheckStatus>,
     pub trace: Option<Vec<TraceItem>>,
+    /// The label of a cover property, i.e., `name` in `kani::cover!(cond, label = name)`.
+    #[serde(skip)]
+    pub cover_label: Option<String>,
 }
 
 /// CBMC's somewhat-ish consistent format for naming properties.
@@ -97,6 +99,9 @@ pub struct PropertyId {
 impl Property {
     const COVER_PROPERTY_CLASS: &'static str = "cover";
     const COVERAGE_PROPERTY_CLASS: &'static str = "code_coverage";
+    const PROBE_PROPERTY_CLASS: &'static str = "probe";
+    const RECURSION_LIMIT_PROPERTY_CLASS: &'static str = "recursion_limit";
+    const VACUITY_CHECK_PROPERTY_CLASS: &'static str = "vacuity_check";
 
     pub fn property_class(&self) -> String {
         self.property_id.class.clone()
@@ -112,6 +117,21 @@ pub fn is_cover_property(&self) -> bool {
         self.property_id.class == Self::COVER_PROPERTY_CLASS
     }
 
+    /// Returns true if this is a probe (i.e., a `kani::probe` marker)
+    pub fn is_probe_property(&self) -> bool {
+        self.property_id.class == Self::PROBE_PROPERTY_CLASS
+    }
+
+    /// Returns true if this checks the recursion limit of the harness
+    pub fn is_recursion_limit_property(&self) -> bool {
+        self.property_id.class == Self::RECURSION_LIMIT_PROPERTY_CLASS
+    }
+
+    /// Returns true if this checks whether the end of the harness is reachable
+    pub fn is_vacuity_check_property(&self) -> bool {
+        self.property_id.class == Self::VACUITY_CHECK_PROPERTY_CLASS
+    }
+
     pub fn property_name(&self) -> String {
         let class = &self.property_id.class;
         let id = self.property_id.id;
@@ -333,13 +353,13 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 #[serde(rename_all = "UPPERCASE")]
 pub enum CheckStatus {
     Failure,
-    Covered,   // for `code_coverage` properties only
+    Covered,   // for `code_coverage` and `probe` properties only
     Satisfied, // for `cover` properties only
     Success,
     Undetermined,
     Unknown,
     Unreachable,
-    Uncovered,     // for `code_coverage` properties only
+    Uncovered,     // for `code_coverage` and `probe` properties only
     Unsatisfiable, // for `cover` properties only
 }
 
@@ -547,7 +567,12 @@ pub async fn process_cbmc_output(
     // This will get us the process's exit code
     let status = process.wait().await?;
 
-    let process_status = match (status.code(), status.signal()) {
+    // Processes aren't terminated by signals on Windows.
+    #[cfg(unix)]
+    let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+    #[cfg(not(unix))]
+    let signal: Option<i32> = None;
+    let process_status = match (status.code(), signal) {
         // normal unix exit codes (cbmc uses currently 0-10)
         // https://github.com/diffblue/cbmc/blob/develop/src/util/exit_codes.h
         (Some(x), _) => x,
@@ -607,6 +632,7 @@ fn check_property_id_deserialization_general() {
             },
             status: CheckStatus::Success,
             reach: None,
+            cover_label: None,
             trace: None,
         };
         assert_eq!(dummy_prop.property_name(), prop_id_string[1..prop_id_string.len() - 1]);
@@ -637,6 +663,7 @@ fn check_property_id_deserialization_only_name() {
             },
             status: CheckStatus::Success,
             reach: None,
+            cover_label: None,
             trace: None,
         };
         assert_eq!(
@@ -666,6 +693,7 @@ fn check_property_id_deserialization_only_class() {
             },
             status: CheckStatus::Success,
             reach: None,
+            cover_label: None,
             trace: None,
         };
         assert_eq!(dummy_prop.property_name(), prop_id_string[1..prop_id_string.len() - 1]);
@@ -692,6 +720,7 @@ fn check_property_id_deserialization_special() {
             },
             status: CheckStatus::Success,
             reach: None,
+            cover_label: None,
             trace: None,
         };
         assert_eq!(dummy_prop.property_name(), "recursion.1");
@@ -721,6 +750,7 @@ fn check_property_id_deserialization_special_name() {
             },
             status: CheckStatus::Success,
             reach: None,
+            cover_label: None,
             trace: None,
         };
         assert_eq!(
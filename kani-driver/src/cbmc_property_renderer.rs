@@ -268,6 +268,10 @@ pub fn format_result(
     }
 
     for prop in properties {
+        // Probes are aggregated by name and reported separately below.
+        if prop.is_probe_property() {
+            continue;
+        }
         let name = prop.property_name();
         let status = &prop.status;
         let description = &prop.description;
@@ -311,6 +315,9 @@ pub fn format_result(
             result_str.push_str(&check_id);
             result_str.push_str(&status_msg);
             result_str.push_str(&description_msg);
+            if let Some(label) = &prop.cover_label {
+                result_str.push_str(&format!("\t - Label: {label}\n"));
+            }
 
             if !location.is_missing() {
                 let location_msg = format!("\t - Location: {location}\n");
@@ -333,7 +340,8 @@ pub fn format_result(
         + number_covers_unsatisfiable
         + number_covers_undetermined;
 
-    let number_properties = properties.len() - number_cover_properties;
+    let number_probes = properties.iter().filter(|prop| prop.is_probe_property()).count();
+    let number_properties = properties.len() - number_cover_properties - number_probes;
 
     let summary = format!("\n ** {number_checks_failed} of {number_properties} failed");
     result_str.push_str(&summary);
@@ -378,20 +386,25 @@ pub fn format_result(
         result_str.push('\n');
     }
 
+    result_str.push_str(&format_probes(properties));
+
     for prop in failed_tests {
         let failure_message = build_failure_message(prop.description.clone(), &prop.trace.clone());
         result_str.push_str(&failure_message);
     }
 
-    let verification_result = if status == VerificationStatus::Success {
-        style("SUCCESSFUL").green()
-    } else {
-        style("FAILED").red()
+    let verification_result = match status {
+        VerificationStatus::Success => style("SUCCESSFUL").green(),
+        VerificationStatus::Failure => style("FAILED").red(),
+        VerificationStatus::Vacuous => style("VACUOUS").yellow(),
     };
     let should_panic_info = if should_panic {
         match failed_properties {
             FailedProperties::None => " (encountered no panics, but at least one was expected)",
             FailedProperties::PanicsOnly => " (encountered one or more panics as expected)",
+            FailedProperties::UnexpectedPanics => {
+                " (encountered panics without the expected message, which were unexpected)"
+            }
             FailedProperties::Other => {
                 " (encountered failures other than panics, which were unexpected)"
             }
@@ -417,10 +430,56 @@ pub fn format_result(
         result_str.push_str("[Kani] info: Verification output shows one or more unwinding failures.\n\
         [Kani] tip: Consider increasing the unwinding value or disabling `--unwinding-assertions`.\n");
     }
+    if status == VerificationStatus::Vacuous {
+        result_str.push_str("[Kani] info: No execution reaches the end of the harness, so its checks hold trivially.\n\
+        [Kani] tip: Check that the assumptions of the harness, e.g., `kani::assume` calls, are not contradictory.\n");
+    }
+    if has_recursion_limit_failures(properties) {
+        result_str.push_str("[Kani] info: Verification output shows one or more recursive calls that exceed the recursion limit.\n\
+        [Kani] tip: Consider increasing the `recursion_limit` of the harness.\n");
+    }
 
     result_str
 }
 
+/// Formats the results of probes (specified using the `kani::probe` macro).
+///
+/// A probe name may be used at several locations, or a probe may be instantiated more than once
+/// (e.g., in a generic function), so results are aggregated by name: a probe is covered if any of
+/// its instances is reachable.
+fn format_probes(properties: &[Property]) -> String {
+    let mut probes: Vec<(&str, CheckStatus)> = vec![];
+    for prop in properties.iter().filter(|prop| prop.is_probe_property()) {
+        match probes.iter_mut().find(|(name, _)| *name == prop.description) {
+            Some((_, status)) => *status = merge_probe_status(*status, prop.status),
+            None => probes.push((&prop.description, prop.status)),
+        }
+    }
+    if probes.is_empty() {
+        return String::new();
+    }
+
+    let mut result_str = String::from("PROBES:\n");
+    for (name, status) in &probes {
+        result_str.push_str(&format!(" - {name}: {status}\n"));
+    }
+    let number_covered =
+        probes.iter().filter(|(_, status)| *status == CheckStatus::Covered).count();
+    let number_probes = probes.len();
+    result_str.push_str(&format!("\n ** {number_covered} of {number_probes} probes covered\n\n"));
+    result_str
+}
+
+/// A probe is covered if any of its instances is covered, and it is only uncovered if all of its
+/// instances are uncovered.
+fn merge_probe_status(first: CheckStatus, second: CheckStatus) -> CheckStatus {
+    match (first, second) {
+        (CheckStatus::Covered, _) | (_, CheckStatus::Covered) => CheckStatus::Covered,
+        (CheckStatus::Uncovered, CheckStatus::Uncovered) => CheckStatus::Uncovered,
+        _ => CheckStatus::Undetermined,
+    }
+}
+
 /// Separate checks into coverage and non-coverage based on property class and
 /// format them separately for `--coverage`. Then we report both verification
 /// and processed coverage results.
@@ -498,23 +557,25 @@ fn postprocess_error_message(message: ParserItem) -> ParserItem {
 /// unwinding assertion), and update the results of impacted checks accordingly.
 ///
 /// This postprocessing follows the same steps:
-///     1. Change all "SUCCESS" results to "UNDETERMINED" if the reachability check
-///     for a Rust construct that is not currently supported by Kani failed, since
-///     the missing exploration of execution paths through the unsupported construct
-///     may hide failures
-///     2. Change a check's result from "SUCCESS" to "UNREACHABLE" if its
-///     reachability check's result was "SUCCESS"
-///     3. Change results from "SUCCESS" to "UNDETERMINED" if an unwinding
-///     assertion failed, since the insufficient unwinding may cause some execution
-///     paths to be left unexplored.
+/// 1. Change all "SUCCESS" results to "UNDETERMINED" if the reachability check
+///    for a Rust construct that is not currently supported by Kani failed, since
+///    the missing exploration of execution paths through the unsupported construct
+///    may hide failures
+/// 2. Change a check's result from "SUCCESS" to "UNREACHABLE" if its
+///    reachability check's result was "SUCCESS"
+/// 3. Change results from "SUCCESS" to "UNDETERMINED" if an unwinding
+///    assertion failed, since the insufficient unwinding may cause some execution
+///    paths to be left unexplored. The same applies if a recursive call exceeded
+///    the recursion limit of the harness.
 ///
-///     Additionally, print a message at the end of the output that indicates if any
-///     of the special cases above was hit.
+/// Additionally, print a message at the end of the output that indicates if any
+/// of the special cases above was hit.
 pub fn postprocess_result(properties: Vec<Property>, extra_ptr_checks: bool) -> Vec<Property> {
     // First, determine if there are reachable unsupported constructs or unwinding assertions
     let has_reachable_unsupported_constructs =
         has_check_failure(&properties, UNSUPPORTED_CONSTRUCT_DESC);
     let has_failed_unwinding_asserts = has_unwinding_assertion_failures(&properties);
+    let has_failed_recursion_limits = has_recursion_limit_failures(&properties);
     // Then, determine if there are reachable undefined functions, and change
     // their description to highlight this fact
     let (properties_with_undefined, has_reachable_undefined_functions) =
@@ -527,7 +588,8 @@ pub fn postprocess_result(properties: Vec<Property>, extra_ptr_checks: bool) ->
     let properties_annotated =
         annotate_properties_with_reach_results(properties_without_sanity_checks, reach_checks);
     // Remove reachability check IDs from regular property descriptions
-    let properties_without_ids = remove_check_ids_from_description(properties_annotated);
+    let properties_without_ids =
+        extract_cover_labels(remove_check_ids_from_description(properties_annotated));
 
     // Filter out extra pointer checks if needed
     let properties_filtered = if !extra_ptr_checks {
@@ -537,12 +599,14 @@ pub fn postprocess_result(properties: Vec<Property>, extra_ptr_checks: bool) ->
     };
     let has_fundamental_failures = has_reachable_unsupported_constructs
         || has_failed_unwinding_asserts
+        || has_failed_recursion_limits
         || has_reachable_undefined_functions;
 
     let updated_properties =
         update_properties_with_reach_status(properties_filtered, has_fundamental_failures);
     let results_after_code_coverage = update_results_of_code_covererage_checks(updated_properties);
-    update_results_of_cover_checks(results_after_code_coverage)
+    let results_after_probes = update_results_of_probes(results_after_code_coverage);
+    update_results_of_cover_checks(results_after_probes)
 }
 
 /// Determines if there is property with status `FAILURE` and the given description
@@ -561,6 +625,13 @@ fn has_unwinding_assertion_failures(properties: &Vec<Property>) -> bool {
         || has_check_failure(properties, UNWINDING_ASSERT_REC_DESC)
 }
 
+/// Determines if a recursive call exceeded the recursion limit of the harness
+fn has_recursion_limit_failures(properties: &[Property]) -> bool {
+    properties
+        .iter()
+        .any(|prop| prop.is_recursion_limit_property() && prop.status == CheckStatus::Failure)
+}
+
 /// Replaces the description of all properties from functions with a missing
 /// definition.
 fn modify_undefined_function_checks(mut properties: Vec<Property>) -> (Vec<Property>, bool) {
@@ -667,6 +738,24 @@ fn update_results_of_code_covererage_checks(mut properties: Vec<Property>) -> Ve
     properties
 }
 
+/// Update the results of probes, which are encoded as `assert(false)`:
+/// - `SUCCESS` -> `UNCOVERED`
+/// - `FAILURE` -> `COVERED`
+///
+/// Other statuses (e.g., `UNDETERMINED`) are left unchanged.
+fn update_results_of_probes(mut properties: Vec<Property>) -> Vec<Property> {
+    for prop in properties.iter_mut() {
+        if prop.is_probe_property() {
+            if prop.status == CheckStatus::Success {
+                prop.status = CheckStatus::Uncovered;
+            } else if prop.status == CheckStatus::Failure {
+                prop.status = CheckStatus::Covered;
+            }
+        }
+    }
+    properties
+}
+
 /// Update the results of cover properties.
 /// We encode cover(cond) as assert(!cond), so if the assertion
 /// fails, then the cover property is satisfied and vice versa:
@@ -707,6 +796,24 @@ fn remove_check_ids_from_description(mut properties: Vec<Property>) -> Vec<Prope
     properties
 }
 
+/// The `kani::cover!(cond, label = name)` macro prefixes the description of the cover property
+/// with a label marker of the following form:
+/// ```text
+/// [KANI_COVER_LABEL_<name>]
+/// ```
+/// This function moves the label from the description to the `cover_label` of the property.
+fn extract_cover_labels(mut properties: Vec<Property>) -> Vec<Property> {
+    let label_pat = Regex::new(r"^\[KANI_COVER_LABEL_([^\]]*)\] ").unwrap();
+    for prop in properties.iter_mut().filter(|prop| prop.is_cover_property()) {
+        if let Some(captures) = label_pat.captures(&prop.description) {
+            let label = captures[1].to_string();
+            prop.description = prop.description[captures[0].len()..].to_string();
+            prop.cover_label = Some(label);
+        }
+    }
+    properties
+}
+
 /// Partitions `properties` into reachability checks (identified by the
 /// "reachability_check" property class) and non-reachability checks
 fn filter_reach_checks(properties: Vec<Property>) -> (Vec<Property>, Vec<Property>) {
@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the `cargo kani clean` subcommand.
+//!
+//! Kani builds a package in the `kani` directory of its target directory, so that it never
+//! interferes with regular builds. The artifacts of each harness live in their own directory,
+//! and the compiler removes the directories of harnesses that no longer exist whenever it compiles
+//! the crate. This subcommand removes everything else.
+
+use anyhow::{Context, Result};
+
+use crate::args::VerificationArgs;
+use crate::args::clean_args::CargoCleanArgs;
+use crate::session::KaniSession;
+use crate::util::info_operation;
+
+pub fn clean_cargo(args: CargoCleanArgs, mut verify_opts: VerificationArgs) -> Result<()> {
+    verify_opts.common_args = args.common_args;
+    let session = KaniSession::new(verify_opts)?;
+    let metadata = session.cargo_metadata(env!("TARGET"))?;
+    let target_dir = session.kani_target_dir(&metadata);
+    if !target_dir.exists() {
+        if !session.args.common_args.quiet {
+            info_operation("Skipping", &format!("`{}` does not exist", target_dir.display()));
+        }
+        return Ok(());
+    }
+    std::fs::remove_dir_all(&target_dir)
+        .with_context(|| format!("Failed to remove `{}`", target_dir.display()))?;
+    if !session.args.common_args.quiet {
+        info_operation("Removed", &target_dir.display().to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,277 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `--compare-backend`, which verifies every harness a second time with another
+//! backend, and reports the harnesses whose results differ. This cross-checks the results of
+//! critical proofs, and helps to develop new backends or to upgrade CBMC.
+//!
+//! Both backends verify the same goto binary, so they must report the same properties. We compare
+//! the verdict of each harness and the status of each of its properties.
+
+use anyhow::Result;
+use kani_metadata::HarnessMetadata;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock, RwLock};
+
+use crate::args::CompareBackend;
+use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::cbmc_output_parser::CheckStatus;
+use crate::session::KaniSession;
+
+/// A difference between the results of the default backend and the other one.
+#[derive(Debug, PartialEq, Eq)]
+enum Difference {
+    /// The harness is verified by one backend but not by the other.
+    Verdict { default: VerificationStatus, other: VerificationStatus },
+    /// One of the backends didn't complete the verification, e.g., because it timed out.
+    Incomplete { default: bool, other: bool },
+    /// A property has a different status in each backend.
+    Status { property: String, default: CheckStatus, other: CheckStatus },
+    /// Only one of the backends reports the property.
+    Missing { property: String, in_default: bool },
+}
+
+impl KaniSession {
+    /// Create the session that verifies the harnesses with the backend of `--compare-backend`.
+    pub(crate) fn comparison_session(&self) -> Option<KaniSession> {
+        let backend = self.args.compare_backend.as_ref()?;
+        let mut args = self.args.clone();
+        args.compare_backend = None;
+        // Only the default backend prints the results of the harnesses.
+        args.common_args.quiet = true;
+        let cbmc = match backend {
+            CompareBackend::Cbmc(path) => path.clone(),
+            CompareBackend::Solver(solver) => {
+                // `--solver` takes precedence over the solver attributes of the harnesses.
+                args.solver = Some(solver.clone());
+                self.cbmc.clone()
+            }
+        };
+        Some(KaniSession {
+            args,
+            autoharness_compiler_flags: None,
+            autoharness_budget: None,
+            budget_start: OnceLock::new(),
+            default_harness_timeout: self.default_harness_timeout,
+            skipped_by_budget: Mutex::new(vec![]),
+            isolation_command: None,
+            cbmc,
+            comparison: None,
+            backend_discrepancies: Mutex::new(vec![]),
+            record_reachable_functions: self.record_reachable_functions,
+            print_type_definitions: self.print_type_definitions,
+            kani_compiler: self.kani_compiler.clone(),
+            kani_lib_c: self.kani_lib_c.clone(),
+            temporaries: Mutex::new(vec![]),
+            compiler_errors: Mutex::new(vec![]),
+            type_definitions: Mutex::new(vec![]),
+            progress: RwLock::new(None),
+            runtime: tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap(),
+        })
+    }
+
+    /// Verify the harness with the backend of `--compare-backend`, and report the differences
+    /// with `result`, the result of the default backend.
+    pub(crate) fn compare_backends(
+        &self,
+        binary: &Path,
+        harness: &HarnessMetadata,
+        result: &VerificationResult,
+    ) -> Result<()> {
+        let (Some(comparison), Some(backend)) = (&self.comparison, &self.args.compare_backend)
+        else {
+            return Ok(());
+        };
+        let other =
+            self.with_timer(|| comparison.run_cbmc(binary, harness), "run_cbmc (compare backend)")?;
+        let differences = diff_results(result, &other);
+        if !self.args.common_args.quiet {
+            print_differences(&harness.pretty_name, backend, &differences);
+        }
+        if !differences.is_empty() {
+            self.backend_discrepancies.lock().unwrap().push(harness.pretty_name.clone());
+        }
+        Ok(())
+    }
+
+    /// Print which harnesses have different results with the backend of `--compare-backend`, and
+    /// return how many there are.
+    pub(crate) fn print_backend_comparison_summary(&self, total: usize) -> usize {
+        let Some(backend) = &self.args.compare_backend else {
+            return 0;
+        };
+        let mut discrepancies = self.backend_discrepancies.lock().unwrap();
+        discrepancies.sort();
+        println!("\nBackend Comparison Summary:");
+        for harness in discrepancies.iter() {
+            println!("Results differ with backend `{backend}` for - {harness}");
+        }
+        println!(
+            "Complete - {} harnesses have the same results with backend `{backend}`, {} differ, \
+            {total} total.",
+            total - discrepancies.len(),
+            discrepancies.len()
+        );
+        discrepancies.len()
+    }
+}
+
+/// Compare the results of the default backend to the results of the other one.
+fn diff_results(default: &VerificationResult, other: &VerificationResult) -> Vec<Difference> {
+    let mut differences = vec![];
+    if default.status != other.status {
+        differences.push(Difference::Verdict { default: default.status, other: other.status });
+    }
+    let (Ok(default_properties), Ok(other_properties)) = (&default.results, &other.results) else {
+        differences.push(Difference::Incomplete {
+            default: default.results.is_ok(),
+            other: other.results.is_ok(),
+        });
+        return differences;
+    };
+    let other_statuses: BTreeMap<String, CheckStatus> =
+        other_properties.iter().map(|prop| (prop.property_name(), prop.status)).collect();
+    let mut default_statuses = BTreeMap::new();
+    for prop in default_properties {
+        let property = prop.property_name();
+        match other_statuses.get(&property) {
+            Some(&status) if status != prop.status => differences.push(Difference::Status {
+                property: property.clone(),
+                default: prop.status,
+                other: status,
+            }),
+            Some(_) => {}
+            None => differences
+                .push(Difference::Missing { property: property.clone(), in_default: true }),
+        }
+        default_statuses.insert(property, prop.status);
+    }
+    differences.extend(
+        other_statuses
+            .into_keys()
+            .filter(|property| !default_statuses.contains_key(property))
+            .map(|property| Difference::Missing { property, in_default: false }),
+    );
+    differences
+}
+
+fn print_differences(harness: &str, backend: &CompareBackend, differences: &[Difference]) {
+    if differences.is_empty() {
+        println!("Backend comparison: harness {harness} has the same results with `{backend}`.");
+        return;
+    }
+    println!("Backend comparison: harness {harness} has different results with `{backend}`:");
+    let completed = |completed: bool| if completed { "completed" } else { "did not complete" };
+    for difference in differences {
+        match difference {
+            Difference::Verdict { default, other } => {
+                println!(
+                    " - Verification {default} with the default backend, {other} with `{backend}`"
+                )
+            }
+            Difference::Incomplete { default, other } => println!(
+                " - Verification {} with the default backend, {} with `{backend}`",
+                completed(*default),
+                completed(*other)
+            ),
+            Difference::Status { property, default, other } => {
+                println!(
+                    " - {property}: {default} with the default backend, {other} with `{backend}`"
+                )
+            }
+            Difference::Missing { property, in_default: true } => {
+                println!(" - {property}: only reported by the default backend")
+            }
+            Difference::Missing { property, in_default: false } => {
+                println!(" - {property}: only reported by `{backend}`")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::{Property, PropertyId, SourceLocation};
+    use CheckStatus::*;
+
+    fn property(name: &str, status: CheckStatus) -> Property {
+        let (class, id) = name.split_once('.').unwrap();
+        Property {
+            description: format!("{class} {id}"),
+            property_id: PropertyId {
+                fn_name: None,
+                class: class.to_string(),
+                id: id.parse().unwrap(),
+            },
+            source_location: SourceLocation {
+                column: None,
+                file: None,
+                function: None,
+                line: None,
+            },
+            status,
+            reach: None,
+            trace: None,
+            cover_label: None,
+        }
+    }
+
+    fn result(status: VerificationStatus, properties: Vec<Property>) -> VerificationResult {
+        VerificationResult { status, results: Ok(properties), ..VerificationResult::mock_success() }
+    }
+
+    #[test]
+    fn check_same_results() {
+        let default = result(
+            VerificationStatus::Success,
+            vec![property("assertion.1", Success), property("cover.1", Satisfied)],
+        );
+        let other = result(
+            VerificationStatus::Success,
+            vec![property("cover.1", Satisfied), property("assertion.1", Success)],
+        );
+        assert_eq!(diff_results(&default, &other), vec![]);
+    }
+
+    #[test]
+    fn check_different_results() {
+        let default = result(
+            VerificationStatus::Success,
+            vec![property("assertion.1", Success), property("assertion.2", Success)],
+        );
+        let other = result(
+            VerificationStatus::Failure,
+            vec![property("assertion.1", Failure), property("assertion.3", Success)],
+        );
+        assert_eq!(
+            diff_results(&default, &other),
+            vec![
+                Difference::Verdict {
+                    default: VerificationStatus::Success,
+                    other: VerificationStatus::Failure
+                },
+                Difference::Status {
+                    property: "assertion.1".to_string(),
+                    default: Success,
+                    other: Failure
+                },
+                Difference::Missing { property: "assertion.2".to_string(), in_default: true },
+                Difference::Missing { property: "assertion.3".to_string(), in_default: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn check_incomplete_results() {
+        let default = result(VerificationStatus::Failure, vec![property("assertion.1", Failure)]);
+        let other = VerificationResult {
+            results: Err(crate::call_cbmc::ExitStatus::Timeout),
+            ..result(VerificationStatus::Failure, vec![])
+        };
+        assert_eq!(
+            diff_results(&default, &other),
+            vec![Difference::Incomplete { default: true, other: false }]
+        );
+    }
+}
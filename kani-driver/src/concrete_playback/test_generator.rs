@@ -258,7 +258,7 @@ fn gen_test_doc(harness: &HarnessMetadata, property: &Property) -> String {
         property.property_class(),
         property.description
     ));
-    if !harness.attributes.stubs.is_empty() {
+    if harness.attributes.has_stubs() {
         doc_str.push_str(
             r#"///
 /// # Warning
@@ -407,7 +407,9 @@ pub fn extract_harness_values(
         result_items
             .iter()
             .filter(|prop| {
-                (prop.property_class() != "unwind" && prop.status == CheckStatus::Failure)
+                (prop.property_class() != "unwind"
+                    && !prop.is_recursion_limit_property()
+                    && prop.status == CheckStatus::Failure)
                     || (prop.property_class() == "cover" && prop.status == CheckStatus::Satisfied)
             })
             .map(|property| {
@@ -740,6 +742,7 @@ fn check_concrete_vals_extractor_primitive() {
             },
             status: CheckStatus::Failure,
             reach: None,
+            cover_label: None,
             source_location: SourceLocation {
                 column: None,
                 file: None,
@@ -785,6 +788,7 @@ fn check_concrete_vals_extractor_array() {
             },
             status: CheckStatus::Failure,
             reach: None,
+            cover_label: None,
             source_location: SourceLocation {
                 column: None,
                 file: None,
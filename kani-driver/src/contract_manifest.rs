@@ -0,0 +1,155 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Publish and consume contract manifests, which let crates use the verified contracts of their
+//! dependencies instead of verifying the body of the functions again.
+
+use anyhow::{Context, Result};
+use cargo_metadata::{Metadata, Package};
+use kani_metadata::{CONTRACT_MANIFEST_FILE, ContractManifest};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use crate::call_cbmc::VerificationStatus;
+use crate::harness_runner::HarnessResult;
+use crate::project::Project;
+use crate::session::KaniSession;
+use crate::util::{info_operation, warning};
+
+/// Return the name of the library crate of `package`, if it has one.
+fn lib_crate_name(package: &Package) -> Option<String> {
+    package
+        .targets
+        .iter()
+        .find(|target| target.is_lib())
+        .map(|target| target.name.replace('-', "_"))
+}
+
+impl KaniSession {
+    /// Find the contract manifests published by the packages in `metadata` that are not verified.
+    ///
+    /// Manifests that do not match the package, e.g. because they were published for another
+    /// version, are ignored with a warning.
+    pub fn dependency_contract_manifests(
+        &self,
+        metadata: &Metadata,
+        verified: &[&Package],
+    ) -> Vec<PathBuf> {
+        // Checking a contract with CBMC requires every replaced function to be listed in the
+        // harness metadata, so we only use the contracts of dependencies with Kani's encoding.
+        if !self.args.is_function_contracts_enabled()
+            || self.args.no_dependency_contracts
+            || self.args.cbmc_contracts
+        {
+            return vec![];
+        }
+        let mut manifests = vec![];
+        for package in metadata.packages.iter().filter(|package| !verified.contains(package)) {
+            let path = package.manifest_path.with_file_name(CONTRACT_MANIFEST_FILE);
+            if !path.exists() {
+                continue;
+            }
+            let manifest = match read_manifest(path.as_std_path()) {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    warning(&format!("ignoring contract manifest `{path}`: {err:#}"));
+                    continue;
+                }
+            };
+            if Some(&manifest.crate_name) != lib_crate_name(package).as_ref()
+                || manifest.version != package.version.to_string()
+            {
+                warning(&format!(
+                    "ignoring contract manifest `{path}`, which was generated for `{}` v{} \
+                    instead of `{}` v{}",
+                    manifest.crate_name, manifest.version, package.name, package.version
+                ));
+                continue;
+            }
+            if !self.args.common_args.quiet {
+                info_operation(
+                    "Using",
+                    &format!(
+                        "the verified contracts of {} function(s) of `{}` v{}",
+                        manifest.functions.len(),
+                        package.name,
+                        package.version
+                    ),
+                );
+            }
+            manifests.push(path.into_std_path_buf());
+        }
+        manifests
+    }
+
+    /// Write the contract manifest of each verified package with a library crate.
+    ///
+    /// A function is listed if it has at least one contract harness, and all of its contract
+    /// harnesses were verified successfully in this session.
+    pub(crate) fn write_contract_manifests(
+        &self,
+        project: &Project,
+        results: &[HarnessResult<'_>],
+    ) -> Result<()> {
+        let Some(cargo_metadata) = &project.cargo_metadata else {
+            warning("`--emit-contract-manifest` requires `cargo kani`. No manifest was written.");
+            return Ok(());
+        };
+        let statuses: HashMap<_, _> = results
+            .iter()
+            .map(|result| {
+                let harness = result.harness;
+                ((harness.crate_name.as_str(), harness.pretty_name.as_str()), result.result.status)
+            })
+            .collect();
+
+        for package in cargo_metadata.workspace_packages() {
+            let Some(crate_name) = lib_crate_name(package) else {
+                continue;
+            };
+            // The library can be compiled more than once, e.g., with `--tests`.
+            let crates: Vec<_> =
+                project.metadata.iter().filter(|krate| krate.crate_name == crate_name).collect();
+            if crates.is_empty() {
+                continue;
+            }
+            let functions: BTreeSet<_> = crates
+                .iter()
+                .flat_map(|krate| &krate.contracted_functions)
+                .filter(|function| {
+                    !function.harnesses.is_empty()
+                        && function.harnesses.iter().all(|harness| {
+                            statuses.get(&(crate_name.as_str(), harness.as_str()))
+                                == Some(&VerificationStatus::Success)
+                        })
+                })
+                .map(|function| function.function.clone())
+                .collect();
+            let manifest = ContractManifest {
+                crate_name,
+                version: package.version.to_string(),
+                functions: functions.into_iter().collect(),
+            };
+            let path = package.manifest_path.with_file_name(CONTRACT_MANIFEST_FILE);
+            let file = File::create(&path).with_context(|| format!("Failed to create `{path}`"))?;
+            serde_json::to_writer_pretty(BufWriter::new(file), &manifest)?;
+            if !self.args.common_args.quiet {
+                info_operation(
+                    "Wrote",
+                    &format!(
+                        "the contract manifest of `{}` with {} verified contract(s) to `{path}`",
+                        package.name,
+                        manifest.functions.len()
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<ContractManifest> {
+    let file = File::open(path)?;
+    Ok(serde_json::from_reader(BufReader::new(file))?)
+}
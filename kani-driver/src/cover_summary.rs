@@ -0,0 +1,141 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the summary of labeled cover properties, i.e., `kani::cover!(cond, label = name)`.
+//!
+//! A label may be used by several cover properties, in one or more harnesses. We report a label as
+//! satisfied if any of its cover properties is satisfied in any harness, so that CI can check that
+//! every scenario the labels describe is reachable by at least one harness.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::cbmc_output_parser::CheckStatus;
+use crate::harness_runner::HarnessResult;
+
+#[derive(Debug, PartialEq, Serialize)]
+struct CoverLabel<'a> {
+    label: &'a str,
+    status: CheckStatus,
+    harnesses: Vec<HarnessCover<'a>>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct HarnessCover<'a> {
+    harness: &'a str,
+    status: CheckStatus,
+}
+
+/// The summary, as written to the JSON file.
+#[derive(Serialize)]
+struct CoverSummary<'a> {
+    covers: &'a [CoverLabel<'a>],
+    satisfied: usize,
+    total: usize,
+}
+
+/// A label is satisfied if any of its cover properties is, and unreachable only if all of them
+/// are. Otherwise, it's undetermined if any of them is, and unsatisfiable if not.
+fn merge_status(first: CheckStatus, second: CheckStatus) -> CheckStatus {
+    use CheckStatus::*;
+    match (first, second) {
+        (Satisfied, _) | (_, Satisfied) => Satisfied,
+        (Undetermined | Unknown, _) | (_, Undetermined | Unknown) => Undetermined,
+        (Unreachable, Unreachable) => Unreachable,
+        _ => Unsatisfiable,
+    }
+}
+
+/// Aggregate the status of each `(harness, label, status)` cover property by label. Both the labels
+/// and the harnesses of each label are sorted by name.
+fn aggregate<'a>(
+    covers: impl IntoIterator<Item = (&'a str, &'a str, CheckStatus)>,
+) -> Vec<CoverLabel<'a>> {
+    let mut labels: BTreeMap<&str, Vec<HarnessCover>> = BTreeMap::new();
+    for (harness, label, status) in covers {
+        let harnesses = labels.entry(label).or_default();
+        match harnesses.iter_mut().find(|cover| cover.harness == harness) {
+            Some(cover) => cover.status = merge_status(cover.status, status),
+            None => harnesses.push(HarnessCover { harness, status }),
+        }
+    }
+    labels
+        .into_iter()
+        .map(|(label, mut harnesses)| {
+            harnesses.sort_by(|a, b| a.harness.cmp(b.harness));
+            let status = harnesses.iter().map(|cover| cover.status).reduce(merge_status).unwrap();
+            CoverLabel { label, status, harnesses }
+        })
+        .collect()
+}
+
+fn labeled_covers<'a>(results: &'a [HarnessResult<'_>]) -> Vec<CoverLabel<'a>> {
+    aggregate(results.iter().flat_map(|result| {
+        result.result.results.iter().flatten().filter_map(|prop| {
+            let label = prop.cover_label.as_deref()?;
+            Some((result.harness.pretty_name.as_str(), label, prop.status))
+        })
+    }))
+}
+
+/// Write the status of every label to `path` as JSON.
+pub(crate) fn write_cover_summary(path: &Path, results: &[HarnessResult<'_>]) -> Result<()> {
+    let covers = labeled_covers(results);
+    let summary = CoverSummary {
+        covers: &covers,
+        satisfied: covers.iter().filter(|cover| cover.status == CheckStatus::Satisfied).count(),
+        total: covers.len(),
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&summary)?)
+        .with_context(|| format!("Failed to write cover summary to `{}`", path.display()))
+}
+
+/// Print the status of every label, if any cover property has one.
+pub(crate) fn print_cover_summary(results: &[HarnessResult<'_>]) {
+    let covers = labeled_covers(results);
+    if covers.is_empty() {
+        return;
+    }
+    println!("\nCover Properties:");
+    for cover in &covers {
+        let harnesses: Vec<_> = cover
+            .harnesses
+            .iter()
+            .map(|harness| format!("{}: {}", harness.harness, harness.status))
+            .collect();
+        println!(" - {}: {} ({})", cover.label, cover.status, harnesses.join(", "));
+    }
+    let satisfied = covers.iter().filter(|cover| cover.status == CheckStatus::Satisfied).count();
+    println!(" ** {satisfied} of {} labeled cover properties satisfied", covers.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CheckStatus::*;
+
+    #[test]
+    fn check_aggregate_by_label() {
+        let covers = aggregate([
+            ("check_b", "full", Unreachable),
+            ("check_a", "full", Unsatisfiable),
+            ("check_a", "empty", Unreachable),
+            ("check_b", "empty", Satisfied),
+            ("check_b", "full", Unknown),
+            ("check_a", "never", Unreachable),
+        ]);
+        let statuses: Vec<_> = covers.iter().map(|cover| (cover.label, cover.status)).collect();
+        assert_eq!(
+            statuses,
+            [("empty", Satisfied), ("full", Undetermined), ("never", Unreachable)]
+        );
+        assert_eq!(
+            covers[1].harnesses,
+            [
+                HarnessCover { harness: "check_a", status: Unsatisfiable },
+                HarnessCover { harness: "check_b", status: Undetermined },
+            ]
+        );
+    }
+}
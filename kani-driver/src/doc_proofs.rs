@@ -0,0 +1,355 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the `doc-proofs` subcommand, which documents what is proven about a package: an index
+//! of its harnesses and functions under contract, with their doc comments, the functions that each
+//! harness covers, and the status of the harnesses in earlier runs.
+//!
+//! The harnesses and the contracts come from the metadata of the compiler, which also records the
+//! functions that each harness reaches. The package of each crate comes from `cargo metadata`, and
+//! the status of the harnesses comes from the results that `--statistics` or `kani merge-results`
+//! wrote. The subcommand only compiles the package, so the status is as recent as those results.
+
+use anyhow::{Context, Result};
+use cargo_metadata::Metadata;
+use kani_metadata::{ContractedFunction, HarnessKind, HarnessMetadata};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::args::doc_proofs_args::{CargoDocProofsArgs, DocFormat};
+use crate::merge_results::{HarnessEntry, dedup, escape_html, read_results};
+use crate::project;
+use crate::session::KaniSession;
+use crate::{InvocationType, print_kani_version};
+
+pub fn doc_proofs_cargo(args: CargoDocProofsArgs) -> Result<()> {
+    let quiet = args.verify_opts.common_args.quiet;
+    let mut session = KaniSession::new(args.verify_opts)?;
+    session.record_reachable_functions = true;
+    if !quiet {
+        print_kani_version(InvocationType::CargoKani(vec![]));
+    }
+    let project = project::cargo_project(&mut session, false)?;
+
+    let files = args.results.iter().map(|path| read_results(path)).collect::<Result<Vec<_>>>()?;
+    let (entries, _) = dedup(files);
+    let results: HashMap<&str, &HarnessEntry> =
+        entries.iter().map(|entry| (entry.harness.as_str(), entry)).collect();
+
+    let mut crates: Vec<_> = project
+        .metadata
+        .iter()
+        .map(|metadata| CrateDocs {
+            crate_name: &metadata.crate_name,
+            package: project
+                .cargo_metadata
+                .as_ref()
+                .and_then(|cargo| package_of(cargo, &metadata.crate_name)),
+            harnesses: metadata
+                .proof_harnesses
+                .iter()
+                .filter(|harness| !harness.is_automatically_generated)
+                .map(|harness| HarnessDocs::new(harness, &results))
+                .collect(),
+            contracts: metadata.contracted_functions.iter().collect(),
+        })
+        .collect();
+    crates.sort_by_key(|docs| docs.crate_name);
+    for docs in &mut crates {
+        docs.harnesses.sort_by_key(|harness| &harness.metadata.pretty_name);
+        docs.contracts.sort_by_key(|contract| &contract.function);
+    }
+
+    let content = match args.format {
+        DocFormat::Html => html(&crates, &results),
+        DocFormat::Markdown => markdown(&crates, &results),
+    };
+    let output = args
+        .output
+        .unwrap_or_else(|| PathBuf::from(format!("kani-proofs.{}", args.format.extension())));
+    std::fs::write(&output, content)
+        .with_context(|| format!("Failed to write the proof index to `{}`", output.display()))?;
+    if !quiet {
+        println!("Wrote the index of the proofs to {}", output.display());
+    }
+    Ok(())
+}
+
+/// The name and version of the package that `crate_name` belongs to.
+fn package_of(metadata: &Metadata, crate_name: &str) -> Option<String> {
+    metadata
+        .workspace_packages()
+        .into_iter()
+        .find(|package| {
+            package.targets.iter().any(|target| target.name.replace('-', "_") == crate_name)
+        })
+        .map(|package| format!("{} {}", package.name, package.version))
+}
+
+/// The harnesses and the functions under contract of a crate.
+struct CrateDocs<'a> {
+    crate_name: &'a str,
+    package: Option<String>,
+    harnesses: Vec<HarnessDocs<'a>>,
+    contracts: Vec<&'a ContractedFunction>,
+}
+
+struct HarnessDocs<'a> {
+    metadata: &'a HarnessMetadata,
+    /// The functions of the package that the harness reaches, other than the harness itself.
+    covered: BTreeSet<&'a str>,
+    /// The latest result of the harness, if it was verified.
+    result: Option<&'a HarnessEntry>,
+}
+
+impl<'a> HarnessDocs<'a> {
+    fn new(metadata: &'a HarnessMetadata, results: &HashMap<&str, &'a HarnessEntry>) -> Self {
+        let covered = metadata
+            .reachable_functions
+            .iter()
+            .map(|function| function.name.as_str())
+            .filter(|name| *name != metadata.pretty_name && !name.contains("{closure"))
+            .collect();
+        HarnessDocs {
+            metadata,
+            covered,
+            result: results.get(metadata.pretty_name.as_str()).copied(),
+        }
+    }
+
+    /// The kind of harness, and the function whose contract it checks if there is one.
+    fn kind(&self) -> (&'static str, Option<&'a str>) {
+        match &self.metadata.attributes.kind {
+            HarnessKind::ProofForContract { target_fn } => {
+                ("proof for contract", Some(target_fn.as_str()))
+            }
+            HarnessKind::Proof | HarnessKind::Test => ("proof", None),
+        }
+    }
+
+    fn location(&self) -> String {
+        format!("{}:{}", self.metadata.original_file, self.metadata.original_start_line)
+    }
+}
+
+fn status(result: Option<&HarnessEntry>) -> &str {
+    result.map_or("Not verified", |entry| entry.status.as_str())
+}
+
+/// Whether every harness of `contract` was verified successfully.
+fn is_proven(contract: &ContractedFunction, results: &HashMap<&str, &HarnessEntry>) -> bool {
+    !contract.harnesses.is_empty()
+        && contract
+            .harnesses
+            .iter()
+            .all(|harness| results.get(harness.as_str()).is_some_and(|entry| entry.succeeded()))
+}
+
+fn summary(crates: &[CrateDocs]) -> String {
+    let harnesses = crates.iter().flat_map(|docs| &docs.harnesses);
+    let total = harnesses.clone().count();
+    let verified = harnesses.clone().filter(|h| h.result.is_some_and(|r| r.succeeded())).count();
+    let failed = harnesses.filter(|h| h.result.is_some_and(|r| !r.succeeded())).count();
+    let contracts: usize = crates.iter().map(|docs| docs.contracts.len()).sum();
+    format!(
+        "{total} harnesses ({verified} verified, {failed} failed, {} not verified), \
+        {contracts} functions under contract.",
+        total - verified - failed
+    )
+}
+
+fn markdown(crates: &[CrateDocs], results: &HashMap<&str, &HarnessEntry>) -> String {
+    let mut md = String::from("# Kani Proofs\n\n");
+    let _ = writeln!(md, "{}\n", summary(crates));
+    for docs in crates {
+        match &docs.package {
+            Some(package) => {
+                let _ = writeln!(md, "## Crate `{}` ({package})\n", docs.crate_name);
+            }
+            None => {
+                let _ = writeln!(md, "## Crate `{}`\n", docs.crate_name);
+            }
+        }
+        if !docs.harnesses.is_empty() {
+            md.push_str("### Harnesses\n\n");
+        }
+        for harness in &docs.harnesses {
+            let _ = writeln!(md, "#### `{}`\n", harness.metadata.pretty_name);
+            match harness.kind() {
+                (kind, Some(target)) => {
+                    let _ = writeln!(md, "- **Kind:** {kind} `{target}`");
+                }
+                (kind, None) => {
+                    let _ = writeln!(md, "- **Kind:** {kind}");
+                }
+            }
+            let _ = writeln!(md, "- **Location:** {}", harness.location());
+            let _ = writeln!(md, "- **Status:** {}", status(harness.result));
+            if !harness.covered.is_empty() {
+                let covered: Vec<_> = harness.covered.iter().map(|f| format!("`{f}`")).collect();
+                let _ = writeln!(md, "- **Covers:** {}", covered.join(", "));
+            }
+            md.push('\n');
+            if !harness.metadata.docs.is_empty() {
+                let _ = writeln!(md, "{}\n", harness.metadata.docs);
+            }
+        }
+        if !docs.contracts.is_empty() {
+            md.push_str("### Contracts\n\n");
+        }
+        for contract in &docs.contracts {
+            let _ = writeln!(md, "#### `{}`\n", contract.function);
+            let _ = writeln!(md, "- **Location:** {}", contract.file);
+            let harnesses: Vec<_> = contract
+                .harnesses
+                .iter()
+                .map(|harness| {
+                    format!("`{harness}` ({})", status(results.get(harness.as_str()).copied()))
+                })
+                .collect();
+            let _ = writeln!(
+                md,
+                "- **Harnesses:** {}",
+                if harnesses.is_empty() { "none".to_string() } else { harnesses.join(", ") }
+            );
+            let proven = if is_proven(contract, results) { "Proven" } else { "Not proven" };
+            let _ = writeln!(md, "- **Status:** {proven}\n");
+            if !contract.docs.is_empty() {
+                let _ = writeln!(md, "{}\n", contract.docs);
+            }
+        }
+    }
+    md
+}
+
+/// A self-contained HTML page with the same content as [markdown].
+fn html(crates: &[CrateDocs], results: &HashMap<&str, &HarnessEntry>) -> String {
+    let code = |text: &str| format!("<code>{}</code>", escape_html(text));
+    let status_class = |result: Option<&HarnessEntry>| match result {
+        Some(entry) if entry.succeeded() => "success",
+        Some(_) => "failure",
+        None => "unknown",
+    };
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>Kani Proofs</title>\n<style>\n\
+        .success { color: green; }\n.failure { color: red; }\n.unknown { color: gray; }\n\
+        .docs { white-space: pre-wrap; }\n\
+        </style>\n</head>\n<body>\n<h1>Kani Proofs</h1>\n",
+    );
+    let _ = writeln!(html, "<p>{}</p>", summary(crates));
+    for docs in crates {
+        let package =
+            docs.package.as_ref().map_or(String::new(), |package| format!(" ({package})"));
+        let _ = writeln!(html, "<h2>Crate {}{}</h2>", code(docs.crate_name), escape_html(&package));
+        if !docs.harnesses.is_empty() {
+            html.push_str("<h3>Harnesses</h3>\n");
+        }
+        for harness in &docs.harnesses {
+            let _ = writeln!(html, "<h4>{}</h4>\n<ul>", code(&harness.metadata.pretty_name));
+            let (kind, target) = harness.kind();
+            let target = target.map_or(String::new(), |target| format!(" {}", code(target)));
+            let _ = writeln!(html, "<li><b>Kind:</b> {kind}{target}</li>");
+            let _ =
+                writeln!(html, "<li><b>Location:</b> {}</li>", escape_html(&harness.location()));
+            let _ = writeln!(
+                html,
+                "<li><b>Status:</b> <span class=\"{}\">{}</span></li>",
+                status_class(harness.result),
+                escape_html(status(harness.result))
+            );
+            if !harness.covered.is_empty() {
+                let covered: Vec<_> = harness.covered.iter().map(|f| code(f)).collect();
+                let _ = writeln!(html, "<li><b>Covers:</b> {}</li>", covered.join(", "));
+            }
+            html.push_str("</ul>\n");
+            if !harness.metadata.docs.is_empty() {
+                let _ =
+                    writeln!(html, "<p class=\"docs\">{}</p>", escape_html(&harness.metadata.docs));
+            }
+        }
+        if !docs.contracts.is_empty() {
+            html.push_str("<h3>Contracts</h3>\n");
+        }
+        for contract in &docs.contracts {
+            let _ = writeln!(html, "<h4>{}</h4>\n<ul>", code(&contract.function));
+            let _ = writeln!(html, "<li><b>Location:</b> {}</li>", escape_html(&contract.file));
+            let harnesses: Vec<_> = contract
+                .harnesses
+                .iter()
+                .map(|harness| {
+                    let result = results.get(harness.as_str()).copied();
+                    format!(
+                        "{} (<span class=\"{}\">{}</span>)",
+                        code(harness),
+                        status_class(result),
+                        escape_html(status(result))
+                    )
+                })
+                .collect();
+            let _ = writeln!(
+                html,
+                "<li><b>Harnesses:</b> {}</li>",
+                if harnesses.is_empty() { "none".to_string() } else { harnesses.join(", ") }
+            );
+            let (class, proven) = if is_proven(contract, results) {
+                ("success", "Proven")
+            } else {
+                ("unknown", "Not proven")
+            };
+            let _ =
+                writeln!(html, "<li><b>Status:</b> <span class=\"{class}\">{proven}</span></li>");
+            html.push_str("</ul>\n");
+            if !contract.docs.is_empty() {
+                let _ = writeln!(html, "<p class=\"docs\">{}</p>", escape_html(&contract.docs));
+            }
+        }
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::tests::mock_proof_harness;
+
+    fn entry(harness: &str, status: &str) -> HarnessEntry {
+        HarnessEntry {
+            harness: harness.to_string(),
+            status: status.to_string(),
+            runtime: 1.0,
+            timestamp: None,
+            other: Default::default(),
+        }
+    }
+
+    #[test]
+    fn check_markdown() {
+        let mut harness = mock_proof_harness("check_foo", None, Some("krate"), None);
+        harness.docs = "Check that `foo` never panics.".to_string();
+        harness.attributes.kind = HarnessKind::ProofForContract { target_fn: "foo".to_string() };
+        let contract = ContractedFunction {
+            function: "foo".to_string(),
+            file: "src/lib.rs".to_string(),
+            harnesses: vec!["check_foo".to_string()],
+            docs: String::new(),
+        };
+        let success = entry("check_foo", "Success");
+        let results = HashMap::from([("check_foo", &success)]);
+        let crates = vec![CrateDocs {
+            crate_name: "krate",
+            package: Some("krate 0.1.0".to_string()),
+            harnesses: vec![HarnessDocs::new(&harness, &results)],
+            contracts: vec![&contract],
+        }];
+        let md = markdown(&crates, &results);
+        assert!(md.contains("1 harnesses (1 verified, 0 failed, 0 not verified)"));
+        assert!(md.contains("## Crate `krate` (krate 0.1.0)"));
+        assert!(md.contains("- **Kind:** proof for contract `foo`"));
+        assert!(md.contains("- **Status:** Success"));
+        assert!(md.contains("Check that `foo` never panics."));
+        assert!(md.contains("- **Harnesses:** `check_foo` (Success)\n- **Status:** Proven"));
+        assert!(html(&crates, &results).contains("<span class=\"success\">Success</span>"));
+    }
+}
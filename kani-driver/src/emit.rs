@@ -0,0 +1,104 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `--emit`, which persists the artifacts of each harness to a directory of the target
+//! dir, so that users can inspect what Kani actually verifies.
+//!
+//! The artifacts that Kani generates during the build are named after the mangled name of the
+//! harness, and they are overwritten by the instrumentation. Instead, the emitted artifacts are named
+//! after the harness, e.g., `<target-dir>/kani-emit/my_crate.proofs.check_foo.goto` for the
+//! harness `my_crate::proofs::check_foo`.
+
+use anyhow::{Context, Result};
+use kani_metadata::{ArtifactType, HarnessMetadata};
+use std::path::{Path, PathBuf};
+
+use crate::args::EmitKind;
+use crate::project::Project;
+use crate::session::KaniSession;
+
+/// The name of the directory where the artifacts are emitted.
+const EMIT_DIR: &str = "kani-emit";
+
+/// The artifacts that were emitted for a harness.
+#[derive(Debug, Default)]
+pub struct EmittedArtifacts {
+    /// The goto program after instrumentation.
+    pub goto: Option<PathBuf>,
+    /// The MIR after Kani's transformations.
+    pub mir: Option<PathBuf>,
+}
+
+impl KaniSession {
+    /// The directory where the artifacts of `project` are emitted.
+    pub fn emit_dir(&self, project: &Project) -> PathBuf {
+        match &project.cargo_metadata {
+            Some(metadata) => self.kani_target_dir(metadata).join(EMIT_DIR),
+            None => project.outdir.join(EMIT_DIR),
+        }
+    }
+
+    /// Copy the artifacts that the user asked for with `--emit` to the emit directory.
+    /// This must be called after the goto binary of the harness was instrumented.
+    pub fn emit_harness_artifacts(
+        &self,
+        project: &Project,
+        harness: &HarnessMetadata,
+        goto_file: &Path,
+    ) -> Result<EmittedArtifacts> {
+        let mut emitted = EmittedArtifacts::default();
+        if self.args.emit.is_empty() {
+            return Ok(emitted);
+        }
+        let dir = self.emit_dir(project);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create directory `{}`", dir.display()))?;
+        let stem = emit_file_stem(&harness.pretty_name);
+        for kind in &self.args.emit {
+            match kind {
+                EmitKind::Goto => {
+                    let path = dir.join(format!("{stem}.goto"));
+                    copy_artifact(goto_file, &path)?;
+                    emitted.goto = Some(path);
+                }
+                EmitKind::Mir => {
+                    // The MIR is only generated for harnesses that were compiled with `--emit mir`.
+                    if let Some(mir) = project.get_harness_artifact(harness, ArtifactType::Mir) {
+                        let extension: &str = ArtifactType::Mir.as_ref();
+                        let path = dir.join(format!("{stem}.{extension}"));
+                        copy_artifact(mir, &path)?;
+                        emitted.mir = Some(path);
+                    }
+                }
+            }
+        }
+        Ok(emitted)
+    }
+}
+
+fn copy_artifact(from: &Path, to: &Path) -> Result<()> {
+    std::fs::copy(from, to)
+        .with_context(|| format!("Failed to copy `{}` to `{}`", from.display(), to.display()))?;
+    Ok(())
+}
+
+/// The file stem of the artifacts of a harness: its name, where the path separators are replaced by
+/// `.` and any other character that may not be valid in a file name by `_`.
+fn emit_file_stem(pretty_name: &str) -> String {
+    pretty_name
+        .replace("::", ".")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::emit_file_stem;
+
+    #[test]
+    fn check_emit_file_stem() {
+        assert_eq!(emit_file_stem("check_foo"), "check_foo");
+        assert_eq!(emit_file_stem("my_crate::proofs::check_foo"), "my_crate.proofs.check_foo");
+        assert_eq!(emit_file_stem("<Foo as Bar>::check::<u8>"), "_Foo_as_Bar_.check._u8_");
+    }
+}
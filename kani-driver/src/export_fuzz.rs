@@ -0,0 +1,521 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the `export-fuzz` subcommand, which translates a harness into a `proptest` test, so
+//! that the same property can be tested on targets where Kani isn't available.
+//!
+//! The harness is parsed from its source file, and translated as follows:
+//! - The arguments of the harness, and the variables initialized by `kani::any()` in its top-level
+//!   statements, become the inputs of the test, which are generated by
+//!   `proptest::prelude::any::<T>()`. Their type must be annotated, e.g. `let x: u8 = kani::any();`,
+//!   or given as a generic argument, e.g. `kani::any::<u8>()`.
+//! - A variable initialized by `kani::any_where(f)` is an input that is rejected if `f` is false.
+//! - An input that shadows an earlier one is generated under a fresh name, and bound to its own
+//!   name where the harness initializes it.
+//! - A variable initialized by `kani::vec::any_vec::<T, N>()` is a vector of up to `N` elements.
+//! - `kani::assume(cond)` becomes `prop_assume!(cond)`, and `kani::assert(cond, msg)` becomes
+//!   `assert!(cond, "{}", msg)`.
+//! - `kani::cover!(..)` is dropped, since tests don't report coverage.
+//!
+//! Any other use of the Kani API cannot be translated, and is reported as an error.
+
+use anyhow::{Context, Result, bail};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use syn::visit::Visit;
+use syn::visit_mut::{self, VisitMut};
+use syn::{Expr, FnArg, GenericArgument, Ident, Item, ItemFn, Pat, PathArguments, Stmt, Type};
+
+use crate::args::export_fuzz_args::{
+    CargoExportFuzzArgs, CommonExportFuzzArgs, StandaloneExportFuzzArgs,
+};
+use crate::inspect::single_harness;
+use crate::project::{self, Project};
+use crate::session::KaniSession;
+use crate::util::info_operation;
+use crate::{InvocationType, print_kani_version};
+
+/// The largest tuple of strategies that `proptest` supports.
+const MAX_INPUTS: usize = 12;
+
+pub fn export_fuzz_cargo(args: CargoExportFuzzArgs) -> Result<()> {
+    let mut session = KaniSession::new(args.verify_opts)?;
+    if !session.args.common_args.quiet {
+        print_kani_version(InvocationType::CargoKani(vec![]));
+    }
+    let project = project::cargo_project(&mut session, false)?;
+    export_fuzz(project, session, args.common_export_fuzz_args)
+}
+
+pub fn export_fuzz_standalone(args: StandaloneExportFuzzArgs) -> Result<()> {
+    let session = KaniSession::new(args.verify_opts)?;
+    if !session.args.common_args.quiet {
+        print_kani_version(InvocationType::Standalone);
+    }
+    let project = project::standalone_project(&args.input, args.crate_name, &session)?;
+    export_fuzz(project, session, args.common_export_fuzz_args)
+}
+
+fn export_fuzz(project: Project, session: KaniSession, args: CommonExportFuzzArgs) -> Result<()> {
+    let harness = single_harness(&session, &project, &session.args.harnesses[0])?;
+    let path = Path::new(&harness.original_file);
+    let source =
+        fs::read_to_string(path).with_context(|| format!("Failed to read `{}`", path.display()))?;
+    let file = syn::parse_file(&source)
+        .with_context(|| format!("Failed to parse `{}`", path.display()))?;
+    let Some(harness_fn) = find_harness(&file.items, &harness.pretty_name) else {
+        bail!("Failed to find harness `{}` in `{}`", harness.pretty_name, path.display());
+    };
+    let test = translate(harness_fn, harness.attributes.should_panic)?;
+    let location = std::env::current_dir()
+        .ok()
+        .and_then(|dir| pathdiff::diff_paths(path, dir))
+        .unwrap_or_else(|| path.to_path_buf());
+    let module = format_ident!("kani_export_{}", harness_fn.sig.ident);
+    let test = quote!(
+        #[cfg(test)]
+        mod #module {
+            use super::*;
+
+            #test
+        }
+    );
+    let test = format!(
+        "// Generated by `kani export-fuzz` from the harness `{}` in `{}`.\n{}",
+        harness.pretty_name,
+        location.display(),
+        rustfmt(test.to_string())
+    );
+
+    if let Some(output) = &args.output {
+        fs::write(output, &test)
+            .with_context(|| format!("Failed to write `{}`", output.display()))?;
+        if !session.args.common_args.quiet {
+            info_operation(
+                "Exported",
+                &format!("`{}` to `{}`", harness.pretty_name, output.display()),
+            );
+        }
+    } else {
+        print!("{test}");
+    }
+    Ok(())
+}
+
+/// Find the function of the harness `pretty_name` in `items`, including the items of their inline
+/// modules. If several functions have the name of the harness, pick the one whose inline modules
+/// match the most segments of the module path of the harness.
+fn find_harness<'a>(items: &'a [Item], pretty_name: &str) -> Option<&'a ItemFn> {
+    let name = pretty_name.rsplit("::").next().unwrap();
+    let mut candidates = vec![];
+    collect_fns(items, name, &mut vec![], &mut candidates);
+    candidates
+        .into_iter()
+        .filter(|(path, _)| {
+            let qualified = path.iter().map(String::as_str).chain([name]).collect::<Vec<_>>();
+            let qualified = qualified.join("::");
+            pretty_name == qualified || pretty_name.ends_with(&format!("::{qualified}"))
+        })
+        .max_by_key(|(path, _)| path.len())
+        .map(|(_, harness_fn)| harness_fn)
+}
+
+fn collect_fns<'a>(
+    items: &'a [Item],
+    name: &str,
+    path: &mut Vec<String>,
+    fns: &mut Vec<(Vec<String>, &'a ItemFn)>,
+) {
+    for item in items {
+        match item {
+            Item::Fn(item_fn) if item_fn.sig.ident == name => fns.push((path.clone(), item_fn)),
+            Item::Mod(item_mod) => {
+                if let Some((_, items)) = &item_mod.content {
+                    path.push(item_mod.ident.to_string());
+                    collect_fns(items, name, path, fns);
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Translate the function of a harness into a test that runs its body with the `proptest` runner.
+fn translate(harness_fn: &ItemFn, should_panic: bool) -> Result<TokenStream> {
+    let mut translation = Translation::default();
+    for arg in &harness_fn.sig.inputs {
+        match arg {
+            FnArg::Typed(arg) => {
+                let ty = &arg.ty;
+                translation.add_input((*arg.pat).clone(), quote!(proptest::prelude::any::<#ty>()));
+            }
+            FnArg::Receiver(_) => translation.errors.push("harnesses cannot take `self`".into()),
+        }
+    }
+    for stmt in harness_fn.block.stmts.iter().filter(|stmt| !is_kani_cover(stmt)) {
+        if let Stmt::Local(local) = stmt
+            && let Some(init) = &local.init
+            && init.diverge.is_none()
+            && let Expr::Call(call) = &*init.expr
+            && let Expr::Path(func) = &*call.func
+            && let Some(kani_fn) = kani_path(&func.path)
+            && ["kani::any", "kani::any_where", "kani::vec::any_vec"].contains(&kani_fn.as_str())
+        {
+            let (pat, ty) = match &local.pat {
+                Pat::Type(pat_type) => ((*pat_type.pat).clone(), Some((*pat_type.ty).clone())),
+                pat => (pat.clone(), None),
+            };
+            let generic_args = generic_args(func.path.segments.last().unwrap());
+            let pat_str = pat.to_token_stream().to_string();
+            let Some(ty) = generic_args.first().and_then(as_type).or(ty) else {
+                translation.errors.push(format!(
+                    "cannot infer the type of `{pat_str}`, add a type annotation to it"
+                ));
+                continue;
+            };
+            if kani_fn == "kani::vec::any_vec" {
+                let [GenericArgument::Type(elem), max_len] = generic_args.as_slice() else {
+                    translation.errors.push(format!(
+                        "the length of `{pat_str}` is unknown, use `kani::vec::any_vec::<T, N>()`"
+                    ));
+                    continue;
+                };
+                translation.add_input(
+                    pat,
+                    quote!(proptest::collection::vec(proptest::prelude::any::<#elem>(), 0..=#max_len)),
+                );
+                continue;
+            }
+            if kani_fn == "kani::any_where" {
+                let Pat::Ident(ident) = &pat else {
+                    translation.errors.push(format!(
+                        "`kani::any_where` must initialize a single variable, but found `{pat_str}`"
+                    ));
+                    continue;
+                };
+                let ident = ident.ident.clone();
+                let Some(predicate) = call.args.first() else { continue };
+                translation.add_input(pat, quote!(proptest::prelude::any::<#ty>()));
+                // The type of the argument of the predicate must be given for a closure to
+                // type-check.
+                translation.stmts.push(syn::parse_quote!(
+                    proptest::prop_assume!({
+                        let predicate: &dyn Fn(&#ty) -> bool = &#predicate;
+                        predicate(&#ident)
+                    });
+                ));
+                continue;
+            }
+            translation.add_input(pat, quote!(proptest::prelude::any::<#ty>()));
+            continue;
+        }
+        let mut stmt = stmt.clone();
+        translation.visit_stmt_mut(&mut stmt);
+        translation.stmts.push(stmt);
+    }
+    // The body of the test is followed by `Ok(())`, so its last expression must be a statement.
+    match translation.stmts.last_mut() {
+        Some(Stmt::Expr(_, semi @ None)) => *semi = Some(Default::default()),
+        Some(Stmt::Macro(mac)) if mac.semi_token.is_none() => {
+            mac.semi_token = Some(Default::default())
+        }
+        _ => {}
+    }
+    if translation.inputs.len() > MAX_INPUTS {
+        translation.errors.push(format!(
+            "the harness has {} inputs, but `proptest` supports up to {MAX_INPUTS}",
+            translation.inputs.len()
+        ));
+    }
+    if !translation.errors.is_empty() {
+        bail!(
+            "Failed to translate harness `{}`:\n- {}",
+            harness_fn.sig.ident,
+            translation.errors.join("\n- ")
+        );
+    }
+
+    let name = &harness_fn.sig.ident;
+    let (pats, strategies): (Vec<_>, Vec<_>) = translation.inputs.into_iter().unzip();
+    let (strategy, pattern) = if pats.is_empty() {
+        (quote!(proptest::strategy::Just(())), quote!(()))
+    } else {
+        (quote!((#(#strategies,)*)), quote!((#(#pats,)*)))
+    };
+    let should_panic = should_panic.then(|| quote!(#[should_panic]));
+    let stmts = translation.stmts;
+    Ok(quote!(
+        #[test]
+        #should_panic
+        fn #name() {
+            proptest::test_runner::TestRunner::default()
+                .run(&#strategy, |#pattern| {
+                    #(#stmts)*
+                    Ok(())
+                })
+                .unwrap();
+        }
+    ))
+}
+
+/// The inputs and the statements of the test that a harness translates to.
+#[derive(Default)]
+struct Translation {
+    /// The pattern that binds each input, with the strategy that generates it.
+    inputs: Vec<(Pat, TokenStream)>,
+    /// The names that the patterns of the inputs bind so far.
+    names: Vec<Ident>,
+    stmts: Vec<Stmt>,
+    errors: Vec<String>,
+}
+
+impl Translation {
+    /// Add an input that `pat` binds at this point of the harness.
+    ///
+    /// The inputs are all bound by the closure of the test, where a name can only be bound once.
+    /// So if `pat` shadows an earlier input, the input gets a fresh name, which is bound to `pat`
+    /// here.
+    fn add_input(&mut self, pat: Pat, strategy: TokenStream) {
+        let mut names = BoundNames::default();
+        names.visit_pat(&pat);
+        if names.0.iter().any(|name| self.names.contains(name)) {
+            let fresh = format_ident!("kani_input_{}", self.inputs.len());
+            self.stmts.push(syn::parse_quote!(let #pat = #fresh;));
+            self.inputs.push((syn::parse_quote!(#fresh), strategy));
+        } else {
+            self.inputs.push((pat, strategy));
+        }
+        self.names.extend(names.0);
+    }
+}
+
+/// The names that a pattern binds.
+#[derive(Default)]
+struct BoundNames(Vec<Ident>);
+
+impl Visit<'_> for BoundNames {
+    fn visit_pat_ident(&mut self, pat: &syn::PatIdent) {
+        self.0.push(pat.ident.clone());
+        syn::visit::visit_pat_ident(self, pat);
+    }
+}
+
+impl VisitMut for Translation {
+    fn visit_block_mut(&mut self, block: &mut syn::Block) {
+        block.stmts.retain(|stmt| !is_kani_cover(stmt));
+        visit_mut::visit_block_mut(self, block);
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        if let Stmt::Macro(stmt) = stmt
+            && let Some(path) = kani_path(&stmt.mac.path)
+        {
+            self.errors.push(format!("`{path}!` is not supported"));
+        }
+        visit_mut::visit_stmt_mut(self, stmt);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        visit_mut::visit_expr_mut(self, expr);
+        match expr {
+            Expr::Call(call) => {
+                let Expr::Path(func) = &*call.func else { return };
+                let Some(path) = kani_path(&func.path) else { return };
+                let args = call.args.clone();
+                match path.as_str() {
+                    "kani::assume" => *expr = syn::parse_quote!(proptest::prop_assume!(#args)),
+                    "kani::assert" if args.len() == 2 => {
+                        let (cond, msg) = (&args[0], &args[1]);
+                        *expr = syn::parse_quote!(assert!(#cond, "{}", #msg));
+                    }
+                    "kani::any" | "kani::any_where" | "kani::vec::any_vec" => {
+                        self.errors.push(format!(
+                            "`{path}` can only initialize a variable in the top-level statements \
+                            of the harness"
+                        ));
+                    }
+                    _ => self.errors.push(format!("`{path}` is not supported")),
+                }
+            }
+            Expr::Macro(mac) => {
+                if let Some(path) = kani_path(&mac.mac.path) {
+                    self.errors.push(format!("`{path}!` is not supported"));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Is this a `kani::cover!(..)` statement?
+fn is_kani_cover(stmt: &Stmt) -> bool {
+    let mac = match stmt {
+        Stmt::Macro(stmt) => &stmt.mac,
+        Stmt::Expr(Expr::Macro(expr), _) => &expr.mac,
+        _ => return false,
+    };
+    kani_path(&mac.path).is_some_and(|path| path == "kani::cover")
+}
+
+/// The path of an item of the Kani library, e.g. `kani::any`, without its generic arguments.
+fn kani_path(path: &syn::Path) -> Option<String> {
+    let segments: Vec<_> = path.segments.iter().map(|segment| segment.ident.to_string()).collect();
+    (segments.len() > 1 && segments[0] == "kani").then(|| segments.join("::"))
+}
+
+fn generic_args(segment: &syn::PathSegment) -> Vec<GenericArgument> {
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().cloned().collect(),
+        PathArguments::None | PathArguments::Parenthesized(_) => vec![],
+    }
+}
+
+fn as_type(arg: &GenericArgument) -> Option<Type> {
+    match arg {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    }
+}
+
+/// Format the test with `rustfmt`, or return it unformatted if `rustfmt` is not available.
+fn rustfmt(code: String) -> String {
+    let formatted = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .and_then(|mut child| {
+            child.stdin.take().unwrap().write_all(code.as_bytes())?;
+            child.wait_with_output()
+        });
+    match formatted {
+        Ok(output) if output.status.success() => String::from_utf8(output.stdout).unwrap_or(code),
+        _ => code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_translate() {
+        let harness: ItemFn = syn::parse_quote!(
+            #[kani::proof]
+            fn check_div(divisor: u8) {
+                let dividend: u32 = kani::any();
+                let small: u8 = kani::any_where(|x| *x < 10);
+                let bytes = kani::vec::any_vec::<u8, 4>();
+                kani::assume(divisor != 0);
+                kani::cover!(dividend == 0);
+                kani::assert(dividend / divisor as u32 <= dividend, "no overflow");
+                assert!(bytes.len() <= 4 + small as usize)
+            }
+        );
+        #[rustfmt::skip]
+        let expected = quote!(
+            #[test]
+            fn check_div() {
+                proptest::test_runner::TestRunner::default()
+                    .run(
+                        &(
+                            proptest::prelude::any::<u8>(),
+                            proptest::prelude::any::<u32>(),
+                            proptest::prelude::any::<u8>(),
+                            proptest::collection::vec(proptest::prelude::any::<u8>(), 0..=4),
+                        ),
+                        |(divisor, dividend, small, bytes,)| {
+                            proptest::prop_assume!({
+                                let predicate: &dyn Fn(&u8) -> bool = &|x| *x < 10;
+                                predicate(&small)
+                            });
+                            proptest::prop_assume!(divisor != 0);
+                            assert!(dividend / divisor as u32 <= dividend, "{}", "no overflow");
+                            assert!(bytes.len() <= 4 + small as usize);
+                            Ok(())
+                        }
+                    )
+                    .unwrap();
+            }
+        );
+        let test = translate(&harness, false).unwrap();
+        assert_eq!(test.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn check_translate_shadowed() {
+        let harness: ItemFn = syn::parse_quote!(
+            fn check_shadowed(x: u8) {
+                let y: u8 = kani::any();
+                assert!(x <= y || y < x);
+                let x: u16 = kani::any_where(|x| *x > 0);
+                assert!(x > 0);
+            }
+        );
+        #[rustfmt::skip]
+        let expected = quote!(
+            #[test]
+            fn check_shadowed() {
+                proptest::test_runner::TestRunner::default()
+                    .run(
+                        &(
+                            proptest::prelude::any::<u8>(),
+                            proptest::prelude::any::<u8>(),
+                            proptest::prelude::any::<u16>(),
+                        ),
+                        |(x, y, kani_input_2,)| {
+                            assert!(x <= y || y < x);
+                            let x = kani_input_2;
+                            proptest::prop_assume!({
+                                let predicate: &dyn Fn(&u16) -> bool = &|x| *x > 0;
+                                predicate(&x)
+                            });
+                            assert!(x > 0);
+                            Ok(())
+                        }
+                    )
+                    .unwrap();
+            }
+        );
+        let test = translate(&harness, false).unwrap();
+        assert_eq!(test.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn check_translate_errors() {
+        let harness: ItemFn = syn::parse_quote!(
+            fn check_unsupported() {
+                let x = kani::any();
+                let y: u8 = if kani::any() { 1 } else { 2 };
+                kani::cover!(x == y);
+                let ptr = kani::PointerGenerator::<8>::new();
+            }
+        );
+        let err = translate(&harness, false).unwrap_err().to_string();
+        assert!(err.contains("cannot infer the type of `x`"), "{err}");
+        assert!(err.contains("`kani::any` can only initialize a variable"), "{err}");
+        assert!(err.contains("`kani::PointerGenerator::new` is not supported"), "{err}");
+    }
+
+    #[test]
+    fn check_find_harness() {
+        let file: syn::File = syn::parse_quote!(
+            fn check() {}
+            mod verify {
+                fn check() {}
+            }
+        );
+        let harness = find_harness(&file.items, "verify::check").unwrap();
+        assert_eq!(harness.sig.ident, "check");
+        assert!(std::ptr::eq(
+            harness,
+            find_harness(&file.items, "my_crate::verify::check").unwrap()
+        ));
+        assert!(!std::ptr::eq(harness, find_harness(&file.items, "check").unwrap()));
+    }
+}
@@ -0,0 +1,381 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `cargo kani fix`, which adds the `kani::Arbitrary` and `kani::Invariant` derives
+//! that the harnesses of a package require to compile.
+//!
+//! Kani compiles the package and looks for errors about a type that does not implement one of
+//! these traits. The compiler also reports the definition of each type of the local crates, and
+//! Kani adds `#[cfg_attr(kani, derive(kani::<Trait>))]` to the definition of the type of the error.
+//! Deriving a trait may require the types of the fields to implement it too, so Kani compiles the
+//! package again until no derive is missing.
+
+use anyhow::{Result, bail};
+use cargo_metadata::diagnostic::Diagnostic;
+use cargo_metadata::{CompilerMessage, Target};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::InvocationType;
+use crate::args::VerificationArgs;
+use crate::args::fix_args::CargoFixArgs;
+use crate::session::KaniSession;
+use crate::util::{info_operation, warning};
+use crate::version::print_kani_version;
+
+/// The number of times Kani compiles the package to find missing derives.
+const MAX_ROUNDS: usize = 10;
+
+/// A type that must implement a Kani trait for the package to compile.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct MissingDerive {
+    /// The path of the type, without its generic arguments, e.g., `shapes::Shape`.
+    type_path: String,
+    /// Either `Arbitrary` or `Invariant`.
+    trait_name: String,
+}
+
+/// The definition of a type that the compiler reports with `--print-type-definitions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TypeDefinition {
+    /// Either `struct`, `enum` or `union`.
+    kind: String,
+    /// The path of the type in its crate, e.g., `shapes::Shape`.
+    path: String,
+    file: PathBuf,
+    /// The index of the line of the `struct`, `enum` or `union` keyword.
+    line: usize,
+    indent: String,
+    /// Whether a macro generates the definition, in which case `file` and `line` point inside the
+    /// macro.
+    from_macro: bool,
+}
+
+/// The definition of a type that must derive a trait.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct DeriveFix {
+    file: PathBuf,
+    /// The index of the line of the `struct` or `enum` keyword.
+    line: usize,
+    indent: String,
+    missing: MissingDerive,
+}
+
+impl DeriveFix {
+    fn attribute(&self) -> String {
+        format!("#[cfg_attr(kani, derive(kani::{}))]", self.missing.trait_name)
+    }
+}
+
+pub fn fix_cargo(args: CargoFixArgs, mut verify_opts: VerificationArgs) -> Result<()> {
+    verify_opts.common_args = args.common_args;
+    let mut session = KaniSession::new(verify_opts)?;
+    session.print_type_definitions = true;
+    if !session.args.common_args.quiet {
+        print_kani_version(InvocationType::CargoKani(vec![]));
+    }
+    let workspace_root = session.cargo_metadata(env!("TARGET"))?.workspace_root.into_std_path_buf();
+
+    let mut added = 0;
+    for _ in 0..MAX_ROUNDS {
+        let Err(err) = session.cargo_build(false) else {
+            if !session.args.common_args.quiet {
+                info_operation("Fixed", &format!("{added} missing derive(s)"));
+            }
+            return Ok(());
+        };
+        let errors = std::mem::take(&mut *session.compiler_errors.lock().unwrap());
+        let definitions = std::mem::take(&mut *session.type_definitions.lock().unwrap());
+        let fixes = find_fixes(&errors, &definitions, &workspace_root)?;
+        if fixes.is_empty() {
+            return Err(err.context(format!(
+                "Added {added} missing derive(s), but the remaining compilation errors cannot be \
+                fixed by `cargo kani fix`"
+            )));
+        }
+        for fix in &fixes {
+            let location = pathdiff::diff_paths(&fix.file, &workspace_root).unwrap();
+            let (operation, preposition) =
+                if args.check { ("Missing", "on") } else { ("Adding", "to") };
+            info_operation(
+                operation,
+                &format!(
+                    "`{}` {preposition} `{}` in {}:{}",
+                    fix.attribute(),
+                    fix.missing.type_path,
+                    location.display(),
+                    fix.line + 1
+                ),
+            );
+        }
+        if args.check {
+            bail!("Found {} missing derive(s). Run `cargo kani fix` to add them", fixes.len());
+        }
+        apply_fixes(&fixes)?;
+        added += fixes.len();
+    }
+    bail!("Failed to fix the compilation errors after adding {added} derive(s)")
+}
+
+/// Find where to add the derives that the compilation errors ask for, using the type definitions
+/// that the compiler reported.
+fn find_fixes(
+    errors: &[CompilerMessage],
+    definitions: &[CompilerMessage],
+    workspace_root: &Path,
+) -> Result<Vec<DeriveFix>> {
+    let definitions: Vec<_> = definitions
+        .iter()
+        .filter_map(|note| Some((&note.target, type_definition(&note.message, workspace_root)?)))
+        .collect();
+    let mut fixes = BTreeMap::new();
+    for error in errors {
+        let Some(missing) = missing_derive(&error.message.message) else {
+            continue;
+        };
+        // The types of the crate of the error have no crate name in their path, so prefer them
+        // over the types of the other local crates.
+        let definition = definitions
+            .iter()
+            .find(|(target, def)| {
+                target.src_path == error.target.src_path && def.path == missing.type_path
+            })
+            .or_else(|| {
+                definitions.iter().find(|(target, def)| {
+                    missing.type_path == format!("{}::{}", crate_name(target), def.path)
+                })
+            });
+        let attribute = format!("#[cfg_attr(kani, derive(kani::{}))]", missing.trait_name);
+        match definition {
+            None => warning(&format!(
+                "cannot find the definition of `{}` in the local crates to derive `kani::{}`",
+                missing.type_path, missing.trait_name
+            )),
+            Some((_, def)) if def.kind == "union" => warning(&format!(
+                "cannot derive `kani::{}` for the union `{}`. Implement it manually",
+                missing.trait_name, missing.type_path
+            )),
+            Some((_, def)) if def.from_macro => warning(&format!(
+                "`{}` is defined by a macro. Add `{attribute}` to its definition manually",
+                missing.type_path
+            )),
+            Some((_, def)) => {
+                let fix = DeriveFix {
+                    file: def.file.clone(),
+                    line: def.line,
+                    indent: def.indent.clone(),
+                    missing: missing.clone(),
+                };
+                if !has_derive(&fix)? {
+                    fixes.insert((fix.file.clone(), fix.line, missing), fix);
+                }
+            }
+        }
+    }
+    Ok(fixes.into_values().collect())
+}
+
+/// The name of the crate of the target, as it appears in the paths of its types.
+fn crate_name(target: &Target) -> String {
+    target.name.replace('-', "_")
+}
+
+/// Parse the message of a compilation error about a type that does not implement
+/// `kani::Arbitrary` or `kani::Invariant`.
+fn missing_derive(message: &str) -> Option<MissingDerive> {
+    static MISSING_TRAIT: OnceLock<Regex> = OnceLock::new();
+    static TYPE_PATH: OnceLock<Regex> = OnceLock::new();
+    let captures = MISSING_TRAIT
+        .get_or_init(|| {
+            Regex::new(
+                r"^the trait bound `(?<type>.+): (?:\w+::)*(?<trait>Arbitrary|Invariant)` is not satisfied$",
+            )
+            .unwrap()
+        })
+        .captures(message)?;
+    // Remove the generic arguments of the type, e.g., `shapes::Wrapper<u8>`.
+    let path = captures["type"].split('<').next().unwrap();
+    let path = path.strip_prefix("crate::").unwrap_or(path);
+    if !TYPE_PATH
+        .get_or_init(|| Regex::new(r"^[A-Za-z_]\w*(::[A-Za-z_]\w*)*$").unwrap())
+        .is_match(path)
+    {
+        return None;
+    }
+    Some(MissingDerive { type_path: path.to_string(), trait_name: captures["trait"].to_string() })
+}
+
+fn type_definition_regex() -> &'static Regex {
+    static TYPE_DEFINITION: OnceLock<Regex> = OnceLock::new();
+    TYPE_DEFINITION.get_or_init(|| {
+        Regex::new(r"^type definition of (?<kind>struct|enum|union) `(?<path>.+)`$").unwrap()
+    })
+}
+
+/// Whether the message is a note of the compiler at the definition of a type.
+pub fn is_type_definition(message: &str) -> bool {
+    type_definition_regex().is_match(message)
+}
+
+/// Parse a note of the compiler at the definition of a type, e.g.,
+/// "type definition of struct `shapes::Shape`".
+fn type_definition(note: &Diagnostic, workspace_root: &Path) -> Option<TypeDefinition> {
+    let captures = type_definition_regex().captures(&note.message)?;
+    let span = note.spans.iter().find(|span| span.is_primary)?;
+    let indent = span.text.first().map_or(String::new(), |line| {
+        line.text.chars().take_while(|c| c.is_whitespace()).collect()
+    });
+    Some(TypeDefinition {
+        kind: captures["kind"].to_string(),
+        path: captures["path"].to_string(),
+        file: workspace_root.join(&span.file_name),
+        line: span.line_start - 1,
+        indent,
+        from_macro: span.expansion.is_some(),
+    })
+}
+
+/// Whether one of the attributes of the definition already derives the trait.
+fn has_derive(fix: &DeriveFix) -> Result<bool> {
+    let content = fs::read_to_string(&fix.file)?;
+    let lines: Vec<_> = content.lines().collect();
+    let derive = Regex::new(&format!(r"derive\([^)]*\b{}\b", fix.missing.trait_name)).unwrap();
+    Ok(lines[..fix.line]
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .take_while(|line| line.starts_with('#') || line.starts_with("//"))
+        .any(|line| derive.is_match(line)))
+}
+
+/// Insert the attributes above the definitions.
+fn apply_fixes(fixes: &[DeriveFix]) -> Result<()> {
+    let mut files: BTreeMap<&Path, Vec<&DeriveFix>> = BTreeMap::new();
+    for fix in fixes {
+        files.entry(&fix.file).or_default().push(fix);
+    }
+    for (file, mut fixes) in files {
+        let content = fs::read_to_string(file)?;
+        let mut lines: Vec<String> = content.lines().map(String::from).collect();
+        // Insert from the bottom so the line indices of the remaining fixes stay valid.
+        fixes.sort_by_key(|fix| std::cmp::Reverse(fix.line));
+        for fix in fixes {
+            lines.insert(fix.line, format!("{}{}", fix.indent, fix.attribute()));
+        }
+        let mut new_content = lines.join("\n");
+        if content.ends_with('\n') {
+            new_content.push('\n');
+        }
+        fs::write(file, new_content)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missing(type_path: &str, trait_name: &str) -> MissingDerive {
+        MissingDerive { type_path: type_path.to_string(), trait_name: trait_name.to_string() }
+    }
+
+    /// A note of the compiler at the definition of a type in `file`.
+    fn note(message: &str, file: &str, line: usize, text: &str, from_macro: bool) -> Diagnostic {
+        let span = |file_name: &str| {
+            serde_json::json!({
+                "file_name": file_name, "byte_start": 0, "byte_end": 0, "line_start": line,
+                "line_end": line, "column_start": 1, "column_end": 1, "is_primary": true,
+                "text": [{ "text": text, "highlight_start": 1, "highlight_end": 1 }],
+                "label": null, "suggested_replacement": null, "suggestion_applicability": null,
+                "expansion": null,
+            })
+        };
+        let mut primary = span(file);
+        if from_macro {
+            primary["expansion"] = serde_json::json!({ "span": span("src/lib.rs"), "macro_decl_name": "mk!", "def_site_span": null });
+        }
+        serde_json::from_value(serde_json::json!({
+            "message": message, "code": null, "level": "note", "spans": [primary],
+            "children": [], "rendered": null,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn check_missing_derive() {
+        assert_eq!(
+            missing_derive("the trait bound `Foo: Arbitrary` is not satisfied"),
+            Some(missing("Foo", "Arbitrary"))
+        );
+        assert_eq!(
+            missing_derive("the trait bound `net::Packet<u8>: kani::Invariant` is not satisfied"),
+            Some(missing("net::Packet", "Invariant"))
+        );
+        assert_eq!(
+            missing_derive("the trait bound `crate::Foo: kani::Arbitrary` is not satisfied"),
+            Some(missing("Foo", "Arbitrary"))
+        );
+        assert_eq!(missing_derive("the trait bound `Foo: Clone` is not satisfied"), None);
+        assert_eq!(missing_derive("the trait bound `[Foo; 2]: Arbitrary` is not satisfied"), None);
+    }
+
+    #[test]
+    fn check_type_definition() {
+        let root = Path::new("/ws");
+        let shape = note(
+            "type definition of struct `shapes::Shape`",
+            "src/shapes.rs",
+            11,
+            "    pub struct Shape<T> {",
+            false,
+        );
+        assert!(is_type_definition(&shape.message));
+        assert_eq!(
+            type_definition(&shape, root),
+            Some(TypeDefinition {
+                kind: "struct".to_string(),
+                path: "shapes::Shape".to_string(),
+                file: PathBuf::from("/ws/src/shapes.rs"),
+                line: 10,
+                indent: "    ".to_string(),
+                from_macro: false,
+            })
+        );
+
+        let made = note("type definition of union `Made`", "src/lib.rs", 3, "union $n {", true);
+        let made = type_definition(&made, root).unwrap();
+        assert_eq!((made.kind.as_str(), made.from_macro), ("union", true));
+
+        let other = note("unused variable `x`", "src/lib.rs", 3, "let x = 1;", false);
+        assert!(!is_type_definition(&other.message));
+        assert_eq!(type_definition(&other, root), None);
+    }
+
+    #[test]
+    fn check_add_derives() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        let source = "#[derive(Clone, kani::Arbitrary)]\npub struct Point { x: u8 }\n\nmod shapes {\n    /// A color.\n    pub(crate) enum Color { Red, Green }\n}\n";
+        fs::write(&file, source).unwrap();
+        let fix = |line, indent: &str, type_path, trait_name| DeriveFix {
+            file: file.clone(),
+            line,
+            indent: indent.to_string(),
+            missing: missing(type_path, trait_name),
+        };
+
+        assert!(has_derive(&fix(1, "", "Point", "Arbitrary")).unwrap());
+        let color = fix(5, "    ", "shapes::Color", "Arbitrary");
+        let invariant = fix(1, "", "Point", "Invariant");
+        assert!(!has_derive(&color).unwrap());
+        assert!(!has_derive(&invariant).unwrap());
+
+        apply_fixes(&[color, invariant]).unwrap();
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "#[derive(Clone, kani::Arbitrary)]\n#[cfg_attr(kani, derive(kani::Invariant))]\npub struct Point { x: u8 }\n\nmod shapes {\n    /// A color.\n    #[cfg_attr(kani, derive(kani::Arbitrary))]\n    pub(crate) enum Color { Red, Green }\n}\n"
+        );
+    }
+}
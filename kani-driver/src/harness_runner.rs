@@ -2,16 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::{Error, Result, bail};
-use kani_metadata::{ArtifactType, HarnessKind, HarnessMetadata};
+use kani_metadata::{ArtifactType, ForeignFunctionEncoding, HarnessKind, HarnessMetadata};
 use rayon::prelude::*;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use crate::args::{NumThreads, OutputFormat};
 use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::cover_summary::{print_cover_summary, write_cover_summary};
+use crate::progress::{HarnessState, ProgressView, REFRESH_INTERVAL};
 use crate::project::Project;
 use crate::session::{BUG_REPORT_URL, KaniSession};
+use crate::statistics::{print_statistics_table, write_statistics};
 
 use std::env::current_dir;
 use std::path::PathBuf;
@@ -56,8 +62,13 @@ pub(crate) fn check_all_harnesses(
         harnesses: &'pr [&HarnessMetadata],
     ) -> Result<Vec<HarnessResult<'pr>>> {
         self.check_stubbing(harnesses)?;
+        self.check_split_harnesses(harnesses)?;
 
-        let sorted_harnesses = crate::metadata::sort_harnesses_by_loc(harnesses);
+        let sorted_harnesses = if self.sess.autoharness_budget.is_some() {
+            crate::metadata::sort_harnesses_by_mir_size(harnesses)
+        } else {
+            crate::metadata::sort_harnesses_by_loc(harnesses)
+        };
         let pool = {
             let mut builder = rayon::ThreadPoolBuilder::new();
             match self.sess.args.jobs() {
@@ -72,33 +83,102 @@ pub(crate) fn check_all_harnesses(
             }
             builder.build()?
         };
-
-        let results = pool.install(|| -> Result<Vec<HarnessResult<'pr>>> {
-            sorted_harnesses
-                .par_iter()
-                .enumerate()
-                .map(|(idx, harness)| -> Result<HarnessResult<'pr>> {
+        let progress = self
+            .sess
+            .should_draw_progress()
+            .then(|| ProgressView::new(&sorted_harnesses))
+            .flatten()
+            .map(Arc::new);
+        *self.sess.progress.write().unwrap() = progress.clone();
+
+        let check_harness =
+            |idx: usize, harness: &'pr HarnessMetadata| -> Result<HarnessResult<'pr>> {
+                if let Some(progress) = &progress {
+                    progress.set_state(harness, HarnessState::Compiling);
+                }
+                // The compiler only skips the harnesses that must be verified in a
+                // separate process.
+                let result = if harness.goto_file.is_none() {
+                    self.sess.check_isolated_harness(harness)?
+                } else {
                     let goto_file =
                         self.project.get_harness_artifact(harness, ArtifactType::Goto).unwrap();
 
-                    self.sess.instrument_model(goto_file, goto_file, self.project, harness)?;
+                    if harness.split_cases.is_empty() {
+                        self.sess.instrument_model(goto_file, goto_file, self.project, harness)?;
 
-                    if self.sess.args.synthesize_loop_contracts {
-                        self.sess.synthesize_loop_contracts(goto_file, goto_file, harness)?;
-                    }
+                        if self.sess.args.synthesize_loop_contracts {
+                            self.sess.synthesize_loop_contracts(goto_file, goto_file, harness)?;
+                        }
 
-                    let result = self.sess.check_harness(goto_file, harness)?;
-                    if self.sess.args.fail_fast && result.status == VerificationStatus::Failure {
-                        Err(Error::new(FailFastHarnessInfo {
-                            index_to_failing_harness: idx,
-                            result,
-                        }))
+                        self.sess.emit_harness_artifacts(self.project, harness, goto_file)?;
+
+                        self.sess.check_harness(goto_file, harness)?
                     } else {
-                        Ok(HarnessResult { harness, result })
+                        // Each case instruments its own copy of the model.
+                        self.sess.check_split_harness(self.project, harness, goto_file)?
+                    }
+                };
+                if let Some(progress) = &progress {
+                    progress.finish(harness, &result);
+                }
+                if self.sess.args.fail_fast && result.status == VerificationStatus::Failure {
+                    Err(Error::new(FailFastHarnessInfo { index_to_failing_harness: idx, result }))
+                } else {
+                    Ok(HarnessResult { harness, result })
+                }
+            };
+
+        self.sess.budget_start.get_or_init(Instant::now);
+        let verify = || -> Result<Vec<HarnessResult<'pr>>> {
+            if self.sess.autoharness_budget.is_none() {
+                return sorted_harnesses
+                    .par_iter()
+                    .enumerate()
+                    .map(|(idx, harness)| check_harness(idx, harness))
+                    .collect();
+            }
+            // Bridging the sequential iterator makes the threads start the harnesses in order,
+            // so the smallest functions are verified before the budget runs out.
+            let mut results = sorted_harnesses
+                .iter()
+                .enumerate()
+                .par_bridge()
+                .filter_map(|(idx, harness)| {
+                    if self.sess.remaining_budget() == Some(Duration::ZERO) {
+                        let function = (harness.crate_name.clone(), harness.pretty_name.clone());
+                        self.sess.skipped_by_budget.lock().unwrap().push(function);
+                        if let Some(progress) = &progress {
+                            progress.set_state(harness, HarnessState::Skipped);
+                        }
+                        return None;
                     }
+                    Some(check_harness(idx, harness).map(|result| (idx, result)))
                 })
-                .collect::<Result<Vec<_>>>()
-        });
+                .collect::<Result<Vec<_>>>()?;
+            results.sort_unstable_by_key(|(idx, _)| *idx);
+            Ok(results.into_iter().map(|(_, result)| result).collect())
+        };
+        let results = if let Some(progress) = &progress {
+            // Redraw the table periodically so the elapsed times are updated.
+            let finished = AtomicBool::new(false);
+            let results = std::thread::scope(|scope| {
+                scope.spawn(|| {
+                    while !finished.load(Ordering::Relaxed) {
+                        progress.refresh();
+                        std::thread::sleep(REFRESH_INTERVAL);
+                    }
+                });
+                let results = pool.install(verify);
+                finished.store(true, Ordering::Relaxed);
+                results
+            });
+            *self.sess.progress.write().unwrap() = None;
+            progress.refresh();
+            results
+        } else {
+            pool.install(verify)
+        };
         match results {
             Ok(results) => Ok(results),
             Err(err) => {
@@ -122,7 +202,7 @@ fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
             let with_stubs: Vec<_> = harnesses
                 .iter()
                 .filter_map(|harness| {
-                    (!harness.attributes.stubs.is_empty()).then_some(harness.pretty_name.as_str())
+                    harness.attributes.has_stubs().then_some(harness.pretty_name.as_str())
                 })
                 .collect();
             match with_stubs.as_slice() {
@@ -141,13 +221,38 @@ fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
         }
         Ok(())
     }
+
+    /// Return an error if a harness that is verified one case at a time is combined with an
+    /// option that works on the model of the whole harness.
+    fn check_split_harnesses(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
+        let Some(harness) = harnesses.iter().find(|harness| !harness.split_cases.is_empty()) else {
+            return Ok(());
+        };
+        let args = &self.sess.args;
+        let option = if args.synthesize_loop_contracts {
+            "--synthesize-loop-contracts"
+        } else if !args.emit.is_empty() {
+            "--emit"
+        } else if args.compare_backend.is_some() {
+            "--compare-backend"
+        } else {
+            return Ok(());
+        };
+        bail!(
+            "`{option}` cannot be used with harness `{}`, which is verified one case at a time \
+            because of its `split_on` attribute",
+            harness.pretty_name
+        )
+    }
 }
 
 impl KaniSession {
-    fn process_output(
+    /// Print the result of `harness`, followed by `summary` if there is one.
+    pub(crate) fn process_output(
         &self,
         result: &VerificationResult,
         harness: &HarnessMetadata,
+        summary: Option<String>,
         thread_index: usize,
     ) {
         if self.should_print_output() {
@@ -155,8 +260,18 @@ fn process_output(
                 self.write_output_to_file(result, harness, thread_index);
             }
 
-            let output = result.render(&self.args.output_format, harness.attributes.should_panic);
-            if rayon::current_num_threads() > 1 {
+            let mut output =
+                result.render(&self.args.output_format, harness.attributes.should_panic);
+            if let Some(summary) = summary {
+                output = format!("{output}\n{summary}");
+            }
+            if let Some(summary) = unsupported_features_summary(harness) {
+                output = format!("{output}\n{summary}");
+            }
+            if let Some(progress) = self.progress() {
+                // The table shows which harnesses are running, so the thread index is omitted.
+                progress.println(&output);
+            } else if rayon::current_num_threads() > 1 {
                 println!("Thread {thread_index}: {output}");
             } else {
                 println!("{output}");
@@ -168,6 +283,12 @@ fn should_print_output(&self) -> bool {
         !self.args.common_args.quiet && self.args.output_format != OutputFormat::Old
     }
 
+    /// Whether to draw the table of `--ui`. With the old output format, CBMC prints to the
+    /// terminal directly, which would garble the table.
+    fn should_draw_progress(&self) -> bool {
+        self.args.ui && self.should_print_output()
+    }
+
     fn write_output_to_file(
         &self,
         result: &VerificationResult,
@@ -208,7 +329,21 @@ pub(crate) fn check_harness(
         harness: &HarnessMetadata,
     ) -> Result<VerificationResult> {
         let thread_index = rayon::current_thread_index().unwrap_or_default();
-        if !self.args.common_args.quiet {
+        self.announce_harness(harness, thread_index);
+
+        let mut result = self.with_timer(|| self.run_cbmc(binary, harness), "run_cbmc")?;
+
+        self.process_output(&result, harness, None, thread_index);
+        self.compare_backends(binary, harness, &result)?;
+        self.gen_and_add_concrete_playback(harness, &mut result)?;
+        Ok(result)
+    }
+
+    /// Report that the solver starts verifying `harness`.
+    pub(crate) fn announce_harness(&self, harness: &HarnessMetadata, thread_index: usize) {
+        if let Some(progress) = self.progress() {
+            progress.set_state(harness, HarnessState::Solving);
+        } else if !self.args.common_args.quiet {
             // If the harness is automatically generated, pretty_name refers to the function under verification.
             let mut msg = if harness.is_automatically_generated {
                 if matches!(harness.attributes.kind, HarnessKind::Proof) {
@@ -232,12 +367,6 @@ pub(crate) fn check_harness(
 
             println!("{msg}");
         }
-
-        let mut result = self.with_timer(|| self.run_cbmc(binary, harness), "run_cbmc")?;
-
-        self.process_output(&result, harness, thread_index);
-        self.gen_and_add_concrete_playback(harness, &mut result)?;
-        Ok(result)
     }
 
     /// Concludes a session by printing a summary report and exiting the process with an
@@ -246,6 +375,13 @@ pub(crate) fn check_harness(
     /// Note: Takes `self` "by ownership". This function wants to be able to drop before
     /// exiting with an error code, if needed.
     pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result<()> {
+        if let Some(path) = &self.args.statistics {
+            write_statistics(&self.args, path, results)?;
+        }
+        if let Some(path) = &self.args.cover_summary {
+            write_cover_summary(path, results)?;
+        }
+
         if self.args.common_args.quiet {
             return Ok(());
         }
@@ -255,10 +391,12 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
 
         let (successes, failures): (Vec<_>, Vec<_>) =
             manual.into_iter().partition(|r| r.result.status == VerificationStatus::Success);
+        let (vacuous, failures): (Vec<_>, Vec<_>) =
+            failures.into_iter().partition(|r| r.result.status == VerificationStatus::Vacuous);
 
         let succeeding = successes.len();
         let failing = failures.len();
-        let total = succeeding + failing;
+        let total = succeeding + vacuous.len() + failing;
 
         if self.args.concrete_playback.is_some() {
             if failures.is_empty() {
@@ -277,11 +415,23 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
         for failure in failures.iter() {
             println!("Verification failed for - {}", failure.harness.pretty_name);
         }
+        for result in vacuous.iter() {
+            println!("Verification was vacuous for - {}", result.harness.pretty_name);
+        }
 
-        if total > 0 {
+        if total > 0 && !vacuous.is_empty() {
+            println!(
+                "Complete - {succeeding} successfully verified harnesses, {} vacuous harnesses, {failing} failures, {total} total.",
+                vacuous.len()
+            );
+        } else if total > 0 {
             println!(
                 "Complete - {succeeding} successfully verified harnesses, {failing} failures, {total} total."
             );
+        } else if let Some(range) = &self.args.affected_by {
+            println!("No proof harnesses reach the code changed in `{range}`.");
+        } else if let Some(shard) = self.args.shard {
+            println!("No proof harnesses were assigned to shard {}/{}.", shard.index, shard.count);
         } else {
             match self.args.harnesses.as_slice() {
                 [] =>
@@ -301,6 +451,13 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
             };
         }
 
+        print_foreign_functions_summary(results);
+        print_cover_summary(results);
+
+        if self.args.statistics.is_some() && !results.is_empty() {
+            print_statistics_table(&self.args, results);
+        }
+
         if self.args.coverage {
             self.show_coverage_summary()?;
         }
@@ -311,7 +468,9 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
             0
         };
 
-        if failing + autoharness_failing > 0 {
+        let differing = self.print_backend_comparison_summary(results.len());
+
+        if failing + vacuous.len() + autoharness_failing + differing > 0 {
             // Failure exit code without additional error message
             drop(self);
             std::process::exit(1);
@@ -327,3 +486,52 @@ fn show_coverage_summary(&self) -> Result<()> {
         Ok(())
     }
 }
+
+/// Summarize the features that Kani does not support and that are reachable from `harness`, or
+/// `None` if there are none. Each feature lists its locations and the issue that tracks it.
+pub(crate) fn unsupported_features_summary(harness: &HarnessMetadata) -> Option<String> {
+    if harness.unsupported_features.is_empty() {
+        return None;
+    }
+    let mut summary = format!("Unsupported features in harness `{}`:\n", harness.pretty_name);
+    for feature in &harness.unsupported_features {
+        summary += &format!(" - {}", feature.feature);
+        if let Some(issue) = &feature.tracking_issue {
+            summary += &format!(" (see {issue})");
+        }
+        summary += "\n";
+        let mut locations: Vec<_> = feature.locations.iter().collect();
+        locations.sort_by(|a, b| (&a.filename, a.start_line).cmp(&(&b.filename, b.start_line)));
+        for location in locations {
+            summary += &format!("   at {}:{}\n", location.filename, location.start_line);
+        }
+    }
+    summary += "Verification fails if any of these features is reached.";
+    Some(summary)
+}
+
+/// Print the foreign functions that Kani does not model for each harness that reaches them,
+/// so that users can judge how they affect the verification results.
+fn print_foreign_functions_summary(results: &[HarnessResult<'_>]) {
+    let mut harnesses =
+        results.iter().map(|r| r.harness).filter(|harness| !harness.foreign_functions.is_empty());
+    let Some(first) = harnesses.next() else {
+        return;
+    };
+    println!("\nForeign functions without a model:");
+    for harness in std::iter::once(first).chain(harnesses) {
+        println!("{}:", harness.pretty_name);
+        for function in &harness.foreign_functions {
+            let encoding = match function.encoding {
+                ForeignFunctionEncoding::Unsupported => "unsupported, calls fail verification",
+                ForeignFunctionEncoding::Declared => {
+                    "declared, calls use the linked C definition if there is one"
+                }
+            };
+            println!(
+                " - {} ({}): {} call site(s), {encoding}",
+                function.name, function.signature, function.call_sites
+            );
+        }
+    }
+}
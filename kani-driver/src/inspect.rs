@@ -0,0 +1,96 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the `inspect` subcommand, which prints what Kani actually verifies for a harness: the
+//! MIR after Kani's transformations, such as stubbing and instrumentation, and the goto program
+//! that is handed to CBMC.
+//!
+//! The subcommand builds the project with `--emit goto,mir`, so the artifacts that it prints are
+//! also kept in the emit directory.
+
+use anyhow::{Context, Result, bail};
+use std::process::Command;
+
+use crate::args::EmitKind;
+use crate::args::VerificationArgs;
+use crate::args::inspect_args::{CargoInspectArgs, CommonInspectArgs, StandaloneInspectArgs};
+use crate::project::{self, Project};
+use crate::session::KaniSession;
+use crate::{InvocationType, print_kani_version};
+use kani_metadata::{ArtifactType, HarnessMetadata};
+
+pub fn inspect_cargo(args: CargoInspectArgs) -> Result<()> {
+    let mut session = KaniSession::new(inspect_opts(args.verify_opts, &args.common_inspect_args))?;
+    if !session.args.common_args.quiet {
+        print_kani_version(InvocationType::CargoKani(vec![]));
+    }
+    let project = project::cargo_project(&mut session, false)?;
+    inspect(project, session, args.common_inspect_args)
+}
+
+pub fn inspect_standalone(args: StandaloneInspectArgs) -> Result<()> {
+    let session = KaniSession::new(inspect_opts(args.verify_opts, &args.common_inspect_args))?;
+    if !session.args.common_args.quiet {
+        print_kani_version(InvocationType::Standalone);
+    }
+    let project = project::standalone_project(&args.input, args.crate_name, &session)?;
+    inspect(project, session, args.common_inspect_args)
+}
+
+/// Only build the harness that we inspect, and emit both its goto program and its MIR.
+fn inspect_opts(mut verify_opts: VerificationArgs, args: &CommonInspectArgs) -> VerificationArgs {
+    verify_opts.harnesses = vec![args.harness.clone()];
+    verify_opts.emit = vec![EmitKind::Goto, EmitKind::Mir];
+    verify_opts
+}
+
+/// Find the only harness of the project that matches `name`, which the session uses as its
+/// `--harness` filter.
+pub(crate) fn single_harness<'a>(
+    session: &KaniSession,
+    project: &'a Project,
+    name: &str,
+) -> Result<&'a HarnessMetadata> {
+    let harnesses = session.determine_targets(project.get_all_harnesses())?;
+    match harnesses.as_slice() {
+        [harness] => Ok(*harness),
+        [] => bail!("No harness matches `{name}`"),
+        _ => {
+            let names: Vec<_> = harnesses.iter().map(|h| h.pretty_name.as_str()).collect();
+            bail!(
+                "`{name}` matches more than one harness: `{}`. Use the full name of the harness \
+                to select one of them.",
+                names.join("`, `")
+            )
+        }
+    }
+}
+
+fn inspect(project: Project, session: KaniSession, args: CommonInspectArgs) -> Result<()> {
+    let harness = single_harness(&session, &project, &args.harness)?;
+    let Some(goto_file) = project.get_harness_artifact(harness, ArtifactType::Goto) else {
+        bail!("Failed to find the goto program of harness `{}`", harness.pretty_name);
+    };
+    session.instrument_model(goto_file, goto_file, &project, harness)?;
+    let emitted = session.emit_harness_artifacts(&project, harness, goto_file)?;
+
+    if let Some(mir) = &emitted.mir {
+        println!("// MIR of harness `{}` ({})", harness.pretty_name, mir.display());
+        let content = std::fs::read_to_string(mir)
+            .with_context(|| format!("Failed to read `{}`", mir.display()))?;
+        println!("{content}");
+    }
+
+    let goto = emitted.goto.as_ref().unwrap();
+    println!("// Goto program of harness `{}` ({})", harness.pretty_name, goto.display());
+    let mut cmd = Command::new("goto-instrument");
+    cmd.arg("--show-goto-functions").arg(goto);
+    session.run_terminal(cmd)?;
+
+    if args.symbol_table {
+        println!("// Symbol table of harness `{}`", harness.pretty_name);
+        let mut cmd = Command::new("goto-instrument");
+        cmd.arg("--show-symbol-table").arg(goto);
+        session.run_terminal(cmd)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Verify harnesses annotated with `#[kani::proof(isolate = "process")]` in a separate process.
+//!
+//! The compiler skips these harnesses when it compiles the crate for the other harnesses. For each
+//! of them, Kani runs the driver again with `--isolated-harness <NAME>`, which compiles and
+//! verifies only that harness. If the child process runs out of memory or crashes, only this
+//! harness fails, and Kani carries on with the remaining ones.
+
+use anyhow::{Context, Result};
+use kani_metadata::HarnessMetadata;
+use std::ffi::OsString;
+use std::process::Command;
+use std::time::Instant;
+
+use crate::call_cbmc::{ExitStatus, FailedProperties, VerificationResult, VerificationStatus};
+use crate::session::KaniSession;
+use crate::statistics::CbmcStatistics;
+use crate::util::render_command;
+
+/// The exit status of a process that was killed by `SIGKILL`, which is usually how the system
+/// stops a process that runs out of memory.
+const KILLED_EXIT_STATUS: i32 = 137;
+
+/// The command line that runs Kani again on the same project.
+#[derive(Debug, Clone)]
+pub struct IsolationCommand {
+    /// Whether Kani was invoked as `cargo kani`.
+    cargo: bool,
+    /// The arguments given by the user, without the executable name.
+    args: Vec<OsString>,
+}
+
+impl IsolationCommand {
+    /// Re-run `cargo kani` with the given arguments.
+    pub fn cargo(args: Vec<OsString>) -> Self {
+        IsolationCommand { cargo: true, args }
+    }
+
+    /// Re-run `kani` with the given arguments.
+    pub fn standalone(args: Vec<OsString>) -> Self {
+        IsolationCommand { cargo: false, args }
+    }
+
+    /// Build the command that verifies `harness` in a process whose virtual memory is limited to
+    /// `memory_limit` MiB, if any.
+    fn command(&self, harness: &str, memory_limit: Option<u64>) -> Result<Command> {
+        let driver = std::env::current_exe().context("Cannot determine current executable")?;
+        let mut cmd = if let Some(limit) = memory_limit {
+            let mut cmd = Command::new("sh");
+            cmd.args(["-c", r#"ulimit -v "$0" && exec "$@""#])
+                .arg((limit * 1024).to_string())
+                .arg(driver);
+            cmd
+        } else {
+            Command::new(driver)
+        };
+        if self.cargo {
+            cmd.arg("kani");
+        }
+        // The harness must come first, since `--cbmc-args` consumes all the arguments after it.
+        cmd.arg("--isolated-harness").arg(harness).args(&self.args);
+        Ok(cmd)
+    }
+}
+
+impl KaniSession {
+    /// Compile and verify `harness` in a separate process, which prints its own results.
+    ///
+    /// Any failure of the process, including a crash, is reported as a failure of the harness.
+    pub(crate) fn check_isolated_harness(
+        &self,
+        harness: &HarnessMetadata,
+    ) -> Result<VerificationResult> {
+        let command = self.isolation_command.as_ref().unwrap();
+        let mut cmd = command.command(&harness.pretty_name, self.args.isolation_memory_limit)?;
+        let progress = self.progress();
+        if progress.is_none() && !self.args.common_args.quiet {
+            println!("Checking harness {} in a separate process...", harness.pretty_name);
+        }
+        if self.args.common_args.verbose {
+            println!("[Kani] Running: `{}`", render_command(&cmd).to_string_lossy());
+        }
+
+        let start_time = Instant::now();
+        let status = if let Some(progress) = progress {
+            // Print the output of the process above the table of `--ui`, rather than letting it
+            // write to the terminal.
+            let output =
+                cmd.output().context("Failed to start the isolated verification process")?;
+            let text = [output.stdout, output.stderr]
+                .iter()
+                .map(|bytes| String::from_utf8_lossy(bytes).trim_end().to_string())
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+            if !text.is_empty() {
+                progress.println(&text);
+            }
+            output.status
+        } else {
+            cmd.status().context("Failed to start the isolated verification process")?
+        };
+        let runtime = start_time.elapsed();
+
+        if status.success() {
+            return Ok(VerificationResult {
+                status: VerificationStatus::Success,
+                failed_properties: FailedProperties::None,
+                results: Ok(vec![]),
+                runtime,
+                generated_concrete_test: false,
+                coverage_results: None,
+                statistics: CbmcStatistics::default(),
+            });
+        }
+
+        // A status of 1 means that the harness failed verification or compilation, and the
+        // process already reported why.
+        if status.code() != Some(1) {
+            eprintln!(
+                "The process that verifies harness `{}` crashed ({status})",
+                harness.pretty_name
+            );
+        }
+        // Processes aren't terminated by signals on Windows.
+        #[cfg(unix)]
+        let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+        #[cfg(not(unix))]
+        let signal: Option<i32> = None;
+        let exit_status = match status.code().or(signal.map(|signal| 128 + signal)) {
+            Some(KILLED_EXIT_STATUS) => ExitStatus::OutOfMemory,
+            Some(code) => ExitStatus::Other(code),
+            None => ExitStatus::Other(-1),
+        };
+        Ok(VerificationResult {
+            status: VerificationStatus::Failure,
+            failed_properties: FailedProperties::Other,
+            results: Err(exit_status),
+            runtime,
+            generated_concrete_test: false,
+            coverage_results: None,
+            statistics: CbmcStatistics::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(cmd: &Command) -> Vec<&str> {
+        cmd.get_args().map(|arg| arg.to_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn check_cargo_command() {
+        let command = IsolationCommand::cargo(vec!["--tests".into(), "--cbmc-args".into()]);
+        let cmd = command.command("mod::check", None).unwrap();
+        assert_eq!(
+            args(&cmd),
+            ["kani", "--isolated-harness", "mod::check", "--tests", "--cbmc-args"]
+        );
+    }
+
+    #[test]
+    fn check_memory_limit() {
+        let command = IsolationCommand::standalone(vec!["lib.rs".into()]);
+        let cmd = command.command("check", Some(512)).unwrap();
+        assert_eq!(cmd.get_program(), "sh");
+        let args = args(&cmd);
+        assert_eq!(args[2], "524288");
+        assert_eq!(args[4..], ["--isolated-harness", "check", "lib.rs"]);
+    }
+}
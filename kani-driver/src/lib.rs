@@ -0,0 +1,381 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! The Kani driver, which builds a project with Kani and runs all of its proof harnesses.
+//!
+//! The `kani-driver` binary is a thin wrapper around [driver_main]. Tools that want to embed
+//! Kani without shelling out should use the [api] module instead.
+use std::ffi::OsString;
+use std::process::ExitCode;
+
+use anyhow::Result;
+use autoharness::{autoharness_cargo, autoharness_standalone};
+use baseline::{baseline_cargo, baseline_standalone};
+use time::{OffsetDateTime, format_description};
+
+use args::{CargoKaniSubcommand, check_is_valid};
+use args_toml::join_args;
+
+use crate::args::StandaloneSubcommand;
+use crate::args::autoharness_args::{CargoAutoharnessArgs, StandaloneAutoharnessArgs};
+use crate::call_cbmc::VerificationStatus;
+use crate::clean::clean_cargo;
+use crate::concrete_playback::playback::{playback_cargo, playback_standalone};
+use crate::doc_proofs::doc_proofs_cargo;
+use crate::export_fuzz::{export_fuzz_cargo, export_fuzz_standalone};
+use crate::fix::fix_cargo;
+use crate::inspect::{inspect_cargo, inspect_standalone};
+use crate::isolation::IsolationCommand;
+use crate::list::collect_metadata::{list_cargo, list_standalone};
+use crate::project::Project;
+use crate::server::{server_cargo, server_standalone};
+use crate::session::KaniSession;
+use crate::version::print_kani_version;
+use clap::Parser;
+use kani_metadata::HarnessMetadata;
+use tracing::debug;
+
+mod affected;
+pub mod api;
+mod args;
+mod args_toml;
+mod autoharness;
+mod baseline;
+mod call_cargo;
+mod call_cbmc;
+mod call_goto_cc;
+mod call_goto_instrument;
+mod call_goto_synthesizer;
+mod call_single_file;
+mod call_smt_solver;
+mod cbmc_output_parser;
+mod cbmc_property_renderer;
+mod clean;
+mod compare_backend;
+mod concrete_playback;
+mod contract_manifest;
+mod cover_summary;
+mod coverage;
+mod doc_proofs;
+mod emit;
+mod export_fuzz;
+mod fix;
+mod harness_runner;
+mod inspect;
+mod isolation;
+mod list;
+mod merge_results;
+mod metadata;
+mod pinned_tools;
+mod progress;
+mod project;
+mod report;
+mod server;
+mod session;
+mod shard;
+mod split;
+mod statistics;
+mod util;
+mod version;
+#[cfg(windows)]
+mod windows;
+
+/// The main function for the `kani-driver`.
+/// The driver can be invoked via `cargo kani` and `kani` commands, which determines what kind of
+/// project should be verified.
+pub fn driver_main() -> ExitCode {
+    let invocation_type = determine_invocation_type(Vec::from_iter(std::env::args_os()));
+
+    let cargo_kani_args = match &invocation_type {
+        InvocationType::CargoKani(args) => Some(args.as_slice()),
+        InvocationType::Standalone => None,
+    };
+    let result =
+        pinned_tools::use_pinned_tools(cargo_kani_args).and_then(|()| match invocation_type {
+            InvocationType::CargoKani(args) => cargokani_main(args),
+            InvocationType::Standalone => standalone_main(),
+        });
+
+    if let Err(error) = result {
+        // We are using the debug format for now to print the all the context.
+        // We should consider creating a standard for error reporting.
+        debug!(?error, "main_failure");
+        util::error(&format!("{error:#}"));
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// The main function for the `cargo kani` command.
+fn cargokani_main(input_args: Vec<OsString>) -> Result<()> {
+    let isolation_command = IsolationCommand::cargo(input_args.iter().skip(1).cloned().collect());
+    let input_args = join_args(input_args)?;
+    let args = args::CargoKaniArgs::parse_from(&input_args);
+    check_is_valid(&args);
+
+    let mut session = match args.command {
+        Some(CargoKaniSubcommand::Autoharness(autoharness_args)) => {
+            return autoharness_cargo(*autoharness_args);
+        }
+        Some(CargoKaniSubcommand::Baseline(baseline_args)) => {
+            return baseline_cargo(*baseline_args);
+        }
+        Some(CargoKaniSubcommand::Clean(clean_args)) => {
+            return clean_cargo(*clean_args, args.verify_opts);
+        }
+        Some(CargoKaniSubcommand::DocProofs(doc_proofs_args)) => {
+            return doc_proofs_cargo(*doc_proofs_args);
+        }
+        Some(CargoKaniSubcommand::ExportFuzz(export_fuzz_args)) => {
+            return export_fuzz_cargo(*export_fuzz_args);
+        }
+        Some(CargoKaniSubcommand::Fix(fix_args)) => {
+            return fix_cargo(*fix_args, args.verify_opts);
+        }
+        Some(CargoKaniSubcommand::Inspect(inspect_args)) => {
+            return inspect_cargo(*inspect_args);
+        }
+        Some(CargoKaniSubcommand::List(list_args)) => {
+            return list_cargo(*list_args, args.verify_opts);
+        }
+        Some(CargoKaniSubcommand::MergeResults(args)) => {
+            return merge_results::merge_results(*args);
+        }
+        Some(CargoKaniSubcommand::Playback(args)) => {
+            return playback_cargo(*args);
+        }
+        Some(CargoKaniSubcommand::Report(args)) => return report::report(*args),
+        Some(CargoKaniSubcommand::Server(args)) => return server_cargo(*args),
+        // `--prove-no-panics` verifies automatic harnesses instead of the crate's harnesses.
+        None if args.verify_opts.prove_no_panics => {
+            return autoharness_cargo(CargoAutoharnessArgs {
+                common_autoharness_args: Default::default(),
+                verify_opts: args.verify_opts,
+            });
+        }
+        None => session::KaniSession::new(args.verify_opts)?,
+    };
+    session.enable_isolation(isolation_command);
+
+    if !session.args.common_args.quiet && session.args.isolated_harness.is_none() {
+        print_kani_version(InvocationType::CargoKani(input_args));
+    }
+
+    let project = project::cargo_project(&mut session, false)?;
+    if session.args.only_codegen {
+        if !session.args.common_args.quiet {
+            print_unsupported_features(&project);
+        }
+        Ok(())
+    } else {
+        verify_project(project, session)
+    }
+}
+
+/// The main function for the `kani` command.
+fn standalone_main() -> Result<()> {
+    let args = args::StandaloneArgs::parse();
+    check_is_valid(&args);
+
+    let (session, project) = match args.command {
+        Some(StandaloneSubcommand::Autoharness(args)) => {
+            return autoharness_standalone(*args);
+        }
+        Some(StandaloneSubcommand::Baseline(args)) => return baseline_standalone(*args),
+        Some(StandaloneSubcommand::ExportFuzz(args)) => return export_fuzz_standalone(*args),
+        Some(StandaloneSubcommand::Inspect(args)) => return inspect_standalone(*args),
+        Some(StandaloneSubcommand::Playback(args)) => return playback_standalone(*args),
+        Some(StandaloneSubcommand::Report(args)) => return report::report(*args),
+        Some(StandaloneSubcommand::Server(args)) => return server_standalone(*args),
+        Some(StandaloneSubcommand::List(list_args)) => {
+            return list_standalone(*list_args, args.verify_opts);
+        }
+        Some(StandaloneSubcommand::MergeResults(args)) => {
+            return merge_results::merge_results(*args);
+        }
+        Some(StandaloneSubcommand::VerifyStd(args)) => {
+            let session = KaniSession::new(args.verify_opts)?;
+            if !session.args.common_args.quiet {
+                print_kani_version(InvocationType::Standalone);
+            }
+
+            let project = project::std_project(&args.std_path, &session)?;
+            (session, project)
+        }
+        None if args.verify_opts.prove_no_panics => {
+            return autoharness_standalone(StandaloneAutoharnessArgs {
+                input: args.input.unwrap(),
+                crate_name: args.crate_name,
+                common_autoharness_args: Default::default(),
+                std: false,
+                verify_opts: args.verify_opts,
+            });
+        }
+        None => {
+            let mut session = KaniSession::new(args.verify_opts)?;
+            session.enable_isolation(IsolationCommand::standalone(
+                std::env::args_os().skip(1).collect(),
+            ));
+            if !session.args.common_args.quiet && session.args.isolated_harness.is_none() {
+                print_kani_version(InvocationType::Standalone);
+            }
+
+            let project =
+                project::standalone_project(&args.input.unwrap(), args.crate_name, &session)?;
+            (session, project)
+        }
+    };
+    if session.args.only_codegen {
+        if !session.args.common_args.quiet {
+            print_unsupported_features(&project);
+        }
+        Ok(())
+    } else {
+        verify_project(project, session)
+    }
+}
+
+/// Run verification on the given project.
+fn verify_project(project: Project, session: KaniSession) -> Result<()> {
+    debug!(?project, "verify_project");
+    let mut harnesses = session.determine_targets(project.get_all_harnesses())?;
+    if let Some(range) = &session.args.affected_by {
+        let (affected, unchanged) = affected::select_affected(harnesses, range)?;
+        if !session.args.common_args.quiet {
+            affected::print_unchanged(&unchanged, range);
+        }
+        harnesses = affected;
+    }
+    if let Some(shard) = session.args.shard {
+        harnesses = shard::select_shard(harnesses, shard, session.args.shard_timings.as_deref())?;
+    }
+    debug!(n = harnesses.len(), ?harnesses, "verify_project");
+
+    if session.args.list_unsafe {
+        print_unsafe_operations(&harnesses);
+        return Ok(());
+    }
+
+    // Verification
+    let runner = harness_runner::HarnessRunner { sess: &session, project: &project };
+    let results = runner.check_all_harnesses(&harnesses)?;
+
+    if session.args.coverage {
+        // We generate a timestamp to save the coverage data in a folder named
+        // `kanicov_<date>` where `<date>` is the current date based on `format`
+        // below. The purpose of adding timestamps to the folder name is to make
+        // coverage results easily identifiable. Using a timestamp makes
+        // coverage results not only distinguishable, but also easy to relate to
+        // verification runs. We expect this to be particularly helpful for
+        // users in a proof debugging session, who are usually interested in the
+        // most recent results.
+        let time_now = OffsetDateTime::now_utc();
+        let format = format_description::parse("[year]-[month]-[day]_[hour]-[minute]").unwrap();
+        let timestamp = time_now.format(&format).unwrap();
+
+        session.save_coverage_metadata(&project, &timestamp)?;
+        session.save_coverage_results(&project, &results, &timestamp)?;
+    }
+
+    if session.args.emit_contract_manifest && session.args.isolated_harness.is_none() {
+        session.write_contract_manifests(&project, &results)?;
+    }
+
+    if session.args.isolated_harness.is_some() {
+        // The process that started this one reports the harness in its summary.
+        if results.iter().any(|result| result.result.status != VerificationStatus::Success) {
+            drop(session);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    session.print_final_summary(&results)
+}
+
+/// Print the unsupported features that are reachable from each harness, which the verification
+/// would otherwise report with the results of the harness.
+fn print_unsupported_features(project: &Project) {
+    for harness in project.get_all_harnesses() {
+        if let Some(summary) = harness_runner::unsupported_features_summary(harness) {
+            println!("{summary}");
+        }
+    }
+}
+
+/// Print the unsafe operations that are reachable from each harness, instead of verifying them.
+fn print_unsafe_operations(harnesses: &[&HarnessMetadata]) {
+    for harness in harnesses {
+        if harness.unsafe_operations.is_empty() {
+            println!("No unsafe operations are reachable from harness `{}`.", harness.pretty_name);
+            continue;
+        }
+        println!("Unsafe operations reachable from harness `{}`:", harness.pretty_name);
+        for operation in &harness.unsafe_operations {
+            println!(" - {} in `{}` at {}", operation.kind, operation.function, operation.location);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum InvocationType {
+    CargoKani(Vec<OsString>),
+    Standalone,
+}
+
+/// Peeks at command line arguments to determine if we're being invoked as 'kani' or 'cargo-kani'
+fn determine_invocation_type(mut args: Vec<OsString>) -> InvocationType {
+    let exe = util::executable_basename(&args.first());
+
+    // Case 1: if 'kani' is our first real argument, then we're being invoked as cargo-kani
+    // 'cargo kani ...' will cause cargo to run 'cargo-kani kani ...' preserving argv1
+    if Some(&OsString::from("kani")) == args.get(1) {
+        // Recreate our command line, but with 'kani' skipped
+        args.remove(1);
+        InvocationType::CargoKani(args)
+    }
+    // Case 2: if 'kani' is the name we're invoked as, then we're being invoked standalone
+    // Note: we care about argv0 here, NOT std::env::current_exe(), as the later will be resolved
+    else if Some("kani".into()) == exe {
+        InvocationType::Standalone
+    }
+    // Case 3: if 'cargo-kani' is the name we're invoked as, then the user is directly invoking
+    // 'cargo-kani' instead of 'cargo kani', and we shouldn't alter arguments.
+    else if Some("cargo-kani".into()) == exe {
+        InvocationType::CargoKani(args)
+    }
+    // Case 4: default fallback, act like standalone
+    else {
+        InvocationType::Standalone
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_invocation_type() {
+        // conversions to/from OsString are rough, simplify the test code below
+        fn x(args: Vec<&str>) -> Vec<OsString> {
+            args.iter().map(|x| x.into()).collect()
+        }
+
+        // Case 1: 'cargo kani'
+        assert_eq!(
+            determine_invocation_type(x(vec!["bar", "kani", "foo"])),
+            InvocationType::CargoKani(x(vec!["bar", "foo"]))
+        );
+        // Case 3: 'cargo-kani'
+        assert_eq!(
+            determine_invocation_type(x(vec!["cargo-kani", "foo"])),
+            InvocationType::CargoKani(x(vec!["cargo-kani", "foo"]))
+        );
+        // Case 2: 'kani'
+        assert_eq!(determine_invocation_type(x(vec!["kani", "foo"])), InvocationType::Standalone);
+        // default
+        assert_eq!(determine_invocation_type(x(vec!["foo"])), InvocationType::Standalone);
+        // weird case can be handled
+        assert_eq!(determine_invocation_type(x(vec![])), InvocationType::Standalone);
+    }
+}
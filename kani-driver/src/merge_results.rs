@@ -0,0 +1,396 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the `merge-results` subcommand, which combines the results that several runs of Kani
+//! wrote with `--statistics`, e.g., the shards of a `--shard` run, or a run of `autoharness` and a
+//! run of the hand-written harnesses of the same crate.
+//!
+//! A harness that appears in more than one file keeps the entry with the latest timestamp, so
+//! rerunning a harness and merging its results with older ones updates its status.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::args::merge_results_args::MergeResultsArgs;
+
+/// The results that `--statistics` writes.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct ResultsFile {
+    pub harnesses: Vec<HarnessEntry>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct HarnessEntry {
+    pub harness: String,
+    pub status: String,
+    /// The time it took to verify the harness, in seconds.
+    pub runtime: f64,
+    /// When the results were written, in RFC 3339 format. Older versions of Kani don't write it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    /// The other statistics of the harness, which we copy as they are.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl HarnessEntry {
+    pub(crate) fn succeeded(&self) -> bool {
+        self.status == "Success"
+    }
+
+    /// Entries without a valid timestamp are older than every entry with one.
+    fn timestamp(&self) -> Option<DateTime<FixedOffset>> {
+        self.timestamp.as_deref().and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+    }
+
+    fn stat_f64(&self, name: &str) -> Option<f64> {
+        self.other.get(name).and_then(serde_json::Value::as_f64)
+    }
+
+    fn stat_u64(&self, name: &str) -> Option<u64> {
+        self.other.get(name).and_then(serde_json::Value::as_u64)
+    }
+}
+
+/// The aggregated statistics of the merged harnesses.
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct Totals {
+    harnesses: usize,
+    successes: usize,
+    failures: usize,
+    /// The sum of the runtimes of every harness, in seconds.
+    runtime: f64,
+    symex_time: f64,
+    solver_time: f64,
+    vccs: u64,
+    /// The largest peak memory of any harness, in bytes.
+    peak_memory: Option<u64>,
+}
+
+impl Totals {
+    fn new(harnesses: &[HarnessEntry]) -> Totals {
+        let mut totals = Totals { harnesses: harnesses.len(), ..Default::default() };
+        for entry in harnesses {
+            if entry.succeeded() {
+                totals.successes += 1;
+            } else {
+                totals.failures += 1;
+            }
+            totals.runtime += entry.runtime;
+            totals.symex_time += entry.stat_f64("symex_time").unwrap_or_default();
+            totals.solver_time += entry.stat_f64("solver_time").unwrap_or_default();
+            totals.vccs += entry.stat_u64("vccs").unwrap_or_default();
+            if let Some(peak) = entry.stat_u64("peak_memory") {
+                totals.peak_memory = Some(totals.peak_memory.map_or(peak, |old| old.max(peak)));
+            }
+        }
+        totals
+    }
+}
+
+/// The merged results, as written to the output file.
+#[derive(Serialize)]
+struct MergedResults<'a> {
+    summary: &'a Totals,
+    harnesses: &'a [HarnessEntry],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    conflicts: &'a [Conflict],
+}
+
+pub(crate) fn read_results(path: &Path) -> Result<ResultsFile> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read results from `{}`", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse results from `{}`", path.display()))
+}
+
+/// Keep the latest entry of every harness, sorted by name, and return how many older entries were
+/// discarded. If two entries have the same timestamp, the one from the later file wins.
+pub(crate) fn dedup(files: Vec<ResultsFile>) -> (Vec<HarnessEntry>, usize) {
+    let mut latest: HashMap<String, HarnessEntry> = HashMap::new();
+    let mut discarded = 0;
+    for entry in files.into_iter().flat_map(|file| file.harnesses) {
+        match latest.entry(entry.harness.clone()) {
+            Entry::Vacant(vacant) => {
+                vacant.insert(entry);
+            }
+            Entry::Occupied(mut occupied) => {
+                discarded += 1;
+                if entry.timestamp() >= occupied.get().timestamp() {
+                    occupied.insert(entry);
+                }
+            }
+        }
+    }
+    let mut harnesses: Vec<_> = latest.into_values().collect();
+    harnesses.sort_by(|a, b| a.harness.cmp(&b.harness));
+    (harnesses, discarded)
+}
+
+/// A harness whose results in different files disagree on its status.
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Conflict {
+    harness: String,
+    /// Every result of the harness, in the order of the files.
+    results: Vec<ConflictingResult>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct ConflictingResult {
+    file: PathBuf,
+    status: String,
+}
+
+/// Find the harnesses that have results with different statuses, sorted by name.
+pub(crate) fn find_conflicts(files: &[(PathBuf, ResultsFile)]) -> Vec<Conflict> {
+    let mut results: BTreeMap<&str, Vec<ConflictingResult>> = BTreeMap::new();
+    for (file, results_file) in files {
+        for entry in &results_file.harnesses {
+            results
+                .entry(&entry.harness)
+                .or_default()
+                .push(ConflictingResult { file: file.clone(), status: entry.status.clone() });
+        }
+    }
+    results
+        .into_iter()
+        .filter(|(_, results)| results.iter().any(|result| result.status != results[0].status))
+        .map(|(harness, results)| Conflict { harness: harness.to_string(), results })
+        .collect()
+}
+
+/// Merge the results of every file, print a summary, and fail if any harness failed.
+pub fn merge_results(args: MergeResultsArgs) -> Result<()> {
+    let files = args.files.iter().map(|path| read_results(path)).collect::<Result<Vec<_>>>()?;
+    write_merged(files, &[], args.output.as_deref(), args.html.as_deref(), args.common_args.quiet)
+}
+
+/// Merge the results of `files`, write them to `output` and `html`, and print a summary unless
+/// `quiet` is set. Exit with a failure code if any harness failed or if there are any `conflicts`.
+pub(crate) fn write_merged(
+    files: Vec<ResultsFile>,
+    conflicts: &[Conflict],
+    output: Option<&Path>,
+    html: Option<&Path>,
+    quiet: bool,
+) -> Result<()> {
+    let num_files = files.len();
+    let (harnesses, discarded) = dedup(files);
+    let totals = Totals::new(&harnesses);
+
+    if let Some(path) = output {
+        let merged = MergedResults { summary: &totals, harnesses: &harnesses, conflicts };
+        std::fs::write(path, serde_json::to_string_pretty(&merged)?)
+            .with_context(|| format!("Failed to write results to `{}`", path.display()))?;
+    }
+    if let Some(path) = html {
+        std::fs::write(path, html_summary(&harnesses, &totals, conflicts))
+            .with_context(|| format!("Failed to write summary to `{}`", path.display()))?;
+    }
+
+    if !quiet {
+        println!("Merged Summary of {num_files} Files:");
+        if discarded > 0 {
+            println!(
+                "Discarded {discarded} older results of harnesses that appear more than once."
+            );
+        }
+        for conflict in conflicts {
+            let results: Vec<_> = conflict
+                .results
+                .iter()
+                .map(|result| format!("{} in `{}`", result.status, result.file.display()))
+                .collect();
+            println!("Conflicting results for - {}: {}", conflict.harness, results.join(", "));
+        }
+        for failure in harnesses.iter().filter(|entry| !entry.succeeded()) {
+            println!("Verification failed for - {}", failure.harness);
+        }
+        println!(
+            "Complete - {} successfully verified harnesses, {} failures, {} total.",
+            totals.successes, totals.failures, totals.harnesses
+        );
+        if !conflicts.is_empty() {
+            println!("Found {} harnesses with conflicting results.", conflicts.len());
+        }
+        println!(
+            "Total runtime: {:.3}s (symex {:.3}s, solver {:.3}s)",
+            totals.runtime, totals.symex_time, totals.solver_time
+        );
+    }
+    if totals.failures > 0 || !conflicts.is_empty() {
+        // Failure exit code without additional error message
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// A self-contained HTML page with a table of the merged results.
+fn html_summary(harnesses: &[HarnessEntry], totals: &Totals, conflicts: &[Conflict]) -> String {
+    let seconds = |time: Option<f64>| time.map_or_else(|| "-".to_string(), |t| format!("{t:.3}"));
+    let mebibytes = |bytes: Option<u64>| {
+        bytes.map_or_else(|| "-".to_string(), |b| format!("{:.1}", b as f64 / 1048576.0))
+    };
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+        <title>Kani Verification Results</title>\n<style>\n\
+        table { border-collapse: collapse; }\n\
+        th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }\n\
+        .success { color: green; }\n.failure, .conflict { color: red; }\n\
+        </style>\n</head>\n<body>\n<h1>Kani Verification Results</h1>\n",
+    );
+    let _ = writeln!(
+        html,
+        "<p>{} successfully verified harnesses, {} failures, {} total. \
+        Total runtime: {:.3}s. Peak memory: {} MiB.</p>",
+        totals.successes,
+        totals.failures,
+        totals.harnesses,
+        totals.runtime,
+        mebibytes(totals.peak_memory)
+    );
+    html.push_str(
+        "<table>\n<tr><th>Harness</th><th>Status</th><th>Runtime (s)</th>\
+        <th>Symex Time (s)</th><th>Solver Time (s)</th><th>Peak Memory (MiB)</th></tr>\n",
+    );
+    for entry in harnesses {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"{}\">{}</td><td>{:.3}</td><td>{}</td><td>{}</td>\
+            <td>{}</td></tr>",
+            escape_html(&entry.harness),
+            if entry.succeeded() { "success" } else { "failure" },
+            escape_html(&entry.status),
+            entry.runtime,
+            seconds(entry.stat_f64("symex_time")),
+            seconds(entry.stat_f64("solver_time")),
+            mebibytes(entry.stat_u64("peak_memory")),
+        );
+    }
+    html.push_str("</table>\n");
+    if !conflicts.is_empty() {
+        html.push_str(
+            "<h2>Conflicting Results</h2>\n<table>\n<tr><th>Harness</th><th>File</th>\
+            <th>Status</th></tr>\n",
+        );
+        for conflict in conflicts {
+            for result in &conflict.results {
+                let _ = writeln!(
+                    html,
+                    "<tr><td class=\"conflict\">{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&conflict.harness),
+                    escape_html(&result.file.display().to_string()),
+                    escape_html(&result.status),
+                );
+            }
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(harness: &str, status: &str, timestamp: Option<&str>) -> HarnessEntry {
+        HarnessEntry {
+            harness: harness.to_string(),
+            status: status.to_string(),
+            runtime: 1.0,
+            timestamp: timestamp.map(str::to_string),
+            other: serde_json::Map::new(),
+        }
+    }
+
+    #[test]
+    fn check_dedup_keeps_latest() {
+        let old = ResultsFile {
+            harnesses: vec![
+                entry("b", "Failure", Some("2025-01-02T00:00:00Z")),
+                entry("a", "Failure", None),
+            ],
+        };
+        let new = ResultsFile {
+            harnesses: vec![
+                entry("a", "Success", Some("2025-01-01T00:00:00Z")),
+                entry("b", "Success", Some("2025-01-01T23:00:00-02:00")),
+                entry("c", "Success", None),
+            ],
+        };
+        let (harnesses, discarded) = dedup(vec![new, old]);
+        let statuses: Vec<_> =
+            harnesses.iter().map(|e| (e.harness.as_str(), e.status.as_str())).collect();
+        // `b` at 23:00-02:00 is one hour later than `b` at midnight UTC.
+        assert_eq!(statuses, [("a", "Success"), ("b", "Success"), ("c", "Success")]);
+        assert_eq!(discarded, 2);
+    }
+
+    #[test]
+    fn check_find_conflicts() {
+        let first = ResultsFile {
+            harnesses: vec![entry("a", "Success", None), entry("b", "Success", None)],
+        };
+        let second = ResultsFile {
+            harnesses: vec![entry("b", "Failure", None), entry("a", "Success", None)],
+        };
+        let third = ResultsFile { harnesses: vec![entry("b", "Success", None)] };
+        let files = vec![
+            (PathBuf::from("first.json"), first),
+            (PathBuf::from("second.json"), second),
+            (PathBuf::from("third.json"), third),
+        ];
+        let conflicts = find_conflicts(&files);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].harness, "b");
+        let results: Vec<_> = conflicts[0]
+            .results
+            .iter()
+            .map(|result| (result.file.to_str().unwrap(), result.status.as_str()))
+            .collect();
+        assert_eq!(
+            results,
+            [("first.json", "Success"), ("second.json", "Failure"), ("third.json", "Success")]
+        );
+    }
+
+    #[test]
+    fn check_totals() {
+        let mut slow = entry("slow", "Success", None);
+        slow.runtime = 3.0;
+        slow.other.insert("solver_time".to_string(), 2.5.into());
+        slow.other.insert("peak_memory".to_string(), 2048.into());
+        let mut failed = entry("failed", "Failure", None);
+        failed.other.insert("peak_memory".to_string(), 1024.into());
+        failed.other.insert("vccs".to_string(), 7.into());
+        assert_eq!(
+            Totals::new(&[slow, failed]),
+            Totals {
+                harnesses: 2,
+                successes: 1,
+                failures: 1,
+                runtime: 4.0,
+                symex_time: 0.0,
+                solver_time: 2.5,
+                vccs: 7,
+                peak_memory: Some(2048),
+            }
+        );
+    }
+
+    #[test]
+    fn check_html_is_escaped() {
+        let harnesses = [entry("check<u8>", "Success", None)];
+        let html = html_summary(&harnesses, &Totals::new(&harnesses), &[]);
+        assert!(html.contains("<td>check&lt;u8&gt;</td>"));
+    }
+}
@@ -146,10 +146,21 @@ pub fn sort_harnesses_by_loc<'a>(harnesses: &[&'a HarnessMetadata]) -> Vec<&'a H
     harnesses_clone
 }
 
+/// Sort harnesses so that the automatic harnesses of the smallest functions come first, followed by
+/// the other harnesses sorted by location.
+pub fn sort_harnesses_by_mir_size<'a>(
+    harnesses: &[&'a HarnessMetadata],
+) -> Vec<&'a HarnessMetadata> {
+    let mut sorted = sort_harnesses_by_loc(harnesses);
+    sorted.sort_by_key(|harness| harness.target_mir_size.unwrap_or(usize::MAX));
+    sorted
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
     use kani_metadata::{HarnessAttributes, HarnessKind};
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
 
     pub fn mock_proof_harness(
@@ -167,11 +178,24 @@ pub fn mock_proof_harness(
             original_file: "<unknown>".into(),
             original_start_line: 0,
             original_end_line: 0,
+            docs: String::new(),
             attributes,
             goto_file: model_file,
+            shared_model: None,
             contract: Default::default(),
+            cbmc_contracts: None,
             has_loop_contracts: false,
             is_automatically_generated: false,
+            foreign_functions: vec![],
+            unsafe_operations: vec![],
+            spec_fns: vec![],
+            reachable_functions: vec![],
+            unsupported_features: vec![],
+            target_mir_size: None,
+            largest_array: None,
+            devirtualized_calls: None,
+            labeled_loops: BTreeMap::new(),
+            split_cases: vec![],
         }
     }
 
@@ -260,4 +284,20 @@ fn check_find_proof_harness_with_exact() {
             "module::not_check_three"
         );
     }
+
+    #[test]
+    fn check_sort_harnesses_by_mir_size() {
+        let mut harnesses = vec![
+            mock_proof_harness("manual", None, None, None),
+            mock_proof_harness("large", None, None, None),
+            mock_proof_harness("small", None, None, None),
+        ];
+        harnesses[1].target_mir_size = Some(120);
+        harnesses[2].target_mir_size = Some(8);
+        let ref_harnesses = harnesses.iter().collect::<Vec<_>>();
+
+        let sorted = sort_harnesses_by_mir_size(&ref_harnesses);
+        let names: Vec<_> = sorted.iter().map(|harness| harness.pretty_name.as_str()).collect();
+        assert_eq!(names, ["small", "large", "manual"]);
+    }
 }
@@ -0,0 +1,138 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for the versions of CBMC and Kissat that a project pins in its `Kani.toml`.
+//!
+//! `kani setup --cbmc-version <VERSION>` installs the pinned tools in the Kani home directory,
+//! e.g., `~/.kani/cbmc-<VERSION>/bin`, and records the pin in the `[tools]` table of the
+//! `Kani.toml` of the current directory, which should be the one of a package or a workspace:
+//!
+//! ```toml
+//! [tools]
+//! cbmc = "6.7.1"
+//! kissat = "4.0.1"
+//! ```
+//!
+//! The driver invokes these tools through the `PATH`, so we put the directories of the pinned
+//! tools in front of it.
+
+use crate::args_toml::{KANI_TOML, locate_kani_toml_files};
+use anyhow::{Context, Result, bail};
+use std::env;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use toml::Value;
+
+/// The versions of the tools pinned by a `Kani.toml`.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PinnedTools {
+    cbmc: Option<String>,
+    kissat: Option<String>,
+}
+
+/// Put the pinned versions of CBMC and Kissat in front of the `PATH`, if the `Kani.toml` files of
+/// the workspace or of the package pin them. The pins of the package take precedence.
+///
+/// This must be called before the driver spawns any thread, since it modifies the environment.
+pub fn use_pinned_tools(cargo_kani_args: Option<&[OsString]>) -> Result<()> {
+    // The version of each tool, and the `Kani.toml` that pins it.
+    let mut pins: [(&str, Option<(String, PathBuf)>); 2] = [("cbmc", None), ("kissat", None)];
+    for kani_toml in locate_kani_toml_files(cargo_kani_args) {
+        let data = std::fs::read_to_string(&kani_toml)
+            .with_context(|| format!("Failed to read `{}`", kani_toml.display()))?;
+        let file_pins =
+            parse_pins(&data).with_context(|| format!("Invalid `{}`", kani_toml.display()))?;
+        for ((_, pin), version) in pins.iter_mut().zip([file_pins.cbmc, file_pins.kissat]) {
+            if let Some(version) = version {
+                *pin = Some((version, kani_toml.clone()));
+            }
+        }
+    }
+    if pins.iter().all(|(_, pin)| pin.is_none()) {
+        return Ok(());
+    }
+
+    let tools_dir = kani_home()?;
+    let mut bin_dirs = vec![];
+    for (tool, pin) in pins {
+        let Some((version, kani_toml)) = pin else { continue };
+        let bin_dir = tools_dir.join(format!("{tool}-{version}")).join("bin");
+        if !bin_dir.join(tool).is_file() {
+            bail!(
+                "`{}` pins {tool} {version}, which is not installed. Run `kani setup --{tool}-version {version}` to install it.",
+                kani_toml.display()
+            );
+        }
+        bin_dirs.push(bin_dir);
+    }
+
+    let path = env::var_os("PATH").unwrap_or_default();
+    let path = env::join_paths(bin_dirs.into_iter().chain(env::split_paths(&path)))?;
+    // SAFETY: The driver calls this function at the beginning of `driver_main`, before it spawns
+    // any thread.
+    unsafe {
+        env::set_var("PATH", path);
+    }
+    Ok(())
+}
+
+/// The Kani home directory, where `kani setup` installs the pinned tools. This is `${KANI_HOME}`
+/// if the variable is set, and `${HOME}/.kani` otherwise.
+fn kani_home() -> Result<PathBuf> {
+    if let Some(kani_home) = env::var_os("KANI_HOME") {
+        return Ok(PathBuf::from(kani_home));
+    }
+    let Some(home) = env::home_dir() else {
+        bail!("Couldn't find the home directory to locate the tools pinned by `{KANI_TOML}`");
+    };
+    Ok(home.join(".kani"))
+}
+
+/// Parse the versions of the `[tools]` table of a `Kani.toml`.
+fn parse_pins(data: &str) -> Result<PinnedTools> {
+    let config = data.parse::<Value>()?;
+    let Some(tools) = config.get("tools") else {
+        return Ok(PinnedTools::default());
+    };
+    let Some(tools) = tools.as_table() else {
+        bail!("Expected `tools` to be a table");
+    };
+    let version = |tool: &str| match tools.get(tool) {
+        None => Ok(None),
+        Some(Value::String(version)) => Ok(Some(version.clone())),
+        Some(_) => bail!("Expected the version of `{tool}` to be a string"),
+    };
+    Ok(PinnedTools { cbmc: version("cbmc")?, kissat: version("kissat")? })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse_pins() {
+        let data = r#"
+            [tools]
+            cbmc = "6.7.1"
+            kissat = "4.0.1"
+        "#;
+        let pins = parse_pins(data).unwrap();
+        assert_eq!(pins.cbmc.as_deref(), Some("6.7.1"));
+        assert_eq!(pins.kissat.as_deref(), Some("4.0.1"));
+
+        let pins = parse_pins("[tools]\ncbmc = \"6.7.1\"\n").unwrap();
+        assert_eq!(pins, PinnedTools { cbmc: Some("6.7.1".into()), kissat: None });
+    }
+
+    #[test]
+    fn check_parse_pins_without_tools() {
+        assert_eq!(parse_pins("").unwrap(), PinnedTools::default());
+        assert_eq!(parse_pins("[other]\ncbmc = \"6.7.1\"\n").unwrap(), PinnedTools::default());
+    }
+
+    #[test]
+    fn check_parse_invalid_pins() {
+        assert!(parse_pins("tools = \"6.7.1\"").is_err());
+        assert!(parse_pins("[tools]\ncbmc = 6\n").is_err());
+    }
+}
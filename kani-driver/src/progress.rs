@@ -0,0 +1,258 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `--ui`, which shows a table with the state of every harness at the bottom of the
+//! terminal, and updates it while the harnesses are verified.
+//!
+//! The table replaces the messages that Kani prints while it checks a harness, but the results of
+//! each harness are still printed above it. If stdout isn't a terminal, e.g., in CI, Kani doesn't
+//! draw the table and prints its regular output instead.
+
+use comfy_table::{Cell, Color, ContentArrangement, Table as PrettyTable, presets};
+use console::Term;
+use kani_metadata::HarnessMetadata;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::cbmc_output_parser::CheckStatus;
+
+/// How often the table is redrawn to update the elapsed times.
+pub(crate) const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The number of lines of the table that aren't rows of a harness: the borders, the header and
+/// the summary line.
+const TABLE_OVERHEAD: usize = 5;
+
+/// The state of a harness in the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HarnessState {
+    Queued,
+    /// The goto binary of the harness is being instrumented, or the harness is being compiled by
+    /// a separate process.
+    Compiling,
+    Solving,
+    Done(VerificationStatus),
+    /// The harness wasn't verified because the time budget of `autoharness` ran out.
+    Skipped,
+}
+
+impl HarnessState {
+    fn is_running(self) -> bool {
+        matches!(self, HarnessState::Compiling | HarnessState::Solving)
+    }
+
+    fn cell(self) -> Cell {
+        match self {
+            HarnessState::Queued => Cell::new("queued"),
+            HarnessState::Compiling => Cell::new("compiling").fg(Color::Cyan),
+            HarnessState::Solving => Cell::new("solving").fg(Color::Yellow),
+            HarnessState::Done(VerificationStatus::Success) => {
+                Cell::new("successful").fg(Color::Green)
+            }
+            HarnessState::Done(VerificationStatus::Failure) => Cell::new("failed").fg(Color::Red),
+            HarnessState::Done(VerificationStatus::Vacuous) => {
+                Cell::new("vacuous").fg(Color::Yellow)
+            }
+            HarnessState::Skipped => Cell::new("skipped"),
+        }
+    }
+}
+
+struct Row {
+    /// The mangled name of the harness, which identifies the row.
+    id: String,
+    name: String,
+    state: HarnessState,
+    started: Option<Instant>,
+    /// The time that the harness took, once it's done.
+    elapsed: Option<Duration>,
+    /// The number of properties that were checked and that failed, once the harness is done.
+    properties: Option<(usize, usize)>,
+}
+
+impl Row {
+    fn elapsed(&self) -> Option<Duration> {
+        self.elapsed.or_else(|| self.started.map(|started| started.elapsed()))
+    }
+}
+
+struct ViewState {
+    rows: Vec<Row>,
+    /// The number of lines of the table that is currently drawn.
+    drawn_lines: usize,
+}
+
+/// The table of harnesses that `--ui` draws.
+pub(crate) struct ProgressView {
+    term: Term,
+    state: Mutex<ViewState>,
+}
+
+impl ProgressView {
+    /// Create the view for `harnesses`, or `None` if stdout isn't a terminal.
+    pub(crate) fn new(harnesses: &[&HarnessMetadata]) -> Option<Self> {
+        let term = Term::stdout();
+        if !term.is_term() {
+            return None;
+        }
+        let rows = harnesses
+            .iter()
+            .map(|harness| Row {
+                id: harness.mangled_name.clone(),
+                name: harness.pretty_name.clone(),
+                state: HarnessState::Queued,
+                started: None,
+                elapsed: None,
+                properties: None,
+            })
+            .collect();
+        Some(ProgressView { term, state: Mutex::new(ViewState { rows, drawn_lines: 0 }) })
+    }
+
+    /// Move `harness` to `state`.
+    pub(crate) fn set_state(&self, harness: &HarnessMetadata, state: HarnessState) {
+        self.update(harness, |row| {
+            if state.is_running() && row.started.is_none() {
+                row.started = Some(Instant::now());
+            }
+            row.state = state;
+        });
+    }
+
+    /// Record the result of `harness`.
+    pub(crate) fn finish(&self, harness: &HarnessMetadata, result: &VerificationResult) {
+        self.update(harness, |row| {
+            row.state = HarnessState::Done(result.status);
+            row.elapsed = Some(result.runtime);
+            row.properties = result.results.as_ref().ok().map(|properties| {
+                let failed =
+                    properties.iter().filter(|prop| prop.status == CheckStatus::Failure).count();
+                (properties.len(), failed)
+            });
+        });
+    }
+
+    /// Print `msg` above the table.
+    pub(crate) fn println(&self, msg: &str) {
+        let mut state = self.state.lock().unwrap();
+        self.clear(&mut state);
+        let _ = self.term.write_line(msg);
+        self.draw(&mut state);
+    }
+
+    /// Redraw the table, e.g., to update the elapsed times.
+    pub(crate) fn refresh(&self) {
+        let mut state = self.state.lock().unwrap();
+        self.clear(&mut state);
+        self.draw(&mut state);
+    }
+
+    fn update(&self, harness: &HarnessMetadata, update: impl FnOnce(&mut Row)) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(row) = state.rows.iter_mut().find(|row| row.id == harness.mangled_name) {
+            update(row);
+        }
+        self.clear(&mut state);
+        self.draw(&mut state);
+    }
+
+    fn clear(&self, state: &mut ViewState) {
+        let _ = self.term.clear_last_lines(state.drawn_lines);
+        state.drawn_lines = 0;
+    }
+
+    fn draw(&self, state: &mut ViewState) {
+        let (height, width) = self.term.size();
+        let table = render(&state.rows, height as usize, width);
+        state.drawn_lines = table.lines().count();
+        let _ = self.term.write_line(&table);
+    }
+}
+
+/// Render the table of `rows`, so that it fits in a terminal of the given size.
+///
+/// If there are too many rows, the running harnesses are shown first, then the queued ones, and
+/// then the ones that are done. The rows that are shown keep their order.
+fn render(rows: &[Row], height: usize, width: u16) -> String {
+    let max_rows = height.saturating_sub(TABLE_OVERHEAD).max(1);
+    let mut visible: Vec<_> = rows.iter().enumerate().collect();
+    if visible.len() > max_rows {
+        visible.sort_by_key(|(idx, row)| match row.state {
+            HarnessState::Compiling | HarnessState::Solving => (0, *idx),
+            HarnessState::Queued => (1, *idx),
+            HarnessState::Done(_) | HarnessState::Skipped => (2, *idx),
+        });
+        visible.truncate(max_rows);
+        visible.sort_by_key(|(idx, _)| *idx);
+    }
+
+    let mut table = PrettyTable::new();
+    table
+        .load_preset(presets::UTF8_HORIZONTAL_ONLY)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_width(width)
+        .set_header(vec!["Harness", "State", "Time", "Properties"]);
+    for (_, row) in visible {
+        let time = row.elapsed().map(|time| format!("{:.1}s", time.as_secs_f64()));
+        let properties = row.properties.map(|(checked, failed)| {
+            if failed == 0 { format!("{checked}") } else { format!("{checked} ({failed} failed)") }
+        });
+        table.add_row(vec![
+            Cell::new(&row.name),
+            row.state.cell(),
+            Cell::new(time.unwrap_or_default()),
+            Cell::new(properties.unwrap_or_default()),
+        ]);
+    }
+
+    let count = |predicate: fn(HarnessState) -> bool| {
+        rows.iter().filter(|row| predicate(row.state)).count()
+    };
+    let done = count(|state| matches!(state, HarnessState::Done(_) | HarnessState::Skipped));
+    let failed = count(|state| state == HarnessState::Done(VerificationStatus::Failure));
+    let running = count(HarnessState::is_running);
+    format!("{table}\n{done}/{} harnesses done, {failed} failed, {running} running", rows.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(name: &str, state: HarnessState) -> Row {
+        Row {
+            id: name.to_string(),
+            name: name.to_string(),
+            state,
+            started: None,
+            elapsed: None,
+            properties: None,
+        }
+    }
+
+    #[test]
+    fn check_render_summary() {
+        let mut done = row("check_done", HarnessState::Done(VerificationStatus::Failure));
+        done.elapsed = Some(Duration::from_millis(1500));
+        done.properties = Some((12, 1));
+        let rows = vec![done, row("check_solving", HarnessState::Solving)];
+        let table = render(&rows, 40, 80);
+        assert!(table.contains("check_done"));
+        assert!(table.contains("1.5s"));
+        assert!(table.contains("12 (1 failed)"));
+        assert!(table.ends_with("1/2 harnesses done, 1 failed, 1 running"));
+    }
+
+    #[test]
+    fn check_render_prioritizes_running_harnesses() {
+        let rows = vec![
+            row("check_done", HarnessState::Done(VerificationStatus::Success)),
+            row("check_queued", HarnessState::Queued),
+            row("check_compiling", HarnessState::Compiling),
+        ];
+        let table = render(&rows, TABLE_OVERHEAD + 2, 80);
+        assert!(table.contains("check_compiling"));
+        assert!(table.contains("check_queued"));
+        assert!(!table.contains("check_done"));
+        assert!(table.find("check_queued") < table.find("check_compiling"));
+    }
+}
@@ -4,6 +4,7 @@
 //! The goal is to provide one project view independent on the build system (cargo / standalone
 //! rustc) and its configuration (e.g.: linker type).
 
+use crate::args_toml::apply_harness_overrides;
 use crate::metadata::from_json;
 use crate::session::KaniSession;
 use crate::util::{crate_name, info_operation};
@@ -11,6 +12,7 @@
 use kani_metadata::{
     ArtifactType, ArtifactType::*, HarnessMetadata, KaniMetadata, artifact::convert_type,
 };
+use std::collections::HashSet;
 use std::env::current_dir;
 use std::fs;
 use std::ops::Deref;
@@ -67,9 +69,10 @@ pub fn get_harness_artifact(
         harness: &HarnessMetadata,
         typ: ArtifactType,
     ) -> Option<&Artifact> {
-        let expected_path = harness
-            .goto_file
-            .as_ref()
+        // Only the goto binary is specific to a harness that shares its model.
+        let model = if typ == Goto { None } else { harness.shared_model.as_ref() };
+        let expected_path = model
+            .or(harness.goto_file.as_ref())
             .and_then(|goto_file| convert_type(goto_file, SymTabGoto, typ).canonicalize().ok());
         trace!(?harness.goto_file, ?expected_path, ?typ, "get_harness_artifact");
         self.artifacts.iter().find(|artifact| {
@@ -92,29 +95,43 @@ fn try_new(
         // For each harness (test or proof) from each metadata, read the path for the goto
         // SymTabGoto file. Use that path to find all the other artifacts.
         let mut artifacts = vec![];
+        let mut linked_models = HashSet::new();
+        // All other harness artifacts that may have been generated as part of the build.
+        let build_artifacts = |symtab_out: &Artifact| {
+            [SymTab, TypeMap, VTableRestriction, PrettyNameMap, Mir]
+                .iter()
+                .filter_map(|typ| Artifact::try_from(symtab_out, *typ).ok())
+                .collect::<Vec<_>>()
+        };
         for crate_metadata in &metadata {
             for harness_metadata in
                 crate_metadata.test_harnesses.iter().chain(crate_metadata.proof_harnesses.iter())
             {
-                let symtab_out = Artifact::try_new(
-                    harness_metadata.goto_file.as_ref().expect("Expected a model file"),
-                    SymTabGoto,
-                )?;
-                let goto_path = convert_type(&symtab_out.path, symtab_out.typ, Goto);
-
-                // Link
-                session.link_goto_binary(&[symtab_out.to_path_buf()], &goto_path)?;
-                let goto = Artifact::try_new(&goto_path, Goto)?;
+                // Harnesses isolated in a separate process are compiled by that process.
+                let Some(goto_file) = &harness_metadata.goto_file else {
+                    continue;
+                };
+                let goto_path = convert_type(goto_file, SymTabGoto, Goto);
+                if let Some(shared_model) = &harness_metadata.shared_model {
+                    // Link the model once, and give each harness that shares it a copy that it
+                    // instruments for itself.
+                    let symtab_out = Artifact::try_new(shared_model, SymTabGoto)?;
+                    let shared_goto = convert_type(&symtab_out.path, symtab_out.typ, Goto);
+                    if linked_models.insert(symtab_out.clone()) {
+                        session.link_goto_binary(&[symtab_out.to_path_buf()], &shared_goto)?;
+                        artifacts.extend(build_artifacts(&symtab_out));
+                        artifacts.push(symtab_out);
+                    }
+                    fs::copy(&shared_goto, &goto_path)?;
+                } else {
+                    let symtab_out = Artifact::try_new(goto_file, SymTabGoto)?;
 
-                // All other harness artifacts that may have been generated as part of the build.
-                artifacts.extend(
-                    [SymTab, TypeMap, VTableRestriction, PrettyNameMap].iter().filter_map(|typ| {
-                        let artifact = Artifact::try_from(&symtab_out, *typ).ok()?;
-                        Some(artifact)
-                    }),
-                );
-                artifacts.push(symtab_out);
-                artifacts.push(goto);
+                    // Link
+                    session.link_goto_binary(&[symtab_out.to_path_buf()], &goto_path)?;
+                    artifacts.extend(build_artifacts(&symtab_out));
+                    artifacts.push(symtab_out);
+                }
+                artifacts.push(Artifact::try_new(&goto_path, Goto)?);
             }
         }
 
@@ -160,7 +177,7 @@ pub fn has_type(&self, typ: ArtifactType) -> bool {
 
     /// Try to derive an artifact based on a different artifact of a different type.
     /// For example:
-    /// ```no_run
+    /// ```ignore
     /// let artifact = Artifact::try_new(&"/tmp/file.kani_metadata.json", Metadata).unwrap();
     /// let goto = Artifact::try_from(artifact, Goto); // Will try to create "/tmp/file.goto"
     /// ```
@@ -183,8 +200,9 @@ pub fn cargo_project(session: &mut KaniSession, keep_going: bool) -> Result<Proj
     }
     let outdir = outputs.outdir.canonicalize()?;
     // For the MIR Linker we know there is only one metadata per crate. Use that in our favor.
-    let metadata =
+    let mut metadata =
         outputs.metadata.iter().map(|md_file| from_json(md_file)).collect::<Result<Vec<_>>>()?;
+    apply_harness_overrides(&mut metadata, &outputs.cargo_metadata, &session.args.tags)?;
     Project::try_new(session, outdir, None, metadata, Some(outputs.cargo_metadata))
 }
 
@@ -239,6 +257,8 @@ fn build(self) -> Result<Project> {
         let rlib_path = self.rlib_name();
         self.session.record_temporary_file(&rlib_path);
         self.session.record_temporary_file(&self.metadata.path);
+        let harnesses_dir = self.outdir.join(format!("{}.kani", self.crate_name));
+        self.session.record_temporary_file(&harnesses_dir);
 
         // Build and link the artifacts.
         debug!(krate=?self.crate_name, input=?self.input, ?rlib_path, "build compile");
@@ -251,6 +271,14 @@ fn build(self) -> Result<Project> {
             Project::try_new(self.session, self.outdir, Some(self.input), vec![metadata], None);
         if let Ok(project) = &result {
             self.session.record_temporary_files(&project.artifacts);
+            // The compiler puts the artifacts of each harness in its own directory.
+            let harness_dirs: HashSet<&Path> = project
+                .artifacts
+                .iter()
+                .filter_map(|artifact| artifact.path.parent())
+                .filter(|dir| dir.starts_with(&harnesses_dir))
+                .collect();
+            self.session.record_temporary_files(&harness_dirs.into_iter().collect::<Vec<_>>());
         }
         result
     }
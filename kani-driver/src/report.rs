@@ -0,0 +1,131 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the `report` subcommand. `report merge` combines the results that independent runs
+//! of Kani wrote with `--statistics`, e.g., the jobs of a sharded CI workflow that each uploaded
+//! their results to a separate directory.
+//!
+//! Unlike `merge-results`, which expects a harness to be rerun and keeps its latest result, a
+//! harness whose results disagree on its status is reported as a conflict, since independent runs
+//! of the same code should agree.
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+
+use crate::args::report_args::{ReportArgs, ReportMergeArgs, ReportSubcommand};
+use crate::merge_results::{ResultsFile, find_conflicts, read_results, write_merged};
+
+pub fn report(args: ReportArgs) -> Result<()> {
+    match args.command {
+        ReportSubcommand::Merge(args) => merge(args),
+    }
+}
+
+fn merge(args: ReportMergeArgs) -> Result<()> {
+    let files = read_inputs(&args.inputs)?;
+    let conflicts = find_conflicts(&files);
+    write_merged(
+        files.into_iter().map(|(_, results)| results).collect(),
+        &conflicts,
+        args.out.as_deref(),
+        args.html.as_deref(),
+        args.common_args.quiet,
+    )
+}
+
+/// Read the results files of every input.
+fn read_inputs(inputs: &[PathBuf]) -> Result<Vec<(PathBuf, ResultsFile)>> {
+    let mut paths = vec![];
+    for input in inputs {
+        paths.extend(results_files(input)?);
+    }
+    paths.into_iter().map(|path| read_results(&path).map(|results| (path, results))).collect()
+}
+
+/// The results files of an input: the input itself if it is a file, or the JSON files of a
+/// directory, sorted by name.
+fn results_files(input: &Path) -> Result<Vec<PathBuf>> {
+    if !input.is_dir() {
+        return Ok(vec![input.to_path_buf()]);
+    }
+    let mut files = vec![];
+    for entry in std::fs::read_dir(input)
+        .with_context(|| format!("Failed to read directory `{}`", input.display()))?
+    {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+            files.push(path);
+        }
+    }
+    if files.is_empty() {
+        bail!("Directory `{}` does not contain any results", input.display());
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merge_results::dedup;
+
+    /// Write a results file with the given harnesses and statuses to `dir`.
+    fn write_results(dir: &Path, name: &str, harnesses: &[(&str, &str)]) -> PathBuf {
+        let harnesses: Vec<_> = harnesses
+            .iter()
+            .map(|(harness, status)| {
+                serde_json::json!({ "harness": harness, "status": status, "runtime": 1.0 })
+            })
+            .collect();
+        let path = dir.join(name);
+        std::fs::write(&path, serde_json::json!({ "harnesses": harnesses }).to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn check_conflicting_statuses() {
+        let dir = tempfile::tempdir().unwrap();
+        let first =
+            write_results(dir.path(), "shard-0.json", &[("a", "Success"), ("b", "Success")]);
+        let second = write_results(dir.path(), "shard-1.json", &[("b", "Failure")]);
+        let files = read_inputs(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(files.iter().map(|(path, _)| path).collect::<Vec<_>>(), [&first, &second]);
+
+        let conflicts = serde_json::to_value(find_conflicts(&files)).unwrap();
+        assert_eq!(
+            conflicts,
+            serde_json::json!([{
+                "harness": "b",
+                "results": [
+                    { "file": first, "status": "Success" },
+                    { "file": second, "status": "Failure" },
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn check_identical_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = write_results(dir.path(), "first.json", &[("a", "Success"), ("b", "Failure")]);
+        let second =
+            write_results(dir.path(), "second.json", &[("a", "Success"), ("b", "Failure")]);
+        let files = read_inputs(&[first, second]).unwrap();
+        assert!(find_conflicts(&files).is_empty());
+
+        // Each harness is reported once.
+        let (harnesses, discarded) = dedup(files.into_iter().map(|(_, results)| results).collect());
+        let statuses: Vec<_> =
+            harnesses.iter().map(|e| (e.harness.as_str(), e.status.as_str())).collect();
+        assert_eq!(statuses, [("a", "Success"), ("b", "Failure")]);
+        assert_eq!(discarded, 2);
+    }
+
+    #[test]
+    fn check_empty_input_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        // Files that aren't JSON aren't results.
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+        let err = read_inputs(&[dir.path().to_path_buf()]).unwrap_err();
+        assert!(err.to_string().contains("does not contain any results"), "{err}");
+    }
+}
@@ -0,0 +1,481 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements the `server` subcommand, which lets editor extensions drive Kani, e.g., to verify
+//! the harness under the cursor and render the status of its properties inline.
+//!
+//! The server listens on a TCP port of the local host and prints the port and a token when it
+//! starts. It exchanges JSON-RPC 2.0 messages with one client at a time, one message per line. The
+//! standard output keeps the usual output of Kani, such as the compilation errors. The methods are:
+//! - `initialize` with `{"token": <token>}`: authenticate the client, which must be its first
+//!   request. The server closes the connection of a client that doesn't send the right token.
+//! - `listHarnesses`: the harnesses of the project and their location.
+//! - `verifyHarness` with `{"harness": <name>}`: verify a harness, and return its status and the
+//!   status and location of each of its properties.
+//! - `getLastTrace` with `{"harness": <name>}`: the trace of the first failed property of the last
+//!   verification of a harness, or `null` if no property failed.
+//! - `didSave`: a notification that the sources changed.
+//! - `shutdown`: stop the server.
+//!
+//! A background thread builds the project when the server starts and again after every `didSave`
+//! notification, so the build usually finished by the time a request needs the project, and
+//! verifying a harness again doesn't rebuild it.
+
+use anyhow::{Result, bail};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+
+use crate::args::server_args::{CargoServerArgs, StandaloneServerArgs};
+use crate::call_cbmc::VerificationResult;
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::harness_runner::HarnessRunner;
+use crate::project::{self, Project};
+use crate::session::KaniSession;
+use crate::{InvocationType, print_kani_version};
+use kani_metadata::{HarnessMetadata, find_proof_harnesses};
+
+/// The JSON-RPC error codes that the server reports.
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+/// The error of a request sent before the client authenticated itself.
+const UNAUTHORIZED: i64 = -32001;
+
+pub fn server_cargo(args: CargoServerArgs) -> Result<()> {
+    let build_session = KaniSession::new(args.verify_opts.clone())?;
+    let session = KaniSession::new(args.verify_opts)?;
+    if !session.args.common_args.quiet {
+        print_kani_version(InvocationType::CargoKani(vec![]));
+    }
+    let builder = ProjectBuilder::spawn(build_session, ProjectSource::Cargo);
+    Server::new(session, builder)?.serve(args.common_server_args.port)
+}
+
+pub fn server_standalone(args: StandaloneServerArgs) -> Result<()> {
+    let build_session = KaniSession::new(args.verify_opts.clone())?;
+    let session = KaniSession::new(args.verify_opts)?;
+    if !session.args.common_args.quiet {
+        print_kani_version(InvocationType::Standalone);
+    }
+    let source = ProjectSource::Standalone { input: args.input, crate_name: args.crate_name };
+    let builder = ProjectBuilder::spawn(build_session, source);
+    Server::new(session, builder)?.serve(args.common_server_args.port)
+}
+
+/// How to build the project of the server.
+enum ProjectSource {
+    Cargo,
+    Standalone { input: PathBuf, crate_name: Option<String> },
+}
+
+impl ProjectSource {
+    fn build(&self, session: &mut KaniSession) -> Result<Project> {
+        match self {
+            ProjectSource::Cargo => project::cargo_project(session, false),
+            ProjectSource::Standalone { input, crate_name } => {
+                project::standalone_project(input, crate_name.clone(), session)
+            }
+        }
+    }
+}
+
+/// Builds the project in a background thread, with a session of its own, whenever the sources
+/// change.
+struct ProjectBuilder {
+    state: Arc<(Mutex<BuildState>, Condvar)>,
+    /// Wakes the build thread up after the sources changed.
+    rebuild: Sender<()>,
+}
+
+#[derive(Default)]
+struct BuildState {
+    /// The number of times that the sources changed.
+    changes: u64,
+    /// The outcome of the latest build, along with the number of changes that it includes.
+    latest: Option<(u64, Result<Arc<Project>, String>)>,
+}
+
+impl ProjectBuilder {
+    /// Start the build thread, which builds the project right away.
+    fn spawn(mut session: KaniSession, source: ProjectSource) -> Self {
+        let state = Arc::new((Mutex::new(BuildState::default()), Condvar::new()));
+        let (rebuild, changes) = mpsc::channel();
+        let shared = state.clone();
+        std::thread::spawn(move || {
+            let (lock, built) = &*shared;
+            loop {
+                let included = lock.lock().unwrap().changes;
+                let project = source.build(&mut session);
+                let project = project.map(Arc::new).map_err(|error| format!("{error:#}"));
+                lock.lock().unwrap().latest = Some((included, project));
+                built.notify_all();
+                // Build once for all the changes made since the last build.
+                if changes.recv().is_err() {
+                    break;
+                }
+                while changes.try_recv().is_ok() {}
+            }
+        });
+        ProjectBuilder { state, rebuild }
+    }
+
+    fn sources_changed(&self) {
+        self.state.0.lock().unwrap().changes += 1;
+        // The build thread only stops once the server drops its sender.
+        self.rebuild.send(()).unwrap();
+    }
+
+    /// The project of the current sources, once the build thread built it.
+    fn project(&self) -> Result<Arc<Project>> {
+        let (lock, built) = &*self.state;
+        let state = built
+            .wait_while(lock.lock().unwrap(), |state| {
+                state.latest.as_ref().is_none_or(|(included, _)| *included < state.changes)
+            })
+            .unwrap();
+        match &state.latest.as_ref().unwrap().1 {
+            Ok(project) => Ok(project.clone()),
+            Err(error) => bail!("{error}"),
+        }
+    }
+}
+
+struct Server {
+    session: KaniSession,
+    builder: ProjectBuilder,
+    /// The token that clients must send in their `initialize` request.
+    token: String,
+    /// The result of the last verification of each harness.
+    results: HashMap<String, VerificationResult>,
+    shutdown: bool,
+}
+
+/// An error that is reported to the client instead of stopping the server.
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn invalid_params(message: impl Into<String>) -> Self {
+        RpcError { code: INVALID_PARAMS, message: message.into() }
+    }
+}
+
+impl From<anyhow::Error> for RpcError {
+    fn from(error: anyhow::Error) -> Self {
+        RpcError { code: INTERNAL_ERROR, message: format!("{error:#}") }
+    }
+}
+
+#[derive(Deserialize)]
+struct Request {
+    /// The identifier of the request, which is missing for notifications.
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarnessParams {
+    harness: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct InitializeParams {
+    token: String,
+}
+
+impl Server {
+    fn new(mut session: KaniSession, builder: ProjectBuilder) -> Result<Self> {
+        // CBMC only reports the traces of the failed properties when it's asked to.
+        session.args.cbmc_args.push("--trace".into());
+        let mut bytes = [0u8; 16];
+        getrandom::fill(&mut bytes)
+            .map_err(|error| anyhow::anyhow!("Failed to generate the server token: {error}"))?;
+        let token = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+        Ok(Server { session, builder, token, results: HashMap::new(), shutdown: false })
+    }
+
+    fn serve(mut self, port: u16) -> Result<()> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        println!("Kani server listening on {} with token {}", listener.local_addr()?, self.token);
+        for stream in listener.incoming() {
+            self.serve_client(stream?)?;
+            if self.shutdown {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Answer the requests of a client until it disconnects, fails to authenticate itself, or
+    /// shuts the server down.
+    fn serve_client(&mut self, stream: TcpStream) -> Result<()> {
+        let reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+        let mut authenticated = false;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = dispatch(&line, |method, params| {
+                if authenticated {
+                    self.handle(method, params)
+                } else {
+                    self.authenticate(method, params)?;
+                    authenticated = true;
+                    Ok(Value::Null)
+                }
+            });
+            if let Some(response) = response {
+                writeln!(writer, "{response}")?;
+                writer.flush()?;
+            }
+            if self.shutdown || !authenticated {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that the first request of a client is an `initialize` request with the right token.
+    fn authenticate(&self, method: &str, params: Value) -> Result<(), RpcError> {
+        let unauthorized = |message: &str| RpcError { code: UNAUTHORIZED, message: message.into() };
+        if method != "initialize" {
+            return Err(unauthorized("The first request must be `initialize`"));
+        }
+        let params: InitializeParams = parse_params(params)?;
+        if params.token != self.token {
+            return Err(unauthorized("Invalid token"));
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "listHarnesses" => self.list_harnesses(),
+            "verifyHarness" => self.verify_harness(parse_params(params)?),
+            "getLastTrace" => self.last_trace(parse_params(params)?),
+            "initialize" => Ok(Value::Null),
+            "didSave" => {
+                self.builder.sources_changed();
+                Ok(Value::Null)
+            }
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            _ => Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("Unknown method `{method}`"),
+            }),
+        }
+    }
+
+    fn list_harnesses(&mut self) -> Result<Value, RpcError> {
+        let harnesses: Vec<_> = self
+            .builder
+            .project()?
+            .get_all_harnesses()
+            .into_iter()
+            .map(|harness| {
+                json!({
+                    "name": harness.pretty_name,
+                    "file": harness.original_file,
+                    "startLine": harness.original_start_line,
+                    "endLine": harness.original_end_line,
+                })
+            })
+            .collect();
+        Ok(Value::Array(harnesses))
+    }
+
+    fn verify_harness(&mut self, params: HarnessParams) -> Result<Value, RpcError> {
+        let project = self.builder.project()?;
+        let harness = find_harness(&project, &params.harness)?;
+        let runner = HarnessRunner { sess: &self.session, project: &project };
+        let harnesses = [harness];
+        let mut results = runner.check_all_harnesses(&harnesses)?;
+        let result = results.pop().unwrap().result;
+        let response = verification_response(harness, &result);
+        self.results.insert(harness.pretty_name.clone(), result);
+        Ok(response)
+    }
+
+    fn last_trace(&mut self, params: HarnessParams) -> Result<Value, RpcError> {
+        let project = self.builder.project()?;
+        let name = find_harness(&project, &params.harness)?.pretty_name.clone();
+        let Some(result) = self.results.get(&name) else {
+            return Err(RpcError::invalid_params(format!("Harness `{name}` wasn't verified yet")));
+        };
+        let failed = result.results.as_ref().ok().and_then(|properties| {
+            properties.iter().find(|property| {
+                property.status == CheckStatus::Failure && property.trace.is_some()
+            })
+        });
+        Ok(failed.map_or(Value::Null, trace_response))
+    }
+}
+
+/// Parse a request, let `handle` compute its result, and return the response to send, if the
+/// request isn't a notification.
+fn dispatch(
+    line: &str,
+    handle: impl FnOnce(&str, Value) -> Result<Value, RpcError>,
+) -> Option<Value> {
+    let request: Request = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(error) => {
+            let error = RpcError { code: PARSE_ERROR, message: error.to_string() };
+            return Some(error_response(Value::Null, error));
+        }
+    };
+    let result = handle(&request.method, request.params);
+    let id = request.id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => error_response(id, error),
+    })
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+fn parse_params<T: for<'a> Deserialize<'a>>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|error| RpcError::invalid_params(error.to_string()))
+}
+
+/// Find the harness called `name`, which may omit the modules of the harness if no other
+/// harness has the same name.
+fn find_harness<'a>(project: &'a Project, name: &str) -> Result<&'a HarnessMetadata> {
+    let name = name.to_string();
+    let harnesses =
+        find_proof_harnesses(&BTreeSet::from([&name]), project.get_all_harnesses(), false);
+    if let Some(harness) = harnesses.iter().find(|harness| harness.pretty_name == name) {
+        return Ok(harness);
+    }
+    match harnesses.as_slice() {
+        [harness] => Ok(harness),
+        [] => bail!("No harness matches `{name}`"),
+        _ => {
+            let names: Vec<_> = harnesses.iter().map(|h| h.pretty_name.as_str()).collect();
+            bail!("`{name}` matches more than one harness: `{}`", names.join("`, `"))
+        }
+    }
+}
+
+fn verification_response(harness: &HarnessMetadata, result: &VerificationResult) -> Value {
+    let mut response = json!({
+        "harness": harness.pretty_name,
+        "status": result.status.to_string(),
+        "runtime": result.runtime.as_secs_f64(),
+    });
+    match &result.results {
+        Ok(properties) => {
+            let properties: Vec<_> = properties
+                .iter()
+                .map(|property| {
+                    let location = &property.source_location;
+                    json!({
+                        "property": property.property_name(),
+                        "description": property.description,
+                        "status": property.status,
+                        "file": location.file,
+                        "line": location.line.as_ref().and_then(|line| line.parse::<u64>().ok()),
+                        "column": location.column.as_ref().and_then(|col| col.parse::<u64>().ok()),
+                    })
+                })
+                .collect();
+            response["properties"] = Value::Array(properties);
+        }
+        Err(exit_status) => {
+            response["error"] = Value::String(format!("{exit_status:?}"));
+        }
+    }
+    response
+}
+
+/// The assignments of the trace of a failed property that have a location and a value.
+fn trace_response(property: &Property) -> Value {
+    let steps: Vec<_> = property
+        .trace
+        .iter()
+        .flatten()
+        .filter(|item| item.step_type == "assignment")
+        .filter_map(|item| {
+            let location = item.source_location.as_ref()?;
+            let value = item.value.as_ref()?.data.as_ref()?;
+            Some(json!({
+                "lhs": item.lhs,
+                "value": value.to_string(),
+                "file": location.file,
+                "line": location.line.as_ref().and_then(|line| line.parse::<u64>().ok()),
+            }))
+        })
+        .collect();
+    json!({
+        "property": property.property_name(),
+        "description": property.description,
+        "steps": steps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo(method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "echo" => Ok(params),
+            _ => Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("Unknown method `{method}`"),
+            }),
+        }
+    }
+
+    #[test]
+    fn check_dispatch_request() {
+        let response = dispatch(r#"{"jsonrpc":"2.0","id":1,"method":"echo","params":[2]}"#, echo);
+        assert_eq!(response, Some(json!({ "jsonrpc": "2.0", "id": 1, "result": [2] })));
+    }
+
+    #[test]
+    fn check_dispatch_notification() {
+        assert_eq!(dispatch(r#"{"jsonrpc":"2.0","method":"echo"}"#, echo), None);
+    }
+
+    #[test]
+    fn check_dispatch_errors() {
+        let response = dispatch(r#"{"jsonrpc":"2.0","id":"a","method":"verify"}"#, echo).unwrap();
+        assert_eq!(response["id"], "a");
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = dispatch("{not json", echo).unwrap();
+        assert_eq!(response["id"], Value::Null);
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+    }
+
+    #[test]
+    fn check_parse_params() {
+        let params: HarnessParams = parse_params(json!({ "harness": "check_foo" })).unwrap();
+        assert_eq!(params.harness, "check_foo");
+        let error = parse_params::<HarnessParams>(json!({})).unwrap_err();
+        assert_eq!(error.code, INVALID_PARAMS);
+    }
+}
@@ -1,17 +1,19 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::args::Timeout;
 use crate::args::VerificationArgs;
 use crate::args::common::Verbosity;
-use crate::util::render_command;
+use crate::isolation::IsolationCommand;
+use crate::progress::ProgressView;
+use crate::util::{executable_name, render_command};
 use anyhow::{Context, Result, bail};
+use cargo_metadata::CompilerMessage;
 use std::io::IsTerminal;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
-use std::sync::Mutex;
-use std::time::Instant;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
 use strum_macros::Display;
 use tokio::process::Command as TokioCommand;
 use tracing::level_filters::LevelFilter;
@@ -30,6 +32,10 @@
 const FLAMEGRAPH_DIR: &str = "flamegraphs";
 const FLAMEGRAPH_SAMPLING_RATE: &str = "8000"; // in Hz
 
+/// Kani installation directory selected by a tool that embeds Kani via [crate::api].
+/// When unset, we locate the installation relative to the current executable.
+static INSTALL_DIR: OnceLock<PathBuf> = OnceLock::new();
+
 /// Contains information about the execution environment and arguments that affect operations
 pub struct KaniSession {
     /// The common command-line arguments
@@ -39,6 +45,43 @@ pub struct KaniSession {
     /// Invariant: this field is_some() iff the autoharness subcommand is enabled.
     pub autoharness_compiler_flags: Option<Vec<String>>,
 
+    /// The wall-clock budget for verifying all automatic harnesses, set by `--total-timeout`.
+    pub autoharness_budget: Option<Duration>,
+
+    /// When the verification of the harnesses started, which the budget of `--total-timeout`
+    /// counts from.
+    pub budget_start: OnceLock<Instant>,
+
+    /// The timeout of the harnesses without `--harness-timeout` or a timeout of their own, e.g.,
+    /// the default timeout of automatic harnesses.
+    pub default_harness_timeout: Option<Duration>,
+
+    /// The crate and name of the functions whose automatic harnesses weren't verified because the
+    /// budget ran out.
+    pub skipped_by_budget: Mutex<Vec<(String, String)>>,
+
+    /// The command that verifies a harness annotated with `#[kani::proof(isolate = "process")]`
+    /// in a separate process. Isolated harnesses are verified by this process if it is `None`.
+    pub isolation_command: Option<IsolationCommand>,
+
+    /// The CBMC binary that verifies the harnesses.
+    pub cbmc: PathBuf,
+
+    /// The session that verifies every harness a second time with the backend given by
+    /// `--compare-backend`.
+    pub comparison: Option<Box<KaniSession>>,
+
+    /// The harnesses whose results differ between the two backends of `--compare-backend`.
+    pub backend_discrepancies: Mutex<Vec<String>>,
+
+    /// Whether the compiler records the functions that each harness reaches, which `--affected-by`
+    /// and the `doc-proofs` subcommand use.
+    pub record_reachable_functions: bool,
+
+    /// Whether the compiler reports the type definitions of the local crates, which
+    /// `cargo kani fix` uses to find where to add the derives that are missing.
+    pub print_type_definitions: bool,
+
     /// The location we found the 'kani_rustc' command
     pub kani_compiler: PathBuf,
     /// The location we found 'kani_lib.c'
@@ -47,6 +90,16 @@ pub struct KaniSession {
     /// The temporary files we littered that need to be cleaned up at the end of execution
     pub temporaries: Mutex<Vec<PathBuf>>,
 
+    /// The errors reported by the compiler, which `cargo kani fix` inspects.
+    pub compiler_errors: Mutex<Vec<CompilerMessage>>,
+
+    /// The notes of the compiler at the type definitions of the local crates, which it only
+    /// reports if `print_type_definitions` is set.
+    pub type_definitions: Mutex<Vec<CompilerMessage>>,
+
+    /// The table that `--ui` draws while the harnesses are verified.
+    pub progress: RwLock<Option<Arc<ProgressView>>>,
+
     /// The tokio runtime
     pub runtime: tokio::runtime::Runtime,
 }
@@ -62,22 +115,55 @@ pub enum InstallType {
 }
 
 impl KaniSession {
-    pub fn new(args: VerificationArgs) -> Result<Self> {
+    pub fn new(mut args: VerificationArgs) -> Result<Self> {
         init_logger(&args);
+        #[cfg(windows)]
+        crate::windows::kill_children_on_exit();
         let install = InstallType::new()?;
 
-        Ok(KaniSession {
+        if let Some(harness) = &args.isolated_harness {
+            args.harnesses = vec![harness.clone()];
+            args.exact = true;
+        }
+
+        let record_reachable_functions = args.affected_by.is_some();
+        let mut session = KaniSession {
             args,
             autoharness_compiler_flags: None,
+            autoharness_budget: None,
+            budget_start: OnceLock::new(),
+            default_harness_timeout: None,
+            skipped_by_budget: Mutex::new(vec![]),
+            isolation_command: None,
+            cbmc: PathBuf::from("cbmc"),
+            comparison: None,
+            backend_discrepancies: Mutex::new(vec![]),
+            record_reachable_functions,
+            print_type_definitions: false,
             kani_compiler: install.kani_compiler()?,
             kani_lib_c: install.kani_lib_c()?,
             temporaries: Mutex::new(vec![]),
+            compiler_errors: Mutex::new(vec![]),
+            type_definitions: Mutex::new(vec![]),
+            progress: RwLock::new(None),
             runtime: tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap(),
-        })
+        };
+        session.comparison = session.comparison_session().map(Box::new);
+        Ok(session)
+    }
+
+    /// Verify the harnesses that must be isolated by running `command` in a separate process.
+    pub fn enable_isolation(&mut self, command: IsolationCommand) {
+        // The process that verifies an isolated harness has to compile it, and there is nothing
+        // to verify if we only generate code.
+        if self.args.isolated_harness.is_none() && !self.args.only_codegen {
+            self.isolation_command = Some(command);
+        }
     }
 
     /// Record a temporary file so we can cleanup after ourselves at the end.
     /// Note that there will be no failure if the file does not exist.
+    /// Directories are only removed if they are empty after every temporary file was removed.
     pub fn record_temporary_file<T: AsRef<Path>>(&self, temp: &T) {
         self.record_temporary_files(&[temp])
     }
@@ -91,6 +177,11 @@ pub fn record_temporary_files<T: AsRef<Path>>(&self, temps: &[T]) {
         t.extend(temps.iter().map(|p| p.as_ref().to_owned()));
     }
 
+    /// The table that `--ui` draws, if it's currently drawn.
+    pub fn progress(&self) -> Option<Arc<ProgressView>> {
+        self.progress.read().unwrap().clone()
+    }
+
     /// Determine which symbols Kani should codegen (i.e. by slicing away symbols
     /// that are considered unreachable.)
     pub fn reachability_mode(&self) -> ReachabilityMode {
@@ -115,10 +206,17 @@ fn drop(&mut self) {
         if !self.args.keep_temps {
             let temporaries = self.temporaries.lock().unwrap();
 
-            for file in temporaries.iter() {
+            let (mut dirs, files): (Vec<_>, Vec<_>) =
+                temporaries.iter().partition(|path| path.is_dir());
+            for file in files {
                 // If it fails, we don't care, skip it
                 let _result = std::fs::remove_file(file);
             }
+            // Only remove the directories that are empty by now, innermost first.
+            dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+            for dir in dirs {
+                let _result = std::fs::remove_dir(dir);
+            }
         }
     }
 }
@@ -131,12 +229,12 @@ pub fn run_terminal(&self, cmd: Command) -> Result<()> {
 
     /// Call [run_terminal_timeout] with the verbosity configured by the user.
     /// The `bool` value indicates whether the command timed out
-    pub fn run_terminal_timeout(&self, cmd: TokioCommand) -> Result<bool> {
-        self.runtime.block_on(run_terminal_timeout(
-            &self.args.common_args,
-            cmd,
-            self.args.harness_timeout,
-        ))
+    pub fn run_terminal_timeout(
+        &self,
+        cmd: TokioCommand,
+        timeout: Option<Duration>,
+    ) -> Result<bool> {
+        self.runtime.block_on(run_terminal_timeout(&self.args.common_args, cmd, timeout))
     }
 
     /// Call [run_suppress] with the verbosity configured by the user.
@@ -198,7 +296,7 @@ pub fn run_terminal(verbosity: &impl Verbosity, mut cmd: Command) -> Result<()>
 async fn run_terminal_timeout(
     verbosity: &impl Verbosity,
     mut cmd: TokioCommand,
-    timeout: Option<Timeout>,
+    timeout: Option<Duration>,
 ) -> Result<bool> {
     if verbosity.quiet() {
         cmd.stdout(std::process::Stdio::null());
@@ -213,7 +311,7 @@ async fn run_terminal_timeout(
         || async {
             if let Some(timeout) = timeout {
                 let mut child = cmd.spawn().unwrap();
-                let res = tokio::time::timeout(timeout.into(), child.wait()).await;
+                let res = tokio::time::timeout(timeout, child.wait()).await;
                 if res.is_err() {
                     // Kill the process
                     child.kill().await.unwrap();
@@ -291,8 +389,32 @@ fn with_timer<T, F>(verbosity: &impl Verbosity, func: F, description: &str) -> T
     ret
 }
 
-/// Return the path for the folder where the current executable is located.
+/// Select the Kani installation used by this process.
+///
+/// This is only needed when Kani is embedded in another executable, since we cannot infer the
+/// installation location from the current executable in that case.
+pub fn set_install_dir(dir: &Path) -> Result<()> {
+    let dir = dir
+        .canonicalize()
+        .with_context(|| format!("Invalid Kani installation directory `{}`", dir.display()))?;
+    let current = INSTALL_DIR.get_or_init(|| dir.clone());
+    if *current != dir {
+        bail!(
+            "Kani installation directory was already set to `{}`, cannot change it to `{}`",
+            current.display(),
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+/// Return the path for the folder where the Kani binaries are located.
+/// This is the folder of the current executable, unless an installation directory was selected
+/// with [set_install_dir].
 fn bin_folder() -> Result<PathBuf> {
+    if let Some(install_dir) = INSTALL_DIR.get() {
+        return Ok(install_dir.join("bin"));
+    }
     let exe = std::env::current_exe().context("Cannot determine current executable location")?;
     let dir = exe.parent().context("Executable isn't in a directory")?.to_owned();
     Ok(dir)
@@ -355,11 +477,11 @@ pub fn kani_compiler(&self) -> Result<PathBuf> {
         match self {
             Self::DevRepo(_) => {
                 // Use bin_folder to hide debug/release differences.
-                let path = bin_folder()?.join("kani-compiler");
+                let path = bin_folder()?.join(executable_name("kani-compiler"));
                 expect_path(path)
             }
             Self::Release(release) => {
-                let path = release.join("bin/kani-compiler");
+                let path = release.join("bin").join(executable_name("kani-compiler"));
                 expect_path(path)
             }
         }
@@ -410,7 +532,9 @@ fn init_logger(args: &VerificationArgs) {
             .with_ansi(use_colors)
             .with_target(true),
     );
-    tracing::subscriber::set_global_default(subscriber).unwrap();
+    // This may fail if Kani is embedded in a tool that already installed its own subscriber, or if
+    // more than one session is created in the same process. Keep the existing one in that case.
+    let _ = tracing::subscriber::set_global_default(subscriber);
 }
 
 pub fn setup_cargo_command() -> Result<Command> {
@@ -460,7 +584,7 @@ pub fn setup_cargo_command_inner(profiling_out_path: Option<String>) -> Result<C
             }
         }
         InstallType::Release(kani_dir) => {
-            let cargo_path = kani_dir.join("toolchain").join("bin").join("cargo");
+            let cargo_path = kani_dir.join("toolchain").join("bin").join(executable_name("cargo"));
             Command::new(cargo_path)
         }
     };
@@ -476,7 +600,9 @@ pub fn get_cargo_path() -> Result<PathBuf> {
 
     let cargo_path = match install_type {
         InstallType::DevRepo(_) => env!("CARGO").into(),
-        InstallType::Release(kani_dir) => kani_dir.join("toolchain").join("bin").join("cargo"),
+        InstallType::Release(kani_dir) => {
+            kani_dir.join("toolchain").join("bin").join(executable_name("cargo"))
+        }
     };
 
     Ok(cargo_path)
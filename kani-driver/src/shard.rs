@@ -0,0 +1,104 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `--shard`, which splits the harnesses of a project across CI jobs. The results of
+//! every shard can be combined with the `merge-results` subcommand.
+//!
+//! Each job computes the same assignment of harnesses to shards independently, so the assignment
+//! must only depend on the names of the harnesses and on the timings file. Without timings, we
+//! hash the name of each harness, which keeps the assignment of a harness stable when other
+//! harnesses are added or removed. With timings, we assign the slowest harnesses first, each to the
+//! shard with the smallest total runtime so far.
+
+use anyhow::Result;
+use kani_metadata::HarnessMetadata;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::args::Shard;
+use crate::merge_results::read_results;
+
+/// Keep the harnesses that belong to `shard`.
+pub fn select_shard<'a>(
+    harnesses: Vec<&'a HarnessMetadata>,
+    shard: Shard,
+    timings: Option<&Path>,
+) -> Result<Vec<&'a HarnessMetadata>> {
+    let timings = timings
+        .map(|path| {
+            let results = read_results(path)?;
+            Ok::<_, anyhow::Error>(
+                results.harnesses.into_iter().map(|entry| (entry.harness, entry.runtime)).collect(),
+            )
+        })
+        .transpose()?;
+    let names: Vec<&str> = harnesses.iter().map(|harness| harness.pretty_name.as_str()).collect();
+    let assignment = assign_shards(&names, shard.count, timings.as_ref());
+    Ok(harnesses
+        .into_iter()
+        .zip(assignment)
+        .filter_map(|(harness, idx)| (idx + 1 == shard.index).then_some(harness))
+        .collect())
+}
+
+/// Return the 0-based shard of each harness in `names`.
+fn assign_shards(
+    names: &[&str],
+    count: usize,
+    timings: Option<&HashMap<String, f64>>,
+) -> Vec<usize> {
+    let Some(timings) = timings else {
+        return names.iter().map(|name| (stable_hash(name) % count as u64) as usize).collect();
+    };
+    // Harnesses without a timing, e.g., new ones, are estimated to take the average runtime.
+    let known: Vec<f64> = names.iter().filter_map(|name| timings.get(*name).copied()).collect();
+    let average =
+        if known.is_empty() { 1.0 } else { known.iter().sum::<f64>() / known.len() as f64 };
+    let runtime = |idx: usize| timings.get(names[idx]).copied().unwrap_or(average);
+
+    let mut order: Vec<usize> = (0..names.len()).collect();
+    order.sort_by(|a, b| runtime(*b).total_cmp(&runtime(*a)).then(names[*a].cmp(names[*b])));
+    let mut loads = vec![0.0; count];
+    let mut assignment = vec![0; names.len()];
+    for idx in order {
+        let shard = (0..count).min_by(|a, b| f64::total_cmp(&loads[*a], &loads[*b])).unwrap();
+        loads[shard] += runtime(idx);
+        assignment[idx] = shard;
+    }
+    assignment
+}
+
+/// The 64-bit FNV-1a hash of `name`, which unlike the hashers of the standard library is
+/// guaranteed to be the same in every Kani version.
+fn stable_hash(name: &str) -> u64 {
+    name.bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_hash_assignment_is_stable() {
+        let names = ["a::check_one", "a::check_two", "b::check_three"];
+        let assignment = assign_shards(&names, 4, None);
+        // Removing a harness doesn't move the others.
+        assert_eq!(assign_shards(&names[1..], 4, None), assignment[1..]);
+        assert!(assignment.iter().all(|shard| *shard < 4));
+        assert_eq!(stable_hash(""), 0xcbf29ce484222325);
+        assert_eq!(stable_hash("a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn check_timing_assignment_is_balanced() {
+        let names = ["slow", "fast1", "fast2", "fast3", "new"];
+        let timings = HashMap::from([
+            ("slow".to_string(), 9.0),
+            ("fast1".to_string(), 3.0),
+            ("fast2".to_string(), 3.0),
+            ("fast3".to_string(), 1.0),
+        ]);
+        // `new` is estimated to take 4 seconds, so it runs after `slow` and before the others.
+        assert_eq!(assign_shards(&names, 2, Some(&timings)), vec![0, 1, 1, 0, 1]);
+    }
+}
@@ -0,0 +1,286 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Verify the harnesses annotated with `#[kani::proof(split_on = "input")]` one case at a time.
+//!
+//! The compiler lists the variants of the enum type of the input in the metadata of the harness,
+//! and constrains the input to the variant whose index is the value of [SPLIT_CASE_VAR], which the
+//! model declares without defining it. For each variant, we link the model with a definition of
+//! the variable and run CBMC on the result. Each run only explores the executions of its variant,
+//! and the runs are independent, so they run in parallel. We then merge their results into the
+//! result of the harness.
+
+use anyhow::Result;
+use kani_metadata::{HarnessMetadata, SPLIT_CASE_VAR};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::call_cbmc::{
+    VerificationResult, VerificationStatus, coverage_results_from_properties,
+    verification_outcome_from_properties,
+};
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::project::Project;
+use crate::session::KaniSession;
+use crate::statistics::CbmcStatistics;
+use crate::util::alter_extension;
+
+impl KaniSession {
+    /// Verify each case of the split harness whose model is `goto_file`, and report the merged
+    /// result of the harness together with the result of each case.
+    pub(crate) fn check_split_harness(
+        &self,
+        project: &Project,
+        harness: &HarnessMetadata,
+        goto_file: &Path,
+    ) -> Result<VerificationResult> {
+        let thread_index = rayon::current_thread_index().unwrap_or_default();
+        self.announce_harness(harness, thread_index);
+
+        let start_time = Instant::now();
+        let results = (0..harness.split_cases.len())
+            .into_par_iter()
+            .map(|case| {
+                let definition = alter_extension(goto_file, &format!("case{case}.c"));
+                let case_file = alter_extension(goto_file, &format!("case{case}.out"));
+                self.record_temporary_files(&[&definition, &case_file]);
+                std::fs::write(&definition, format!("unsigned int {SPLIT_CASE_VAR} = {case};\n"))?;
+                self.link_definitions(goto_file, &definition, &case_file)?;
+                self.instrument_model(&case_file, &case_file, project, harness)?;
+                self.with_timer(|| self.run_cbmc(&case_file, harness), "run_cbmc")
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut result = merge_case_results(
+            &results,
+            harness.attributes.should_panic,
+            harness.attributes.expected_panic.as_deref(),
+        );
+        result.runtime = start_time.elapsed();
+
+        self.process_output(&result, harness, Some(case_summary(harness, &results)), thread_index);
+        self.gen_and_add_concrete_playback(harness, &mut result)?;
+        Ok(result)
+    }
+}
+
+/// The status of a property over every case, where `first` and `second` are its statuses over
+/// some of the cases. A property fails if it fails in any case, and a cover property is satisfied
+/// if any case satisfies it. Cases that don't reach the property don't affect its status.
+fn merge_case_status(first: CheckStatus, second: CheckStatus) -> CheckStatus {
+    use CheckStatus::*;
+    match (first, second) {
+        (Failure, _) | (_, Failure) => Failure,
+        (Satisfied, _) | (_, Satisfied) => Satisfied,
+        (Covered, _) | (_, Covered) => Covered,
+        (Undetermined | Unknown, _) | (_, Undetermined | Unknown) => Undetermined,
+        (Unreachable, status) | (status, Unreachable) => status,
+        (status, _) => status,
+    }
+}
+
+/// Merge the results of the cases of a harness into the result of the harness, as if a single
+/// run of CBMC had verified every case. The runtime of the result is left to the caller.
+fn merge_case_results(
+    results: &[VerificationResult],
+    should_panic: bool,
+    expected_panic: Option<&str>,
+) -> VerificationResult {
+    let statistics = results
+        .iter()
+        .fold(CbmcStatistics::default(), |statistics, result| statistics.merge(&result.statistics));
+    // The harness fails the same way as the first case whose verification didn't complete.
+    if let Some(incomplete) = results.iter().find(|result| result.results.is_err()) {
+        return VerificationResult {
+            status: VerificationStatus::Failure,
+            failed_properties: incomplete.failed_properties,
+            results: Err(*incomplete.results.as_ref().unwrap_err()),
+            runtime: Duration::ZERO,
+            generated_concrete_test: false,
+            coverage_results: None,
+            statistics,
+        };
+    }
+
+    let mut properties: Vec<Property> = vec![];
+    let mut indices: HashMap<String, usize> = HashMap::new();
+    for property in results.iter().flat_map(|result| result.results.as_ref().unwrap()) {
+        match indices.entry(property.property_name()) {
+            Entry::Vacant(entry) => {
+                entry.insert(properties.len());
+                properties.push(property.clone());
+            }
+            Entry::Occupied(entry) => {
+                let merged = &mut properties[*entry.get()];
+                let status = merge_case_status(merged.status, property.status);
+                // Keep the trace of the case that determines the status, e.g., the case that
+                // violates the property.
+                if status != merged.status {
+                    *merged = Property { status, ..property.clone() };
+                }
+            }
+        }
+    }
+
+    let (status, failed_properties) =
+        verification_outcome_from_properties(&properties, should_panic, expected_panic);
+    let vacuous = results.iter().all(|result| result.status == VerificationStatus::Vacuous);
+    let status = if vacuous && status == VerificationStatus::Success {
+        VerificationStatus::Vacuous
+    } else {
+        status
+    };
+    let coverage_results = coverage_results_from_properties(&properties);
+    VerificationResult {
+        status,
+        failed_properties,
+        results: Ok(properties),
+        runtime: Duration::ZERO,
+        generated_concrete_test: false,
+        coverage_results,
+        statistics,
+    }
+}
+
+/// The status of each case of a split harness, e.g.:
+/// ```text
+/// Cases of `op`:
+///  - Op::Add: SUCCESSFUL
+///  - Op::Div: FAILED
+/// ```
+fn case_summary(harness: &HarnessMetadata, results: &[VerificationResult]) -> String {
+    let input = harness.attributes.split_on.as_deref().unwrap_or_default();
+    let mut summary = format!("Cases of `{input}`:");
+    for (case, result) in harness.split_cases.iter().zip(results) {
+        let status = match result.status {
+            VerificationStatus::Success => "SUCCESSFUL",
+            VerificationStatus::Failure => "FAILED",
+            VerificationStatus::Vacuous => "VACUOUS",
+        };
+        write!(summary, "\n - {case}: {status}").unwrap();
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::ExitStatus;
+    use crate::cbmc_output_parser::{PropertyId, SourceLocation};
+    use crate::metadata::tests::mock_proof_harness;
+    use CheckStatus::*;
+
+    fn property(name: &str, status: CheckStatus, line: &str) -> Property {
+        let (class, id) = name.split_once('.').unwrap();
+        Property {
+            description: format!("{class} {id}"),
+            property_id: PropertyId {
+                fn_name: None,
+                class: class.to_string(),
+                id: id.parse().unwrap(),
+            },
+            source_location: SourceLocation {
+                column: None,
+                file: None,
+                function: None,
+                line: Some(line.to_string()),
+            },
+            status,
+            reach: None,
+            trace: None,
+            cover_label: None,
+        }
+    }
+
+    fn result(properties: Vec<Property>) -> VerificationResult {
+        let (status, failed_properties) =
+            verification_outcome_from_properties(&properties, false, None);
+        VerificationResult {
+            status,
+            failed_properties,
+            results: Ok(properties),
+            ..VerificationResult::mock_success()
+        }
+    }
+
+    fn statuses(result: &VerificationResult) -> Vec<(String, CheckStatus)> {
+        let properties = result.results.as_ref().unwrap();
+        properties.iter().map(|prop| (prop.property_name(), prop.status)).collect()
+    }
+
+    #[test]
+    fn check_merge_case_status() {
+        assert_eq!(merge_case_status(Success, Failure), Failure);
+        assert_eq!(merge_case_status(Unreachable, Failure), Failure);
+        assert_eq!(merge_case_status(Unsatisfiable, Satisfied), Satisfied);
+        assert_eq!(merge_case_status(Success, Undetermined), Undetermined);
+        assert_eq!(merge_case_status(Unreachable, Success), Success);
+        assert_eq!(merge_case_status(Unsatisfiable, Unreachable), Unsatisfiable);
+        assert_eq!(merge_case_status(Unreachable, Unreachable), Unreachable);
+    }
+
+    #[test]
+    fn check_merge_case_results() {
+        let results = [
+            result(vec![
+                property("assertion.1", Success, "1"),
+                property("assertion.2", Unreachable, "2"),
+                property("cover.1", Unsatisfiable, "3"),
+            ]),
+            result(vec![
+                property("assertion.1", Success, "1"),
+                property("assertion.2", Failure, "20"),
+                property("cover.1", Satisfied, "30"),
+            ]),
+            result(vec![
+                property("assertion.1", Unreachable, "1"),
+                property("assertion.2", Success, "2"),
+                property("cover.1", Unreachable, "3"),
+            ]),
+        ];
+        let merged = merge_case_results(&results, false, None);
+        assert_eq!(merged.status, VerificationStatus::Failure);
+        assert_eq!(
+            statuses(&merged),
+            [
+                ("assertion.1".to_string(), Success),
+                ("assertion.2".to_string(), Failure),
+                ("cover.1".to_string(), Satisfied)
+            ]
+        );
+        // The properties come from the cases that determine their status.
+        let properties = merged.results.unwrap();
+        assert_eq!(properties[1].source_location.line.as_deref(), Some("20"));
+        assert_eq!(properties[2].source_location.line.as_deref(), Some("30"));
+    }
+
+    #[test]
+    fn check_merge_incomplete_case() {
+        let timeout = VerificationResult {
+            status: VerificationStatus::Failure,
+            results: Err(ExitStatus::Timeout),
+            ..VerificationResult::mock_success()
+        };
+        let results = [result(vec![property("assertion.1", Success, "1")]), timeout];
+        let merged = merge_case_results(&results, false, None);
+        assert_eq!(merged.status, VerificationStatus::Failure);
+        assert!(matches!(merged.results, Err(ExitStatus::Timeout)));
+    }
+
+    #[test]
+    fn check_case_summary() {
+        let mut harness = mock_proof_harness("check_op", None, None, None);
+        harness.attributes.split_on = Some("op".to_string());
+        harness.split_cases = vec!["Op::Add".to_string(), "Op::Div".to_string()];
+        let results = [
+            result(vec![property("assertion.1", Success, "1")]),
+            result(vec![property("assertion.1", Failure, "1")]),
+        ];
+        assert_eq!(
+            case_summary(&harness, &results),
+            "Cases of `op`:\n - Op::Add: SUCCESSFUL\n - Op::Div: FAILED"
+        );
+    }
+}
@@ -0,0 +1,373 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `--statistics`, which records the cost of verifying each harness.
+//!
+//! Most of the statistics come from the status messages that CBMC prints with `--verbosity 9`.
+//! CBMC doesn't report how much memory it used, so Kani samples the peak resident set size of
+//! the CBMC process while it runs. This is only supported on Linux.
+
+use anyhow::{Context, Result};
+use comfy_table::Table as PrettyTable;
+use regex::Regex;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use crate::args::VerificationArgs;
+use crate::call_cbmc::resolve_array_encoding;
+use crate::cbmc_output_parser::{CheckStatus, ParserItem};
+use crate::harness_runner::HarnessResult;
+
+/// How often Kani samples the memory usage of CBMC.
+const MEMORY_SAMPLING_PERIOD: Duration = Duration::from_millis(10);
+
+/// The statistics that CBMC reports about the verification of a harness.
+///
+/// Statistics that were not reported, e.g., the number of clauses when using an SMT solver, are
+/// `None`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct CbmcStatistics {
+    /// The number of verification conditions generated by symbolic execution.
+    pub vccs: Option<u64>,
+    /// The number of verification conditions left after simplification.
+    pub remaining_vccs: Option<u64>,
+    /// The number of variables of the SAT instance with the most clauses.
+    pub sat_variables: Option<u64>,
+    /// The number of clauses of the SAT instance with the most clauses.
+    pub sat_clauses: Option<u64>,
+    /// The time spent in symbolic execution, in seconds.
+    pub symex_time: Option<f64>,
+    /// The time spent in the solver, in seconds.
+    pub solver_time: Option<f64>,
+    /// The peak resident set size of CBMC, in bytes.
+    pub peak_memory: Option<u64>,
+}
+
+impl CbmcStatistics {
+    /// Collect the statistics from the status messages of CBMC.
+    ///
+    /// CBMC solves the formula more than once when checking cover properties, so we add up the
+    /// times of every run, and keep the size of the instance with the most clauses.
+    ///
+    /// Messages whose numbers don't fit the statistics are ignored, since they only affect what
+    /// `--statistics` reports, not the verification result.
+    pub fn from_messages(items: &[ParserItem]) -> CbmcStatistics {
+        static VCCS: OnceLock<Regex> = OnceLock::new();
+        static SAT_SIZE: OnceLock<Regex> = OnceLock::new();
+        static RUNTIME: OnceLock<Regex> = OnceLock::new();
+        let vccs = VCCS.get_or_init(|| {
+            Regex::new(r"^Generated (\d+) VCC\(s\), (\d+) remaining after simplification").unwrap()
+        });
+        let sat_size =
+            SAT_SIZE.get_or_init(|| Regex::new(r"^(\d+) variables, (\d+) clauses").unwrap());
+        let runtime =
+            RUNTIME.get_or_init(|| Regex::new(r"^Runtime (Symex|Solver): ([0-9.eE+-]+)s").unwrap());
+
+        let mut stats = CbmcStatistics::default();
+        let messages = items.iter().filter_map(|item| match item {
+            ParserItem::Message { message_text, .. } => Some(message_text.as_str()),
+            _ => None,
+        });
+        for message in messages {
+            if let Some(captures) = vccs.captures(message) {
+                if let (Ok(generated), Ok(remaining)) = (captures[1].parse(), captures[2].parse()) {
+                    add(&mut stats.vccs, generated);
+                    add(&mut stats.remaining_vccs, remaining);
+                } else {
+                    debug!(?message, "ignoring the number of VCCs that doesn't fit in u64");
+                }
+            } else if let Some(captures) = sat_size.captures(message) {
+                if let (Ok(variables), Ok(clauses)) = (captures[1].parse(), captures[2].parse()) {
+                    stats.record_sat_instance(variables, clauses);
+                } else {
+                    debug!(?message, "ignoring the size of a SAT instance that doesn't fit in u64");
+                }
+            } else if let Some(captures) = runtime.captures(message)
+                && let Ok(time) = captures[2].parse()
+            {
+                match &captures[1] {
+                    "Symex" => add(&mut stats.symex_time, time),
+                    _ => add(&mut stats.solver_time, time),
+                }
+            }
+        }
+        stats
+    }
+
+    /// Combine the statistics of two runs of CBMC that verify different cases of the same
+    /// harness, in the same way as the statistics of the solver runs of one invocation.
+    pub fn merge(mut self, other: &CbmcStatistics) -> CbmcStatistics {
+        for (stat, value) in
+            [(&mut self.vccs, other.vccs), (&mut self.remaining_vccs, other.remaining_vccs)]
+        {
+            if let Some(value) = value {
+                add(stat, value);
+            }
+        }
+        if let (Some(variables), Some(clauses)) = (other.sat_variables, other.sat_clauses) {
+            self.record_sat_instance(variables, clauses);
+        }
+        if let Some(peak_memory) = other.peak_memory {
+            max(&mut self.peak_memory, peak_memory);
+        }
+        for (stat, value) in
+            [(&mut self.symex_time, other.symex_time), (&mut self.solver_time, other.solver_time)]
+        {
+            if let Some(value) = value {
+                add(stat, value);
+            }
+        }
+        self
+    }
+
+    /// Keep the size of the given SAT instance if it has more clauses than the one we have.
+    fn record_sat_instance(&mut self, variables: u64, clauses: u64) {
+        if self.sat_clauses.is_none_or(|largest| clauses > largest) {
+            self.sat_variables = Some(variables);
+            self.sat_clauses = Some(clauses);
+        }
+    }
+}
+
+fn add<T: std::ops::Add<Output = T> + Copy>(stat: &mut Option<T>, value: T) {
+    *stat = Some(stat.map_or(value, |old| old + value));
+}
+
+fn max(stat: &mut Option<u64>, value: u64) {
+    *stat = Some(stat.map_or(value, |old| old.max(value)));
+}
+
+/// Samples the peak memory usage of a process until it is stopped.
+pub struct PeakMemory {
+    peak: Arc<AtomicU64>,
+    sampler: JoinHandle<()>,
+}
+
+impl PeakMemory {
+    /// Start sampling the memory usage of the process `pid`.
+    ///
+    /// Must be called from a tokio runtime. Note that the sampling only makes progress while the
+    /// runtime is waiting on the process.
+    pub fn track(pid: u32) -> PeakMemory {
+        let peak = Arc::new(AtomicU64::new(0));
+        let sampler = tokio::spawn({
+            let peak = peak.clone();
+            async move {
+                let status = format!("/proc/{pid}/status");
+                while let Ok(content) = std::fs::read_to_string(&status) {
+                    if let Some(hwm) = parse_vm_hwm(&content) {
+                        peak.fetch_max(hwm, Ordering::Relaxed);
+                    }
+                    tokio::time::sleep(MEMORY_SAMPLING_PERIOD).await;
+                }
+            }
+        });
+        PeakMemory { peak, sampler }
+    }
+
+    /// Stop sampling, and return the largest memory usage observed, in bytes.
+    pub fn stop(self) -> Option<u64> {
+        self.sampler.abort();
+        let peak = self.peak.load(Ordering::Relaxed);
+        (peak > 0).then_some(peak)
+    }
+}
+
+/// Parse the peak resident set size from the content of `/proc/<pid>/status`.
+fn parse_vm_hwm(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib: u64 =
+        line.trim_start_matches("VmHWM:").trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// The statistics of one harness, as written to the JSON file.
+#[derive(Serialize)]
+struct HarnessStatistics<'a> {
+    harness: &'a str,
+    status: String,
+    /// The time it took to verify the harness, in seconds.
+    runtime: f64,
+    /// When the statistics were written, which `merge-results` uses to pick the latest result of a
+    /// harness.
+    timestamp: &'a str,
+    /// The number of elements of the largest array of the harness.
+    largest_array: Option<u64>,
+    /// How CBMC encoded the arrays of the harness, if it wasn't CBMC's default encoding.
+    array_encoding: Option<String>,
+    /// The number of dynamic calls that `-Z devirtualize` replaced by direct calls.
+    devirtualized_calls: Option<u64>,
+    #[serde(flatten)]
+    cbmc: &'a CbmcStatistics,
+    properties: Vec<PropertyStatistics<'a>>,
+}
+
+#[derive(Serialize)]
+struct PropertyStatistics<'a> {
+    property: String,
+    description: &'a str,
+    status: CheckStatus,
+}
+
+/// Write the statistics of every harness to `path` as JSON.
+pub(crate) fn write_statistics(
+    args: &VerificationArgs,
+    path: &Path,
+    results: &[HarnessResult<'_>],
+) -> Result<()> {
+    let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let harnesses: Vec<_> = results
+        .iter()
+        .map(|result| HarnessStatistics {
+            harness: &result.harness.pretty_name,
+            status: result.result.status.to_string(),
+            runtime: result.result.runtime.as_secs_f64(),
+            timestamp: &timestamp,
+            largest_array: result.harness.largest_array,
+            array_encoding: resolve_array_encoding(args, result.harness)
+                .map(|encoding| encoding.to_string()),
+            devirtualized_calls: result.harness.devirtualized_calls,
+            cbmc: &result.result.statistics,
+            properties: result
+                .result
+                .results
+                .as_ref()
+                .map(|properties| {
+                    properties
+                        .iter()
+                        .map(|property| PropertyStatistics {
+                            property: property.property_name(),
+                            description: &property.description,
+                            status: property.status,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+        .collect();
+    let json = serde_json::json!({ "harnesses": harnesses });
+    std::fs::write(path, serde_json::to_string_pretty(&json)?)
+        .with_context(|| format!("Failed to write statistics to `{}`", path.display()))
+}
+
+/// Print a table with the statistics of every harness.
+pub(crate) fn print_statistics_table(args: &VerificationArgs, results: &[HarnessResult<'_>]) {
+    let mut table = PrettyTable::new();
+    table.set_header(vec![
+        "Harness",
+        "VCCs",
+        "SAT Variables",
+        "SAT Clauses",
+        "Symex Time (s)",
+        "Solver Time (s)",
+        "Peak Memory (MiB)",
+        "Largest Array",
+        "Array Encoding",
+        "Devirtualized Calls",
+        "Total Time (s)",
+    ]);
+    let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    for result in results {
+        let stats = &result.result.statistics;
+        table.add_row(vec![
+            result.harness.pretty_name.clone(),
+            or_dash(stats.vccs.map(|vccs| vccs.to_string())),
+            or_dash(stats.sat_variables.map(|vars| vars.to_string())),
+            or_dash(stats.sat_clauses.map(|clauses| clauses.to_string())),
+            or_dash(stats.symex_time.map(|time| format!("{time:.3}"))),
+            or_dash(stats.solver_time.map(|time| format!("{time:.3}"))),
+            or_dash(stats.peak_memory.map(|bytes| format!("{:.1}", bytes as f64 / 1048576.0))),
+            or_dash(result.harness.largest_array.map(|len| len.to_string())),
+            or_dash(
+                resolve_array_encoding(args, result.harness).map(|encoding| encoding.to_string()),
+            ),
+            or_dash(result.harness.devirtualized_calls.map(|calls| calls.to_string())),
+            format!("{:.3}", result.result.runtime.as_secs_f64()),
+        ]);
+    }
+    println!("\nVerification Statistics:");
+    println!("{table}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(text: &str) -> ParserItem {
+        ParserItem::Message {
+            message_text: text.to_string(),
+            message_type: "STATUS-MESSAGE".to_string(),
+        }
+    }
+
+    #[test]
+    fn check_statistics_from_messages() {
+        let items = [
+            message("Runtime Symex: 0.25s"),
+            message("Generated 12 VCC(s), 4 remaining after simplification"),
+            message("Solving with CaDiCaL sc2021"),
+            message("1449 variables, 1727 clauses"),
+            message("Runtime Solver: 2.5e-1s"),
+            message("200 variables, 3000 clauses"),
+            message("Runtime Solver: 0.5s"),
+            message("Runtime decision procedure: 0.6s"),
+        ];
+        let stats = CbmcStatistics::from_messages(&items);
+        assert_eq!(
+            stats,
+            CbmcStatistics {
+                vccs: Some(12),
+                remaining_vccs: Some(4),
+                sat_variables: Some(200),
+                sat_clauses: Some(3000),
+                symex_time: Some(0.25),
+                solver_time: Some(0.75),
+                peak_memory: None,
+            }
+        );
+        assert_eq!(CbmcStatistics::from_messages(&[]), CbmcStatistics::default());
+    }
+
+    #[test]
+    fn check_statistics_ignore_overflowing_numbers() {
+        let items = [
+            message("Generated 99999999999999999999 VCC(s), 4 remaining after simplification"),
+            message("1449 variables, 99999999999999999999 clauses"),
+            message("1449 variables, 1727 clauses"),
+        ];
+        let stats = CbmcStatistics::from_messages(&items);
+        assert_eq!(stats.vccs, None);
+        assert_eq!(stats.remaining_vccs, None);
+        assert_eq!((stats.sat_variables, stats.sat_clauses), (Some(1449), Some(1727)));
+    }
+
+    #[test]
+    fn check_merge_keeps_instance_with_most_clauses() {
+        let small = CbmcStatistics {
+            sat_variables: Some(5000),
+            sat_clauses: Some(100),
+            ..Default::default()
+        };
+        let large = CbmcStatistics {
+            sat_variables: Some(10),
+            sat_clauses: Some(200),
+            ..Default::default()
+        };
+        let merged = small.clone().merge(&large);
+        assert_eq!((merged.sat_variables, merged.sat_clauses), (Some(10), Some(200)));
+        let merged = large.merge(&small);
+        assert_eq!((merged.sat_variables, merged.sat_clauses), (Some(10), Some(200)));
+    }
+
+    #[test]
+    fn check_parse_vm_hwm() {
+        let status = "Name:\tcbmc\nVmPeak:\t  20000 kB\nVmHWM:\t    1024 kB\nVmRSS:\t 512 kB\n";
+        assert_eq!(parse_vm_hwm(status), Some(1024 * 1024));
+        assert_eq!(parse_vm_hwm("Name:\tcbmc\n"), None);
+    }
+}
@@ -38,16 +38,36 @@ pub fn executable_basename(argv0: &Option<&OsString>) -> Option<OsString> {
     None
 }
 
+/// The file name of the executable `name` on the host, e.g., `kani-compiler.exe` on Windows.
+pub fn executable_name(name: &str) -> String {
+    format!("{name}{}", std::env::consts::EXE_SUFFIX)
+}
+
 /// Render a Command as a string, to log it (e.g. in dry runs)
+///
+/// The command is quoted for the shell of the host, i.e., `cmd` on Windows and a POSIX shell
+/// elsewhere, so that users can copy it.
 pub fn render_command(cmd: &Command) -> OsString {
+    render_command_for(cmd, cfg!(windows))
+}
+
+fn render_command_for(cmd: &Command, windows: bool) -> OsString {
     let mut str = OsString::new();
 
     for (k, v) in cmd.get_envs() {
         if let Some(v) = v {
-            str.push(k);
-            str.push("=\"");
-            str.push(v);
-            str.push("\" ");
+            if windows {
+                str.push("set \"");
+                str.push(k);
+                str.push("=");
+                str.push(v);
+                str.push("\" && ");
+            } else {
+                str.push(k);
+                str.push("=\"");
+                str.push(v);
+                str.push("\" ");
+            }
         }
     }
 
@@ -55,7 +75,9 @@ pub fn render_command(cmd: &Command) -> OsString {
 
     for a in cmd.get_args() {
         str.push(" ");
-        if a.to_string_lossy().contains(' ') {
+        if windows {
+            str.push(quote_cmd_arg(&a.to_string_lossy()));
+        } else if a.to_string_lossy().contains(' ') {
             str.push("\"");
             str.push(a);
             str.push("\"");
@@ -67,6 +89,27 @@ pub fn render_command(cmd: &Command) -> OsString {
     str
 }
 
+/// Quote an argument the way Windows programs split their command line, if it's needed, so that
+/// `cmd` passes it as is. Backslashes are only escaped when they precede a quote.
+fn quote_cmd_arg(arg: &str) -> String {
+    const SPECIAL: [char; 10] = [' ', '\t', '"', '&', '|', '<', '>', '^', '(', ')'];
+    if !arg.is_empty() && !arg.contains(SPECIAL) {
+        return arg.to_string();
+    }
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        if c == '"' {
+            quoted.push_str(&"\\".repeat(backslashes + 1));
+        }
+        backslashes = if c == '\\' { backslashes + 1 } else { 0 };
+        quoted.push(c);
+    }
+    quoted.push_str(&"\\".repeat(backslashes));
+    quoted.push('"');
+    quoted
+}
+
 /// Print a warning message. This will add a "warning:" tag before the message and style accordingly.
 pub fn warning(msg: &str) {
     let warning = console::style("warning:").bold().yellow();
@@ -272,10 +315,23 @@ fn check_exe_basename() {
     fn check_render_command() {
         let mut c1 = Command::new("a");
         c1.arg("b");
-        assert_eq!(render_command(&c1), OsString::from("a b"));
+        assert_eq!(render_command_for(&c1, false), OsString::from("a b"));
         c1.arg("/c d/");
-        assert_eq!(render_command(&c1), OsString::from("a b \"/c d/\""));
+        assert_eq!(render_command_for(&c1, false), OsString::from("a b \"/c d/\""));
         c1.env("PARAM", "VALUE");
-        assert_eq!(render_command(&c1), OsString::from("PARAM=\"VALUE\" a b \"/c d/\""));
+        assert_eq!(render_command_for(&c1, false), OsString::from("PARAM=\"VALUE\" a b \"/c d/\""));
+    }
+
+    #[test]
+    fn check_render_command_windows() {
+        let mut c1 = Command::new("a");
+        c1.args(["b", r"C:\Program Files\", r#"say "hi""#, "x&y", ""]);
+        c1.env("PARAM", "VALUE");
+        assert_eq!(
+            render_command_for(&c1, true),
+            OsString::from(
+                r#"set "PARAM=VALUE" && a b "C:\Program Files\\" "say \"hi\"" "x&y" """#
+            )
+        );
     }
 }
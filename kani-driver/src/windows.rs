@@ -0,0 +1,47 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Process management that is specific to Windows.
+//!
+//! On Unix, CTRL-C interrupts every process of the foreground process group, which includes the
+//! CBMC and solver processes that Kani starts. Windows has no such group that outlives Kani, so
+//! the children of a Kani process that is interrupted, or that crashes, would keep running. Kani
+//! avoids that by running in a job object that kills all of its processes when Kani exits.
+
+use std::sync::Once;
+use tracing::debug;
+use windows_sys::Win32::Foundation::CloseHandle;
+use windows_sys::Win32::System::JobObjects::{
+    AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+    JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JobObjectExtendedLimitInformation,
+    SetInformationJobObject,
+};
+use windows_sys::Win32::System::Threading::GetCurrentProcess;
+
+/// Make sure that the processes that Kani starts are terminated when Kani exits, e.g., when the
+/// user presses CTRL-C. Kani still runs if this fails, e.g., if it's in a job that forbids it.
+pub fn kill_children_on_exit() {
+    static JOB: Once = Once::new();
+    JOB.call_once(|| {
+        // SAFETY: The job handle is checked before it's used. It's intentionally never closed
+        // once the process is assigned to the job, since closing it kills the job's processes.
+        unsafe {
+            let job = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+            if job.is_null() {
+                debug!("kill_children_on_exit: failed to create job object");
+                return;
+            }
+            let mut info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+            info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+            let configured = SetInformationJobObject(
+                job,
+                JobObjectExtendedLimitInformation,
+                &info as *const _ as *const std::ffi::c_void,
+                size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+            ) != 0;
+            if !configured || AssignProcessToJobObject(job, GetCurrentProcess()) == 0 {
+                debug!("kill_children_on_exit: failed to assign Kani to the job object");
+                CloseHandle(job);
+            }
+        }
+    });
+}
@@ -25,6 +25,8 @@ pub enum ArtifactType {
     /// A `json` file that stores the name to prettyName mapping for symbols
     /// (used to demangle names from the C dump).
     PrettyNameMap,
+    /// The MIR of the functions of a harness after Kani's transformations, as verified.
+    Mir,
 }
 
 impl ArtifactType {
@@ -37,6 +39,7 @@ const fn extension(&self) -> &'static str {
             ArtifactType::TypeMap => "type_map.json",
             ArtifactType::VTableRestriction => "restrictions.json",
             ArtifactType::PrettyNameMap => "pretty_name_map.json",
+            ArtifactType::Mir => "kani.mir",
         }
     }
 }
@@ -64,7 +67,8 @@ pub fn convert_type(path: &Path, from: ArtifactType, to: ArtifactType) -> PathBu
         | ArtifactType::SymTabGoto
         | ArtifactType::TypeMap
         | ArtifactType::VTableRestriction
-        | ArtifactType::PrettyNameMap => {
+        | ArtifactType::PrettyNameMap
+        | ArtifactType::Mir => {
             result.set_extension("");
             result.set_extension(to);
         }
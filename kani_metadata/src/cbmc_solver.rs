@@ -6,7 +6,7 @@
 
 /// An enum for CBMC solver options. All variants are handled by Kani, except for
 /// the `Binary` one, which it passes as is to CBMC's `--external-sat-solver`
-/// option.
+/// option, and the `Smt` one, which Kani runs on the SMT formula generated by CBMC.
 #[derive(Debug, Clone, AsRefStr, EnumString, VariantNames, PartialEq, Eq, Serialize, Deserialize)]
 #[strum(serialize_all = "snake_case")]
 pub enum CbmcSolver {
@@ -32,4 +32,9 @@ pub enum CbmcSolver {
     /// `--external-sat-solver`. The specified binary must exist in path.
     #[strum(disabled, serialize = "bin=<SAT_SOLVER_BINARY>")]
     Binary(String),
+
+    /// An SMT solver binary that Kani runs on the SMT-LIB formula that CBMC
+    /// writes with `--smt2 --outfile`. The specified binary must exist in path.
+    #[strum(disabled, serialize = "smt=<SMT_SOLVER_BINARY>")]
+    Smt(String),
 }
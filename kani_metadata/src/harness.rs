@@ -1,10 +1,16 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use crate::CbmcSolver;
+use crate::{CbmcSolver, UnsupportedFeature};
 use serde::{Deserialize, Serialize};
-use std::{borrow::Borrow, collections::BTreeSet, path::PathBuf};
-use strum_macros::Display;
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+use strum_macros::{Display, EnumIter, EnumString, VariantNames};
 use tracing::{debug, trace};
 
 /// A CBMC-level `assigns` contract that needs to be enforced on a function.
@@ -17,6 +23,77 @@ pub struct AssignsContract {
     pub recursion_tracker: Option<String>,
 }
 
+/// Function contracts that CBMC should check and replace for a harness. These contracts are
+/// attached to the functions in the goto program instead of being encoded by Kani.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CbmcContracts {
+    /// The function whose contract should be checked, if any.
+    pub enforce: Option<String>,
+    /// Whether the function whose contract is checked is recursive.
+    pub recursive: bool,
+    /// The functions whose calls should be replaced by their contract.
+    pub replace: Vec<String>,
+}
+
+/// A function that is reachable from a harness, and the lines that define it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ReachableFunction {
+    /// The canonical path of the file that defines the function.
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// The name of the function, without its generic arguments.
+    pub name: String,
+}
+
+/// A foreign function without a Rust body, which Kani does not model, that is reachable from a harness.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ForeignFunction {
+    /// The name of the function.
+    pub name: String,
+    /// The signature of the function, as declared in Rust.
+    pub signature: String,
+    /// The number of calls to the function in the code that is reachable from the harness,
+    /// not counting calls through function pointers.
+    pub call_sites: usize,
+    /// How Kani encodes the calls to the function.
+    pub encoding: ForeignFunctionEncoding,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ForeignFunctionEncoding {
+    /// The calls are replaced by a check that fails if it is reachable.
+    Unsupported,
+    /// The declaration is trusted (`-Z c-ffi`), so the calls use the C definition that is linked
+    /// with the harness if there is one, or are handled as calls to an undefined function otherwise.
+    Declared,
+}
+
+/// An unsafe operation in the code that is reachable from a harness.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnsafeOperation {
+    /// The function that performs the operation.
+    pub function: String,
+    /// The location of the operation, as `file:line:column`.
+    pub location: String,
+    /// What kind of unsafe operation this is.
+    pub kind: UnsafeOperationKind,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Display)]
+pub enum UnsafeOperationKind {
+    #[strum(serialize = "raw pointer dereference")]
+    RawPointerDeref,
+    #[strum(serialize = "union field access")]
+    UnionAccess,
+    #[strum(serialize = "transmute")]
+    Transmute,
+    #[strum(serialize = "unsafe function call")]
+    UnsafeCall,
+    #[strum(serialize = "inline assembly")]
+    InlineAsm,
+}
+
 /// We emit this structure for each annotated proof harness (`#[kani::proof]`) we find.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct HarnessMetadata {
@@ -32,18 +109,57 @@ pub struct HarnessMetadata {
     pub original_start_line: usize,
     /// The line in that file where the proof harness ends.
     pub original_end_line: usize,
+    /// The documentation comments of the harness.
+    pub docs: String,
     /// Optional modeling file that was generated by the compiler that includes this harness.
     pub goto_file: Option<PathBuf>,
+    /// The model that the harness shares with the other harnesses of its codegen unit, which the
+    /// driver links into `goto_file` instead of a model of its own (`-Z shared-model`).
+    pub shared_model: Option<PathBuf>,
     /// The `#[kani::<>]` attributes added to a harness.
     pub attributes: HarnessAttributes,
     /// A CBMC-level assigns contract that should be enforced when running this harness.
     pub contract: Option<AssignsContract>,
+    /// Contracts that should be checked and replaced by CBMC when running this harness.
+    pub cbmc_contracts: Option<CbmcContracts>,
     /// If the harness contains some usage of loop contracts.
     pub has_loop_contracts: bool,
     /// If the harness was automatically generated or manually written.
     pub is_automatically_generated: bool,
+    /// The foreign functions that Kani does not model and that are reachable from the harness.
+    pub foreign_functions: Vec<ForeignFunction>,
+    /// The unsafe operations that are reachable from the harness, if they were requested with
+    /// `--list-unsafe`.
+    pub unsafe_operations: Vec<UnsafeOperation>,
+    /// The symbols of the functions annotated with `#[kani::spec_fn]` that are reachable from the
+    /// harness, whose recursion is bounded by `#[kani::spec_unwind]`.
+    pub spec_fns: Vec<String>,
+    /// The functions outside of the standard library that are reachable from the harness, if they
+    /// were requested with `--affected-by`.
+    pub reachable_functions: Vec<ReachableFunction>,
+    /// The features that Kani does not support and that are reachable from this harness.
+    /// Verification fails if any of them is reached.
+    pub unsupported_features: Vec<UnsupportedFeature>,
+    /// The number of MIR statements and terminators of the function that an automatic harness
+    /// verifies, which the driver uses to verify the smallest functions first.
+    pub target_mir_size: Option<usize>,
+    /// The number of elements of the largest array that the harness reaches, which selects how
+    /// CBMC encodes arrays when the harness doesn't specify it.
+    pub largest_array: Option<u64>,
+    /// The number of dynamic calls of the harness that `-Z devirtualize` replaced by direct calls.
+    pub devirtualized_calls: Option<u64>,
+    /// The CBMC ids of the loops of the harness whose label has an unwind value in its `unwind`
+    /// attribute, indexed by label.
+    pub labeled_loops: BTreeMap<String, Vec<String>>,
+    /// The variants of the enum input that the harness is split on, in the order of their indices.
+    /// The driver verifies each of them in a separate run of CBMC.
+    pub split_cases: Vec<String>,
 }
 
+/// The global variable that holds the index of the variant that a split harness verifies. The goto
+/// model only declares it, and the driver links a definition of it for each case.
+pub const SPLIT_CASE_VAR: &str = "__kani_split_case";
+
 /// The attributes added by the user to control how a harness is executed.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct HarnessAttributes {
@@ -51,14 +167,215 @@ pub struct HarnessAttributes {
     pub kind: HarnessKind,
     /// Whether the harness is expected to panic or not.
     pub should_panic: bool,
+    /// The text that the message of every panic of a harness that should panic must contain.
+    pub expected_panic: Option<String>,
     /// Optional data to store solver.
     pub solver: Option<CbmcSolver>,
     /// Optional data to store unwind value.
     pub unwind_value: Option<u32>,
+    /// The unwind values of the labeled loops of the harness, indexed by label.
+    pub loop_unwind: BTreeMap<String, u32>,
+    /// The bound on the recursion of the spec functions that the harness reaches.
+    pub spec_unwind: Option<u32>,
+    /// Optional timeout for the verification of the harness.
+    pub timeout: Option<Duration>,
     /// The stubs used in this harness.
     pub stubs: Vec<Stub>,
+    /// The types that are replaced by a model, together with their inherent methods.
+    pub type_stubs: Vec<Stub>,
     /// The name of the functions being stubbed by their contract.
     pub verified_stubs: Vec<String>,
+    /// Whether the harness must be verified in a separate process.
+    pub isolation: Option<Isolation>,
+    /// The CBMC checks that the harness enables or disables.
+    pub cbmc_checks: CbmcChecks,
+    /// The categories of checks that the harness enables or disables.
+    pub checks: HarnessChecks,
+    /// How CBMC encodes the arrays of the harness.
+    pub array_encoding: Option<ArrayEncoding>,
+    /// The maximum depth of the recursive calls that the harness reaches.
+    pub recursion_limit: Option<u32>,
+    /// The argument of the harness whose enum variants are verified separately, e.g.
+    /// `#[kani::proof(split_on = "input")]`.
+    pub split_on: Option<String>,
+}
+
+/// The categories of checks that a harness turns on or off with
+/// `#[kani::proof(checks(overflow = false, unwinding = true))]`, overriding the command-line
+/// options.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HarnessChecks {
+    pub enable: Vec<CheckCategory>,
+    pub disable: Vec<CheckCategory>,
+}
+
+impl HarnessChecks {
+    /// Whether the checks of `category` are performed, where `default` is whether the command-line
+    /// options enable them.
+    pub fn is_enabled(&self, category: CheckCategory, default: bool) -> bool {
+        if self.enable.contains(&category) {
+            true
+        } else if self.disable.contains(&category) {
+            false
+        } else {
+            default
+        }
+    }
+}
+
+/// A category of the checks that Kani adds to a harness.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Display,
+    EnumIter,
+    EnumString,
+    VariantNames,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum CheckCategory {
+    /// The arithmetic overflow checks of Rust, e.g., `attempt to add with overflow`, as well as
+    /// CBMC's division by zero and NaN checks.
+    Overflow,
+    /// The bounds checks of Rust's indexing operations.
+    Bounds,
+    /// The unwinding assertions, which fail if a loop isn't fully unwound.
+    Unwinding,
+}
+
+/// The CBMC checks that a harness enables or disables with
+/// `#[kani::proof(cbmc_checks(enable = "...", disable = "..."))]`, on top of the ones that the
+/// command-line options select.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CbmcChecks {
+    pub enable: Vec<CbmcCheck>,
+    pub disable: Vec<CbmcCheck>,
+}
+
+/// A property that CBMC can check automatically, named after its CBMC option, e.g.,
+/// `pointer-overflow` for `--pointer-overflow-check`.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Display,
+    EnumIter,
+    EnumString,
+    VariantNames,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CbmcCheck {
+    Bounds,
+    Pointer,
+    PointerOverflow,
+    PointerPrimitive,
+    DivByZero,
+    FloatOverflow,
+    Nan,
+    Conversion,
+}
+
+impl CbmcCheck {
+    /// Whether CBMC performs this check unless it is given `--no-<check>-check`.
+    pub fn is_cbmc_default(self) -> bool {
+        match self {
+            CbmcCheck::Bounds
+            | CbmcCheck::Pointer
+            | CbmcCheck::PointerPrimitive
+            | CbmcCheck::DivByZero => true,
+            CbmcCheck::PointerOverflow
+            | CbmcCheck::FloatOverflow
+            | CbmcCheck::Nan
+            | CbmcCheck::Conversion => false,
+        }
+    }
+}
+
+/// How a harness is isolated from the other harnesses of the crate.
+#[derive(Clone, Copy, Debug, Display, EnumString, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Isolation {
+    /// The harness is compiled and verified by a child process, so it cannot exhaust the memory
+    /// of the process that verifies the other harnesses.
+    #[strum(serialize = "process")]
+    Process,
+}
+
+/// How CBMC encodes the arrays of a harness, e.g. `#[kani::proof(array_encoding = "uf")]`.
+#[derive(Clone, Copy, Debug, Display, EnumString, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ArrayEncoding {
+    /// Every array is encoded with uninterpreted functions, rather than by flattening the small
+    /// ones into a bit-vector, which keeps the formula small when the harness uses large arrays.
+    #[strum(serialize = "uf")]
+    Uf,
+    /// Every element of the arrays, up to the largest array of the harness, is a separate
+    /// variable in the SSA, which helps symbolic execution to simplify accesses at constant
+    /// indices.
+    #[strum(serialize = "field_sensitive")]
+    FieldSensitive,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString)]
+enum TimeUnit {
+    #[strum(serialize = "s")]
+    Seconds,
+    #[strum(serialize = "m")]
+    Minutes,
+    #[strum(serialize = "h")]
+    Hours,
+}
+
+/// The timeout of a harness, e.g. `--harness-timeout 5m` or
+/// `#[kani::autoharness(timeout = "5m")]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timeout {
+    value: u32,
+    unit: TimeUnit,
+}
+
+impl FromStr for Timeout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let last_char = s.chars().last().ok_or("Invalid timeout value")?;
+        let (value_str, unit_str) = if last_char.is_ascii_digit() {
+            // no suffix
+            (s, "s")
+        } else {
+            s.split_at(s.len() - last_char.len_utf8())
+        };
+        let value = value_str.parse::<u32>().map_err(|_| "Invalid timeout value")?;
+
+        let unit = TimeUnit::from_str(unit_str).map_err(
+            |_| "Invalid time unit. Use 's' for seconds, 'm' for minutes, or 'h' for hours",
+        )?;
+
+        Ok(Timeout { value, unit })
+    }
+}
+
+impl From<Timeout> for Duration {
+    fn from(timeout: Timeout) -> Self {
+        match timeout.unit {
+            TimeUnit::Seconds => Duration::from_secs(timeout.value as u64),
+            TimeUnit::Minutes => Duration::from_secs(timeout.value as u64 * 60),
+            TimeUnit::Hours => Duration::from_secs(timeout.value as u64 * 3600),
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Display, Serialize, Deserialize)]
@@ -80,10 +397,21 @@ pub fn new(kind: HarnessKind) -> HarnessAttributes {
         HarnessAttributes {
             kind,
             should_panic: false,
+            expected_panic: None,
             solver: None,
             unwind_value: None,
+            loop_unwind: BTreeMap::new(),
+            spec_unwind: None,
+            timeout: None,
             stubs: vec![],
+            type_stubs: vec![],
             verified_stubs: vec![],
+            isolation: None,
+            cbmc_checks: CbmcChecks::default(),
+            checks: HarnessChecks::default(),
+            array_encoding: None,
+            recursion_limit: None,
+            split_on: None,
         }
     }
 
@@ -91,6 +419,11 @@ pub fn new(kind: HarnessKind) -> HarnessAttributes {
     pub fn is_proof_harness(&self) -> bool {
         matches!(self.kind, HarnessKind::Proof | HarnessKind::ProofForContract { .. })
     }
+
+    /// Return whether this harness replaces any function or type by a stub.
+    pub fn has_stubs(&self) -> bool {
+        !self.stubs.is_empty() || !self.type_stubs.is_empty()
+    }
 }
 
 /// The stubbing type.
@@ -41,6 +41,10 @@ pub struct KaniMetadata {
     pub autoharness_md: Option<AutoHarnessMetadata>,
 }
 
+/// The default maximum number of elements that a raw pointer argument of an `extern "C"` function
+/// points to in an automatic harness.
+pub const DEFAULT_EXTERN_C_BUFFER_LEN: usize = 8;
+
 /// For the autoharness subcommand, all of the user-defined functions we found,
 /// which are "chosen" if we generated an automatic harness for them, and "skipped" otherwise.
 /// We use ordered data structures so that the metadata is in alphabetical order.
@@ -68,6 +72,9 @@ pub enum AutoHarnessSkipReason {
     /// The function does not have a body.
     #[strum(serialize = "The function does not have a body")]
     NoBody,
+    /// The function is not part of the crate's public API, and we only check public functions.
+    #[strum(serialize = "Not a public function")]
+    NotPublic,
     /// The function doesn't match the user's provided filters.
     #[strum(serialize = "Did not match provided filters")]
     UserFilter,
@@ -80,15 +87,36 @@ pub struct ContractedFunction {
     pub file: String,
     /// The pretty names of the proof harnesses (`#[kani::proof_for_contract]`) for this function
     pub harnesses: Vec<String>,
+    /// The documentation comments of the function.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub docs: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The name of the file, at the root of a package, that lists the contracts Kani verified.
+pub const CONTRACT_MANIFEST_FILE: &str = "kani-contracts.json";
+
+/// The structure of contract manifests, which list the functions of a crate whose contracts were
+/// verified. Kani replaces the calls to these functions by their contracts when it verifies
+/// crates that depend on this one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContractManifest {
+    /// The name of the crate.
+    pub crate_name: String,
+    /// The version of the package that was verified.
+    pub version: String,
+    /// The fully qualified names of the functions, without the crate name.
+    pub functions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct UnsupportedFeature {
     // We could replace this with an enum: https://github.com/model-checking/kani/issues/1765
     /// A string identifying the feature.
     pub feature: String,
     /// A list of locations (file, line) where this unsupported feature can be found.
     pub locations: HashSet<Location>,
+    /// The issue that tracks the support of this feature, or where users can report it.
+    pub tracking_issue: Option<String>,
 }
 
 /// The location in a file
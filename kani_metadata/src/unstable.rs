@@ -75,6 +75,8 @@ pub enum UnstableFeature {
     ConcretePlayback,
     /// Allow Kani to link against C code.
     CFfi,
+    /// Replace the dynamic calls whose receiver can only have one concrete type by direct calls.
+    Devirtualize,
     /// Kani APIs related to floating-point operations (e.g. `float_to_int_in_range`)
     FloatLib,
     /// Enable function contracts [RFC 9](https://model-checking.github.io/kani/rfc/rfcs/0009-function-contracts.html)
@@ -89,8 +91,13 @@ pub enum UnstableFeature {
     LoopContracts,
     /// Memory predicate APIs.
     MemPredicates,
+    /// Automatically check that pointers cast from integers have the provenance of an exposed
+    /// allocation, and stay within its bounds.
+    ProvenanceChecks,
     /// Enable vtable restriction.
     RestrictVtable,
+    /// Compile the harnesses of a codegen unit into one goto model, which each of them links.
+    SharedModel,
     /// Enable source-based code coverage workflow.
     /// See [RFC-0011](https://model-checking.github.io/kani/rfc/rfcs/0011-source-coverage.html)
     SourceCoverage,
@@ -99,6 +106,10 @@ pub enum UnstableFeature {
     Stubbing,
     /// Enable quantifiers [RFC 10](https://model-checking.github.io/kani/rfc/rfcs/0010-quantifiers.html)
     Quantifiers,
+    /// Automatically check that integer `as` casts that may not preserve their value, and
+    /// float-to-integer casts, are applied to values that the target type can represent.
+    /// Implies `-Z float-lib`, since the checks of float-to-integer casts use its range check.
+    TruncationChecks,
     /// Automatically check that uninitialized memory is not used.
     UninitChecks,
     /// Enable an unstable option or subcommand.
@@ -146,7 +157,7 @@ pub fn stabilization_version(&self) -> Option<String> {
 /// compiler and the driver.
 ///
 /// For usage see the [module level documentation][self].
-#[derive(clap::Args, Debug)]
+#[derive(clap::Args, Clone, Debug)]
 pub struct EnabledUnstableFeatures {
     #[clap(short = 'Z', long = "unstable", num_args(1), value_name = "UNSTABLE_FEATURE")]
     enabled_unstable_features: Vec<UnstableFeature>,
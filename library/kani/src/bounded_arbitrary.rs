@@ -4,6 +4,7 @@
 //! This module introduces implementations for some std containers.
 
 use kani::{Arbitrary, BoundedArbitrary};
+use std::borrow::{Borrow, Cow};
 
 // This implementation overlaps with `kani::any_vec` in `kani/library/kani/src/vec.rs`.
 // This issue `https://github.com/model-checking/kani/issues/4027` tracks deprecating
@@ -26,6 +27,26 @@ fn bounded_any<const N: usize>() -> Self {
     }
 }
 
+/// Generate either a `Cow::Owned` value or a `Cow::Borrowed` reference, whose owned buffer is
+/// bounded by `N`.
+///
+/// The borrowed value is leaked so it lives as long as the harness.
+impl<B> BoundedArbitrary for Cow<'static, B>
+where
+    B: ToOwned + ?Sized + 'static,
+    B::Owned: BoundedArbitrary + 'static,
+{
+    fn bounded_any<const N: usize>() -> Self {
+        let owned = B::Owned::bounded_any::<N>();
+        if bool::any() {
+            Cow::Owned(owned)
+        } else {
+            let leaked: &'static B::Owned = Box::leak(Box::new(owned));
+            Cow::Borrowed(leaked.borrow())
+        }
+    }
+}
+
 impl<K, V> BoundedArbitrary
     for std::collections::HashMap<K, V, std::hash::BuildHasherDefault<std::hash::DefaultHasher>>
 where
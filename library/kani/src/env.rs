@@ -0,0 +1,141 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Models of the command-line arguments of a program.
+//!
+//! During verification, `std::env::args()` and `std::env::args_os()` return the arguments that
+//! [`args`] generates, so that a harness on the `main` function of a binary verifies it for every
+//! invocation with at most [`MAX_ARGS`] arguments of at most [`MAX_ARG_LEN`] bytes each.
+//!
+//! For example, the following harness proves that `main` doesn't panic for any such invocation:
+//! ```no_run
+//! fn greet(name: &str) -> String {
+//!     format!("Hello, {name}!")
+//! }
+//!
+//! #[kani::proof]
+//! fn main() {
+//!     let name = std::env::args().nth(1).unwrap_or_default();
+//!     println!("{}", greet(&name));
+//! }
+//! ```
+
+use crate::BoundedArbitrary;
+use std::ffi::OsString;
+use std::fmt;
+
+/// The maximum number of arguments that [`args`] generates, besides the program name.
+pub const MAX_ARGS: usize = 3;
+
+/// The maximum length in bytes of every argument that [`args`] generates, including the program
+/// name.
+pub const MAX_ARG_LEN: usize = 4;
+
+/// An iterator over the arguments of a program, which replaces `std::env::Args` during
+/// verification.
+pub struct Args {
+    inner: std::vec::IntoIter<String>,
+}
+
+/// An iterator over the arguments of a program as [`OsString`], which replaces
+/// `std::env::ArgsOs` during verification.
+pub struct ArgsOs {
+    inner: std::vec::IntoIter<String>,
+}
+
+/// The arguments of the program, which are generated the first time they are read.
+static mut ARGS: Option<Vec<String>> = None;
+
+#[allow(static_mut_refs)]
+fn program_args() -> Vec<String> {
+    // SAFETY: Kani verifies the threads of a harness one step at a time, so there are no data
+    // races on the arguments.
+    unsafe { ARGS.get_or_insert_with(any_args::<MAX_ARGS, MAX_ARG_LEN>).clone() }
+}
+
+/// The arguments of a program: a program name followed by at most [`MAX_ARGS`] arguments, all of
+/// which are strings of at most [`MAX_ARG_LEN`] bytes.
+///
+/// The arguments are generated by the first call to [`args`] or [`args_os`], and the later calls
+/// return the same arguments.
+pub fn args() -> Args {
+    Args { inner: program_args().into_iter() }
+}
+
+/// The arguments of a program like [`args`], as [`OsString`].
+///
+/// Only arguments that are valid UTF-8 are generated.
+pub fn args_os() -> ArgsOs {
+    ArgsOs { inner: program_args().into_iter() }
+}
+
+/// Generate a program name followed by at most `MAX_COUNT` arguments, all of which are strings of
+/// at most `MAX_LEN` bytes.
+pub fn any_args<const MAX_COUNT: usize, const MAX_LEN: usize>() -> Vec<String> {
+    let count: usize = kani::any_where(|count| *count <= MAX_COUNT);
+    let mut args = Vec::with_capacity(count + 1);
+    args.push(String::bounded_any::<MAX_LEN>());
+    for _ in 0..count {
+        args.push(String::bounded_any::<MAX_LEN>());
+    }
+    args
+}
+
+impl Iterator for Args {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Args {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl DoubleEndedIterator for Args {
+    fn next_back(&mut self) -> Option<String> {
+        self.inner.next_back()
+    }
+}
+
+impl fmt::Debug for Args {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Args").field("args", &self.inner.as_slice()).finish()
+    }
+}
+
+impl Iterator for ArgsOs {
+    type Item = OsString;
+
+    fn next(&mut self) -> Option<OsString> {
+        self.inner.next().map(OsString::from)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ArgsOs {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl DoubleEndedIterator for ArgsOs {
+    fn next_back(&mut self) -> Option<OsString> {
+        self.inner.next_back().map(OsString::from)
+    }
+}
+
+impl fmt::Debug for ArgsOs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArgsOs").field("args", &self.inner.as_slice()).finish()
+    }
+}
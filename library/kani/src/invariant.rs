@@ -87,6 +87,11 @@
 ///   assert!(date.is_safe());
 /// }
 /// ```
+///
+/// If the implementation is derived with `#[derive(kani::Invariant)]`, `kani::any()` only
+/// creates values that uphold the invariant. Kani can also check that public methods taking
+/// `&mut self` preserve the invariant with the `--check-invariant-preservation` option.
+#[rustc_diagnostic_item = "KaniInvariant"]
 pub trait Invariant
 where
     Self: Sized,
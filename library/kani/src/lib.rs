@@ -22,6 +22,7 @@
 #![feature(f128)]
 #![feature(convert_float_to_int)]
 #![feature(sized_hierarchy)]
+#![feature(allocator_api)]
 
 // Allow us to use `kani::` to access crate features.
 extern crate self as kani;
@@ -30,16 +31,21 @@
 pub mod bounded_arbitrary;
 #[cfg(feature = "concrete_playback")]
 mod concrete_playback;
+pub mod env;
 pub mod futures;
 pub mod invariant;
+pub mod refcount;
 pub mod shadow;
+mod snapshot;
+pub mod thread;
 pub mod vec;
 
-mod models;
+pub mod models;
 
 #[cfg(feature = "concrete_playback")]
 pub use concrete_playback::concrete_playback_run;
 pub use invariant::Invariant;
+pub use snapshot::from_snapshot;
 
 #[cfg(not(feature = "concrete_playback"))]
 /// NOP `concrete_playback` for type checking during verification mode.
@@ -64,6 +70,25 @@ pub fn concrete_playback_run<F: Fn()>(_: Vec<Vec<u8>>, _: F) {
 
 #[macro_export]
 macro_rules! cover {
+    // A label groups cover properties in the summary that Kani prints after verifying every
+    // harness. It must come before the other rules, since `label = name` is a valid expression.
+    (label = $label:ident $(,)?) => {
+        kani::cover(true, concat!("[KANI_COVER_LABEL_", stringify!($label), "] cover location"));
+    };
+    ($cond:expr, label = $label:ident $(,)?) => {
+        kani::cover(
+            $cond,
+            concat!(
+                "[KANI_COVER_LABEL_",
+                stringify!($label),
+                "] cover condition: ",
+                stringify!($cond)
+            ),
+        );
+    };
+    ($cond:expr, $msg:literal, label = $label:ident $(,)?) => {
+        kani::cover($cond, concat!("[KANI_COVER_LABEL_", stringify!($label), "] ", $msg));
+    };
     () => {
         kani::cover(true, "cover location");
     };
@@ -75,6 +100,15 @@ macro_rules! cover {
     };
 }
 
+/// `kani::probe!("name")` marks a location that Kani reports as covered or uncovered at the end
+/// of every harness. Probes with the same name are aggregated.
+#[macro_export]
+macro_rules! probe {
+    ($name:literal $(,)?) => {
+        kani::probe($name);
+    };
+}
+
 /// `implies!(premise => conclusion)` means that if the `premise` is true, so
 /// must be the `conclusion`.
 ///
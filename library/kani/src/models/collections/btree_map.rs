@@ -0,0 +1,574 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A model of `std::collections::BTreeMap` that stores its entries in a vector sorted by key.
+//!
+//! Looking up a key is a binary search over the vector, and inserting or removing an entry moves
+//! the entries after it with a single copy, which is much cheaper to verify than the rebalancing
+//! of the B-tree nodes for the small maps used in harnesses.
+//!
+//! With `--model-std-collections`, Kani replaces `std::collections::BTreeMap` and its iterators
+//! by the types in this module. Their layout must match the layout of the types they replace,
+//! so each of them is padded to the size of the original type.
+
+use crate::models::hash_map::padding;
+use std::alloc::{Allocator, Global};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+use std::ops::{Bound, Index, RangeBounds};
+
+const MAP_PADDING: usize = padding::<std::collections::BTreeMap<(), ()>, Vec<((), ())>>();
+const ITER_PADDING: usize = padding::<
+    std::collections::btree_map::Iter<'static, (), ()>,
+    std::slice::Iter<'static, ((), ())>,
+>();
+const RANGE_PADDING: usize = padding::<
+    std::collections::btree_map::Range<'static, (), ()>,
+    std::slice::Iter<'static, ((), ())>,
+>();
+const INTO_ITER_PADDING: usize =
+    padding::<std::collections::btree_map::IntoIter<(), ()>, std::vec::IntoIter<((), ())>>();
+
+/// A map with the interface of `std::collections::BTreeMap`.
+///
+/// The entries are sorted by key, and keys are unique.
+pub struct BTreeMap<K, V, A: Allocator + Clone = Global> {
+    entries: Vec<(K, V), A>,
+    _padding: [usize; MAP_PADDING],
+}
+
+impl<K, V> BTreeMap<K, V> {
+    pub fn new() -> BTreeMap<K, V> {
+        BTreeMap::new_in(Global)
+    }
+}
+
+impl<K, V, A: Allocator + Clone> BTreeMap<K, V, A> {
+    pub fn new_in(alloc: A) -> BTreeMap<K, V, A> {
+        BTreeMap { entries: Vec::new_in(alloc), _padding: [0; MAP_PADDING] }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear()
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized + Ord,
+    {
+        self.search(key).ok().map(|idx| &self.entries[idx].1)
+    }
+
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized + Ord,
+    {
+        self.search(k).ok().map(|idx| {
+            let (key, value) = &self.entries[idx];
+            (key, value)
+        })
+    }
+
+    pub fn first_key_value(&self) -> Option<(&K, &V)>
+    where
+        K: Ord,
+    {
+        self.entries.first().map(|(key, value)| (key, value))
+    }
+
+    pub fn pop_first(&mut self) -> Option<(K, V)>
+    where
+        K: Ord,
+    {
+        (!self.entries.is_empty()).then(|| self.entries.remove(0))
+    }
+
+    pub fn last_key_value(&self) -> Option<(&K, &V)>
+    where
+        K: Ord,
+    {
+        self.entries.last().map(|(key, value)| (key, value))
+    }
+
+    pub fn pop_last(&mut self) -> Option<(K, V)>
+    where
+        K: Ord,
+    {
+        self.entries.pop()
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized + Ord,
+    {
+        self.search(key).is_ok()
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized + Ord,
+    {
+        self.search(key).ok().map(|idx| &mut self.entries[idx].1)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        K: Ord,
+    {
+        match self.search(&key) {
+            Ok(idx) => Some(std::mem::replace(&mut self.entries[idx].1, value)),
+            Err(idx) => {
+                self.entries.insert(idx, (key, value));
+                None
+            }
+        }
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized + Ord,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized + Ord,
+    {
+        self.search(key).ok().map(|idx| self.entries.remove(idx))
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.entries.retain_mut(|(key, value)| f(key, value))
+    }
+
+    pub fn append(&mut self, other: &mut Self)
+    where
+        K: Ord,
+        A: Clone,
+    {
+        for (key, value) in other.entries.drain(..) {
+            self.insert(key, value);
+        }
+    }
+
+    pub fn range<T, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        T: ?Sized + Ord,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        if let (
+            Bound::Included(start) | Bound::Excluded(start),
+            Bound::Included(end) | Bound::Excluded(end),
+        ) = (range.start_bound(), range.end_bound())
+        {
+            match start.cmp(end) {
+                Ordering::Greater => panic!("range start is greater than range end in BTreeMap"),
+                Ordering::Equal
+                    if matches!(
+                        (range.start_bound(), range.end_bound()),
+                        (Bound::Excluded(_), Bound::Excluded(_))
+                    ) =>
+                {
+                    panic!("range start and end are equal and excluded in BTreeMap")
+                }
+                _ => {}
+            }
+        }
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.entries.partition_point(|(key, _)| key.borrow() < start),
+            Bound::Excluded(start) => {
+                self.entries.partition_point(|(key, _)| key.borrow() <= start)
+            }
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => self.entries.partition_point(|(key, _)| key.borrow() <= end),
+            Bound::Excluded(end) => self.entries.partition_point(|(key, _)| key.borrow() < end),
+            Bound::Unbounded => self.entries.len(),
+        };
+        Range { entries: self.entries[start..end].iter(), _padding: [0; RANGE_PADDING] }
+    }
+
+    pub fn split_off<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self
+    where
+        K: Borrow<Q> + Ord,
+        A: Clone,
+    {
+        let at = self.entries.partition_point(|(k, _)| k.borrow() < key);
+        BTreeMap { entries: self.entries.split_off(at), _padding: [0; MAP_PADDING] }
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { entries: self.entries.iter(), _padding: [0; ITER_PADDING] }
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Find the index of the entry with the given key, or the index where it should be inserted.
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.entries.binary_search_by(|(k, _)| k.borrow().cmp(key))
+    }
+}
+
+impl<K: Clone, V: Clone, A: Allocator + Clone> Clone for BTreeMap<K, V, A> {
+    fn clone(&self) -> Self {
+        BTreeMap { entries: self.entries.clone(), _padding: [0; MAP_PADDING] }
+    }
+}
+
+impl<K: PartialEq, V: PartialEq, A: Allocator + Clone> PartialEq for BTreeMap<K, V, A> {
+    fn eq(&self, other: &BTreeMap<K, V, A>) -> bool {
+        self.entries.as_slice() == other.entries.as_slice()
+    }
+}
+
+impl<K: Eq, V: Eq, A: Allocator + Clone> Eq for BTreeMap<K, V, A> {}
+
+impl<K: PartialOrd, V: PartialOrd, A: Allocator + Clone> PartialOrd for BTreeMap<K, V, A> {
+    fn partial_cmp(&self, other: &BTreeMap<K, V, A>) -> Option<Ordering> {
+        self.entries.as_slice().partial_cmp(other.entries.as_slice())
+    }
+}
+
+impl<K: Ord, V: Ord, A: Allocator + Clone> Ord for BTreeMap<K, V, A> {
+    fn cmp(&self, other: &BTreeMap<K, V, A>) -> Ordering {
+        self.entries.as_slice().cmp(other.entries.as_slice())
+    }
+}
+
+impl<K: Hash, V: Hash, A: Allocator + Clone> Hash for BTreeMap<K, V, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.entries.as_slice().hash(state)
+    }
+}
+
+impl<K: Debug, V: Debug, A: Allocator + Clone> Debug for BTreeMap<K, V, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> Default for BTreeMap<K, V> {
+    fn default() -> BTreeMap<K, V> {
+        BTreeMap::new()
+    }
+}
+
+impl<K, Q, V, A: Allocator + Clone> Index<&Q> for BTreeMap<K, V, A>
+where
+    K: Borrow<Q> + Ord,
+    Q: ?Sized + Ord,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<K: Ord, V, const N: usize> From<[(K, V); N]> for BTreeMap<K, V> {
+    fn from(arr: [(K, V); N]) -> Self {
+        BTreeMap::from_iter(arr)
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for BTreeMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> BTreeMap<K, V> {
+        let mut map = BTreeMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord, V, A: Allocator + Clone> Extend<(K, V)> for BTreeMap<K, V, A> {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K: Ord + Copy, V: Copy, A: Allocator + Clone> Extend<(&'a K, &'a V)>
+    for BTreeMap<K, V, A>
+{
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+impl<'a, K, V, A: Allocator + Clone> IntoIterator for &'a BTreeMap<K, V, A> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<K, V, A: Allocator + Clone> IntoIterator for BTreeMap<K, V, A> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, A>;
+
+    fn into_iter(self) -> IntoIter<K, V, A> {
+        IntoIter { entries: self.entries.into_iter(), _padding: [0; INTO_ITER_PADDING] }
+    }
+}
+
+/// An iterator over the entries of a `BTreeMap`, in the order of their keys.
+pub struct Iter<'a, K: 'a, V: 'a> {
+    entries: std::slice::Iter<'a, (K, V)>,
+    _padding: [usize; ITER_PADDING],
+}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Iter { entries: self.entries.clone(), _padding: [0; ITER_PADDING] }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for Iter<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.entries.next().map(|(key, value)| (key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        self.entries.next_back().map(|(key, value)| (key, value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+/// An iterator over the keys of a `BTreeMap`.
+pub struct Keys<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<K, V> Clone for Keys<'_, K, V> {
+    fn clone(&self) -> Self {
+        Keys { inner: self.inner.clone() }
+    }
+}
+
+impl<K: Debug, V> Debug for Keys<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.inner.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a K> {
+        self.inner.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+/// An iterator over the values of a `BTreeMap`, in the order of their keys.
+pub struct Values<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<K, V> Clone for Values<'_, K, V> {
+    fn clone(&self) -> Self {
+        Values { inner: self.inner.clone() }
+    }
+}
+
+impl<K, V: Debug> Debug for Values<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.inner.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V> {
+    fn next_back(&mut self) -> Option<&'a V> {
+        self.inner.next_back().map(|(_, value)| value)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+impl<K, V> FusedIterator for Values<'_, K, V> {}
+
+/// An iterator over a range of the entries of a `BTreeMap`.
+pub struct Range<'a, K: 'a, V: 'a> {
+    entries: std::slice::Iter<'a, (K, V)>,
+    _padding: [usize; RANGE_PADDING],
+}
+
+impl<K, V> Clone for Range<'_, K, V> {
+    fn clone(&self) -> Self {
+        Range { entries: self.entries.clone(), _padding: [0; RANGE_PADDING] }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for Range<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.entries.next().map(|(key, value)| (key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Range<'a, K, V> {
+    fn next_back(&mut self) -> Option<(&'a K, &'a V)> {
+        self.entries.next_back().map(|(key, value)| (key, value))
+    }
+}
+
+impl<K, V> FusedIterator for Range<'_, K, V> {}
+
+/// An owning iterator over the entries of a `BTreeMap`, in the order of their keys.
+pub struct IntoIter<K, V, A: Allocator + Clone = Global> {
+    entries: std::vec::IntoIter<(K, V), A>,
+    _padding: [usize; INTO_ITER_PADDING],
+}
+
+impl<K: Debug, V: Debug, A: Allocator + Clone> Debug for IntoIter<K, V, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.entries.as_slice()).finish()
+    }
+}
+
+impl<K, V, A: Allocator + Clone> Iterator for IntoIter<K, V, A> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        self.entries.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V, A: Allocator + Clone> DoubleEndedIterator for IntoIter<K, V, A> {
+    fn next_back(&mut self) -> Option<(K, V)> {
+        self.entries.next_back()
+    }
+}
+
+impl<K, V, A: Allocator + Clone> ExactSizeIterator for IntoIter<K, V, A> {}
+
+impl<K, V, A: Allocator + Clone> FusedIterator for IntoIter<K, V, A> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Check that the models have the same size as the types they replace.
+    #[test]
+    fn test_layout() {
+        use std::collections::btree_map;
+        assert_eq!(
+            size_of::<BTreeMap<u8, u64>>(),
+            size_of::<std::collections::BTreeMap<u8, u64>>()
+        );
+        assert_eq!(size_of::<Iter<u8, u64>>(), size_of::<btree_map::Iter<u8, u64>>());
+        assert_eq!(size_of::<Keys<u8, u64>>(), size_of::<btree_map::Keys<u8, u64>>());
+        assert_eq!(size_of::<Values<u8, u64>>(), size_of::<btree_map::Values<u8, u64>>());
+        assert_eq!(size_of::<Range<u8, u64>>(), size_of::<btree_map::Range<u8, u64>>());
+        assert_eq!(size_of::<IntoIter<u8, u64>>(), size_of::<btree_map::IntoIter<u8, u64>>());
+    }
+
+    #[test]
+    fn test_map_operations() {
+        let mut map = BTreeMap::new();
+        assert_eq!(map.insert(3, 'c'), None);
+        assert_eq!(map.insert(1, 'a'), None);
+        assert_eq!(map.insert(2, 'b'), None);
+        assert_eq!(map.insert(1, 'd'), Some('a'));
+        assert_eq!(map.keys().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        assert_eq!(map.range(2..).map(|(_, v)| *v).collect::<Vec<_>>(), vec!['b', 'c']);
+        assert_eq!(map.first_key_value(), Some((&1, &'d')));
+        assert_eq!(map.remove(&2), Some('b'));
+        let tail = map.split_off(&3);
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![(1, 'd')]);
+        assert_eq!(tail[&3], 'c');
+    }
+}
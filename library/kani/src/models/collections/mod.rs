@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Models of the collections of `std::collections`, which Kani uses in their place with
+//! `--model-std-collections`.
+
+pub mod btree_map;
+pub mod vec_deque;
+
+pub use super::{HashMap, HashSet, hash_map, hash_set};
+pub use btree_map::BTreeMap;
+pub use vec_deque::VecDeque;
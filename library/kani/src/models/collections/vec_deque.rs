@@ -0,0 +1,555 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A model of `std::collections::VecDeque` that stores its elements contiguously in a `Vec`.
+//!
+//! Pushing and popping at the front moves the other elements with a single copy, which is much
+//! cheaper to verify than the wrapping arithmetic of the ring buffer for the small queues used in
+//! harnesses.
+//!
+//! With `--model-std-collections`, Kani replaces `std::collections::VecDeque` and its iterators
+//! by the types in this module. Their layout must match the layout of the types they replace,
+//! so each of them is padded to the size of the original type.
+
+use crate::models::hash_map::padding;
+use std::alloc::{Allocator, Global};
+use std::cmp::Ordering;
+use std::collections::TryReserveError;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+use std::ops::{Index, IndexMut, RangeBounds};
+
+const DEQUE_PADDING: usize = padding::<std::collections::VecDeque<()>, Vec<()>>();
+const ITER_PADDING: usize =
+    padding::<std::collections::vec_deque::Iter<'static, ()>, std::slice::Iter<'static, ()>>();
+const ITER_MUT_PADDING: usize =
+    padding::<std::collections::vec_deque::IterMut<'static, ()>, std::slice::IterMut<'static, ()>>(
+    );
+const INTO_ITER_PADDING: usize =
+    padding::<std::collections::vec_deque::IntoIter<()>, std::vec::IntoIter<()>>();
+
+/// A double-ended queue with the interface of `std::collections::VecDeque`.
+///
+/// The front of the queue is the first element of the vector, so the whole queue is always
+/// contiguous.
+pub struct VecDeque<T, A: Allocator = Global> {
+    buf: Vec<T, A>,
+    _padding: [usize; DEQUE_PADDING],
+}
+
+impl<T> VecDeque<T> {
+    pub fn new() -> VecDeque<T> {
+        VecDeque::from(Vec::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> VecDeque<T> {
+        VecDeque::from(Vec::with_capacity(capacity))
+    }
+}
+
+impl<T, A: Allocator> VecDeque<T, A> {
+    pub fn new_in(alloc: A) -> VecDeque<T, A> {
+        VecDeque::from(Vec::new_in(alloc))
+    }
+
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> VecDeque<T, A> {
+        VecDeque::from(Vec::with_capacity_in(capacity, alloc))
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.buf.get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.buf.get_mut(index)
+    }
+
+    pub fn swap(&mut self, i: usize, j: usize) {
+        assert!(i < self.len());
+        assert!(j < self.len());
+        self.buf.swap(i, j)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.buf.reserve_exact(additional)
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.buf.reserve(additional)
+    }
+
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buf.try_reserve_exact(additional)
+    }
+
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buf.try_reserve(additional)
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.buf.shrink_to_fit()
+    }
+
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.buf.shrink_to(min_capacity)
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        self.buf.truncate(len)
+    }
+
+    pub fn allocator(&self) -> &A {
+        self.buf.allocator()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { elems: self.buf.iter(), _padding: [0; ITER_PADDING] }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { elems: self.buf.iter_mut(), _padding: [0; ITER_MUT_PADDING] }
+    }
+
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        (self.buf.as_slice(), &[])
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        (self.buf.as_mut_slice(), &mut [])
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    pub fn range<R>(&self, range: R) -> Iter<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        Iter { elems: self.buf[bounds].iter(), _padding: [0; ITER_PADDING] }
+    }
+
+    pub fn range_mut<R>(&mut self, range: R) -> IterMut<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        IterMut { elems: self.buf[bounds].iter_mut(), _padding: [0; ITER_MUT_PADDING] }
+    }
+
+    pub fn clear(&mut self) {
+        self.buf.clear()
+    }
+
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq<T>,
+    {
+        self.buf.contains(x)
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.buf.first()
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.buf.first_mut()
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.buf.last()
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.buf.last_mut()
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        (!self.buf.is_empty()).then(|| self.buf.remove(0))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.buf.pop()
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        self.buf.insert(0, value)
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        self.buf.push(value)
+    }
+
+    pub fn swap_remove_front(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        let front = self.buf.remove(0);
+        if index == 0 {
+            Some(front)
+        } else {
+            Some(std::mem::replace(&mut self.buf[index - 1], front))
+        }
+    }
+
+    pub fn swap_remove_back(&mut self, index: usize) -> Option<T> {
+        (index < self.len()).then(|| self.buf.swap_remove(index))
+    }
+
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len(), "index out of bounds");
+        self.buf.insert(index, value)
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        (index < self.len()).then(|| self.buf.remove(index))
+    }
+
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        A: Clone,
+    {
+        assert!(at <= self.len(), "`at` out of bounds");
+        VecDeque::from(self.buf.split_off(at))
+    }
+
+    pub fn append(&mut self, other: &mut Self) {
+        self.buf.append(&mut other.buf)
+    }
+
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.buf.retain(f)
+    }
+
+    pub fn retain_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.buf.retain_mut(f)
+    }
+
+    pub fn resize_with(&mut self, new_len: usize, generator: impl FnMut() -> T) {
+        self.buf.resize_with(new_len, generator)
+    }
+
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        self.buf.as_mut_slice()
+    }
+
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len());
+        self.buf.rotate_left(n)
+    }
+
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len());
+        self.buf.rotate_right(n)
+    }
+}
+
+impl<T: Clone, A: Allocator> VecDeque<T, A> {
+    pub fn resize(&mut self, new_len: usize, value: T) {
+        self.buf.resize(new_len, value)
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for VecDeque<T, A> {
+    fn clone(&self) -> Self {
+        VecDeque::from(self.buf.clone())
+    }
+}
+
+impl<T> Default for VecDeque<T> {
+    fn default() -> VecDeque<T> {
+        VecDeque::new()
+    }
+}
+
+impl<T: PartialEq, A: Allocator> PartialEq for VecDeque<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf.as_slice() == other.buf.as_slice()
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for VecDeque<T, A> {}
+
+/// Implement the comparisons of `VecDeque` with the slice-like types that the standard library
+/// supports.
+macro_rules! impl_slice_eq {
+    ([$($vars:tt)*] $rhs:ty) => {
+        impl<T, U, A: Allocator, $($vars)*> PartialEq<$rhs> for VecDeque<T, A>
+        where
+            T: PartialEq<U>,
+        {
+            fn eq(&self, other: &$rhs) -> bool {
+                self.buf.as_slice() == &other[..]
+            }
+        }
+    };
+}
+
+impl_slice_eq! { [] Vec<U, A> }
+impl_slice_eq! { [] &[U] }
+impl_slice_eq! { [] &mut [U] }
+impl_slice_eq! { [const N: usize] [U; N] }
+impl_slice_eq! { [const N: usize] &[U; N] }
+impl_slice_eq! { [const N: usize] &mut [U; N] }
+
+impl<T: PartialOrd, A: Allocator> PartialOrd for VecDeque<T, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.buf.as_slice().partial_cmp(other.buf.as_slice())
+    }
+}
+
+impl<T: Ord, A: Allocator> Ord for VecDeque<T, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.buf.as_slice().cmp(other.buf.as_slice())
+    }
+}
+
+impl<T: Hash, A: Allocator> Hash for VecDeque<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.buf.as_slice().hash(state)
+    }
+}
+
+impl<T, A: Allocator> Index<usize> for VecDeque<T, A> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("Out of bounds access")
+    }
+}
+
+impl<T, A: Allocator> IndexMut<usize> for VecDeque<T, A> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("Out of bounds access")
+    }
+}
+
+impl<T> FromIterator<T> for VecDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> VecDeque<T> {
+        VecDeque::from(Vec::from_iter(iter))
+    }
+}
+
+impl<T, A: Allocator> IntoIterator for VecDeque<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(self) -> IntoIter<T, A> {
+        IntoIter { elems: self.buf.into_iter(), _padding: [0; INTO_ITER_PADDING] }
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a VecDeque<T, A> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a mut VecDeque<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for VecDeque<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.buf.extend(iter)
+    }
+}
+
+impl<'a, T: 'a + Copy, A: Allocator> Extend<&'a T> for VecDeque<T, A> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.buf.extend(iter)
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for VecDeque<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, A: Allocator> From<Vec<T, A>> for VecDeque<T, A> {
+    fn from(buf: Vec<T, A>) -> Self {
+        VecDeque { buf, _padding: [0; DEQUE_PADDING] }
+    }
+}
+
+impl<T, A: Allocator> From<VecDeque<T, A>> for Vec<T, A> {
+    fn from(other: VecDeque<T, A>) -> Self {
+        other.buf
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for VecDeque<T> {
+    fn from(arr: [T; N]) -> Self {
+        VecDeque::from(Vec::from(arr))
+    }
+}
+
+/// An iterator over the elements of a `VecDeque`.
+pub struct Iter<'a, T: 'a> {
+    elems: std::slice::Iter<'a, T>,
+    _padding: [usize; ITER_PADDING],
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { elems: self.elems.clone(), _padding: [0; ITER_PADDING] }
+    }
+}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.elems.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elems.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.elems.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// A mutable iterator over the elements of a `VecDeque`.
+pub struct IterMut<'a, T: 'a> {
+    elems: std::slice::IterMut<'a, T>,
+    _padding: [usize; ITER_MUT_PADDING],
+}
+
+impl<T: Debug> Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.elems.as_slice()).finish()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.elems.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elems.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        self.elems.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An owning iterator over the elements of a `VecDeque`.
+pub struct IntoIter<T, A: Allocator = Global> {
+    elems: std::vec::IntoIter<T, A>,
+    _padding: [usize; INTO_ITER_PADDING],
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for IntoIter<T, A> {
+    fn clone(&self) -> Self {
+        IntoIter { elems: self.elems.clone(), _padding: [0; INTO_ITER_PADDING] }
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for IntoIter<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.elems.as_slice()).finish()
+    }
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.elems.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elems.size_hint()
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        self.elems.next_back()
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {}
+
+impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Check that the models have the same size as the types they replace.
+    #[test]
+    fn test_layout() {
+        use std::collections::vec_deque;
+        assert_eq!(size_of::<VecDeque<u64>>(), size_of::<std::collections::VecDeque<u64>>());
+        assert_eq!(size_of::<Iter<u64>>(), size_of::<vec_deque::Iter<u64>>());
+        assert_eq!(size_of::<IterMut<u64>>(), size_of::<vec_deque::IterMut<u64>>());
+        assert_eq!(size_of::<IntoIter<u64>>(), size_of::<vec_deque::IntoIter<u64>>());
+    }
+
+    #[test]
+    fn test_deque_operations() {
+        let mut deque = VecDeque::new();
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_front(1);
+        assert_eq!(deque, [1, 2, 3]);
+        assert_eq!(deque.swap_remove_front(2), Some(3));
+        assert_eq!(deque, [2, 1]);
+        deque.extend([4, 5]);
+        assert_eq!(deque.pop_front(), Some(2));
+        assert_eq!(deque.pop_back(), Some(5));
+        assert_eq!(deque.range(1..).collect::<Vec<_>>(), vec![&4]);
+        assert_eq!(Vec::from(deque), vec![1, 4]);
+    }
+}
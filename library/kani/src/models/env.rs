@@ -0,0 +1,81 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Models of the environment variables of a process, which Kani uses in place of the functions of
+//! `std::env` that read and write them with `-Z unstable-options --model-std-io`.
+//!
+//! The environment is symbolic: the first time a harness reads a variable that it didn't set, the
+//! variable is either absent or holds an arbitrary string of at most [`MAX_VAR_LEN`] bytes. Later
+//! reads of the same variable return the same value, until the harness sets or removes it. Only
+//! values that are valid UTF-8 are generated, so [`var`] never returns
+//! [`VarError::NotUnicode`] for a variable that the harness didn't set.
+//!
+//! ```no_run
+//! #[kani::proof]
+//! fn check_log_level() {
+//!     let level = kani::models::env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
+//!     assert!(level.len() <= kani::models::env::MAX_VAR_LEN || level == "info");
+//! }
+//! ```
+
+use crate::BoundedArbitrary;
+use std::env::VarError;
+use std::ffi::{OsStr, OsString};
+
+/// The maximum length in bytes of the value of a variable that the harness didn't set.
+pub const MAX_VAR_LEN: usize = 4;
+
+/// The variables that were read or written, and their value, which is `None` if the variable is
+/// absent.
+static mut VARS: Vec<(OsString, Option<OsString>)> = Vec::new();
+
+#[allow(static_mut_refs)]
+fn vars() -> &'static mut Vec<(OsString, Option<OsString>)> {
+    // SAFETY: Kani verifies the threads of a harness one step at a time, so there are no data
+    // races on the environment.
+    unsafe { &mut VARS }
+}
+
+/// The value of the variable `key`, if the harness accessed it before.
+fn lookup(key: &OsStr) -> Option<&'static mut Option<OsString>> {
+    vars().iter_mut().find(|(name, _)| name == key).map(|(_, value)| value)
+}
+
+fn update(key: &OsStr, value: Option<OsString>) {
+    match lookup(key) {
+        Some(current) => *current = value,
+        None => vars().push((key.to_os_string(), value)),
+    }
+}
+
+/// Model of `std::env::var_os`.
+#[kanitool::fn_marker = "EnvVarOsModel"]
+pub fn var_os<K: AsRef<OsStr>>(key: K) -> Option<OsString> {
+    let key = key.as_ref();
+    if let Some(value) = lookup(key) {
+        return value.clone();
+    }
+    let value = crate::any::<bool>().then(|| OsString::from(String::bounded_any::<MAX_VAR_LEN>()));
+    update(key, value.clone());
+    value
+}
+
+/// Model of `std::env::var`.
+#[kanitool::fn_marker = "EnvVarModel"]
+pub fn var<K: AsRef<OsStr>>(key: K) -> Result<String, VarError> {
+    match var_os(key) {
+        Some(value) => value.into_string().map_err(VarError::NotUnicode),
+        None => Err(VarError::NotPresent),
+    }
+}
+
+/// Model of `std::env::set_var`.
+#[kanitool::fn_marker = "EnvSetVarModel"]
+pub fn set_var<K: AsRef<OsStr>, V: AsRef<OsStr>>(key: K, value: V) {
+    update(key.as_ref(), Some(value.as_ref().to_os_string()))
+}
+
+/// Model of `std::env::remove_var`.
+#[kanitool::fn_marker = "EnvRemoveVarModel"]
+pub fn remove_var<K: AsRef<OsStr>>(key: K) {
+    update(key.as_ref(), None)
+}
@@ -0,0 +1,74 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! A model of the file system, which Kani uses in place of the functions of `std::fs` that read
+//! and write whole files with `-Z unstable-options --model-std-io`.
+//!
+//! The file system is an in-memory map from paths to contents. The first time a harness reads a
+//! file that it didn't write, the file either doesn't exist or holds arbitrary contents of at most
+//! [`MAX_FILE_LEN`] bytes, and later reads return the same contents. Writes always succeed and
+//! replace the contents of the file. Paths are compared as they are given, without resolving them
+//! against the working directory or following links.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The maximum length in bytes of a file that the harness didn't write.
+pub const MAX_FILE_LEN: usize = 4;
+
+/// The files that were read or written, and their contents, which are `None` if the file doesn't
+/// exist.
+static mut FILES: Vec<(PathBuf, Option<Vec<u8>>)> = Vec::new();
+
+/// The contents of the file at `path`, which are initialized by `init` if the harness didn't
+/// access the file before.
+#[allow(static_mut_refs)]
+fn file(path: &Path, init: impl FnOnce() -> Option<Vec<u8>>) -> &'static mut Option<Vec<u8>> {
+    // SAFETY: Kani verifies the threads of a harness one step at a time, so there are no data
+    // races on the file system.
+    let files = unsafe { &mut FILES };
+    let idx = match files.iter().position(|(name, _)| name == path) {
+        Some(idx) => idx,
+        None => {
+            files.push((path.to_path_buf(), init()));
+            files.len() - 1
+        }
+    };
+    &mut files[idx].1
+}
+
+/// The contents of a file that the harness didn't write.
+fn any_contents() -> Option<Vec<u8>> {
+    crate::any::<bool>().then(crate::vec::any_vec::<u8, MAX_FILE_LEN>)
+}
+
+/// Model of `std::fs::read`.
+#[kanitool::fn_marker = "FsReadModel"]
+pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    file(path.as_ref(), any_contents)
+        .clone()
+        .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+}
+
+/// Model of `std::fs::read_to_string`.
+#[kanitool::fn_marker = "FsReadToStringModel"]
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|_| {
+        io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+    })
+}
+
+/// Model of `std::fs::write`.
+#[kanitool::fn_marker = "FsWriteModel"]
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    *file(path.as_ref(), || None) = Some(contents.as_ref().to_vec());
+    Ok(())
+}
+
+/// Model of `std::fs::remove_file`.
+#[kanitool::fn_marker = "FsRemoveFileModel"]
+pub fn remove_file<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    file(path.as_ref(), any_contents)
+        .take()
+        .map(|_| ())
+        .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+}
@@ -0,0 +1,492 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A model of `std::collections::HashMap` that stores its entries in an association list.
+//!
+//! Looking up a key compares it with every entry instead of hashing it, which is much cheaper to
+//! verify than SipHash and the probing of the hash table for the small maps used in harnesses.
+//!
+//! With `--model-std-collections`, Kani replaces `std::collections::HashMap` and its iterators
+//! by the types in this module. Their layout must match the layout of the types they replace,
+//! so each of them is padded to the size of the original type.
+
+use std::borrow::Borrow;
+use std::collections::TryReserveError;
+use std::fmt::{self, Debug};
+use std::hash::{BuildHasher, Hash, RandomState};
+use std::iter::FusedIterator;
+use std::mem::size_of;
+use std::ops::Index;
+
+/// The number of words that a model of `Orig` needs in addition to the fields in `Used`.
+pub(crate) const fn padding<Orig, Used>() -> usize {
+    (size_of::<Orig>() - size_of::<Used>()) / size_of::<usize>()
+}
+
+const MAP_PADDING: usize = padding::<std::collections::HashMap<(), (), ()>, Vec<()>>();
+const ITER_PADDING: usize =
+    padding::<std::collections::hash_map::Iter<'static, (), ()>, std::slice::Iter<'static, ()>>();
+const INTO_ITER_PADDING: usize =
+    padding::<std::collections::hash_map::IntoIter<(), ()>, std::vec::IntoIter<()>>();
+
+/// An association list with the interface of `std::collections::HashMap`.
+///
+/// Keys are unique, and entries are kept in insertion order, except that removing an entry moves
+/// the last entry into its place.
+pub struct HashMap<K, V, S = RandomState> {
+    entries: Vec<(K, V)>,
+    hash_builder: S,
+    _padding: [usize; MAP_PADDING],
+}
+
+impl<K, V> HashMap<K, V, RandomState> {
+    pub fn new() -> HashMap<K, V, RandomState> {
+        HashMap::with_hasher(RandomState::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> HashMap<K, V, RandomState> {
+        HashMap::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S> {
+    pub fn with_hasher(hash_builder: S) -> HashMap<K, V, S> {
+        HashMap { entries: Vec::new(), hash_builder, _padding: [0; MAP_PADDING] }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> HashMap<K, V, S> {
+        HashMap {
+            entries: Vec::with_capacity(capacity),
+            hash_builder: hasher,
+            _padding: [0; MAP_PADDING],
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { entries: self.entries.iter(), _padding: [0; ITER_PADDING] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.entries.retain_mut(|(k, v)| f(k, v))
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear()
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    fn position<Q>(&self, k: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq,
+    {
+        self.entries.iter().position(|(key, _)| key.borrow() == k)
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    pub fn reserve(&mut self, additional: usize) {
+        self.entries.reserve(additional)
+    }
+
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.entries.try_reserve(additional)
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.entries.shrink_to_fit()
+    }
+
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.entries.shrink_to(min_capacity)
+    }
+
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.position(k).map(|idx| &self.entries[idx].1)
+    }
+
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.position(k).map(|idx| {
+            let (key, value) = &self.entries[idx];
+            (key, value)
+        })
+    }
+
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.position(k).is_some()
+    }
+
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.position(k).map(|idx| &mut self.entries[idx].1)
+    }
+
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        match self.position(&k) {
+            Some(idx) => Some(std::mem::replace(&mut self.entries[idx].1, v)),
+            None => {
+                self.entries.push((k, v));
+                None
+            }
+        }
+    }
+
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.remove_entry(k).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.position(k).map(|idx| self.entries.swap_remove(idx))
+    }
+}
+
+impl<K, V, S> Clone for HashMap<K, V, S>
+where
+    K: Clone,
+    V: Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        HashMap {
+            entries: self.entries.clone(),
+            hash_builder: self.hash_builder.clone(),
+            _padding: [0; MAP_PADDING],
+        }
+    }
+}
+
+impl<K, V, S> PartialEq for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &HashMap<K, V, S>) -> bool {
+        self.len() == other.len()
+            && self.iter().all(|(key, value)| other.get(key).is_some_and(|v| *value == *v))
+    }
+}
+
+impl<K, V, S> Eq for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: Eq,
+    S: BuildHasher,
+{
+}
+
+impl<K, V, S> Debug for HashMap<K, V, S>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, S> Default for HashMap<K, V, S>
+where
+    S: Default,
+{
+    fn default() -> HashMap<K, V, S> {
+        HashMap::with_hasher(Default::default())
+    }
+}
+
+impl<K, Q: ?Sized, V, S> Index<&Q> for HashMap<K, V, S>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<K, V, const N: usize> From<[(K, V); N]> for HashMap<K, V, RandomState>
+where
+    K: Eq + Hash,
+{
+    fn from(arr: [(K, V); N]) -> Self {
+        HashMap::from_iter(arr)
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> HashMap<K, V, S> {
+        let mut map = HashMap::with_hasher(Default::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<'a, K, V, S> Extend<(&'a K, &'a V)> for HashMap<K, V, S>
+where
+    K: Eq + Hash + Copy,
+    V: Copy,
+    S: BuildHasher,
+{
+    fn extend<T: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a HashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<K, V, S> IntoIterator for HashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter { entries: self.entries.into_iter(), _padding: [0; INTO_ITER_PADDING] }
+    }
+}
+
+/// An iterator over the entries of a `HashMap`.
+pub struct Iter<'a, K: 'a, V: 'a> {
+    entries: std::slice::Iter<'a, (K, V)>,
+    _padding: [usize; ITER_PADDING],
+}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Iter { entries: self.entries.clone(), _padding: [0; ITER_PADDING] }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for Iter<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.entries.next().map(|(key, value)| (key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+/// An iterator over the keys of a `HashMap`.
+pub struct Keys<'a, K: 'a, V: 'a> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<K, V> Clone for Keys<'_, K, V> {
+    fn clone(&self) -> Self {
+        Keys { inner: self.inner.clone() }
+    }
+}
+
+impl<K: Debug, V> Debug for Keys<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.inner.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+/// An iterator over the values of a `HashMap`.
+pub struct Values<'a, K: 'a, V: 'a> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<K, V> Clone for Values<'_, K, V> {
+    fn clone(&self) -> Self {
+        Values { inner: self.inner.clone() }
+    }
+}
+
+impl<K, V: Debug> Debug for Values<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.inner.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+impl<K, V> FusedIterator for Values<'_, K, V> {}
+
+/// An owning iterator over the entries of a `HashMap`.
+pub struct IntoIter<K, V> {
+    entries: std::vec::IntoIter<(K, V)>,
+    _padding: [usize; INTO_ITER_PADDING],
+}
+
+impl<K: Debug, V: Debug> Debug for IntoIter<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.entries.as_slice()).finish()
+    }
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        self.entries.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Check that the models have the same size as the types they replace.
+    #[test]
+    fn test_layout() {
+        use std::collections::{hash_map, hash_set};
+        assert_eq!(size_of::<HashMap<u8, u64>>(), size_of::<std::collections::HashMap<u8, u64>>());
+        assert_eq!(size_of::<Iter<u8, u64>>(), size_of::<hash_map::Iter<u8, u64>>());
+        assert_eq!(size_of::<Keys<u8, u64>>(), size_of::<hash_map::Keys<u8, u64>>());
+        assert_eq!(size_of::<Values<u8, u64>>(), size_of::<hash_map::Values<u8, u64>>());
+        assert_eq!(size_of::<IntoIter<u8, u64>>(), size_of::<hash_map::IntoIter<u8, u64>>());
+        assert_eq!(
+            size_of::<super::super::HashSet<u32>>(),
+            size_of::<std::collections::HashSet<u32>>()
+        );
+        assert_eq!(
+            size_of::<super::super::hash_set::Iter<u32>>(),
+            size_of::<hash_set::Iter<u32>>()
+        );
+        assert_eq!(
+            size_of::<super::super::hash_set::IntoIter<u32>>(),
+            size_of::<hash_set::IntoIter<u32>>()
+        );
+    }
+
+    #[test]
+    fn test_map_operations() {
+        let mut map = HashMap::new();
+        assert_eq!(map.insert(1, 'a'), None);
+        assert_eq!(map.insert(2, 'b'), None);
+        assert_eq!(map.insert(1, 'c'), Some('a'));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&1], 'c');
+        assert_eq!(map.remove(&1), Some('c'));
+        assert_eq!(map.get(&1), None);
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), vec![(2, 'b')]);
+    }
+}
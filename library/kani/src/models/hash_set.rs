@@ -0,0 +1,336 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! A model of `std::collections::HashSet` built on top of the [`HashMap`] model.
+//!
+//! With `--model-std-collections`, Kani replaces `std::collections::HashSet` and its iterators
+//! by the types in this module.
+
+use super::hash_map::{self, HashMap, Keys, padding};
+use std::borrow::Borrow;
+use std::collections::TryReserveError;
+use std::fmt::{self, Debug};
+use std::hash::{BuildHasher, Hash, RandomState};
+use std::iter::FusedIterator;
+
+const SET_PADDING: usize =
+    padding::<std::collections::HashSet<(), ()>, std::collections::HashMap<(), (), ()>>();
+const ITER_PADDING: usize = padding::<
+    std::collections::hash_set::Iter<'static, ()>,
+    std::collections::hash_map::Keys<'static, (), ()>,
+>();
+const INTO_ITER_PADDING: usize = padding::<
+    std::collections::hash_set::IntoIter<()>,
+    std::collections::hash_map::IntoIter<(), ()>,
+>();
+
+/// A set with the interface of `std::collections::HashSet`, stored as a [`HashMap`] from its
+/// elements to `()`.
+pub struct HashSet<T, S = RandomState> {
+    map: HashMap<T, (), S>,
+    _padding: [usize; SET_PADDING],
+}
+
+impl<T> HashSet<T, RandomState> {
+    pub fn new() -> HashSet<T, RandomState> {
+        HashSet::with_hasher(RandomState::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> HashSet<T, RandomState> {
+        HashSet::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<T, S> HashSet<T, S> {
+    pub fn capacity(&self) -> usize {
+        self.map.capacity()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { keys: self.map.keys(), _padding: [0; ITER_PADDING] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.map.retain(|k, _| f(k))
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    pub fn with_hasher(hasher: S) -> HashSet<T, S> {
+        HashSet { map: HashMap::with_hasher(hasher), _padding: [0; SET_PADDING] }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> HashSet<T, S> {
+        HashSet {
+            map: HashMap::with_capacity_and_hasher(capacity, hasher),
+            _padding: [0; SET_PADDING],
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+}
+
+impl<T, S> HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    pub fn reserve(&mut self, additional: usize) {
+        self.map.reserve(additional)
+    }
+
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.map.try_reserve(additional)
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.map.shrink_to_fit()
+    }
+
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.map.shrink_to(min_capacity)
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.map.contains_key(value)
+    }
+
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.map.get_key_value(value).map(|(k, _)| k)
+    }
+
+    pub fn get_or_insert(&mut self, value: T) -> &T {
+        if !self.map.contains_key(&value) {
+            self.map.insert(value, ());
+            return self.map.keys().last().unwrap();
+        }
+        self.map.get_key_value(&value).unwrap().0
+    }
+
+    pub fn is_disjoint(&self, other: &HashSet<T, S>) -> bool {
+        self.iter().all(|v| !other.contains(v))
+    }
+
+    pub fn is_subset(&self, other: &HashSet<T, S>) -> bool {
+        self.len() <= other.len() && self.iter().all(|v| other.contains(v))
+    }
+
+    pub fn is_superset(&self, other: &HashSet<T, S>) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn insert(&mut self, value: T) -> bool {
+        self.map.insert(value, ()).is_none()
+    }
+
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        let old = self.map.remove_entry(&value).map(|(k, _)| k);
+        self.map.insert(value, ());
+        old
+    }
+
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.map.remove_entry(value).map(|(k, _)| k)
+    }
+}
+
+impl<T, S> Clone for HashSet<T, S>
+where
+    T: Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        HashSet { map: self.map.clone(), _padding: [0; SET_PADDING] }
+    }
+}
+
+impl<T, S> PartialEq for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &HashSet<T, S>) -> bool {
+        self.len() == other.len() && self.is_subset(other)
+    }
+}
+
+impl<T, S> Eq for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+}
+
+impl<T, S> Debug for HashSet<T, S>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S> Default for HashSet<T, S>
+where
+    S: Default,
+{
+    fn default() -> HashSet<T, S> {
+        HashSet::with_hasher(Default::default())
+    }
+}
+
+impl<T, S> FromIterator<T> for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> HashSet<T, S> {
+        let mut set = HashSet::with_hasher(Default::default());
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for HashSet<T, RandomState>
+where
+    T: Eq + Hash,
+{
+    fn from(arr: [T; N]) -> Self {
+        HashSet::from_iter(arr)
+    }
+}
+
+impl<T, S> Extend<T> for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.map.extend(iter.into_iter().map(|k| (k, ())));
+    }
+}
+
+impl<'a, T, S> Extend<&'a T> for HashSet<T, S>
+where
+    T: 'a + Eq + Hash + Copy,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a HashSet<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T, S> IntoIterator for HashSet<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { iter: self.map.into_iter(), _padding: [0; INTO_ITER_PADDING] }
+    }
+}
+
+/// An iterator over the elements of a `HashSet`.
+pub struct Iter<'a, K: 'a> {
+    keys: Keys<'a, K, ()>,
+    _padding: [usize; ITER_PADDING],
+}
+
+impl<K> Clone for Iter<'_, K> {
+    fn clone(&self) -> Self {
+        Iter { keys: self.keys.clone(), _padding: [0; ITER_PADDING] }
+    }
+}
+
+impl<K: Debug> Debug for Iter<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K> Iterator for Iter<'a, K> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.keys.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<K> ExactSizeIterator for Iter<'_, K> {}
+
+impl<K> FusedIterator for Iter<'_, K> {}
+
+/// An owning iterator over the elements of a `HashSet`.
+pub struct IntoIter<K> {
+    iter: hash_map::IntoIter<K, ()>,
+    _padding: [usize; INTO_ITER_PADDING],
+}
+
+impl<K: Debug> Debug for IntoIter<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.iter.fmt(f)
+    }
+}
+
+impl<K> Iterator for IntoIter<K> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        self.iter.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K> ExactSizeIterator for IntoIter<K> {}
+
+impl<K> FusedIterator for IntoIter<K> {}
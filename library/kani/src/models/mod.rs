@@ -4,6 +4,19 @@
 //! verification or functions without a body, such as intrinsics.
 //!
 //! Note that these are models that Kani uses by default; thus, we keep them separate from stubs.
+//! The exception are the models of the standard library collections, clocks, environment and file
+//! system, which Kani only uses with `--model-std-collections`, `--model-time` and
+//! `--model-std-io`, and which can also be used directly in harnesses.
+
+pub mod collections;
+pub mod env;
+pub mod fs;
+pub mod hash_map;
+pub mod hash_set;
+pub mod time;
+
+pub use hash_map::HashMap;
+pub use hash_set::HashSet;
 
 // Definitions in this module are not meant to be visible to the end user, only the compiler.
 #[allow(dead_code)]
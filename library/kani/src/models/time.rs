@@ -0,0 +1,82 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Models of the clocks of `std::time`, which Kani uses in place of `Instant::now` and
+//! `SystemTime::now` with `-Z unstable-options --model-time`.
+//!
+//! The clocks are nondeterministic, so a harness checks its timeout and retry logic for every
+//! amount of time that elapses between two readings of a clock:
+//! - The monotonic clock never goes backwards: every call to [`instant_now`] returns an instant
+//!   that is equal to or later than the one returned by the previous call.
+//! - The system clock can be adjusted at any time, so [`system_time_now`] may return any time,
+//!   even one that is earlier than a previous reading.
+//!
+//! Harnesses can also call these functions directly, e.g., to get an arbitrary instant without
+//! replacing the clocks of the standard library.
+
+use std::time::{Duration, Instant, SystemTime};
+
+/// The instant returned by the previous call to [`instant_now`], if any.
+static mut LAST_INSTANT: Option<Instant> = None;
+
+/// Read the monotonic clock, which has advanced by an arbitrary duration since the previous
+/// reading. The first reading is an arbitrary instant.
+///
+/// ```no_run
+/// #[kani::proof]
+/// fn check_monotonic() {
+///     let first = kani::models::time::instant_now();
+///     let second = kani::models::time::instant_now();
+///     assert!(second >= first);
+/// }
+/// ```
+#[kanitool::fn_marker = "InstantNowModel"]
+pub fn instant_now() -> Instant {
+    // SAFETY: Kani verifies the threads of a harness one step at a time, so there are no data
+    // races on the clock.
+    let now = match unsafe { LAST_INSTANT } {
+        Some(last) => {
+            let next = last.checked_add(Duration::from_nanos(crate::any()));
+            crate::assume(next.is_some());
+            next.unwrap()
+        }
+        None => any_instant(),
+    };
+    unsafe { LAST_INSTANT = Some(now) };
+    now
+}
+
+/// Read the system clock, which may return any time that is within `u64::MAX` nanoseconds (about
+/// 584 years) of the UNIX epoch, before or after it.
+#[kanitool::fn_marker = "SystemTimeNowModel"]
+pub fn system_time_now() -> SystemTime {
+    let offset = Duration::from_nanos(crate::any());
+    let time = if crate::any() {
+        SystemTime::UNIX_EPOCH.checked_add(offset)
+    } else {
+        SystemTime::UNIX_EPOCH.checked_sub(offset)
+    };
+    crate::assume(time.is_some());
+    time.unwrap()
+}
+
+/// An arbitrary instant. Since `Instant` is opaque, we build it from arbitrary bytes that form a
+/// valid value of the type.
+#[cfg(not(feature = "concrete_playback"))]
+fn any_instant() -> Instant {
+    let bytes: [u8; size_of::<Instant>()] = crate::any();
+    let mut value = std::mem::MaybeUninit::<Instant>::uninit();
+    // SAFETY: `value` has the size of `bytes`, and they can't overlap.
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), value.as_mut_ptr() as *mut u8, bytes.len())
+    };
+    // SAFETY: `value` is aligned, and all its bytes were initialized.
+    crate::assume(unsafe { crate::mem::has_valid_value(value.as_ptr()) });
+    // SAFETY: `value` holds a valid instant.
+    unsafe { value.assume_init() }
+}
+
+/// Concrete playback executes the harness natively, where the real clock is available.
+#[cfg(feature = "concrete_playback")]
+fn any_instant() -> Instant {
+    Instant::now()
+}
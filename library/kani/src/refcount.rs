@@ -0,0 +1,90 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Bounded model of the reference counting of `Rc` and `Arc`.
+//!
+//! With `-Z unstable-options --check-refcounts`, the compiler calls the hooks of this module
+//! whenever the standard library updates the strong count of an `Rc` or an `Arc`, and at the end
+//! of every harness. Kani then checks that:
+//! - A strong count is never decremented when it's already zero, which happens when an allocation
+//!   is released twice, e.g., with [`Rc::decrement_strong_count`](std::rc::Rc).
+//! - The strong count of every allocation that the harness updated is zero when the harness
+//!   returns, i.e., the allocation isn't leaked, e.g., by a reference cycle.
+//!
+//! Rather than keeping a table of counters, the model tracks a single counter, which it picks
+//! nondeterministically among the ones that are updated. Since the verification covers every
+//! choice, the leak check applies to every counter, while the cost of the model doesn't depend on
+//! the number of allocations.
+//!
+//! An allocation whose count is never updated after its creation, e.g., an `Rc` that is passed to
+//! [`std::mem::forget`] without being cloned, isn't checked for leaks.
+
+use core::cell::Cell;
+use core::sync::atomic::AtomicUsize;
+
+/// The counter that the model tracks, and its value after its latest update.
+#[derive(Clone, Copy)]
+struct Tracked {
+    counter: *const (),
+    value: usize,
+}
+
+/// The counter that is tracked in the current harness, if one was picked.
+static mut TRACKED: Option<Tracked> = None;
+
+/// Record that the strong count at `counter` is now `value`.
+fn strong_updated(counter: *const (), value: usize) {
+    let tracked = unsafe { TRACKED };
+    match tracked {
+        Some(Tracked { counter: tracked, .. }) if tracked == counter => unsafe {
+            TRACKED = Some(Tracked { counter, value })
+        },
+        Some(_) => {}
+        None => {
+            if kani::any() {
+                unsafe { TRACKED = Some(Tracked { counter, value }) }
+            }
+        }
+    }
+}
+
+/// Check that the strong count that is about to be decremented isn't zero.
+fn strong_decrement(value: usize) {
+    kani::assert(value != 0, "reference count must not be decremented below zero (double free)");
+}
+
+/// The compiler calls this function after the strong count of an `Rc` was updated.
+#[doc(hidden)]
+pub fn rc_strong_updated(counter: &Cell<usize>) {
+    strong_updated(counter as *const Cell<usize> as *const (), counter.get());
+}
+
+/// The compiler calls this function before the strong count of an `Rc` is decremented.
+#[doc(hidden)]
+pub fn rc_strong_decrement(counter: &Cell<usize>) {
+    strong_decrement(counter.get());
+}
+
+/// The compiler calls this function after the strong count of an `Arc` was updated.
+///
+/// The counter is read without an atomic operation, so that the model doesn't add a preemption
+/// point with `--concurrency`.
+#[doc(hidden)]
+pub fn arc_strong_updated(counter: &AtomicUsize) {
+    strong_updated(counter as *const AtomicUsize as *const (), unsafe { *counter.as_ptr() });
+}
+
+/// The compiler calls this function before the strong count of an `Arc` is decremented.
+#[doc(hidden)]
+pub fn arc_strong_decrement(counter: &AtomicUsize) {
+    strong_decrement(unsafe { *counter.as_ptr() });
+}
+
+/// Check that the tracked counter, if any, dropped to zero.
+///
+/// The compiler calls this function before every harness returns.
+#[doc(hidden)]
+pub fn check_refcounts() {
+    if let Some(Tracked { value, .. }) = unsafe { TRACKED } {
+        kani::assert(value == 0, "reference counted allocation must be released (leak)");
+    }
+}
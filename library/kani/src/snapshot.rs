@@ -0,0 +1,52 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Start the verification from a state that was captured from a running program, e.g., a RAM
+//! snapshot of an embedded device.
+//!
+//! A harness can build a value from the bytes of a snapshot with [`from_snapshot`]. To initialize
+//! a static with a snapshot instead, pass `-Z unstable-options --snapshot <STATIC>=<FILE>` to Kani.
+
+/// Build a value of type `T` from its in-memory representation, e.g., the bytes of a snapshot
+/// that was captured from a running program.
+///
+/// Kani checks that `bytes` has the size of `T` and that it's a valid value of `T`, e.g., that a
+/// `bool` is either `0` or `1`. The bytes don't need to be aligned.
+///
+/// The snapshot must not contain pointers, since they wouldn't point to any allocation of the
+/// harness.
+///
+/// ```no_run
+/// #[repr(C)]
+/// #[derive(Clone, Copy)]
+/// struct State {
+///     mode: u8,
+///     ticks: u32,
+/// }
+///
+/// #[kani::proof]
+/// fn check_from_snapshot() {
+///     // The bytes of a snapshot, e.g., from `include_bytes!("state.bin")`.
+///     let snapshot: [u8; 8] = [2, 0, 0, 0, 0x10, 0x27, 0, 0];
+///     let state: State = kani::from_snapshot(&snapshot);
+///     assert!(state.mode < 4);
+/// }
+/// ```
+pub fn from_snapshot<T>(bytes: &[u8]) -> T {
+    crate::assert(
+        bytes.len() == core::mem::size_of::<T>(),
+        "snapshot must have the size of the type",
+    );
+    let mut value = core::mem::MaybeUninit::<T>::uninit();
+    // SAFETY: The snapshot has the size of `T`, and `value` can't overlap with it.
+    unsafe {
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), value.as_mut_ptr() as *mut u8, bytes.len())
+    };
+    #[cfg(not(feature = "concrete_playback"))]
+    // SAFETY: `value` is aligned, and all its bytes were initialized.
+    crate::assert(
+        unsafe { crate::mem::has_valid_value(value.as_ptr()) },
+        "snapshot must hold a valid value of the type",
+    );
+    // SAFETY: `value` holds a valid value of `T`.
+    unsafe { value.assume_init() }
+}
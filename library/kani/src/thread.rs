@@ -0,0 +1,603 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Models of `std::thread` for the bounded verification of concurrent code.
+//!
+//! Kani verifies sequential programs, so with `-Z unstable-options --concurrency bounded:N`,
+//! [`spawn`] doesn't start a thread. It registers the closure of the thread with a scheduler, which
+//! may start the thread at any preemption point that follows: the calls to [`spawn`] and
+//! [`yield_now`], and the atomic operations of the crates under verification. Once started, a
+//! thread runs to completion, but its own preemption points may start other threads. A thread that
+//! didn't start by the time it's joined starts when it's joined.
+//!
+//! The scheduler starts at most `N` threads at preemption points, i.e., it explores every schedule
+//! with up to `N` context switches. This is a subset of the schedules of the program, so
+//! a verified harness proves that no schedule within the bound violates a property, not that no
+//! schedule does. For example, the following harness fails, since the spawned thread may increment
+//! the counter between the load and the store of the main thread:
+//! ```no_run
+//! use std::sync::Arc;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! #[kani::proof]
+//! fn check_lost_update() {
+//!     let counter = Arc::new(AtomicUsize::new(0));
+//!     let other = counter.clone();
+//!     let handle = std::thread::spawn(move || {
+//!         other.fetch_add(1, Ordering::SeqCst);
+//!     });
+//!     let value = counter.load(Ordering::SeqCst);
+//!     counter.store(value + 1, Ordering::SeqCst);
+//!     handle.join().unwrap();
+//!     assert_eq!(counter.load(Ordering::SeqCst), 2);
+//! }
+//! ```
+//!
+//! Without `--concurrency`, these functions forward to the ones of the standard library.
+//!
+//! # Weak memory
+//!
+//! By default, the atomic operations are sequentially consistent, whatever their ordering. With
+//! `--memory-model release-acquire`, they follow the release/acquire semantics of C11 instead: a
+//! load may read any store of the location that the thread didn't observe to be overwritten, and
+//! only the acquire operations that read a release store synchronize with the thread that made it.
+//! For example, the spawned thread of the following harness may read the flag before the data:
+//! ```no_run
+//! use std::sync::Arc;
+//! use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+//!
+//! #[kani::proof]
+//! fn check_message_passing() {
+//!     let data = Arc::new(AtomicUsize::new(0));
+//!     let flag = Arc::new(AtomicBool::new(false));
+//!     let (data2, flag2) = (data.clone(), flag.clone());
+//!     let handle = std::thread::spawn(move || {
+//!         if flag2.load(Ordering::Relaxed) {
+//!             // Fails, since the load of the flag should use `Ordering::Acquire`.
+//!             assert_eq!(data2.load(Ordering::Relaxed), 42);
+//!         }
+//!     });
+//!     data.store(42, Ordering::Relaxed);
+//!     flag.store(true, Ordering::Release);
+//!     handle.join().unwrap();
+//! }
+//! ```
+//!
+//! To keep the programs that only use `SeqCst` sequentially consistent, `SeqCst` loads always read
+//! the latest store. Fences synchronize the thread with every store that happened before them,
+//! which is stronger than C11, so some weak behaviors of code that relies on fences are missed.
+
+use std::cell::{OnceCell, RefCell};
+use std::fmt;
+use std::io;
+use std::mem;
+use std::rc::Rc;
+use std::sync::atomic::Ordering;
+
+type Thread = Box<dyn FnOnce()>;
+
+/// The scheduler of the threads of a harness.
+struct Scheduler {
+    /// The closures of the threads that were spawned, until they are started.
+    pending: Vec<Option<Thread>>,
+    /// The number of context switches that the scheduler can still make.
+    switches_left: usize,
+    /// The running thread: `0` for the harness, and `id + 1` for the thread `id`.
+    current: usize,
+    /// The model of the memory of `--memory-model release-acquire`.
+    memory: Option<WeakMemory>,
+}
+
+/// The scheduler of the current harness, which is set by [`init_scheduler`].
+static mut SCHEDULER: Option<Scheduler> = None;
+
+#[allow(static_mut_refs)]
+fn scheduler() -> Option<&'static mut Scheduler> {
+    unsafe { SCHEDULER.as_mut() }
+}
+
+/// Enable the scheduler, with a bound of `bound` context switches.
+///
+/// The compiler calls this function at the beginning of every harness when `--concurrency` is
+/// enabled.
+#[doc(hidden)]
+pub fn init_scheduler(bound: usize) {
+    let scheduler =
+        Scheduler { pending: Vec::new(), switches_left: bound, current: 0, memory: None };
+    unsafe { SCHEDULER = Some(scheduler) }
+}
+
+/// Make the atomic operations follow the release/acquire semantics.
+///
+/// The compiler calls this function at the beginning of every harness, after [`init_scheduler`],
+/// when `--memory-model release-acquire` is enabled.
+#[doc(hidden)]
+pub fn init_weak_memory() {
+    if let Some(scheduler) = scheduler() {
+        scheduler.memory = Some(WeakMemory::default());
+    }
+}
+
+/// Run the thread `id` to completion.
+fn run(id: usize, thread: Thread) {
+    let parent = scheduler().map(|scheduler| mem::replace(&mut scheduler.current, id + 1));
+    thread();
+    if let (Some(scheduler), Some(parent)) = (scheduler(), parent) {
+        scheduler.current = parent;
+    }
+}
+
+/// Nondeterministically start one of the threads that didn't start yet, as long as the bound of
+/// context switches isn't reached.
+///
+/// The compiler calls this function before every atomic operation when `--concurrency` is enabled.
+#[doc(hidden)]
+pub fn preemption_point() {
+    let Some(scheduler) = scheduler() else { return };
+    if scheduler.switches_left == 0 || scheduler.pending.is_empty() || !kani::any::<bool>() {
+        return;
+    }
+    let id: usize = kani::any_where(|id| *id < scheduler.pending.len());
+    // Threads that already started can't be started again.
+    let thread = scheduler.pending[id].take();
+    kani::assume(thread.is_some());
+    if let Some(thread) = thread {
+        scheduler.switches_left -= 1;
+        run(id, thread)
+    }
+}
+
+/// Spawn a new thread, like `std::thread::spawn`.
+///
+/// With `--concurrency`, the thread runs at one of the following preemption points, or when it's
+/// joined. See the [module documentation](self) for more details.
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    match scheduler() {
+        Some(scheduler) => spawn_model(scheduler, f),
+        None => JoinHandle(Inner::Std(std::thread::spawn(f))),
+    }
+}
+
+/// Register the closure of a new thread with the scheduler.
+fn spawn_model<F, T>(scheduler: &mut Scheduler, f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let result = Rc::new(RefCell::new(None));
+    let slot = result.clone();
+    let id = scheduler.pending.len();
+    scheduler.pending.push(Some(Box::new(move || *slot.borrow_mut() = Some(f()))));
+    // The new thread observes everything that its parent observed.
+    if let Some(memory) = &mut scheduler.memory {
+        let view = memory.thread(scheduler.current).view.clone();
+        memory.thread(id + 1).view = view;
+    }
+    // The new thread may start right away.
+    preemption_point();
+    JoinHandle(Inner::Model { id, result, thread: OnceCell::new() })
+}
+
+/// A thread factory, which replaces `std::thread::Builder`.
+///
+/// With `--concurrency`, the threads of the scheduler have no name or stack of their own, so the
+/// configuration of the builder only applies to the threads of the standard library.
+#[derive(Debug)]
+pub struct Builder(std::thread::Builder);
+
+impl Builder {
+    /// Create a builder, like `std::thread::Builder::new`.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Builder {
+        Builder(std::thread::Builder::new())
+    }
+
+    /// Name the thread, like `std::thread::Builder::name`.
+    pub fn name(self, name: String) -> Builder {
+        Builder(self.0.name(name))
+    }
+
+    /// Set the size of the stack of the thread, like `std::thread::Builder::stack_size`.
+    pub fn stack_size(self, size: usize) -> Builder {
+        Builder(self.0.stack_size(size))
+    }
+
+    /// Spawn a new thread, like `std::thread::Builder::spawn`.
+    pub fn spawn<F, T>(self, f: F) -> io::Result<JoinHandle<T>>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        match scheduler() {
+            Some(scheduler) => Ok(spawn_model(scheduler, f)),
+            None => self.0.spawn(f).map(|handle| JoinHandle(Inner::Std(handle))),
+        }
+    }
+
+    /// Spawn a new scoped thread, like `std::thread::Builder::spawn_scoped`.
+    ///
+    /// Scoped threads aren't modeled, so this always spawns a thread of the standard library.
+    pub fn spawn_scoped<'scope, 'env, F, T>(
+        self,
+        scope: &'scope std::thread::Scope<'scope, 'env>,
+        f: F,
+    ) -> io::Result<std::thread::ScopedJoinHandle<'scope, T>>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        self.0.spawn_scoped(scope, f)
+    }
+}
+
+/// Let the scheduler start another thread, like `std::thread::yield_now`.
+pub fn yield_now() {
+    if scheduler().is_some() { preemption_point() } else { std::thread::yield_now() }
+}
+
+/// An owned permission to join on a thread, which replaces `std::thread::JoinHandle`.
+pub struct JoinHandle<T>(Inner<T>);
+
+enum Inner<T> {
+    /// A thread of the standard library, if `--concurrency` isn't enabled.
+    Std(std::thread::JoinHandle<T>),
+    /// A thread of the scheduler, the slot where it stores its result, and the handle of the thread
+    /// of the harness, which runs it.
+    Model { id: usize, result: Rc<RefCell<Option<T>>>, thread: OnceCell<std::thread::Thread> },
+}
+
+// The threads of the scheduler run on the thread of the harness, so sharing the slot of their
+// result between them is safe.
+unsafe impl<T: Send> Send for JoinHandle<T> {}
+unsafe impl<T: Send> Sync for JoinHandle<T> {}
+
+impl<T> JoinHandle<T> {
+    /// Wait for the thread to finish, like `std::thread::JoinHandle::join`.
+    ///
+    /// Panics of the thread are verification failures, so this never returns an error with
+    /// `--concurrency`.
+    pub fn join(self) -> std::thread::Result<T> {
+        match self.0 {
+            Inner::Std(handle) => handle.join(),
+            Inner::Model { id, result, .. } => {
+                // The joining thread blocks until the thread finishes, so start it if it didn't.
+                let thread = scheduler().and_then(|scheduler| scheduler.pending[id].take());
+                if let Some(thread) = thread {
+                    run(id, thread);
+                }
+                // The joining thread observes everything that the thread observed.
+                if let Some((memory, current)) = weak_memory() {
+                    let view = memory.thread(id + 1).view.clone();
+                    memory.thread(current).view.join(&view);
+                }
+                // A thread that joins a thread that started it would wait for a thread that
+                // can't resume in this model, so we discard this schedule.
+                let value = result.borrow_mut().take();
+                kani::assume(value.is_some());
+                Ok(value.unwrap())
+            }
+        }
+    }
+
+    /// Check if the thread has finished running, like `std::thread::JoinHandle::is_finished`.
+    pub fn is_finished(&self) -> bool {
+        match &self.0 {
+            Inner::Std(handle) => handle.is_finished(),
+            Inner::Model { result, .. } => result.borrow().is_some(),
+        }
+    }
+
+    /// Return the handle of the thread, like `std::thread::JoinHandle::thread`.
+    ///
+    /// The threads of the scheduler run on the thread of the harness, so this returns its handle
+    /// with `--concurrency`.
+    pub fn thread(&self) -> &std::thread::Thread {
+        match &self.0 {
+            Inner::Std(handle) => handle.thread(),
+            Inner::Model { thread, .. } => thread.get_or_init(std::thread::current),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl<T> std::os::unix::thread::JoinHandleExt for JoinHandle<T> {
+    fn as_pthread_t(&self) -> std::os::unix::thread::RawPthread {
+        match &self.0 {
+            Inner::Std(handle) => handle.as_pthread_t(),
+            Inner::Model { .. } => {
+                crate::unsupported("the threads of `--concurrency` have no pthread")
+            }
+        }
+    }
+
+    fn into_pthread_t(self) -> std::os::unix::thread::RawPthread {
+        match self.0 {
+            Inner::Std(handle) => handle.into_pthread_t(),
+            Inner::Model { .. } => {
+                crate::unsupported("the threads of `--concurrency` have no pthread")
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+impl<T> std::os::windows::io::AsRawHandle for JoinHandle<T> {
+    fn as_raw_handle(&self) -> std::os::windows::io::RawHandle {
+        match &self.0 {
+            Inner::Std(handle) => handle.as_raw_handle(),
+            Inner::Model { .. } => {
+                crate::unsupported("the threads of `--concurrency` have no handle")
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+impl<T> std::os::windows::io::IntoRawHandle for JoinHandle<T> {
+    fn into_raw_handle(self) -> std::os::windows::io::RawHandle {
+        match self.0 {
+            Inner::Std(handle) => handle.into_raw_handle(),
+            Inner::Model { .. } => {
+                crate::unsupported("the threads of `--concurrency` have no handle")
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+impl<T> std::os::windows::io::AsHandle for JoinHandle<T> {
+    fn as_handle(&self) -> std::os::windows::io::BorrowedHandle<'_> {
+        match &self.0 {
+            Inner::Std(handle) => handle.as_handle(),
+            Inner::Model { .. } => {
+                crate::unsupported("the threads of `--concurrency` have no handle")
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+impl<T> From<JoinHandle<T>> for std::os::windows::io::OwnedHandle {
+    fn from(handle: JoinHandle<T>) -> Self {
+        match handle.0 {
+            Inner::Std(handle) => handle.into(),
+            Inner::Model { .. } => {
+                crate::unsupported("the threads of `--concurrency` have no handle")
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinHandle").finish_non_exhaustive()
+    }
+}
+
+/// The size of the largest atomic type.
+const MAX_ATOMIC_SIZE: usize = 16;
+
+/// The bytes of the value of an atomic type.
+type Value = [u8; MAX_ATOMIC_SIZE];
+
+fn encode<T: Copy>(value: T) -> Value {
+    assert!(mem::size_of::<T>() <= MAX_ATOMIC_SIZE, "unexpected size of atomic type");
+    let mut bytes = [0; MAX_ATOMIC_SIZE];
+    unsafe {
+        std::ptr::copy_nonoverlapping(
+            &value as *const T as *const u8,
+            bytes.as_mut_ptr(),
+            mem::size_of::<T>(),
+        )
+    };
+    bytes
+}
+
+fn decode<T: Copy>(bytes: &Value) -> T {
+    unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const T) }
+}
+
+/// For every location, the index of the oldest store of the location that a thread may read.
+#[derive(Clone, Debug, Default)]
+struct View(Vec<usize>);
+
+impl View {
+    fn get(&self, location: usize) -> usize {
+        self.0.get(location).copied().unwrap_or(0)
+    }
+
+    fn set(&mut self, location: usize, store: usize) {
+        if self.0.len() <= location {
+            self.0.resize(location + 1, 0);
+        }
+        self.0[location] = store;
+    }
+
+    /// Merge `other` into this view, so that the thread no longer reads the stores that were
+    /// overwritten in `other`.
+    fn join(&mut self, other: &View) {
+        for (location, store) in other.0.iter().enumerate() {
+            if *store > self.get(location) {
+                self.set(location, *store);
+            }
+        }
+    }
+}
+
+struct Store {
+    value: Value,
+    /// The view that an acquire load of this store synchronizes with, if it's a release store.
+    view: Option<View>,
+}
+
+/// The stores of an atomic location, in modification order.
+struct Location {
+    address: usize,
+    stores: Vec<Store>,
+}
+
+#[derive(Default)]
+struct ThreadMemory {
+    view: View,
+    /// Whether the thread executed a release fence, which makes all its later stores release
+    /// stores.
+    release_fence: bool,
+}
+
+/// The state of the release/acquire memory model.
+#[derive(Default)]
+struct WeakMemory {
+    locations: Vec<Location>,
+    /// The memory of every thread, indexed like [`Scheduler::current`].
+    threads: Vec<ThreadMemory>,
+}
+
+impl WeakMemory {
+    fn thread(&mut self, thread: usize) -> &mut ThreadMemory {
+        if self.threads.len() <= thread {
+            self.threads.resize_with(thread + 1, ThreadMemory::default);
+        }
+        &mut self.threads[thread]
+    }
+
+    /// Return the location at `address`, whose current value is `value`.
+    ///
+    /// If this is the first atomic access to the location, or if its value was changed by a
+    /// non-atomic write since the last atomic access, e.g., because the memory was reused, this
+    /// starts its history with the current value.
+    fn track(&mut self, address: usize, value: Value) -> usize {
+        let location = match self.locations.iter().position(|loc| loc.address == address) {
+            Some(location) => location,
+            None => {
+                self.locations.push(Location { address, stores: Vec::new() });
+                self.locations.len() - 1
+            }
+        };
+        let stores = &mut self.locations[location].stores;
+        if stores.last().is_none_or(|store| store.value != value) {
+            stores.clear();
+            stores.push(Store { value, view: None });
+            for thread in &mut self.threads {
+                thread.view.set(location, 0);
+            }
+        }
+        location
+    }
+
+    fn location(&self, address: usize) -> usize {
+        self.locations.iter().position(|loc| loc.address == address).unwrap()
+    }
+}
+
+fn weak_memory() -> Option<(&'static mut WeakMemory, usize)> {
+    let scheduler = scheduler()?;
+    Some((scheduler.memory.as_mut()?, scheduler.current))
+}
+
+fn is_acquire(order: Ordering) -> bool {
+    matches!(order, Ordering::Acquire | Ordering::AcqRel | Ordering::SeqCst)
+}
+
+fn is_release(order: Ordering) -> bool {
+    matches!(order, Ordering::Release | Ordering::AcqRel | Ordering::SeqCst)
+}
+
+/// Return the value that an atomic load of `dst` with `order` reads, where `latest` is the value
+/// of the latest store to `dst`.
+///
+/// The compiler calls this function at the end of `core::sync::atomic::atomic_load` when
+/// `--memory-model release-acquire` is enabled.
+#[doc(hidden)]
+pub unsafe fn weak_load<T: Copy>(dst: *const T, latest: T, order: Ordering) -> T {
+    let Some((memory, thread)) = weak_memory() else { return latest };
+    let location = memory.track(dst as usize, encode(latest));
+    let newest = memory.locations[location].stores.len() - 1;
+    let oldest = memory.thread(thread).view.get(location);
+    let store = if order == Ordering::SeqCst {
+        newest
+    } else {
+        kani::any_where(|store: &usize| oldest <= *store && *store <= newest)
+    };
+    memory.thread(thread).view.set(location, store);
+    if is_acquire(order)
+        && let Some(view) = memory.locations[location].stores[store].view.clone()
+    {
+        memory.thread(thread).view.join(&view);
+    }
+    decode(&memory.locations[location].stores[store].value)
+}
+
+/// Record the value of `dst` before an atomic store or read-modify-write operation.
+///
+/// The compiler calls this function at the beginning of the atomic operations that write to `dst`
+/// when `--memory-model release-acquire` is enabled.
+#[doc(hidden)]
+pub unsafe fn weak_track<T: Copy>(dst: *mut T) {
+    if let Some((memory, _)) = weak_memory() {
+        memory.track(dst as usize, encode(unsafe { *dst }));
+    }
+}
+
+/// Record the store of the current value of `dst` by an atomic store with `order`.
+///
+/// The compiler calls this function at the end of `core::sync::atomic::atomic_store` when
+/// `--memory-model release-acquire` is enabled.
+#[doc(hidden)]
+pub unsafe fn weak_store<T: Copy>(dst: *mut T, order: Ordering) {
+    let Some((memory, thread)) = weak_memory() else { return };
+    let value = encode(unsafe { *dst });
+    let location = memory.location(dst as usize);
+    let index = memory.locations[location].stores.len();
+    let thread = memory.thread(thread);
+    thread.view.set(location, index);
+    let view = (is_release(order) || thread.release_fence).then(|| thread.view.clone());
+    memory.locations[location].stores.push(Store { value, view });
+}
+
+/// Record the store of the current value of `dst` by an atomic read-modify-write operation with
+/// `order`, which reads the latest store of `dst`.
+///
+/// The compiler calls this function at the end of the read-modify-write operations of
+/// `core::sync::atomic` when `--memory-model release-acquire` is enabled.
+#[doc(hidden)]
+pub unsafe fn weak_rmw<T: Copy>(dst: *mut T, order: Ordering) {
+    let Some((memory, thread)) = weak_memory() else { return };
+    let value = encode(unsafe { *dst });
+    let location = memory.location(dst as usize);
+    let stores = &memory.locations[location].stores;
+    let index = stores.len();
+    // The operation continues the release sequence of the store that it reads.
+    let mut view = stores[index - 1].view.clone();
+    let thread = memory.thread(thread);
+    if is_acquire(order)
+        && let Some(view) = &view
+    {
+        thread.view.join(view);
+    }
+    thread.view.set(location, index);
+    if is_release(order) || thread.release_fence {
+        let released = view.get_or_insert_with(View::default);
+        released.join(&thread.view);
+    }
+    memory.locations[location].stores.push(Store { value, view });
+}
+
+/// Synchronize the current thread with every store that happened before a fence with `order`.
+///
+/// The compiler calls this function at the beginning of `core::sync::atomic::fence` when
+/// `--memory-model release-acquire` is enabled.
+#[doc(hidden)]
+pub fn weak_fence(order: Ordering) {
+    let Some((memory, thread)) = weak_memory() else { return };
+    let newest = View(memory.locations.iter().map(|location| location.stores.len() - 1).collect());
+    let thread = memory.thread(thread);
+    if is_acquire(order) {
+        thread.view.join(&newest);
+    }
+    if is_release(order) {
+        thread.release_fence = true;
+    }
+}
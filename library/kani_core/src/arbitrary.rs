@@ -76,8 +76,8 @@ fn any() -> Self {
         trivial_arbitrary!(i128);
         trivial_arbitrary!(isize);
 
-        // We do not constrain floating points values per type spec. Users must add assumptions to their
-        // verification code if they want to eliminate NaN, infinite, or subnormal.
+        // We do not constrain floating points values per type spec. Users can use `ArbitraryFloat`
+        // if they want to eliminate NaN, infinite, or subnormal.
         trivial_arbitrary!(f32);
         trivial_arbitrary!(f64);
 
@@ -122,6 +122,177 @@ fn any() -> Self {
             }
         }
 
+        /// Which classes of floating-point values may be generated by [ArbitraryFloat::any_float].
+        ///
+        /// Zero (with either sign) and normal values are always allowed.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct FloatOptions {
+            /// Allow any NaN value, i.e., any payload and sign.
+            pub nan: bool,
+            /// Allow positive and negative infinity.
+            pub infinite: bool,
+            /// Allow subnormal values.
+            pub subnormal: bool,
+        }
+
+        impl FloatOptions {
+            /// Every value of the type, which is equivalent to `kani::any()`.
+            pub const ANY: FloatOptions =
+                FloatOptions { nan: true, infinite: true, subnormal: true };
+            /// Every value except NaN and infinity.
+            pub const FINITE: FloatOptions =
+                FloatOptions { nan: false, infinite: false, subnormal: true };
+            /// Only zero and normal values.
+            pub const NORMAL: FloatOptions =
+                FloatOptions { nan: false, infinite: false, subnormal: false };
+
+            /// Exclude NaN values.
+            pub const fn exclude_nan(self) -> Self {
+                FloatOptions { nan: false, ..self }
+            }
+
+            /// Exclude positive and negative infinity.
+            pub const fn exclude_infinite(self) -> Self {
+                FloatOptions { infinite: false, ..self }
+            }
+
+            /// Exclude subnormal values.
+            pub const fn exclude_subnormal(self) -> Self {
+                FloatOptions { subnormal: false, ..self }
+            }
+        }
+
+        /// Generation of floating-point values restricted to some classes of values.
+        ///
+        /// The restrictions are encoded as assumptions over the exponent and the mantissa bits
+        /// of the value instead of floating-point comparisons, which are more expensive for the
+        /// solver.
+        ///
+        /// # Example:
+        ///
+        /// ```no_run
+        /// # use crate::kani;
+        /// use kani::{ArbitraryFloat, FloatOptions};
+        ///
+        /// let x = f64::any_float(FloatOptions::ANY.exclude_nan());
+        /// assert!(x == x);
+        /// ```
+        pub trait ArbitraryFloat: Arbitrary + Copy + PartialOrd {
+            /// Create a symbolic value that belongs to one of the classes allowed by `options`.
+            fn any_float(options: FloatOptions) -> Self;
+
+            /// Create a symbolic value that is neither NaN nor infinite.
+            #[inline(always)]
+            fn any_finite() -> Self {
+                Self::any_float(FloatOptions::FINITE)
+            }
+
+            /// Create a symbolic value that is contained in `range`.
+            ///
+            /// Note: If the range is empty, or only contains NaN bounds, no value satisfies the
+            /// constraint, and every check that follows is vacuously true.
+            #[inline(always)]
+            fn any_in<R: core_path::ops::RangeBounds<Self>>(range: R) -> Self {
+                // NaN is never contained in a range, so excluding it upfront is cheap and
+                // prunes the comparisons below.
+                let value = Self::any_float(FloatOptions::ANY.exclude_nan());
+                assume(range.contains(&value));
+                value
+            }
+        }
+
+        macro_rules! float_arbitrary {
+            ( $type: ty, $bits: ty, $mantissa_bits: expr ) => {
+                impl ArbitraryFloat for $type {
+                    #[inline(always)]
+                    fn any_float(options: FloatOptions) -> Self {
+                        const MANTISSA_MASK: $bits = (1 << $mantissa_bits) - 1;
+                        const EXPONENT_MASK: $bits = !MANTISSA_MASK & (<$bits>::MAX >> 1);
+                        let bits = <$bits>::any();
+                        let exponent = bits & EXPONENT_MASK;
+                        let is_mantissa_zero = bits & MANTISSA_MASK == 0;
+                        // NaN and infinity have all exponent bits set, NaN has a non-zero mantissa.
+                        match (options.nan, options.infinite) {
+                            (true, true) => {}
+                            (true, false) => assume(exponent != EXPONENT_MASK || !is_mantissa_zero),
+                            (false, true) => assume(exponent != EXPONENT_MASK || is_mantissa_zero),
+                            (false, false) => assume(exponent != EXPONENT_MASK),
+                        }
+                        // Subnormal values have no exponent bit set and a non-zero mantissa.
+                        if !options.subnormal {
+                            assume(exponent != 0 || is_mantissa_zero);
+                        }
+                        <$type>::from_bits(bits)
+                    }
+                }
+            };
+        }
+
+        float_arbitrary!(f32, u32, f32::MANTISSA_DIGITS - 1);
+        float_arbitrary!(f64, u64, f64::MANTISSA_DIGITS - 1);
+
+        /// Generation of integer values restricted to a range.
+        ///
+        /// The value is encoded as the lower bound of the range plus a non-deterministic offset.
+        /// When the range has a power of two values, the offset is masked to the width of the
+        /// range, which needs no assumption. Otherwise, a single unsigned comparison bounds the
+        /// offset. Both are cheaper for the solver than `kani::any_where(|x| lo <= *x && *x <= hi)`.
+        ///
+        /// # Example:
+        ///
+        /// ```no_run
+        /// # use crate::kani;
+        /// use kani::ArbitraryInt;
+        ///
+        /// let x = i32::any_between(-8, 7);
+        /// assert!(-8 <= x && x <= 7);
+        /// ```
+        pub trait ArbitraryInt: Arbitrary + Copy + PartialOrd {
+            /// Create a symbolic value between `lo` and `hi`, inclusive.
+            ///
+            /// Note: This fails verification if `lo` is greater than `hi`.
+            fn any_between(lo: Self, hi: Self) -> Self;
+        }
+
+        macro_rules! int_arbitrary {
+            ( $type: ty, $unsigned: ty ) => {
+                impl ArbitraryInt for $type {
+                    #[inline(always)]
+                    fn any_between(lo: Self, hi: Self) -> Self {
+                        assert(
+                            lo <= hi,
+                            "kani::any_between: the lower bound is greater than the upper bound",
+                        );
+                        // The number of values of the range minus one, which always fits in the
+                        // unsigned type of the same width.
+                        let width = hi.wrapping_sub(lo) as $unsigned;
+                        let offset = <$unsigned>::any();
+                        let offset = if width & width.wrapping_add(1) == 0 {
+                            offset & width
+                        } else {
+                            assume(offset <= width);
+                            offset
+                        };
+                        lo.wrapping_add(offset as $type)
+                    }
+                }
+            };
+        }
+
+        int_arbitrary!(u8, u8);
+        int_arbitrary!(u16, u16);
+        int_arbitrary!(u32, u32);
+        int_arbitrary!(u64, u64);
+        int_arbitrary!(u128, u128);
+        int_arbitrary!(usize, usize);
+
+        int_arbitrary!(i8, u8);
+        int_arbitrary!(i16, u16);
+        int_arbitrary!(i32, u32);
+        int_arbitrary!(i64, u64);
+        int_arbitrary!(i128, u128);
+        int_arbitrary!(isize, usize);
+
         impl<T, const N: usize> Arbitrary for [T; N]
         where
             T: Arbitrary,
@@ -189,10 +360,6 @@ mod arbitrary_ptr {
             kani_core::ptr_generator!();
         }
 
-        pub mod slice {
-            kani_core::slice_generator!();
-        }
-
         mod range_structures {
             use super::{
                 Arbitrary,
@@ -260,6 +427,36 @@ fn any() -> Self {
                 }
             }
         }
+
+        /// Atomics are needed to havoc statics with interior mutability, e.g., when a contract
+        /// with `#[kani::modifies(&COUNTER)]` replaces a call.
+        mod atomic_structures {
+            use super::{Arbitrary, core_path::sync::atomic::*};
+
+            macro_rules! atomic_arbitrary {
+                ( $atomic: ty, $base: ty, $width: tt ) => {
+                    #[cfg(target_has_atomic = $width)]
+                    impl Arbitrary for $atomic {
+                        #[inline(always)]
+                        fn any() -> Self {
+                            <$atomic>::new(<$base>::any())
+                        }
+                    }
+                };
+            }
+
+            atomic_arbitrary!(AtomicBool, bool, "8");
+            atomic_arbitrary!(AtomicU8, u8, "8");
+            atomic_arbitrary!(AtomicU16, u16, "16");
+            atomic_arbitrary!(AtomicU32, u32, "32");
+            atomic_arbitrary!(AtomicU64, u64, "64");
+            atomic_arbitrary!(AtomicUsize, usize, "ptr");
+            atomic_arbitrary!(AtomicI8, i8, "8");
+            atomic_arbitrary!(AtomicI16, i16, "16");
+            atomic_arbitrary!(AtomicI32, i32, "32");
+            atomic_arbitrary!(AtomicI64, i64, "64");
+            atomic_arbitrary!(AtomicIsize, isize, "ptr");
+        }
     };
 }
 
@@ -2,10 +2,39 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 //! This macro generates the logic required to generate slice with arbitrary contents and length.
+//! Only the `std` version can allocate the array that backs a slice.
 #[allow(clippy::crate_in_macro_def)]
 #[macro_export]
 macro_rules! slice_generator {
-    () => {
+    (std) => {
+        kani_core::slice_generator!(core);
+
+        /// Returns a slice of `T` with a non-deterministic length of at most `MAX_LENGTH` and
+        /// non-deterministic contents. This is useful to verify functions that take a slice
+        /// without declaring an array and picking a slice of it in every harness.
+        ///
+        /// The slice is backed by an array that is allocated on the heap and never freed, which
+        /// is what gives it a `'static` lifetime.
+        ///
+        /// # Example:
+        ///
+        /// ```no_run
+        /// # fn foo(_: &[i32]) {}
+        /// let slice = kani::slice::any_slice_of::<i32, 3>();
+        /// foo(slice); // where foo is a function that takes a slice and verifies a property about it
+        /// ```
+        pub fn any_slice_of<T: kani::Arbitrary, const MAX_LENGTH: usize>() -> &'static [T] {
+            any_slice_of_mut::<T, MAX_LENGTH>()
+        }
+
+        /// A mutable version of [any_slice_of].
+        pub fn any_slice_of_mut<T: kani::Arbitrary, const MAX_LENGTH: usize>() -> &'static mut [T] {
+            let array = std::boxed::Box::leak(std::boxed::Box::new(T::any_array::<MAX_LENGTH>()));
+            let length = kani::any_where(|length: &usize| *length <= MAX_LENGTH);
+            &mut array[..length]
+        }
+    };
+    (core) => {
         use crate::kani;
 
         /// Given an array `arr` of length `LENGTH`, this function returns a **valid**
@@ -0,0 +1,124 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// This module contains checks for the laws that implementations of standard traits must satisfy.
+
+#[allow(clippy::crate_in_macro_def)]
+#[macro_export]
+macro_rules! generate_laws {
+    ($core:tt) => {
+        use $core::cmp::Ordering;
+        use $core::hash::{Hash, Hasher};
+
+        /// Check that `==` is an equivalence relation on the given values, as required by `Eq`,
+        /// and that `!=` is its negation.
+        ///
+        /// Call this function with nondeterministic values to check the `Eq` implementation of `T`
+        /// for all possible values:
+        ///
+        /// ```no_run
+        /// #[derive(PartialEq, Eq, kani::Arbitrary)]
+        /// struct Id(u8);
+        ///
+        /// #[kani::proof]
+        /// fn check_id_eq() {
+        ///     kani::laws::check_eq_laws::<Id>(kani::any(), kani::any(), kani::any());
+        /// }
+        /// ```
+        #[kanitool::fn_marker = "CheckEqLawsModel"]
+        #[allow(clippy::eq_op)]
+        pub fn check_eq_laws<T: Eq>(a: T, b: T, c: T) {
+            assert!(a == a, "`Eq` must be reflexive");
+            assert!((a == b) == (b == a), "`Eq` must be symmetric");
+            assert!(!(a == b && b == c) || a == c, "`Eq` must be transitive");
+            assert!((a != b) == !(a == b), "`ne` must be the negation of `eq`");
+        }
+
+        /// Check that `cmp` is a total order on the given values, as required by `Ord`,
+        /// and that it is consistent with `Eq` and `PartialOrd`.
+        #[kanitool::fn_marker = "CheckOrdLawsModel"]
+        pub fn check_ord_laws<T: Ord>(a: T, b: T, c: T) {
+            let ab = a.cmp(&b);
+            assert!(b.cmp(&a) == ab.reverse(), "`Ord` must be antisymmetric");
+            assert!(ab != b.cmp(&c) || a.cmp(&c) == ab, "`Ord` must be transitive");
+            assert!((ab == Ordering::Equal) == (a == b), "`Ord` must be consistent with `Eq`");
+            assert!(a.partial_cmp(&b) == Some(ab), "`Ord` must be consistent with `PartialOrd`");
+        }
+
+        /// Check that equal values have equal hashes, as required by `Hash`.
+        #[kanitool::fn_marker = "CheckHashLawsModel"]
+        pub fn check_hash_laws<T: Hash + Eq>(a: T, b: T) {
+            if a == b {
+                assert!(hash_of(&a) == hash_of(&b), "Equal values must have equal hashes");
+            }
+        }
+
+        /// Check that `From` and `TryFrom` convert between `T` and `U` in inverse ways: converting
+        /// `t` to `U` and back must give `t`, and if `u` converts to `T`, converting the result back
+        /// must give `u`.
+        ///
+        /// ```no_run
+        /// #[derive(Clone, Copy, PartialEq, kani::Arbitrary)]
+        /// enum Level { Low, High }
+        ///
+        /// impl From<Level> for u8 {
+        ///     fn from(level: Level) -> u8 {
+        ///         level as u8
+        ///     }
+        /// }
+        ///
+        /// impl TryFrom<u8> for Level {
+        ///     type Error = u8;
+        ///     fn try_from(value: u8) -> Result<Level, u8> {
+        ///         match value {
+        ///             0 => Ok(Level::Low),
+        ///             1 => Ok(Level::High),
+        ///             _ => Err(value),
+        ///         }
+        ///     }
+        /// }
+        ///
+        /// #[kani::proof]
+        /// fn check_level_conversions() {
+        ///     kani::laws::check_conversion_laws::<Level, u8>(kani::any(), kani::any());
+        /// }
+        /// ```
+        #[kanitool::fn_marker = "CheckConversionLawsModel"]
+        pub fn check_conversion_laws<T, U>(t: T, u: U)
+        where
+            T: TryFrom<U> + Clone + PartialEq,
+            U: From<T> + Clone + PartialEq,
+        {
+            assert!(
+                matches!(T::try_from(U::from(t.clone())), Ok(back) if back == t),
+                "Converting a value with `From` and back with `TryFrom` must give the same value"
+            );
+            if let Ok(back) = T::try_from(u.clone()) {
+                assert!(
+                    U::from(back) == u,
+                    "Converting a value with `TryFrom` and back with `From` must give the same value"
+                );
+            }
+        }
+
+        /// Hash `value` with the models of `--model-hashing`, which only need to be deterministic
+        /// for the law to hold.
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = LawHasher(0);
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        struct LawHasher(u64);
+
+        impl Hasher for LawHasher {
+            fn write(&mut self, bytes: &[u8]) {
+                super::hasher_models::write(&mut self.0, bytes)
+            }
+
+            fn finish(&self) -> u64 {
+                super::hasher_models::finish(&self.0)
+            }
+        }
+    };
+}
@@ -0,0 +1,80 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// This module provides instrumentation for checking that heap allocations are deallocated exactly
+// once. For full documentation, see the usage of `kani_core::kani_leaks!(std);` in
+// library/kani_core/src/lib.rs
+
+// Definitions in this module are not meant to be visible to the end user, only the compiler.
+#![allow(dead_code)]
+
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! kani_leaks {
+    ($core:path) => {
+        /// Global object for tracking whether an allocation is live.
+        static mut LEAK_STATE: LeakState = LeakState::new();
+
+        /// Currently tracked non-deterministically chosen allocation.
+        struct LeakState {
+            pub tracked_object_id: usize,
+            pub allocated: bool,
+            pub live: bool,
+        }
+
+        impl LeakState {
+            /// This is a dummy initialization function -- the values will be eventually overwritten
+            /// by a call to `initialize_leak_state`.
+            pub const fn new() -> Self {
+                Self { tracked_object_id: 0, allocated: false, live: false }
+            }
+        }
+
+        /// Set the tracked allocation to a non-deterministic one, which isn't allocated yet.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "InitializeLeakStateModel"]
+        fn initialize_leak_state() {
+            unsafe {
+                LEAK_STATE.tracked_object_id = super::any();
+                LEAK_STATE.allocated = false;
+                LEAK_STATE.live = false;
+            }
+        }
+
+        /// Record that the global allocator returned `ptr`. A null pointer means that the
+        /// allocation failed.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "RecordAllocationModel"]
+        fn record_allocation(ptr: *const u8) {
+            unsafe {
+                if !ptr.is_null() && super::mem::pointer_object(ptr) == LEAK_STATE.tracked_object_id
+                {
+                    LEAK_STATE.allocated = true;
+                    LEAK_STATE.live = true;
+                }
+            }
+        }
+
+        /// Record that `ptr` is returned to the global allocator, and return whether its allocation
+        /// was still live, i.e., whether it isn't deallocated twice.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "RecordDeallocationModel"]
+        fn record_deallocation(ptr: *const u8) -> bool {
+            unsafe {
+                if super::mem::pointer_object(ptr) != LEAK_STATE.tracked_object_id {
+                    return true;
+                }
+                let double_free = LEAK_STATE.allocated && !LEAK_STATE.live;
+                LEAK_STATE.live = false;
+                !double_free
+            }
+        }
+
+        /// Whether the tracked allocation was deallocated, if it was allocated at all.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "IsLeakFreeModel"]
+        fn is_leak_free() -> bool {
+            unsafe { !LEAK_STATE.live }
+        }
+    };
+}
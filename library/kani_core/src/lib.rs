@@ -23,9 +23,13 @@
 mod arbitrary;
 mod bounded_arbitrary;
 mod float;
+mod laws;
+mod leaks;
 mod mem;
 mod mem_init;
 mod models;
+mod provenance;
+mod recursion;
 
 pub use kani_macros::*;
 
@@ -54,6 +58,18 @@ pub mod float {
                 kani_core::generate_float!(core);
             }
 
+            pub mod slice {
+                kani_core::slice_generator!(core);
+            }
+
+            pub mod laws {
+                kani_core::generate_laws!(core);
+            }
+
+            mod leaks {
+                kani_core::kani_leaks!(core);
+            }
+
             pub mod mem {
                 kani_core::kani_mem!(core);
             }
@@ -61,6 +77,14 @@ pub mod mem {
             mod mem_init {
                 kani_core::kani_mem_init!(core);
             }
+
+            mod provenance {
+                kani_core::kani_provenance!(core);
+            }
+
+            mod recursion {
+                kani_core::kani_recursion!(core);
+            }
         }
     };
 
@@ -78,6 +102,33 @@ pub mod float {
             kani_core::generate_float!(std);
         }
 
+        pub mod slice {
+            //! This module contains functions that generate slices with arbitrary length and
+            //! contents.
+            kani_core::slice_generator!(std);
+        }
+
+        pub mod laws {
+            //! This module contains functions that check whether implementations of standard traits
+            //! satisfy the laws documented by the standard library.
+            //! The `autoharness` subcommand calls them with the `--traits` option.
+            kani_core::generate_laws!(std);
+        }
+
+        mod leaks {
+            //! This module provides instrumentation for checking that every heap allocation of a
+            //! harness is deallocated exactly once.
+            //!
+            //! Compiler automatically inserts calls to `record_allocation` after calls to the global
+            //! allocator, checks `record_deallocation` before memory is returned to it, and checks
+            //! `is_leak_free` when the harness returns.
+            //!
+            //! Note that for each harness, the tracked allocation is chosen non-deterministically,
+            //! so calls to `record_deallocation` and `is_leak_free` should be only used in assertion
+            //! contexts.
+            kani_core::kani_leaks!(std);
+        }
+
         pub mod mem {
             //! This module contains functions useful for checking unsafe memory access.
             //!
@@ -121,6 +172,32 @@ mod mem_init {
             //! so calls to `is_xxx_initialized` should be only used in assertion contexts.
             kani_core::kani_mem_init!(std);
         }
+
+        mod provenance {
+            //! This module provides instrumentation for checking that pointers cast from integers
+            //! have the provenance of an exposed allocation, i.e., of an allocation that a pointer
+            //! was cast to an integer from, and that they are within the bounds of that allocation.
+            //! It also checks that wrapping pointer arithmetic doesn't leave the allocation.
+            //!
+            //! The compiler inserts calls to `expose_provenance` at pointer-to-integer casts, checks
+            //! `has_exposed_provenance` and `is_within_allocation` after integer-to-pointer casts,
+            //! and checks `stays_in_allocation` after wrapping pointer arithmetic.
+            //!
+            //! `has_exposed_provenance` only knows whether the single allocation that is tracked
+            //! for the harness was exposed, so it must only be used in assertions.
+            kani_core::kani_provenance!(std);
+        }
+
+        mod recursion {
+            //! This module provides instrumentation for bounding the depth of recursive calls with
+            //! `#[kani::proof(recursion_limit = N)]`.
+            //!
+            //! Compiler numbers each group of mutually recursive functions that a harness reaches,
+            //! and wraps the calls between the functions of a group with `recursion_enter` and
+            //! `recursion_exit`. A call that would exceed the limit fails `recursion_limit_check`,
+            //! and the paths that go through it are not explored.
+            kani_core::kani_recursion!(std);
+        }
     };
 }
 
@@ -249,6 +326,32 @@ macro_rules! exists {
         #[kanitool::fn_marker = "CoverHook"]
         pub const fn cover(_cond: bool, _msg: &'static str) {}
 
+        /// Creates a probe with the specified name, i.e., a marker that Kani reports as
+        /// reached if at least one execution of the harness reaches it.
+        ///
+        /// # Example:
+        ///
+        /// ```no_run
+        /// # use crate::kani;
+        /// #
+        /// # let input: &[u8] = &[];
+        /// if input.is_empty() {
+        ///     kani::probe("empty input");
+        /// }
+        /// ```
+        ///
+        /// Unlike cover properties, probes have no condition and they are reported separately
+        /// for every harness, aggregated by name:
+        ///  - COVERED: if at least one of the probes with that name is reachable
+        ///  - UNCOVERED: if Kani proved that none of the probes with that name is reachable
+        ///
+        /// This function is called by the [`probe!`] macro. The macro is more
+        /// convenient to use.
+        ///
+        #[inline(never)]
+        #[kanitool::fn_marker = "ProbeHook"]
+        pub const fn probe(_name: &'static str) {}
+
         /// This creates an symbolic *valid* value of type `T`. You can assign the return value of this
         /// function to a variable that you want to make symbolic.
         ///
@@ -327,6 +430,105 @@ pub fn any_where<T: Arbitrary, F: FnOnce(&T) -> bool>(f: F) -> T {
             result
         }
 
+        /// Creates a symbolic index that is in bounds for the given slice, i.e., the result is
+        /// always smaller than `slice.len()`.
+        ///
+        /// This is equivalent to `kani::any_where(|idx: &usize| *idx < slice.len())`, and it
+        /// avoids the out-of-bounds failures caused by indexing with an unconstrained symbolic
+        /// value.
+        ///
+        /// # Example:
+        ///
+        /// ```no_run
+        /// # use crate::kani;
+        /// #
+        /// let array: [u8; 5] = kani::any();
+        /// let idx = kani::any_usize_index(&array);
+        /// assert!(array.get(idx).is_some());
+        /// ```
+        ///
+        /// Note: An empty slice has no valid index, so calling this function with an empty slice
+        /// results in a verification failure.
+        #[inline(always)]
+        pub fn any_usize_index<T>(slice: &[T]) -> usize {
+            assert(
+                !slice.is_empty(),
+                "kani::any_usize_index: cannot create an index for an empty slice",
+            );
+            any_where(|idx: &usize| *idx < slice.len())
+        }
+
+        /// Creates a symbolic integer between `lo` and `hi`, inclusive.
+        ///
+        /// This is equivalent to `kani::any_where(|x| lo <= *x && *x <= hi)`, but the range is
+        /// encoded without comparing the value to both bounds. See [ArbitraryInt] for more details.
+        ///
+        /// # Example:
+        ///
+        /// ```no_run
+        /// # use crate::kani;
+        /// #
+        /// let day: u8 = kani::any_between(1, 31);
+        /// assert!(day > 0 && day < 32);
+        /// ```
+        ///
+        /// Note: An empty range has no values, so calling this function with `lo` greater than
+        /// `hi` results in a verification failure.
+        #[inline(always)]
+        pub fn any_between<T: ArbitraryInt>(lo: T, hi: T) -> T {
+            T::any_between(lo, hi)
+        }
+
+        /// Creates a symbolic `f32` that is neither NaN nor infinite.
+        ///
+        /// See [ArbitraryFloat] for finer control over the classes of generated values.
+        ///
+        /// # Example:
+        ///
+        /// ```no_run
+        /// # use crate::kani;
+        /// #
+        /// let x = kani::any_f32_finite();
+        /// assert!(x.is_finite());
+        /// ```
+        #[inline(always)]
+        pub fn any_f32_finite() -> f32 {
+            f32::any_finite()
+        }
+
+        /// Creates a symbolic `f64` that is neither NaN nor infinite.
+        ///
+        /// See [ArbitraryFloat] for finer control over the classes of generated values.
+        #[inline(always)]
+        pub fn any_f64_finite() -> f64 {
+            f64::any_finite()
+        }
+
+        /// Creates a symbolic `f32` that is contained in the given range.
+        ///
+        /// # Example:
+        ///
+        /// ```no_run
+        /// # use crate::kani;
+        /// #
+        /// let x = kani::any_f32_in(-1.0..=1.0);
+        /// assert!(x.abs() <= 1.0);
+        /// ```
+        ///
+        /// Note: An empty range has no values, so every check that follows is vacuously true.
+        #[inline(always)]
+        pub fn any_f32_in<R: core_path::ops::RangeBounds<f32>>(range: R) -> f32 {
+            f32::any_in(range)
+        }
+
+        /// Creates a symbolic `f64` that is contained in the given range.
+        ///
+        /// Note: An empty range has no values, so every check that follows is vacuously true.
+        #[inline(always)]
+        pub fn any_f64_in<R: core_path::ops::RangeBounds<f64>>(range: R) -> f64 {
+            f64::any_in(range)
+        }
+
         /// This function creates a symbolic value of type `T`. This may result in an invalid value.
         ///
         /// # Safety
@@ -506,6 +708,23 @@ pub fn untracked_deref<T>(_: &T) -> T {
             #[kanitool::fn_marker = "InitContractsHook"]
             pub fn init_contracts() {}
 
+            /// Set whether the function whose contract is being checked is expected to panic.
+            ///
+            /// While a panic is expected, panics end the execution instead of failing
+            /// verification. Only for use within contracts with an `ensures_panics_iff` clause.
+            #[inline(never)]
+            #[doc(hidden)]
+            #[kanitool::fn_marker = "ExpectPanicHook"]
+            pub fn expect_panic(_expected: bool) {}
+
+            /// Constrain the argument that a harness annotated with
+            /// `#[kani::proof(split_on = "...")]` is split on to the variant that the current
+            /// run of the harness verifies. It has no effect outside of verification.
+            #[inline(never)]
+            #[doc(hidden)]
+            #[kanitool::fn_marker = "SplitCaseHook"]
+            pub fn split_case<T>(_value: &T) {}
+
             /// This should only be used within contracts. The intent is to
             /// perform type inference on a closure's argument
             #[doc(hidden)]
@@ -513,6 +732,33 @@ pub fn apply_closure<T, U: Fn(&T) -> bool>(f: U, x: &T) -> bool {
                 f(x)
             }
 
+            /// Types whose values can be remembered by `old` expressions of contracts.
+            ///
+            /// The value is cloned before the function runs, so that the postcondition can refer
+            /// to it after the function modified the original.
+            #[doc(hidden)]
+            #[diagnostic::on_unimplemented(
+                message = "`old` cannot take a snapshot of a value of type `{Self}`",
+                label = "the value of `old` must implement `Clone`",
+                note = "`old(expr)` evaluates `expr` before the function runs and keeps a clone of its value"
+            )]
+            pub trait Snapshot: Sized {
+                fn snapshot(&self) -> Self;
+            }
+
+            impl<T: Clone> Snapshot for T {
+                fn snapshot(&self) -> Self {
+                    self.clone()
+                }
+            }
+
+            /// Take a snapshot of the value of an `old` expression.
+            /// Only for use within function contracts.
+            #[doc(hidden)]
+            pub fn snapshot<T: Snapshot>(value: &T) -> T {
+                value.snapshot()
+            }
+
             /// Recieves a reference to a pointer-like object and assigns kani::any_modifies to that object.
             /// Only for use within function contracts and will not be replaced if the recursive or function stub
             /// replace contracts are not used.
@@ -543,6 +789,25 @@ pub unsafe fn write_any_slim<T: Arbitrary>(pointer: *mut T) {
                 ptr::write(pointer, T::any())
             }
 
+            /// Create a pointer argument for an automatic harness of an `extern "C"` function.
+            ///
+            /// The pointer is either null, or it points into `buffer` such that a nondeterministic
+            /// number of elements, between 1 and `N`, can be accessed through it.
+            /// Pointing towards the end of the buffer ensures that accesses past the chosen
+            /// size are reported as out of bounds.
+            #[kanitool::fn_marker = "AnyCPtrModel"]
+            #[inline(never)]
+            #[doc(hidden)]
+            pub fn any_c_ptr<T, const N: usize>(buffer: &mut [T; N]) -> *mut T {
+                if N == 0 || super::any::<bool>() {
+                    return ptr::null_mut();
+                }
+                let len: usize = super::any();
+                super::assume(len > 0 && len <= N);
+                // SAFETY: `N - len` is in bounds of `buffer`, since `0 < len <= N`.
+                unsafe { buffer.as_mut_ptr().add(N - len) }
+            }
+
             /// Fill in a str with kani::any.
             /// Intended as a post compilation replacement for write_any.
             /// Not yet implemented
@@ -598,6 +863,9 @@ fn run_loop_contract_fn<F: Fn() -> bool>(func: &F, _transformed: usize) -> bool
             /// Insert the contract into the body of the function as assertion(s).
             pub const ASSERT: Mode = 4;
 
+            /// Keep the original body and leave the contract to CBMC's contract instrumentation.
+            pub const CBMC_CONTRACT: Mode = 5;
+
             /// Creates a non-fatal property with the specified condition and message.
             ///
             /// This check will not impact the program control flow even when it fails.
@@ -24,16 +24,96 @@ macro_rules! kani_mem {
         /// This function does not check if the value stored is valid for the given type. Use
         /// [self::can_dereference] for that.
         ///
-        /// This function will panic today if the pointer is not null, and it points to an unallocated or
-        /// deallocated memory location. This is an existing Kani limitation.
-        /// See <https://github.com/model-checking/kani/issues/2690> for more details.
-        #[crate::kani::unstable_feature(
-            feature = "mem-predicates",
-            issue = 2690,
-            reason = "experimental memory predicate API"
-        )]
+        /// See [self::can_write_bytes] for the soundness caveats of this check.
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
         pub fn can_write<T: MetaSized>(ptr: *mut T) -> bool {
-            is_ptr_aligned(ptr) && is_inbounds(ptr)
+            is_ptr_aligned(ptr)
+                && checked_size_of_raw(ptr).is_some_and(|sz| can_write_bytes(ptr, sz))
+        }
+
+        /// Check if the pointer is valid for read access according to [crate::mem] conditions 1, 2
+        /// and 3.
+        ///
+        /// Note this function also checks for pointer alignment, but it does not check if the
+        /// memory is initialized, nor if the value stored is valid for the given type. Use
+        /// [self::can_dereference] for that.
+        ///
+        /// See [self::can_read_bytes] for the soundness caveats of this check.
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        pub fn can_read<T: MetaSized>(ptr: *const T) -> bool {
+            is_ptr_aligned(ptr)
+                && checked_size_of_raw(ptr).is_some_and(|sz| can_read_bytes(ptr, sz))
+        }
+
+        /// Check if the `len` bytes starting at `ptr` can be read, i.e., they are all within the
+        /// bounds of a single live allocation.
+        ///
+        /// This lowers to CBMC's `__CPROVER_r_ok`. A read of zero bytes is valid for every
+        /// pointer, and a null pointer is never valid for reads of one or more bytes.
+        ///
+        /// # Soundness
+        ///
+        /// - Kani cannot decide whether an arbitrary address, e.g., one obtained from an integer, is
+        ///   part of an allocation. If `ptr` is not null and does not point to an allocation, the
+        ///   check reports an unsupported construct and verification fails, rather than
+        ///   returning `false`.
+        /// - This function does not check if the memory is initialized, nor that the access
+        ///   respects Rust's aliasing rules ([crate::mem] conditions 4 and 5).
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        pub fn can_read_bytes<T: PointeeSized>(ptr: *const T, len: usize) -> bool {
+            can_access_bytes(ptr as *const (), len, false)
+        }
+
+        /// Check if the `len` bytes starting at `ptr` can be written, i.e., they are all within
+        /// the bounds of a single live allocation.
+        ///
+        /// This lowers to CBMC's `__CPROVER_w_ok`, and it has the same caveats as
+        /// [self::can_read_bytes]. In addition, CBMC does not track which memory is immutable, so
+        /// this function returns `true` for memory that Rust does not allow to be modified, such
+        /// as string literals or a `static` without interior mutability.
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        pub fn can_write_bytes<T: PointeeSized>(ptr: *mut T, len: usize) -> bool {
+            can_access_bytes(ptr as *const (), len, true)
+        }
+
+        /// Check if `ptr` is aligned and valid for reads of `len` consecutive values of type `T`,
+        /// i.e., if it is safe to call [core::slice::from_raw_parts] with `ptr` and `len` on
+        /// initialized memory.
+        ///
+        /// See [self::can_read_bytes] for the soundness caveats of this check.
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        pub fn can_read_slice<T>(ptr: *const T, len: usize) -> bool {
+            is_ptr_aligned(ptr)
+                && len.checked_mul(size_of::<T>()).is_some_and(|sz| can_read_bytes(ptr, sz))
+        }
+
+        /// Check if `ptr` is aligned and valid for writes of `len` consecutive values of type
+        /// `T`.
+        ///
+        /// See [self::can_write_bytes] for the soundness caveats of this check.
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        pub fn can_write_slice<T>(ptr: *mut T, len: usize) -> bool {
+            is_ptr_aligned(ptr)
+                && len.checked_mul(size_of::<T>()).is_some_and(|sz| can_write_bytes(ptr, sz))
+        }
+
+        /// Check if all the bytes of the string slice pointed to by `ptr` can be read.
+        ///
+        /// This function does not check that the bytes are valid UTF-8.
+        /// See [self::can_read_bytes] for the soundness caveats of this check.
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        pub fn can_read_str(ptr: *const str) -> bool {
+            let (data, len) = ptr.to_raw_parts();
+            can_read_bytes(data, len)
+        }
+
+        /// Check if all the bytes of the string slice pointed to by `ptr` can be written.
+        ///
+        /// See [self::can_write_bytes] for the soundness caveats of this check.
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        pub fn can_write_str(ptr: *mut str) -> bool {
+            let (data, len) = ptr.to_raw_parts();
+            can_write_bytes(data, len)
         }
 
         /// Check if the pointer is valid for unaligned write access according to [crate::mem] conditions
@@ -202,6 +282,27 @@ pub fn is_inbounds<T: MetaSized>(ptr: *const T) -> bool {
             }
         }
 
+        /// Assume that the `count` values of type `T` starting at `ptr` are initialized.
+        ///
+        /// This only has an effect with `-Z uninit-checks`, where it marks the data bytes of these
+        /// values as initialized, e.g., after a function that Kani cannot analyze filled a buffer of
+        /// `MaybeUninit`. The bytes of the `MaybeUninit` fields of `T` keep their state.
+        ///
+        /// Kani neither checks that the values are initialized, nor that `ptr` points to allocated
+        /// memory.
+        #[kanitool::fn_marker = "AssumeInitRangeIntrinsic"]
+        #[inline(never)]
+        pub fn assume_init_range<T>(_ptr: *const T, _count: usize) {
+            kani_intrinsic()
+        }
+
+        /// Assume that all elements of `slice` are initialized.
+        ///
+        /// See [self::assume_init_range] for more details.
+        pub fn assume_init_slice<T>(slice: &[$core::mem::MaybeUninit<T>]) {
+            assume_init_range(slice.as_ptr() as *const T, slice.len())
+        }
+
         // Return whether the pointer is aligned
         #[allow(clippy::manual_is_power_of_two)]
         fn is_ptr_aligned<T: MetaSized>(ptr: *const T) -> bool {
@@ -216,6 +317,26 @@ fn is_ptr_aligned<T: MetaSized>(ptr: *const T) -> bool {
             }
         }
 
+        /// Shared implementation of [can_read_bytes] and [can_write_bytes].
+        fn can_access_bytes(ptr: *const (), len: usize, write: bool) -> bool {
+            if len == 0 {
+                true
+            } else if ptr.is_null() || len > isize::MAX as usize {
+                false
+            } else {
+                if !unsafe { is_allocated(ptr, 0) } {
+                    crate::kani::unsupported(
+                        "Kani does not support reasoning about pointer to unallocated memory",
+                    );
+                }
+                if write {
+                    unsafe { is_writable(ptr, len) }
+                } else {
+                    unsafe { is_allocated(ptr, len) }
+                }
+            }
+        }
+
         /// Check if the pointer `_ptr` contains an allocated address of size equal or greater than `_size`.
         ///
         /// # Safety
@@ -227,7 +348,18 @@ fn is_ptr_aligned<T: MetaSized>(ptr: *const T) -> bool {
         /// Otherwise, it returns non-det boolean.
         #[kanitool::fn_marker = "IsAllocatedHook"]
         #[inline(never)]
-        unsafe fn is_allocated(_ptr: *const (), _size: usize) -> bool {
+        pub(super) unsafe fn is_allocated(_ptr: *const (), _size: usize) -> bool {
+            kani_intrinsic()
+        }
+
+        /// Check if the pointer `_ptr` can be used to write `_size` bytes.
+        ///
+        /// # Safety
+        ///
+        /// Same as [is_allocated].
+        #[kanitool::fn_marker = "IsWritableHook"]
+        #[inline(never)]
+        unsafe fn is_writable(_ptr: *const (), _size: usize) -> bool {
             kani_intrinsic()
         }
 
@@ -238,10 +370,30 @@ unsafe fn is_allocated(_ptr: *const (), _size: usize) -> bool {
         /// - Users have to ensure that the pointed to memory is allocated.
         #[kanitool::fn_marker = "ValidValueIntrinsic"]
         #[inline(never)]
-        unsafe fn has_valid_value<T: PointeeSized>(_ptr: *const T) -> bool {
+        pub(crate) unsafe fn has_valid_value<T: PointeeSized>(_ptr: *const T) -> bool {
             kani_intrinsic()
         }
 
+        /// Check if the `count` values stored from `ptr` satisfy type `T` validity requirements.
+        ///
+        /// The result must hold for any index, so checking the value at a non-deterministic index
+        /// checks every value.
+        ///
+        /// # Safety
+        ///
+        /// - Users have to ensure that the `count` values are allocated.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "HasValidValuesModel"]
+        #[allow(dead_code)]
+        unsafe fn has_valid_values<T>(ptr: *const T, count: usize) -> bool {
+            if count == 0 {
+                return true;
+            }
+            let idx: usize = super::any();
+            super::assume(idx < count);
+            unsafe { has_valid_value(ptr.wrapping_add(idx)) }
+        }
+
         /// Check whether `len * size_of::<T>()` bytes are initialized starting from `ptr`.
         #[kanitool::fn_marker = "IsInitializedIntrinsic"]
         #[inline(never)]
@@ -185,6 +185,30 @@ pub fn set_slice<const LAYOUT_SIZE: usize>(
                     self.value = layout[(self.tracked_offset - offset) % LAYOUT_SIZE] && value;
                 }
             }
+
+            /// Same as `set_slice`, but keep the currently tracked memory initialization state if
+            /// the tracked byte is marked as `true` in `preserved`.
+            #[kanitool::disable_checks(pointer)]
+            pub fn set_slice_preserving<const LAYOUT_SIZE: usize>(
+                &mut self,
+                ptr: *const u8,
+                layout: Layout<LAYOUT_SIZE>,
+                preserved: Layout<LAYOUT_SIZE>,
+                num_elts: usize,
+                value: bool,
+            ) {
+                let obj = super::mem::pointer_object(ptr);
+                let offset = super::mem::pointer_offset(ptr);
+                if self.tracked_object_id == obj
+                    && self.tracked_offset >= offset
+                    && self.tracked_offset < offset + num_elts * LAYOUT_SIZE
+                {
+                    let idx = (self.tracked_offset - offset) % LAYOUT_SIZE;
+                    if !preserved[idx] {
+                        self.value = layout[idx] && value;
+                    }
+                }
+            }
         }
 
         /// Set tracked object and tracked offset to a non-deterministic value.
@@ -324,6 +348,70 @@ fn set_str_ptr_initialized<const LAYOUT_SIZE: usize>(
             }
         }
 
+        /// Set initialization state to `value` for the item laid out according to the `layout` starting
+        /// at address `ptr`, except for the bytes marked in `preserved`.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "SetPtrInitializedPreservingModel"]
+        fn set_ptr_initialized_preserving<const LAYOUT_SIZE: usize, T>(
+            ptr: *const T,
+            layout: Layout<LAYOUT_SIZE>,
+            preserved: Layout<LAYOUT_SIZE>,
+            value: bool,
+        ) {
+            set_slice_chunk_ptr_initialized_preserving(ptr, layout, preserved, 1, value);
+        }
+
+        /// Set initialization state to `value` for `num_elts` items laid out according to the `layout`
+        /// starting at address `ptr`, except for the bytes marked in `preserved`.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "SetSliceChunkPtrInitializedPreservingModel"]
+        fn set_slice_chunk_ptr_initialized_preserving<const LAYOUT_SIZE: usize, T>(
+            ptr: *const T,
+            layout: Layout<LAYOUT_SIZE>,
+            preserved: Layout<LAYOUT_SIZE>,
+            num_elts: usize,
+            value: bool,
+        ) {
+            if LAYOUT_SIZE == 0 {
+                return;
+            }
+            let (ptr, _) = ptr.to_raw_parts();
+            unsafe {
+                MEM_INIT_STATE.set_slice_preserving(
+                    ptr as *const u8,
+                    layout,
+                    preserved,
+                    num_elts,
+                    value,
+                );
+            }
+        }
+
+        /// Set initialization state of the slice, items of which are laid out according to the `layout`
+        /// starting at address `ptr`, to `value`, except for the bytes marked in `preserved`.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "SetSlicePtrInitializedPreservingModel"]
+        fn set_slice_ptr_initialized_preserving<const LAYOUT_SIZE: usize, T>(
+            ptr: *const [T],
+            layout: Layout<LAYOUT_SIZE>,
+            preserved: Layout<LAYOUT_SIZE>,
+            value: bool,
+        ) {
+            if LAYOUT_SIZE == 0 {
+                return;
+            }
+            let (ptr, num_elts) = ptr.to_raw_parts();
+            unsafe {
+                MEM_INIT_STATE.set_slice_preserving(
+                    ptr as *const u8,
+                    layout,
+                    preserved,
+                    num_elts,
+                    value,
+                );
+            }
+        }
+
         /// Copy initialization state of `size_of::<T> * num_elts` bytes from one pointer to the other. Note
         /// that in this case `LAYOUT_SIZE == size_of::<T>`.
         #[kanitool::disable_checks(pointer)]
@@ -347,6 +435,21 @@ fn copy_init_state<const LAYOUT_SIZE: usize, T>(
             }
         }
 
+        /// Copy initialization state of the bytes preserved by `realloc`, i.e., the first
+        /// `min(old_size, new_size)` elements, from the old allocation to the new one. Note that in
+        /// this case `LAYOUT_SIZE == size_of::<T>`.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "CopyInitStateReallocModel"]
+        fn copy_init_state_realloc<const LAYOUT_SIZE: usize, T>(
+            from: *const T,
+            to: *const T,
+            old_size: usize,
+            new_size: usize,
+        ) {
+            let num_elts = if old_size < new_size { old_size } else { new_size };
+            copy_init_state::<LAYOUT_SIZE, T>(from, to, num_elts);
+        }
+
         /// Copy initialization state of `size_of::<T>` bytes from one pointer to the other. Note that in
         /// this case `LAYOUT_SIZE == size_of::<T>`.
         #[kanitool::disable_checks(pointer)]
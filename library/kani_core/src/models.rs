@@ -289,5 +289,70 @@ pub(crate) fn size_of_slice_object(
                 }
             }
         }
+
+        /// Models of the SipHash hasher used by `std::hash::DefaultHasher` that replace it
+        /// when the `--model-hashing` option is enabled.
+        ///
+        /// The compiler passes the models the `tail` field of the hasher, which they use as the
+        /// state, and every byte is mixed into the state with a rotation and an XOR. Thus, equal
+        /// byte sequences still produce equal hashes, but the formula no longer has to encode the
+        /// SipHash rounds. The checks of the `Hash` laws hash their values with the same models.
+        #[allow(dead_code)]
+        mod hasher_models {
+            #[kanitool::fn_marker = "HasherWriteModel"]
+            pub fn write(state: &mut u64, bytes: &[u8]) {
+                for byte in bytes {
+                    *state = state.rotate_left(5) ^ *byte as u64;
+                }
+            }
+
+            #[kanitool::fn_marker = "HasherFinishModel"]
+            pub fn finish(state: &u64) -> u64 {
+                *state
+            }
+        }
+
+        /// Models of `CStr` methods that Kani uses instead of the ones of the standard library,
+        /// which scan the bytes a word at a time. Scanning them one at a time instead is much
+        /// cheaper to encode.
+        #[allow(dead_code)]
+        mod cstr_models {
+            use core::ffi::{CStr, FromBytesWithNulError};
+            use core::str::Utf8Error;
+
+            #[kanitool::fn_marker = "CStrFromBytesWithNulModel"]
+            pub fn from_bytes_with_nul(bytes: &[u8]) -> Result<&CStr, FromBytesWithNulError> {
+                let mut position = 0;
+                while position < bytes.len() {
+                    if bytes[position] == 0 {
+                        return if position + 1 == bytes.len() {
+                            // SAFETY: The only nul byte is the last one.
+                            Ok(unsafe { CStr::from_bytes_with_nul_unchecked(bytes) })
+                        } else {
+                            Err(FromBytesWithNulError::InteriorNul { position })
+                        };
+                    }
+                    position += 1;
+                }
+                Err(FromBytesWithNulError::NotNulTerminated)
+            }
+
+            /// Only check ASCII strings byte by byte, since the error of the other strings can
+            /// only be built by the standard library.
+            #[kanitool::fn_marker = "CStrToStrModel"]
+            pub fn to_str(cstr: &CStr) -> Result<&str, Utf8Error> {
+                let bytes = cstr.to_bytes();
+                let mut idx = 0;
+                while idx < bytes.len() && bytes[idx].is_ascii() {
+                    idx += 1;
+                }
+                if idx == bytes.len() {
+                    // SAFETY: ASCII strings are valid UTF-8.
+                    Ok(unsafe { core::str::from_utf8_unchecked(bytes) })
+                } else {
+                    core::str::from_utf8(bytes)
+                }
+            }
+        }
     };
 }
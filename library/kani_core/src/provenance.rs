@@ -0,0 +1,83 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// This module provides instrumentation for checking the provenance of pointers that are cast from
+// integers. For full documentation, see the usage of `kani_core::kani_provenance!(std);` in
+// library/kani_core/src/lib.rs
+
+// Definitions in this module are not meant to be visible to the end user, only the compiler.
+#![allow(dead_code)]
+
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! kani_provenance {
+    ($core:path) => {
+        /// Global object for tracking whether the provenance of an allocation was exposed.
+        static mut PROVENANCE_STATE: ProvenanceState = ProvenanceState::new();
+
+        /// Currently tracked non-deterministically chosen allocation.
+        struct ProvenanceState {
+            pub tracked_object_id: usize,
+            pub exposed: bool,
+        }
+
+        impl ProvenanceState {
+            /// This is a dummy initialization function -- the values will be eventually overwritten
+            /// by a call to `initialize_provenance_state`.
+            pub const fn new() -> Self {
+                Self { tracked_object_id: 0, exposed: false }
+            }
+        }
+
+        /// Set the tracked allocation to a non-deterministic one, whose provenance is not exposed.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "InitializeProvenanceStateModel"]
+        fn initialize_provenance_state() {
+            unsafe {
+                PROVENANCE_STATE.tracked_object_id = super::any();
+                PROVENANCE_STATE.exposed = false;
+            }
+        }
+
+        /// Record that `ptr` was cast to an integer, which exposes the provenance of its allocation.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "ExposeProvenanceModel"]
+        fn expose_provenance(ptr: *const u8) {
+            unsafe {
+                if super::mem::pointer_object(ptr) == PROVENANCE_STATE.tracked_object_id {
+                    PROVENANCE_STATE.exposed = true;
+                }
+            }
+        }
+
+        /// Whether `ptr`, which was cast from an integer, can use the provenance of its allocation,
+        /// i.e., whether that provenance was exposed. The null pointer doesn't need any provenance.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "HasExposedProvenanceModel"]
+        fn has_exposed_provenance(ptr: *const u8) -> bool {
+            ptr.is_null()
+                || unsafe {
+                    super::mem::pointer_object(ptr) != PROVENANCE_STATE.tracked_object_id
+                        || PROVENANCE_STATE.exposed
+                }
+        }
+
+        /// Whether `ptr`, which was cast from an integer, points to a byte of its allocation, i.e.,
+        /// whether the arithmetic on its address stayed in bounds. Unlike for pointer arithmetic,
+        /// the address one byte past the end is rejected, since it may also be the address of
+        /// the next allocation, whose provenance the pointer would then pick up.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "IsWithinAllocationModel"]
+        fn is_within_allocation(ptr: *const u8) -> bool {
+            ptr.is_null() || unsafe { super::mem::is_allocated(ptr as *const (), 1) }
+        }
+
+        /// Whether `ptr`, the result of wrapping arithmetic on a pointer, is within the bounds of
+        /// the allocation of its provenance, or one byte past its end.
+        #[kanitool::disable_checks(pointer)]
+        #[kanitool::fn_marker = "StaysInAllocationModel"]
+        fn stays_in_allocation(ptr: *const u8) -> bool {
+            ptr.is_null() || unsafe { super::mem::is_allocated(ptr as *const (), 0) }
+        }
+    };
+}
@@ -0,0 +1,59 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// This module provides instrumentation for bounding the depth of recursive calls with
+// `#[kani::proof(recursion_limit = N)]`. For full documentation, see the usage of
+// `kani_core::kani_recursion!(std);` in library/kani_core/src/lib.rs
+
+// Definitions in this module are not meant to be visible to the end user, only the compiler.
+#![allow(dead_code)]
+
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! kani_recursion {
+    ($core:path) => {
+        /// The number of recursion depth counters.
+        const DEPTH_COUNTERS: usize = 64;
+
+        /// The depth of the recursive calls that are active in each group of mutually recursive
+        /// functions, indexed by the number that the compiler assigns to the group.
+        static mut RECURSION_DEPTH: [u32; DEPTH_COUNTERS] = [0; DEPTH_COUNTERS];
+
+        /// Enter a recursive call of the group `scc`, unless `limit` recursive calls of that group
+        /// are already active. Return whether the call was entered.
+        ///
+        /// Groups share a counter if there are more groups than counters, which can only make the
+        /// limit be reached sooner.
+        #[kanitool::fn_marker = "RecursionEnterModel"]
+        fn recursion_enter(scc: usize, limit: u32) -> bool {
+            let idx = scc % DEPTH_COUNTERS;
+            unsafe {
+                if RECURSION_DEPTH[idx] >= limit {
+                    false
+                } else {
+                    RECURSION_DEPTH[idx] += 1;
+                    true
+                }
+            }
+        }
+
+        /// Return from a recursive call of the group `scc`.
+        #[kanitool::fn_marker = "RecursionExitModel"]
+        fn recursion_exit(scc: usize) {
+            unsafe { RECURSION_DEPTH[scc % DEPTH_COUNTERS] -= 1 }
+        }
+
+        /// Check that a recursive call is within the recursion limit of the harness. The calls
+        /// beyond the limit are not explored, and their failures are reported apart from the
+        /// failures of the harness.
+        #[kanitool::fn_marker = "RecursionLimitCheckHook"]
+        #[inline(never)]
+        fn recursion_limit_check(cond: bool, msg: &'static str) {
+            #[cfg(not(feature = "concrete_playback"))]
+            return super::kani_intrinsic();
+
+            #[cfg(feature = "concrete_playback")]
+            assert!(cond, "{msg}");
+        }
+    };
+}
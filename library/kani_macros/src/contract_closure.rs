@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Parsing of the `kani::contract_closure!` input, which is shared by the Kani and the regular
+//! expansions of the macro.
+
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, ExprClosure, Ident, Token};
+
+/// The input of `kani::contract_closure!`, i.e. `[harness = NAME,] #[CONTRACT]* CLOSURE`.
+pub struct ContractClosure {
+    /// The name of the `proof_for_contract` harness that should be generated, if any.
+    /// Harnesses are only generated when compiling with Kani.
+    #[cfg_attr(not(kani_sysroot), allow(dead_code))]
+    pub harness: Option<Ident>,
+    /// The closure, with its contract attributes.
+    pub closure: ExprClosure,
+}
+
+impl Parse for ContractClosure {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let harness = if input.peek(Ident) && input.peek2(Token![=]) {
+            let option = input.parse::<Ident>()?;
+            if option != "harness" {
+                return Err(syn::Error::new(
+                    option.span(),
+                    format!("`{option}` is not a valid option for `kani::contract_closure`"),
+                ));
+            }
+            let _ = input.parse::<Token![=]>()?;
+            let name = input.parse::<Ident>()?;
+            let _ = input.parse::<Token![,]>()?;
+            Some(name)
+        } else {
+            None
+        };
+        let attrs = input.call(Attribute::parse_outer)?;
+        let mut closure = input.parse::<ExprClosure>()?;
+        closure.attrs.splice(0..0, attrs);
+        Ok(ContractClosure { harness, closure })
+    }
+}
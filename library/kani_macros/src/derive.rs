@@ -12,10 +12,14 @@
 use proc_macro_error2::abort;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::visit_mut::{self, VisitMut};
 use syn::{
-    Data, DataEnum, DeriveInput, Fields, GenericParam, Generics, Index, parse_macro_input,
-    parse_quote,
+    Data, DataEnum, DeriveInput, Fields, GenericParam, Generics, Index, LitStr, Member, Token,
+    parse_macro_input, parse_quote,
 };
 
 #[cfg(feature = "no_core")]
@@ -85,6 +89,109 @@ fn any() -> Self {
     proc_macro::TokenStream::from(expanded)
 }
 
+/// The condition of a `#[kani::arbitrary(where = "<cond>")]` attribute.
+struct ArbitraryConstraint {
+    cond: LitStr,
+}
+
+impl Parse for ArbitraryConstraint {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![where]>()?;
+        input.parse::<Token![=]>()?;
+        let cond = input.parse()?;
+        if !input.is_empty() {
+            return Err(input.error("expected a single `where = \"<condition>\"` argument"));
+        }
+        Ok(ArbitraryConstraint { cond })
+    }
+}
+
+/// Generate the Arbitrary implementation for a struct annotated with
+/// `#[kani::arbitrary(where = "<cond>")]`, which only generates values that satisfy `cond`.
+/// The condition refers to the fields of the generated value through `self`, e.g.,
+/// `self.len <= self.cap`.
+pub fn expand_arbitrary_attr(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let constraint = parse_macro_input!(attr as ArbitraryConstraint);
+    let derive_item = parse_macro_input!(item as DeriveInput);
+    let item_name = &derive_item.ident;
+    let kani_path = kani_path();
+
+    let Data::Struct(struct_data) = &derive_item.data else {
+        abort!(Span::call_site(), "Cannot implement `Arbitrary` for `{}`", item_name;
+            note = item_name.span() =>
+            "`#[kani::arbitrary(where = ...)]` can only be used on structs"
+        )
+    };
+    let mut cond: syn::Expr = match constraint.cond.parse() {
+        Ok(cond) => cond,
+        Err(err) => abort!(constraint.cond, "the condition could not be parsed: {}", err),
+    };
+    let mut fields = ConstraintFields { fields: &struct_data.fields };
+    fields.visit_expr(&cond);
+    SelfToObj.visit_expr_mut(&mut cond);
+
+    let body = fn_any_body(item_name, &derive_item.data);
+    let generics = add_trait_bound_arbitrary(derive_item.generics.clone());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let expanded = quote! {
+        #derive_item
+
+        impl #impl_generics #kani_path::Arbitrary for #item_name #ty_generics #where_clause {
+            fn any() -> Self {
+                let obj = #body;
+                #kani_path::assume(#cond);
+                obj
+            }
+        }
+    };
+    proc_macro::TokenStream::from(expanded)
+}
+
+/// Abort if the condition of `#[kani::arbitrary(where = ...)]` accesses a field of `self` that
+/// the struct doesn't have.
+struct ConstraintFields<'a> {
+    fields: &'a Fields,
+}
+
+impl<'ast> Visit<'ast> for ConstraintFields<'_> {
+    fn visit_expr_field(&mut self, expr: &'ast syn::ExprField) {
+        if matches!(&*expr.base, syn::Expr::Path(path) if path.path.is_ident("self")) {
+            let is_field = match (&expr.member, self.fields) {
+                (Member::Named(name), Fields::Named(fields)) => {
+                    fields.named.iter().any(|field| field.ident.as_ref() == Some(name))
+                }
+                (Member::Unnamed(index), Fields::Unnamed(fields)) => {
+                    (index.index as usize) < fields.unnamed.len()
+                }
+                _ => false,
+            };
+            if !is_field {
+                let member = &expr.member;
+                abort!(expr.member, "`{}` is not a field of this struct", quote!(#member);
+                    help = "the condition of `#[kani::arbitrary(where = ...)]` can only access the fields of `self`"
+                );
+            }
+        }
+        visit::visit_expr_field(self, expr)
+    }
+}
+
+/// Replace `self` in the condition of `#[kani::arbitrary(where = ...)]` by the generated value.
+struct SelfToObj;
+
+impl VisitMut for SelfToObj {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        if matches!(expr, syn::Expr::Path(path) if path.path.is_ident("self")) {
+            *expr = parse_quote!(obj);
+        } else {
+            visit_mut::visit_expr_mut(self, expr)
+        }
+    }
+}
+
 /// Add a bound `T: Arbitrary` to every type parameter T.
 fn add_trait_bound_arbitrary(mut generics: Generics) -> Generics {
     let kani_path = kani_path();
@@ -238,8 +345,9 @@ fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
             // is. An example is shown in the readme of the parent directory.
             let init = fields.named.iter().map(|field| {
                 let name = &field.ident;
+                let any = any_field(ident, field);
                 quote_spanned! {field.span()=>
-                    #name: kani::any()
+                    #name: #any
                 }
             });
             quote! {
@@ -249,13 +357,7 @@ fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
         Fields::Unnamed(fields) => {
             // Expands to an expression like
             // Self(kani::any(), kani::any(), ..., kani::any());
-            let init = fields.unnamed.iter().map(|field| {
-                let span = field.span();
-                let kani_path = kani_path_spanned(span);
-                quote_spanned! {span=>
-                    #kani_path::any()
-                }
-            });
+            let init = fields.unnamed.iter().map(|field| any_field(ident, field));
             quote! {
                 #ident(#( #init,)*)
             }
@@ -268,6 +370,30 @@ fn init_symbolic_item(ident: &Ident, fields: &Fields) -> TokenStream {
     }
 }
 
+/// Generate the symbolic value of a field, which is `kani::any_between(<lo>, <hi>)` if the field
+/// has a `#[between(<lo>, <hi>)]` attribute, and `kani::any()` otherwise.
+fn any_field(ident: &Ident, field: &syn::Field) -> TokenStream {
+    let span = field.span();
+    let kani_path = kani_path_spanned(span);
+    let Some(attr) = field.attrs.iter().find(|attr| attr.path().is_ident("between")) else {
+        return quote_spanned! {span=> #kani_path::any() };
+    };
+    match attr.parse_args_with(Punctuated::<syn::Expr, Token![,]>::parse_terminated) {
+        Ok(bounds) if bounds.len() == 2 => {
+            let (lo, hi) = (&bounds[0], &bounds[1]);
+            quote_spanned! {span=> #kani_path::any_between(#lo, #hi) }
+        }
+        Ok(_) => abort!(Span::call_site(), "Cannot derive impl for `{}`", ident;
+            note = attr.span() =>
+            "expected the inclusive bounds of the field, as in `#[between(<lo>, <hi>)]`"
+        ),
+        Err(err) => abort!(Span::call_site(), "Cannot derive impl for `{}`", ident;
+            note = attr.span() =>
+            "bounds of the field could not be parsed: {}", err
+        ),
+    }
+}
+
 /// Extract, parse and return the expression `cond` (i.e., `Some(cond)`) in the
 /// `#[safety_constraint(<cond>)]` attribute helper associated with a given field.
 /// Return `None` if the attribute isn't specified.
@@ -417,6 +543,7 @@ pub fn expand_derive_invariant(item: proc_macro::TokenStream) -> proc_macro::Tok
 
     let safe_body = safe_body_with_calls(item_name, &derive_item, trait_name);
     let field_refs = field_refs(item_name, &derive_item.data);
+    let derived_marker = derived_invariant_marker();
 
     // Add a bound `T: Invariant` to every type parameter T.
     let generics = add_trait_bound_invariant(derive_item.generics);
@@ -426,6 +553,7 @@ pub fn expand_derive_invariant(item: proc_macro::TokenStream) -> proc_macro::Tok
     let expanded = quote! {
         // The generated implementation.
         impl #impl_generics #kani_path::Invariant for #item_name #ty_generics #where_clause {
+            #derived_marker
             fn is_safe(&self) -> bool {
                 let obj = self;
                 #field_refs
@@ -436,6 +564,17 @@ fn is_safe(&self) -> bool {
     proc_macro::TokenStream::from(expanded)
 }
 
+/// Mark the derived `is_safe` implementation so the compiler can make `kani::any()` assume it.
+#[cfg(kani_sysroot)]
+fn derived_invariant_marker() -> TokenStream {
+    quote! { #[kanitool::fn_marker = "kani_derived_invariant"] }
+}
+
+#[cfg(not(kani_sysroot))]
+fn derived_invariant_marker() -> TokenStream {
+    TokenStream::new()
+}
+
 /// Looks for `#[safety_constraint(...)]` attributes used in the struct or its
 /// fields, and returns the constraints if there were any, otherwise returns
 /// `None`.
@@ -9,8 +9,10 @@
 //   RUSTFLAGS="-Zcrate-attr=feature(register_tool) -Zcrate-attr=register_tool(kanitool)"
 #![feature(proc_macro_diagnostic)]
 #![feature(proc_macro_span)]
+mod contract_closure;
 mod derive;
 mod derive_bounded;
+mod state_machine;
 
 // proc_macro::quote is nightly-only, so we'll cobble things together instead
 use proc_macro::TokenStream;
@@ -30,6 +32,46 @@
 /// e.g. `#[kani::proof(schedule = kani::RoundRobin::default())]`.
 ///
 /// This will wrap the async function in a call to [`block_on_with_spawn`](https://model-checking.github.io/kani/crates/doc/kani/futures/fn.block_on_with_spawn.html) (see its documentation for more information).
+///
+/// The arguments of a harness, if any, are arbitrary values, i.e., Kani calls the harness with
+/// `kani::any()` for each argument, so their types must implement `kani::Arbitrary`. Since the
+/// attribute is ignored outside of Kani, the same function can be used as a property-based test:
+/// ```ignore
+/// #[cfg_attr(kani, kani::proof)]
+/// #[cfg_attr(test, proptest::property_test)]
+/// fn check_abs(x: i8) {
+///     if x != i8::MIN {
+///         assert!(x.abs() >= 0);
+///     }
+/// }
+/// ```
+///
+/// Use `#[kani::proof(isolate = "process")]` to compile and verify a harness in a separate
+/// process, so that running out of memory or crashing only fails this harness.
+///
+/// Use `#[kani::proof(array_encoding = "uf")]` to encode every array of the harness with
+/// uninterpreted functions, which can make harnesses with large arrays tractable, or
+/// `array_encoding = "field_sensitive"` to track each element of its arrays separately. By
+/// default, Kani picks the encoding from the size of the largest array of the harness.
+///
+/// Use `#[kani::proof(split_on = "input")]` to verify each variant of the enum argument `input`
+/// in a separate run of the solver. The runs are smaller than the run that covers every variant,
+/// and they run in parallel. Kani reports the result of each variant, and the harness succeeds if
+/// all of them do. Since there is no model of the whole harness, such harnesses can't be used with
+/// `--emit`, `--compare-backend` or `--synthesize-loop-contracts`.
+///
+/// Use `#[kani::proof(recursion_limit = 5)]` to bound the depth of the recursive calls of the
+/// harness. Recursive calls beyond that depth are not explored, and are reported as failures of
+/// the recursion limit rather than of the harness.
+///
+/// Use `#[kani::proof(checks(overflow = false, unwinding = true))]` to turn categories of checks
+/// off or on for this harness, regardless of the command-line options. The categories are
+/// `overflow`, `bounds` and `unwinding`.
+///
+/// Use `#[kani::proof(cbmc_checks(disable = "pointer-overflow"))]` to turn off some of the checks
+/// that CBMC adds automatically for this harness, or `enable = "..."` to turn them on. Both
+/// options take a comma-separated list of `bounds`, `pointer`, `pointer-overflow`,
+/// `pointer-primitive`, `div-by-zero`, `float-overflow`, `nan` and `conversion`.
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -44,10 +86,16 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// allows users to exercise [negative testing](https://en.wikipedia.org/wiki/Negative_testing)
 /// for Rust unit tests.
 ///
+/// Use `#[kani::should_panic(expected = "<message>")]` to also require the message of every panic
+/// to contain `<message>`, so the harness doesn't succeed because of an unrelated panic.
+///
 /// # Limitations
 ///
 /// The `#[kani::should_panic]` attribute verifies that there are one or more failed checks related to panics.
-/// At the moment, it's not possible to pin it down to specific panics.
+/// The message of a panic that is formatted at runtime is the source text of its arguments, e.g.,
+/// the `expected` message of `panic!("{} is too large", x)` can be `"is too large"`, but it can't
+/// contain the value of `x`.
+#[proc_macro_error]
 #[proc_macro_attribute]
 pub fn should_panic(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::should_panic(attr, item)
@@ -66,12 +114,38 @@ pub fn recursion(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Set Loop unwind limit for proof harnesses
 /// The attribute `#[kani::unwind(arg)]` can only be called alongside `#[kani::proof]`.
 /// arg - Takes in a integer value (u32) that represents the unwind value for the harness.
+///
+/// The unwind value of a labeled loop of the harness can be set with `<label> = <u32>`, e.g.
+/// `#[kani::unwind(outer = 3, inner = 64)]` for the loops `'outer` and `'inner`. These values take
+/// precedence over the unwind value of the harness, which may come first, e.g.
+/// `#[kani::unwind(8, inner = 64)]`.
 #[allow(clippy::too_long_first_doc_paragraph)]
 #[proc_macro_attribute]
 pub fn unwind(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::unwind(attr, item)
 }
 
+/// Allow the `as` casts of a statement or expression to lose information.
+///
+/// With `-Z truncation-checks`, Kani checks that every integer `as` cast that may not preserve its
+/// value, and every float-to-integer cast, is applied to a value that the target type can
+/// represent. Since the checks of float-to-integer casts use the range check of
+/// `kani::float::float_to_int_in_range`, `-Z truncation-checks` also enables `-Z float-lib`.
+/// Casts inside a statement or an expression annotated with this attribute are not checked, e.g.:
+///
+/// ```ignore
+/// #[kani::allow_truncation]
+/// let low_byte = word as u8;
+/// ```
+///
+/// Attributes on statements and expressions require `#![feature(proc_macro_hygiene)]` and
+/// `#![feature(stmt_expr_attributes)]`.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn allow_truncation(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::allow_truncation(attr, item)
+}
+
 /// Specify a function/method stub pair to use for proof harness
 ///
 /// The attribute `#[kani::stub(original, replacement)]` can only be used alongside `#[kani::proof]`.
@@ -84,6 +158,42 @@ pub fn stub(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::stub(attr, item)
 }
 
+/// Specify a type and a model type that should replace it for a proof harness.
+///
+/// The attribute `#[kani::stub_type(original, replacement)]` can only be used alongside
+/// `#[kani::proof]`. Every inherent method of `original` is stubbed by the method with the same
+/// name in `replacement`, which must have the same generic parameters and the same memory layout.
+///
+/// # Arguments
+/// * `original` - The struct, enum or union to replace, specified as a path.
+/// * `replacement` - The model type to use as a replacement, specified as a path.
+#[proc_macro_attribute]
+pub fn stub_type(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::stub_type(attr, item)
+}
+
+/// Ignore the `global_asm!` items of a module, which Kani cannot verify, instead of the ones of
+/// the whole crate with `--ignore-global-asm`.
+///
+/// The optional arguments are the statics that the assembly may assign, as paths relative to the
+/// module, which must declare them. Kani doesn't know their values when a harness starts, so every
+/// value of their types is considered, while the other statics keep their initial values.
+///
+/// ```ignore
+/// #[kani::ignore_global_asm(COUNTER)]
+/// mod startup {
+///     pub static mut COUNTER: u32 = 0;
+///     core::arch::global_asm!(/* assembly that increments COUNTER */);
+/// }
+/// ```
+///
+/// Attributes on inline modules are stable, but the ones on modules in their own file are not. For
+/// the latter, use `#[cfg_attr(kani, kanitool::ignore_global_asm(...))]` instead.
+#[proc_macro_attribute]
+pub fn ignore_global_asm(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::ignore_global_asm(attr, item)
+}
+
 /// Select the SAT solver to use with CBMC for this harness
 ///
 /// The attribute `#[kani::solver(arg)]` can only be used alongside `#[kani::proof]`.
@@ -94,6 +204,25 @@ pub fn solver(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::solver(attr, item)
 }
 
+/// Configure the harness that `kani autoharness` generates for this function.
+///
+/// The attribute `#[kani::autoharness(...)]` cannot be used alongside `#[kani::proof]`, and it
+/// accepts the following options, all of which are optional:
+/// * `unwind = <u32>` - The unwind value for the harness, like `#[kani::unwind]`.
+/// * `solver = <solver>` - The solver to use for the harness, like `#[kani::solver]`.
+/// * `timeout = "<timeout>"` - The timeout for the harness, using the format of `--harness-timeout`.
+///
+/// These values take precedence over the global defaults of the `autoharness` subcommand.
+///
+/// ```ignore
+/// #[kani::autoharness(unwind = 8, solver = cadical, timeout = "120s")]
+/// fn checksum(data: [u8; 7]) -> u8 { ... }
+/// ```
+#[proc_macro_attribute]
+pub fn autoharness(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::autoharness(attr, item)
+}
+
 /// Mark an API as unstable. This should only be used inside the Kani sysroot.
 /// See https://model-checking.github.io/kani/rfc/rfcs/0006-unstable-api.html for more details.
 #[doc(hidden)]
@@ -202,12 +331,50 @@ pub fn unstable_feature(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     y: i32,
 /// }
 /// ```
+///
+/// ## Integer ranges with the `#[between(<lo>, <hi>)]` attribute
+///
+/// The `#[between(<lo>, <hi>)]` attribute restricts an integer field to the values between `lo`
+/// and `hi`, inclusive. The field is generated with `kani::any_between(<lo>, <hi>)`, which is
+/// cheaper for the solver than the equivalent `#[safety_constraint(...)]` attribute:
+///
+/// ```rust
+/// #[derive(Arbitrary)]
+/// struct Date {
+///     #[between(1, 12)]
+///     month: u8,
+///     #[between(1, 31)]
+///     day: u8,
+/// }
+/// ```
 #[proc_macro_error]
-#[proc_macro_derive(Arbitrary, attributes(safety_constraint))]
+#[proc_macro_derive(Arbitrary, attributes(safety_constraint, between))]
 pub fn derive_arbitrary(item: TokenStream) -> TokenStream {
     derive::expand_derive_arbitrary(item)
 }
 
+/// Implement `Arbitrary` for a struct like `#[derive(Arbitrary)]`, but only generate values that
+/// satisfy the given condition, so that callers of `kani::any()` don't need to assume it.
+///
+/// The condition is a string with a Rust expression that refers to the fields of the generated
+/// value through `self`. Accessing anything other than a field of `self` is a compilation error.
+///
+/// ```rust
+/// #[kani::arbitrary(where = "self.len <= self.cap")]
+/// struct Buffer {
+///     len: usize,
+///     cap: usize,
+/// }
+/// ```
+///
+/// Don't combine this attribute with `#[derive(Arbitrary)]` on the same struct, since both
+/// implement the trait.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn arbitrary(attr: TokenStream, item: TokenStream) -> TokenStream {
+    derive::expand_arbitrary_attr(attr, item)
+}
+
 /// Allow users to generate `BoundedArbitrary` implementations by using the
 /// `#[derive(BoundedArbitrary)]` macro.
 ///
@@ -293,6 +460,10 @@ pub fn derive_bounded_arbitrary(item: TokenStream) -> TokenStream {
 /// Note: the assignments to `obj` and `inner` are made so that we can treat the
 /// fields as if they were references.
 ///
+/// Values of a type with a derived `Invariant` implementation that are created
+/// with `kani::any()` are assumed to satisfy `is_safe()`, even if the
+/// `Arbitrary` implementation was written by hand.
+///
 /// ### Adding `#[safety_constraint(...)]` to the struct as opposed to its fields
 ///
 /// As mentioned earlier, the `#[safety_constraint(...)]` attribute can be added
@@ -376,6 +547,57 @@ pub fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::ensures(attr, item)
 }
 
+/// Specify exactly when this function panics.
+///
+/// This is part of the function contract API, for more general information see
+/// the [module-level documentation](../kani/contracts/index.html).
+///
+/// The contents of the attribute is a condition over the input values to the
+/// annotated function, which holds if and only if the function panics. The
+/// same restrictions as for [`requires`][macro@requires] apply.
+///
+/// The harness that checks the contract fails if the function panics when the
+/// condition doesn't hold, or returns when it does. When the contract replaces
+/// the function, calling it with arguments that satisfy the condition panics.
+///
+/// A function should have at most one such clause. This clause is not supported
+/// with `--cbmc-contracts`.
+#[proc_macro_attribute]
+pub fn ensures_panics_iff(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::ensures_panics_iff(attr, item)
+}
+
+/// Designates this function as a spec function, i.e., a pure function that contracts may call,
+/// e.g. `#[kani::requires(is_sorted(v))]`.
+///
+/// Calls to a spec function always execute its body, even in harnesses that replace the functions
+/// with a contract by their contract, so a spec function cannot be the target of
+/// [`stub_verified`][macro@stub_verified]. A spec function may itself have a contract, which is
+/// checked by its [`proof_for_contract`][macro@proof_for_contract] harnesses.
+///
+/// Spec functions must not take mutable references. They may be recursive, in which case the
+/// harnesses that reach them should bound their recursion with
+/// [`spec_unwind`][macro@spec_unwind].
+///
+/// This is part of the function contract API, for more general information see
+/// the [module-level documentation](../kani/contracts/index.html).
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn spec_fn(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::spec_fn(attr, item)
+}
+
+/// Bound the recursion of the spec functions that this harness reaches.
+///
+/// The attribute `#[kani::spec_unwind(arg)]` can only be used alongside `#[kani::proof]` or
+/// [`proof_for_contract`][macro@proof_for_contract], and takes the maximum number of recursive
+/// calls of each [`spec_fn`][macro@spec_fn]. Unlike [`unwind`][macro@unwind], it doesn't bound
+/// the loops or the recursion of other functions.
+#[proc_macro_attribute]
+pub fn spec_unwind(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::spec_unwind(attr, item)
+}
+
 /// Designates this function as a harness to check a function contract.
 ///
 /// The argument to this macro is the relative path (e.g. `foo` or
@@ -428,6 +650,66 @@ pub fn modifies(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::modifies(attr, item)
 }
 
+/// Attach a function contract to a closure.
+///
+/// The closure is annotated with the contract attributes, e.g.
+/// [`requires`][macro@requires] and [`ensures`][macro@ensures], and it must declare the types of
+/// its arguments, as well as its return type unless it is `()`.
+///
+/// Closures with contracts cannot capture their environment, since the closure is turned into a
+/// function. Using a variable that the closure doesn't bind, or a `move` closure, is a compilation
+/// error. Such values must be passed to the closure as arguments instead.
+///
+/// If `harness = NAME` is given, a [`proof_for_contract`][macro@proof_for_contract] harness named
+/// `NAME` that checks the contract for arbitrary inputs is generated as well:
+///
+/// ```ignore
+/// let decrement = kani::contract_closure!(
+///     harness = check_decrement,
+///     #[kani::requires(x > 0)]
+///     #[kani::ensures(|result: &u32| *result < x)]
+///     |x: u32| -> u32 { x - 1 }
+/// );
+/// ```
+///
+/// This is part of the function contract API, for more general information see
+/// the [module-level documentation](../kani/contracts/index.html).
+#[proc_macro]
+pub fn contract_closure(item: TokenStream) -> TokenStream {
+    attr_impl::contract_closure(item)
+}
+
+/// Generate a harness that checks that an invariant of a state is preserved by any sequence of
+/// operations, e.g., the messages of a protocol.
+///
+/// The harness starts from the `init` state, and applies up to `steps` operations, each of which
+/// is chosen nondeterministically. It asserts the `invariant` on the initial state and after every
+/// operation. Each operation is a closure that takes a mutable reference to the state, followed by
+/// any number of arguments, for which Kani generates arbitrary values. Attributes before the
+/// options, e.g., `#[kani::solver(kissat)]`, are added to the harness.
+///
+/// ```ignore
+/// kani::state_machine! {
+///     name = check_connection,
+///     init = Connection::new(),
+///     steps = 4,
+///     invariant = |conn: &Connection| conn.sent >= conn.acked,
+///     operations = {
+///         send = |conn: &mut Connection, len: u8| conn.send(len),
+///         ack = |conn: &mut Connection, seq: u32| conn.ack(seq),
+///         reset = |conn: &mut Connection| conn.reset(),
+///     },
+/// }
+/// ```
+///
+/// The harness is annotated with `#[kani::unwind(steps + 1)]`, which bounds the loop over the
+/// operations. If the operations contain loops themselves, add a `#[kani::unwind]` attribute with
+/// a larger bound instead.
+#[proc_macro]
+pub fn state_machine(item: TokenStream) -> TokenStream {
+    attr_impl::state_machine(item)
+}
+
 /// Add a loop invariant to this loop.
 ///
 /// The contents of the attribute is a condition that should be satisfied at the
@@ -444,23 +726,91 @@ pub fn loop_invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
 pub fn loop_modifies(attr: TokenStream, item: TokenStream) -> TokenStream {
     attr_impl::loop_modifies(attr, item)
 }
+
+/// Check that the safety invariant of a type that is meant to be shared holds after every public
+/// method, even if other methods run in the middle of it.
+///
+/// The attribute must be applied to an inherent `impl` block of a type that implements
+/// [`Invariant`](../kani/trait.Invariant.html), e.g., `#[kani::invariant(atomic)]`.
+/// Every public method of the block that takes `&self` assumes the invariant on entry and asserts
+/// it on exit. Between any two statements of its body, other methods of the block may run with
+/// arbitrary arguments, which approximates the effect of concurrent calls on types that use
+/// interior mutability. Only methods without type parameters whose arguments are primitive types
+/// can run at these interleaving points.
+///
+/// Use `#[kani::invariant(atomic, interleavings = N)]` to allow up to `N` methods to run at every
+/// interleaving point. The default is 1.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::invariant(attr, item)
+}
+
+/// Attach a contract to a function declared in an `extern` block, e.g. a function of a C library.
+///
+/// Kani cannot verify code that calls a foreign function without a definition. Calls to a function
+/// with a contract are replaced by a stub that asserts the `requires` clauses, assigns arbitrary
+/// values to the `modifies` targets, and returns an arbitrary value that satisfies the `ensures`
+/// clauses:
+///
+/// ```ignore
+/// #[kani::c_contracts]
+/// unsafe extern "C" {
+///     #[kani::c_contract(
+///         requires = !buf.is_null() && len > 0,
+///         modifies = buf,
+///         ensures = |result: &i32| *result <= len as i32
+///     )]
+///     fn read_bytes(buf: *mut u8, len: usize) -> i32;
+/// }
+/// ```
+///
+/// Each clause may appear more than once. A `requires` clause is a condition over the arguments, an
+/// `ensures` clause is a closure that takes a reference to the return value, and a `modifies`
+/// clause is a pointer or a reference whose pointee implements
+/// [`Arbitrary`](../kani/arbitrary/trait.Arbitrary.html). The return type must implement
+/// `Arbitrary` as well.
+///
+/// The `extern` block must be annotated with [`c_contracts`][macro@c_contracts]. Like the other
+/// contract attributes, this requires the unstable `function-contracts` feature.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn c_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::c_contract(attr, item)
+}
+
+/// Expand the [`c_contract`][macro@c_contract] attributes of the functions in this `extern` block.
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn c_contracts(attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr_impl::c_contracts(attr, item)
+}
 /// This module implements Kani attributes in a way that only Kani's compiler can understand.
 /// This code should only be activated when pre-building Kani's sysroot.
 #[cfg(kani_sysroot)]
 mod sysroot {
     use proc_macro_error2::{abort, abort_call_site};
 
+    mod atomic_invariant;
+    mod c_contracts;
     mod contracts;
     mod loop_contracts;
 
-    pub use contracts::{ensures, modifies, proof_for_contract, requires, stub_verified};
+    pub use atomic_invariant::invariant;
+    pub use c_contracts::{c_contract, c_contracts};
+
+    pub use contracts::{
+        contract_closure, ensures, ensures_panics_iff, modifies, proof_for_contract, requires,
+        stub_verified,
+    };
     pub use loop_contracts::{loop_invariant, loop_modifies};
 
     use super::*;
 
     use {
-        quote::{format_ident, quote},
+        quote::{format_ident, quote, quote_spanned},
         syn::parse::{Parse, ParseStream},
+        syn::spanned::Spanned,
         syn::{ItemFn, parse_macro_input},
     };
 
@@ -492,24 +842,78 @@ pub fn $name(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     struct ProofOptions {
         schedule: Option<syn::Expr>,
+        isolate: Option<syn::LitStr>,
+        array_encoding: Option<syn::LitStr>,
+        recursion_limit: Option<syn::LitInt>,
+        /// The name of the argument whose variants are verified separately.
+        split_on: Option<syn::LitStr>,
+        /// The arguments of `cbmc_checks(...)`, which the compiler validates.
+        cbmc_checks: Option<proc_macro2::TokenStream>,
+        /// The arguments of `checks(...)`, which the compiler validates.
+        checks: Option<proc_macro2::TokenStream>,
     }
 
     impl Parse for ProofOptions {
         fn parse(input: ParseStream) -> syn::Result<Self> {
-            if input.is_empty() {
-                Ok(ProofOptions { schedule: None })
-            } else {
+            let mut options = ProofOptions {
+                schedule: None,
+                isolate: None,
+                array_encoding: None,
+                recursion_limit: None,
+                split_on: None,
+                cbmc_checks: None,
+                checks: None,
+            };
+            while !input.is_empty() {
                 let ident = input.parse::<syn::Ident>()?;
-                if ident != "schedule" {
+                if ident == "cbmc_checks" || ident == "checks" {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    if ident == "checks" {
+                        options.checks = Some(content.parse()?);
+                    } else {
+                        options.cbmc_checks = Some(content.parse()?);
+                    }
+                    if !input.is_empty() {
+                        let _ = input.parse::<syn::Token![,]>()?;
+                    }
+                    continue;
+                }
+                let _ = input.parse::<syn::Token![=]>()?;
+                if ident == "schedule" {
+                    options.schedule = Some(input.parse::<syn::Expr>()?);
+                } else if ident == "isolate" {
+                    let isolate = input.parse::<syn::LitStr>()?;
+                    if isolate.value() != "process" {
+                        abort!(isolate, "`{}` is not a valid isolation.", isolate.value();
+                            help = "use `isolate = \"process\"`";
+                        );
+                    }
+                    options.isolate = Some(isolate);
+                } else if ident == "array_encoding" {
+                    let encoding = input.parse::<syn::LitStr>()?;
+                    if !["uf", "field_sensitive"].contains(&encoding.value().as_str()) {
+                        abort!(encoding, "`{}` is not a valid array encoding.", encoding.value();
+                            help = "use `array_encoding = \"uf\"` or `array_encoding = \"field_sensitive\"`";
+                        );
+                    }
+                    options.array_encoding = Some(encoding);
+                } else if ident == "recursion_limit" {
+                    let limit = input.parse::<syn::LitInt>()?;
+                    limit.base10_parse::<u32>()?;
+                    options.recursion_limit = Some(limit);
+                } else if ident == "split_on" {
+                    options.split_on = Some(input.parse::<syn::LitStr>()?);
+                } else {
                     abort_call_site!("`{}` is not a valid option for `#[kani::proof]`.", ident;
-                        help = "did you mean `schedule`?";
-                        note = "for now, `schedule` is the only option for `#[kani::proof]`.";
+                        note = "the options of `#[kani::proof]` are `schedule`, `isolate`, `array_encoding`, `recursion_limit`, `split_on`, `checks` and `cbmc_checks`.";
                     );
                 }
-                let _ = input.parse::<syn::Token![=]>()?;
-                let schedule = Some(input.parse::<syn::Expr>()?);
-                Ok(ProofOptions { schedule })
+                if !input.is_empty() {
+                    let _ = input.parse::<syn::Token![,]>()?;
+                }
             }
+            Ok(options)
         }
     }
 
@@ -521,11 +925,57 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
         let sig = fn_item.sig;
         let body = fn_item.block;
 
+        let isolate = proof_options.isolate.map(|isolate| quote!(#[kanitool::isolate = #isolate]));
+        let array_encoding = proof_options
+            .array_encoding
+            .map(|encoding| quote!(#[kanitool::array_encoding = #encoding]));
+        let recursion_limit =
+            proof_options.recursion_limit.map(|limit| quote!(#[kanitool::recursion_limit(#limit)]));
+        let cbmc_checks =
+            proof_options.cbmc_checks.map(|checks| quote!(#[kanitool::cbmc_checks(#checks)]));
+        let checks = proof_options.checks.map(|checks| quote!(#[kanitool::checks(#checks)]));
+        let split_on =
+            proof_options.split_on.as_ref().map(|input| quote!(#[kanitool::split_on = #input]));
         let kani_attributes = quote!(
             #[allow(dead_code)]
             #[kanitool::proof]
+            #isolate
+            #array_encoding
+            #recursion_limit
+            #split_on
+            #checks
+            #cbmc_checks
         );
 
+        if let Some(receiver) = sig.receiver() {
+            abort!(receiver, "`#[kani::proof]` cannot be applied to methods");
+        }
+        // Kani generates an arbitrary value for each argument of the harness. The argument that
+        // the harness is split on is constrained to the variant of the current run.
+        let split_arg = proof_options.split_on.as_ref().map(|split_on| {
+            let arg = sig.inputs.iter().position(|input| {
+                matches!(input, syn::FnArg::Typed(syn::PatType { pat, .. })
+                    if matches!(pat.as_ref(), syn::Pat::Ident(pat) if pat.ident == split_on.value()))
+            });
+            arg.unwrap_or_else(|| {
+                abort!(split_on, "`{}` is not an argument of the harness.", split_on.value();
+                    help = "`split_on` takes the name of an argument whose type is an enum";
+                )
+            })
+        });
+        let fn_name = &sig.ident;
+        let any_args = sig.inputs.iter().enumerate().map(|(idx, input)| match input {
+            syn::FnArg::Typed(syn::PatType { ty, .. }) if Some(idx) == split_arg => {
+                quote_spanned!(input.span()=> {
+                    let value: #ty = kani::any();
+                    kani::internal::split_case(&value);
+                    value
+                })
+            }
+            _ => quote_spanned!(input.span()=> kani::any()),
+        });
+        let call = quote!(#fn_name(#(#any_args),*));
+
         if sig.asyncness.is_none() {
             if proof_options.schedule.is_some() {
                 abort_call_site!(
@@ -533,11 +983,39 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
                     help = "did you mean to make this function `async`?";
                 );
             }
-            // Adds `#[kanitool::proof]` and other attributes
+            if sig.inputs.is_empty() {
+                // Adds `#[kanitool::proof]` and other attributes
+                return quote!(
+                    #kani_attributes
+                    #(#attrs)*
+                    #vis #sig #body
+                )
+                .into();
+            }
+            // For functions with arguments, it translates
+            // ```ignore
+            // #[kani::proof]
+            // #[attribute]
+            // pub fn harness(x: u8, flag: bool) { ... }
+            // ```
+            // to
+            // ```ignore
+            // #[kanitool::proof]
+            // #[attribute]
+            // pub fn harness() {
+            //   fn harness(x: u8, flag: bool) { ... }
+            //   harness(kani::any(), kani::any())
+            // }
+            // ```
+            let mut modified_sig = sig.clone();
+            modified_sig.inputs.clear();
             quote!(
                 #kani_attributes
                 #(#attrs)*
-                #vis #sig #body
+                #vis #modified_sig {
+                    #sig #body
+                    #call
+                }
             )
             .into()
         } else {
@@ -560,21 +1038,15 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
             //   // where `schedule` was provided as an argument to `#[kani::proof]`.
             // }
             // ```
-            if !sig.inputs.is_empty() {
-                abort!(
-                    sig.inputs,
-                    "`#[kani::proof]` cannot be applied to async functions that take arguments for now";
-                    help = "try removing the arguments";
-                );
-            }
+            // Like for synchronous functions, the arguments of `harness` are arbitrary values.
             let mut modified_sig = sig.clone();
             modified_sig.asyncness = None;
-            let fn_name = &sig.ident;
+            modified_sig.inputs.clear();
             let schedule = proof_options.schedule;
             let block_on_call = if let Some(schedule) = schedule {
-                quote!(kani::block_on_with_spawn(#fn_name(), #schedule))
+                quote!(kani::block_on_with_spawn(#call, #schedule))
             } else {
-                quote!(kani::block_on(#fn_name()))
+                quote!(kani::block_on(#call))
             };
             quote!(
                 #kani_attributes
@@ -588,12 +1060,114 @@ pub fn proof(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     }
 
-    kani_attribute!(should_panic, no_args);
+    kani_attribute!(autoharness);
+    /// The message of `#[kani::should_panic(expected = "<message>")]`.
+    struct ExpectedPanic {
+        message: syn::LitStr,
+    }
+
+    impl Parse for ExpectedPanic {
+        fn parse(input: ParseStream) -> syn::Result<Self> {
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "expected" {
+                abort!(ident, "`{}` is not a valid option for `#[kani::should_panic]`.", ident;
+                    help = "use `#[kani::should_panic(expected = \"<message>\")]`";
+                );
+            }
+            let _ = input.parse::<syn::Token![=]>()?;
+            let message = input.parse::<syn::LitStr>()?;
+            if !input.is_empty() {
+                let _ = input.parse::<syn::Token![,]>()?;
+            }
+            Ok(ExpectedPanic { message })
+        }
+    }
+
+    pub fn should_panic(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let expected =
+            if attr.is_empty() { None } else { Some(parse_macro_input!(attr as ExpectedPanic)) };
+        let fn_item = parse_macro_input!(item as ItemFn);
+        let attribute = if let Some(ExpectedPanic { message }) = expected {
+            quote!(#[kanitool::should_panic = #message])
+        } else {
+            quote!(#[kanitool::should_panic])
+        };
+        quote!(
+            #attribute
+            #fn_item
+        )
+        .into()
+    }
+
     kani_attribute!(recursion, no_args);
     kani_attribute!(solver);
     kani_attribute!(stub);
+    kani_attribute!(stub_type);
     kani_attribute!(unstable);
     kani_attribute!(unwind);
+    kani_attribute!(spec_unwind);
+
+    /// Mark the statement or expression, whose casts the truncation checks skip.
+    pub fn allow_truncation(attr: TokenStream, item: TokenStream) -> TokenStream {
+        if !attr.is_empty() {
+            abort_call_site!("`#[kani::allow_truncation]` does not take any arguments");
+        }
+        let item = proc_macro2::TokenStream::from(item);
+        quote!(
+            #[kanitool::allow_truncation]
+            #item
+        )
+        .into()
+    }
+
+    /// Mark the module, whose global assembly the compiler ignores.
+    pub fn ignore_global_asm(attr: TokenStream, item: TokenStream) -> TokenStream {
+        let args = proc_macro2::TokenStream::from(attr);
+        let mod_item = parse_macro_input!(item as syn::ItemMod);
+        let attribute = if args.is_empty() {
+            quote!(#[kanitool::ignore_global_asm])
+        } else {
+            quote!(#[kanitool::ignore_global_asm(#args)])
+        };
+        quote!(
+            #attribute
+            #mod_item
+        )
+        .into()
+    }
+
+    /// Generate the harness with the attributes that Kani uses to verify it.
+    pub fn state_machine(item: TokenStream) -> TokenStream {
+        parse_macro_input!(item as crate::state_machine::StateMachine).expand(true).into()
+    }
+
+    pub fn spec_fn(attr: TokenStream, item: TokenStream) -> TokenStream {
+        if !attr.is_empty() {
+            abort_call_site!("`#[kani::spec_fn]` does not take any arguments");
+        }
+        let fn_item = parse_macro_input!(item as ItemFn);
+        if let Some(asyncness) = fn_item.sig.asyncness {
+            abort!(asyncness, "spec functions cannot be `async`");
+        }
+        for input in &fn_item.sig.inputs {
+            let is_mutable = match input {
+                syn::FnArg::Receiver(receiver) => receiver.mutability.is_some(),
+                syn::FnArg::Typed(pat_type) => {
+                    matches!(&*pat_type.ty, syn::Type::Reference(ty) if ty.mutability.is_some())
+                }
+            };
+            if is_mutable {
+                abort!(input, "spec functions must not take mutable references";
+                    note = "the contracts that call spec functions must not have side effects";
+                );
+            }
+        }
+        quote!(
+            #[kanitool::spec_fn]
+            #fn_item
+        )
+        .into()
+    }
 }
 
 /// This module provides dummy implementations of Kani attributes which cannot be interpreted by
@@ -614,24 +1188,68 @@ pub fn $name(_attr: TokenStream, item: TokenStream) -> TokenStream {
     }
 
     /// Add #[allow(dead_code)] to a proof harness to avoid dead code warnings.
+    ///
+    /// Like with Kani, a harness with arguments becomes a function without arguments that calls it
+    /// with `kani::any()` for each argument, so that concrete playback can run it.
     pub fn proof(_attr: TokenStream, item: TokenStream) -> TokenStream {
-        let mut result = TokenStream::new();
-        result.extend("#[allow(dead_code)]".parse::<TokenStream>().unwrap());
-        result.extend(item);
-        result
+        let syn::ItemFn { attrs, vis, sig, block } = syn::parse_macro_input!(item as syn::ItemFn);
+        if sig.inputs.is_empty() || sig.asyncness.is_some() || sig.receiver().is_some() {
+            return quote::quote!(
+                #[allow(dead_code)]
+                #(#attrs)*
+                #vis #sig #block
+            )
+            .into();
+        }
+        let fn_name = &sig.ident;
+        let any_args = sig.inputs.iter().map(|_| quote::quote!(kani::any()));
+        let mut modified_sig = sig.clone();
+        modified_sig.inputs.clear();
+        quote::quote!(
+            #[allow(dead_code)]
+            #(#attrs)*
+            #vis #modified_sig {
+                #sig #block
+                #fn_name(#(#any_args),*)
+            }
+        )
+        .into()
     }
 
+    no_op!(allow_truncation);
+    no_op!(autoharness);
+    no_op!(ignore_global_asm);
     no_op!(should_panic);
     no_op!(recursion);
     no_op!(solver);
     no_op!(stub);
+    no_op!(stub_type);
     no_op!(unstable);
     no_op!(unwind);
+    no_op!(spec_fn);
+    no_op!(spec_unwind);
     no_op!(requires);
     no_op!(ensures);
+    no_op!(ensures_panics_iff);
     no_op!(modifies);
     no_op!(proof_for_contract);
     no_op!(stub_verified);
     no_op!(loop_invariant);
     no_op!(loop_modifies);
+    no_op!(invariant);
+    no_op!(c_contracts);
+    no_op!(c_contract);
+
+    /// Generate the harness without the Kani attributes.
+    pub fn state_machine(item: TokenStream) -> TokenStream {
+        syn::parse_macro_input!(item as crate::state_machine::StateMachine).expand(false).into()
+    }
+
+    /// Drop the contract and return the closure unchanged.
+    pub fn contract_closure(item: TokenStream) -> TokenStream {
+        let crate::contract_closure::ContractClosure { mut closure, .. } =
+            syn::parse_macro_input!(item as crate::contract_closure::ContractClosure);
+        closure.attrs.clear();
+        quote::quote!(#closure).into()
+    }
 }
@@ -0,0 +1,208 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Parsing and expansion of `kani::state_machine!`, which generates a harness that applies a
+//! sequence of nondeterministically chosen operations to a state, and checks an invariant after
+//! each of them. I.e.:
+//!
+//! ```ignore
+//! kani::state_machine! {
+//!     name = check_counter,
+//!     init = Counter::new(),
+//!     steps = 3,
+//!     invariant = |counter: &Counter| counter.value <= counter.max,
+//!     operations = {
+//!         increment = |counter: &mut Counter| counter.increment(),
+//!         set = |counter: &mut Counter, value: u8| counter.set(value),
+//!     },
+//! }
+//! ```
+//!
+//! expands to:
+//!
+//! ```ignore
+//! #[kani::proof]
+//! #[kani::unwind(4)]
+//! fn check_counter() {
+//!     let mut state = Counter::new();
+//!     let invariant = |counter: &Counter| counter.value <= counter.max;
+//!     kani::assert(invariant(&state), "state machine invariant holds initially");
+//!     for _ in 0..3 {
+//!         match kani::any::<usize>() {
+//!             0 => {
+//!                 (|counter: &mut Counter| counter.increment())(&mut state);
+//!                 kani::assert(invariant(&state), "state machine invariant holds after `increment`");
+//!             }
+//!             1 => { /* same for `set`, with `kani::any()` as the value */ }
+//!             _ => break,
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Since the loop can stop after any step, the harness covers every sequence of up to `steps`
+//! operations. The unwinding bound covers the loop, but operations that contain loops themselves
+//! need a larger bound, which can be set with a `#[kani::unwind]` attribute on the macro input.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Error, Expr, ExprClosure, Ident, LitInt, LitStr, Token, braced};
+
+/// The input of `kani::state_machine!`.
+pub struct StateMachine {
+    /// Attributes of the harness, such as doc comments or `#[kani::solver]`.
+    attrs: Vec<Attribute>,
+    name: Ident,
+    init: Expr,
+    steps: u32,
+    /// Takes a reference to the state.
+    invariant: ExprClosure,
+    /// Each operation takes a mutable reference to the state, followed by any number of
+    /// arguments, which are arbitrary values.
+    operations: Vec<(Ident, ExprClosure)>,
+}
+
+/// An operation of the state machine, i.e., `NAME = CLOSURE`.
+struct Operation {
+    name: Ident,
+    closure: ExprClosure,
+}
+
+impl Parse for Operation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<Ident>()?;
+        let _ = input.parse::<Token![=]>()?;
+        let closure = input.parse::<ExprClosure>()?;
+        if closure.inputs.is_empty() {
+            return Err(Error::new_spanned(
+                &closure,
+                format!("operation `{name}` must take a mutable reference to the state"),
+            ));
+        }
+        Ok(Operation { name, closure })
+    }
+}
+
+impl Parse for StateMachine {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let (mut name, mut init, mut steps, mut invariant, mut operations) =
+            (None, None, None, None, None);
+        while !input.is_empty() {
+            let option = input.parse::<Ident>()?;
+            let _ = input.parse::<Token![=]>()?;
+            match option.to_string().as_str() {
+                "name" => name = Some(input.parse::<Ident>()?),
+                "init" => init = Some(input.parse::<Expr>()?),
+                "steps" => {
+                    let lit = input.parse::<LitInt>()?;
+                    let value = lit.base10_parse::<u32>()?;
+                    if value == 0 || value == u32::MAX {
+                        return Err(Error::new_spanned(
+                            lit,
+                            "the number of steps must be between 1 and `u32::MAX - 1`",
+                        ));
+                    }
+                    steps = Some(value);
+                }
+                "invariant" => {
+                    let closure = input.parse::<ExprClosure>()?;
+                    if closure.inputs.len() != 1 {
+                        return Err(Error::new_spanned(
+                            &closure,
+                            "the invariant must take a reference to the state",
+                        ));
+                    }
+                    invariant = Some(closure);
+                }
+                "operations" => {
+                    let content;
+                    braced!(content in input);
+                    let ops = Punctuated::<Operation, Token![,]>::parse_terminated(&content)?;
+                    if ops.is_empty() {
+                        return Err(Error::new(option.span(), "expected at least one operation"));
+                    }
+                    operations =
+                        Some(ops.into_iter().map(|op| (op.name, op.closure)).collect::<Vec<_>>());
+                }
+                _ => {
+                    return Err(Error::new(
+                        option.span(),
+                        format!(
+                            "`{option}` is not a valid option for `kani::state_machine`, expected \
+                            `name`, `init`, `steps`, `invariant` or `operations`"
+                        ),
+                    ));
+                }
+            }
+            if !input.is_empty() {
+                let _ = input.parse::<Token![,]>()?;
+            }
+        }
+        let missing = |option: &str| {
+            Error::new(
+                input.span(),
+                format!("`kani::state_machine` requires the `{option}` option"),
+            )
+        };
+        Ok(StateMachine {
+            attrs,
+            name: name.ok_or_else(|| missing("name"))?,
+            init: init.ok_or_else(|| missing("init"))?,
+            steps: steps.ok_or_else(|| missing("steps"))?,
+            invariant: invariant.ok_or_else(|| missing("invariant"))?,
+            operations: operations.ok_or_else(|| missing("operations"))?,
+        })
+    }
+}
+
+impl StateMachine {
+    /// Generate the harness. Its Kani attributes are only added when compiling with Kani, since
+    /// other tools don't understand them.
+    pub fn expand(self, with_kani: bool) -> TokenStream2 {
+        let StateMachine { attrs, name, init, steps, invariant, operations } = self;
+        let arms = operations.iter().enumerate().map(|(idx, (op_name, closure))| {
+            let args = (1..closure.inputs.len()).map(|_| quote!(kani::any()));
+            let msg = LitStr::new(
+                &format!("state machine invariant holds after `{op_name}`"),
+                op_name.span(),
+            );
+            quote!(
+                #idx => {
+                    (#closure)(&mut state #(, #args)*);
+                    kani::assert(invariant(&state), #msg);
+                }
+            )
+        });
+        let has_unwind =
+            attrs.iter().any(|attr| attr.path().segments.last().unwrap().ident == "unwind");
+        let kani_attrs = if !with_kani {
+            quote!(#[allow(dead_code)])
+        } else if has_unwind {
+            quote!(#[kani::proof])
+        } else {
+            let unwind = LitInt::new(&(steps + 1).to_string(), name.span());
+            quote!(
+                #[kani::proof]
+                #[kani::unwind(#unwind)]
+            )
+        };
+        quote!(
+            #kani_attrs
+            #(#attrs)*
+            fn #name() {
+                let mut state = #init;
+                let invariant = #invariant;
+                kani::assert(invariant(&state), "state machine invariant holds initially");
+                for _ in 0..#steps {
+                    match kani::any::<usize>() {
+                        #(#arms)*
+                        _ => break,
+                    }
+                }
+            }
+        )
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of `#[kani::invariant(atomic)]`.
+//!
+//! For every public method that takes `&self` in the annotated `impl` block, we generate:
+//! 1. `__kani_atomic_<name>`: a private copy of the original method, which runs without
+//!    interleavings.
+//! 2. `__kani_interleaved_<name>`: a private copy of the original method with an interleaving
+//!    point between every two statements of its body.
+//! 3. The public method itself, which assumes the invariant of `self`, calls the interleaved copy,
+//!    and asserts that the invariant still holds.
+//!
+//! At an interleaving point, up to `interleavings` nondeterministically chosen methods of the
+//! `impl` block run with arbitrary arguments, each of which must preserve the invariant as well.
+//! Only methods without type parameters whose arguments are primitive types can be chosen, since
+//! we generate their arguments with `kani::any()`.
+
+use proc_macro::TokenStream;
+use proc_macro_error2::abort;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    FnArg, GenericParam, Ident, ImplItem, ImplItemFn, ItemImpl, LitInt, Stmt, Type, Visibility,
+    parse_macro_input, parse_quote,
+};
+
+/// The primitive types that we can generate arguments of interleaved calls for.
+const PRIMITIVES: [&str; 16] = [
+    "bool", "char", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128",
+    "isize", "f32", "f64",
+];
+
+struct AtomicOptions {
+    /// The maximum number of methods that run at every interleaving point.
+    interleavings: usize,
+}
+
+impl Parse for AtomicOptions {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mode = input.parse::<Ident>()?;
+        if mode != "atomic" {
+            abort!(mode, "`{}` is not a valid mode for `#[kani::invariant]`.", mode;
+                help = "use `#[kani::invariant(atomic)]`";
+            );
+        }
+        let mut options = AtomicOptions { interleavings: 1 };
+        while !input.is_empty() {
+            let _ = input.parse::<syn::Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let ident = input.parse::<Ident>()?;
+            let _ = input.parse::<syn::Token![=]>()?;
+            if ident != "interleavings" {
+                abort!(ident, "`{}` is not a valid option for `#[kani::invariant(atomic)]`.", ident;
+                    note = "the only option of `#[kani::invariant(atomic)]` is `interleavings`.";
+                );
+            }
+            options.interleavings = input.parse::<LitInt>()?.base10_parse()?;
+        }
+        Ok(options)
+    }
+}
+
+pub fn invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let options = parse_macro_input!(attr as AtomicOptions);
+    let mut item_impl = parse_macro_input!(item as ItemImpl);
+    if let Some((_, path, _)) = &item_impl.trait_ {
+        abort!(path, "`#[kani::invariant(atomic)]` can only be applied to inherent `impl` blocks");
+    }
+    let type_name = type_name(&item_impl.self_ty);
+    let shared_methods: Vec<ImplItemFn> = item_impl
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Fn(method) if is_shared_method(method) => Some(method.clone()),
+            _ => None,
+        })
+        .collect();
+    let Some(first) = shared_methods.first() else {
+        return quote!(#item_impl).into();
+    };
+    let interleave = format_ident!("__kani_interleave_{}", first.sig.ident);
+    let step = format_ident!("__kani_step_{}", first.sig.ident);
+
+    let mut new_items: Vec<ImplItem> = vec![];
+    let mut step_arms = vec![];
+    for method in &shared_methods {
+        let name = &method.sig.ident;
+        let msg = format!(
+            "`{type_name}::{name}` must preserve the safety invariant of `{type_name}` when other \
+            methods interleave with it"
+        );
+        if is_interleavable(method) {
+            let atomic = format_ident!("__kani_atomic_{name}");
+            let args = method.sig.inputs.iter().skip(1).map(|_| quote!(kani::any()));
+            step_arms.push(quote!({
+                let _ = self.#atomic(#(#args),*);
+                kani::assert(kani::Invariant::is_safe(self), #msg);
+            }));
+            new_items.push(ImplItem::Fn(private_copy(method, atomic)));
+        }
+
+        let interleaved = format_ident!("__kani_interleaved_{name}");
+        let mut interleaved_copy = private_copy(method, interleaved.clone());
+        let stmts = std::mem::take(&mut interleaved_copy.block.stmts);
+        let len = stmts.len();
+        for (idx, stmt) in stmts.into_iter().enumerate() {
+            let is_item = matches!(stmt, Stmt::Item(_));
+            interleaved_copy.block.stmts.push(stmt);
+            if idx + 1 < len && !is_item {
+                interleaved_copy.block.stmts.push(parse_quote!(self.#interleave();));
+            }
+        }
+        new_items.push(ImplItem::Fn(interleaved_copy));
+
+        // Rename the arguments of the public method, since they may be patterns.
+        let mut wrapper = method.clone();
+        let mut args = vec![];
+        for (idx, input) in wrapper.sig.inputs.iter_mut().skip(1).enumerate() {
+            if let FnArg::Typed(pat_type) = input {
+                let arg = format_ident!("__kani_arg_{idx}");
+                *pat_type.pat = parse_quote!(#arg);
+                args.push(arg);
+            }
+        }
+        wrapper.block = parse_quote!({
+            kani::assume(kani::Invariant::is_safe(self));
+            let __kani_result = self.#interleaved(#(#args),*);
+            kani::assert(kani::Invariant::is_safe(self), #msg);
+            __kani_result
+        });
+        let position = item_impl
+            .items
+            .iter()
+            .position(|item| matches!(item, ImplItem::Fn(orig) if orig.sig.ident == *name))
+            .unwrap();
+        item_impl.items[position] = ImplItem::Fn(wrapper);
+    }
+
+    let interleavings = (0..options.interleavings).map(|_| {
+        quote!(if kani::any() {
+            self.#step();
+        })
+    });
+    // Methods with a single statement have no interleaving point.
+    new_items.push(parse_quote!(
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        fn #interleave(&self) {
+            #(#interleavings)*
+        }
+    ));
+    let step_body: TokenStream2 = if let Some((last, arms)) = step_arms.split_last() {
+        // The last method is chosen for every index that is out of range.
+        let indices = 0..arms.len();
+        quote!(match kani::any::<usize>() {
+            #(#indices => #arms)*
+            _ => #last
+        })
+    } else {
+        quote!()
+    };
+    new_items.push(parse_quote!(
+        #[doc(hidden)]
+        fn #step(&self) {
+            #step_body
+        }
+    ));
+    item_impl.items.extend(new_items);
+    quote!(#item_impl).into()
+}
+
+/// Whether `method` is a public safe method that takes `&self`.
+fn is_shared_method(method: &ImplItemFn) -> bool {
+    let sig = &method.sig;
+    matches!(method.vis, Visibility::Public(_))
+        && sig.constness.is_none()
+        && sig.asyncness.is_none()
+        && sig.unsafety.is_none()
+        && sig
+            .receiver()
+            .is_some_and(|receiver| receiver.reference.is_some() && receiver.mutability.is_none())
+}
+
+/// Whether `method` can run at an interleaving point, i.e., whether we can generate its arguments.
+fn is_interleavable(method: &ImplItemFn) -> bool {
+    method.sig.generics.params.iter().all(|param| matches!(param, GenericParam::Lifetime(_)))
+        && method.sig.inputs.iter().skip(1).all(|input| match input {
+            FnArg::Typed(pat_type) => match &*pat_type.ty {
+                Type::Path(path) => {
+                    path.qself.is_none()
+                        && path.path.get_ident().is_some_and(|ident| {
+                            PRIMITIVES.iter().any(|primitive| ident == primitive)
+                        })
+                }
+                _ => false,
+            },
+            FnArg::Receiver(_) => false,
+        })
+}
+
+/// A private copy of `method` named `name`.
+fn private_copy(method: &ImplItemFn, name: Ident) -> ImplItemFn {
+    let mut copy = method.clone();
+    copy.sig.ident = name;
+    copy.vis = Visibility::Inherited;
+    copy.attrs = vec![parse_quote!(#[doc(hidden)])];
+    copy
+}
+
+/// The name of the type to use in messages.
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .map_or_else(|| quote!(#ty).to_string(), |segment| segment.ident.to_string()),
+        _ => quote!(#ty).to_string(),
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of `#[kani::c_contracts]` and `#[kani::c_contract(...)]`.
+//!
+//! An attribute on a function of an `extern` block can only expand to other foreign items, so the
+//! contracts are expanded by `#[kani::c_contracts]` on the block itself. For every function
+//! `foo` of the block with a `#[kani::c_contract(...)]` attribute, we generate:
+//!
+//! ```ignore
+//! #[allow(dead_code, non_snake_case, unused_variables)]
+//! unsafe extern "C" fn kani_c_contract_foo(x: i32, buf: *mut u8) -> i32 {
+//!     let kani_c_contract = || -> i32 {
+//!         kani::assert(x > 0, stringify!(x > 0));
+//!         unsafe {
+//!             kani::internal::write_any(kani::internal::Pointer::assignable(
+//!                 kani::internal::untracked_deref(&buf),
+//!             ))
+//!         };
+//!         let result_kani_internal: i32 = kani::any_modifies();
+//!         kani::assume((|result: &i32| *result < x)(&result_kani_internal));
+//!         result_kani_internal
+//!     };
+//!     kani_c_contract()
+//! }
+//! ```
+//!
+//! and we mark `foo` with `#[kanitool::c_contract = "kani_c_contract_foo"]`, which tells the
+//! compiler to call the generated function instead. The body is a closure so that Kani replaces
+//! `write_any` and `any_modifies` the same way it does for the contracts of Rust functions.
+
+use proc_macro::TokenStream;
+use proc_macro_error2::{abort, abort_call_site};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, FnArg, ForeignItem, ForeignItemFn, ItemForeignMod, Pat, parse_macro_input};
+
+/// The clauses of a `#[kani::c_contract(...)]` attribute. Each clause may be repeated.
+#[derive(Default)]
+struct CContract {
+    requires: Vec<Expr>,
+    ensures: Vec<Expr>,
+    modifies: Vec<Expr>,
+}
+
+impl Parse for CContract {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut contract = CContract::default();
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            let _ = input.parse::<syn::Token![=]>()?;
+            let clause = input.parse::<Expr>()?;
+            if ident == "requires" {
+                contract.requires.push(clause);
+            } else if ident == "ensures" {
+                contract.ensures.push(clause);
+            } else if ident == "modifies" {
+                contract.modifies.push(clause);
+            } else {
+                abort!(ident, "`{}` is not a valid clause for `#[kani::c_contract]`.", ident;
+                    note = "the clauses of `#[kani::c_contract]` are `requires`, `ensures` and `modifies`.";
+                );
+            }
+            if !input.is_empty() {
+                let _ = input.parse::<syn::Token![,]>()?;
+            }
+        }
+        Ok(contract)
+    }
+}
+
+pub fn c_contracts(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        abort_call_site!("`#[kani::c_contracts]` does not take any arguments");
+    }
+    let mut block = parse_macro_input!(item as ItemForeignMod);
+    let mut replacements = Vec::new();
+    for item in block.items.iter_mut() {
+        let ForeignItem::Fn(foreign_fn) = item else { continue };
+        let mut contracts = Vec::new();
+        foreign_fn.attrs.retain(|attr| {
+            if is_c_contract(attr) {
+                contracts.push(attr.clone());
+                false
+            } else {
+                true
+            }
+        });
+        if contracts.is_empty() {
+            continue;
+        }
+        let contract = contracts.iter().fold(CContract::default(), |mut result, attr| {
+            let clauses = attr.parse_args::<CContract>().unwrap_or_else(|err| {
+                abort!(attr, "invalid `#[kani::c_contract]` attribute: {}", err)
+            });
+            result.requires.extend(clauses.requires);
+            result.ensures.extend(clauses.ensures);
+            result.modifies.extend(clauses.modifies);
+            result
+        });
+        let replacement = format_ident!("kani_c_contract_{}", foreign_fn.sig.ident);
+        let replacement_name = replacement.to_string();
+        foreign_fn.attrs.push(syn::parse_quote!(#[kanitool::c_contract = #replacement_name]));
+        replacements.push(replacement_fn(foreign_fn, &replacement, contract));
+    }
+    quote!(
+        #block
+        #(#replacements)*
+    )
+    .into()
+}
+
+pub fn c_contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let foreign_fn = parse_macro_input!(item as ForeignItemFn);
+    abort!(foreign_fn.sig.ident, "`#[kani::c_contract]` requires the `extern` block to be annotated with `#[kani::c_contracts]`";
+        help = "add `#[kani::c_contracts]` to the `extern` block that declares `{}`", foreign_fn.sig.ident;
+    )
+}
+
+/// Is this a `#[kani::c_contract(...)]` or `#[c_contract(...)]` attribute?
+fn is_c_contract(attr: &syn::Attribute) -> bool {
+    attr.path().segments.last().is_some_and(|segment| segment.ident == "c_contract")
+}
+
+/// Generate the function that the compiler calls instead of `foreign_fn`.
+fn replacement_fn(
+    foreign_fn: &mut ForeignItemFn,
+    replacement: &syn::Ident,
+    contract: CContract,
+) -> TokenStream2 {
+    if let Some(variadic) = &foreign_fn.sig.variadic {
+        abort!(variadic, "`#[kani::c_contract]` does not support variadic functions");
+    }
+    // Name the arguments that the declaration leaves anonymous, e.g., `fn free(_: *mut u8)`.
+    for (idx, input) in foreign_fn.sig.inputs.iter_mut().enumerate() {
+        if let FnArg::Typed(arg) = input
+            && matches!(*arg.pat, Pat::Wild(_))
+        {
+            let name = format_ident!("kani_arg_{idx}");
+            *arg.pat = syn::parse_quote!(#name);
+        }
+    }
+    let inputs: Punctuated<FnArg, syn::Token![,]> = foreign_fn.sig.inputs.clone();
+    let output = &foreign_fn.sig.output;
+    let result_ty = match output {
+        syn::ReturnType::Default => syn::parse_quote!(()),
+        syn::ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+    let requires = contract
+        .requires
+        .iter()
+        .map(|requires| quote!(kani::assert(#requires, stringify!(#requires));));
+    let modifies = contract.modifies.iter().map(|target| {
+        quote!(
+            unsafe {
+                kani::internal::write_any(kani::internal::Pointer::assignable(
+                    kani::internal::untracked_deref(&#target),
+                ))
+            };
+        )
+    });
+    let ensures = contract
+        .ensures
+        .iter()
+        .map(|ensures| quote!(kani::assume((#ensures)(&result_kani_internal));));
+    quote!(
+        #[allow(dead_code, non_snake_case, unused_variables)]
+        unsafe extern "C" fn #replacement(#inputs) #output {
+            let kani_c_contract = || -> #result_ty {
+                #(#requires)*
+                #(#modifies)*
+                let result_kani_internal: #result_ty = kani::any_modifies();
+                #(#ensures)*
+                result_kani_internal
+            };
+            kani_c_contract()
+        }
+    )
+}
@@ -9,7 +9,8 @@
 use syn::{Stmt, parse_quote};
 
 use super::{
-    ContractConditionsData, ContractConditionsHandler, ContractMode, INTERNAL_RESULT_IDENT,
+    ContractConditionsData, ContractConditionsHandler, ContractMode, INTERNAL_PANICS_IDENT,
+    INTERNAL_RESULT_IDENT,
     helpers::*,
     shared::{build_ensures, split_for_remembers},
 };
@@ -21,38 +22,35 @@ impl<'a> ContractConditionsHandler<'a> {
     /// function.
     pub fn assert_closure(&self) -> TokenStream2 {
         let assert_ident = Ident::new(&self.assert_name, Span::call_site());
-        let sig = &self.annotated_fn.sig;
-        let output = &sig.output;
         let body_stmts = self.initial_assert_stmts();
-        let body = self.make_assert_body(body_stmts);
+        let closure = self.contract_closure(quote!(), self.make_assert_body(body_stmts));
 
         quote!(
             #[kanitool::is_contract_generated(assert)]
             #[allow(dead_code, unused_variables, unused_mut)]
-            let mut #assert_ident = kani_force_fn_once(|| #output #body);
+            let mut #assert_ident = kani_force_fn_once(#closure);
         )
     }
 
     /// Expand the assert closure body.
     pub fn expand_assert(&self, closure: &mut Stmt) {
         let body = closure_body(closure);
-        *body = syn::parse2(self.make_assert_body(mem::take(&mut body.block.stmts))).unwrap();
+        *body = syn::parse2(self.make_assert_body(mem::take(&mut body.stmts))).unwrap();
     }
 
     /// Initialize the list of statements for the assert closure body.
     /// Construct a closure that wraps the body of the function, then invoke it and return the result.
     fn initial_assert_stmts(&self) -> Vec<Stmt> {
         let body_wrapper_ident = Ident::new("body_wrapper", Span::call_site());
-        let output = &self.annotated_fn.sig.output;
-        let return_type = return_type_to_type(output);
+        let return_type = self.result_type();
         let stmts = &self.annotated_fn.block.stmts;
         let result = Ident::new(INTERNAL_RESULT_IDENT, Span::call_site());
+        let body_wrapper = self.body_closure(quote!(), quote!({ #(#stmts)* }));
+        let body_wrapper_call = self.call_contract_closure(quote!(#body_wrapper_ident()));
 
         parse_quote!(
-            let mut body_wrapper = kani_force_fn_once(|| #output {
-                #(#stmts)*
-            });
-            let #result : #return_type = #body_wrapper_ident();
+            let mut #body_wrapper_ident = kani_force_fn_once(#body_wrapper);
+            let #result : #return_type = #body_wrapper_call;
             #result
         )
     }
@@ -72,8 +70,11 @@ fn make_assert_body(&self, mut body_stmts: Vec<Stmt>) -> TokenStream2 {
             ContractConditionsData::Ensures { attr } => {
                 let (remembers, ensures_clause) = build_ensures(attr);
 
+                // See `make_check_body` for the cfg.
                 let exec_postconditions = quote!(
-                    kani::assert(#ensures_clause, stringify!(#attr_copy));
+                    if !cfg!(kani_prove_no_panics) {
+                        kani::assert(#ensures_clause, stringify!(#attr_copy));
+                    }
                 );
 
                 let return_expr = body_stmts.pop();
@@ -92,6 +93,27 @@ fn make_assert_body(&self, mut body_stmts: Vec<Stmt>) -> TokenStream2 {
             ContractConditionsData::Modifies { .. } => {
                 quote!({#(#body_stmts)*})
             }
+            ContractConditionsData::PanicsIff { attr } => {
+                let panics = Ident::new(INTERNAL_PANICS_IDENT, Span::call_site());
+                let return_expr = body_stmts.pop();
+
+                let (asserts, rest_of_body) =
+                    split_for_remembers(&body_stmts[..], ContractMode::Assert);
+
+                // If the function panics, the execution ends before this assertion.
+                quote!({
+                    #(#asserts)*
+                    let #panics: bool = #attr;
+                    #(#rest_of_body)*
+                    if !cfg!(kani_prove_no_panics) {
+                        kani::assert(
+                            !#panics,
+                            concat!("function must panic when ", stringify!(#attr_copy)),
+                        );
+                    }
+                    #return_expr
+                })
+            }
         }
     }
 }
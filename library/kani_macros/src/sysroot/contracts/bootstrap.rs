@@ -32,13 +32,36 @@ pub fn handle_untouched(&mut self) {
         let check_ident = Ident::new(&self.check_name, span);
         let recursion_ident = Ident::new(&self.recursion_name, span);
         let assert_ident = Ident::new(&self.assert_name, span);
+        let (cbmc_attrs, cbmc_stmts) = self.cbmc_predicates().unzip();
+        // The return type cannot be named if it contains an `impl Trait`, so the contract closures
+        // refer to it through the type of `kani_return_type`, which is given by the original body.
+        let block = &self.annotated_fn.block;
+        let (return_type_stmts, original_body) = if self.returns_impl_trait() {
+            let result = Ident::new(INTERNAL_RESULT_IDENT, Span::call_site());
+            let stmts = quote!(
+                #[inline(never)]
+                const fn kani_return_type_of<T>(_: &T, _: ::core::marker::PhantomData<T>) {}
+                let kani_return_type = ::core::marker::PhantomData;
+            );
+            let body = quote!({
+                let #result = #block;
+                kani_return_type_of(&#result, kani_return_type);
+                #result
+            });
+            (Some(stmts), body)
+        } else {
+            (None, quote!(#block))
+        };
+        let [recursion_call, replace_call, check_call, assert_call] =
+            [&recursion_ident, &replace_ident, &check_ident, &assert_ident]
+                .map(|ident| self.call_contract_closure(quote!(kani_register_contract(#ident))));
 
         // The order of `attrs` and `kanitool::{checked_with,
         // is_contract_generated}` is important here, because macros are
         // expanded outside in. This way other contract annotations in `attrs`
         // sees those attributes and can use them to determine
         // `function_state`.
-        let ItemFn { attrs, vis, sig, block } = &self.annotated_fn;
+        let ItemFn { attrs, vis, sig, .. } = &self.annotated_fn;
         self.output.extend(quote!(
             #(#attrs)*
             #[kanitool::recursion_check = #recursion_name]
@@ -46,6 +69,7 @@ pub fn handle_untouched(&mut self) {
             #[kanitool::replaced_with = #replace_name]
             #[kanitool::asserted_with = #assert_name]
             #[kanitool::modifies_wrapper = #modifies_name]
+            #cbmc_attrs
             #vis #sig {
                 // Dummy functions used to force the compiler to annotate Kani's
                 // closures as `FnOnce`. Without this, Rust infers the generated closures as `FnMut`,
@@ -76,25 +100,30 @@ const fn kani_register_contract<T, F: FnOnce() -> T>(f: F) -> T {
                 const fn kani_contract_mode() -> kani::internal::Mode {
                     kani::internal::ORIGINAL
                 }
+                #return_type_stmts
                 let kani_contract_mode = kani_contract_mode();
+                #cbmc_stmts
+                // The original body comes first, so the type it returns is known when the
+                // contract closures are type checked.
                 match kani_contract_mode {
+                    kani::internal::ORIGINAL | kani::internal::CBMC_CONTRACT => #original_body
                     kani::internal::RECURSION_CHECK => {
                         #recursion_closure;
-                        kani_register_contract(#recursion_ident)
+                        #recursion_call
                     }
                     kani::internal::REPLACE => {
                         #replace_closure;
-                        kani_register_contract(#replace_ident)
+                        #replace_call
                     }
                     kani::internal::SIMPLE_CHECK => {
                         #check_closure;
-                        kani_register_contract(#check_ident)
+                        #check_call
                     }
                     kani::internal::ASSERT => {
                         #assert_closure;
-                        kani_register_contract(#assert_ident)
+                        #assert_call
                     }
-                    _ => #block
+                    _ => unreachable!(),
                 }
             }
         ));
@@ -119,6 +148,8 @@ pub fn handle_expanded(&mut self) {
         let assert_closure = expect_closure_in_match(&mut block.stmts, "assert");
         self.expand_assert(assert_closure);
 
+        self.expand_cbmc(&mut annotated_fn);
+
         self.output.extend(quote!(#annotated_fn));
     }
 
@@ -128,32 +159,35 @@ fn new_recursion_closure(
         replace_closure: &TokenStream,
         check_closure: &TokenStream,
     ) -> TokenStream {
-        let ItemFn { sig, .. } = self.annotated_fn;
-        let output = &sig.output;
         let span = Span::call_site();
         let result = Ident::new(INTERNAL_RESULT_IDENT, span);
         let replace_ident = Ident::new(&self.replace_name, span);
         let check_ident = Ident::new(&self.check_name, span);
         let recursion_ident = Ident::new(&self.recursion_name, span);
-
-        quote!(
-            #[kanitool::is_contract_generated(recursion_check)]
-            #[allow(dead_code, unused_variables, unused_mut)]
-            let mut #recursion_ident = kani_force_fn_once(|| #output
-            {
+        let replace_call = self.call_contract_closure(quote!(#replace_ident()));
+        let check_call = self.call_contract_closure(quote!(#check_ident()));
+        let closure = self.contract_closure(
+            quote!(),
+            quote!({
                 #[kanitool::recursion_tracker]
                 static mut REENTRY: bool = false;
                 if unsafe { REENTRY } {
                     #replace_closure
-                    #replace_ident()
+                    #replace_call
                 } else {
                     unsafe { REENTRY = true };
                     #check_closure
-                    let #result = #check_ident();
+                    let #result = #check_call;
                     unsafe { REENTRY = false };
                     #result
                 }
-            });
+            }),
+        );
+
+        quote!(
+            #[kanitool::is_contract_generated(recursion_check)]
+            #[allow(dead_code, unused_variables, unused_mut)]
+            let mut #recursion_ident = kani_force_fn_once(#closure);
         )
     }
 
@@ -161,7 +195,7 @@ fn new_recursion_closure(
     fn expand_recursion(&self, closure: &mut Stmt) {
         // TODO: Need to enter if / else. Make this traverse body and return list statements :(
         let body = closure_body(closure);
-        let stmts = &mut body.block.stmts;
+        let stmts = &mut body.stmts;
         let if_reentry = stmts
             .iter_mut()
             .find_map(|stmt| {
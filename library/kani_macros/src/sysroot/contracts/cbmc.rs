@@ -0,0 +1,241 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Generation of the predicates used to hand a contract over to CBMC.
+//!
+//! With `--cbmc-contracts`, Kani does not instrument the body of the function under contract.
+//! Instead, the compiler attaches `requires` and `ensures` clauses to the function in the goto
+//! program, and CBMC checks or replaces the function using its own contract instrumentation.
+//! Each clause calls one of the following closures declared inside the annotated function:
+//!
+//! ```ignore
+//! #[kanitool::cbmc_requires_with = "__kani_cbmc_requires_div"]
+//! #[kanitool::cbmc_ensures_with = "__kani_cbmc_ensures_div"]
+//! fn div(dividend: u32, divisor: u32) -> u32 {
+//!     // ...
+//!     let kani_contract_mode = kani_contract_mode();
+//!     #[kanitool::is_contract_generated(cbmc_contract)]
+//!     #[cfg(kani_cbmc_contracts)]
+//!     let _ = if kani_contract_mode == kani::internal::CBMC_CONTRACT {
+//!         #[allow(unused_variables, unused_mut, unused_unsafe)]
+//!         let __kani_cbmc_requires_div = |dividend: u32, divisor: u32| -> bool {
+//!             true && (divisor != 0)
+//!         };
+//!         #[allow(unused_variables, unused_mut, unused_unsafe)]
+//!         let __kani_cbmc_ensures_div =
+//!             |result_kani_internal: u32, dividend: u32, divisor: u32| -> bool {
+//!                 true && kani::internal::apply_closure(|result: &u32| *result <= dividend, &result_kani_internal)
+//!             };
+//!         core::hint::black_box((
+//!             __kani_cbmc_requires_div as fn(u32, u32) -> bool,
+//!             __kani_cbmc_ensures_div as fn(u32, u32, u32) -> bool,
+//!         ));
+//!     };
+//!     match kani_contract_mode {
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! The cast to function pointers ensures the predicates are code generated when the function is
+//! used in CBMC contract mode. Only contracts that are conditions over the arguments and the
+//! result can be encoded this way, i.e., contracts without `modifies` clauses or `old`
+//! expressions, on functions without a receiver. For other contracts, the predicates are not
+//! generated and the compiler rejects their usage with `--cbmc-contracts`.
+//!
+//! Unlike nested functions, the closures can refer to the generic parameters of the function and
+//! of its enclosing `impl` block, so each instance of the function is paired with the instance of
+//! the predicates with the same generic arguments. The closures take their parameters explicitly,
+//! so they don't capture anything and coerce to function pointers. The statement is only compiled
+//! with `--cfg kani_cbmc_contracts`, which the driver only sets with `--cbmc-contracts`.
+
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::{Attribute, Expr, ExprClosure, FnArg, ItemFn, Local, Pat, Stmt, parse_quote};
+
+use super::{
+    ContractConditionsData, ContractConditionsHandler, INTERNAL_RESULT_IDENT, helpers::*,
+    shared::build_ensures,
+};
+
+const CBMC_ATTRIBUTES: [&str; 2] = ["cbmc_requires_with", "cbmc_ensures_with"];
+
+impl<'a> ContractConditionsHandler<'a> {
+    /// Generate the attributes and the body statements needed to encode the contract as a CBMC
+    /// contract, or `None` if this clause cannot be encoded.
+    pub fn cbmc_predicates(&self) -> Option<(TokenStream2, TokenStream2)> {
+        if !self.supports_cbmc_contract() {
+            return None;
+        }
+        let requires_name = &self.cbmc_requires_name;
+        let ensures_name = &self.cbmc_ensures_name;
+        let span = Span::call_site();
+        let requires_ident = Ident::new(requires_name, span);
+        let ensures_ident = Ident::new(ensures_name, span);
+        let result = Ident::new(INTERNAL_RESULT_IDENT, span);
+
+        let sig = &self.annotated_fn.sig;
+        let inputs = &sig.inputs;
+        let return_type = return_type_to_type(&sig.output);
+        let arg_types = inputs
+            .iter()
+            .map(|arg| match arg {
+                FnArg::Typed(typed) => &typed.ty,
+                FnArg::Receiver(_) => unreachable!("receivers are not supported"),
+            })
+            .collect::<Vec<_>>();
+        let (requires, ensures) = match &self.condition_type {
+            ContractConditionsData::Requires { attr } => (quote!(true && (#attr)), quote!(true)),
+            ContractConditionsData::Ensures { attr } => {
+                let (_, ensures_clause) = build_ensures(attr);
+                (quote!(true), quote!(true && #ensures_clause))
+            }
+            ContractConditionsData::Modifies { .. } | ContractConditionsData::PanicsIff { .. } => {
+                unreachable!()
+            }
+        };
+        // The conditions of an unsafe function may call unsafe functions.
+        let (requires, ensures) = if sig.unsafety.is_some() {
+            (quote!(unsafe { #requires }), quote!(unsafe { #ensures }))
+        } else {
+            (requires, ensures)
+        };
+
+        let attrs = quote!(
+            #[kanitool::cbmc_requires_with = #requires_name]
+            #[kanitool::cbmc_ensures_with = #ensures_name]
+        );
+        let stmts = quote!(
+            #[kanitool::is_contract_generated(cbmc_contract)]
+            #[cfg(kani_cbmc_contracts)]
+            let _ = if kani_contract_mode == kani::internal::CBMC_CONTRACT {
+                #[allow(unused_variables, unused_mut, unused_unsafe)]
+                let #requires_ident = |#inputs| -> bool { #requires };
+                #[allow(unused_variables, unused_mut, unused_unsafe)]
+                let #ensures_ident = |#result: #return_type, #inputs| -> bool { #ensures };
+                core::hint::black_box((
+                    #requires_ident as fn(#(#arg_types),*) -> bool,
+                    #ensures_ident as fn(#return_type, #(#arg_types),*) -> bool,
+                ));
+            };
+        );
+        Some((attrs, stmts))
+    }
+
+    /// Add the condition of this clause to the CBMC predicates. If this clause cannot be
+    /// encoded, remove the predicates and their attributes instead.
+    pub fn expand_cbmc(&self, annotated_fn: &mut ItemFn) {
+        if !self.supports_cbmc_contract() {
+            annotated_fn.block.stmts.retain(|stmt| !is_cbmc_stmt(stmt));
+            annotated_fn.attrs.retain(|attr| {
+                !CBMC_ATTRIBUTES.iter().any(|name| matches_path(attr.path(), &["kanitool", *name]))
+            });
+            return;
+        }
+        let (name, condition) = match &self.condition_type {
+            ContractConditionsData::Requires { attr } => {
+                (&self.cbmc_requires_name, quote!((#attr)))
+            }
+            ContractConditionsData::Ensures { attr } => (&self.cbmc_ensures_name, {
+                let (_, ensures_clause) = build_ensures(attr);
+                quote!(#ensures_clause)
+            }),
+            ContractConditionsData::Modifies { .. } | ContractConditionsData::PanicsIff { .. } => {
+                unreachable!()
+            }
+        };
+        // The predicates may have been removed by a previous clause.
+        let Some(predicate) = find_predicate(&mut annotated_fn.block.stmts, name) else {
+            return;
+        };
+        let condition_expr = match predicate.body.as_mut() {
+            Expr::Block(block) => block.block.stmts.last_mut(),
+            _ => None,
+        };
+        let condition_expr = match condition_expr {
+            Some(Stmt::Expr(Expr::Unsafe(unsafe_block), None)) => {
+                unsafe_block.block.stmts.last_mut()
+            }
+            stmt => stmt,
+        };
+        let Some(Stmt::Expr(expr, None)) = condition_expr else {
+            unreachable!("Expected `{name}` to end with its condition")
+        };
+        *expr = parse_quote!(#expr && #condition);
+    }
+
+    /// Whether this clause can be encoded as part of a CBMC contract for the annotated function.
+    fn supports_cbmc_contract(&self) -> bool {
+        let sig = &self.annotated_fn.sig;
+        let clause_supported = match &self.condition_type {
+            ContractConditionsData::Requires { .. } => true,
+            ContractConditionsData::Ensures { attr } => !calls_old(quote!(#attr)),
+            // CBMC contracts cannot describe when a function panics.
+            ContractConditionsData::Modifies { .. } | ContractConditionsData::PanicsIff { .. } => {
+                false
+            }
+        };
+        let inputs = &sig.inputs;
+        let output = &sig.output;
+        clause_supported
+            && sig.asyncness.is_none()
+            && sig.variadic.is_none()
+            && sig.receiver().is_none()
+            // The parameters of the predicates cannot have an `impl Trait` type.
+            && !has_impl(quote!(#inputs #output))
+    }
+}
+
+/// Whether the tokens contain a call to `old`, i.e. the identifier `old` followed by
+/// parentheses.
+fn calls_old(tokens: TokenStream2) -> bool {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    tokens.iter().enumerate().any(|(idx, token)| match token {
+        TokenTree::Ident(ident) => {
+            ident == "old"
+                && matches!(tokens.get(idx + 1), Some(TokenTree::Group(group))
+                    if group.delimiter() == proc_macro2::Delimiter::Parenthesis)
+        }
+        TokenTree::Group(group) => calls_old(group.stream()),
+        _ => false,
+    })
+}
+
+fn has_impl(tokens: TokenStream2) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == "impl",
+        TokenTree::Group(group) => has_impl(group.stream()),
+        _ => false,
+    })
+}
+
+fn is_contract_generated(attrs: &[Attribute], name: &str) -> bool {
+    let ident = Ident::new(name, Span::call_site());
+    let attr: Attribute = parse_quote!(#[kanitool::is_contract_generated(#ident)]);
+    attrs.contains(&attr)
+}
+
+/// Is this the statement generated by [`ContractConditionsHandler::cbmc_predicates`]?
+fn is_cbmc_stmt(stmt: &Stmt) -> bool {
+    matches!(stmt, Stmt::Local(local) if is_contract_generated(&local.attrs, "cbmc_contract"))
+}
+
+/// Find the predicate closure called `name` in the statement generated by
+/// [`ContractConditionsHandler::cbmc_predicates`].
+fn find_predicate<'a>(stmts: &'a mut [Stmt], name: &str) -> Option<&'a mut ExprClosure> {
+    let Stmt::Local(Local { init: Some(init), .. }) =
+        stmts.iter_mut().find(|stmt| is_cbmc_stmt(stmt))?
+    else {
+        unreachable!()
+    };
+    let Expr::If(if_expr) = init.expr.as_mut() else { unreachable!() };
+    if_expr.then_branch.stmts.iter_mut().find_map(|stmt| match stmt {
+        Stmt::Local(Local { pat: Pat::Ident(pat), init: Some(init), .. }) if pat.ident == name => {
+            match init.expr.as_mut() {
+                Expr::Closure(closure) => Some(closure),
+                _ => None,
+            }
+        }
+        _ => None,
+    })
+}
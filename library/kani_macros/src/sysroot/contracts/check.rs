@@ -6,10 +6,11 @@
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::quote;
 use std::mem;
-use syn::{Block, Expr, FnArg, Local, LocalInit, Pat, PatIdent, ReturnType, Stmt, parse_quote};
+use syn::{Expr, FnArg, Local, LocalInit, Pat, PatIdent, Stmt, parse_quote};
 
 use super::{
-    ContractConditionsData, ContractConditionsHandler, ContractMode, INTERNAL_RESULT_IDENT,
+    ContractConditionsData, ContractConditionsHandler, ContractMode, INTERNAL_PANICS_IDENT,
+    INTERNAL_RESULT_IDENT,
     helpers::*,
     shared::{build_ensures, split_for_remembers},
 };
@@ -33,9 +34,12 @@ pub fn make_check_body(&self, mut body_stmts: Vec<Stmt>) -> TokenStream2 {
                 let (remembers, ensures_clause) = build_ensures(attr);
 
                 // The code that enforces the postconditions and cleans up the shallow
-                // argument copies (with `mem::forget`).
+                // argument copies (with `mem::forget`). `--prove-no-panics` only checks that the
+                // function doesn't panic, so it sets this cfg to skip the postconditions.
                 let exec_postconditions = quote!(
-                    kani::assert(#ensures_clause, stringify!(#attr_copy));
+                    if !cfg!(kani_prove_no_panics) {
+                        kani::assert(#ensures_clause, stringify!(#attr_copy));
+                    }
                 );
 
                 let return_expr = body_stmts.pop();
@@ -76,6 +80,30 @@ pub fn make_check_body(&self, mut body_stmts: Vec<Stmt>) -> TokenStream2 {
                 }
                 quote!({#(#body_stmts)*})
             }
+            ContractConditionsData::PanicsIff { attr } => {
+                let panics = Ident::new(INTERNAL_PANICS_IDENT, Span::call_site());
+                let return_expr = body_stmts.pop();
+
+                let (assumes, rest_of_body) =
+                    split_for_remembers(&body_stmts[..], ContractMode::SimpleCheck);
+
+                // Panics end the execution while they are expected, so the function must panic
+                // if it returns with `panics` still set.
+                quote!({
+                    #(#assumes)*
+                    let #panics: bool = #attr;
+                    kani::internal::expect_panic(#panics);
+                    #(#rest_of_body)*
+                    kani::internal::expect_panic(false);
+                    if !cfg!(kani_prove_no_panics) {
+                        kani::assert(
+                            !#panics,
+                            concat!("function must panic when ", stringify!(#attr_copy)),
+                        );
+                    }
+                    #return_expr
+                })
+            }
         }
     }
 
@@ -83,19 +111,17 @@ pub fn make_check_body(&self, mut body_stmts: Vec<Stmt>) -> TokenStream2 {
     fn initial_check_stmts(&self) -> Vec<syn::Stmt> {
         let modifies_ident = Ident::new(&self.modify_name, Span::call_site());
         let wrapper_arg_ident = Ident::new(WRAPPER_ARG, Span::call_site());
-        let return_type = return_type_to_type(&self.annotated_fn.sig.output);
+        let return_type = self.result_type();
         let mut_recv = self.has_mutable_receiver().then(|| quote!(core::ptr::addr_of!(self),));
         let redefs_mut_only = self.arg_redefinitions(true);
-        let modifies_closure = self.modifies_closure(
-            &self.annotated_fn.sig.output,
-            &self.annotated_fn.block,
-            redefs_mut_only,
-        );
+        let modifies_closure =
+            self.modifies_closure(&self.annotated_fn.block.stmts, redefs_mut_only);
+        let modifies_call = self.call_contract_closure(quote!(#modifies_ident(#wrapper_arg_ident)));
         let result = Ident::new(INTERNAL_RESULT_IDENT, Span::call_site());
         parse_quote!(
             let #wrapper_arg_ident = (#mut_recv);
             #modifies_closure
-            let #result : #return_type = #modifies_ident(#wrapper_arg_ident);
+            let #result : #return_type = #modifies_call;
             #result
         )
     }
@@ -106,15 +132,13 @@ fn initial_check_stmts(&self) -> Vec<syn::Stmt> {
     /// function.
     pub fn check_closure(&self) -> TokenStream2 {
         let check_ident = Ident::new(&self.check_name, Span::call_site());
-        let sig = &self.annotated_fn.sig;
-        let output = &sig.output;
         let body_stmts = self.initial_check_stmts();
-        let body = self.make_check_body(body_stmts);
+        let closure = self.contract_closure(quote!(), self.make_check_body(body_stmts));
 
         quote!(
             #[kanitool::is_contract_generated(check)]
             #[allow(dead_code, unused_variables, unused_mut)]
-            let mut #check_ident = kani_force_fn_once(|| #output #body);
+            let mut #check_ident = kani_force_fn_once(#closure);
         )
     }
 
@@ -124,48 +148,36 @@ pub fn check_closure(&self) -> TokenStream2 {
     pub fn expand_check(&self, closure: &mut Stmt) {
         let body = closure_body(closure);
         self.expand_modifies(
-            find_contract_closure(&mut body.block.stmts, "wrapper")
+            find_contract_closure(&mut body.stmts, "wrapper")
                 .expect("Internal Failure: Expected to find `wrapper` closure, but found none"),
         );
-        *body = syn::parse2(self.make_check_body(mem::take(&mut body.block.stmts))).unwrap();
+        *body = syn::parse2(self.make_check_body(mem::take(&mut body.stmts))).unwrap();
     }
 
     /// Emit a modifies wrapper. It's only argument is the list of addresses that may be modified.
-    pub fn modifies_closure(
-        &self,
-        output: &ReturnType,
-        body: &Block,
-        redefs: TokenStream2,
-    ) -> TokenStream2 {
+    pub fn modifies_closure(&self, stmts: &[Stmt], redefs: TokenStream2) -> TokenStream2 {
         // Filter receiver
         let wrapper_ident = Ident::new(WRAPPER_ARG, Span::call_site());
         let modifies_ident = Ident::new(&self.modify_name, Span::call_site());
-        let stmts = &body.stmts;
+        let closure = self.body_closure(
+            quote!(#wrapper_ident: _),
+            quote!({
+                #redefs
+                #(#stmts)*
+            }),
+        );
         quote!(
             #[kanitool::is_contract_generated(wrapper)]
             #[allow(dead_code, unused_variables, unused_mut)]
-            let mut #modifies_ident = kani_force_fn_once_with_args(|#wrapper_ident: _| #output {
-                #redefs
-                #(#stmts)*
-            });
+            let mut #modifies_ident = kani_force_fn_once_with_args(#closure);
         )
     }
 
     /// Expand the modifies closure if we are handling a modifies attribute. Otherwise, no-op.
     pub fn expand_modifies(&self, closure_stmt: &mut Stmt) {
         if matches!(&self.condition_type, ContractConditionsData::Modifies { .. }) {
-            let Stmt::Local(Local { init: Some(LocalInit { expr, .. }), .. }) = closure_stmt else {
-                unreachable!()
-            };
-            // The closure is wrapped into `kani_force_fn_once_with_args`
-            let Expr::Call(call) = expr.as_ref() else { unreachable!() };
-            if call.args.len() != 1 {
-                unreachable!()
-            }
-            let expr = call.args.first().unwrap();
-            let Expr::Closure(closure) = expr else { unreachable!() };
-            let Expr::Block(body) = closure.body.as_ref() else { unreachable!() };
-            let stream = self.modifies_closure(&closure.output, &body.block, TokenStream2::new());
+            let body = closure_body(closure_stmt);
+            let stream = self.modifies_closure(&body.stmts, TokenStream2::new());
             *closure_stmt = syn::parse2(stream).unwrap();
         }
     }
@@ -0,0 +1,203 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Expansion of `kani::contract_closure!`.
+//!
+//! Contracts are attached to closures by lowering the closure to a function declared in the
+//! enclosing block, which then goes through the regular contract expansion. I.e.:
+//!
+//! ```ignore
+//! kani::contract_closure!(
+//!     harness = check_dec,
+//!     #[kani::requires(x > 0)]
+//!     |x: u32| -> u32 { x - 1 }
+//! )
+//! ```
+//!
+//! expands to:
+//!
+//! ```ignore
+//! {
+//!     #[kani::requires(x > 0)]
+//!     fn __kani_contract_check_dec(x: u32) -> u32 { x - 1 }
+//!
+//!     #[kani::proof_for_contract(__kani_contract_check_dec)]
+//!     fn check_dec() {
+//!         let arg0: u32 = kani::any();
+//!         __kani_contract_check_dec(arg0);
+//!     }
+//!
+//!     __kani_contract_check_dec
+//! }
+//! ```
+//!
+//! The compiler resolves the target of the harness to the function declared in the same body.
+//! Since the closure becomes a function item, it cannot capture its environment, so we reject the
+//! closures whose body or contract use a variable that they don't bind.
+
+use crate::contract_closure::ContractClosure;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Error, Expr, ExprClosure, Ident, Pat, Token, Type, parse_macro_input};
+
+pub fn contract_closure(item: TokenStream) -> TokenStream {
+    let ContractClosure { harness, closure } = parse_macro_input!(item as ContractClosure);
+    expand(harness, closure).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(harness: Option<Ident>, closure: ExprClosure) -> syn::Result<TokenStream2> {
+    if closure.lifetimes.is_some()
+        || closure.constness.is_some()
+        || closure.movability.is_some()
+        || closure.asyncness.is_some()
+    {
+        return Err(Error::new_spanned(
+            &closure,
+            "`kani::contract_closure` does not support `for<...>`, `const`, `static` or `async` \
+            closures",
+        ));
+    }
+    if let Some(capture) = &closure.capture {
+        return Err(Error::new_spanned(
+            capture,
+            "closures with contracts cannot capture variables from their environment, so they \
+            cannot be `move` closures",
+        ));
+    }
+    if let Some(variable) = find_capture(&closure) {
+        return Err(Error::new_spanned(
+            &variable,
+            format!(
+                "closures with contracts cannot capture variables from their environment, but \
+                `{variable}` is not bound by the closure"
+            ),
+        ));
+    }
+    let mut inputs = Vec::with_capacity(closure.inputs.len());
+    for input in &closure.inputs {
+        match input {
+            Pat::Type(pat_type) => inputs.push(pat_type),
+            _ => {
+                return Err(Error::new_spanned(
+                    input,
+                    "closures with contracts must have explicit argument types",
+                ));
+            }
+        }
+    }
+
+    let fn_name = match &harness {
+        Some(harness) => format_ident!("__kani_contract_{harness}"),
+        None => format_ident!("__kani_contract_closure"),
+    };
+    let harness_fn = harness.map(|harness| {
+        let (decls, args): (Vec<_>, Vec<_>) = inputs
+            .iter()
+            .enumerate()
+            .map(|(idx, input)| {
+                let var = format_ident!("arg{idx}");
+                // References are created from an arbitrary value of the pointee type.
+                match &*input.ty {
+                    Type::Reference(reference) if reference.mutability.is_some() => {
+                        let elem = &reference.elem;
+                        (quote!(let mut #var: #elem = kani::any();), quote!(&mut #var))
+                    }
+                    Type::Reference(reference) => {
+                        let elem = &reference.elem;
+                        (quote!(let #var: #elem = kani::any();), quote!(&#var))
+                    }
+                    ty => (quote!(let #var: #ty = kani::any();), quote!(#var)),
+                }
+            })
+            .unzip();
+        quote!(
+            #[kani::proof_for_contract(#fn_name)]
+            fn #harness() {
+                #(#decls)*
+                #fn_name(#(#args),*);
+            }
+        )
+    });
+
+    let attrs = &closure.attrs;
+    let output = &closure.output;
+    let body = &closure.body;
+    Ok(quote!({
+        #(#attrs)*
+        fn #fn_name(#(#inputs),*) #output {
+            #body
+        }
+        #harness_fn
+        #fn_name
+    }))
+}
+
+/// Find a variable that `closure` would capture, i.e., a variable that its body or its contract
+/// use, but that is not bound by its arguments or by a pattern inside of them.
+///
+/// We don't resolve names, so a variable is any path with a single lowercase identifier, or
+/// `self`. Functions are only excluded when they are called, and the arguments of macros aren't
+/// inspected, so the compiler still reports the captures that we miss, although less clearly.
+fn find_capture(closure: &ExprClosure) -> Option<Ident> {
+    // The arguments of the contract attributes, e.g., the conditions of `requires`.
+    let contract: Vec<Expr> = closure
+        .attrs
+        .iter()
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated).ok()
+        })
+        .flatten()
+        .collect();
+    let mut bindings = Bindings::default();
+    closure.inputs.iter().for_each(|input| bindings.visit_pat(input));
+    bindings.visit_expr(&closure.body);
+    contract.iter().for_each(|expr| bindings.visit_expr(expr));
+
+    let mut uses = Uses { bound: &bindings.0, capture: None };
+    uses.visit_expr(&closure.body);
+    contract.iter().for_each(|expr| uses.visit_expr(expr));
+    uses.capture
+}
+
+/// Collect the names of the variables bound by the patterns that are visited.
+#[derive(Default)]
+struct Bindings(HashSet<Ident>);
+
+impl<'ast> Visit<'ast> for Bindings {
+    fn visit_pat_ident(&mut self, pat: &'ast syn::PatIdent) {
+        self.0.insert(pat.ident.clone());
+        visit::visit_pat_ident(self, pat)
+    }
+}
+
+/// Find the first use of a variable that is not in `bound`.
+struct Uses<'a> {
+    bound: &'a HashSet<Ident>,
+    capture: Option<Ident>,
+}
+
+impl<'ast> Visit<'ast> for Uses<'_> {
+    fn visit_expr_path(&mut self, expr: &'ast syn::ExprPath) {
+        if self.capture.is_none()
+            && expr.qself.is_none()
+            && let Some(ident) = expr.path.get_ident()
+            && !self.bound.contains(ident)
+            && (ident == "self" || ident.to_string().starts_with(|c: char| c.is_lowercase()))
+        {
+            self.capture = Some(ident.clone());
+        }
+        visit::visit_expr_path(self, expr)
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        // A function that is called by name is not a variable.
+        if !matches!(&*call.func, Expr::Path(_)) {
+            self.visit_expr(&call.func);
+        }
+        call.args.iter().for_each(|arg| self.visit_expr(arg));
+    }
+}
@@ -7,7 +7,9 @@
 use proc_macro2::{Ident, Span};
 use std::borrow::Cow;
 use syn::spanned::Spanned;
-use syn::{Attribute, Expr, ExprBlock, Local, LocalInit, PatIdent, Stmt, parse_quote};
+use syn::visit::Visit;
+use syn::visit_mut::VisitMut;
+use syn::{Attribute, Block, Expr, Local, LocalInit, PatIdent, Stmt, parse_quote};
 
 /// If an explicit return type was provided it is returned, otherwise `()`.
 pub fn return_type_to_type(return_type: &syn::ReturnType) -> Cow<'_, syn::Type> {
@@ -20,6 +22,39 @@ pub fn return_type_to_type(return_type: &syn::ReturnType) -> Cow<'_, syn::Type>
     }
 }
 
+/// Whether the type contains an `impl Trait`.
+pub fn contains_impl_trait(typ: &syn::Type) -> bool {
+    struct ImplTraitFinder(bool);
+    impl Visit<'_> for ImplTraitFinder {
+        fn visit_type_impl_trait(&mut self, _: &syn::TypeImplTrait) {
+            self.0 = true;
+        }
+    }
+    let mut finder = ImplTraitFinder(false);
+    finder.visit_type(typ);
+    finder.0
+}
+
+/// Replace every `impl Trait` in the type by `_`.
+///
+/// Opaque types cannot be named inside the body of the function that defines them, but the
+/// rest of the type can still be used to annotate bindings and closures in that body.
+pub fn erase_impl_trait(typ: &syn::Type) -> syn::Type {
+    struct ImplTraitEraser;
+    impl VisitMut for ImplTraitEraser {
+        fn visit_type_mut(&mut self, typ: &mut syn::Type) {
+            if let syn::Type::ImplTrait(_) = typ {
+                *typ = parse_quote!(_);
+            } else {
+                syn::visit_mut::visit_type_mut(self, typ)
+            }
+        }
+    }
+    let mut typ = typ.clone();
+    ImplTraitEraser.visit_type_mut(&mut typ);
+    typ
+}
+
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub enum MutBinding {
     Mut,
@@ -119,27 +154,24 @@ pub fn expect_closure_in_match<'a>(stmts: &'a mut [Stmt], name: &'static str) ->
 }
 
 /// Extract the body of a closure declaration.
-pub fn closure_body(closure: &mut Stmt) -> &mut ExprBlock {
+///
+/// The closures of an `async fn` return an `async` block, whose body is returned instead.
+pub fn closure_body(closure: &mut Stmt) -> &mut Block {
     let Stmt::Local(Local { init: Some(LocalInit { expr, .. }), .. }) = closure else {
         unreachable!()
     };
-    match expr.as_mut() {
+    let closure = match expr.as_mut() {
         // The case of closures wrapped in `kani_force_fn_once`
-        Expr::Call(call) if call.args.len() == 1 => {
-            let arg = call.args.first_mut().unwrap();
-            match arg {
-                Expr::Closure(closure) => {
-                    let Expr::Block(body) = closure.body.as_mut() else { unreachable!() };
-                    body
-                }
-                _ => unreachable!(),
-            }
-        }
-
-        Expr::Closure(closure) => {
-            let Expr::Block(body) = closure.body.as_mut() else { unreachable!() };
-            body
-        }
+        Expr::Call(call) if call.args.len() == 1 => match call.args.first_mut().unwrap() {
+            Expr::Closure(closure) => closure,
+            _ => unreachable!(),
+        },
+        Expr::Closure(closure) => closure,
+        _ => unreachable!(),
+    };
+    match closure.body.as_mut() {
+        Expr::Block(body) => &mut body.block,
+        Expr::Async(body) => &mut body.block,
         _ => unreachable!(),
     }
 }
@@ -67,6 +67,9 @@ pub fn new(
             ContractConditionsType::Modifies => {
                 ContractConditionsData::new_modifies(attr, &mut output)
             }
+            ContractConditionsType::EnsuresPanicsIff => {
+                ContractConditionsData::PanicsIff { attr: syn::parse(attr)? }
+            }
         };
 
         let fn_name = &annotated_fn.sig.ident;
@@ -76,6 +79,8 @@ pub fn new(
         let replace_name = generate_name("replace");
         let recursion_name = generate_name("recursion_check");
         let modifies_name = generate_name("modifies");
+        let cbmc_requires_name = generate_name("cbmc_requires");
+        let cbmc_ensures_name = generate_name("cbmc_ensures");
 
         Ok(Self {
             condition_type,
@@ -87,6 +92,8 @@ pub fn new(
             recursion_name,
             assert_name,
             modify_name: modifies_name,
+            cbmc_requires_name,
+            cbmc_ensures_name,
         })
     }
 }
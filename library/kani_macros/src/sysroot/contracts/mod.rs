@@ -178,6 +178,7 @@
 //!     }
 //!     let kani_contract_mode = kani_contract_mode();
 //!     match kani_contract_mode {
+//!         kani::internal::ORIGINAL | kani::internal::CBMC_CONTRACT => dividend / divisor,
 //!         kani::internal::RECURSION_CHECK => {
 //!             #[kanitool::is_contract_generated(recursion_check)]
 //!             #[allow(dead_code, unused_variables, unused_mut)]
@@ -285,7 +286,7 @@
 //!             });
 //!             kani_register_contract(__kani_assert_div)
 //!         }
-//!         _ => dividend / divisor,
+//!         _ => unreachable!(),
 //!     }
 //! }
 //! ```
@@ -297,8 +298,10 @@
 //! `old` monad which lets you access the old state within the present
 //! state. Each occurrence of `old` is lifted, so is is necessary that
 //! each lifted occurrence is closed with respect to the function arguments.
-//! The results of these old computations are placed into
-//! `remember_kani_internal_XXX` variables which are hashed. Consider the following example:
+//! The results of these old computations are cloned with
+//! `kani::internal::snapshot`, so they can be places behind references such
+//! as `self.field`, and placed into `remember_kani_internal_XXX` variables
+//! which are hashed. Consider the following example:
 //!
 //! ```
 //! #[kani::ensures(|result| old(*ptr + 1) == *ptr)]
@@ -348,6 +351,9 @@
 //!     }
 //!     let kani_contract_mode = kani_contract_mode();
 //!     match kani_contract_mode {
+//!         kani::internal::ORIGINAL | kani::internal::CBMC_CONTRACT => {
+//!             *ptr += 1;
+//!         }
 //!         kani::internal::RECURSION_CHECK => {
 //!             #[kanitool::is_contract_generated(recursion_check)]
 //!             #[allow(dead_code, unused_variables, unused_mut)]
@@ -359,8 +365,8 @@
 //!                     #[allow(dead_code, unused_variables, unused_mut)]
 //!                     let mut __kani_replace_modify = kani_force_fn_once(|| {
 //!                         kani::assert(*ptr < 100, stringify!(*ptr < 100));
-//!                         let remember_kani_internal_2e780b148d45b5c8 = *ptr + 1;
-//!                         let remember_kani_internal_2e780b148d45b5c8 = *ptr + 1;
+//!                         let remember_kani_internal_2e780b148d45b5c8 = kani::internal::snapshot(&(*ptr + 1));
+//!                         let remember_kani_internal_2e780b148d45b5c8 = kani::internal::snapshot(&(*ptr + 1));
 //!                         let result_kani_internal: () = kani::any_modifies();
 //!                         unsafe {
 //!                             kani::internal::write_any(kani::internal::Pointer::assignable(
@@ -385,8 +391,8 @@
 //!                     #[allow(dead_code, unused_variables, unused_mut)]
 //!                     let mut __kani_check_modify = kani_force_fn_once(|| {
 //!                         kani::assume(*ptr < 100);
-//!                         let remember_kani_internal_2e780b148d45b5c8 = *ptr + 1;
-//!                         let remember_kani_internal_2e780b148d45b5c8 = *ptr + 1;
+//!                         let remember_kani_internal_2e780b148d45b5c8 = kani::internal::snapshot(&(*ptr + 1));
+//!                         let remember_kani_internal_2e780b148d45b5c8 = kani::internal::snapshot(&(*ptr + 1));
 //!                         let _wrapper_arg = (ptr as *const _,);
 //!                         #[kanitool::is_contract_generated(wrapper)]
 //!                         #[allow(dead_code, unused_variables, unused_mut)]
@@ -423,8 +429,8 @@
 //!             #[allow(dead_code, unused_variables, unused_mut)]
 //!             let mut __kani_replace_modify = kani_force_fn_once(|| {
 //!                 kani::assert(*ptr < 100, stringify!(*ptr < 100));
-//!                 let remember_kani_internal_2e780b148d45b5c8 = *ptr + 1;
-//!                 let remember_kani_internal_2e780b148d45b5c8 = *ptr + 1;
+//!                 let remember_kani_internal_2e780b148d45b5c8 = kani::internal::snapshot(&(*ptr + 1));
+//!                 let remember_kani_internal_2e780b148d45b5c8 = kani::internal::snapshot(&(*ptr + 1));
 //!                 let result_kani_internal: () = kani::any_modifies();
 //!                 unsafe {
 //!                     kani::internal::write_any(kani::internal::Pointer::assignable(
@@ -449,8 +455,8 @@
 //!             #[allow(dead_code, unused_variables, unused_mut)]
 //!             let mut __kani_check_modify = kani_force_fn_once(|| {
 //!                 kani::assume(*ptr < 100);
-//!                 let remember_kani_internal_2e780b148d45b5c8 = *ptr + 1;
-//!                 let remember_kani_internal_2e780b148d45b5c8 = *ptr + 1;
+//!                 let remember_kani_internal_2e780b148d45b5c8 = kani::internal::snapshot(&(*ptr + 1));
+//!                 let remember_kani_internal_2e780b148d45b5c8 = kani::internal::snapshot(&(*ptr + 1));
 //!                 let _wrapper_arg = (ptr as *const _,);
 //!                 #[kanitool::is_contract_generated(wrapper)]
 //!                 #[allow(dead_code, unused_variables, unused_mut)]
@@ -481,8 +487,8 @@
 //!             #[allow(dead_code, unused_variables, unused_mut)]
 //!             let mut __kani_assert_modify = kani_force_fn_once(|| {
 //!                 kani::assert(*ptr < 100, stringify!(*ptr < 100));
-//!                 let remember_kani_internal_2e780b148d45b5c8 = *ptr + 1;
-//!                 let remember_kani_internal_2e780b148d45b5c8 = *ptr + 1;
+//!                 let remember_kani_internal_2e780b148d45b5c8 = kani::internal::snapshot(&(*ptr + 1));
+//!                 let remember_kani_internal_2e780b148d45b5c8 = kani::internal::snapshot(&(*ptr + 1));
 //!                 let mut body_wrapper = kani_force_fn_once(|| {
 //!                     *ptr += 1;
 //!                 });
@@ -505,9 +511,7 @@
 //!             });
 //!             kani_register_contract(__kani_assert_modify)
 //!         }
-//!         _ => {
-//!             *ptr += 1;
-//!         }
+//!         _ => unreachable!(),
 //!     }
 //! }
 //! ```
@@ -520,15 +524,22 @@
 
 mod assert;
 mod bootstrap;
+mod cbmc;
 mod check;
+mod closure;
 #[macro_use]
 mod helpers;
 mod initialize;
 mod replace;
 mod shared;
 
+pub use closure::contract_closure;
+
 const INTERNAL_RESULT_IDENT: &str = "result_kani_internal";
 
+/// The variable that stores whether the function under contract must panic.
+const INTERNAL_PANICS_IDENT: &str = "panics_kani_internal";
+
 pub fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
     contract_main(attr, item, ContractConditionsType::Requires)
 }
@@ -541,6 +552,10 @@ pub fn modifies(attr: TokenStream, item: TokenStream) -> TokenStream {
     contract_main(attr, item, ContractConditionsType::Modifies)
 }
 
+pub fn ensures_panics_iff(attr: TokenStream, item: TokenStream) -> TokenStream {
+    contract_main(attr, item, ContractConditionsType::EnsuresPanicsIff)
+}
+
 /// This is very similar to the kani_attribute macro, but it instead creates
 /// key-value style attributes which I find a little easier to parse.
 macro_rules! passthrough {
@@ -609,6 +624,10 @@ struct ContractConditionsHandler<'a> {
     assert_name: String,
     /// The name of the modifies closure.
     modify_name: String,
+    /// The name of the precondition predicate used for CBMC contracts.
+    cbmc_requires_name: String,
+    /// The name of the postcondition predicate used for CBMC contracts.
+    cbmc_ensures_name: String,
 }
 
 /// Which kind of contract attribute are we dealing with?
@@ -620,6 +639,8 @@ enum ContractConditionsType {
     Requires,
     Ensures,
     Modifies,
+    #[strum(serialize = "ensures_panics_iff")]
+    EnsuresPanicsIff,
 }
 
 /// Clause-specific information mostly generated by parsing the attribute.
@@ -637,6 +658,10 @@ enum ContractConditionsData {
     Modifies {
         attr: Vec<Expr>,
     },
+    PanicsIff {
+        /// The condition under which the function panics.
+        attr: Expr,
+    },
 }
 
 /// Enumeration that stores (some of) the contract mode values.
@@ -682,6 +707,19 @@ fn contract_main(
     }
     let attr_copy = TokenStream2::from(attr.clone());
     let mut item_fn = parse_macro_input!(item as ItemFn);
+    // The attributes that are expanded after this one are still attached to the function.
+    if contract_typ == ContractConditionsType::EnsuresPanicsIff
+        && let Some(other) = item_fn.attrs.iter().find(|attr| {
+            attr.path().segments.last().is_some_and(|seg| seg.ident == "ensures_panics_iff")
+        })
+    {
+        return Error::new_spanned(
+            other,
+            "a function can have at most one #[kani::ensures_panics_iff] clause",
+        )
+        .into_compile_error()
+        .into();
+    }
     let function_state = ContractFunctionState::from_attributes(&item_fn.attrs);
     let handler = match ContractConditionsHandler::new(contract_typ, attr, &mut item_fn, attr_copy)
     {
@@ -17,17 +17,23 @@
 impl<'a> ContractConditionsHandler<'a> {
     /// Create initial set of replace statements which is the return havoc.
     fn initial_replace_stmts(&self) -> Vec<syn::Stmt> {
-        let return_type = return_type_to_type(&self.annotated_fn.sig.output);
+        let return_type = self.result_type();
         let result = Ident::new(INTERNAL_RESULT_IDENT, Span::call_site());
         // Add dummy assignments of the input variables to local variables
         // to avoid may drop checks in const generic functions.
         // https://github.com/model-checking/kani/issues/3667
         let redefs = self.arg_redefinitions(false);
         let redefs_block: Block = syn::parse_quote!({#redefs});
+        // The hidden type of an `impl Trait` is only known from the original body, which the
+        // replace closure doesn't have.
+        let return_type_stmt: Option<Stmt> = self
+            .returns_impl_trait()
+            .then(|| syn::parse_quote!(kani_return_type_of(&#result, kani_return_type);));
         [
             vec![syn::parse_quote!(
                 let #result : #return_type = kani::any_modifies();
             )],
+            return_type_stmt.into_iter().collect(),
             redefs_block.stmts,
         ]
         .concat()
@@ -116,6 +122,19 @@ fn expand_replace_body(&self, before: &[Stmt], after: &[Stmt]) -> TokenStream {
                     #result
                 })
             }
+            ContractConditionsData::PanicsIff { attr } => {
+                let Self { attr_copy, .. } = self;
+                let result = Ident::new(INTERNAL_RESULT_IDENT, Span::call_site());
+                // Model the panic before the arguments are havocked by a `modifies` clause.
+                quote!({
+                    #(#before)*
+                    if #attr {
+                        kani::panic(concat!("function panics when ", stringify!(#attr_copy)));
+                    }
+                    #(#after)*
+                    #result
+                })
+            }
         }
     }
 
@@ -125,22 +144,20 @@ fn expand_replace_body(&self, before: &[Stmt], after: &[Stmt]) -> TokenStream {
     /// function.
     pub fn replace_closure(&self) -> TokenStream {
         let replace_ident = Ident::new(&self.replace_name, Span::call_site());
-        let sig = &self.annotated_fn.sig;
-        let output = &sig.output;
         let before = self.initial_replace_stmts();
-        let body = self.expand_replace_body(&before, &[]);
+        let closure = self.contract_closure(quote!(), self.expand_replace_body(&before, &[]));
 
         quote!(
             #[kanitool::is_contract_generated(replace)]
             #[allow(dead_code, unused_variables, unused_mut)]
-            let mut #replace_ident = kani_force_fn_once(|| #output #body);
+            let mut #replace_ident = kani_force_fn_once(#closure);
         )
     }
 
     /// Expand the `replace` body with the new attribute.
     pub fn expand_replace(&self, closure: &mut Stmt) {
         let body = closure_body(closure);
-        let (before, after) = self.split_replace(mem::take(&mut body.block.stmts));
+        let (before, after) = self.split_replace(mem::take(&mut body.stmts));
         let stream = self.expand_replace_body(&before, &after);
         *body = syn::parse2(stream).unwrap();
     }
@@ -10,13 +10,63 @@
 use std::collections::HashMap;
 
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use std::hash::{DefaultHasher, Hash, Hasher};
 use syn::{
     Expr, ExprCall, ExprClosure, ExprPath, Path, Stmt, spanned::Spanned, visit_mut::VisitMut,
 };
 
-use super::{ContractMode, INTERNAL_RESULT_IDENT};
+use super::{ContractConditionsHandler, ContractMode, INTERNAL_RESULT_IDENT, helpers::*};
+
+impl ContractConditionsHandler<'_> {
+    /// The type of the value returned by the annotated function, for annotating the `result`
+    /// binding. Any `impl Trait` in it is left for the compiler to infer.
+    pub fn result_type(&self) -> syn::Type {
+        erase_impl_trait(&return_type_to_type(&self.annotated_fn.sig.output))
+    }
+
+    /// Whether the annotated function returns an `impl Trait`, whose hidden type is only known
+    /// from the original body of the function.
+    pub fn returns_impl_trait(&self) -> bool {
+        contains_impl_trait(&return_type_to_type(&self.annotated_fn.sig.output))
+    }
+
+    /// Create a closure that takes `args` and runs `body` the way the annotated function runs it.
+    ///
+    /// For an `async fn`, the closure returns a future, which is awaited by
+    /// [`Self::call_contract_closure`]. Its type cannot be named, so the closure is not annotated.
+    pub fn contract_closure(&self, args: TokenStream2, body: TokenStream2) -> TokenStream2 {
+        self.make_closure(args, quote!(async move), body)
+    }
+
+    /// Like [`Self::contract_closure`], for the closures that wrap the original body of the
+    /// function. They are awaited right away, so their future borrows the arguments instead of
+    /// taking them, which keeps them available to the postconditions.
+    pub fn body_closure(&self, args: TokenStream2, body: TokenStream2) -> TokenStream2 {
+        self.make_closure(args, quote!(async), body)
+    }
+
+    fn make_closure(
+        &self,
+        args: TokenStream2,
+        async_block: TokenStream2,
+        body: TokenStream2,
+    ) -> TokenStream2 {
+        if self.annotated_fn.sig.asyncness.is_some() {
+            quote!(|#args| #async_block #body)
+        } else if let syn::ReturnType::Default = self.annotated_fn.sig.output {
+            quote!(|#args| #body)
+        } else {
+            let result_type = self.result_type();
+            quote!(|#args| -> #result_type #body)
+        }
+    }
+
+    /// Call a closure created by [`Self::contract_closure`] and return the value of its body.
+    pub fn call_contract_closure(&self, call: TokenStream2) -> TokenStream2 {
+        if self.annotated_fn.sig.asyncness.is_some() { quote!(#call.await) } else { call }
+    }
+}
 
 /// Splits `stmts` into (preconditions, rest).
 /// For example, ContractMode::SimpleCheck assumes preconditions, so given this sequence of statements:
@@ -113,8 +163,8 @@ pub fn try_as_result_assign(stmt: &syn::Stmt) -> Option<&syn::LocalInit> {
 
 /// When a `#[kani::ensures(|result|expr)]` is expanded, this function is called on with `build_ensures(|result|expr)`.
 /// This function goes through the expr and extracts out all the `old` expressions and creates a sequence
-/// of statements that instantiate these expressions as `let remember_kani_internal_x = old_expr;`
-/// where x is a unique hash. This is returned as the first return parameter. The second
+/// of statements that instantiate these expressions as
+/// `let remember_kani_internal_x = kani::internal::snapshot(&(old_expr));` where x is a unique hash. This is returned as the first return parameter. The second
 /// return parameter is the expression formed by passing in the result variable into the input closure.
 pub fn build_ensures(data: &ExprClosure) -> (TokenStream2, Expr) {
     let mut remembers_exprs = HashMap::new();
@@ -219,8 +269,11 @@ fn trigger(
         e.hash(&mut hasher);
         let ident =
             Ident::new(&format!("remember_kani_internal_{:x}", hasher.finish()), Span::call_site());
-        // save the original expression to be lifted into the past remember environment
-        remembers_exprs.insert(ident.clone(), (*e).clone());
+        // save a snapshot of the original expression to be lifted into the past remember
+        // environment. Borrowing the expression lets `old` refer to places that can't be moved,
+        // such as fields behind a reference.
+        let snapshot = Expr::Verbatim(quote_spanned!(e.span()=> kani::internal::snapshot(&(#e))));
+        remembers_exprs.insert(ident.clone(), snapshot);
         // change the expression to refer to the new remember variable
         let _ = std::mem::replace(e, Expr::Verbatim(quote!((#ident))));
         true
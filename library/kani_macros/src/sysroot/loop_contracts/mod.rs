@@ -425,7 +425,7 @@ pub fn loop_modifies(attr: TokenStream, item: TokenStream) -> TokenStream {
     let loop_assign_name: String = "kani_loop_modifies".to_owned();
     let loop_assign_ident = format_ident!("{}", loop_assign_name);
     let loop_assign_stmt: Stmt = parse_quote! {
-        let #loop_assign_ident = (#(#assigns),*);
+        let #loop_assign_ident = (#(#assigns,)*);
     };
     let loop_stmt: Stmt = syn::parse(item.clone()).unwrap();
     let ret: TokenStream = quote!(
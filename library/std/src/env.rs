@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module replaces the command-line arguments of the program with the models in
+//! `kani::env`, so that a harness on `main` is verified for every invocation within the bounds.
+
+// Export everything else from std::env.
+pub use std::env::*;
+
+pub use kani::env::{Args, ArgsOs};
+
+#[inline(always)]
+pub fn args() -> Args {
+    kani::env::args()
+}
+
+#[inline(always)]
+pub fn args_os() -> ArgsOs {
+    kani::env::args_os()
+}
@@ -16,6 +16,14 @@
 // Override process calls with stubs.
 pub mod process;
 
+#[cfg(not(feature = "concrete_playback"))]
+// Override the command-line arguments with nondeterministic ones.
+pub mod env;
+
+#[cfg(not(feature = "concrete_playback"))]
+// Override the threads with the bounded scheduler of `--concurrency`.
+pub mod thread;
+
 /// This assert macro calls kani's assert function passing it down the condition
 /// as well as a message that will be used when reporting the assertion result.
 ///
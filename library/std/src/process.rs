@@ -2,6 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 //! This module introduces stubs for process methods.
+//!
+//! Kani replaces the calls to `abort` and `exit` with terminal states of the harness, so the
+//! bodies of these stubs are never verified. They keep the implementation of the standard library
+//! out of the model.
 
 // Export everything else from std::process.
 pub use std::process::*;
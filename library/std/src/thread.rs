@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module replaces the ways to spawn a thread of `std::thread` with the models in
+//! `kani::thread`, which run the threads with a bounded scheduler when `--concurrency` is enabled,
+//! and forward to the standard library otherwise. `kani::thread::JoinHandle` provides the API of
+//! `std::thread::JoinHandle`, including its platform extensions, so the code that doesn't enable
+//! `--concurrency` compiles unchanged.
+
+// Export everything else from std::thread.
+pub use std::thread::*;
+
+pub use kani::thread::{Builder, JoinHandle};
+
+#[inline(always)]
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    kani::thread::spawn(f)
+}
+
+#[inline(always)]
+pub fn yield_now() {
+    kani::thread::yield_now()
+}
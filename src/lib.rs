@@ -31,6 +31,9 @@ pub fn proxy(bin: &str) -> Result<()> {
         ArgsResult::ExplicitSetup { use_local_bundle, use_local_toolchain } => {
             setup::setup(use_local_bundle, use_local_toolchain)
         }
+        ArgsResult::PinnedSetup { cbmc_version, kissat_version } => {
+            setup::setup_pinned_tools(cbmc_version, kissat_version)
+        }
         ArgsResult::Default => {
             fail_if_in_dev_environment()?;
             if !setup::appears_setup() {
@@ -53,7 +56,16 @@ pub fn proxy(bin: &str) -> Result<()> {
 /// Minimalist argument parsing result type
 #[derive(PartialEq, Eq, Debug)]
 enum ArgsResult {
-    ExplicitSetup { use_local_bundle: Option<OsString>, use_local_toolchain: Option<OsString> },
+    ExplicitSetup {
+        use_local_bundle: Option<OsString>,
+        use_local_toolchain: Option<OsString>,
+    },
+    /// Install the given versions of CBMC and Kissat, and pin them in the `Kani.toml` of the
+    /// current directory.
+    PinnedSetup {
+        cbmc_version: OsString,
+        kissat_version: Option<OsString>,
+    },
     Default,
 }
 
@@ -103,6 +115,30 @@ fn parse_args(args: Vec<OsString>) -> ArgsResult {
                 use_local_toolchain: Some(args[4].clone()),
             }
         }
+        &[_, Some("setup"), Some("--cbmc-version"), _, Some("--kissat-version"), _] => {
+            ArgsResult::PinnedSetup {
+                cbmc_version: args[3].clone(),
+                kissat_version: Some(args[5].clone()),
+            }
+        }
+        &[
+            _,
+            Some("kani"),
+            Some("setup"),
+            Some("--cbmc-version"),
+            _,
+            Some("--kissat-version"),
+            _,
+        ] => ArgsResult::PinnedSetup {
+            cbmc_version: args[4].clone(),
+            kissat_version: Some(args[6].clone()),
+        },
+        &[_, Some("setup"), Some("--cbmc-version"), _] => {
+            ArgsResult::PinnedSetup { cbmc_version: args[3].clone(), kissat_version: None }
+        }
+        &[_, Some("kani"), Some("setup"), Some("--cbmc-version"), _] => {
+            ArgsResult::PinnedSetup { cbmc_version: args[4].clone(), kissat_version: None }
+        }
         &[_, Some("setup")] | &[_, Some("kani"), Some("setup")] => {
             ArgsResult::ExplicitSetup { use_local_bundle: None, use_local_toolchain: None }
         }
@@ -331,5 +367,43 @@ fn trial(args: &[&str]) -> ArgsResult {
                 ])
             );
         }
+        {
+            let e = ArgsResult::PinnedSetup {
+                cbmc_version: OsString::from("6.7.1"),
+                kissat_version: None,
+            };
+            assert_eq!(e, trial(&["cargo-kani", "kani", "setup", "--cbmc-version", "6.7.1"]));
+            assert_eq!(e, trial(&["cargo", "kani", "setup", "--cbmc-version", "6.7.1"]));
+            assert_eq!(e, trial(&["cargo-kani", "setup", "--cbmc-version", "6.7.1"]));
+        }
+        {
+            let e = ArgsResult::PinnedSetup {
+                cbmc_version: OsString::from("6.7.1"),
+                kissat_version: Some(OsString::from("4.0.1")),
+            };
+            assert_eq!(
+                e,
+                trial(&[
+                    "cargo-kani",
+                    "kani",
+                    "setup",
+                    "--cbmc-version",
+                    "6.7.1",
+                    "--kissat-version",
+                    "4.0.1"
+                ])
+            );
+            assert_eq!(
+                e,
+                trial(&[
+                    "cargo-kani",
+                    "setup",
+                    "--cbmc-version",
+                    "6.7.1",
+                    "--kissat-version",
+                    "4.0.1"
+                ])
+            );
+        }
     }
 }
@@ -17,6 +17,9 @@
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 /// Set by our `build.rs`, reflects the Rust target triple we're building for
 const TARGET: &str = env!("TARGET");
+/// The file where `kani setup --cbmc-version` pins the versions of the tools of a project, which
+/// `kani-driver` reads to prefer the pinned binaries.
+const KANI_TOML: &str = "Kani.toml";
 
 /// The directory where Kani is installed, either:
 ///  * (custom) `${KANI_HOME}/kani-<VERSION>` if the environment variable
@@ -96,6 +99,40 @@ pub fn setup(
     Ok(())
 }
 
+/// Installs the given versions of CBMC and Kissat next to the Kani installation, e.g., in
+/// `~/.kani/cbmc-VERSION`, and pins them in the `Kani.toml` of the current directory.
+pub fn setup_pinned_tools(cbmc_version: OsString, kissat_version: Option<OsString>) -> Result<()> {
+    let cbmc_version = parse_tool_version("CBMC", cbmc_version)?;
+    let kissat_version =
+        kissat_version.map(|version| parse_tool_version("Kissat", version)).transpose()?;
+    let kani_dir = kani_dir()?;
+    // e.g. `~/.kani/`
+    let base_dir = kani_dir.parent().expect("No base directory?");
+    std::fs::create_dir_all(base_dir)?;
+
+    println!("[1/3] Installing CBMC version: {cbmc_version}");
+    setup_cbmc(base_dir, &cbmc_version)?;
+
+    if let Some(kissat_version) = &kissat_version {
+        println!("[2/3] Installing Kissat version: {kissat_version}");
+        setup_kissat(base_dir, kissat_version)?;
+    } else {
+        println!("[2/3] No Kissat version given, skipping Kissat");
+    }
+
+    let kani_toml = Path::new(KANI_TOML);
+    let data = if kani_toml.exists() {
+        std::fs::read_to_string(kani_toml).context("Reading Kani.toml")?
+    } else {
+        String::new()
+    };
+    std::fs::write(kani_toml, pin_tools(&data, &cbmc_version, kissat_version.as_deref()))
+        .context("Writing Kani.toml")?;
+    println!("[3/3] Pinned the tools in: {}", kani_toml.canonicalize()?.display());
+
+    Ok(())
+}
+
 /// Download and unpack the Kani release bundle
 fn setup_kani_bundle(kani_dir: &Path, use_local_bundle: Option<OsString>) -> Result<()> {
     // e.g. `~/.kani/`
@@ -184,6 +221,88 @@ fn setup_rust_toolchain(kani_dir: &Path, use_local_toolchain: Option<OsString>)
     Ok(toolchain_version)
 }
 
+/// Install CBMC in `base_dir/cbmc-<version>`, unless it's already there. We use the Debian
+/// package of the release on x86_64 Ubuntu, and build CBMC from source everywhere else, like our
+/// `scripts/setup/*/install_cbmc.sh` scripts.
+fn setup_cbmc(base_dir: &Path, version: &str) -> Result<()> {
+    let install_dir = base_dir.join(format!("cbmc-{version}"));
+    if install_dir.join("bin").join("cbmc").is_file() {
+        println!("CBMC {version} is already installed in: {}", install_dir.display());
+        return Ok(());
+    }
+    let work_dir = fresh_work_dir(base_dir, &format!("cbmc-{version}-setup"))?;
+
+    if let Some(ubuntu_version) = ubuntu_version_with_cbmc_package() {
+        let file = format!("ubuntu-{ubuntu_version}-cbmc-{version}-Linux.deb");
+        let package = work_dir.join(&file);
+        Command::new("curl")
+            .args(["-sSLf", "-o"])
+            .arg(&package)
+            .arg(format!(
+                "https://github.com/diffblue/cbmc/releases/download/cbmc-{version}/{file}"
+            ))
+            .run()
+            .with_context(|| format!("Failed to download CBMC {version}"))?;
+        let contents = work_dir.join("contents");
+        Command::new("dpkg-deb").arg("-x").arg(&package).arg(&contents).run()?;
+        // The package installs its binaries in `/usr/bin`.
+        std::fs::rename(contents.join("usr"), &install_dir)?;
+    } else {
+        let source = work_dir.join("cbmc");
+        Command::new("git")
+            .args(["clone", "--depth", "1", "--branch", &format!("cbmc-{version}")])
+            .arg("https://github.com/diffblue/cbmc")
+            .arg(&source)
+            .run()
+            .with_context(|| format!("Failed to download CBMC {version}"))?;
+        Command::new("cmake")
+            .args(["-S", ".", "-Bbuild", "-DWITH_JBMC=OFF", "-Dsat_impl=minisat2;cadical"])
+            .current_dir(&source)
+            .run()?;
+        Command::new("cmake")
+            .args(["--build", "build", "--parallel"])
+            .current_dir(&source)
+            .run()?;
+        Command::new("cmake")
+            .args(["--install", "build", "--prefix"])
+            .arg(&install_dir)
+            .current_dir(&source)
+            .run()?;
+    }
+
+    std::fs::remove_dir_all(work_dir)?;
+    Ok(())
+}
+
+/// Install Kissat in `base_dir/kissat-<version>`, unless it's already there. Kissat doesn't
+/// release binaries, so we build it from source.
+fn setup_kissat(base_dir: &Path, version: &str) -> Result<()> {
+    let install_dir = base_dir.join(format!("kissat-{version}"));
+    if install_dir.join("bin").join("kissat").is_file() {
+        println!("Kissat {version} is already installed in: {}", install_dir.display());
+        return Ok(());
+    }
+    let work_dir = fresh_work_dir(base_dir, &format!("kissat-{version}-setup"))?;
+
+    let archive = work_dir.join(format!("rel-{version}.tar.gz"));
+    Command::new("curl")
+        .args(["-sSLf", "-o"])
+        .arg(&archive)
+        .arg(format!("https://github.com/arminbiere/kissat/archive/refs/tags/rel-{version}.tar.gz"))
+        .run()
+        .with_context(|| format!("Failed to download Kissat {version}"))?;
+    Command::new("tar").arg("zxf").arg(&archive).current_dir(&work_dir).run()?;
+    let source = work_dir.join(format!("kissat-rel-{version}"));
+    Command::new("./configure").arg("--safe").current_dir(&source).run()?;
+    Command::new("make").arg("kissat").current_dir(&source).run()?;
+
+    std::fs::create_dir_all(install_dir.join("bin"))?;
+    std::fs::copy(source.join("build").join("kissat"), install_dir.join("bin").join("kissat"))?;
+
+    std::fs::remove_dir_all(work_dir)?;
+    Ok(())
+}
+
 // This ends the setup steps above.
 //
 // Just putting a bit of space between that and the helper functions below.
@@ -234,6 +353,73 @@ fn fail_if_unsupported_target() -> Result<()> {
     }
 }
 
+/// Checks that `version` looks like a release version, e.g. `6.7.1`, since it ends up in paths
+/// and download URLs.
+fn parse_tool_version(tool: &str, version: OsString) -> Result<String> {
+    match version.into_string() {
+        Ok(version)
+            if !version.is_empty() && version.chars().all(|c| c.is_ascii_digit() || c == '.') =>
+        {
+            Ok(version)
+        }
+        Ok(version) => bail!("Invalid {tool} version `{version}`, expected e.g. `1.2.3`"),
+        Err(version) => {
+            bail!("Invalid {tool} version `{}`, expected e.g. `1.2.3`", version.to_string_lossy())
+        }
+    }
+}
+
+/// The Ubuntu release, e.g. `22.04`, if we're on an x86_64 Ubuntu release for which CBMC
+/// publishes Debian packages.
+fn ubuntu_version_with_cbmc_package() -> Option<String> {
+    if TARGET != "x86_64-unknown-linux-gnu" || os_info::get().os_type() != os_info::Type::Ubuntu {
+        return None;
+    }
+    let output = Command::new("lsb_release").arg("-rs").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    ["20.04", "22.04", "24.04"].contains(&version.as_str()).then_some(version)
+}
+
+/// Creates an empty `base_dir/name` directory, removing what an interrupted setup left there.
+fn fresh_work_dir(base_dir: &Path, name: &str) -> Result<PathBuf> {
+    let work_dir = base_dir.join(name);
+    if work_dir.exists() {
+        std::fs::remove_dir_all(&work_dir)?;
+    }
+    std::fs::create_dir_all(&work_dir)?;
+    Ok(work_dir)
+}
+
+/// Returns the contents of a `Kani.toml` where the `[tools]` table of `data` is replaced by one
+/// that pins the given versions. We don't depend on a TOML parser, but we own the `[tools]`
+/// table, so we keep every other line as is.
+fn pin_tools(data: &str, cbmc_version: &str, kissat_version: Option<&str>) -> String {
+    let mut in_tools = false;
+    let mut lines = vec![];
+    for line in data.lines() {
+        let header = line.trim();
+        if header.starts_with('[') {
+            in_tools = header == "[tools]";
+        }
+        if !in_tools {
+            lines.push(line);
+        }
+    }
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let mut pinned = lines.join("\n");
+    if !pinned.is_empty() {
+        pinned.push_str("\n\n");
+    }
+    pinned.push_str(&format!("[tools]\ncbmc = \"{cbmc_version}\"\n"));
+    if let Some(kissat_version) = kissat_version {
+        pinned.push_str(&format!("kissat = \"{kissat_version}\"\n"));
+    }
+    pinned
+}
+
 /// Creates a `kani_dir/toolchain` symlink pointing to `toolchain`.
 fn symlink_rust_toolchain(toolchain: &Path, kani_dir: &Path) -> Result<()> {
     let path = kani_dir.join("toolchain");
@@ -244,3 +430,30 @@ fn symlink_rust_toolchain(toolchain: &Path, kani_dir: &Path) -> Result<()> {
     std::os::unix::fs::symlink(toolchain, path)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_pin_tools() {
+        assert_eq!(pin_tools("", "6.7.1", None), "[tools]\ncbmc = \"6.7.1\"\n");
+        assert_eq!(
+            pin_tools("", "6.7.1", Some("4.0.1")),
+            "[tools]\ncbmc = \"6.7.1\"\nkissat = \"4.0.1\"\n"
+        );
+        // Replace an existing pin, and keep the other tables.
+        let data = "[tools]\ncbmc = \"6.0.0\"\nkissat = \"4.0.1\"\n\n[other]\nkey = 1\n";
+        assert_eq!(
+            pin_tools(data, "6.7.1", None),
+            "[other]\nkey = 1\n\n[tools]\ncbmc = \"6.7.1\"\n"
+        );
+    }
+
+    #[test]
+    fn check_parse_tool_version() {
+        assert_eq!(parse_tool_version("CBMC", "6.7.1".into()).unwrap(), "6.7.1");
+        assert!(parse_tool_version("CBMC", "".into()).is_err());
+        assert!(parse_tool_version("CBMC", "../6.7.1".into()).is_err());
+    }
+}
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Companion of `my-crate` with stubs that target its private items.
+
+#[cfg(kani)]
+mod verify {
+    fn zero_seed() -> u8 {
+        0
+    }
+
+    #[kani::proof]
+    #[kani::stub(my_crate::internal::seed, zero_seed)]
+    fn check_checksum() {
+        let data: [u8; 2] = kani::any();
+        assert_eq!(my_crate::checksum(&data), data[0].wrapping_add(data[1]));
+    }
+}
@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Production crate whose verification lives in the `my-crate-proofs` companion.
+
+use internal::seed;
+
+pub fn checksum(data: &[u8]) -> u8 {
+    data.iter().fold(seed(), |sum, byte| sum.wrapping_add(*byte))
+}
+
+mod internal {
+    /// A private function that the companion stubs.
+    pub(crate) fn seed() -> u8 {
+        std::process::id() as u8
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the `main` function of a binary can be verified with nondeterministic command-line
+//! arguments.
+
+use std::process::exit;
+
+fn parse_count(arg: &str) -> Option<u8> {
+    arg.parse().ok()
+}
+
+#[kani::proof]
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    assert!(!args.is_empty());
+    assert!(args.len() <= kani::env::MAX_ARGS + 1);
+    assert!(args.iter().all(|arg| arg.len() <= kani::env::MAX_ARG_LEN));
+    assert!(std::env::args().eq(args.iter().cloned()));
+    assert!(std::env::args_os().eq(args.iter().map(std::ffi::OsString::from)));
+    kani::cover!(args.len() == 3 && args[1] == "-n");
+
+    let count = match args.get(1) {
+        Some(arg) => parse_count(arg).unwrap_or_else(|| exit(2)),
+        None => 1,
+    };
+    // Fails if the user passes `0`.
+    assert!(count > 0, "count must be positive");
+}
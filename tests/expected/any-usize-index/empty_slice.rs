@@ -0,0 +1,9 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `kani::any_usize_index` fails for empty slices, since there is no valid index.
+
+#[kani::proof]
+fn check_empty_slice() {
+    let empty: [u8; 0] = [];
+    let _idx = kani::any_usize_index(&empty);
+}
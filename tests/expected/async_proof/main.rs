@@ -11,4 +11,4 @@ fn main() {}
 async fn test_async_proof_with_options() {}
 
 #[kani::proof]
-async fn test_async_proof_on_function_with_arguments(_: ()) {}
+async fn test_async_proof_on_method(&self) {}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that a bounded `Cow` can be either borrowed or owned, and that its value is bounded.
+
+use std::borrow::Cow;
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_cow() {
+    let cow: Cow<'static, [bool]> = kani::bounded_any::<_, 3>();
+    assert!(cow.len() <= 3);
+    kani::cover!(matches!(cow, Cow::Borrowed(_)));
+    kani::cover!(matches!(cow, Cow::Owned(_)));
+    kani::cover!(matches!(cow, Cow::Borrowed(slice) if slice.len() == 3));
+    kani::cover!(cow.is_empty());
+
+    let mut owned = cow.clone();
+    owned.to_mut().push(true);
+    assert!(matches!(owned, Cow::Owned(_)));
+    assert_eq!(owned.len(), cow.len() + 1);
+}
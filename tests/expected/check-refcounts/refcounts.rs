@@ -0,0 +1,61 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --check-refcounts
+//! Check that `--check-refcounts` detects the `Rc` and `Arc` allocations that are leaked by a
+//! reference cycle, and the strong counts that are decremented below zero.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+struct Node {
+    next: RefCell<Option<Rc<Node>>>,
+}
+
+#[kani::proof]
+fn check_rc_balanced() {
+    let first = Rc::new(kani::any::<u8>());
+    let second = first.clone();
+    let weak = Rc::downgrade(&second);
+    drop(second);
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(Rc::strong_count(&upgraded), 2);
+    drop(upgraded);
+    assert!(Rc::try_unwrap(first).is_ok());
+    assert!(weak.upgrade().is_none());
+}
+
+#[kani::proof]
+fn check_rc_cycle() {
+    let a = Rc::new(Node { next: RefCell::new(None) });
+    let b = Rc::new(Node { next: RefCell::new(Some(a.clone())) });
+    *a.next.borrow_mut() = Some(b.clone());
+}
+
+#[kani::proof]
+fn check_rc_double_release() {
+    let value = Rc::new(kani::any::<u8>());
+    let weak = Rc::downgrade(&value);
+    let ptr = Rc::into_raw(value);
+    unsafe {
+        Rc::decrement_strong_count(ptr);
+        Rc::decrement_strong_count(ptr);
+    }
+    drop(weak);
+}
+
+#[kani::proof]
+fn check_arc_balanced() {
+    let first = Arc::new(kani::any::<u8>());
+    let second = Arc::clone(&first);
+    let weak = Arc::downgrade(&second);
+    drop(second);
+    assert_eq!(weak.upgrade().as_deref(), Some(&*first));
+    assert!(Arc::into_inner(first).is_some());
+}
+
+#[kani::proof]
+fn check_arc_forgotten_clone() {
+    let first = Arc::new(kani::any::<u8>());
+    std::mem::forget(first.clone());
+}
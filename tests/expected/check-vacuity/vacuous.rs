@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --check-vacuity
+
+//! Check that `--check-vacuity` reports the harnesses whose assumptions are contradictory as
+//! vacuous, but not the harnesses that are expected to panic.
+
+#[kani::proof]
+fn check_contradictory_assumptions() {
+    let x: u8 = kani::any();
+    kani::assume(x > 10);
+    kani::assume(x < 5);
+    assert!(x == 0);
+}
+
+#[kani::proof]
+fn check_consistent_assumptions() {
+    let x: u8 = kani::any();
+    kani::assume(x > 10);
+    assert!(x != 0);
+}
+
+#[kani::proof]
+#[kani::should_panic]
+fn check_expected_panic() {
+    let x: u8 = kani::any();
+    kani::assume(x > 10);
+    panic!("always panics");
+}
@@ -0,0 +1,47 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --concurrency bounded:1
+//! Check that the spawned threads interleave with the atomic operations of the main thread.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The spawned thread may increment the counter between the load and the store.
+#[kani::proof]
+fn check_lost_update() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let other = counter.clone();
+    let handle = std::thread::spawn(move || {
+        other.fetch_add(1, Ordering::SeqCst);
+    });
+    let value = counter.load(Ordering::SeqCst);
+    counter.store(value + 1, Ordering::SeqCst);
+    handle.join().unwrap();
+    assert!(counter.load(Ordering::SeqCst) == 2);
+}
+
+/// Incrementing the counter with a single atomic operation doesn't lose updates.
+#[kani::proof]
+fn check_fetch_add() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let other = counter.clone();
+    let handle = std::thread::spawn(move || {
+        other.fetch_add(1, Ordering::SeqCst);
+        10
+    });
+    counter.fetch_add(1, Ordering::SeqCst);
+    assert_eq!(handle.join().unwrap(), 10);
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+}
+
+/// The spawned thread may not have started before it is joined.
+#[kani::proof]
+fn check_not_started() {
+    let flag = Arc::new(AtomicUsize::new(0));
+    let other = flag.clone();
+    let handle = std::thread::spawn(move || other.store(1, Ordering::SeqCst));
+    kani::cover!(flag.load(Ordering::SeqCst) == 0, "thread not started");
+    kani::cover!(flag.load(Ordering::SeqCst) == 1, "thread started");
+    handle.join().unwrap();
+    assert_eq!(flag.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the cover properties with a label are aggregated across harnesses, and that the
+//! label is removed from their description.
+
+fn classify(x: u8) -> u8 {
+    if x == 0 {
+        kani::cover!(label = zero);
+        0
+    } else if x < 100 {
+        kani::cover!(x > 50, label = large);
+        1
+    } else {
+        2
+    }
+}
+
+#[kani::proof]
+fn check_small() {
+    let x: u8 = kani::any();
+    kani::assume(x < 50);
+    classify(x);
+    kani::cover!(x == 200, "x may be 200", label = huge);
+}
+
+#[kani::proof]
+fn check_any() {
+    let x: u8 = kani::any();
+    kani::assume(x > 0);
+    classify(x);
+}
@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `#[kani::arbitrary(where = ...)]` implements `Arbitrary` so that `kani::any()` only
+//! generates values that satisfy the condition.
+
+extern crate kani;
+
+#[kani::arbitrary(where = "self.len <= self.cap")]
+struct Buffer {
+    len: usize,
+    cap: usize,
+}
+
+#[kani::arbitrary(where = "self.0 < self.1")]
+struct Range(u8, u8);
+
+#[kani::proof]
+fn check_named_struct() {
+    let buffer: Buffer = kani::any();
+    assert!(buffer.len <= buffer.cap);
+    kani::cover!(buffer.len == buffer.cap);
+}
+
+#[kani::proof]
+fn check_unnamed_struct() {
+    let range: Range = kani::any();
+    assert!(range.0 != range.1);
+}
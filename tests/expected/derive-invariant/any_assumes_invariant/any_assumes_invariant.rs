@@ -0,0 +1,68 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::any()` assumes the derived `Invariant` of a type, even
+//! if its `Arbitrary` implementation does not respect the invariant.
+
+extern crate kani;
+use kani::Invariant;
+
+#[derive(kani::Arbitrary)]
+struct NotNegative(i32);
+
+impl kani::Invariant for NotNegative {
+    fn is_safe(&self) -> bool {
+        self.0 >= 0
+    }
+}
+
+#[derive(kani::Arbitrary)]
+#[derive(kani::Invariant)]
+struct NotNegativeWrapper {
+    x: NotNegative,
+}
+
+struct Even(u8);
+
+impl kani::Invariant for Even {
+    fn is_safe(&self) -> bool {
+        true
+    }
+}
+
+impl kani::Arbitrary for Even {
+    fn any() -> Self {
+        Even(kani::any())
+    }
+}
+
+#[derive(kani::Invariant)]
+struct EvenWrapper {
+    #[safety_constraint(inner.0 % 2 == 0)]
+    inner: Even,
+}
+
+impl kani::Arbitrary for EvenWrapper {
+    fn any() -> Self {
+        EvenWrapper { inner: kani::any() }
+    }
+}
+
+#[kani::proof]
+fn check_derived_invariant_assumed() {
+    let wrapper: NotNegativeWrapper = kani::any();
+    assert!(wrapper.is_safe());
+    assert!(wrapper.x.0 >= 0);
+}
+
+#[kani::proof]
+fn check_safety_constraint_assumed() {
+    let wrapper: EvenWrapper = kani::any();
+    assert!(wrapper.inner.0 % 2 == 0);
+}
+
+#[kani::proof]
+fn check_manual_invariant_not_assumed() {
+    let value: NotNegative = kani::any();
+    kani::cover!(!value.is_safe());
+}
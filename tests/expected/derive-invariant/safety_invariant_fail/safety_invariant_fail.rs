@@ -8,9 +8,8 @@
 use kani::Invariant;
 // Note: This represents an incorrect usage of `Arbitrary` and `Invariant`.
 //
-// The `Arbitrary` implementation should respect the type invariant,
-// but Kani does not enforce this in any way at the moment.
-// <https://github.com/model-checking/kani/issues/3265>
+// The `Arbitrary` implementation should respect the type invariant.
+// Kani only enforces this for types that derive `Invariant`.
 #[derive(kani::Arbitrary)]
 struct NotNegative(i32);
 
@@ -28,6 +27,8 @@ struct NotNegativeWrapper {
 
 #[kani::proof]
 fn check_invariant_fail() {
-    let wrapper: NotNegativeWrapper = kani::any();
+    // `kani::any()` would assume the derived invariant of the wrapper, so build it from an
+    // unconstrained field instead.
+    let wrapper = NotNegativeWrapper { x: kani::any() };
     assert!(wrapper.is_safe());
 }
@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani reports the `f128` operations that it can't encode, instead of approximating
+//! them.
+#![feature(f128)]
+
+#[kani::proof]
+fn check_sqrt() {
+    let x: f128 = kani::any();
+    let _ = x.sqrt();
+}
+
+#[kani::proof]
+fn check_cos() {
+    let x: f128 = kani::any();
+    let _ = x.cos();
+}
+
+#[kani::proof]
+fn check_abs() {
+    assert!((-2.0f128).abs() == 2.0);
+}
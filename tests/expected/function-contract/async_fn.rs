@@ -0,0 +1,57 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that contracts can be written for `async fn`s, and that they apply to the value that the
+//! function returns once it is awaited.
+
+async fn fetch(x: u8) -> u8 {
+    x
+}
+
+#[kani::requires(x < 100)]
+#[kani::ensures(|result| *result == x + 1)]
+async fn increment(x: u8) -> u8 {
+    fetch(x).await + 1
+}
+
+#[kani::ensures(|result| *result <= x)]
+async fn halve_wrong(x: u8) -> u8 {
+    fetch(x).await / 2 + 1
+}
+
+struct Counter {
+    value: u8,
+}
+
+impl Counter {
+    #[kani::requires(self.value < u8::MAX)]
+    #[kani::ensures(|result| *result == old(self.value) + 1 && self.value == *result)]
+    async fn bump(&mut self) -> u8 {
+        self.value = fetch(self.value).await + 1;
+        self.value
+    }
+}
+
+#[kani::proof_for_contract(increment)]
+fn check_increment() {
+    kani::block_on(increment(kani::any()));
+}
+
+#[kani::proof_for_contract(halve_wrong)]
+fn check_halve_wrong() {
+    kani::block_on(halve_wrong(kani::any()));
+}
+
+#[kani::proof_for_contract(Counter::bump)]
+fn check_bump() {
+    let mut counter = Counter { value: kani::any() };
+    kani::block_on(counter.bump());
+}
+
+#[kani::proof]
+#[kani::stub_verified(increment)]
+fn use_increment() {
+    let x: u8 = kani::any_where(|x| *x < 100);
+    assert!(kani::block_on(increment(x)) > x, "increment grows");
+}
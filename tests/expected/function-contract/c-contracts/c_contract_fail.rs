@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that calling a foreign function with a contract fails if the precondition doesn't hold,
+//! and that the targets of its `modifies` clause may change.
+
+#[kani::c_contracts]
+unsafe extern "C" {
+    #[kani::c_contract(requires = !buf.is_null() && len > 0, modifies = buf)]
+    fn fill(buf: *mut u8, len: usize);
+}
+
+#[kani::proof]
+fn check_null_buffer() {
+    unsafe { fill(std::ptr::null_mut(), 1) };
+}
+
+#[kani::proof]
+fn check_havoc() {
+    let mut byte = 0u8;
+    unsafe { fill(&mut byte, 1) };
+    assert_eq!(byte, 0);
+}
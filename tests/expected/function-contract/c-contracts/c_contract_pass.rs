@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that calls to a foreign function with a contract, and function pointers to it, are
+//! replaced by its contract.
+
+#[kani::c_contracts]
+unsafe extern "C" {
+    #[kani::c_contract(
+        requires = !buf.is_null() && len > 0,
+        modifies = buf,
+        ensures = |result: &i32| *result >= 0 && *result <= len as i32
+    )]
+    fn read_byte(buf: *mut u8, len: usize) -> i32;
+
+    #[kani::c_contract(ensures = |result: &u32| *result < 100)]
+    fn small() -> u32;
+}
+
+#[kani::proof]
+fn check_read_byte() {
+    let mut byte = 0u8;
+    let read = unsafe { read_byte(&mut byte, 1) };
+    assert!(read <= 1);
+}
+
+#[kani::proof]
+fn check_fn_pointer() {
+    let small_fn: unsafe extern "C" fn() -> u32 = small;
+    assert!(unsafe { small_fn() } < 100);
+}
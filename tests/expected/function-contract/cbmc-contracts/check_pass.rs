@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --cbmc-contracts
+
+//! Check a contract using CBMC's contract instrumentation.
+
+#[kani::requires(divisor != 0)]
+#[kani::ensures(|result: &u32| *result <= dividend)]
+fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[kani::proof_for_contract(div)]
+fn check_div() {
+    let dividend: u32 = kani::any();
+    let divisor: u32 = kani::any();
+    div(dividend, divisor);
+}
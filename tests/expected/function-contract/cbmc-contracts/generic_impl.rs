@@ -0,0 +1,57 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --cbmc-contracts
+
+//! Check that CBMC contracts support functions of generic `impl` blocks, whose clauses refer to
+//! the generic parameters of the block and to `Self`.
+
+trait Capacity {
+    const CAPACITY: usize;
+}
+
+struct Small;
+
+impl Capacity for Small {
+    const CAPACITY: usize = 4;
+}
+
+struct Large;
+
+impl Capacity for Large {
+    const CAPACITY: usize = 64;
+}
+
+struct Buffer<C: Capacity> {
+    len: usize,
+    _capacity: core::marker::PhantomData<C>,
+}
+
+impl<C: Capacity> Buffer<C> {
+    #[kani::requires(len <= C::CAPACITY)]
+    #[kani::ensures(|result: &Self| result.len == len)]
+    fn with_len(len: usize) -> Self {
+        Buffer { len, _capacity: core::marker::PhantomData }
+    }
+
+    #[kani::requires(buffer.len < C::CAPACITY)]
+    #[kani::ensures(|result: &usize| *result <= C::CAPACITY)]
+    fn next_len(buffer: &Self) -> usize {
+        buffer.len + 1
+    }
+}
+
+#[kani::proof_for_contract(Buffer::with_len)]
+fn check_with_len_small() {
+    let _ = Buffer::<Small>::with_len(kani::any());
+}
+
+#[kani::proof_for_contract(Buffer::with_len)]
+fn check_with_len_large() {
+    let _ = Buffer::<Large>::with_len(kani::any());
+}
+
+#[kani::proof_for_contract(Buffer::next_len)]
+fn check_next_len() {
+    let buffer = Buffer::<Small> { len: kani::any(), _capacity: core::marker::PhantomData };
+    let _ = Buffer::next_len(&buffer);
+}
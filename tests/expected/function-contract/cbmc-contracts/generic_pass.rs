@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --cbmc-contracts
+
+//! Check that CBMC contracts support clauses that call trait methods on generic arguments, and
+//! that each instance of a generic function is checked against its own predicates.
+
+trait Bounded {
+    const MAX: u32;
+    fn value(&self) -> u32;
+}
+
+impl Bounded for u8 {
+    const MAX: u32 = 100;
+    fn value(&self) -> u32 {
+        *self as u32
+    }
+}
+
+impl Bounded for u16 {
+    const MAX: u32 = 1000;
+    fn value(&self) -> u32 {
+        *self as u32
+    }
+}
+
+#[kani::requires(x.value() < T::MAX && N > 0)]
+#[kani::ensures(|result: &u32| *result <= T::MAX * N as u32)]
+fn scale<T, const N: usize>(x: T) -> u32
+where
+    T: Bounded + Copy,
+{
+    x.value() * N as u32
+}
+
+#[kani::proof_for_contract(scale)]
+fn check_scale_u8() {
+    scale::<u8, 3>(kani::any());
+}
+
+#[kani::proof_for_contract(scale)]
+fn check_scale_u16() {
+    scale::<u16, 2>(kani::any());
+}
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --cbmc-contracts
+
+//! Replace calls to a function by its contract using CBMC's contract instrumentation.
+
+#[kani::requires(divisor != 0)]
+#[kani::ensures(|result: &u32| *result <= dividend)]
+fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[kani::proof]
+#[kani::stub_verified(div)]
+fn use_div() {
+    let dividend: u32 = kani::any_where(|x| *x < 100);
+    assert!(div(dividend, 3) < 100, "contract guarantees smallness");
+}
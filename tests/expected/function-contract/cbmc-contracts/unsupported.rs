@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts --cbmc-contracts
+
+//! Contracts with `modifies` clauses or `old` expressions cannot be handed over to CBMC.
+
+#[kani::requires(*counter < 100)]
+#[kani::ensures(|_| old(*counter + 1) == *counter)]
+#[kani::modifies(counter)]
+fn increment(counter: &mut u32) {
+    *counter += 1;
+}
+
+#[kani::proof_for_contract(increment)]
+fn check_increment() {
+    let mut counter: u32 = kani::any();
+    increment(&mut counter);
+}
@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that closures with contracts that capture their environment are rejected.
+
+#[kani::proof]
+fn capture_in_contract() {
+    let limit: u32 = kani::any();
+    let check = kani::contract_closure!(
+        #[kani::requires(x <= limit)]
+        |x: u32| -> u32 { x }
+    );
+    check(0);
+}
+
+#[kani::proof]
+fn capture_in_body() {
+    let step: u32 = kani::any();
+    let add = kani::contract_closure!(|x: u32| -> u32 { x.wrapping_add(step) });
+    add(0);
+}
+
+#[kani::proof]
+fn move_closure() {
+    let double = kani::contract_closure!(
+        #[kani::ensures(|result: &u32| *result == x.wrapping_mul(2))]
+        move |x: u32| -> u32 { x.wrapping_mul(2) }
+    );
+    double(0);
+}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a closure that does not satisfy its contract fails verification.
+
+fn double_all(values: &mut [u32], double: impl Fn(u32) -> u32) {
+    values.iter_mut().for_each(|val| *val = double(*val));
+}
+
+#[kani::proof]
+fn use_closure() {
+    let double = kani::contract_closure!(
+        harness = check_double,
+        #[kani::requires(x <= u32::MAX / 2)]
+        #[kani::ensures(|result : &u32| *result > x)]
+        |x: u32| -> u32 { x * 2 }
+    );
+    let mut values = [1, 2, 3];
+    double_all(&mut values, double);
+    assert_eq!(values, [2, 4, 6]);
+}
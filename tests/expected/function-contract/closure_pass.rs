@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the contract of a closure is verified by the harness generated by
+//! `kani::contract_closure`, and that the closure can still be called as usual.
+
+#[kani::proof]
+fn use_closures() {
+    let decrement = kani::contract_closure!(
+        harness = check_decrement,
+        #[kani::requires(x > 0)]
+        #[kani::ensures(|result : &u32| *result < x)]
+        |x: u32| -> u32 { x - 1 }
+    );
+    let increment = kani::contract_closure!(
+        harness = check_increment,
+        #[kani::requires(*x < u8::MAX)]
+        #[kani::modifies(x)]
+        #[kani::ensures(|_| *x > 0)]
+        |x: &mut u8| { *x += 1 }
+    );
+
+    let mut val: u8 = kani::any_where(|v| *v < 10);
+    increment(&mut val);
+    assert!(decrement(val as u32) < 10);
+}
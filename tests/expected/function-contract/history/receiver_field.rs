@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `old` can take a snapshot of fields behind references, including the receiver, even
+//! when their type isn't `Copy`.
+
+#[derive(Clone, kani::Arbitrary)]
+struct Buffer {
+    values: [u8; 4],
+    len: usize,
+}
+
+#[derive(kani::Arbitrary)]
+struct Writer {
+    buffer: Buffer,
+}
+
+impl Writer {
+    #[kani::requires(self.buffer.len < 4)]
+    #[kani::modifies(&mut self.buffer)]
+    #[kani::ensures(|_| self.buffer.len == old(self.buffer.len) + 1)]
+    #[kani::ensures(|_| self.buffer.values[old(self.buffer).len] == value)]
+    #[kani::ensures(|_| self.buffer.values[..self.buffer.len - 1] == old(self.buffer.values)[..self.buffer.len - 1])]
+    fn push(&mut self, value: u8) {
+        self.buffer.values[self.buffer.len] = value;
+        self.buffer.len += 1;
+    }
+}
+
+#[kani::requires(writer.buffer.len < 4)]
+#[kani::modifies(&mut writer.buffer)]
+#[kani::ensures(|_| writer.buffer.len == old(writer.buffer.clone()).len + 1)]
+fn push_zero(writer: &mut Writer) {
+    writer.push(0)
+}
+
+#[kani::proof_for_contract(Writer::push)]
+fn check_push() {
+    let mut writer: Writer = kani::any();
+    writer.push(kani::any());
+}
+
+#[kani::proof_for_contract(push_zero)]
+#[kani::stub_verified(Writer::push)]
+fn check_push_zero() {
+    let mut writer: Writer = kani::any();
+    push_zero(&mut writer);
+}
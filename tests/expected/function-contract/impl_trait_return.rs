@@ -0,0 +1,45 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that contracts can be written for functions that return an `impl Trait`.
+
+#[kani::requires(n <= 5)]
+#[kani::ensures(|result| result.clone().count() == n as usize)]
+fn evens(n: u32) -> impl Iterator<Item = u32> + Clone {
+    (0..n * 2).step_by(2)
+}
+
+#[kani::ensures(|result| Into::<u16>::into(*result) == u16::from(x) * 2)]
+fn double(x: u8) -> impl Into<u16> + Copy {
+    u16::from(x) * 2
+}
+
+#[kani::ensures(|result: &Result<_, ()>| result.is_ok() == (x != 0))]
+fn non_zero(x: u8) -> Result<impl Into<u32> + Copy, ()> {
+    let x = if x == 0 { Err(()) } else { Ok(x) }?;
+    Ok(x)
+}
+
+#[kani::proof_for_contract(evens)]
+#[kani::unwind(7)]
+fn check_evens() {
+    let _ = evens(kani::any());
+}
+
+#[kani::proof_for_contract(double)]
+fn check_double() {
+    let _ = double(kani::any());
+}
+
+#[kani::proof_for_contract(non_zero)]
+fn check_non_zero() {
+    let _ = non_zero(kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(double)]
+fn use_double() {
+    let doubled: u16 = double(kani::any()).into();
+    assert!(doubled <= 510, "double is bounded");
+}
@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check contracts of functions that update a static through interior mutability.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+#[kani::requires(NEXT_ID.load(Ordering::Relaxed) < u32::MAX)]
+#[kani::modifies(&NEXT_ID)]
+#[kani::ensures(|id| *id == old(NEXT_ID.load(Ordering::Relaxed)))]
+#[kani::ensures(|id| NEXT_ID.load(Ordering::Relaxed) == *id + 1)]
+fn fresh_id() -> u32 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+#[kani::proof_for_contract(fresh_id)]
+fn check_fresh_id() {
+    fresh_id();
+}
+
+#[kani::proof]
+#[kani::stub_verified(fresh_id)]
+fn check_distinct_ids() {
+    NEXT_ID.store(kani::any_where(|id: &u32| *id < 10), Ordering::Relaxed);
+    let first = fresh_id();
+    let second = fresh_id();
+    kani::assert(first != second, "replaced");
+}
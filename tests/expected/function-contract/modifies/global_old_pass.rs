@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that postconditions can refer to the value of a mutated static before the call,
+//! and that the static is havocked when the contract replaces the function.
+
+static mut COUNTER: u32 = 0;
+
+#[kani::requires(unsafe { COUNTER } < u32::MAX)]
+#[kani::modifies(&raw mut COUNTER)]
+#[kani::ensures(|result| unsafe { COUNTER } == old(unsafe { COUNTER }) + 1 && *result == unsafe { COUNTER })]
+fn increment() -> u32 {
+    unsafe {
+        COUNTER += 1;
+        COUNTER
+    }
+}
+
+#[kani::proof_for_contract(increment)]
+fn check_increment() {
+    increment();
+}
+
+#[kani::proof]
+#[kani::stub_verified(increment)]
+fn check_increment_twice() {
+    unsafe { COUNTER = kani::any_where(|count: &u32| *count < 10) };
+    let first = increment();
+    let second = increment();
+    kani::assert(second == first + 1, "replaced");
+}
@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a `modifies` clause over a nested field only makes that field assignable.
+
+struct Header {
+    len: u32,
+    flags: u32,
+}
+
+struct Packet {
+    header: Header,
+    checksum: u32,
+}
+
+#[kani::requires(packet.header.len < 100)]
+#[kani::modifies(&packet.header.len)]
+#[kani::ensures(|_| packet.header.len == old(packet.header.len) + 1)]
+fn grow(packet: &mut Packet) {
+    packet.header.len += 1;
+}
+
+#[kani::proof_for_contract(grow)]
+fn check_grow() {
+    let mut packet =
+        Packet { header: Header { len: kani::any(), flags: kani::any() }, checksum: kani::any() };
+    grow(&mut packet);
+}
+
+#[kani::proof]
+#[kani::stub_verified(grow)]
+fn check_other_fields_unchanged() {
+    let mut packet = Packet { header: Header { len: 10, flags: 3 }, checksum: 7 };
+    grow(&mut packet);
+    kani::assert(packet.header.len == 11, "Length updated");
+    kani::assert(packet.header.flags == 3 && packet.checksum == 7, "Other fields unchanged");
+}
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a `modifies` clause over a range of a slice only makes that range assignable,
+//! both when checking the contract and when replacing calls by it.
+
+#[kani::requires(idx <= buf.len() && len <= buf.len() - idx)]
+#[kani::modifies(&buf[idx..idx + len])]
+#[kani::ensures(|_| buf[idx..idx + len].iter().all(|b| *b == 0))]
+fn clear_range(buf: &mut [u8], idx: usize, len: usize) {
+    buf[idx..idx + len].fill(0)
+}
+
+#[kani::proof_for_contract(clear_range)]
+fn check_clear_range() {
+    let mut buf: [u8; 8] = kani::any();
+    clear_range(&mut buf, kani::any(), kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(clear_range)]
+fn check_outside_range_unchanged() {
+    let mut buf = [1u8; 8];
+    clear_range(&mut buf, 2, 3);
+    kani::assert(buf[2..5].iter().all(|b| *b == 0), "Range cleared");
+    kani::assert(buf[..2].iter().chain(&buf[5..]).all(|b| *b == 1), "Rest unchanged");
+}
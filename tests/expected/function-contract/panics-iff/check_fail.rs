@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the contract harness fails if the function returns although its
+//! `ensures_panics_iff` condition holds, or panics although it doesn't.
+
+#[kani::ensures_panics_iff(x > 10)]
+fn checked_double(x: u8) -> u8 {
+    if x > 100 {
+        panic!("too large");
+    }
+    x * 2
+}
+
+#[kani::ensures_panics_iff(x == 0)]
+fn inverse(x: u8) -> u8 {
+    if x == 0 {
+        panic!("zero has no inverse");
+    }
+    255 / (x - 1)
+}
+
+#[kani::proof_for_contract(checked_double)]
+fn check_double() {
+    let _ = checked_double(kani::any());
+}
+
+#[kani::proof_for_contract(inverse)]
+fn check_inverse() {
+    let _ = inverse(kani::any());
+}
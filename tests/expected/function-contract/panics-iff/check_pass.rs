@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a function that panics exactly when its `ensures_panics_iff` condition holds
+//! satisfies its contract.
+
+#[kani::ensures_panics_iff(divisor == 0)]
+#[kani::ensures(|result: &u32| *result <= dividend)]
+fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[kani::proof_for_contract(div)]
+fn check_div() {
+    let _ = div(kani::any(), kani::any());
+}
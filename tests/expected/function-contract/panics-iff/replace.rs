@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that replacing a function by its contract panics when the `ensures_panics_iff`
+//! condition holds.
+
+#[kani::ensures_panics_iff(divisor == 0)]
+#[kani::ensures(|result: &u32| *result <= dividend)]
+fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[kani::proof_for_contract(div)]
+fn check_div() {
+    let _ = div(kani::any(), kani::any());
+}
+
+#[kani::proof]
+#[kani::stub_verified(div)]
+fn use_nonzero_divisor() {
+    let divisor: u32 = kani::any();
+    kani::assume(divisor != 0);
+    assert!(div(10, divisor) <= 10);
+}
+
+#[kani::proof]
+#[kani::stub_verified(div)]
+fn use_any_divisor() {
+    let _ = div(10, kani::any());
+}
@@ -0,0 +1,45 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+//! Check that contracts can call a recursive spec function, whose body is executed even when the
+//! contract replaces the function that calls it, and whose recursion is bounded per harness.
+
+#[kani::spec_fn]
+#[kani::ensures(|result: &bool| !*result || xs.len() < 2 || xs[0] <= xs[1])]
+fn is_sorted(xs: &[u8]) -> bool {
+    xs.len() < 2 || (xs[0] <= xs[1] && is_sorted(&xs[1..]))
+}
+
+#[kani::requires(!xs.is_empty() && is_sorted(xs))]
+#[kani::ensures(|result: &u8| xs.iter().all(|x| *result <= *x))]
+fn min(xs: &[u8]) -> u8 {
+    xs[0]
+}
+
+#[kani::proof_for_contract(min)]
+#[kani::spec_unwind(4)]
+fn check_min() {
+    let xs: [u8; 3] = kani::any();
+    min(&xs);
+}
+
+#[kani::proof]
+#[kani::stub_verified(min)]
+#[kani::spec_unwind(4)]
+fn check_min_replaced() {
+    assert!(min(&[1, 2, 3]) <= 1);
+}
+
+#[kani::proof]
+#[kani::stub_verified(min)]
+#[kani::spec_unwind(4)]
+fn check_min_replaced_unsorted() {
+    min(&[1, 3, 2]);
+}
+
+#[kani::proof_for_contract(min)]
+#[kani::spec_unwind(1)]
+fn check_min_insufficient_unwind() {
+    let xs: [u8; 3] = kani::any();
+    min(&xs);
+}
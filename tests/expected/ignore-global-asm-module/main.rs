@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::ignore_global_asm]` ignores the global assembly of a module without
+//! `--ignore-global-asm`, and that only the statics that the assembly assigns become arbitrary.
+
+#[kani::ignore_global_asm(COUNTER)]
+mod startup {
+    pub static mut COUNTER: u32 = 0;
+    pub static mut LIMIT: u32 = 10;
+
+    // Defines the function `kani_startup`.
+    std::arch::global_asm!(".global kani_startup", "kani_startup:", "nop");
+}
+
+#[kani::proof]
+fn check_assigned_static_is_arbitrary() {
+    let counter = unsafe { startup::COUNTER };
+    assert_eq!(counter, 0);
+}
+
+#[kani::proof]
+fn check_other_static_keeps_its_value() {
+    let limit = unsafe { startup::LIMIT };
+    assert_eq!(limit, 10);
+}
@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that casting a vector with `simd_cast` to a vector of a different
+//! length causes an error.
+#![feature(repr_simd, core_intrinsics)]
+use std::intrinsics::simd::simd_cast;
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct i64x2([i64; 2]);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct i32x4([i32; 4]);
+
+#[kani::proof]
+fn main() {
+    let x = i64x2([1, 2]);
+
+    unsafe {
+        let _invalid_simd: i32x4 = simd_cast(x);
+        // ^^^^ The code above fails to type-check in Rust with the error:
+        // ```
+        // error[E0511]: invalid monomorphization of `simd_cast` intrinsic: expected
+        // return type with length 2 (same as input type `i64x2`), found `i32x4` with length 4
+        // ```
+    }
+}
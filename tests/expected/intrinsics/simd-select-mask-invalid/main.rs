@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `simd_select` fails when a lane of the mask is neither all
+//! zeros nor all ones.
+#![feature(repr_simd, core_intrinsics)]
+use std::intrinsics::simd::simd_select;
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct i32x2([i32; 2]);
+
+#[kani::proof]
+fn main() {
+    let mask = i32x2([-1, kani::any()]);
+    let x = i32x2([1, 2]);
+    let y = i32x2([3, 4]);
+    let _ = unsafe { simd_select(mask, x, y) };
+}
@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `simd_select` with a mask whose length is different to the
+//! length of the operands causes an error.
+#![feature(repr_simd, core_intrinsics)]
+use std::intrinsics::simd::simd_select;
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct i64x4([i64; 4]);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct i32x2([i32; 2]);
+
+#[kani::proof]
+fn main() {
+    let mask = i32x2([-1, 0]);
+    let x = i64x4([1, 2, 3, 4]);
+    let y = i64x4([5, 6, 7, 8]);
+
+    unsafe {
+        let _invalid_simd: i64x4 = simd_select(mask, x, y);
+        // ^^^^ The code above fails to type-check in Rust with the error:
+        // ```
+        // error[E0511]: invalid monomorphization of `simd_select` intrinsic: expected
+        // mask with length 4 (same as input type `i64x4`), found `i32x2` with length 2
+        // ```
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z uninit-checks
+
+//! Checks that `transmute_copy` copies the first `size_of::<Dst>()` bytes of the source, that
+//! Kani checks these bytes are initialized, and that Kani reports which types were involved when
+//! the destination is larger than the source.
+
+use std::mem::transmute_copy;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Padded {
+    first: u8,
+    second: u32,
+}
+
+#[kani::proof]
+fn check_copy_prefix() {
+    let src: [u16; 4] = kani::any();
+    let dst: u32 = unsafe { transmute_copy(&src) };
+    assert_eq!(dst.to_ne_bytes()[..2], src[0].to_ne_bytes());
+    assert_eq!(dst.to_ne_bytes()[2..], src[1].to_ne_bytes());
+}
+
+#[kani::proof]
+fn check_copy_unaligned() {
+    let src: [u8; 3] = kani::any();
+    let dst: u16 = unsafe { transmute_copy(&src) };
+    assert_eq!(dst.to_ne_bytes(), [src[0], src[1]]);
+}
+
+/// This should fail since the bytes after `first` are padding.
+#[kani::proof]
+fn check_copy_padding() {
+    let src = Padded { first: kani::any(), second: kani::any() };
+    let _dst: u32 = unsafe { transmute_copy(&src) };
+}
+
+/// This should fail since the destination is larger than the source.
+#[kani::proof]
+fn check_larger_dst() {
+    let src: u32 = kani::any();
+    let _dst: (u32, u16) = unsafe { transmute_copy(&src) };
+}
@@ -0,0 +1,61 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `#[kani::invariant(atomic)]` checks that public methods that take `&self` preserve
+//! the safety invariant of `self` when other methods run in the middle of them.
+
+extern crate kani;
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+pub struct Bounds {
+    low: AtomicU8,
+    high: AtomicU8,
+}
+
+impl kani::Invariant for Bounds {
+    fn is_safe(&self) -> bool {
+        self.low.load(Ordering::SeqCst) <= self.high.load(Ordering::SeqCst)
+    }
+}
+
+#[kani::invariant(atomic)]
+impl Bounds {
+    /// Breaks the invariant if `lower` runs between the load and the store.
+    pub fn collapse(&self) {
+        let high = self.high.load(Ordering::SeqCst);
+        self.low.store(high, Ordering::SeqCst);
+    }
+
+    /// Preserves the invariant, since it checks and updates `high` in a single step.
+    pub fn lower(&self) {
+        let _ = self.high.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |high| {
+            (self.low.load(Ordering::SeqCst) < high).then(|| high - 1)
+        });
+    }
+
+    /// Preserves the invariant, since `high` only grows.
+    pub fn raise(&self, value: u8) {
+        let old = self.high.fetch_max(value, Ordering::SeqCst);
+        kani::cover!(old < value);
+    }
+}
+
+fn any_bounds() -> Bounds {
+    Bounds { low: AtomicU8::new(kani::any()), high: AtomicU8::new(kani::any()) }
+}
+
+#[kani::proof]
+fn check_collapse() {
+    any_bounds().collapse();
+}
+
+#[kani::proof]
+fn check_lower() {
+    any_bounds().lower();
+}
+
+#[kani::proof]
+fn check_raise() {
+    any_bounds().raise(kani::any());
+}
@@ -0,0 +1,60 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --check-invariant-preservation
+
+//! Check that Kani checks that public methods that take `&mut self` preserve the safety
+//! invariant of `self`.
+
+extern crate kani;
+
+#[derive(kani::Arbitrary)]
+#[derive(kani::Invariant)]
+#[safety_constraint(*low <= *high)]
+pub struct Interval {
+    low: i32,
+    high: i32,
+}
+
+impl Interval {
+    /// Preserves the invariant.
+    pub fn widen(&mut self) {
+        self.low = self.low.saturating_sub(1);
+        self.high = self.high.saturating_add(1);
+    }
+
+    /// Breaks the invariant if `low` is `i32::MIN`.
+    pub fn shift_down(&mut self) {
+        self.low = self.low.wrapping_sub(1);
+    }
+
+    /// Private methods are not checked.
+    fn set_low(&mut self, low: i32) {
+        self.low = low;
+    }
+
+    /// Restores the invariant after calling a private method that breaks it.
+    pub fn clamp_to(&mut self, value: i32) {
+        self.set_low(value);
+        if self.high < self.low {
+            self.high = self.low;
+        }
+    }
+}
+
+#[kani::proof]
+fn check_widen() {
+    let mut interval: Interval = kani::any();
+    interval.widen();
+}
+
+#[kani::proof]
+fn check_shift_down() {
+    let mut interval: Interval = kani::any();
+    interval.shift_down();
+}
+
+#[kani::proof]
+fn check_clamp_to() {
+    let mut interval: Interval = kani::any();
+    interval.clamp_to(kani::any());
+}
@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --isolation-memory-limit 1
+//
+//! Check that a crash of the process that verifies an isolated harness, here because it cannot
+//! even start with 1 MiB of memory, only fails that harness.
+
+#[kani::proof]
+fn check_in_process() {
+    let x: u32 = kani::any();
+    assert_eq!(x.wrapping_add(1).wrapping_sub(1), x);
+}
+
+#[kani::proof(isolate = "process")]
+fn check_isolated() {
+    let x: u32 = kani::any();
+    assert_eq!(x ^ x, 0);
+}
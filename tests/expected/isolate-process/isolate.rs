@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that harnesses annotated with `#[kani::proof(isolate = "process")]` are verified in a
+//! separate process, and that their results are included in the summary.
+
+fn checked_double(x: u8) -> Option<u8> {
+    x.checked_mul(2)
+}
+
+#[kani::proof]
+fn check_in_process() {
+    let x: u8 = kani::any_where(|x| *x < 128);
+    assert!(checked_double(x).is_some());
+}
+
+#[kani::proof(isolate = "process")]
+fn check_isolated_success() {
+    let x: u8 = kani::any_where(|x| *x < 128);
+    assert_eq!(checked_double(x), Some(x + x));
+}
+
+#[kani::proof(isolate = "process")]
+fn check_isolated_failure() {
+    let x: u8 = kani::any();
+    assert!(checked_double(x).is_some(), "doubling overflows");
+}
@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --check-leaks -Z unstable-options
+//! Check that every heap allocation of a harness must be deallocated exactly once.
+
+use std::rc::Rc;
+
+#[kani::proof]
+fn check_dropped() {
+    let boxed = Box::new(kani::any::<u32>());
+    let mut vec = vec![*boxed];
+    vec.push(1);
+    let rc = Rc::new(vec);
+    let alias = Rc::clone(&rc);
+    drop(rc);
+    assert_eq!(alias.len(), 2);
+}
+
+#[kani::proof]
+fn check_forget_fails() {
+    let vec = vec![1u8, 2, 3];
+    std::mem::forget(vec);
+}
+
+#[kani::proof]
+fn check_double_drop_fails() {
+    let boxed = Box::new(10u32);
+    let copy = unsafe { std::ptr::read(&boxed) };
+    drop(boxed);
+    drop(copy);
+}
@@ -0,0 +1,48 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --list-unsafe
+
+//! Check that `--list-unsafe` lists the unsafe operations that are reachable from each harness
+//! instead of verifying it.
+
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+unsafe fn double(ptr: *mut u32) {
+    unsafe { *ptr *= 2 };
+}
+
+fn read_raw(value: &u32) -> u32 {
+    let ptr = value as *const u32;
+    unsafe { *ptr }
+}
+
+fn to_bits(float: f32) -> u32 {
+    let bits = Bits { float };
+    unsafe { bits.int }
+}
+
+fn from_bits(int: u32) -> f32 {
+    unsafe { std::mem::transmute::<u32, f32>(int) }
+}
+
+#[kani::proof]
+fn check_raw() {
+    let mut value: u32 = kani::any_where(|value| *value < 100);
+    unsafe { double(&mut value) };
+    assert!(read_raw(&value) < 200);
+}
+
+#[kani::proof]
+fn check_bits() {
+    let float: f32 = kani::any();
+    assert!(from_bits(to_bits(float)).to_bits() == float.to_bits());
+}
+
+#[kani::proof]
+fn check_safe() {
+    let value: u8 = kani::any();
+    kani::assert(value / 2 < 128, "no unsafe code is needed");
+}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: -Z loop-contracts
+
+//! Check that `loop_modifies` over a range of an array only makes that range assignable.
+
+#![feature(proc_macro_hygiene)]
+#![feature(stmt_expr_attributes)]
+
+#[kani::proof]
+fn main() {
+    let mut i = 2;
+    let mut a: [u8; 10] = [1; 10];
+    #[kani::loop_invariant(i >= 2 && i <= 6)]
+    #[kani::loop_modifies(&i, &a[2..6])]
+    while i < 6 {
+        a[i] = 0;
+        i = i + 1;
+    }
+    assert!(a[..2].iter().chain(&a[6..]).all(|v| *v == 1));
+}
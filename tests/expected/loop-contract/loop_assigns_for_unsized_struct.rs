@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: -Z loop-contracts
+
+//! Check the use of loop_modifies for a fat pointer to a struct with an unsized tail, which makes
+//! the whole struct assignable.
+
+#![feature(proc_macro_hygiene)]
+#![feature(stmt_expr_attributes)]
+
+struct Buffer<T: ?Sized> {
+    len: usize,
+    data: T,
+}
+
+#[kani::proof]
+fn main() {
+    let mut i = 0;
+    let mut buffer: Buffer<[u8; 4]> = Buffer { len: 4, data: kani::any() };
+    let ptr: *mut Buffer<[u8]> = &mut buffer;
+    #[kani::loop_invariant(i <= 4)]
+    #[kani::loop_modifies(&i, ptr)]
+    while i < 4 {
+        unsafe {
+            (*ptr).data[i] = 1;
+            (*ptr).len = i;
+        }
+        i = i + 1;
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --concurrency bounded:1 --memory-model release-acquire
+//! Check that the loads of `--memory-model release-acquire` may read stale values, unless they
+//! synchronize with the thread that stored the value they read.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering, fence};
+
+/// The relaxed load of the flag doesn't synchronize with the release store, so the spawned thread
+/// may not observe the store of the data.
+#[kani::proof]
+fn check_relaxed_flag() {
+    let data = Arc::new(AtomicUsize::new(0));
+    let flag = Arc::new(AtomicBool::new(false));
+    let (data2, flag2) = (data.clone(), flag.clone());
+    let handle = std::thread::spawn(move || {
+        if flag2.load(Ordering::Relaxed) {
+            assert!(data2.load(Ordering::Relaxed) == 42);
+        }
+    });
+    data.store(42, Ordering::Relaxed);
+    flag.store(true, Ordering::Release);
+    handle.join().unwrap();
+}
+
+#[kani::proof]
+fn check_acquire_flag() {
+    let data = Arc::new(AtomicUsize::new(0));
+    let flag = Arc::new(AtomicBool::new(false));
+    let (data2, flag2) = (data.clone(), flag.clone());
+    let handle = std::thread::spawn(move || {
+        if flag2.load(Ordering::Acquire) {
+            assert!(data2.load(Ordering::Relaxed) == 42);
+        }
+        kani::cover!(flag2.load(Ordering::Relaxed), "flag observed");
+    });
+    data.store(42, Ordering::Relaxed);
+    flag.store(true, Ordering::Release);
+    handle.join().unwrap();
+}
+
+#[kani::proof]
+fn check_fences() {
+    let data = Arc::new(AtomicUsize::new(0));
+    let flag = Arc::new(AtomicBool::new(false));
+    let (data2, flag2) = (data.clone(), flag.clone());
+    let handle = std::thread::spawn(move || {
+        if flag2.load(Ordering::Relaxed) {
+            fence(Ordering::Acquire);
+            assert!(data2.load(Ordering::Relaxed) == 42);
+        }
+    });
+    data.store(42, Ordering::Relaxed);
+    fence(Ordering::Release);
+    flag.store(true, Ordering::Relaxed);
+    handle.join().unwrap();
+}
+
+/// The joining thread observes every store of the joined thread.
+#[kani::proof]
+fn check_join() {
+    let data = Arc::new(AtomicUsize::new(0));
+    let data2 = data.clone();
+    let handle = std::thread::spawn(move || data2.store(42, Ordering::Relaxed));
+    handle.join().unwrap();
+    assert!(data.load(Ordering::Relaxed) == 42);
+}
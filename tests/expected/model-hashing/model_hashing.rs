@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --model-hashing
+
+//! Check that the model of `DefaultHasher` produces equal hashes for equal inputs, and that
+//! hash-based collections still behave as expected.
+
+use std::collections::HashSet;
+use std::hash::{BuildHasherDefault, DefaultHasher, Hash, Hasher};
+
+fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[kani::proof]
+fn check_equal_inputs() {
+    let x: u32 = kani::any();
+    let y: u32 = kani::any();
+    if x == y {
+        assert_eq!(hash_of(&x), hash_of(&y));
+    }
+    kani::cover!(hash_of(&x) != hash_of(&y), "different inputs may have different hashes");
+}
+
+#[kani::proof]
+fn check_equal_strings() {
+    let s = String::from("kani");
+    assert_eq!(hash_of(&s), hash_of(&"kani"));
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_hash_set() {
+    let mut set: HashSet<u16, BuildHasherDefault<DefaultHasher>> = HashSet::default();
+    let x: u16 = kani::any();
+    let y: u16 = kani::any();
+    set.insert(x);
+    set.insert(y);
+    assert!(set.contains(&x));
+    assert_eq!(set.len() == 1, x == y);
+}
@@ -0,0 +1,60 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --model-std-collections
+
+//! Check that `HashMap` and `HashSet` keep their semantics when they are replaced by the models
+//! in `kani::models`, and that methods without a model fail verification.
+
+use std::collections::{HashMap, HashSet};
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_map() {
+    let mut map = HashMap::new();
+    let x: u8 = kani::any();
+    let y: u8 = kani::any();
+    assert_eq!(map.insert(x, 'x'), None);
+    map.insert(y, 'y');
+    assert_eq!(map.get(&y), Some(&'y'));
+    assert_eq!(map.len() == 1, x == y);
+    assert!(map.remove(&x).is_some());
+    assert!(!map.contains_key(&x));
+    assert_eq!(map.values().count(), map.len());
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_map_traits() {
+    let map = HashMap::from([(1u8, 10u32), (2, 20)]);
+    let copy = map.clone();
+    assert_eq!(map, copy);
+    assert_eq!(copy[&2], 20);
+    let sum: u32 = map.into_iter().map(|(_, value)| value).sum();
+    assert_eq!(sum, 30);
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_set() {
+    let mut set: HashSet<u16> = HashSet::new();
+    let x: u16 = kani::any();
+    let y: u16 = kani::any();
+    set.insert(x);
+    set.insert(y);
+    assert!(set.contains(&x));
+    assert_eq!(set.len() == 1, x == y);
+    assert_eq!(set.iter().count(), set.len());
+}
+
+#[kani::proof]
+fn check_model_directly() {
+    let mut map: kani::models::HashMap<u8, u8> = kani::models::HashMap::new();
+    map.insert(1, 2);
+    assert_eq!(map.get(&1), Some(&2));
+}
+
+#[kani::proof]
+fn check_unsupported() {
+    let mut map: HashMap<u8, u8> = HashMap::new();
+    *map.entry(0).or_insert(0) += 1;
+}
@@ -0,0 +1,52 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --model-std-collections
+
+//! Check that `VecDeque` and `BTreeMap` keep their semantics when they are replaced by the models
+//! in `kani::models::collections`.
+
+use std::collections::{BTreeMap, VecDeque};
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_deque() {
+    let mut deque = VecDeque::new();
+    let x: u8 = kani::any();
+    let y: u8 = kani::any();
+    deque.push_back(x);
+    deque.push_front(y);
+    deque.push_back(0);
+    assert_eq!(deque.len(), 3);
+    assert_eq!(deque.front(), Some(&y));
+    assert_eq!(deque[1], x);
+    assert_eq!(deque.pop_back(), Some(0));
+    assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![y, x]);
+    let vec: Vec<u8> = deque.into();
+    assert_eq!(vec, [y, x]);
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_map() {
+    let mut map = BTreeMap::new();
+    let x: u8 = kani::any();
+    let y: u8 = kani::any();
+    assert_eq!(map.insert(x, 'x'), None);
+    map.insert(y, 'y');
+    assert_eq!(map.get(&y), Some(&'y'));
+    assert_eq!(map.len() == 1, x == y);
+    let (first, _) = map.first_key_value().unwrap();
+    assert_eq!(*first, x.min(y));
+    assert!(map.keys().zip(map.keys().skip(1)).all(|(a, b)| a < b));
+    assert_eq!(map.range(..=x).next_back(), Some((&x, &map[&x])));
+    assert!(map.remove(&x).is_some());
+    assert!(!map.contains_key(&x));
+}
+
+#[kani::proof]
+fn check_model_directly() {
+    let mut map: kani::models::collections::BTreeMap<u8, u8> =
+        kani::models::collections::BTreeMap::new();
+    map.insert(1, 2);
+    assert_eq!(map.get(&1), Some(&2));
+}
@@ -0,0 +1,35 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --model-std-io
+
+//! Check that `--model-std-io` replaces the environment variables and the files accessed through
+//! `std::env` and `std::fs` by a symbolic model.
+
+use std::path::Path;
+
+/// Load the port of a server from the environment, with a default value.
+fn port() -> u16 {
+    std::env::var("PORT").ok().and_then(|port| port.parse().ok()).unwrap_or(8080)
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn check_env_is_stable() {
+    let first = std::env::var_os("PORT");
+    let port = port();
+    assert_eq!(std::env::var_os("PORT"), first);
+    kani::cover!(first.is_none(), "unset");
+    kani::cover!(port != 8080, "parsed");
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn check_fs_round_trip() {
+    let path = Path::new("config.toml");
+    let before = std::fs::read(path);
+    kani::cover!(before.is_err(), "missing");
+    std::fs::write(path, b"key").unwrap();
+    assert_eq!(std::fs::read_to_string(path).unwrap(), "key");
+    std::fs::remove_file(path).unwrap();
+    assert!(std::fs::read(path).is_err());
+}
@@ -0,0 +1,53 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --model-time
+
+//! Check that `--model-time` replaces the clocks of `std::time` by nondeterministic models, where
+//! the monotonic clock never goes backwards, so that timeout logic is verified for every elapsed
+//! time.
+
+use std::time::{Duration, Instant, SystemTime};
+
+/// Retry `op` until it succeeds or `timeout` elapses.
+fn retry_until(timeout: Duration, mut op: impl FnMut() -> bool) -> bool {
+    let start = Instant::now();
+    for _ in 0..3 {
+        if op() {
+            return true;
+        }
+        if start.elapsed() >= timeout {
+            return false;
+        }
+    }
+    false
+}
+
+#[kani::proof]
+fn check_instant_is_monotonic() {
+    let first = Instant::now();
+    let second = Instant::now();
+    assert!(second >= first);
+    assert!(second.duration_since(first) == second - first);
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_retry_timeout() {
+    let succeeds: bool = kani::any();
+    let done = retry_until(Duration::from_secs(1), || succeeds);
+    assert_eq!(done, succeeds);
+}
+
+#[kani::proof]
+fn check_elapsed_can_exceed_timeout() {
+    let start = Instant::now();
+    kani::cover!(start.elapsed() >= Duration::from_secs(60), "timeout may expire");
+    kani::cover!(start.elapsed() < Duration::from_millis(1), "timeout may not expire");
+}
+
+#[kani::proof]
+fn check_system_time_may_go_backwards() {
+    let first = SystemTime::now();
+    let second = SystemTime::now();
+    kani::cover!(second.duration_since(first).is_err(), "system clock went backwards");
+}
@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani reports which probes are reachable for every harness, and that probes with
+//! the same name are aggregated.
+
+fn classify(x: u8) -> u8 {
+    if x == 0 {
+        kani::probe!("zero");
+        0
+    } else if x < 10 {
+        kani::probe!("small");
+        1
+    } else if x > 200 && x < 100 {
+        kani::probe!("impossible");
+        2
+    } else {
+        kani::probe!("large");
+        3
+    }
+}
+
+fn check_generic<T: Default + PartialEq>(value: T) {
+    if value == T::default() {
+        kani::probe!("default value");
+    }
+}
+
+#[kani::proof]
+fn check_classify() {
+    let x: u8 = kani::any();
+    assert!(classify(x) <= 3);
+}
+
+#[kani::proof]
+fn check_aggregated() {
+    // Only one of the instances of the probe is reachable.
+    check_generic::<u8>(kani::any());
+    check_generic::<u16>(1);
+    kani::probe!("harness end");
+}
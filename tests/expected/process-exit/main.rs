@@ -0,0 +1,39 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `std::process::exit` and `std::process::abort` are terminal states with their own
+//! property class, and that exiting with code `0` is a successful termination.
+
+use std::process;
+
+fn run(input: u8) -> u8 {
+    match input {
+        0 => process::exit(0),
+        1..=9 => input * 2,
+        _ => process::exit(1),
+    }
+}
+
+#[kani::proof]
+fn check_exit_success() {
+    let input: u8 = kani::any();
+    kani::assume(input < 10);
+    let output = run(input);
+    assert!(input != 0, "exit(0) terminates the harness");
+    assert!(output < 20);
+}
+
+#[kani::proof]
+fn check_exit_failure() {
+    let _ = run(kani::any());
+}
+
+/// An abort is not a panic, so it doesn't satisfy `should_panic`.
+#[kani::proof]
+#[kani::should_panic]
+fn check_abort_is_not_panic() {
+    if kani::any() {
+        process::abort();
+    }
+    panic!("expected panic");
+}
@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// compile-flags: --edition 2018
+
+//! Check that the arguments of a harness are arbitrary values.
+
+#[kani::proof]
+fn check_args(x: u8, flag: bool) {
+    kani::cover!(flag && x == u8::MAX, "arbitrary arguments");
+    if flag {
+        assert!(x.checked_add(1).is_some(), "x must be smaller than u8::MAX");
+    }
+}
+
+#[derive(kani::Arbitrary)]
+struct Point {
+    x: i8,
+    y: i8,
+}
+
+/// Arguments can be patterns, and their types any type that implements `Arbitrary`.
+#[kani::proof]
+fn check_patterns(Point { x, y }: Point, (a, b): (u8, u8)) {
+    assert_eq!(x as i16 + y as i16, y as i16 + x as i16);
+    assert!(a as u16 + b as u16 <= 510);
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+async fn check_async_args(values: [u8; 2]) {
+    let sum = async { values.iter().map(|v| *v as u16).sum::<u16>() }.await;
+    assert!(sum <= 510);
+}
@@ -0,0 +1,61 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z provenance-checks
+//! Check that pointers cast from integers must have the provenance of an exposed allocation, and
+//! must point into it, and that wrapping pointer arithmetic must stay within the bounds of the
+//! allocation.
+
+#[kani::proof]
+fn check_exposed_round_trip() {
+    let value = 10u32;
+    let addr = (&value as *const u32).expose_provenance();
+    let ptr = std::ptr::with_exposed_provenance::<u32>(addr);
+    assert_eq!(unsafe { *ptr }, 10);
+}
+
+#[kani::proof]
+fn check_one_past_end_fails() {
+    let array = [1u8, 2, 3];
+    let addr = array.as_ptr() as usize;
+    let _end = (addr + array.len()) as *const u8;
+}
+
+#[kani::proof]
+fn check_wrapping_one_past_end() {
+    let array = [1u8, 2, 3];
+    let end = array.as_ptr().wrapping_add(array.len());
+    assert_eq!(end.addr(), array.as_ptr().addr() + 3);
+}
+
+#[kani::proof]
+fn check_wrapping_out_of_bounds_fails() {
+    let array = [1u8, 2, 3];
+    let _ptr = array.as_ptr().wrapping_add(4);
+}
+
+#[kani::proof]
+fn check_with_addr_out_of_bounds_fails() {
+    let array = [1u8, 2, 3];
+    let ptr = array.as_ptr();
+    let _moved = ptr.with_addr(ptr.addr() + 8);
+}
+
+#[kani::proof]
+fn check_null() {
+    let ptr = 0usize as *const u8;
+    assert!(ptr.is_null());
+}
+
+#[kani::proof]
+fn check_not_exposed_fails() {
+    let value = 10u32;
+    let addr = (&value as *const u32).addr();
+    let _ptr = std::ptr::with_exposed_provenance::<u32>(addr);
+}
+
+#[kani::proof]
+fn check_out_of_bounds_fails() {
+    let array = [1u8, 2, 3];
+    let addr = array.as_ptr() as usize;
+    let _ptr = (addr + 4) as *const u8;
+}
@@ -0,0 +1,52 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that Kani detects accesses through pointers that were built with `ptr::from_raw_parts`
+//! from metadata that doesn't match the object that they point to.
+
+#![feature(ptr_metadata)]
+
+use std::ptr;
+
+struct Header<T: ?Sized> {
+    len: usize,
+    data: T,
+}
+
+trait Describe {
+    fn describe(&self) -> u64;
+}
+
+impl Describe for u8 {
+    fn describe(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl Describe for u64 {
+    fn describe(&self) -> u64 {
+        *self
+    }
+}
+
+#[kani::proof]
+fn check_slice_len_too_large() {
+    let header = Header { len: 2, data: [1u16, 2] };
+    let unsized_ref: &Header<[u16]> = &header;
+    let len: usize = kani::any_where(|len| *len <= 3);
+    let new_ptr: *const Header<[u16]> =
+        ptr::from_raw_parts(unsized_ref as *const Header<[u16]> as *const (), len);
+    let new_ref = unsafe { &*new_ptr };
+    if len > 0 {
+        let _ = new_ref.data[len - 1];
+    }
+}
+
+#[kani::proof]
+fn check_vtable_of_other_type() {
+    let small: u8 = kani::any();
+    let large: u64 = kani::any();
+    let metadata = ptr::metadata(&large as &dyn Describe);
+    // The data pointer points to a `u8`, but the vtable is the one of `u64`.
+    let mismatched: *const dyn Describe = ptr::from_raw_parts(&small as *const u8, metadata);
+    let _ = unsafe { (*mismatched).describe() };
+}
@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `#[kani::proof(recursion_limit = N)]` bounds the depth of the recursive calls of a
+//! harness, and that the calls that reach the limit are reported apart from genuine failures.
+
+fn fib(n: u32) -> u32 {
+    if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+}
+
+fn is_even(n: u32) -> bool {
+    if n == 0 { true } else { is_odd(n - 1) }
+}
+
+fn is_odd(n: u32) -> bool {
+    if n == 0 { false } else { is_even(n - 1) }
+}
+
+/// The recursion never reaches the limit.
+#[kani::proof(recursion_limit = 6)]
+fn check_within_limit() {
+    let n: u32 = kani::any_where(|n| *n <= 5);
+    assert!(fib(n) <= 5);
+}
+
+/// Without the limit, symbolic execution would never terminate.
+#[kani::proof(recursion_limit = 3)]
+fn check_limit_reached() {
+    let n: u32 = kani::any();
+    assert!(is_even(n) != is_odd(n));
+}
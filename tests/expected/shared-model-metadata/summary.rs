@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z shared-model
+
+//! Check that the harnesses that share a goto model only list the foreign functions that they
+//! reach, with the number of places that call them from the code that they reach.
+
+extern "C" {
+    fn ffi_checksum(data: *const u8, len: usize) -> u32;
+    fn ffi_log(level: i32);
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    if data.is_empty() { 0 } else { unsafe { ffi_checksum(data.as_ptr(), data.len()) } }
+}
+
+#[kani::proof]
+fn check_checksum() {
+    let data: [u8; 2] = kani::any();
+    let first = checksum(&data[..kani::any_where(|len: &usize| *len <= 2)]);
+    let second = unsafe { ffi_checksum(data.as_ptr(), 1) };
+    assert!(first == 0 || second > 0);
+}
+
+#[kani::proof]
+fn check_log() {
+    let enabled: bool = kani::any_where(|enabled| !enabled);
+    if enabled {
+        unsafe { ffi_log(1) };
+    }
+}
+
+#[kani::proof]
+fn check_pure() {
+    assert_eq!(checksum(&[]), 0);
+}
@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z shared-model
+
+//! Check that the harnesses that share a goto model are each verified from their own entry point,
+//! i.e., a failure that is only reachable from one of them isn't reported for the others, and that
+//! they keep the attributes that only affect verification, such as their unwind bound.
+
+fn checked_double(x: u8) -> u8 {
+    x.checked_mul(2).unwrap()
+}
+
+fn halve(x: u8) -> u8 {
+    x / 2
+}
+
+#[kani::proof]
+fn check_halve() {
+    let x: u8 = kani::any();
+    assert!(halve(x) <= x, "halving doesn't increase");
+}
+
+#[kani::proof]
+fn check_double_small() {
+    let x: u8 = kani::any();
+    kani::assume(x < 128);
+    assert!(checked_double(halve(x)) <= x, "doubling half of a value");
+}
+
+#[kani::proof]
+fn check_double_any() {
+    let x: u8 = kani::any();
+    let _ = checked_double(x);
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_sum() {
+    let mut sum: u8 = 0;
+    for i in 0..2u8 {
+        sum += halve(i);
+    }
+    assert!(sum == 0, "sum of halves");
+}
@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that a harness annotated with `#[kani::should_panic(expected = "...")]` only succeeds if
+//! every panic that it encounters has the expected message.
+
+fn checked_div(num: u32, den: u32) -> u32 {
+    if den == 0 {
+        panic!("attempt to divide by zero");
+    }
+    num / den
+}
+
+fn lookup(table: &[u32; 4], index: usize) -> u32 {
+    if index >= table.len() {
+        panic!("index is out of the table");
+    }
+    table[index]
+}
+
+#[kani::proof]
+#[kani::should_panic(expected = "divide by zero")]
+fn check_expected_message() {
+    let num: u32 = kani::any();
+    checked_div(num, 0);
+}
+
+#[kani::proof]
+#[kani::should_panic(expected = "divide by zero")]
+fn check_unexpected_message() {
+    let index: usize = kani::any();
+    let den = lookup(&[1, 2, 0, 4], index);
+    checked_div(10, den);
+}
+
+#[kani::proof]
+#[kani::should_panic(expected = "is too large")]
+fn check_formatted_message() {
+    let value: u8 = kani::any();
+    if value > 100 {
+        panic!("{} is too large", value);
+    }
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 #![feature(rustc_private)]
 #![feature(c_str_literals)]
-//! FIXME: <https://github.com/rust-lang/rust/issues/113333>
+//! Check that Kani can verify code that uses C string literals.
 extern crate libc;
 use libc::c_char;
 
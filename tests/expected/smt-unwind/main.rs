@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that an unwinding assertion that fails with an SMT solver is reported for its loop,
+//! with the location of the loop.
+
+fn count_to(n: u8) -> u8 {
+    let mut i = 0;
+    while i < n {
+        i += 1;
+    }
+    i
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+#[kani::solver(smt = "z3")]
+fn check_small_loop() {
+    let n: u8 = kani::any_where(|n| *n < 3);
+    assert_eq!(count_to(n), n);
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+#[kani::solver(smt = "z3")]
+fn check_large_loop() {
+    let n: u8 = kani::any();
+    assert_eq!(count_to(n), n);
+}
@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --emit goto
+
+//! Check that Kani rejects the options that work on the model of the whole harness for a harness
+//! that is verified one case at a time.
+
+#[derive(kani::Arbitrary, Clone, Copy)]
+enum Sign {
+    Negative,
+    Positive,
+}
+
+#[kani::proof(split_on = "sign")]
+fn check_sign(sign: Sign, x: i8) {
+    let _ = match sign {
+        Sign::Negative => x.wrapping_neg(),
+        Sign::Positive => x,
+    };
+}
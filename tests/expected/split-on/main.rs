@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that a harness split on an enum argument is verified one variant at a time, and that
+//! its result is the merge of the results of its cases.
+
+#[derive(kani::Arbitrary, Clone, Copy)]
+enum Op {
+    Add,
+    Sub,
+    Div,
+}
+
+fn apply(op: Op, x: u8, y: u8) -> u8 {
+    match op {
+        Op::Add => x.wrapping_add(y),
+        Op::Sub => x.wrapping_sub(y),
+        Op::Div => x / y,
+    }
+}
+
+#[kani::proof(split_on = "op")]
+fn check_apply(op: Op, x: u8, y: u8) {
+    kani::cover!(matches!(op, Op::Sub), "sub is reachable");
+    let _ = apply(op, x, y);
+}
+
+#[kani::proof(split_on = "op")]
+fn check_apply_nonzero(op: Op, x: u8, y: u8) {
+    kani::assume(y != 0);
+    let _ = apply(op, x, y);
+}
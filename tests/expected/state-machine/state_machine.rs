@@ -0,0 +1,62 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::state_machine!` generates a harness that checks the invariant after every
+//! sequence of operations, and reports the operation that breaks it.
+
+/// A sliding window of sent and acknowledged messages.
+struct Window {
+    sent: u32,
+    acked: u32,
+}
+
+impl Window {
+    const SIZE: u32 = 4;
+
+    fn new() -> Self {
+        Window { sent: 0, acked: 0 }
+    }
+
+    fn send(&mut self) {
+        if self.sent - self.acked < Self::SIZE {
+            self.sent += 1;
+        }
+    }
+
+    fn ack(&mut self, seq: u32) {
+        if self.acked < seq && seq <= self.sent {
+            self.acked = seq;
+        }
+    }
+
+    /// Bug: acknowledges a message that may not have been sent.
+    fn ack_unchecked(&mut self, seq: u32) {
+        if self.acked < seq {
+            self.acked = seq;
+        }
+    }
+}
+
+kani::state_machine! {
+    name = check_window,
+    init = Window::new(),
+    steps = 5,
+    invariant = |window: &Window| {
+        window.acked <= window.sent && window.sent - window.acked <= Window::SIZE
+    },
+    operations = {
+        send = |window: &mut Window| window.send(),
+        ack = |window: &mut Window, seq: u32| window.ack(seq),
+    },
+}
+
+kani::state_machine! {
+    name = check_window_unchecked_ack,
+    init = Window::new(),
+    steps = 2,
+    invariant = |window: &Window| window.acked <= window.sent,
+    operations = {
+        send = |window: &mut Window| window.send(),
+        ack = |window: &mut Window, seq: u32| window.ack_unchecked(seq),
+    },
+}
@@ -0,0 +1,61 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z truncation-checks
+//! Check that `as` casts that may lose information fail if the value doesn't fit in the target
+//! type, unless they are annotated with `#[kani::allow_truncation]`.
+
+#![feature(stmt_expr_attributes)]
+#![feature(proc_macro_hygiene)]
+
+#[kani::proof]
+fn check_narrowing_in_range() {
+    let value: u32 = kani::any_where(|v| *v <= 255);
+    assert_eq!(value as u8 as u32, value);
+}
+
+#[kani::proof]
+fn check_narrowing_fails() {
+    let value: u32 = kani::any();
+    let _byte = value as u8;
+}
+
+#[kani::proof]
+fn check_sign_change_fails() {
+    let value: i8 = kani::any();
+    let _wide = value as u64;
+}
+
+#[kani::proof]
+fn check_widening() {
+    let value: u8 = kani::any();
+    let _signed = value as i16;
+    let _unsigned = value as usize;
+}
+
+#[kani::proof]
+fn check_char_fails() {
+    let c: char = kani::any();
+    let _byte = c as u8;
+}
+
+#[kani::proof]
+fn check_float_in_range() {
+    let value: f32 = kani::any_where(|v: &f32| *v > -1.0 && *v < 256.0);
+    let _byte = value as u8;
+}
+
+#[kani::proof]
+fn check_float_nan_fails() {
+    let value = f64::NAN;
+    let _int = value as i32;
+}
+
+#[kani::proof]
+fn check_allowed() {
+    let value: u64 = kani::any();
+    #[kani::allow_truncation]
+    let low = value as u32;
+    let high = #[kani::allow_truncation]
+    ((value >> 32) as u32);
+    assert_eq!(((high as u64) << 32) | low as u64, value);
+}
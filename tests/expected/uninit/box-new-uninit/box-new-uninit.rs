@@ -0,0 +1,51 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z uninit-checks
+
+//! Checks that the heap memory of `Box::new_uninit` is tracked as uninitialized until it's
+//! written.
+
+use std::mem::MaybeUninit;
+
+#[kani::proof]
+fn write_then_assume_init_should_pass() {
+    let mut boxed = Box::<u32>::new_uninit();
+    boxed.write(5);
+    let boxed = unsafe { boxed.assume_init() };
+    assert_eq!(*boxed, 5);
+}
+
+#[kani::proof]
+fn assume_init_should_fail() {
+    let boxed: Box<MaybeUninit<u32>> = Box::new_uninit();
+    let boxed = unsafe { boxed.assume_init() };
+    let _ = *boxed; // ~ERROR: reading uninitialized memory is UB.
+}
+
+#[kani::proof]
+fn partially_initialized_slice_should_fail() {
+    let mut boxed = Box::<[u8]>::new_uninit_slice(4);
+    boxed[0].write(1);
+    boxed[1].write(2);
+    let boxed = unsafe { boxed.assume_init() };
+    let _ = boxed[2]; // ~ERROR: reading uninitialized memory is UB.
+}
+
+#[kani::proof]
+fn realloc_should_fail() {
+    let mut v: Vec<u8> = Vec::with_capacity(1);
+    v.push(1);
+    v.reserve(8);
+    assert_eq!(v[0], 1);
+    let _ = unsafe { *v.as_ptr().add(1) }; // ~ERROR: reading uninitialized memory is UB.
+}
+
+#[kani::proof]
+fn realloc_shrink_should_pass() {
+    let mut v: Vec<u8> = Vec::with_capacity(8);
+    v.push(1);
+    v.push(2);
+    v.shrink_to_fit();
+    assert_eq!(v[0], 1);
+    assert_eq!(v[1], 2);
+}
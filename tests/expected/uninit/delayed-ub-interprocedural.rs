@@ -0,0 +1,51 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z ghost-state -Z uninit-checks
+
+//! Checks that Kani catches delayed UB caused by writes that happen inside helper functions.
+
+/// Helper that writes through a pointer of different padding that was casted inside of it.
+unsafe fn write_padded(ptr: *mut u128) {
+    let padded = ptr as *mut (u8, u32, u64);
+    unsafe { *padded = (4, 4, 4) };
+}
+
+/// Helper that passes the pointer down a few times before writing through it.
+unsafe fn write_padded_recursive(ptr: *mut u128, depth: u8) {
+    if depth == 0 {
+        unsafe { write_padded(ptr) };
+    } else {
+        unsafe { write_padded_recursive(ptr, depth - 1) };
+    }
+}
+
+/// Helper that copies into a pointer of different padding that was transmuted inside of it.
+unsafe fn copy_padded(dst: *mut u128, src: *const (u8, u32, u64)) {
+    let dst: *mut (u8, u32, u64) = unsafe { std::mem::transmute(dst) };
+    unsafe { std::ptr::copy_nonoverlapping(src, dst, 1) };
+}
+
+/// Delayed UB via a casted pointer write inside of a helper function.
+#[kani::proof]
+fn delayed_ub_in_callee() {
+    let mut value: u128 = 0;
+    unsafe { write_padded(&mut value) };
+    assert!(value > 0); // UB: This reads a padding value!
+}
+
+/// Delayed UB via a casted pointer write inside of a recursive helper function.
+#[kani::proof]
+fn delayed_ub_in_recursive_callee() {
+    let mut value: u128 = 0;
+    unsafe { write_padded_recursive(&mut value, 2) };
+    assert!(value > 0); // UB: This reads a padding value!
+}
+
+/// Delayed UB via a copy into a transmuted pointer inside of a helper function.
+#[kani::proof]
+fn delayed_ub_copy_in_callee() {
+    let mut value: u128 = 0;
+    let src = (4u8, 4u32, 4u64);
+    unsafe { copy_padded(&mut value, &src) };
+    assert!(value > 0); // UB: This reads a padding value!
+}
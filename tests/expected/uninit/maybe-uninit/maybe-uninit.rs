@@ -0,0 +1,53 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z uninit-checks
+
+//! Checks that the initialization state of `MaybeUninit` is tracked through its API.
+
+use std::mem::MaybeUninit;
+
+#[kani::proof]
+fn write_then_assume_init_ref_should_pass() {
+    let mut value = MaybeUninit::<u32>::uninit();
+    value.write(5);
+    assert_eq!(unsafe { *value.assume_init_ref() }, 5);
+}
+
+#[kani::proof]
+fn assume_init_ref_should_fail() {
+    let value = MaybeUninit::<u32>::uninit();
+    let _ = unsafe { *value.assume_init_ref() }; // ~ERROR: reading uninitialized memory is UB.
+}
+
+/// Writing to some elements of an array only initializes these elements.
+#[kani::proof]
+fn partially_initialized_array_should_pass() {
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 4];
+    buffer[0].write(1);
+    buffer[1].write(2);
+    let init = unsafe { &*(&buffer[..2] as *const [MaybeUninit<u8>] as *const [u8]) };
+    assert_eq!(init, [1, 2]);
+}
+
+#[kani::proof]
+fn partially_initialized_array_should_fail() {
+    let mut buffer = [MaybeUninit::<u8>::uninit(); 4];
+    buffer[0].write(1);
+    buffer[1].write(2);
+    let _ = unsafe { buffer[2].assume_init() }; // ~ERROR: reading uninitialized memory is UB.
+}
+
+/// Harnesses can mark memory as initialized when the code that writes it is not analyzed.
+#[kani::proof]
+fn assume_init_slice_should_pass() {
+    let buffer = [MaybeUninit::<u16>::uninit(); 4];
+    kani::mem::assume_init_slice(&buffer[1..3]);
+    let _ = unsafe { (buffer[1].assume_init(), buffer[2].assume_init()) };
+}
+
+#[kani::proof]
+fn assume_init_range_should_fail() {
+    let buffer = [MaybeUninit::<u16>::uninit(); 4];
+    kani::mem::assume_init_range(buffer.as_ptr() as *const u16, 2);
+    let _ = unsafe { buffer[2].assume_init() }; // ~ERROR: reading uninitialized memory is UB.
+}
@@ -0,0 +1,64 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `#[kani::unwind]` bounds the labeled loops of a harness with the unwind value of
+//! their label, which takes precedence over the unwind value of the harness, and only applies to
+//! the loop with that label.
+
+#![allow(unused_labels)]
+
+#[kani::proof]
+#[kani::unwind(2, inner = 6, closure = 4)]
+fn check_labeled_bounds() {
+    let mut sum = 0;
+    'outer: for _ in 0..1 {
+        let mut col = 0;
+        'inner: while col < 5 {
+            sum += col;
+            col += 1;
+        }
+    }
+    assert_eq!(sum, 10);
+
+    let count = || {
+        let mut n = 0;
+        'closure: loop {
+            if n == 3 {
+                break 'closure;
+            }
+            n += 1;
+        }
+        n
+    };
+    assert_eq!(count(), 3);
+}
+
+#[kani::proof]
+#[kani::unwind(outer = 2, inner = 5)]
+fn check_inner_too_small() {
+    let mut sum = 0;
+    'outer: for _ in 0..1 {
+        let mut col = 0;
+        'inner: while col < 5 {
+            sum += col;
+            col += 1;
+        }
+    }
+    assert_eq!(sum, 10);
+}
+
+/// The unwind value of `'outer` would be too small for `'inner`, which is bounded by the unwind
+/// value of the harness instead.
+#[kani::proof]
+#[kani::unwind(7, outer = 2)]
+fn check_outer_bound_only_outer() {
+    let mut sum = 0;
+    'outer: for _ in 0..1 {
+        let mut col = 0;
+        'inner: while col < 5 {
+            sum += col;
+            col += 1;
+        }
+    }
+    assert_eq!(sum, 10);
+}
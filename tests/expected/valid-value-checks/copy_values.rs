@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z valid-value-checks
+//! Check that Kani checks the validity of every value copied by `copy_nonoverlapping` and `copy`.
+
+use std::ptr;
+
+#[kani::proof]
+pub fn check_copy_valid_bools() {
+    let bytes: [u8; 3] = [kani::any_where(|b: &u8| *b <= 1), 0, 1];
+    let mut bools = [false; 3];
+    unsafe { ptr::copy_nonoverlapping(bytes.as_ptr() as *const bool, bools.as_mut_ptr(), 3) };
+    assert!(bools[2]);
+}
+
+#[kani::proof]
+pub fn check_copy_invalid_bool() {
+    let mut bytes: [u8; 3] = [0, 1, 0];
+    let idx: usize = kani::any_where(|i: &usize| *i < 3);
+    bytes[idx] = 2;
+    let mut bools = [false; 3];
+    // Should fail given that one of the copied bytes is not a valid `bool`.
+    unsafe { ptr::copy_nonoverlapping(bytes.as_ptr() as *const bool, bools.as_mut_ptr(), 3) };
+}
+
+#[kani::proof]
+pub fn check_copy_outside_count() {
+    let bytes: [u8; 2] = [1, kani::any()];
+    let mut bools = [false; 2];
+    // Only the first byte is copied.
+    unsafe { ptr::copy(bytes.as_ptr() as *const bool, bools.as_mut_ptr(), 1) };
+    assert!(bools[0]);
+}
+
+#[kani::proof]
+pub fn check_copy_invalid_char() {
+    let code: u32 = kani::any();
+    let mut c = 'a';
+    // Should fail given that `code` may not be a valid `char`.
+    unsafe { ptr::copy(&code as *const u32 as *const char, &mut c, 1) };
+}
@@ -73,11 +73,9 @@ pub fn check_invalid_transmute_copy() {
 }
 
 /// This code does not trigger UB, and verification should succeed.
-///
-/// FIX-ME: This is not supported today, and we fail due to unsupported check.
 #[kani::proof]
 pub fn check_copy_nonoverlap() {
-    let stars = kani::any_where(|s: &u8| *s == 0 || *s > 5);
+    let stars = kani::any_where(|s: &u8| *s >= 1 && *s <= 5);
     let mut rating: Rating = kani::any();
     unsafe { ptr::copy_nonoverlapping(&stars as *const _ as *const Rating, &mut rating, 1) };
 }
@@ -0,0 +1,86 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z valid-value-checks
+//! Check that Kani can check the validity of enums with a niche encoding, such as `Option<T>`.
+
+use std::mem::transmute;
+use std::num::NonZeroU8;
+
+/// The niche of `Option<Pair>` is in `flag`, so `count` is only valid if `flag` is not the niche.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Pair {
+    flag: bool,
+    count: NonZeroU8,
+}
+
+#[kani::proof]
+pub fn check_option_bool_valid() {
+    let byte: u8 = kani::any_where(|b: &u8| *b <= 2);
+    let opt: Option<bool> = unsafe { transmute(byte) };
+    assert_eq!(opt.is_none(), byte == 2);
+}
+
+#[kani::proof]
+pub fn check_option_bool_invalid() {
+    let byte: u8 = kani::any();
+    // Should fail given that only 0, 1 and 2 are valid.
+    let _opt: Option<bool> = unsafe { transmute(byte) };
+}
+
+#[kani::proof]
+pub fn check_option_char_none() {
+    let opt: Option<char> = unsafe { transmute(0x110000u32) };
+    assert!(opt.is_none());
+}
+
+#[kani::proof]
+pub fn check_option_char_surrogate() {
+    let code: u32 = kani::any_where(|c: &u32| (0xD800..=0xDFFF).contains(c));
+    // Should fail given that surrogates are neither a valid `char` nor the niche.
+    let _opt: Option<char> = unsafe { transmute(code) };
+}
+
+#[kani::proof]
+pub fn check_option_pair_none() {
+    // Any value of `count` is valid for `None`.
+    let bytes: [u8; 2] = [2, kani::any()];
+    let opt: Option<Pair> = unsafe { transmute(bytes) };
+    assert!(opt.is_none());
+}
+
+#[kani::proof]
+pub fn check_option_pair_invalid_count() {
+    let flag: u8 = kani::any_where(|f: &u8| *f <= 1);
+    // Should fail given that `count` must not be zero for `Some`.
+    let _opt: Option<Pair> = unsafe { transmute([flag, 0u8]) };
+}
+
+#[kani::proof]
+pub fn check_nested_option() {
+    let byte: u8 = kani::any_where(|b: &u8| *b <= 3);
+    let opt: Option<Option<bool>> = unsafe { transmute(byte) };
+    assert_eq!(opt.is_none(), byte == 3);
+}
+
+/// The niche of `Gap` covers all variants, including the untagged variant `B`, whose niche value
+/// is reserved but doesn't encode any variant.
+#[derive(Clone, Copy)]
+pub enum Gap {
+    A,
+    B(bool),
+    C,
+}
+
+#[kani::proof]
+pub fn check_gap_valid() {
+    let byte: u8 = kani::any_where(|b: &u8| *b <= 4 && *b != 3);
+    let gap: Gap = unsafe { transmute(byte) };
+    assert_eq!(matches!(gap, Gap::B(_)), byte <= 1);
+}
+
+#[kani::proof]
+pub fn check_gap_untagged_niche() {
+    // Should fail given that 3 is the niche value of the untagged variant.
+    let _gap: Gap = unsafe { transmute(3u8) };
+}
@@ -18,3 +18,12 @@ pub fn check_valid_write() {
     let ptr = &mut val as *mut _;
     unsafe { std::intrinsics::write_bytes(ptr, kani::any(), 1) };
 }
+
+#[kani::proof]
+pub fn check_empty_write() {
+    let mut val = true;
+    let ptr = &mut val as *mut bool;
+    // Nothing is written, so any byte is fine.
+    unsafe { std::intrinsics::write_bytes(ptr, kani::any(), 0) };
+    assert!(val);
+}
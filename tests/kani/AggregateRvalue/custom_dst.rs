@@ -0,0 +1,78 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Test that custom DSTs can be built with `ptr::from_raw_parts` and taken apart with
+//! `ptr::metadata`, for thin pointers, slices, tuples and trait objects.
+
+#![feature(ptr_metadata)]
+
+use std::ptr;
+
+/// A DST with a header, like the ones that arena and string interning crates build.
+struct Header<T: ?Sized> {
+    len: usize,
+    data: T,
+}
+
+trait Area {
+    fn area(&self) -> u32;
+}
+
+struct Square(u32);
+
+impl Area for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+#[kani::proof]
+fn check_thin_metadata() {
+    let value: u8 = kani::any();
+    let ptr = &value as *const u8;
+    let () = ptr::metadata(ptr);
+    let new_ptr: *const u8 = ptr::from_raw_parts(ptr as *const (), ());
+    assert_eq!(unsafe { *new_ptr }, value);
+}
+
+#[kani::proof]
+fn check_header_slice() {
+    let header = Header { len: 3, data: [1u16, 2, 3] };
+    let unsized_ref: &Header<[u16]> = &header;
+    let len = ptr::metadata(unsized_ref);
+    assert_eq!(len, unsized_ref.len);
+
+    let new_len: usize = kani::any_where(|new_len| *new_len <= len);
+    let new_ptr: *const Header<[u16]> =
+        ptr::from_raw_parts(unsized_ref as *const Header<[u16]> as *const (), new_len);
+    let new_ref = unsafe { &*new_ptr };
+    assert_eq!(ptr::metadata(new_ptr), new_len);
+    assert_eq!(new_ref.data.len(), new_len);
+    if new_len > 0 {
+        assert_eq!(new_ref.data[new_len - 1], new_len as u16);
+    }
+}
+
+#[kani::proof]
+fn check_tuple_slice() {
+    let tuple: (u8, [u32; 2]) = (kani::any(), kani::any());
+    // Tuples can't be unsized by a coercion, so this is the only way to build a pointer to them.
+    let unsized_ptr: *const (u8, [u32]) = ptr::from_raw_parts(&tuple as *const _ as *const (), 2);
+    assert_eq!(ptr::metadata(unsized_ptr), 2);
+    let new_ptr: *const (u8, [u32]) = ptr::from_raw_parts(unsized_ptr as *const (), 1);
+    let new_ref = unsafe { &*new_ptr };
+    assert_eq!(new_ref.0, tuple.0);
+    assert_eq!(new_ref.1, tuple.1[..1]);
+}
+
+#[kani::proof]
+fn check_header_dyn() {
+    let header = Header { len: 1, data: Square(kani::any_where(|side| *side < 100)) };
+    let unsized_ref: &Header<dyn Area> = &header;
+    let metadata = ptr::metadata(unsized_ref);
+    assert_eq!(metadata.size_of(), size_of::<Square>());
+    let new_ptr: *const Header<dyn Area> =
+        ptr::from_raw_parts(unsized_ref as *const Header<dyn Area> as *const (), metadata);
+    let new_ref = unsafe { &*new_ptr };
+    assert_eq!(new_ref.len, 1);
+    assert_eq!(new_ref.data.area(), header.data.area());
+}
@@ -0,0 +1,30 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `kani::any_usize_index` creates indices that are always in bounds.
+
+#[kani::proof]
+fn check_array_index() {
+    let array: [u32; 4] = kani::any();
+    let idx = kani::any_usize_index(&array);
+    assert!(idx < 4);
+    let _ = array[idx];
+}
+
+#[kani::proof]
+fn check_every_index_reachable() {
+    let array = [10u8, 20, 30];
+    let idx = kani::any_usize_index(&array);
+    kani::cover!(idx == 0);
+    kani::cover!(idx == 2);
+    assert_eq!(array[idx], 10 * (idx as u8 + 1));
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_nondet_slice_index() {
+    let array: [u8; 3] = kani::any();
+    let slice = kani::slice::any_slice_of_array(&array);
+    kani::assume(!slice.is_empty());
+    let idx = kani::any_usize_index(slice);
+    assert_eq!(slice[idx], slice.iter().copied().nth(idx).unwrap());
+}
@@ -0,0 +1,77 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `kani::any_between` and the `#[between(..)]` attribute of the `Arbitrary` derive
+//! generate every value of the range, and only those values.
+
+use kani::ArbitraryInt;
+
+macro_rules! check_between {
+    ( $name: ident, $type: ty, $lo: expr, $hi: expr ) => {
+        #[kani::proof]
+        fn $name() {
+            let value: $type = kani::any_between($lo, $hi);
+            assert!($lo <= value && value <= $hi);
+            kani::cover!(value == $lo);
+            kani::cover!(value == $hi);
+        }
+    };
+}
+
+// Ranges whose number of values is a power of two.
+check_between!(check_u8_power_of_two, u8, 16, 31);
+check_between!(check_i8_power_of_two, i8, -8, 7);
+check_between!(check_u8_full, u8, u8::MIN, u8::MAX);
+check_between!(check_i64_full, i64, i64::MIN, i64::MAX);
+
+// Other ranges.
+check_between!(check_u32, u32, 10, 20);
+check_between!(check_i32, i32, -100, 3);
+check_between!(check_i16_wide, i16, i16::MIN, 1);
+check_between!(check_usize_single, usize, 5, 5);
+check_between!(check_u128, u128, 1, u128::MAX - 1);
+
+#[kani::proof]
+fn check_symbolic_bounds() {
+    let lo: i8 = kani::any();
+    let hi: i8 = kani::any_where(|hi| *hi >= lo);
+    let value = i8::any_between(lo, hi);
+    assert!(lo <= value && value <= hi);
+}
+
+#[kani::proof]
+#[kani::should_panic]
+fn check_empty_range() {
+    let _ = kani::any_between(10u8, 9);
+}
+
+#[derive(kani::Arbitrary)]
+struct Date {
+    #[between(1, 12)]
+    month: u8,
+    #[between(1, 31)]
+    day: u8,
+    year: u16,
+}
+
+#[derive(kani::Arbitrary)]
+enum Time {
+    Hour(#[between(0, 23)] u8),
+    Minute {
+        #[between(0, 59)]
+        minute: u8,
+    },
+}
+
+#[kani::proof]
+fn check_derive_between() {
+    let date: Date = kani::any();
+    assert!(1 <= date.month && date.month <= 12);
+    assert!(1 <= date.day && date.day <= 31);
+    kani::cover!(date.year == u16::MAX);
+
+    match kani::any() {
+        Time::Hour(hour) => assert!(hour < 24),
+        Time::Minute { minute } => assert!(minute < 60),
+    }
+}
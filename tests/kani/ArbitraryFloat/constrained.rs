@@ -0,0 +1,45 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the floating-point generators only create values of the requested classes.
+
+use kani::{ArbitraryFloat, FloatOptions};
+
+#[kani::proof]
+fn check_finite() {
+    let x = kani::any_f32_finite();
+    assert!(x.is_finite());
+    kani::cover!(x.is_subnormal());
+    let y = kani::any_f64_finite();
+    assert!(y.is_finite());
+    kani::cover!(y == f64::MAX);
+}
+
+#[kani::proof]
+fn check_normal() {
+    let x = f32::any_float(FloatOptions::NORMAL);
+    assert!(x.is_normal() || x == 0.0);
+    kani::cover!(x == 0.0);
+    let y = f64::any_float(FloatOptions::NORMAL);
+    assert!(!y.is_subnormal() && !y.is_nan() && !y.is_infinite());
+}
+
+#[kani::proof]
+fn check_exclude_nan() {
+    let x = f64::any_float(FloatOptions::ANY.exclude_nan());
+    assert!(!x.is_nan());
+    kani::cover!(x == f64::INFINITY);
+    kani::cover!(x == f64::NEG_INFINITY);
+    let y = f32::any_float(FloatOptions::ANY.exclude_infinite());
+    assert!(!y.is_infinite());
+    kani::cover!(y.is_nan());
+}
+
+#[kani::proof]
+fn check_in_range() {
+    let x = kani::any_f32_in(-1.0..=1.0);
+    assert!((-1.0..=1.0).contains(&x));
+    kani::cover!(x == 1.0);
+    let y = kani::any_f64_in(0.5..);
+    assert!(y >= 0.5);
+    kani::cover!(y == f64::INFINITY);
+}
@@ -0,0 +1,48 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check C string literals and the `CStr` methods that Kani models.
+
+use std::ffi::{CStr, FromBytesWithNulError};
+
+#[kani::proof]
+fn check_literal() {
+    let s = c"kani";
+    assert_eq!(s.to_bytes_with_nul(), b"kani\0");
+    assert_eq!(s.count_bytes(), 4);
+    assert_eq!(s.to_str(), Ok("kani"));
+    assert!(c"".is_empty());
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_from_bytes_with_nul() {
+    let bytes: [u8; 4] = kani::any();
+    match CStr::from_bytes_with_nul(&bytes) {
+        Ok(s) => {
+            assert_eq!(bytes[3], 0);
+            assert!(!bytes[..3].contains(&0));
+            assert_eq!(s.to_bytes().len(), 3);
+        }
+        Err(FromBytesWithNulError::InteriorNul { position }) => {
+            assert!(position < 3);
+            assert_eq!(bytes[position], 0);
+            assert!(!bytes[..position].contains(&0));
+        }
+        Err(FromBytesWithNulError::NotNulTerminated) => assert!(!bytes.contains(&0)),
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_to_str() {
+    let bytes: [u8; 3] = [kani::any_where(|b: &u8| *b != 0), kani::any_where(|b: &u8| *b != 0), 0];
+    let s = CStr::from_bytes_with_nul(&bytes).unwrap();
+    match s.to_str() {
+        Ok(s) => assert_eq!(s.as_bytes(), &bytes[..2]),
+        Err(err) => assert!(err.valid_up_to() < 2 && !bytes[..2].is_ascii()),
+    }
+    if bytes[..2].is_ascii() {
+        assert!(s.to_str().is_ok());
+    }
+}
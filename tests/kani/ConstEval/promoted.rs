@@ -0,0 +1,57 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that inline `const` blocks and promoted temporaries are handled consistently, including
+//! when their content matches memory that can be mutated.
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static COUNTER: AtomicU32 = AtomicU32::new(0);
+static mut BUFFER: &mut [u8] = &mut [1, 2, 3];
+static mut OTHER_BUFFER: &mut [u8] = &mut [1, 2, 3];
+
+#[kani::proof]
+fn check_inline_const() {
+    let x = const { 10u32 * 3 };
+    let arr = const { [1u8, 2, 3, 4] };
+    let r: &[u8; 4] = &const { [1u8, 2, 3, 4] };
+    assert_eq!(x, 30);
+    assert_eq!(&arr, r);
+}
+
+#[kani::proof]
+fn check_nested_promoteds() {
+    let r: &&&u32 = &&&5;
+    let pair: &(&u32, &[u8]) = &(&7, &[1, 2]);
+    assert_eq!(***r, 5);
+    assert_eq!(*pair.0, 7);
+    assert_eq!(pair.1, &[1, 2]);
+}
+
+#[kani::proof]
+fn check_slices_of_promoteds() {
+    let slice: &[&u16] = &[&1, &2, &3];
+    let sub = &slice[1..];
+    assert_eq!(sub.len(), 2);
+    assert_eq!(*sub[0], 2);
+    let nested: &[&[u8]] = const { &[&[], &[1], &[1, 2]] };
+    assert_eq!(nested[2], &[1, 2]);
+}
+
+#[kani::proof]
+fn check_promoted_not_aliased_with_static() {
+    let zero: &u32 = &0;
+    COUNTER.fetch_add(1, Ordering::Relaxed);
+    assert_eq!(*zero, 0);
+    assert_eq!(*const { &0u32 }, 0);
+    assert_eq!(COUNTER.load(Ordering::Relaxed), 1);
+}
+
+#[kani::proof]
+fn check_nested_static_allocations_are_distinct() {
+    unsafe {
+        let buffer = &mut *&raw mut BUFFER;
+        buffer[0] = 10;
+        let other = &*&raw const OTHER_BUFFER;
+        assert_eq!(other[0], 1);
+        assert_eq!(buffer[0], 10);
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Checks that contract clauses can call trait methods on generic parameters, whether the bounds
+//! are declared by the function, by a where-clause, or by the enclosing `impl` block.
+// kani-flags: -Zfunction-contracts
+
+trait Validate {
+    fn is_valid(&self) -> bool;
+}
+
+#[derive(Copy, Clone, kani::Arbitrary)]
+struct Percent(u8);
+
+impl Validate for Percent {
+    fn is_valid(&self) -> bool {
+        self.0 <= 100
+    }
+}
+
+#[kani::requires(x.is_valid())]
+#[kani::ensures(|result: &bool| *result)]
+fn check_bound<T: Validate>(x: T) -> bool {
+    x.is_valid()
+}
+
+#[kani::requires(x.is_valid() && y.is_valid())]
+fn check_where<T, U>(x: &T, y: U) -> bool
+where
+    T: Validate,
+    U: Validate + Copy,
+{
+    x.is_valid() && y.is_valid()
+}
+
+struct Checker<T>(T);
+
+impl<T: Validate> Checker<T> {
+    #[kani::requires(x.is_valid())]
+    #[kani::ensures(|result: &bool| *result)]
+    fn check_impl_bound(x: T) -> bool {
+        x.is_valid()
+    }
+}
+
+#[kani::proof_for_contract(check_bound)]
+fn check_bound_harness() {
+    check_bound::<Percent>(kani::any());
+}
+
+#[kani::proof_for_contract(check_where)]
+fn check_where_harness() {
+    let x: Percent = kani::any();
+    assert!(check_where(&x, Percent(50)));
+}
+
+#[kani::proof_for_contract(Checker::check_impl_bound)]
+fn check_impl_bound_harness() {
+    Checker::<Percent>::check_impl_bound(kani::any());
+}
@@ -0,0 +1,86 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Checks the arithmetic intrinsics of `f16` and `f128` that Kani supports:
+//  * Exact results for some concrete cases.
+//  * Properties that hold for every argument of the operations that Kani encodes exactly.
+#![feature(f16, f128)]
+
+#[kani::proof]
+fn check_f16_rounding() {
+    assert!(2.5f16.ceil() == 3.0);
+    assert!(2.5f16.floor() == 2.0);
+    assert!((-2.5f16).trunc() == -2.0);
+    assert!(2.5f16.round() == 3.0);
+    assert!(2.5f16.round_ties_even() == 2.0);
+}
+
+#[kani::proof]
+fn check_f128_rounding() {
+    assert!(2.5f128.ceil() == 3.0);
+    assert!(2.5f128.floor() == 2.0);
+    assert!((-2.5f128).trunc() == -2.0);
+    assert!((-2.5f128).round() == -3.0);
+    assert!(3.5f128.round_ties_even() == 4.0);
+}
+
+#[kani::proof]
+fn check_f16_abs_copysign() {
+    let x: f16 = kani::any();
+    let y: f16 = kani::any();
+    kani::assume(!x.is_nan());
+    let abs = x.abs();
+    assert!(abs >= 0.0 && (abs == x || abs == -x));
+    let signed = x.copysign(y);
+    assert!(signed.abs() == abs);
+    assert_eq!(signed.is_sign_negative(), y.is_sign_negative());
+}
+
+#[kani::proof]
+fn check_f128_abs_copysign() {
+    let x: f128 = kani::any();
+    kani::assume(!x.is_nan());
+    let abs = x.abs();
+    assert!(abs >= 0.0 && (abs == x || abs == -x));
+    assert!((-0.0f128).abs().is_sign_positive());
+    assert!(1.5f128.copysign(-0.0) == -1.5);
+}
+
+#[kani::proof]
+fn check_min_max() {
+    let x: f16 = kani::any();
+    let y: f16 = kani::any();
+    kani::assume(!x.is_nan() && !y.is_nan());
+    assert!(x.max(y) >= x && x.max(y) >= y);
+    assert!(x.min(y) <= x && x.min(y) <= y);
+    assert!(f128::NAN.max(1.0) == 1.0);
+    assert!(2.0f128.min(f128::NAN) == 2.0);
+}
+
+#[kani::proof]
+fn check_sqrt() {
+    assert!(4.0f16.sqrt() == 2.0);
+    assert!((-1.0f16).sqrt().is_nan());
+    assert!((-0.0f16).sqrt().is_sign_negative());
+}
+
+/// The exact result, 1 + 2^-11 + 2^-31, is just above the midpoint between two `f16` values, so it
+/// rounds up. Rounding it to `f32` first would drop 2^-31, and the tie would round down to 1.
+#[kani::proof]
+fn check_f16_fma_rounds_once() {
+    let x = f16::from_bits(0x9001); // -(1 + 2^-10) * 2^-11
+    let y = f16::from_bits(0x3BFE); // 1 - 2^-10
+    let z = f16::from_bits(0x3C01); // 1 + 2^-10
+    assert_eq!(x.mul_add(y, z).to_bits(), 0x3C01);
+}
+
+#[kani::proof]
+fn check_comparisons_and_casts() {
+    let x: f16 = kani::any();
+    kani::assume(x.is_finite());
+    let wide = x as f128;
+    assert!(wide as f16 == x);
+    assert!((x < 1.0) == (wide < 1.0));
+    let small: u8 = kani::any();
+    assert!(small as f16 as u8 == small);
+}
@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `simd_cast` casts each lane like an `as` cast.
+#![feature(repr_simd, core_intrinsics)]
+use std::intrinsics::simd::simd_cast;
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct i64x2([i64; 2]);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct u8x2([u8; 2]);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct f32x2([f32; 2]);
+
+#[kani::proof]
+fn check_int_to_int() {
+    let (x0, x1): (i64, i64) = kani::any();
+    let res: u8x2 = unsafe { simd_cast(i64x2([x0, x1])) };
+    assert!(res == u8x2([x0 as u8, x1 as u8]));
+}
+
+#[kani::proof]
+fn check_int_to_float() {
+    let (x0, x1): (u8, u8) = kani::any();
+    let res: f32x2 = unsafe { simd_cast(u8x2([x0, x1])) };
+    assert!(res == f32x2([x0 as f32, x1 as f32]));
+}
+
+#[kani::proof]
+fn check_float_to_int() {
+    let (x0, x1): (f32, f32) = kani::any();
+    kani::assume(x0.is_finite() && x0 > -1.0 && x0 < 256.0);
+    kani::assume(x1.is_finite() && x1 > -1.0 && x1 < 256.0);
+    let res: u8x2 = unsafe { simd_cast(f32x2([x0, x1])) };
+    assert!(res == u8x2([x0 as u8, x1 as u8]));
+}
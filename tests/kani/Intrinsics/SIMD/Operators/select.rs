@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `simd_select` takes each lane from the first vector if the lane
+//! of the mask is set, and from the second vector otherwise.
+#![feature(repr_simd, core_intrinsics)]
+use std::intrinsics::simd::simd_select;
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct i64x2([i64; 2]);
+
+#[repr(simd)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+pub struct i8x2([i8; 2]);
+
+#[kani::proof]
+fn check_select() {
+    let (x0, x1, y0, y1): (i64, i64, i64, i64) = kani::any();
+    let (take0, take1): (bool, bool) = kani::any();
+    let lane = |take: bool| if take { -1 } else { 0 };
+    let mask = i8x2([lane(take0), lane(take1)]);
+    let res = unsafe { simd_select(mask, i64x2([x0, x1]), i64x2([y0, y1])) };
+    let expected = i64x2([if take0 { x0 } else { y0 }, if take1 { x1 } else { y1 }]);
+    assert!(res == expected);
+}
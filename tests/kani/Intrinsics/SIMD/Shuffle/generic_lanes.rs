@@ -0,0 +1,94 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `simd_shuffle` is supported when the number of lanes is a generic
+//! `const N: usize` parameter, with the indexes given either as a SIMD vector or
+//! as an array of `u32`, and that the `std::simd` swizzle APIs that are built on
+//! top of it work across lane widths.
+#![feature(repr_simd, core_intrinsics, portable_simd)]
+use std::intrinsics::simd::simd_shuffle;
+use std::simd::{LaneCount, Simd, SupportedLaneCount};
+
+#[repr(simd)]
+#[derive(Clone, Copy)]
+struct Vector<const N: usize>([u8; N]);
+
+#[repr(simd)]
+struct SimdShuffleIdx<const N: usize>([u32; N]);
+
+/// Selects the elements of `x` in reverse order, then the elements of `y` in reverse order.
+fn reverse_each<const N: usize>(x: Vector<N>, y: Vector<N>) -> [u8; N] {
+    let rev_x: Vector<N> = unsafe {
+        simd_shuffle(
+            x,
+            y,
+            const {
+                let mut idx = [0; N];
+                let mut i = 0;
+                while i < N {
+                    idx[i] = (N - 1 - i) as u32;
+                    i += 1;
+                }
+                SimdShuffleIdx(idx)
+            },
+        )
+    };
+    let rev_y: Vector<N> = unsafe {
+        simd_shuffle(
+            x,
+            y,
+            const {
+                let mut idx = [0; N];
+                let mut i = 0;
+                while i < N {
+                    idx[i] = (2 * N - 1 - i) as u32;
+                    i += 1;
+                }
+                idx
+            },
+        )
+    };
+    assert_eq!(rev_x.0[0], x.0[N - 1]);
+    rev_y.0
+}
+
+fn check_reverse_each<const N: usize>() {
+    let x = Vector::<N>(kani::any());
+    let y = Vector::<N>(kani::any());
+    let rev_y = reverse_each(x, y);
+    for i in 0..N {
+        assert_eq!(rev_y[i], y.0[N - 1 - i]);
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(9)]
+fn check_generic_lanes() {
+    check_reverse_each::<2>();
+    check_reverse_each::<4>();
+    check_reverse_each::<8>();
+}
+
+fn check_swizzle<const N: usize>()
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let array: [u32; N] = kani::any();
+    let vector = Simd::from_array(array);
+    let reversed = vector.reverse().to_array();
+    let rotated = vector.rotate_elements_left::<1>().to_array();
+    for i in 0..N {
+        assert_eq!(reversed[i], array[N - 1 - i]);
+        assert_eq!(rotated[i], array[(i + 1) % N]);
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(17)]
+fn check_std_swizzle() {
+    check_swizzle::<1>();
+    check_swizzle::<2>();
+    check_swizzle::<4>();
+    check_swizzle::<8>();
+    check_swizzle::<16>();
+}
@@ -0,0 +1,66 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check the `can_read` and `can_write` memory predicates and their byte, slice and str variants.
+
+extern crate kani;
+
+use kani::mem::{
+    can_read, can_read_bytes, can_read_slice, can_read_str, can_write, can_write_bytes,
+    can_write_slice, can_write_str,
+};
+
+#[kani::proof]
+fn check_bytes() {
+    let mut buffer = [0u8; 8];
+    let len: usize = kani::any();
+    let ptr = buffer.as_mut_ptr();
+    assert_eq!(can_read_bytes(ptr, len), len <= 8);
+    assert_eq!(can_write_bytes(ptr, len), len <= 8);
+    let offset: usize = kani::any_where(|offset| *offset <= 8);
+    let end = unsafe { ptr.add(offset) };
+    assert_eq!(can_read_bytes(end, len), len <= 8 - offset);
+}
+
+#[kani::proof]
+fn check_null() {
+    let ptr = std::ptr::null_mut::<u32>();
+    assert!(!can_read(ptr));
+    assert!(!can_write(ptr));
+    assert!(!can_read_bytes(ptr, 1));
+    assert!(can_read_bytes(ptr, 0));
+    assert!(can_write_slice(ptr, 0));
+}
+
+#[kani::proof]
+fn check_slice() {
+    let mut buffer = [0u32; 4];
+    let len: usize = kani::any();
+    let ptr = buffer.as_mut_ptr();
+    assert_eq!(can_read_slice(ptr, len), len <= 4);
+    assert_eq!(can_write_slice(ptr, len), len <= 4);
+    // The size of the slice overflows.
+    assert!(!can_read_slice(ptr, usize::MAX));
+    // Pointers to `u32` must be aligned.
+    let unaligned = ptr.cast::<u8>().wrapping_add(1).cast::<u32>();
+    assert!(!can_read_slice(unaligned, 1));
+    assert!(can_read_bytes(unaligned, 4));
+}
+
+#[kani::proof]
+fn check_str() {
+    let mut text = String::from("kani");
+    assert!(can_read_str(text.as_str()));
+    assert!(can_write_str(text.as_mut_str()));
+    let ptr = text.as_mut_ptr();
+    assert!(can_read_str(std::ptr::slice_from_raw_parts(ptr, 4) as *const str));
+    assert!(!can_read_str(std::ptr::slice_from_raw_parts(ptr, 5) as *const str));
+}
+
+#[kani::proof]
+fn check_value() {
+    let mut value = (0u16, [0u8; 3]);
+    assert!(can_read(&value));
+    assert!(can_write(&mut value));
+    let slice: &mut [u8] = &mut value.1;
+    assert!(can_write(slice as *mut [u8]));
+}
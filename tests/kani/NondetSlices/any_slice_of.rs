@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// This test uses `kani::slice::any_slice_of` to verify functions that take a slice.
+
+fn first_or_zero(slice: &[u8]) -> u8 {
+    slice.first().copied().unwrap_or(0)
+}
+
+fn zero(slice: &mut [u8]) {
+    for x in slice {
+        *x = 0;
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_any_slice_of() {
+    let slice = kani::slice::any_slice_of::<u8, 3>();
+    assert!(slice.len() <= 3);
+    let first = first_or_zero(slice);
+    assert!(slice.is_empty() || first == slice[0]);
+    kani::cover!(slice.len() == 3);
+    kani::cover!(slice.is_empty());
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_any_slice_of_mut() {
+    let slice = kani::slice::any_slice_of_mut::<u8, 3>();
+    zero(slice);
+    assert!(slice.iter().all(|x| *x == 0));
+}
@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// compile-flags: -Zmir-keep-place-mention
+
+//! Check that Kani can verify code with place mentions, which rustc keeps in runtime MIR with
+//! `-Z mir-keep-place-mention`, e.g., for the scrutinee of `let _ = expr;`.
+
+struct Pair {
+    first: u8,
+    second: [u16; 4],
+}
+
+#[kani::proof]
+fn check_let_underscore() {
+    let x: u32 = kani::any();
+    let _ = x;
+    let _ = x.wrapping_add(1);
+    assert_eq!(x, x);
+}
+
+#[kani::proof]
+fn check_projections() {
+    let pair = Pair { first: kani::any(), second: kani::any() };
+    let idx: usize = kani::any_where(|idx| *idx < 4);
+    let _ = pair.first;
+    let _ = pair.second[idx];
+    let reference = &pair;
+    let _ = *reference;
+    let _ = reference.second;
+    assert_eq!(reference.first, pair.first);
+}
+
+#[kani::proof]
+fn check_wildcard_match() {
+    let value: Option<u8> = kani::any();
+    match value {
+        _ => {}
+    }
+    let ptr = &value as *const Option<u8>;
+    // SAFETY: `ptr` points to `value`, which is live.
+    let _ = unsafe { *ptr };
+    assert_eq!(unsafe { *ptr }, value);
+}
@@ -0,0 +1,53 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that dereferencing `Rc` and `Arc` gives access to the value they point to, for sized
+//! and unsized values.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+trait Area {
+    fn area(&self) -> u32;
+}
+
+struct Square(u16);
+
+impl Area for Square {
+    fn area(&self) -> u32 {
+        self.0 as u32 * self.0 as u32
+    }
+}
+
+#[kani::proof]
+fn check_rc_deref() {
+    let val: u64 = kani::any();
+    let rc = Rc::new(val);
+    let other = rc.clone();
+    assert_eq!(*rc, val);
+    assert_eq!(*other, val);
+    assert!(std::ptr::eq(&*rc, &*other));
+}
+
+#[kani::proof]
+fn check_arc_deref() {
+    let val: (u8, u32) = kani::any();
+    let arc = Arc::new(val);
+    assert_eq!(arc.0, val.0);
+    assert_eq!(arc.1, val.1);
+}
+
+#[kani::proof]
+fn check_unsized_deref() {
+    let bytes: [u8; 3] = kani::any();
+    let slice: Arc<[u8]> = Arc::new(bytes);
+    assert_eq!(slice.len(), 3);
+    assert_eq!(slice[2], bytes[2]);
+
+    let text: Rc<str> = Rc::from("kani");
+    assert_eq!(&*text, "kani");
+
+    let side: u16 = kani::any_where(|side| *side < 100);
+    let shape: Rc<dyn Area> = Rc::new(Square(side));
+    assert_eq!(shape.area(), side as u32 * side as u32);
+}
@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the code that uses the API of `std::thread` compiles with the override of the
+//! thread module, even without `--concurrency`.
+#![allow(dead_code)]
+
+use std::os::unix::thread::JoinHandleExt;
+use std::thread::{Builder, JoinHandle, Thread};
+
+fn spawn_named(name: &str) -> std::io::Result<JoinHandle<u8>> {
+    Builder::new().name(name.to_string()).stack_size(1 << 16).spawn(|| 42)
+}
+
+fn thread_name(handle: &JoinHandle<u8>) -> Option<String> {
+    let thread: &Thread = handle.thread();
+    thread.name().map(str::to_string)
+}
+
+fn raw_handle(handle: JoinHandle<u8>) -> std::os::unix::thread::RawPthread {
+    handle.into_pthread_t()
+}
+
+fn scoped_sum(values: &[u8]) -> u8 {
+    std::thread::scope(|scope| {
+        let handle = Builder::new().spawn_scoped(scope, || values.iter().sum::<u8>()).unwrap();
+        handle.join().unwrap()
+    })
+}
+
+#[kani::proof]
+fn check_builder() {
+    // Spawning a thread of the standard library isn't supported, so the functions above are only
+    // type-checked.
+    let _builder = Builder::new().name("worker".to_string()).stack_size(1 << 16);
+}
@@ -3,9 +3,8 @@
 //
 // kani-flags: -Z stubbing --harness main
 //
-// We currently require a stub and the original/function method to have the same
-// names for generic parameters; instead, we should allow for renaming.
-// See <https://github.com/model-checking/kani/issues/1953> for more information.
+//! Check that a stub may name its generic parameters differently from the original
+//! function/method. See <https://github.com/model-checking/kani/issues/1953>.
 
 fn foo<T>(_x: T) -> bool {
     false
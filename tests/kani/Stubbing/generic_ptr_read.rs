@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! Check that a generic function of the standard library can be stubbed by a generic model, and
+//! that the stub is instantiated for every instantiation of the original function.
+
+static mut READS: usize = 0;
+
+/// Count the reads, so that the harnesses can check that the model replaced every instance of
+/// `core::ptr::read`.
+unsafe fn read_model<U>(src: *const U) -> U {
+    assert!(src.is_aligned(), "read from a misaligned pointer");
+    unsafe {
+        READS += 1;
+        core::ptr::read_unaligned(src)
+    }
+}
+
+#[kani::proof]
+#[kani::stub(core::ptr::read, read_model)]
+fn check_read_instances() {
+    let byte: u8 = kani::any();
+    let word: u32 = kani::any();
+    let pair = (byte, word);
+    unsafe {
+        assert_eq!(core::ptr::read(&byte), byte);
+        assert_eq!(core::ptr::read(&word), word);
+        assert_eq!(core::ptr::read(&pair), pair);
+        assert!(READS == 3);
+    }
+}
+
+#[kani::proof]
+#[kani::stub(core::ptr::read::<T>, read_model::<U>)]
+fn check_read_with_generic_args() {
+    let values: [i16; 2] = kani::any();
+    unsafe {
+        assert_eq!(core::ptr::read(&values[1]), values[1]);
+        assert_eq!(core::ptr::read(&values), values);
+        assert!(READS == 2);
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! This tests replacing a local generic type and all its inherent methods by a model.
+
+/// A cell that keeps track of how many times it was read.
+#[repr(C)]
+pub struct Cell<T> {
+    value: T,
+    reads: u32,
+}
+
+impl<T: Copy> Cell<T> {
+    pub fn new(value: T) -> Self {
+        Cell { value, reads: 0 }
+    }
+
+    pub fn get(&mut self) -> T {
+        self.reads += 1;
+        self.value
+    }
+
+    pub fn reads(&self) -> u32 {
+        self.reads
+    }
+}
+
+mod model {
+    /// A model that pretends the cell was always read many times.
+    #[repr(C)]
+    pub struct Cell<T> {
+        value: T,
+        reads: u32,
+    }
+
+    impl<T: Copy> Cell<T> {
+        pub fn new(value: T) -> Self {
+            Cell { value, reads: 100 }
+        }
+
+        pub fn get(&mut self) -> T {
+            self.value
+        }
+
+        pub fn reads(&self) -> u32 {
+            self.reads
+        }
+    }
+}
+
+#[kani::proof]
+#[kani::stub_type(Cell, model::Cell)]
+fn check_stub_type() {
+    let value: u8 = kani::any();
+    let mut cell = Cell::new(value);
+    assert_eq!(cell.get(), value);
+    assert_eq!(cell.reads(), 100);
+}
+
+#[kani::proof]
+#[kani::stub_type(Cell, model::Cell)]
+#[kani::stub(model::Cell::reads, no_reads)]
+fn check_stub_type_and_method() {
+    let mut cell = Cell::new(kani::any::<u16>());
+    cell.get();
+    assert_eq!(cell.reads(), 0);
+}
+
+fn no_reads<T>(_cell: &model::Cell<T>) -> u32 {
+    0
+}
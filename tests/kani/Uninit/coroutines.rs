@@ -0,0 +1,39 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z uninit-checks -Z async-lib
+
+//! Check that the uninit checks support the layouts of closures and of the state machines of async
+//! blocks, which are pinned and polled through pointers.
+
+use std::pin::pin;
+
+async fn add(a: u32, b: u32) -> u32 {
+    a.wrapping_add(b)
+}
+
+#[kani::proof]
+fn check_closure_by_ref() {
+    let x: u32 = kani::any();
+    let y: u64 = kani::any();
+    let closure = move || (x as u64).wrapping_add(y);
+    let closure_ref = &closure;
+    assert_eq!(closure_ref(), (x as u64).wrapping_add(y));
+}
+
+#[kani::proof]
+fn check_block_on() {
+    let x: u32 = kani::any();
+    let result = kani::block_on(async move {
+        let first = add(x, 1).await;
+        add(first, 1).await
+    });
+    assert_eq!(result, x.wrapping_add(2));
+}
+
+#[kani::proof]
+fn check_pinned_future() {
+    let x: u32 = kani::any();
+    let mut future = pin!(add(x, 2));
+    let result = kani::block_on(future.as_mut());
+    assert_eq!(result, x.wrapping_add(2));
+}
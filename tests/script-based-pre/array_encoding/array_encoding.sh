@@ -0,0 +1,20 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that the array encoding of each harness is reported by `--statistics`, whether the
+# harness picks it with `#[kani::proof(array_encoding = "...")]` or `--array-encoding auto` picks
+# it from the size of its largest array.
+
+set -eu
+
+OUT_DIR=$(mktemp -d)
+trap "rm -rf ${OUT_DIR}" EXIT
+
+kani arrays.rs -Z unstable-options --array-encoding auto --statistics ${OUT_DIR}/stats.json | grep "Complete -"
+python3 - ${OUT_DIR}/stats.json <<'PY'
+import json, sys
+for harness in sorted(json.load(open(sys.argv[1]))["harnesses"], key=lambda h: h["harness"]):
+    large = (harness["largest_array"] or 0) >= 4096
+    print(f'{harness["harness"]}: {harness["array_encoding"]} (large array: {large})')
+PY
@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#[kani::proof]
+fn check_small_array() {
+    let arr: [u8; 8] = kani::any();
+    let idx: usize = kani::any_where(|idx| *idx < arr.len());
+    assert!(arr[idx] <= u8::MAX);
+}
+
+#[kani::proof]
+fn check_large_array() {
+    let mut arr = [0u32; 4096];
+    let idx: usize = kani::any_where(|idx| *idx < arr.len());
+    arr[idx] = 1;
+    assert!(arr[idx] == 1);
+}
+
+fn set(arr: &mut [u32; 4096], idx: usize) {
+    arr[idx] = 1;
+}
+
+#[kani::proof]
+fn check_large_array_ref() {
+    let arr = Box::leak(Box::new([0u32; 4096]));
+    let idx: usize = kani::any_where(|idx| *idx < arr.len());
+    set(arr, idx);
+    assert!(arr[idx] == 1);
+}
+
+#[kani::proof(array_encoding = "field_sensitive")]
+fn check_field_sensitive() {
+    let mut arr = [0u32; 100];
+    arr[3] = kani::any();
+    assert!(arr[4] == 0);
+}
+
+#[kani::proof(array_encoding = "uf")]
+fn check_uf() {
+    let arr: [u8; 16] = kani::any();
+    let idx: usize = kani::any_where(|idx| *idx < arr.len());
+    kani::assume(arr[idx] == 7);
+    assert!(arr.contains(&7));
+}
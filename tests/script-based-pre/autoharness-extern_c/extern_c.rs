@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Test that `--extern-c` generates harnesses for exported `extern "C"` functions,
+//! where each pointer argument is either null or points to a valid allocation.
+
+use std::ffi::c_void;
+
+/// Fails, since the pointer may be null.
+#[unsafe(no_mangle)]
+pub extern "C" fn read_unchecked(ptr: *const u32) -> u32 {
+    unsafe { *ptr }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn read_or_zero(ptr: *const u32) -> u32 {
+    if ptr.is_null() { 0 } else { unsafe { *ptr } }
+}
+
+/// Fails, since the allocation may only have room for one element.
+#[unsafe(no_mangle)]
+pub extern "C" fn read_second(ptr: *const u16) -> u16 {
+    if ptr.is_null() { 0 } else { unsafe { *ptr.add(1) } }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn clear_first_byte(ptr: *mut c_void, len: usize) {
+    if !ptr.is_null() && len > 0 {
+        unsafe { *(ptr as *mut u8) = 0 }
+    }
+}
+
+/// Kani only models raw pointer arguments of exported `extern "C"` functions.
+pub fn not_exported(ptr: *const u8) -> bool {
+    ptr.is_null()
+}
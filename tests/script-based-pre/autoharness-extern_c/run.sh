@@ -0,0 +1,5 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+kani autoharness -Z autoharness extern_c.rs --extern-c --extern-c-buffer-len 4
@@ -0,0 +1,30 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `cargo kani baseline compare` only fails for the properties that fail but didn't fail
+# when the baseline was recorded.
+
+set -e
+
+OUT_DIR=tmp_sample_crate
+
+# Ensure output folder is clean
+rm -rf ${OUT_DIR}
+
+# Move the original source to the output folder since it will be modified
+cp -r sample_crate ${OUT_DIR}
+pushd $OUT_DIR > /dev/null
+
+echo "Record..."
+cargo kani baseline record -Z unstable-options
+grep -c '"FAILURE"' kani-baseline.json | sed 's/^/recorded failures: /'
+
+echo "Compare without changes..."
+cargo kani baseline compare -Z unstable-options && echo "compare succeeded"
+
+echo "Compare with a regression..."
+sed -i.bak 's/kani::assume(y > 0);//' src/lib.rs
+cargo kani baseline compare -Z unstable-options || echo "compare failed"
+
+popd > /dev/null
+rm -rf ${OUT_DIR}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! A crate with a known failure, whose source the test modifies between runs.
+
+pub fn div(x: u8, y: u8) -> u8 {
+    x / y
+}
+
+#[kani::proof]
+fn check_known_failure() {
+    let x: u8 = kani::any();
+    assert!(x < 255, "known failure");
+}
+
+#[kani::proof]
+fn check_div() {
+    let x: u8 = kani::any();
+    let y: u8 = kani::any();
+    kani::assume(y > 0);
+    assert!(div(x, y) <= x);
+}
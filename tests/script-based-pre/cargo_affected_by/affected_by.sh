@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Check that `--affected-by` only verifies the harnesses that reach the lines changed by a git
+# range. The template is copied to its own repository, since the range is resolved by git.
+TMP_DIR=$(mktemp -d)
+cp -r template/. ${TMP_DIR}
+cd ${TMP_DIR}
+git init -q
+git add .
+git -c user.name=kani -c user.email=kani@example.com commit -qm "Initial version"
+
+echo "[TEST] No change"
+cargo kani -Z unstable-options --affected-by HEAD
+
+sed -i 's|x / 2|x >> 1|' src/lib.rs
+
+echo "[TEST] Uncommitted change"
+cargo kani -Z unstable-options --affected-by HEAD
+
+git -c user.name=kani -c user.email=kani@example.com commit -qam "Shift instead of dividing"
+
+echo "[TEST] Committed change"
+cargo kani -Z unstable-options --affected-by HEAD~1..HEAD
+
+cd - > /dev/null
+rm -rf ${TMP_DIR}
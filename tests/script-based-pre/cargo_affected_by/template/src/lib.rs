@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The script changes `halve`, which only `check_halve` and `check_average` reach.
+
+pub fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
+
+pub fn halve(x: u8) -> u8 {
+    x / 2
+}
+
+pub fn average(x: u8, y: u8) -> u8 {
+    halve(x) + halve(y) + (x & y & 1)
+}
+
+#[cfg(kani)]
+mod verification {
+    use super::*;
+
+    #[kani::proof]
+    fn check_double() {
+        let x: u8 = kani::any();
+        assert!(double(x) >= x as u16);
+    }
+
+    #[kani::proof]
+    fn check_halve() {
+        let x: u8 = kani::any();
+        assert!(halve(x) <= x);
+    }
+
+    #[kani::proof]
+    fn check_average() {
+        let x: u8 = kani::any();
+        let y: u8 = kani::any();
+        assert!(average(x, y) >= x.min(y) && average(x, y) <= x.max(y));
+    }
+}
@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Test that the `#[kani::autoharness]` attribute configures the automatic harness of a function
+// instead of the default bounds of the autoharness subcommand.
+
+// The default unwind bound of 20 is not enough to verify this function.
+#[kani::autoharness(unwind = 257)]
+fn sum_up_to(n: u8) -> u32 {
+    let mut sum = 0;
+    for i in 0..n {
+        sum += i as u32;
+    }
+    sum
+}
+
+// The unwinding bound of the attribute is so large that the verification of this function cannot
+// finish before the timeout of the attribute, which replaces the default timeout of 60s.
+#[kani::autoharness(unwind = 4000000000, timeout = "5s")]
+fn check_harness_timeout(mut x: u64) {
+    loop {
+        x = x.wrapping_mul(3).wrapping_add(1);
+    }
+}
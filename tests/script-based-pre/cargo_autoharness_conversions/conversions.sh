@@ -0,0 +1,6 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Only check the conversion laws, not the conversions themselves.
+cargo kani autoharness -Z autoharness --traits --include-pattern 'kani::laws::check_conversion_laws'
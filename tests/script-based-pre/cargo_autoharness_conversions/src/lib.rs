@@ -0,0 +1,77 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Test that the autoharness subcommand checks that pairs of `From` and `TryFrom`
+//! implementations are inverses of each other with the `--traits` option.
+
+/// The conversions to and from `u8` are inverses of each other.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Level {
+    Low,
+    High,
+}
+
+impl From<Level> for u8 {
+    fn from(level: Level) -> u8 {
+        level as u8
+    }
+}
+
+impl TryFrom<u8> for Level {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Level, u8> {
+        match value {
+            0 => Ok(Level::Low),
+            1 => Ok(Level::High),
+            _ => Err(value),
+        }
+    }
+}
+
+/// Converting from `u16` truncates the value instead of failing, so converting it back doesn't
+/// always give the original value.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Flags(u8);
+
+impl From<Flags> for u16 {
+    fn from(flags: Flags) -> u16 {
+        flags.0 as u16
+    }
+}
+
+impl TryFrom<u16> for Flags {
+    type Error = ();
+
+    fn try_from(value: u16) -> Result<Flags, ()> {
+        Ok(Flags(value as u8))
+    }
+}
+
+/// Converting to `i16` uses a different scale than converting from it.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Celsius(i8);
+
+impl From<Celsius> for i16 {
+    fn from(celsius: Celsius) -> i16 {
+        celsius.0 as i16 * 10
+    }
+}
+
+impl TryFrom<i16> for Celsius {
+    type Error = ();
+
+    fn try_from(value: i16) -> Result<Celsius, ()> {
+        i8::try_from(value).map(Celsius).map_err(|_| ())
+    }
+}
+
+/// `String` doesn't implement `TryFrom<Name>`, so there is no conversion law to check.
+#[derive(Clone, PartialEq)]
+pub struct Name(u8);
+
+impl From<Name> for String {
+    fn from(name: Name) -> String {
+        name.0.to_string()
+    }
+}
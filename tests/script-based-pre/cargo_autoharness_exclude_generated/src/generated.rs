@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Stands for a module generated by a build script.
+
+fn generated_fn(x: u8) -> u8 {
+    x
+}
@@ -0,0 +1,22 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Test that the automatic harness generation feature skips functions generated by macros
+// when --exclude-expanded is provided, and functions defined in files that match
+// --exclude-file-pattern.
+
+mod generated;
+
+macro_rules! make_fn {
+    ($name:ident) => {
+        fn $name(x: u8) -> u8 {
+            x
+        }
+    };
+}
+
+make_fn!(from_macro);
+
+fn hand_written(x: u8, _y: u16) -> u8 {
+    x
+}
@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Test that the budget of `--total-timeout` is shared by the packages of the workspace.
+
+pub fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
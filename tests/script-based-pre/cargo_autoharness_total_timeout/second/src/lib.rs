@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+pub fn clamp_sum(values: [u8; 4]) -> u8 {
+    let mut sum: u8 = 0;
+    for value in values {
+        sum = sum.saturating_add(value);
+    }
+    sum
+}
+
+/// Factoring the product of two large primes is too hard for the solver, so the verification of
+/// this function is still running when a small budget runs out.
+pub fn check_not_factors(a: u64, b: u64) {
+    assert!(!(a > 1 && b > 1 && a.wrapping_mul(b) == 18446743979220271189));
+}
@@ -0,0 +1,10 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# A budget of zero seconds runs out before any verification starts.
+cargo kani autoharness -Z autoharness --workspace --total-timeout 0
+
+# The small functions are verified first, and the verification of `check_not_factors` is stopped
+# when the budget runs out, well before the default timeout of the harness.
+cargo kani autoharness -Z autoharness --workspace --total-timeout 20s
@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Test that the autoharness subcommand checks the laws of `Eq`, `Ord` and `Hash` implementations
+//! with the `--traits` option.
+
+use std::cmp::Ordering;
+
+/// The derived implementations satisfy every law.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    major: u8,
+    minor: u8,
+}
+
+/// Values that differ by at most one are equal, which is not transitive,
+/// and the derived `Hash` distinguishes values that are equal.
+#[derive(Hash)]
+pub struct Approx(pub u8);
+
+impl PartialEq for Approx {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.abs_diff(other.0) <= 1
+    }
+}
+
+impl Eq for Approx {}
+
+/// Tasks are ordered by priority only, so tasks with different ids compare as `Equal`.
+#[derive(PartialEq, Eq)]
+pub struct Task {
+    priority: u8,
+    id: u8,
+}
+
+impl PartialOrd for Task {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Task {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
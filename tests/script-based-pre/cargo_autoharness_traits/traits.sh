@@ -0,0 +1,6 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Only check the trait laws, not the methods of the trait implementations themselves.
+cargo kani autoharness -Z autoharness --traits --include-pattern 'kani::laws'
@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! A dependency that verifies the contracts of its functions and publishes them in a manifest.
+
+/// Compute `1 + 2 + ... + n` with a loop.
+///
+/// The precondition is the bound that the contract harness can unwind, since a manifest only
+/// lists contracts that were verified for every input that satisfies them.
+#[kani::requires(n <= 5)]
+#[kani::ensures(|result| *result == n as u64 * (n as u64 + 1) / 2)]
+pub fn triangle(n: u32) -> u64 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < n {
+        i += 1;
+        sum += i as u64;
+    }
+    sum
+}
+
+/// The contract of this function is wrong, so it is not listed in the manifest.
+#[kani::ensures(|result| *result > x)]
+pub fn saturating_double(x: u8) -> u8 {
+    x.saturating_mul(2)
+}
+
+#[cfg(kani)]
+mod verify {
+    use super::*;
+
+    #[kani::proof_for_contract(triangle)]
+    #[kani::unwind(6)]
+    fn check_triangle() {
+        triangle(kani::any());
+    }
+
+    #[kani::proof_for_contract(saturating_double)]
+    fn check_saturating_double() {
+        saturating_double(kani::any());
+    }
+}
@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+rm -f checked_math/kani-contracts.json
+
+echo "[TEST] Verify the dependency and write its contract manifest"
+(cd checked_math && cargo kani -Z function-contracts --emit-contract-manifest)
+cat checked_math/kani-contracts.json
+
+echo "[TEST] Verify the crate with the contracts of the dependency"
+cargo kani -Z function-contracts
+
+echo "[TEST] Verify the crate without the contracts of the dependency"
+cargo kani -Z function-contracts --no-dependency-contracts
+
+rm -f checked_math/kani-contracts.json
+rm -rf target checked_math/target
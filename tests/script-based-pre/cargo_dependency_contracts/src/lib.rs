@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that Kani replaces the calls to functions of a dependency by their contracts when the
+//! dependency publishes a contract manifest.
+
+use checked_math::triangle;
+
+/// The number of handshakes between `n` people.
+pub fn handshakes(n: u32) -> u64 {
+    if n == 0 { 0 } else { triangle(n - 1) }
+}
+
+#[cfg(kani)]
+mod verify {
+    use super::*;
+
+    /// Unwinding the loop of `triangle` would fail, but its contract has no loop.
+    #[kani::proof]
+    #[kani::unwind(3)]
+    fn check_handshakes() {
+        let n: u32 = kani::any_where(|n| *n <= 6);
+        let count = handshakes(n);
+        if n > 0 {
+            assert_eq!(count, n as u64 * (n as u64 - 1) / 2);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Check that `kani doc-proofs` documents the harnesses and contracts of a package, with the status
+# of the harnesses that were verified before.
+
+set -e
+
+rm -rf target results.json kani-proofs.md
+cargo kani -Z unstable-options -Z function-contracts --harness check_double --statistics results.json
+cargo kani doc-proofs -Z unstable-options -Z function-contracts --results results.json --format markdown
+cat kani-proofs.md
+rm -rf target results.json kani-proofs.md
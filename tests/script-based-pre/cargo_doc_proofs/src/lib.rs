@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check the index of the proofs generated by `kani doc-proofs`.
+
+/// Double a small number.
+#[kani::requires(x < 100)]
+#[kani::ensures(|result: &u32| *result == x * 2)]
+pub fn double(x: u32) -> u32 {
+    add(x, x)
+}
+
+fn add(x: u32, y: u32) -> u32 {
+    x + y
+}
+
+#[cfg(kani)]
+mod proofs {
+    use super::*;
+
+    /// `double` satisfies its contract for every input.
+    #[kani::proof_for_contract(double)]
+    fn check_double() {
+        double(kani::any());
+    }
+
+    /// Adding zero is the identity.
+    #[kani::proof]
+    fn check_add_zero() {
+        let x = kani::any();
+        assert_eq!(add(x, 0), x);
+    }
+}
@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Fix a copy of the template, since `cargo kani fix` modifies the sources.
+TMP_DIR=$(mktemp -d)
+cp -r template/. ${TMP_DIR}
+cd ${TMP_DIR}
+
+echo "[TEST] Print the missing derives"
+cargo kani fix --check
+echo "Exit status: $?"
+
+echo "[TEST] Add the missing derives"
+cargo kani fix
+cat src/shapes.rs
+
+echo "[TEST] Verify the fixed crate"
+cargo kani
+
+cd - > /dev/null
+rm -rf ${TMP_DIR}
@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! `cargo kani fix` must derive `kani::Arbitrary` for `shapes::Shape`, and then for `Color`, but
+//! not for `legacy::Shape`, which has the same name.
+
+mod shapes;
+
+pub use shapes::Shape;
+
+#[allow(dead_code)]
+mod legacy {
+    pub struct Shape;
+}
+
+#[cfg(kani)]
+mod verification {
+    use super::*;
+
+    #[kani::proof]
+    fn check_area() {
+        let shape: Shape = kani::any();
+        assert!(shape.area() <= u8::MAX as u16 * u8::MAX as u16);
+    }
+}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#[derive(Clone, Copy, Debug)]
+pub enum Color {
+    Red,
+    Blue,
+}
+
+/// A rectangle of some color.
+pub struct Shape {
+    pub color: Color,
+    pub width: u8,
+    pub height: u8,
+}
+
+impl Shape {
+    pub fn area(&self) -> u16 {
+        self.width as u16 * self.height as u16
+    }
+}
@@ -0,0 +1,11 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+echo "[TEST] Verify the package with its companion"
+cargo kani -p my-crate
+
+echo "[TEST] Verify the workspace without the companion"
+cargo kani --workspace --exclude my-crate-proofs
+
+rm -rf target
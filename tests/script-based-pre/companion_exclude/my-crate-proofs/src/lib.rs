@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Companion of `my-crate`, which `--exclude` can leave out.
+
+#[cfg(kani)]
+mod verify {
+    #[kani::proof]
+    fn check_companion() {
+        let x: u8 = kani::any();
+        assert_eq!(my_crate::double(x) % 2, 0);
+    }
+}
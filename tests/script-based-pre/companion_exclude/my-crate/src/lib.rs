@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Production crate with a harness of its own and a companion.
+
+pub fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
+
+#[cfg(kani)]
+mod verify {
+    #[kani::proof]
+    fn check_crate() {
+        assert!(super::double(kani::any()) <= 510);
+    }
+}
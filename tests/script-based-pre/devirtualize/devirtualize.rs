@@ -0,0 +1,69 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that devirtualization preserves the behavior of dynamic calls, both for the traits that
+//! only one type is coerced to, whose calls are replaced by direct calls, and for the traits with
+//! several concrete types, whose calls are kept.
+
+trait Counter {
+    fn count(&self) -> u32;
+    fn bump(&mut self, by: u32);
+}
+
+struct Simple {
+    value: u32,
+}
+
+impl Counter for Simple {
+    fn count(&self) -> u32 {
+        self.value
+    }
+
+    fn bump(&mut self, by: u32) {
+        self.value = self.value.wrapping_add(by);
+    }
+}
+
+trait Shape {
+    fn area(&self) -> u32;
+}
+
+struct Square(u32);
+struct Rectangle(u32, u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+impl Shape for Rectangle {
+    fn area(&self) -> u32 {
+        self.0 * self.1
+    }
+}
+
+fn bump_twice(counter: &mut dyn Counter, by: u32) -> u32 {
+    counter.bump(by);
+    counter.bump(by);
+    counter.count()
+}
+
+fn area(shape: &dyn Shape) -> u32 {
+    shape.area()
+}
+
+#[kani::proof]
+fn check_single_implementor() {
+    let value: u32 = kani::any_where(|v| *v < 100);
+    let mut counter = Simple { value };
+    let count = bump_twice(&mut counter, 2);
+    assert_eq!(count, value + 4, "count after bumps");
+}
+
+#[kani::proof]
+fn check_multiple_implementors() {
+    let side: u32 = kani::any_where(|v| *v < 10);
+    let shape: &dyn Shape = if kani::any() { &Square(side) } else { &Rectangle(side, 2) };
+    assert!(area(shape) <= side * side || area(shape) == side * 2, "area of the shape");
+}
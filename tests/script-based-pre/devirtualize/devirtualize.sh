@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `-Z devirtualize` preserves the results of the harnesses, and that `--statistics`
+# reports that it replaced the dynamic calls of `bump_twice` by direct calls.
+
+set -eu
+
+OUT_DIR=$(mktemp -d)
+trap "rm -rf ${OUT_DIR}" EXIT
+
+kani devirtualize.rs -Z devirtualize -Z unstable-options --statistics ${OUT_DIR}/stats.json \
+    | grep -E "Checking harness|Description: \"(area|count)|VERIFICATION|Complete -"
+python3 - ${OUT_DIR}/stats.json <<'PY'
+import json, sys
+for harness in sorted(json.load(open(sys.argv[1]))["harnesses"], key=lambda h: h["harness"]):
+    calls = harness["devirtualized_calls"]
+    if harness["harness"] == "check_single_implementor":
+        # `bump_twice` makes three dynamic calls on a `Simple`.
+        print(f'{harness["harness"]}: devirtualized its calls: {calls is not None and calls >= 3}')
+    else:
+        print(f'{harness["harness"]}: reported devirtualized calls: {calls is not None}')
+PY
@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `--emit goto,mir` keeps the artifacts of each harness under predictable names, and
+# that `kani inspect` prints them.
+
+set -e
+
+OUT_DIR=tmp_emit
+rm -rf ${OUT_DIR}
+
+kani -Z unstable-options --emit goto,mir --target-dir ${OUT_DIR} test.rs
+ls ${OUT_DIR}/kani-emit | sort
+
+kani inspect check_double test.rs -Z unstable-options --target-dir ${OUT_DIR}
+
+rm -rf ${OUT_DIR}
@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
+
+#[kani::proof]
+fn check_double() {
+    let x: u8 = kani::any();
+    assert!(double(x) >= x as u16);
+}
+
+mod proofs {
+    #[kani::proof]
+    fn check_even() {
+        let x: u8 = kani::any();
+        assert_eq!(super::double(x) % 2, 0);
+    }
+}
@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that `kani export-fuzz` translates a harness into a proptest test, and that it rejects a
+# harness that uses the Kani API in a way that has no proptest equivalent.
+
+set -e
+
+OUT_DIR=tmp_export_fuzz
+rm -rf ${OUT_DIR}
+
+kani export-fuzz test.rs --harness check_clamp -Z unstable-options --target-dir ${OUT_DIR} -o ${OUT_DIR}/fuzz.rs
+cat ${OUT_DIR}/fuzz.rs
+
+kani export-fuzz test.rs --harness check_pointer -Z unstable-options --target-dir ${OUT_DIR} || true
+
+rm -rf ${OUT_DIR}
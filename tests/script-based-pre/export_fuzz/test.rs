@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+fn clamp(value: i32, low: i32, high: i32) -> i32 {
+    if value < low {
+        low
+    } else if value > high {
+        high
+    } else {
+        value
+    }
+}
+
+#[kani::proof]
+fn check_clamp() {
+    let value: i32 = kani::any();
+    let low: i32 = kani::any();
+    let high: i32 = kani::any_where(|high| *high >= low);
+    kani::assume(low > -100);
+    let result = clamp(value, low, high);
+    kani::cover!(result == low);
+    kani::assert(low <= result && result <= high, "result is within bounds");
+}
+
+#[kani::proof]
+fn check_pointer() {
+    let mut generator = kani::PointerGenerator::<4>::new();
+    let _ptr: *mut u8 = generator.any_in_bounds().ptr;
+}
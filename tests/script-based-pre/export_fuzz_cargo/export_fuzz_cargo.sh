@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that the test that `cargo kani export-fuzz` generates compiles and passes, including for a
+# harness whose inputs shadow each other and whose `kani::any_where` predicate has an untyped
+# closure argument.
+
+set -e
+
+OUT_DIR=tmp_sample_crate
+rm -rf ${OUT_DIR}
+cp -r sample_crate ${OUT_DIR}
+pushd ${OUT_DIR} > /dev/null
+
+cargo kani export-fuzz --harness check_scale -Z unstable-options -o exported.rs
+# The test belongs next to the harness.
+cat exported.rs >> src/lib.rs
+cargo test --lib 2>&1
+
+popd > /dev/null
+rm -rf ${OUT_DIR}
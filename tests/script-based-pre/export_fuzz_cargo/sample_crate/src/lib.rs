@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+pub fn scale(value: u8, factor: u8) -> u16 {
+    value as u16 * factor as u16
+}
+
+#[cfg(kani)]
+#[kani::proof]
+fn check_scale() {
+    let value: u8 = kani::any_where(|value| *value > 10);
+    let factor: u8 = kani::any();
+    assert!(scale(value, factor) >= value as u16 || factor == 0);
+    let factor: u8 = kani::any_where(|factor| *factor != 0);
+    assert!(scale(value, factor) >= value as u16);
+}
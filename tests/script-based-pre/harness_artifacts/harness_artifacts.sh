@@ -0,0 +1,41 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+# Check that every harness has its own artifact directory, that the directories of harnesses that
+# no longer exist are removed, and that `cargo kani clean` only removes Kani's artifacts.
+
+set -e
+
+OUT_DIR=tmp_sample_crate
+
+# Ensure output folder is clean
+rm -rf ${OUT_DIR}
+
+# Move the original source to the output folder since it will be modified
+cp -r sample_crate ${OUT_DIR}
+pushd $OUT_DIR > /dev/null
+
+# The directories that hold the artifacts of each harness.
+function harness_dirs {
+    find target/kani -type d -path "*.kani/*" -prune | sed 's/.*\.kani\///' | sort
+}
+
+echo "Run verification..."
+cargo kani
+harness_dirs | grep -c "check_kept" | sed 's/^/check_kept directories: /'
+harness_dirs | grep -c "check_old" | sed 's/^/check_old directories: /'
+
+echo "Rename check_old to check_new..."
+sed -i.bak 's/check_old/check_new/' src/lib.rs
+cargo kani
+harness_dirs | grep -c "check_new" | sed 's/^/check_new directories: /'
+harness_dirs | grep -c "check_old" | sed 's/^/check_old directories: /'
+
+echo "Clean..."
+touch target/not_kani
+cargo kani clean
+[ -d target/kani ] && echo "target/kani still exists" || echo "target/kani was removed"
+[ -f target/not_kani ] && echo "other artifacts were kept"
+
+popd > /dev/null
+rm -rf ${OUT_DIR}
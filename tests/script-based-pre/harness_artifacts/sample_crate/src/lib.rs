@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! The test script renames `check_old` to check that its artifacts are removed.
+
+#[kani::proof]
+fn check_old() {
+    let x: u8 = kani::any();
+    assert!(x.checked_add(0).is_some());
+}
+
+#[kani::proof]
+fn check_kept() {
+    let x: u8 = kani::any();
+    assert_eq!(x.wrapping_sub(x), 0);
+}
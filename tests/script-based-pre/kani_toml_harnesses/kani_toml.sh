@@ -0,0 +1,14 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Check that the stub sets and the tags of the harnesses in a Kani.toml apply.
+cd project
+
+echo "[TEST] Verify all the harnesses"
+cargo kani
+
+echo "[TEST] Verify the harnesses with the slow tag"
+cargo kani --tag slow
+
+cargo clean
@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! The harnesses of this crate get their stubs and tags from the `Kani.toml`.
+
+pub fn seed() -> u32 {
+    42
+}
+
+#[cfg(kani)]
+mod verify {
+    use super::*;
+
+    fn zero() -> u32 {
+        0
+    }
+
+    /// The `zero_seed` stub set of the `Kani.toml` replaces `seed` by `zero`.
+    #[kani::proof]
+    fn check_stubbed_seed() {
+        assert_eq!(seed(), 0);
+    }
+
+    #[kani::proof]
+    fn check_seed() {
+        assert_eq!(seed(), 42);
+    }
+
+    #[kani::proof]
+    fn check_untagged() {
+        assert_eq!(seed(), 42);
+    }
+}
@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Harness with arguments used to test that the playback of its counterexample calls it with the
+// concrete values of the arguments.
+
+#[kani::proof]
+pub fn harness(x: u8, flag: bool) {
+    if flag {
+        assert!(x != 42, "Found {x}");
+    }
+}
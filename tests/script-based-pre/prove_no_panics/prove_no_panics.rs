@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `--prove-no-panics` verifies every public function, and only checks that it
+//! doesn't panic for the inputs that satisfy its preconditions.
+
+pub fn checked_div(dividend: u32, divisor: u32) -> Option<u32> {
+    dividend.checked_div(divisor)
+}
+
+pub fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+// The postcondition doesn't hold, but `--prove-no-panics` doesn't check it.
+#[kani::requires(divisor != 0)]
+#[kani::ensures(|result: &u32| *result == 0)]
+pub fn contract_div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+// Functions that can't be called from outside the crate are skipped.
+fn private_div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+mod internal {
+    pub fn helper(x: u8) -> u8 {
+        super::private_div(x as u32, 0) as u8
+    }
+}
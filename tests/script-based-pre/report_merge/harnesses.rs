@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#[kani::proof]
+fn check_add() {
+    let x: u8 = kani::any();
+    assert_eq!(x.wrapping_add(0), x);
+}
+
+#[kani::proof]
+fn check_sub() {
+    let x: u8 = kani::any();
+    assert_eq!(x.wrapping_sub(x), 0);
+}
+
+#[kani::proof]
+fn check_mul() {
+    let x: u8 = kani::any();
+    assert_eq!(x.wrapping_mul(1), x);
+}
+
+#[kani::proof]
+fn check_fail() {
+    let x: u8 = kani::any();
+    assert!(x < 255);
+}
@@ -0,0 +1,29 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `kani report merge` combines the results of independent runs that were written to
+# separate directories, reports each harness once, and detects harnesses whose results disagree.
+
+set -eu
+
+OUT_DIR=$(mktemp -d)
+trap "rm -rf ${OUT_DIR}" EXIT
+mkdir ${OUT_DIR}/job1 ${OUT_DIR}/job2
+
+# Both jobs verify `check_add`.
+kani harnesses.rs -Z unstable-options --harness check_add --harness check_sub \
+    --statistics ${OUT_DIR}/job1/stats.json > /dev/null
+kani harnesses.rs -Z unstable-options --harness check_add --harness check_mul \
+    --statistics ${OUT_DIR}/job2/stats.json > /dev/null
+
+kani report merge -Z unstable-options ${OUT_DIR}/job1 ${OUT_DIR}/job2 \
+    --out ${OUT_DIR}/report.json --html ${OUT_DIR}/report.html
+grep -c '"harness": "check_add"' ${OUT_DIR}/report.json
+
+# A third job that disagrees on the status of `check_add`.
+mkdir ${OUT_DIR}/job3
+sed 's/"Success"/"Failure"/' ${OUT_DIR}/job1/stats.json > ${OUT_DIR}/job3/stats.json
+kani report merge -Z unstable-options ${OUT_DIR}/job1 ${OUT_DIR}/job3 \
+    --out ${OUT_DIR}/conflicts.json > ${OUT_DIR}/conflicts.log || echo "report merge failed"
+grep "Conflicting results for - check_add\|conflicting results" ${OUT_DIR}/conflicts.log
@@ -0,0 +1,59 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that the server only answers the clients that send its token, and that it lists and
+# verifies harnesses for them.
+
+set -eu
+
+OUT_DIR=$(mktemp -d)
+trap "rm -rf ${OUT_DIR}" EXIT
+
+kani server -Z unstable-options harnesses.rs > ${OUT_DIR}/server.log 2>&1 &
+SERVER=$!
+
+for _ in $(seq 120); do
+    grep -q "Kani server listening" ${OUT_DIR}/server.log && break
+    sleep 1
+done
+
+python3 - ${OUT_DIR}/server.log <<'PY'
+import json, re, socket, sys
+
+address, token = re.search(r"listening on (\S+) with token (\S+)", open(sys.argv[1]).read()).groups()
+host, port = address.rsplit(":", 1)
+
+def connect():
+    sock = socket.create_connection((host, int(port)))
+    return sock, sock.makefile("r")
+
+def request(conn, id, method, params=None):
+    sock, reader = conn
+    message = {"jsonrpc": "2.0", "id": id, "method": method}
+    if params is not None:
+        message["params"] = params
+    sock.sendall((json.dumps(message) + "\n").encode())
+    line = reader.readline()
+    return json.loads(line) if line else None
+
+# A client without the token is disconnected.
+conn = connect()
+print("listHarnesses without token:", request(conn, 1, "listHarnesses")["error"]["code"])
+print("connection closed:", conn[1].readline() == "")
+
+conn = connect()
+print("initialize with wrong token:", request(conn, 1, "initialize", {"token": "0"})["error"]["code"])
+
+conn = connect()
+print("initialize:", request(conn, 1, "initialize", {"token": token})["result"])
+names = sorted(harness["name"] for harness in request(conn, 2, "listHarnesses")["result"])
+print("harnesses:", ", ".join(names))
+for id, name in enumerate(names, start=3):
+    print(f"{name}:", request(conn, id, "verifyHarness", {"harness": name})["result"]["status"])
+trace = request(conn, 10, "getLastTrace", {"harness": "check_fail"})["result"]
+print("trace of check_fail:", trace["description"])
+request(conn, 11, "shutdown")
+PY
+
+wait ${SERVER}
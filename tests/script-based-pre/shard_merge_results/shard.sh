@@ -0,0 +1,30 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `--shard` splits the harnesses across shards, and that `kani merge-results` merges
+# the results of every shard into one summary. Merging the results again with the merged file
+# keeps a single result per harness.
+
+set -eu
+
+OUT_DIR=$(mktemp -d)
+trap "rm -rf ${OUT_DIR}" EXIT
+
+for shard in 1/3 2/3 3/3; do
+    # The shard that verifies `check_fail` fails.
+    kani harnesses.rs -Z unstable-options --shard ${shard} \
+        --statistics ${OUT_DIR}/stats-${shard%/*}.json > /dev/null || true
+done
+
+kani merge-results -Z unstable-options ${OUT_DIR}/stats-*.json --output ${OUT_DIR}/merged.json \
+    || echo "merge-results failed"
+
+kani merge-results -Z unstable-options ${OUT_DIR}/merged.json ${OUT_DIR}/stats-*.json \
+    -o ${OUT_DIR}/combined.json --html ${OUT_DIR}/summary.html > ${OUT_DIR}/combined.log || true
+grep "Merged Summary\|Discarded" ${OUT_DIR}/combined.log
+grep -o "<td>check_fail</td>" ${OUT_DIR}/summary.html
+
+# The merged results can balance later runs. A single shard verifies every harness.
+kani harnesses.rs -Z unstable-options --shard 1/1 --shard-timings ${OUT_DIR}/merged.json \
+    | grep "Complete -"
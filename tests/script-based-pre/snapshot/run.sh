@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+set +e
+
+TMP_DIR=$(mktemp -d)
+printf '\x02\x01\x02\x01' > ${TMP_DIR}/state.bin
+printf '\x02\x01' > ${TMP_DIR}/short.bin
+printf '\x02\x03\x02\x01' > ${TMP_DIR}/invalid.bin
+
+echo "[TEST] Initialize static with snapshot..."
+kani snapshot.rs -Z unstable-options --snapshot snapshot::STATE=${TMP_DIR}/state.bin
+
+echo "[TEST] Snapshot with wrong size..."
+kani snapshot.rs -Z unstable-options --snapshot snapshot::STATE=${TMP_DIR}/short.bin
+
+echo "[TEST] Snapshot with invalid value..."
+kani snapshot.rs -Z unstable-options --snapshot snapshot::STATE=${TMP_DIR}/invalid.bin
+
+rm -r ${TMP_DIR}
@@ -0,0 +1,46 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that a harness can start from a snapshot of the program state, either by initializing a
+//! static with `--snapshot`, or by building a value with `kani::from_snapshot`.
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct State {
+    mode: u8,
+    armed: bool,
+    ticks: u16,
+}
+
+static STATE: State = State { mode: 0, armed: false, ticks: 0 };
+
+#[kani::proof]
+fn check_static_snapshot() {
+    assert_eq!(STATE.mode, 2);
+    assert!(STATE.armed);
+    assert_eq!(STATE.ticks, u16::from_le_bytes([2, 1]));
+}
+
+#[kani::proof]
+fn check_from_snapshot() {
+    let state: State = kani::from_snapshot(&[2, 1, 2, 1]);
+    assert_eq!(state.mode, 2);
+    assert!(state.armed);
+    assert_eq!(state.ticks, u16::from_le_bytes([2, 1]));
+}
+
+#[kani::proof]
+fn check_from_snapshot_unaligned() {
+    let bytes = [0u8, 2, 1, 2, 1];
+    let state: State = kani::from_snapshot(&bytes[1..]);
+    assert_eq!(state.ticks, u16::from_le_bytes([2, 1]));
+}
+
+#[kani::proof]
+fn check_invalid_bool() {
+    let _state: State = kani::from_snapshot(&[2, 3, 2, 1]);
+}
+
+#[kani::proof]
+fn check_wrong_size() {
+    let _state: State = kani::from_snapshot(&[2, 1]);
+}
@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --verbose
+//
+// Check that a harness can disable the CBMC checks that are performed by default, and that the
+// checks of each harness are printed in verbose mode.
+
+fn read_dead_object() -> u8 {
+    let raw_ptr = {
+        let var = 10u8;
+        &var as *const u8
+    };
+    unsafe { *raw_ptr }
+}
+
+#[kani::proof]
+fn check_with_pointer_check() {
+    read_dead_object();
+}
+
+#[kani::proof(cbmc_checks(disable = "pointer"))]
+fn check_without_pointer_check() {
+    read_dead_object();
+}
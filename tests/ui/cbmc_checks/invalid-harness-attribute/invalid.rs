@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that the `cbmc_checks` option of `#[kani::proof]` rejects unknown checks and checks that
+// are both enabled and disabled.
+
+#[kani::proof(cbmc_checks(disable = "pointer, overflow"))]
+fn check_unknown() {}
+
+#[kani::proof(cbmc_checks(enable = "nan", disable = "nan"))]
+fn check_conflict() {}
@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that a harness can turn categories of checks off with `#[kani::proof(checks(...))]`, and
+// that the other harnesses still perform them.
+
+fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+fn get(array: &[u8; 4], index: usize) -> u8 {
+    array[index]
+}
+
+#[kani::proof]
+fn check_overflow() {
+    add(kani::any(), kani::any());
+}
+
+#[kani::proof(checks(overflow = false))]
+fn check_no_overflow() {
+    add(kani::any(), kani::any());
+}
+
+#[kani::proof]
+fn check_bounds() {
+    get(&[1, 2, 3, 4], kani::any_where(|index| *index < 5));
+}
+
+#[kani::proof(checks(bounds = false))]
+fn check_no_bounds() {
+    // The index can be out of bounds, but the check is off.
+    get(&[1, 2, 3, 4], kani::any_where(|index| *index < 5));
+}
+
+#[kani::proof(checks(unwinding = false))]
+#[kani::unwind(2)]
+fn check_no_unwinding() {
+    let mut count = 0u8;
+    while count < 10 {
+        count += 1;
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Check that the `checks` option of `#[kani::proof]` rejects unknown categories, values other
+// than booleans, and categories that are set more than once.
+
+#[kani::proof(checks(pointer = false))]
+fn check_unknown() {}
+
+#[kani::proof(checks(overflow = "no"))]
+fn check_value() {}
+
+#[kani::proof(checks(bounds = false, bounds = true))]
+fn check_duplicate() {}
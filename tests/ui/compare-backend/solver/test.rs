@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --compare-backend minisat
+
+//! Checks that `--compare-backend` verifies the harnesses a second time with another solver, and
+//! reports that the results are the same.
+
+#[kani::proof]
+fn check_abs() {
+    let x: i32 = kani::any();
+    kani::assume(x > i32::MIN);
+    assert!(x.abs() >= 0);
+    kani::cover!(x == -789);
+}
+
+#[kani::proof]
+#[kani::should_panic]
+fn check_overflow() {
+    let x: u8 = kani::any();
+    let _ = x + 1;
+}
@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that there's a compilation error if the condition of `#[kani::arbitrary(where = ...)]`
+//! accesses something that isn't a field of the struct.
+
+#[kani::arbitrary(where = "self.len <= self.capacity")]
+struct Buffer {
+    len: usize,
+    cap: usize,
+}
+
+#[kani::proof]
+fn dead_harness() {
+    panic!("This shouldn't compile");
+}
@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check the errors for closures that `kani::contract_closure` does not support.
+
+#[kani::proof]
+fn untyped_argument() {
+    let id = kani::contract_closure!(
+        #[kani::ensures(|result : &u32| *result == x)]
+        |x| x
+    );
+    assert_eq!(id(1u32), 1);
+}
+
+#[kani::proof]
+fn async_closure() {
+    let _ = kani::contract_closure!(
+        #[kani::requires(x > 0)]
+        async |x: u32| -> u32 { x }
+    );
+}
+
+#[kani::proof]
+fn invalid_option() {
+    let _ = kani::contract_closure!(
+        proof = check_id,
+        #[kani::requires(x > 0)]
+        |x: u32| -> u32 { x }
+    );
+}
@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a function with more than one `ensures_panics_iff` clause is rejected.
+
+#[kani::ensures_panics_iff(divisor == 0)]
+#[kani::ensures_panics_iff(dividend == 0)]
+fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+#[kani::proof_for_contract(div)]
+fn check_div() {
+    let _ = div(kani::any(), kani::any());
+}
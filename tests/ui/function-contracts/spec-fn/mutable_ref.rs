@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+//! Check that spec functions cannot take mutable references.
+
+#[kani::spec_fn]
+fn is_zero(x: &mut u8) -> bool {
+    *x == 0
+}
+
+#[kani::requires(is_zero(&mut x))]
+fn id(mut x: u8) -> u8 {
+    x
+}
+
+#[kani::proof_for_contract(id)]
+fn check_id() {
+    id(kani::any());
+}
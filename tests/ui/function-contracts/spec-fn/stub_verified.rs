@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+//! Check that calls to a spec function cannot be replaced by its contract.
+
+#[kani::spec_fn]
+#[kani::ensures(|result: &bool| *result == (x == 0))]
+fn is_zero(x: u8) -> bool {
+    x == 0
+}
+
+#[kani::proof]
+#[kani::stub_verified(is_zero)]
+fn check_is_zero() {
+    assert!(is_zero(0));
+}
@@ -8,11 +8,6 @@
 #[kani::proof]
 fn multiple_proof_annotations() {}
 
-#[kani::proof]
-fn proof_with_arg(arg: bool) {
-    assert!(arg);
-}
-
 #[kani::proof]
 fn generic_harness<T: Default>() {
     let _ = T::default();
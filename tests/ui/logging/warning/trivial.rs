@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //
 // kani-flags: --only-codegen
-//! This test is to make sure we are correctly printing warnings from the kani-compiler.
+//! This test is to make sure we report the unsupported features that the kani-compiler found.
 
 pub fn asm() {
     unsafe {
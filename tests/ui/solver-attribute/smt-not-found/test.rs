@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that Kani errors out if the specified SMT solver binary is not found
+
+#[kani::proof]
+#[kani::solver(smt = "non_existing_smt_solver")]
+fn check() {}
@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness harness -Z stubbing
+//
+//! Check that the generic arguments in the path of a stub must match the generic parameters of
+//! the function.
+
+fn orig<T>(x: T) -> T {
+    x
+}
+
+fn model<U>(x: U) -> U {
+    x
+}
+
+#[kani::proof]
+#[kani::stub(orig::<T, U>, model::<U>)]
+fn harness() {
+    assert_eq!(orig(1), 1);
+}
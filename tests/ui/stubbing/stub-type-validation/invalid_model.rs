@@ -0,0 +1,71 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! This tests that we report models that cannot replace the original type.
+
+pub struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    pub fn new() -> Self {
+        Counter { count: 0 }
+    }
+
+    pub fn increment(&mut self) {
+        self.count += 1;
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+}
+
+pub struct Pair<A, B>(A, B);
+
+impl<A, B> Pair<A, B> {
+    pub fn first(&self) -> &A {
+        &self.0
+    }
+}
+
+mod model {
+    pub struct Counter {
+        count: u32,
+    }
+
+    impl Counter {
+        pub fn new() -> Self {
+            Counter { count: 0 }
+        }
+
+        pub fn count(&self) -> u32 {
+            self.count
+        }
+    }
+
+    pub struct Pair<A>(A, A);
+
+    impl<A> Pair<A> {
+        pub fn first(&self) -> &A {
+            &self.0
+        }
+    }
+}
+
+#[kani::proof]
+#[kani::stub_type(Counter, model::Counter)]
+fn missing_method() {
+    let mut counter = Counter::new();
+    counter.increment();
+    assert_eq!(counter.count(), 1);
+}
+
+#[kani::proof]
+#[kani::stub_type(Pair, model::Pair)]
+fn generics_mismatch() {
+    let pair = Pair(1u8, 2u8);
+    assert_eq!(*pair.first(), 1);
+}
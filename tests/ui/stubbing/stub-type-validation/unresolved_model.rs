@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! This tests that both arguments of `kani::stub_type` must resolve to a type.
+
+pub struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+}
+
+mod model {
+    pub fn count() -> u32 {
+        0
+    }
+}
+
+#[kani::proof]
+#[kani::stub_type(Counter, model::Unknown)]
+fn unknown_model() {
+    assert_eq!(Counter { count: 0 }.count(), 0);
+}
+
+#[kani::proof]
+#[kani::stub_type(Counter::count, model::count)]
+fn not_a_type() {
+    assert_eq!(Counter { count: 0 }.count(), 0);
+}
@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that Kani warns about assertions that only check an earlier assumption, but not about
+//! assertions on the result of the code under verification.
+
+fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
+
+#[kani::proof]
+fn suspicious() {
+    let x: u8 = kani::any();
+    kani::assume(x < 100);
+    let y = double(x);
+    assert!(x < 100);
+    let _ = y;
+}
+
+#[kani::proof]
+fn meaningful() {
+    let x: u8 = kani::any();
+    kani::assume(x < 100);
+    assert!(double(x) < 200);
+}
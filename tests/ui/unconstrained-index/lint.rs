@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that Kani suggests `kani::any_usize_index` for symbolic indices that are not
+//! constrained before indexing, but not for indices that were constrained.
+
+#[kani::proof]
+fn unconstrained() {
+    let array: [u8; 4] = kani::any();
+    let idx: usize = kani::any();
+    let _ = array[idx];
+}
+
+#[kani::proof]
+fn constrained() {
+    let array: [u8; 4] = kani::any();
+    let idx: usize = kani::any();
+    kani::assume(idx < array.len());
+    let _ = array[idx];
+}
+
+#[kani::proof]
+fn with_helper() {
+    let array: [u8; 4] = kani::any();
+    let idx = kani::any_usize_index(&array);
+    let _ = array[idx];
+}
@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that Kani reports an error for an unwind value of a label that no loop of the harness
+//! has.
+
+#![allow(unused_labels)]
+
+#[kani::proof]
+#[kani::unwind(outer = 3, missing = 2)]
+fn harness() {
+    let mut counter = 0;
+    'outer: while counter < 2 {
+        counter += 1;
+    }
+    assert!(counter == 2);
+}